@@ -61,25 +61,21 @@ async fn main() -> CommonResult<()> {
                     "".to_string(),
                     AnalyticsAction::EdiFileNew,
                 );
-                launcher::run_app(None).await?;
+                launcher::run_app(vec![]).await?;
             }
             1 => {
                 report_analytics::start_task_to_generate_event(
                     "".to_string(),
                     AnalyticsAction::EdiFileOpenSingle,
                 );
-                launcher::run_app(Some(cli_arg.file_paths[0].clone())).await?;
+                launcher::run_app(cli_arg.file_paths).await?;
             }
             _ => {
-                if let Some(file_path) =
-                    edi_ui_templates::handle_multiple_files_not_supported_yet(cli_arg)
-                {
-                    report_analytics::start_task_to_generate_event(
-                        "".to_string(),
-                        AnalyticsAction::EdiFileOpenMultiple,
-                    );
-                    launcher::run_app(Some(file_path)).await?;
-                }
+                report_analytics::start_task_to_generate_event(
+                    "".to_string(),
+                    AnalyticsAction::EdiFileOpenMultiple,
+                );
+                launcher::run_app(cli_arg.file_paths).await?;
             }
         }
 
@@ -95,37 +91,10 @@ async fn main() -> CommonResult<()> {
 
 pub mod edi_ui_templates {
     use r3bl_ansi_color::{AnsiStyledText, Style};
-    use r3bl_tuify::{select_from_list,
-                     SelectionMode,
-                     StyleSheet,
-                     LIZARD_GREEN,
-                     SLATE_GRAY};
+    use r3bl_tuify::{LIZARD_GREEN, SLATE_GRAY};
 
     use super::*;
 
-    pub fn handle_multiple_files_not_supported_yet(cli_arg: CLIArg) -> Option<String> {
-        // Ask the user to select a file to edit.
-        let maybe_user_choices = select_from_list(
-            "edi currently only allows you to edit one file at a time. Select one:"
-                .to_string(),
-            cli_arg.file_paths.clone(),
-            5,
-            0,
-            SelectionMode::Single,
-            StyleSheet::default(),
-        );
-
-        // Return the single user choice, if there is one.
-        if let Some(user_choices) = maybe_user_choices {
-            if let Some(user_choice) = user_choices.first() {
-                return Some(user_choice.clone());
-            }
-        }
-
-        // Otherwise, return None.
-        None
-    }
-
     pub fn print_exit_message() {
         if upgrade_check::is_update_required() {
             println!("{}", {
@@ -15,7 +15,9 @@
  *   limitations under the License.
  */
 
-use std::fmt::*;
+use std::{fmt::*,
+          sync::Arc,
+          time::{Duration, Instant}};
 
 use crossterm::style::Stylize;
 use r3bl_rs_utils_core::*;
@@ -23,7 +25,20 @@ use r3bl_rs_utils_macro::tui_style;
 use r3bl_tui::*;
 use tokio::sync::mpsc::Sender;
 
-use crate::edi::{file_utils, State};
+use crate::{edi::{file_explorer,
+                  file_explorer_actions,
+                  file_utils,
+                  CommandPalette,
+                  CommandPaletteItem,
+                  FileExplorerComponent,
+                  OpenBuffer,
+                  SearchKeyHints,
+                  State,
+                  StatusMessage,
+                  COMMAND_PALETTE_MAX_FILE_COUNT,
+                  FILE_EXPLORER_WIDTH_PERCENT},
+            report_analytics,
+            AnalyticsAction};
 
 /// Signals that can be sent to the app.
 #[derive(Default, Clone, Debug)]
@@ -31,6 +46,14 @@ use crate::edi::{file_utils, State};
 pub enum AppSignal {
     AskForFilenameToSaveFile,
     SaveFile,
+    AskForSearchQuery,
+    AskForReplacement,
+    CloseBuffer,
+    Exit,
+    /// Open the file (picked in the command palette) & focus the editor.
+    OpenFile(String),
+    /// Run the action (picked in the command palette) w/ this id.
+    RunAction(String),
     #[default]
     Noop,
 }
@@ -50,13 +73,24 @@ pub enum Id {
     // Components.
     ComponentEditor = 1,
     ComponentSimpleDialogAskForFilenameToSaveFile = 2,
+    ComponentSimpleDialogSearch = 3,
+    ComponentSimpleDialogReplace = 4,
+    ComponentSimpleDialogCloseBuffer = 5,
+    ComponentSimpleDialogExit = 6,
+    ComponentSimpleDialogReloadFile = 7,
+    ComponentFileExplorer = 8,
+    ComponentAutocompleteDialogCommandPalette = 9,
+
+    // Containers.
+    ContainerMain = 10,
 
     // Styles.
-    StyleEditorDefault = 10,
-    StyleDialogBorder = 11,
-    StyleDialogTitle = 12,
-    StyleDialogEditor = 13,
-    StyleDialogResultsPanel = 14,
+    StyleEditorDefault = 11,
+    StyleDialogBorder = 12,
+    StyleDialogTitle = 13,
+    StyleDialogEditor = 14,
+    StyleDialogResultsPanel = 15,
+    StyleDialogResultsPanelHighlight = 16,
 }
 
 mod id_impl {
@@ -71,8 +105,39 @@ mod id_impl {
     }
 }
 
+/// Whether the file of the active buffer has been changed by another program is only
+/// checked on the first key press after the user hasn't pressed any keys for this long
+/// (and before saving), so that the file isn't read on every key press while typing.
+const CHANGED_ON_DISK_CHECK_IDLE_DURATION: Duration = Duration::from_secs(2);
+
 /// The main app struct.
-pub struct AppMain;
+pub struct AppMain {
+    /// Turns key presses into [edi_actions]. The ones that aren't bound to any of them
+    /// are routed to the focused component.
+    keymap: Keymap,
+    /// When the last key press was handled, see [CHANGED_ON_DISK_CHECK_IDLE_DURATION].
+    maybe_last_key_press_instant: Option<Instant>,
+}
+
+/// The ids of the [KeymapAction]s that edi handles, see [AppMain::get_keymap_actions].
+pub mod edi_actions {
+    pub const SCOPE: &str = "edi";
+
+    pub const SAVE: &str = "edi.save";
+    pub const FIND: &str = "edi.find";
+    pub const REPLACE: &str = "edi.replace";
+    pub const FEEDBACK: &str = "edi.feedback";
+    pub const TOGGLE_CASE_SENSITIVE: &str = "edi.toggle_case_sensitive";
+    pub const TOGGLE_REGEX: &str = "edi.toggle_regex";
+    pub const REPLACE_CURRENT: &str = "edi.replace_current";
+    pub const NEXT_BUFFER: &str = "edi.next_buffer";
+    pub const PREVIOUS_BUFFER: &str = "edi.previous_buffer";
+    pub const CLOSE_BUFFER: &str = "edi.close_buffer";
+    pub const EXIT: &str = "edi.exit";
+    pub const CONVERT_LINE_ENDINGS: &str = "edi.convert_line_endings";
+    pub const TOGGLE_FILE_EXPLORER: &str = "edi.toggle_file_explorer";
+    pub const COMMAND_PALETTE: &str = "edi.command_palette";
+}
 
 mod app_main_constructor {
     use super::*;
@@ -83,16 +148,118 @@ mod app_main_constructor {
                 let msg = format!("🪙 {}", "construct edi::AppMain");
                 log_debug(msg);
             });
-            Self
+            global_action_registry::register(Self::get_keymap_actions());
+            global_action_registry::register(FileExplorerComponent::get_keymap_actions());
+            Self {
+                keymap: Self::new_keymap(),
+                maybe_last_key_press_instant: None,
+            }
         }
     }
 
     impl AppMain {
         /// Note that this needs to be initialized before it can be used.
         pub fn new_boxed() -> BoxedSafeApp<State, AppSignal> {
-            let it = Self;
+            let it = Self::default();
             Box::new(it)
         }
+
+        pub fn new_keymap() -> Keymap {
+            Keymap::new(edi_actions::SCOPE, &[edi_actions::SCOPE])
+        }
+
+        /// The actions that are registered in the [global_action_registry] for edi, w/
+        /// their default bindings.
+        pub fn get_keymap_actions() -> Vec<KeymapAction> {
+            use edi_actions::*;
+            vec![
+                KeymapAction::new(SAVE, "Save the file", &["Ctrl+S"]),
+                KeymapAction::new(FIND, "Find", &["Ctrl+F"]),
+                KeymapAction::new(REPLACE, "Replace", &["Ctrl+H"]),
+                KeymapAction::new(FEEDBACK, "Open the feedback link", &["Ctrl+K"]),
+                KeymapAction::new(
+                    TOGGLE_CASE_SENSITIVE,
+                    "Toggle case sensitive search (in the find dialog)",
+                    &["Alt+C"],
+                ),
+                KeymapAction::new(
+                    TOGGLE_REGEX,
+                    "Toggle regex search (in the find dialog)",
+                    &["Alt+R"],
+                ),
+                KeymapAction::new(
+                    REPLACE_CURRENT,
+                    "Replace the current match only (in the replace dialog)",
+                    &["Alt+Enter"],
+                ),
+                KeymapAction::new(
+                    NEXT_BUFFER,
+                    "Switch to the next open file",
+                    &["Ctrl+PageDown", "Alt+Right"],
+                ),
+                KeymapAction::new(
+                    PREVIOUS_BUFFER,
+                    "Switch to the previous open file",
+                    &["Ctrl+PageUp", "Alt+Left"],
+                ),
+                KeymapAction::new(
+                    CLOSE_BUFFER,
+                    "Close the file (asks first if it has unsaved changes)",
+                    &["Ctrl+W"],
+                ),
+                KeymapAction::new(
+                    EXIT,
+                    "Exit (asks first if there are unsaved changes)",
+                    &["Ctrl+Q"],
+                ),
+                KeymapAction::new(
+                    CONVERT_LINE_ENDINGS,
+                    "Convert the line endings of the file between LF and CRLF",
+                    &["Alt+L"],
+                ),
+                KeymapAction::new(
+                    TOGGLE_FILE_EXPLORER,
+                    "Show the file explorer (or hide it, if it has the focus)",
+                    &["Ctrl+B"],
+                ),
+                KeymapAction::new(
+                    COMMAND_PALETTE,
+                    "Find a file to open, or an action to run",
+                    &["Ctrl+P"],
+                ),
+            ]
+        }
+
+        /// Key presses go to this app's [Keymap] and to the ones of the component that has
+        /// focus, so a chord in any of them (eg: `Ctrl+K Ctrl+S` in the keymap file) can
+        /// make a binding in another one (eg: `Ctrl+K` for [edi_actions::FEEDBACK])
+        /// unreachable. Returns these conflicts, for each component that can have focus.
+        pub fn get_keymap_conflicts() -> Vec<KeymapConflict> {
+            let mut acc: Vec<KeymapConflict> = vec![];
+            for component_keymaps in [
+                // The completion popup sees the key presses before the editor.
+                vec![EditorCompletion::new_keymap(), EditorEvent::new_keymap()],
+                vec![FileExplorerComponent::new_keymap()],
+            ] {
+                let mut keymaps = vec![Self::new_keymap()];
+                keymaps.extend(component_keymaps);
+                for conflict in Keymap::get_conflicts(&mut keymaps) {
+                    if !acc.contains(&conflict) {
+                        acc.push(conflict);
+                    }
+                }
+            }
+            acc
+        }
+
+        /// Returns the first key sequence that is bound to the action (eg: `Ctrl+S`), to
+        /// show it to the user.
+        pub fn get_key_hint(keymap: &mut Keymap, action_id: &str) -> String {
+            match keymap.get_bindings(action_id).first() {
+                Some(key_sequence) => key_sequence.to_string(),
+                None => "Unbound".to_string(),
+            }
+        }
     }
 }
 
@@ -121,47 +288,200 @@ mod app_main_impl_app_trait {
             component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
             has_focus: &mut HasFocus,
         ) -> CommonResult<EventPropagation> {
-            // Handle Ctrl + s.
-            if input_event.matches_keypress(KeyPress::WithModifiers {
-                key: Key::Character('s'),
-                mask: ModifierKeysMask::new().with_ctrl(),
-            }) {
-                send_signal!(
-                    global_data.main_thread_channel_sender,
-                    TerminalWindowMainThreadSignal::ApplyAction(AppSignal::SaveFile)
-                );
-
-                return Ok(EventPropagation::Consumed);
+            if let InputEvent::Keyboard(_) = input_event {
+                global_data.state.maybe_status_message = None;
+
+                // Before the buffer is changed, make sure that the file hasn't been
+                // changed by another program, unless the user is typing.
+                let is_first_key_press_after_idle = self
+                    .maybe_last_key_press_instant
+                    .is_none_or(|it| it.elapsed() >= CHANGED_ON_DISK_CHECK_IDLE_DURATION);
+                self.maybe_last_key_press_instant = Some(Instant::now());
+                if is_first_key_press_after_idle
+                    && modal_dialog_confirm::activate_if_changed_on_disk(
+                        component_registry_map,
+                        has_focus,
+                        &mut global_data.state,
+                    )
+                {
+                    return Ok(EventPropagation::ConsumedRender);
+                }
             }
 
-            // Handle Ctrl + k.
-            if input_event.matches_keypress(KeyPress::WithModifiers {
-                key: Key::Character('k'),
-                mask: ModifierKeysMask::new().with_ctrl(),
-            }) {
-                let link_url =
-                    "https://github.com/r3bl-org/r3bl-open-core/issues/new/choose";
-                let result_open = open::that(link_url);
-                match result_open {
-                    Ok(_) => {
-                        call_if_true!(DEBUG_TUI_MOD, {
-                            log_debug(
-                                format!("\n📣 Opened feedback link: {link_url:?}")
-                                    .green()
+            let maybe_action_id = match self.keymap.resolve(&input_event) {
+                KeymapResult::Action(action_id) => Some(action_id),
+                KeymapResult::Pending | KeymapResult::Cancelled => {
+                    return Ok(EventPropagation::Consumed);
+                }
+                KeymapResult::NotBound => None,
+            };
+
+            match maybe_action_id.as_deref() {
+                Some(edi_actions::SAVE) => {
+                    // Don't overwrite the changes that another program made.
+                    if modal_dialog_confirm::activate_if_changed_on_disk(
+                        component_registry_map,
+                        has_focus,
+                        &mut global_data.state,
+                    ) {
+                        return Ok(EventPropagation::ConsumedRender);
+                    }
+
+                    send_signal!(
+                        global_data.main_thread_channel_sender,
+                        TerminalWindowMainThreadSignal::ApplyAction(AppSignal::SaveFile)
+                    );
+
+                    return Ok(EventPropagation::Consumed);
+                }
+
+                Some(edi_actions::FIND) => {
+                    send_signal!(
+                        global_data.main_thread_channel_sender,
+                        TerminalWindowMainThreadSignal::ApplyAction(
+                            AppSignal::AskForSearchQuery
+                        )
+                    );
+
+                    return Ok(EventPropagation::Consumed);
+                }
+
+                Some(edi_actions::REPLACE) => {
+                    send_signal!(
+                        global_data.main_thread_channel_sender,
+                        TerminalWindowMainThreadSignal::ApplyAction(
+                            AppSignal::AskForReplacement
+                        )
+                    );
+
+                    return Ok(EventPropagation::Consumed);
+                }
+
+                // Switching or closing the buffer while a dialog is open would change the
+                // buffer that the dialog works on, so these go to the dialog instead.
+                Some(edi_actions::NEXT_BUFFER) if !has_focus.is_modal_set() => {
+                    global_data.state.select_next_buffer();
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+
+                Some(edi_actions::PREVIOUS_BUFFER) if !has_focus.is_modal_set() => {
+                    global_data.state.select_previous_buffer();
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+
+                Some(edi_actions::CLOSE_BUFFER) if !has_focus.is_modal_set() => {
+                    send_signal!(
+                        global_data.main_thread_channel_sender,
+                        TerminalWindowMainThreadSignal::ApplyAction(
+                            AppSignal::CloseBuffer
+                        )
+                    );
+
+                    return Ok(EventPropagation::Consumed);
+                }
+
+                Some(edi_actions::EXIT) if !has_focus.is_modal_set() => {
+                    send_signal!(
+                        global_data.main_thread_channel_sender,
+                        TerminalWindowMainThreadSignal::ApplyAction(AppSignal::Exit)
+                    );
+
+                    return Ok(EventPropagation::Consumed);
+                }
+
+                Some(edi_actions::CONVERT_LINE_ENDINGS) if !has_focus.is_modal_set() => {
+                    let state = &mut global_data.state;
+                    let Some(open_buffer) = state.get_mut_active_buffer() else {
+                        return Ok(EventPropagation::Consumed);
+                    };
+                    let status_message = match open_buffer.try_convert_line_endings() {
+                        Ok(line_ending) => StatusMessage::Info(format!(
+                            "Line endings: {line_ending} (save to apply)"
+                        )),
+                        Err(error) => StatusMessage::from(error),
+                    };
+                    state.maybe_status_message = Some(status_message);
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+
+                Some(edi_actions::TOGGLE_FILE_EXPLORER) if !has_focus.is_modal_set() => {
+                    let file_explorer = &mut global_data.state.file_explorer;
+                    let file_explorer_id = FlexBoxId::from(Id::ComponentFileExplorer);
+                    if !file_explorer.is_visible {
+                        file_explorer.show();
+                        has_focus.set_id(file_explorer_id);
+                    } else if has_focus.does_id_have_focus(file_explorer_id) {
+                        file_explorer.hide();
+                        has_focus.set_id(FlexBoxId::from(Id::ComponentEditor));
+                    } else {
+                        has_focus.set_id(file_explorer_id);
+                    }
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+
+                Some(edi_actions::COMMAND_PALETTE) if !has_focus.is_modal_set() => {
+                    modal_dialog_command_palette::activate(
+                        component_registry_map,
+                        has_focus,
+                        &mut global_data.state,
+                    );
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+
+                Some(edi_actions::FEEDBACK) => {
+                    let link_url =
+                        "https://github.com/r3bl-org/r3bl-open-core/issues/new/choose";
+                    let result_open = open::that(link_url);
+                    match result_open {
+                        Ok(_) => {
+                            call_if_true!(DEBUG_TUI_MOD, {
+                                log_debug(
+                                    format!("\n📣 Opened feedback link: {link_url:?}")
+                                        .green()
+                                        .to_string(),
+                                );
+                            });
+                        }
+                        Err(err) => {
+                            log_error(
+                                format!("\n📣 Error opening feedback link: {err:?}")
+                                    .red()
                                     .to_string(),
                             );
-                        });
+                        }
                     }
-                    Err(err) => {
-                        log_error(
-                            format!("\n📣 Error opening feedback link: {err:?}")
-                                .red()
-                                .to_string(),
-                        );
+
+                    return Ok(EventPropagation::Consumed);
+                }
+
+                // Handle the actions that only apply while the search & replace dialogs
+                // are open.
+                Some(action_id) => {
+                    if let Some(event_propagation) =
+                        modal_dialog_search_and_replace::handle_action(
+                            action_id,
+                            &mut global_data.state,
+                            has_focus,
+                        )
+                    {
+                        return Ok(event_propagation);
                     }
                 }
 
-                return Ok(EventPropagation::Consumed);
+                None => {}
+            }
+
+            // Mouse events go to the component that they are on, if the file explorer
+            // is visible.
+            if let InputEvent::Mouse(mouse_input) = input_event {
+                if let Some(event_propagation) = file_explorer::route_mouse_event(
+                    global_data,
+                    mouse_input,
+                    component_registry_map,
+                    has_focus,
+                )? {
+                    return Ok(event_propagation);
+                }
             }
 
             // If modal not activated, route the input event to the focused component.
@@ -186,64 +506,122 @@ mod app_main_impl_app_trait {
                     // filename, etc).
                     let GlobalData { state, .. } = global_data;
 
-                    let maybe_editor_buffer = state
-                        .editor_buffers
-                        .get_mut(&FlexBoxId::from(Id::ComponentEditor));
-
-                    if let Some(editor_buffer) = maybe_editor_buffer {
-                        let maybe_file_path =
-                            editor_buffer.editor_content.maybe_file_path.clone();
-                        let content: String = editor_buffer.get_as_string_with_newlines();
-
-                        match maybe_file_path {
-                            // Found file path in the editor buffer.
-                            Some(file_path) => {
-                                file_utils::save_content_to_file(file_path, content);
-                            }
-                            // Could not find file path in the editor buffer. This is a
-                            // new buffer. Need to ask user via dialog box.
-                            _ => {
-                                if !editor_buffer.is_empty() {
-                                    send_signal!(
-                                        global_data.main_thread_channel_sender,
-                                        TerminalWindowMainThreadSignal::ApplyAction(
-                                            AppSignal::AskForFilenameToSaveFile
-                                        )
-                                    );
-                                }
-                            }
+                    let Some(open_buffer) = state.get_mut_active_buffer() else {
+                        return Ok(EventPropagation::ConsumedRender);
+                    };
+
+                    // Could not find file path in the editor buffer. This is a new
+                    // buffer. Need to ask user via dialog box.
+                    if open_buffer.get_maybe_file_path().is_none() {
+                        if !open_buffer.editor_buffer.is_empty() {
+                            send_signal!(
+                                global_data.main_thread_channel_sender,
+                                TerminalWindowMainThreadSignal::ApplyAction(
+                                    AppSignal::AskForFilenameToSaveFile
+                                )
+                            );
                         }
+                        return Ok(EventPropagation::ConsumedRender);
                     }
+
+                    report_analytics::start_task_to_generate_event(
+                        "".to_string(),
+                        AnalyticsAction::EdiFileSave,
+                    );
+
+                    let status_message = match open_buffer.try_save() {
+                        Ok(_) => StatusMessage::Info(format!(
+                            "Saved {}",
+                            open_buffer.get_display_name()
+                        )),
+                        Err(error) => StatusMessage::from(error),
+                    };
+                    state.maybe_status_message = Some(status_message);
                 }
                 AppSignal::AskForFilenameToSaveFile => {
                     let GlobalData { state, .. } = global_data;
-
-                    // Reset the dialog component prior to activating / showing it.
-                    ComponentRegistry::reset_component(
+                    modal_dialog_ask_for_filename_to_save_file::activate(
                         component_registry_map,
-                        FlexBoxId::from(
-                            Id::ComponentSimpleDialogAskForFilenameToSaveFile,
-                        ),
+                        has_focus,
+                        state,
                     );
 
-                    if let Err(err) = modal_dialog_ask_for_filename_to_save_file::show(
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+                AppSignal::AskForSearchQuery => {
+                    let GlobalData { state, .. } = global_data;
+                    modal_dialog_search_and_replace::activate(
                         component_registry_map,
                         has_focus,
                         state,
-                    ) {
-                        if let Some(CommonError {
-                            err_type: _,
-                            err_msg: msg,
-                        }) = err.downcast_ref::<CommonError>()
-                        {
-                            log_error(format!(
-                                "📣 Error activating simple modal: {msg:?}"
+                        &mut self.keymap,
+                        FlexBoxId::from(Id::ComponentSimpleDialogSearch),
+                    );
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+                AppSignal::AskForReplacement => {
+                    let GlobalData { state, .. } = global_data;
+                    if let Some(open_buffer) = state.get_active_buffer() {
+                        if open_buffer.editor_buffer.is_read_only() {
+                            state.maybe_status_message = Some(StatusMessage::Error(
+                                open_buffer.get_read_only_message(),
                             ));
+                            return Ok(EventPropagation::ConsumedRender);
                         }
-                    };
+                    }
+                    modal_dialog_search_and_replace::activate(
+                        component_registry_map,
+                        has_focus,
+                        state,
+                        &mut self.keymap,
+                        FlexBoxId::from(Id::ComponentSimpleDialogReplace),
+                    );
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+                AppSignal::CloseBuffer => {
+                    let GlobalData { state, .. } = global_data;
 
+                    if !state.get_active_buffer().is_some_and(OpenBuffer::is_dirty) {
+                        state.close_active_buffer();
+                        return Ok(EventPropagation::ConsumedRender);
+                    }
+
+                    modal_dialog_confirm::activate(
+                        component_registry_map,
+                        has_focus,
+                        state,
+                        FlexBoxId::from(Id::ComponentSimpleDialogCloseBuffer),
+                    );
+                    return Ok(EventPropagation::ConsumedRender);
+                }
+                AppSignal::Exit => {
+                    let GlobalData { state, .. } = global_data;
+
+                    if state.get_dirty_buffer_count() == 0 {
+                        return Ok(EventPropagation::ExitMainEventLoop);
+                    }
+
+                    modal_dialog_confirm::activate(
+                        component_registry_map,
+                        has_focus,
+                        state,
+                        FlexBoxId::from(Id::ComponentSimpleDialogExit),
+                    );
                     return Ok(EventPropagation::ConsumedRender);
                 }
+                AppSignal::OpenFile(file_path) => {
+                    global_data.state.open_file(file_path);
+                    has_focus.set_id(FlexBoxId::from(Id::ComponentEditor));
+                }
+                AppSignal::RunAction(action_id) => {
+                    return modal_dialog_command_palette::run_action(
+                        self,
+                        action_id,
+                        global_data,
+                        component_registry_map,
+                        has_focus,
+                    );
+                }
                 AppSignal::Noop => {}
             }
 
@@ -264,10 +642,10 @@ mod app_main_impl_app_trait {
                     let mut it = surface!(stylesheet: stylesheet::create_stylesheet()?);
 
                     it.surface_start(SurfaceProps {
-                        pos: position!(col_index: 0, row_index: 0),
+                        pos: position!(col_index: 0, row_index: 1), // Top row for buffer bar.
                         size: size!(
                             col_count: window_size.col_count,
-                            row_count: window_size.row_count - 1), // Bottom row for for status bar.
+                            row_count: window_size.row_count - 2), // Bottom row for for status bar.
                     })?;
 
                     perform_layout::ContainerSurfaceRender { _app: self }
@@ -283,8 +661,20 @@ mod app_main_impl_app_trait {
                     it
                 };
 
+                // Render buffer bar.
+                buffer_bar::render_buffer_bar(
+                    &mut surface.render_pipeline,
+                    window_size,
+                    &global_data.state,
+                );
+
                 // Render status bar.
-                status_bar::render_status_bar(&mut surface.render_pipeline, window_size);
+                status_bar::render_status_bar(
+                    &mut surface.render_pipeline,
+                    window_size,
+                    &mut self.keymap,
+                    &global_data.state,
+                );
 
                 // Return RenderOps pipeline (which will actually be painted elsewhere).
                 surface.render_pipeline
@@ -293,177 +683,849 @@ mod app_main_impl_app_trait {
     }
 }
 
-mod modal_dialog_ask_for_filename_to_save_file {
+/// Helpers that are shared by the modal dialogs below. Each dialog is a
+/// [DialogComponent] w/ a single line editor, and its [DialogBuffer] (in
+/// [State::dialog_buffers]) has the same id.
+mod modal_dialog {
     use super::*;
 
+    /// Replace the [DialogBuffer] of the dialog w/ a new one, that has the `title`, and
+    /// the `text` in its editor.
     pub fn initialize(state: &mut State, id: FlexBoxId, title: String, text: String) {
         let new_dialog_buffer = {
             let mut it = DialogBuffer::new_empty();
             it.title = title;
-            let line: String = {
-                if text.is_empty() {
-                    "".to_string()
-                } else {
-                    text.clone()
-                }
-            };
-            it.editor_buffer.set_lines(vec![line]);
+            it.editor_buffer.set_lines(vec![text]);
             it
         };
         state.dialog_buffers.insert(id, new_dialog_buffer);
     }
 
-    pub fn show(
-        _component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+    /// Show the dialog w/ the `title` & `text`, after resetting its component.
+    pub fn activate(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+        id: FlexBoxId,
+        title: String,
+        text: String,
+    ) {
+        // Reset the dialog component prior to activating / showing it.
+        ComponentRegistry::reset_component(component_registry_map, id);
+
+        if let Err(err) = show(has_focus, state, id, title, text) {
+            if let Some(CommonError {
+                err_type: _,
+                err_msg: msg,
+            }) = err.downcast_ref::<CommonError>()
+            {
+                log_error(format!("📣 Error activating modal dialog: {msg:?}"));
+            }
+        };
+    }
+
+    fn show(
         has_focus: &mut HasFocus,
         state: &mut State,
+        id: FlexBoxId,
+        title: String,
+        text: String,
     ) -> CommonResult<()> {
         throws!({
-            // Initialize the dialog buffer with title & text.
-            let title = "File name or path to save content to:";
-            let text = "".to_string();
-
-            // Setting the has_focus to Id::ComponentSimpleDialogAskForFilenameToSaveFile
-            // will cause the dialog to appear on the next render.
-            has_focus.try_set_modal_id(FlexBoxId::from(
-                Id::ComponentSimpleDialogAskForFilenameToSaveFile,
-            ))?;
+            // Setting the has_focus to the dialog's id will cause the dialog to appear on
+            // the next render.
+            has_focus.try_set_modal_id(id)?;
 
             // Change the state so that it will trigger a render. This will show the title
             // & text on the next render.
-            initialize(
-                state,
-                FlexBoxId::from(Id::ComponentSimpleDialogAskForFilenameToSaveFile),
-                title.to_owned(),
-                text,
-            );
+            initialize(state, id, title, text);
 
             call_if_true!(DEBUG_TUI_MOD, {
-                let msg = format!("📣 activate modal simple: {:?}", has_focus);
+                let msg = format!("📣 activate modal dialog: {:?}", has_focus);
                 log_debug(msg);
             });
         });
     }
 
-    /// Insert simple dialog component into registry if it's not already there.
-    pub fn insert_component_into_registry(
+    pub fn get_dialog_text(state: &State, id: FlexBoxId) -> String {
+        state
+            .dialog_buffers
+            .get(&id)
+            .map(|it| it.editor_buffer.get_as_string_with_newlines())
+            .unwrap_or_default()
+    }
+
+    /// Insert a dialog component into the registry. In
+    /// [DialogEngineMode::ModalAutocomplete] it has a results panel. If the `edit_mode`
+    /// is [EditMode::ReadOnly], the text in its editor can't be changed, which is what a
+    /// dialog that only asks a yes / no question needs.
+    pub fn insert_component(
         component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        id: FlexBoxId,
+        mode: DialogEngineMode,
+        edit_mode: EditMode,
+        on_dialog_press_handler: OnDialogPressFn<State, AppSignal>,
+        on_dialog_editor_change_handler: OnDialogEditorChangeFn<State, AppSignal>,
     ) {
         let result_stylesheet = stylesheet::create_stylesheet();
 
         let dialog_options = DialogEngineConfigOptions {
-            mode: DialogEngineMode::ModalSimple,
+            mode,
             maybe_style_border: get_tui_style! { @from_result: result_stylesheet , Id::StyleDialogBorder.into() },
             maybe_style_title: get_tui_style! { @from_result: result_stylesheet , Id::StyleDialogTitle.into() },
             maybe_style_editor: get_tui_style! { @from_result: result_stylesheet , Id::StyleDialogEditor.into() },
             maybe_style_results_panel: get_tui_style! { @from_result: result_stylesheet , Id::StyleDialogResultsPanel.into() },
+            maybe_style_results_panel_highlight: get_tui_style! { @from_result: result_stylesheet , Id::StyleDialogResultsPanelHighlight.into() },
             ..Default::default()
         };
 
         let editor_options = EditorEngineConfig {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
-            edit_mode: EditMode::ReadWrite,
+            edit_mode,
+            soft_wrap: SoftWrapMode::Disable,
+            smart_list: SmartListMode::Disable,
+            auto_indent: AutoIndentMode::Disable,
+            tab_width: DEFAULT_TAB_WIDTH,
+            auto_pair: AutoPairMode::Disable,
         };
 
-        let boxed_dialog_component = {
-            let it = DialogComponent::new_boxed(
-                FlexBoxId::from(Id::ComponentSimpleDialogAskForFilenameToSaveFile),
-                dialog_options,
-                editor_options,
-                on_dialog_press_handler,
-                on_dialog_editor_change_handler,
+        let boxed_dialog_component = DialogComponent::new_boxed(
+            id,
+            dialog_options,
+            editor_options,
+            on_dialog_press_handler,
+            on_dialog_editor_change_handler,
+        );
+
+        ComponentRegistry::put(component_registry_map, id, boxed_dialog_component);
+    }
+}
+
+mod modal_dialog_ask_for_filename_to_save_file {
+    use super::*;
+
+    pub fn activate(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+    ) {
+        modal_dialog::activate(
+            component_registry_map,
+            has_focus,
+            state,
+            FlexBoxId::from(Id::ComponentSimpleDialogAskForFilenameToSaveFile),
+            "File name or path to save content to:".to_string(),
+            "".to_string(),
+        );
+    }
+
+    /// Insert simple dialog component into registry if it's not already there.
+    pub fn insert_component_into_registry(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+    ) {
+        modal_dialog::insert_component(
+            component_registry_map,
+            FlexBoxId::from(Id::ComponentSimpleDialogAskForFilenameToSaveFile),
+            DialogEngineMode::ModalSimple,
+            EditMode::ReadWrite,
+            on_dialog_press_handler,
+            on_dialog_editor_change_handler,
+        );
+
+        call_if_true!(DEBUG_TUI_MOD, {
+            let msg = format!(
+                "🪙 {}",
+                "construct DialogComponent (simple) { on_dialog_press }"
             );
+            log_debug(msg);
+        });
 
-            fn on_dialog_press_handler(
-                dialog_choice: DialogChoice,
-                state: &mut State,
-                main_thread_channel_sender: &mut Sender<
-                    TerminalWindowMainThreadSignal<AppSignal>,
-                >,
-            ) {
-                match dialog_choice {
-                    DialogChoice::Yes(text) => {
-                        modal_dialog_ask_for_filename_to_save_file::initialize(
-                            state,
-                            FlexBoxId::from(
-                                Id::ComponentSimpleDialogAskForFilenameToSaveFile,
-                            ),
-                            "Yes".to_string(),
-                            text.clone(),
-                        );
-
-                        let user_input_file_path = text.trim().to_string();
-                        if !user_input_file_path.is_empty() {
-                            call_if_true!(DEBUG_TUI_MOD, {
-                                let msg = format!("\n💾💾💾 About to save the new buffer with given filename: {user_input_file_path:?}")
-                                    .magenta()
-                                    .to_string();
-                                log_debug(msg);
-                            });
+        fn on_dialog_press_handler(
+            dialog_choice: DialogChoice,
+            state: &mut State,
+            main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+            match dialog_choice {
+                DialogChoice::Yes(text) => {
+                    modal_dialog::initialize(
+                        state,
+                        FlexBoxId::from(
+                            Id::ComponentSimpleDialogAskForFilenameToSaveFile,
+                        ),
+                        "Yes".to_string(),
+                        text.clone(),
+                    );
 
-                            let maybe_editor_buffer = state.get_mut_editor_buffer(
-                                FlexBoxId::from(Id::ComponentEditor),
-                            );
+                    let user_input_file_path = text.trim().to_string();
+                    if !user_input_file_path.is_empty() {
+                        call_if_true!(DEBUG_TUI_MOD, {
+                            let msg = format!("\n💾💾💾 About to save the new buffer with given filename: {user_input_file_path:?}")
+                                .magenta()
+                                .to_string();
+                            log_debug(msg);
+                        });
 
-                            if let Some(editor_buffer) = maybe_editor_buffer {
-                                // Set the file path.
-                                editor_buffer.editor_content.maybe_file_path =
-                                    Some(user_input_file_path.clone());
-
-                                // Set the file extension.
-                                editor_buffer.editor_content.maybe_file_extension =
-                                    Some(file_utils::get_file_extension(&Some(
-                                        user_input_file_path.clone(),
-                                    )));
-
-                                // Fire a signal to save the file.
-                                send_signal!(
-                                    main_thread_channel_sender,
-                                    TerminalWindowMainThreadSignal::ApplyAction(
-                                        AppSignal::SaveFile
-                                    )
-                                );
-                            }
+                        let maybe_editor_buffer = state
+                            .get_mut_editor_buffer(FlexBoxId::from(Id::ComponentEditor));
+
+                        if let Some(editor_buffer) = maybe_editor_buffer {
+                            // Set the file path.
+                            editor_buffer.editor_content.maybe_file_path =
+                                Some(user_input_file_path.clone());
+
+                            // Set the file extension.
+                            editor_buffer.editor_content.maybe_file_extension =
+                                Some(file_utils::get_file_extension(&Some(
+                                    user_input_file_path.clone(),
+                                )));
+
+                            // Fire a signal to save the file.
+                            send_signal!(
+                                main_thread_channel_sender,
+                                TerminalWindowMainThreadSignal::ApplyAction(
+                                    AppSignal::SaveFile
+                                )
+                            );
                         }
                     }
-                    DialogChoice::No => {
-                        modal_dialog_ask_for_filename_to_save_file::initialize(
-                            state,
-                            FlexBoxId::from(
-                                Id::ComponentSimpleDialogAskForFilenameToSaveFile,
-                            ),
-                            "No".to_string(),
-                            "".to_string(),
-                        );
+                }
+                DialogChoice::No => {
+                    modal_dialog::initialize(
+                        state,
+                        FlexBoxId::from(
+                            Id::ComponentSimpleDialogAskForFilenameToSaveFile,
+                        ),
+                        "No".to_string(),
+                        "".to_string(),
+                    );
+                }
+            }
+        }
+
+        fn on_dialog_editor_change_handler(
+            _state: &mut State,
+            _main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+        }
+    }
+}
+
+mod modal_dialog_search_and_replace {
+    use super::*;
+
+    /// Show the search dialog (`id` is [Id::ComponentSimpleDialogSearch]) or the replace
+    /// dialog (`id` is [Id::ComponentSimpleDialogReplace]). Since the replace dialog
+    /// replaces the matches of the current search, the search dialog is shown instead if
+    /// there is no search yet.
+    pub fn activate(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+        keymap: &mut Keymap,
+        id: FlexBoxId,
+    ) {
+        state.search_key_hints = SearchKeyHints {
+            toggle_case_sensitive: AppMain::get_key_hint(
+                keymap,
+                edi_actions::TOGGLE_CASE_SENSITIVE,
+            ),
+            toggle_regex: AppMain::get_key_hint(keymap, edi_actions::TOGGLE_REGEX),
+            replace_current: AppMain::get_key_hint(keymap, edi_actions::REPLACE_CURRENT),
+        };
+
+        let is_search_active = state
+            .get_active_editor_buffer()
+            .map(search::is_active)
+            .unwrap_or(false);
+
+        let id = if is_search_active {
+            id
+        } else {
+            FlexBoxId::from(Id::ComponentSimpleDialogSearch)
+        };
+
+        let title = if id == FlexBoxId::from(Id::ComponentSimpleDialogSearch) {
+            get_search_title(state)
+        } else {
+            get_replace_title(state)
+        };
+        modal_dialog::activate(
+            component_registry_map,
+            has_focus,
+            state,
+            id,
+            title,
+            "".to_string(),
+        );
+    }
+
+    /// Handle the actions that only apply while one of the dialogs is open:
+    /// - Search dialog: [edi_actions::TOGGLE_CASE_SENSITIVE] (`Alt + c` by default) and
+    ///   [edi_actions::TOGGLE_REGEX] (`Alt + r` by default).
+    /// - Replace dialog: [edi_actions::REPLACE_CURRENT] (`Alt + Enter` by default)
+    ///   replaces the current match only, and leaves the dialog open (`Enter` replaces all
+    ///   the matches).
+    pub fn handle_action(
+        action_id: &str,
+        state: &mut State,
+        has_focus: &mut HasFocus,
+    ) -> Option<EventPropagation> {
+        if has_focus.is_modal_id(FlexBoxId::from(Id::ComponentSimpleDialogSearch)) {
+            if action_id == edi_actions::TOGGLE_CASE_SENSITIVE {
+                state.search_options.is_case_sensitive =
+                    !state.search_options.is_case_sensitive;
+                update_search(state);
+                return Some(EventPropagation::ConsumedRender);
+            }
+
+            if action_id == edi_actions::TOGGLE_REGEX {
+                state.search_options.is_regex = !state.search_options.is_regex;
+                update_search(state);
+                return Some(EventPropagation::ConsumedRender);
+            }
+        }
+
+        if has_focus.is_modal_id(FlexBoxId::from(Id::ComponentSimpleDialogReplace))
+            && action_id == edi_actions::REPLACE_CURRENT
+        {
+            let replacement = modal_dialog::get_dialog_text(
+                state,
+                FlexBoxId::from(Id::ComponentSimpleDialogReplace),
+            );
+            if let Some(editor_buffer) =
+                state.get_mut_editor_buffer(FlexBoxId::from(Id::ComponentEditor))
+            {
+                search::replace_current(editor_buffer, &replacement);
+            }
+            return Some(EventPropagation::ConsumedRender);
+        }
+
+        None
+    }
+
+    /// Run the search w/ the text in the search dialog, and show the number of matches
+    /// in its title.
+    fn update_search(state: &mut State) {
+        let query = modal_dialog::get_dialog_text(
+            state,
+            FlexBoxId::from(Id::ComponentSimpleDialogSearch),
+        );
+        let options = state.search_options;
+        if let Some(editor_buffer) =
+            state.get_mut_editor_buffer(FlexBoxId::from(Id::ComponentEditor))
+        {
+            search::set_query(editor_buffer, &query, options);
+        }
+
+        let title = get_search_title(state);
+        if let Some(dialog_buffer) = state
+            .dialog_buffers
+            .get_mut(&FlexBoxId::from(Id::ComponentSimpleDialogSearch))
+        {
+            dialog_buffer.title = title;
+        }
+    }
+
+    fn get_search_title(state: &State) -> String {
+        let checkbox = |it: bool| if it { "[x]" } else { "[ ]" };
+        let status = match state.get_active_editor_buffer() {
+            Some(editor_buffer) if editor_buffer.search.get_maybe_error().is_some() => {
+                " │ Invalid regex".to_string()
+            }
+            Some(editor_buffer) if search::is_active(editor_buffer) => {
+                format!(" │ {} matches", editor_buffer.search.get_matches().len())
+            }
+            _ => "".to_string(),
+        };
+        format!(
+            "Find │ {} Case: {} │ {} Regex: {}{}",
+            checkbox(state.search_options.is_case_sensitive),
+            state.search_key_hints.toggle_case_sensitive,
+            checkbox(state.search_options.is_regex),
+            state.search_key_hints.toggle_regex,
+            status
+        )
+    }
+
+    fn get_replace_title(state: &State) -> String {
+        let query = state
+            .get_active_editor_buffer()
+            .map(|it| it.search.get_query().to_string())
+            .unwrap_or_default();
+        format!(
+            "Replace {query:?} │ All: Enter │ Next: {}",
+            state.search_key_hints.replace_current
+        )
+    }
+
+    /// Insert the search & replace dialog components into registry.
+    pub fn insert_components_into_registry(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+    ) {
+        modal_dialog::insert_component(
+            component_registry_map,
+            FlexBoxId::from(Id::ComponentSimpleDialogSearch),
+            DialogEngineMode::ModalSimple,
+            EditMode::ReadWrite,
+            on_search_dialog_press_handler,
+            on_search_dialog_editor_change_handler,
+        );
+        modal_dialog::insert_component(
+            component_registry_map,
+            FlexBoxId::from(Id::ComponentSimpleDialogReplace),
+            DialogEngineMode::ModalSimple,
+            EditMode::ReadWrite,
+            on_replace_dialog_press_handler,
+            on_replace_dialog_editor_change_handler,
+        );
+
+        call_if_true!(DEBUG_TUI_MOD, {
+            let msg = format!(
+                "🪙 {}",
+                "construct DialogComponent (simple) { search, replace }"
+            );
+            log_debug(msg);
+        });
+
+        fn on_search_dialog_press_handler(
+            dialog_choice: DialogChoice,
+            state: &mut State,
+            _main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+            match dialog_choice {
+                // Keep the matches highlighted, so that F3 & Shift+F3 can be used to move
+                // between them.
+                DialogChoice::Yes(_) => {}
+                DialogChoice::No => {
+                    if let Some(editor_buffer) =
+                        state.get_mut_editor_buffer(FlexBoxId::from(Id::ComponentEditor))
+                    {
+                        search::clear(editor_buffer);
                     }
                 }
             }
+        }
 
-            fn on_dialog_editor_change_handler(
-                _state: &mut State,
-                _main_thread_channel_sender: &mut Sender<
-                    TerminalWindowMainThreadSignal<AppSignal>,
-                >,
-            ) {
+        fn on_search_dialog_editor_change_handler(
+            state: &mut State,
+            _main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+            update_search(state);
+        }
+
+        fn on_replace_dialog_press_handler(
+            dialog_choice: DialogChoice,
+            state: &mut State,
+            _main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+            if let DialogChoice::Yes(replacement) = dialog_choice {
+                if let Some(editor_buffer) =
+                    state.get_mut_editor_buffer(FlexBoxId::from(Id::ComponentEditor))
+                {
+                    search::replace_all(editor_buffer, &replacement);
+                }
             }
+        }
 
-            it
+        fn on_replace_dialog_editor_change_handler(
+            _state: &mut State,
+            _main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+        }
+    }
+}
+
+mod modal_dialog_confirm {
+    use super::*;
+
+    /// Show one of the dialogs that ask the user a yes / no question:
+    /// - [Id::ComponentSimpleDialogCloseBuffer]: close the active buffer, which has
+    ///   unsaved changes?
+    /// - [Id::ComponentSimpleDialogExit]: exit, even though some buffers have unsaved
+    ///   changes?
+    /// - [Id::ComponentSimpleDialogReloadFile]: reload the active buffer, since its file
+    ///   has been changed by another program?
+    ///
+    /// The question is the title, and the (read only) editor shows the keys to answer it.
+    pub fn activate(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+        id: FlexBoxId,
+    ) {
+        let title = get_title(state, id);
+        let keymap = &mut DialogEvent::new_keymap();
+        let text = format!(
+            "Yes: {} │ No: {}",
+            AppMain::get_key_hint(keymap, dialog_actions::ACCEPT),
+            AppMain::get_key_hint(keymap, dialog_actions::CANCEL),
+        );
+        modal_dialog::activate(component_registry_map, has_focus, state, id, title, text);
+    }
+
+    /// Show the [Id::ComponentSimpleDialogReloadFile] dialog if the file of the active
+    /// buffer has been changed by another program (and no other dialog is open). Returns
+    /// true if it is shown.
+    pub fn activate_if_changed_on_disk(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+    ) -> bool {
+        if has_focus.is_modal_set() {
+            return false;
+        }
+        let is_changed_on_disk = state
+            .get_mut_active_buffer()
+            .is_some_and(|it| it.is_changed_on_disk());
+        if is_changed_on_disk {
+            activate(
+                component_registry_map,
+                has_focus,
+                state,
+                FlexBoxId::from(Id::ComponentSimpleDialogReloadFile),
+            );
+        }
+        is_changed_on_disk
+    }
+
+    fn get_title(state: &State, id: FlexBoxId) -> String {
+        let (name, is_dirty) = state
+            .get_active_buffer()
+            .map(|it| (it.get_display_name(), it.is_dirty()))
+            .unwrap_or_default();
+
+        if id == FlexBoxId::from(Id::ComponentSimpleDialogCloseBuffer) {
+            format!("Close {name:?} w/out saving?")
+        } else if id == FlexBoxId::from(Id::ComponentSimpleDialogExit) {
+            match state.get_dirty_buffer_count() {
+                1 => "1 file isn't saved. Exit anyway?".to_string(),
+                count => format!("{count} files aren't saved. Exit anyway?"),
+            }
+        } else if is_dirty {
+            format!("{name:?} changed on disk. Reload it & lose your changes?")
+        } else {
+            format!("{name:?} changed on disk. Reload it?")
+        }
+    }
+
+    /// Insert the yes / no dialog components into registry.
+    pub fn insert_components_into_registry(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+    ) {
+        modal_dialog::insert_component(
+            component_registry_map,
+            FlexBoxId::from(Id::ComponentSimpleDialogCloseBuffer),
+            DialogEngineMode::ModalSimple,
+            EditMode::ReadOnly,
+            on_close_buffer_dialog_press_handler,
+            on_dialog_editor_change_handler,
+        );
+        modal_dialog::insert_component(
+            component_registry_map,
+            FlexBoxId::from(Id::ComponentSimpleDialogExit),
+            DialogEngineMode::ModalSimple,
+            EditMode::ReadOnly,
+            on_exit_dialog_press_handler,
+            on_dialog_editor_change_handler,
+        );
+        modal_dialog::insert_component(
+            component_registry_map,
+            FlexBoxId::from(Id::ComponentSimpleDialogReloadFile),
+            DialogEngineMode::ModalSimple,
+            EditMode::ReadOnly,
+            on_reload_file_dialog_press_handler,
+            on_dialog_editor_change_handler,
+        );
+
+        fn on_close_buffer_dialog_press_handler(
+            dialog_choice: DialogChoice,
+            state: &mut State,
+            _main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+            if let DialogChoice::Yes(_) = dialog_choice {
+                state.close_active_buffer();
+            }
+        }
+
+        fn on_exit_dialog_press_handler(
+            dialog_choice: DialogChoice,
+            _state: &mut State,
+            main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+            if let DialogChoice::Yes(_) = dialog_choice {
+                send_signal!(
+                    main_thread_channel_sender,
+                    TerminalWindowMainThreadSignal::Exit
+                );
+            }
+        }
+
+        fn on_reload_file_dialog_press_handler(
+            dialog_choice: DialogChoice,
+            state: &mut State,
+            _main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+            let Some(open_buffer) = state.get_mut_active_buffer() else {
+                return;
+            };
+            match dialog_choice {
+                DialogChoice::Yes(_) => {
+                    open_buffer.reload_from_disk();
+                    let name = open_buffer.get_display_name();
+                    state.maybe_status_message =
+                        Some(StatusMessage::Info(format!("Reloaded {name}")));
+                }
+                DialogChoice::No => open_buffer.keep_instead_of_changes_on_disk(),
+            }
+        }
+
+        fn on_dialog_editor_change_handler(
+            _state: &mut State,
+            _main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+        }
+    }
+}
+
+mod modal_dialog_command_palette {
+    use super::*;
+
+    /// These only do something while one of the search & replace dialogs is open, and
+    /// the command palette is already open, so they can't be run from it.
+    const ACTIONS_NOT_IN_COMMAND_PALETTE: [&str; 4] = [
+        edi_actions::TOGGLE_CASE_SENSITIVE,
+        edi_actions::TOGGLE_REGEX,
+        edi_actions::REPLACE_CURRENT,
+        edi_actions::COMMAND_PALETTE,
+    ];
+
+    /// Show the command palette, w/ the actions & files (see [CommandPalette]) read
+    /// again, since they may have changed since it was last shown.
+    pub fn activate(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+        state: &mut State,
+    ) {
+        state.command_palette = CommandPalette::new(get_items(state));
+        modal_dialog::activate(
+            component_registry_map,
+            has_focus,
+            state,
+            FlexBoxId::from(Id::ComponentAutocompleteDialogCommandPalette),
+            get_title(),
+            "".to_string(),
+        );
+        update_results(state);
+    }
+
+    /// The actions in the [global_action_registry] that can be run, and the files in the
+    /// folder that edi was started in.
+    fn get_items(state: &State) -> Vec<CommandPaletteItem> {
+        // The actions of edi itself come first, since they are the ones that are most
+        // likely to be looked for.
+        let mut actions = global_action_registry::get_all();
+        actions.sort_by_key(|it| it.get_scope() != edi_actions::SCOPE);
+
+        let mut items: Vec<CommandPaletteItem> = actions
+            .into_iter()
+            .filter(|it| !ACTIONS_NOT_IN_COMMAND_PALETTE.contains(&it.id.as_str()))
+            .filter_map(|action| {
+                let keymap = &mut new_keymap(action.get_scope())?;
+                Some(CommandPaletteItem::Action {
+                    key_hint: AppMain::get_key_hint(keymap, &action.id),
+                    id: action.id,
+                    description: action.description,
+                })
+            })
+            .collect();
+
+        let file_explorer = &state.file_explorer;
+        items.extend(
+            file_explorer
+                .list_files(COMMAND_PALETTE_MAX_FILE_COUNT)
+                .into_iter()
+                .map(|path| CommandPaletteItem::new_file(&file_explorer.root, path)),
+        );
+
+        items
+    }
+
+    /// The [Keymap] that the component which handles the actions in the `scope` starts
+    /// w/, or `None` if they can't be run from the command palette.
+    fn new_keymap(scope: &str) -> Option<Keymap> {
+        match scope {
+            edi_actions::SCOPE => Some(AppMain::new_keymap()),
+            editor_actions::SCOPE => Some(EditorEvent::new_keymap()),
+            file_explorer_actions::SCOPE => Some(FileExplorerComponent::new_keymap()),
+            _ => None,
+        }
+    }
+
+    fn get_title() -> String {
+        let keymap = &mut DialogEvent::new_keymap();
+        format!(
+            "Open a file or run an action │ Go: {} │ Close: {}",
+            AppMain::get_key_hint(keymap, dialog_actions::ACCEPT),
+            AppMain::get_key_hint(keymap, dialog_actions::CANCEL),
+        )
+    }
+
+    /// Show the items that match the text in the dialog, w/ the parts that match
+    /// highlighted.
+    fn update_results(state: &mut State) {
+        let id = FlexBoxId::from(Id::ComponentAutocompleteDialogCommandPalette);
+        let query = modal_dialog::get_dialog_text(state, id);
+        state.command_palette.set_query(&query);
+
+        let results = state.command_palette.get_result_labels();
+        let results_highlights = state.command_palette.get_result_highlights();
+        if let Some(dialog_buffer) = state.dialog_buffers.get_mut(&id) {
+            dialog_buffer.maybe_results = Some(results);
+            dialog_buffer.results_highlights = results_highlights;
+        }
+    }
+
+    /// Run the action by replaying the first key sequence that it is bound to, in the
+    /// component that handles it, so that it does exactly what pressing those keys
+    /// does.
+    pub fn run_action(
+        app: &mut AppMain,
+        action_id: &str,
+        global_data: &mut GlobalData<State, AppSignal>,
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+        has_focus: &mut HasFocus,
+    ) -> CommonResult<EventPropagation> {
+        let Some(action) = global_action_registry::get(action_id) else {
+            return Ok(EventPropagation::ConsumedRender);
+        };
+        let maybe_key_sequence = new_keymap(action.get_scope())
+            .and_then(|mut keymap| keymap.get_bindings(action_id).into_iter().next());
+        let Some(key_sequence) = maybe_key_sequence else {
+            global_data.state.maybe_status_message = Some(StatusMessage::Error(format!(
+                "{:?} isn't bound to any keys",
+                action.description
+            )));
+            return Ok(EventPropagation::ConsumedRender);
         };
 
-        ComponentRegistry::put(
+        match action.get_scope() {
+            editor_actions::SCOPE => {
+                has_focus.set_id(FlexBoxId::from(Id::ComponentEditor));
+            }
+            file_explorer_actions::SCOPE => {
+                let file_explorer = &mut global_data.state.file_explorer;
+                if !file_explorer.is_visible {
+                    file_explorer.show();
+                }
+                has_focus.set_id(FlexBoxId::from(Id::ComponentFileExplorer));
+            }
+            _ => {}
+        }
+
+        let mut event_propagation = EventPropagation::ConsumedRender;
+        for key_press in key_sequence.key_presses {
+            event_propagation = app.app_handle_input_event(
+                InputEvent::Keyboard(key_press),
+                global_data,
+                component_registry_map,
+                has_focus,
+            )?;
+        }
+
+        match event_propagation {
+            EventPropagation::ExitMainEventLoop => Ok(event_propagation),
+            _ => Ok(EventPropagation::ConsumedRender),
+        }
+    }
+
+    /// Insert the command palette dialog component (w/ a results panel) into the
+    /// registry.
+    pub fn insert_component_into_registry(
+        component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+    ) {
+        modal_dialog::insert_component(
             component_registry_map,
-            FlexBoxId::from(Id::ComponentSimpleDialogAskForFilenameToSaveFile),
-            boxed_dialog_component,
+            FlexBoxId::from(Id::ComponentAutocompleteDialogCommandPalette),
+            DialogEngineMode::ModalAutocomplete,
+            EditMode::ReadWrite,
+            on_dialog_press_handler,
+            on_dialog_editor_change_handler,
         );
 
         call_if_true!(DEBUG_TUI_MOD, {
             let msg = format!(
                 "🪙 {}",
-                "construct DialogComponent (simple) { on_dialog_press }"
+                "construct DialogComponent (autocomplete) { command palette }"
             );
             log_debug(msg);
         });
+
+        /// The file is opened, or the action is run, once the dialog is closed (and the
+        /// focus is back on the component that had it before).
+        fn on_dialog_press_handler(
+            dialog_choice: DialogChoice,
+            state: &mut State,
+            main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+            let DialogChoice::Yes(_) = dialog_choice else {
+                return;
+            };
+            // The labels of the results aren't unique, so the result is found by its
+            // index instead.
+            let id = FlexBoxId::from(Id::ComponentAutocompleteDialogCommandPalette);
+            let Some(index) = state
+                .dialog_buffers
+                .get(&id)
+                .and_then(|it| it.maybe_selected_result_index)
+            else {
+                return;
+            };
+            let signal = match state.command_palette.get_result(index) {
+                Some(CommandPaletteItem::File { path, .. }) => {
+                    AppSignal::OpenFile(path.to_string_lossy().to_string())
+                }
+                Some(CommandPaletteItem::Action { id, .. }) => {
+                    AppSignal::RunAction(id.clone())
+                }
+                None => return,
+            };
+            send_signal!(
+                main_thread_channel_sender,
+                TerminalWindowMainThreadSignal::ApplyAction(signal)
+            );
+        }
+
+        fn on_dialog_editor_change_handler(
+            state: &mut State,
+            _main_thread_channel_sender: &mut Sender<
+                TerminalWindowMainThreadSignal<AppSignal>,
+            >,
+        ) {
+            update_results(state);
+        }
     }
 }
 
@@ -483,13 +1545,44 @@ mod perform_layout {
             has_focus: &mut HasFocus,
         ) -> CommonResult<()> {
             throws!({
-                // Layout editor component, and render it.
+                // Layout the file explorer (if it is visible) to the left of the editor
+                // component, and render them.
+                let is_file_explorer_visible = global_data.state.file_explorer.is_visible;
+                let editor_width_percent = if is_file_explorer_visible {
+                    100 - FILE_EXPLORER_WIDTH_PERCENT
+                } else {
+                    100
+                };
+                box_start! (
+                    in:                     surface,
+                    id:                     FlexBoxId::from(Id::ContainerMain),
+                    dir:                    LayoutDirection::Horizontal,
+                    requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                    styles:                 []
+                );
+                if is_file_explorer_visible {
+                    box_start! (
+                        in:                     surface,
+                        id:                     FlexBoxId::from(Id::ComponentFileExplorer),
+                        dir:                    LayoutDirection::Vertical,
+                        requested_size_percent: requested_size_percent!(width: FILE_EXPLORER_WIDTH_PERCENT, height: 100),
+                        styles:                 []
+                    );
+                    render_component_in_current_box!(
+                        in:                 surface,
+                        component_id:       FlexBoxId::from(Id::ComponentFileExplorer),
+                        from:               component_registry_map,
+                        global_data:        global_data,
+                        has_focus:          has_focus
+                    );
+                    box_end!(in: surface);
+                }
                 {
                     box_start! (
                         in:                     surface,
                         id:                     FlexBoxId::from(Id::ComponentEditor),
                         dir:                    LayoutDirection::Vertical,
-                        requested_size_percent: requested_size_percent!(width: 100, height: 100),
+                        requested_size_percent: requested_size_percent!(width: editor_width_percent, height: 100),
                         styles:                 [Id::StyleEditorDefault.into()]
                     );
                     render_component_in_current_box!(
@@ -501,6 +1594,7 @@ mod perform_layout {
                     );
                     box_end!(in: surface);
                 }
+                box_end!(in: surface);
 
                 // Then, render simple modal dialog (if it is active, on top of the editor
                 // component).
@@ -516,6 +1610,28 @@ mod perform_layout {
                       has_focus:          has_focus
                     };
                 }
+
+                // Then, render the search, replace, command palette or one of the yes / no
+                // dialogs (if one of them is active, on top of the editor component).
+                for id in [
+                    Id::ComponentSimpleDialogSearch,
+                    Id::ComponentSimpleDialogReplace,
+                    Id::ComponentSimpleDialogCloseBuffer,
+                    Id::ComponentSimpleDialogExit,
+                    Id::ComponentSimpleDialogReloadFile,
+                    Id::ComponentAutocompleteDialogCommandPalette,
+                ] {
+                    if has_focus.is_modal_id(FlexBoxId::from(id)) {
+                        render_component_in_given_box! {
+                          in:                 surface,
+                          box:                FlexBox::default(), /* This is not used as the modal breaks out of its box. */
+                          component_id:       FlexBoxId::from(id),
+                          from:               component_registry_map,
+                          global_data:        global_data,
+                          has_focus:          has_focus
+                        };
+                    }
+                }
             });
         }
     }
@@ -532,6 +1648,18 @@ mod populate_component_registry {
         modal_dialog_ask_for_filename_to_save_file::insert_component_into_registry(
             component_registry_map,
         );
+        modal_dialog_search_and_replace::insert_components_into_registry(
+            component_registry_map,
+        );
+        modal_dialog_confirm::insert_components_into_registry(component_registry_map);
+        modal_dialog_command_palette::insert_component_into_registry(
+            component_registry_map,
+        );
+        ComponentRegistry::put(
+            component_registry_map,
+            FlexBoxId::from(Id::ComponentFileExplorer),
+            FileExplorerComponent::new_boxed(FlexBoxId::from(Id::ComponentFileExplorer)),
+        );
 
         // Switch focus to the editor component if focus is not set.
         let id = FlexBoxId::from(Id::ComponentEditor);
@@ -563,8 +1691,26 @@ mod populate_component_registry {
                 );
             }
 
-            let config_options = EditorEngineConfig::default();
-            EditorComponent::new_boxed(id, config_options, on_buffer_change)
+            // Prose is easier to read and edit w/ soft wrapping.
+            let config_options = EditorEngineConfig {
+                soft_wrap: SoftWrapMode::Enable,
+                smart_list: SmartListMode::Enable,
+                auto_pair: AutoPairMode::Enable,
+                ..Default::default()
+            };
+            let it = EditorComponent::new(id, config_options, on_buffer_change)
+                .with_completion_providers(vec![
+                    Arc::new(WordCompletionProvider),
+                    Arc::new(FilePathCompletionProvider::default()),
+                ])
+                .with_empty_state_hint(EditorEmptyStateHint::new(
+                    AppMain::new_keymap(),
+                    &[
+                        (edi_actions::SAVE, "Save your work."),
+                        (edi_actions::EXIT, "Exit the app."),
+                    ],
+                ));
+            Box::new(it)
         };
 
         ComponentRegistry::put(component_registry_map, id, boxed_editor_component);
@@ -612,6 +1758,11 @@ mod stylesheet {
                 id: Id::StyleDialogResultsPanel.into()
                 // attrib: [bold]
                 color_fg: TuiColor::Basic(ANSIBasicColor::Blue)
+              },
+              tui_style! {
+                id: Id::StyleDialogResultsPanelHighlight.into()
+                attrib: [bold]
+                color_fg: TuiColor::Basic(ANSIBasicColor::Yellow)
               }
             }
         })
@@ -621,8 +1772,15 @@ mod stylesheet {
 mod status_bar {
     use super::*;
 
-    /// Shows helpful messages at the bottom row of the screen.
-    pub fn render_status_bar(pipeline: &mut RenderPipeline, size: Size) {
+    /// Shows helpful messages at the bottom row of the screen. If there is a
+    /// [State::maybe_status_message] (eg: an error when saving a file), then it is shown
+    /// instead of the shortcuts.
+    pub fn render_status_bar(
+        pipeline: &mut RenderPipeline,
+        size: Size,
+        keymap: &mut Keymap,
+        state: &State,
+    ) {
         let separator_style = tui_style!(
             attrib: [dim]
             color_fg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
@@ -652,17 +1810,56 @@ mod status_bar {
             let mut it = Default::default();
             it += app_text_styled_texts;
             it += tui_styled_text! { @style: separator_style , @text: " │ "};
-            it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Save: Ctrl+S "};
+            match &state.maybe_status_message {
+                Some(StatusMessage::Info(msg)) => {
+                    it += tui_styled_text! { @style: tui_style!(color_fg: TuiColor::Basic(ANSIBasicColor::Green)) , @text: msg};
+                    return render(pipeline, size, it);
+                }
+                Some(StatusMessage::Error(msg)) => {
+                    it += tui_styled_text! { @style: tui_style!(attrib: [bold] color_fg: TuiColor::Basic(ANSIBasicColor::Red)) , @text: msg};
+                    return render(pipeline, size, it);
+                }
+                None => {}
+            }
+            if let Some(open_buffer) = state.get_active_buffer() {
+                if open_buffer.is_dirty() {
+                    it += tui_styled_text! { @style: tui_style!(color_fg: TuiColor::Basic(ANSIBasicColor::Yellow)) , @text: "● Modified"};
+                    it += tui_styled_text! { @style: separator_style , @text: " │ "};
+                }
+                // Only the parts of the file format that aren't the default are shown.
+                let file_format = open_buffer.editor_buffer.get_file_format();
+                if file_format.is_lossy {
+                    it += tui_styled_text! { @style: tui_style!(attrib: [bold] color_fg: TuiColor::Basic(ANSIBasicColor::Red)) , @text: "Read only (not UTF-8)"};
+                    it += tui_styled_text! { @style: separator_style , @text: " │ "};
+                }
+                if file_format.line_ending != LineEnding::Lf {
+                    it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: &file_format.line_ending.to_string()};
+                    it += tui_styled_text! { @style: separator_style , @text: " │ "};
+                }
+                if file_format.has_bom {
+                    it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "BOM"};
+                    it += tui_styled_text! { @style: separator_style , @text: " │ "};
+                }
+            }
+            it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: &format!("Save: {} ", AppMain::get_key_hint(keymap, edi_actions::SAVE))};
             it += tui_styled_text! { @style: tui_style!() , @text: "💾"};
             it += tui_styled_text! { @style: separator_style , @text: " │ "};
-            it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Feedback: Ctrl+K "};
+            it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: &format!("Find: {} ", AppMain::get_key_hint(keymap, edi_actions::FIND))};
+            it += tui_styled_text! { @style: tui_style!() , @text: "🔍"};
+            it += tui_styled_text! { @style: separator_style , @text: " │ "};
+            it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: &format!("Feedback: {} ", AppMain::get_key_hint(keymap, edi_actions::FEEDBACK))};
             it += tui_styled_text! { @style: tui_style!() , @text: "💭"};
             it += tui_styled_text! { @style: separator_style , @text: " │ "};
-            it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: "Exit: Ctrl+Q "};
+            it += tui_styled_text! { @style: tui_style!(attrib: [dim]) , @text: &format!("Exit: {} ", AppMain::get_key_hint(keymap, edi_actions::EXIT))};
             it += tui_styled_text! { @style: tui_style!() , @text: "🖖"};
             it
         };
 
+        render(pipeline, size, styled_texts);
+    }
+
+    /// Center the `styled_texts` in the bottom row.
+    fn render(pipeline: &mut RenderPipeline, size: Size, styled_texts: TuiStyledTexts) {
         let display_width = styled_texts.display_width();
         let col_center: ChUnit = (size.col_count - display_width) / 2;
         let row_bottom: ChUnit = size.row_count - 1;
@@ -674,3 +1871,74 @@ mod status_bar {
         pipeline.push(ZOrder::Normal, render_ops);
     }
 }
+
+mod buffer_bar {
+    use super::*;
+
+    /// Shows a tab for each open buffer at the top row of the screen. The active one is
+    /// highlighted, and the ones w/ unsaved changes are marked w/ a `*`. If they don't all
+    /// fit, then the ones before the active one are left out.
+    pub fn render_buffer_bar(pipeline: &mut RenderPipeline, size: Size, state: &State) {
+        let separator_style = tui_style!(
+            attrib: [dim]
+            color_fg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
+        );
+        let active_style = tui_style!(
+            attrib: [bold, reverse]
+        );
+        let inactive_style = tui_style!(
+            attrib: [dim]
+        );
+
+        let labels: Vec<UnicodeString> = state
+            .open_buffers
+            .iter()
+            .map(|it| {
+                let dirty_marker = if it.is_dirty() { "*" } else { "" };
+                UnicodeString::from(format!(" {}{dirty_marker} ", it.get_display_name()))
+            })
+            .collect();
+        let separator = UnicodeString::from("│");
+        let get_width = |it: &UnicodeString| ch!(@to_usize it.display_width + separator.display_width);
+
+        // Leave out the tabs before the active one until it fits.
+        let max_width = ch!(@to_usize size.col_count);
+        let active_index = state
+            .active_buffer_index
+            .min(labels.len().saturating_sub(1));
+        let mut first_index = 0;
+        while first_index < active_index
+            && labels[first_index..=active_index]
+                .iter()
+                .map(get_width)
+                .sum::<usize>()
+                > max_width
+        {
+            first_index += 1;
+        }
+
+        let mut styled_texts: TuiStyledTexts = Default::default();
+        let mut width = 0;
+        for (index, label) in labels.iter().enumerate().skip(first_index) {
+            width += get_width(label);
+            if width > max_width && index != first_index {
+                break;
+            }
+            let style = if index == active_index {
+                active_style
+            } else {
+                inactive_style
+            };
+            styled_texts += tui_styled_text! { @style: style , @text: &label.string };
+            styled_texts +=
+                tui_styled_text! { @style: separator_style , @text: &separator.string };
+        }
+
+        let mut render_ops = render_ops!();
+        render_ops.push(RenderOp::MoveCursorPositionAbs(
+            position!(col_index: 0, row_index: 0),
+        ));
+        styled_texts.render_into(&mut render_ops);
+        pipeline.push(ZOrder::Normal, render_ops);
+    }
+}
@@ -0,0 +1,355 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{cmp::Reverse,
+          path::{Path, PathBuf}};
+
+use r3bl_rs_utils_core::*;
+
+/// How many of the best matches are shown in the results panel of the command palette.
+pub const COMMAND_PALETTE_MAX_RESULT_COUNT: usize = 100;
+
+/// How many of the files in the folder that edi was started in (and the folders below
+/// it) can be found w/ the command palette, so that it still opens quickly in a huge
+/// folder.
+pub const COMMAND_PALETTE_MAX_FILE_COUNT: usize = 10_000;
+
+/// The actions that can be run, and the files that can be opened, from the command
+/// palette. What the user types is matched against them w/ [fuzzy_match], and the ones
+/// that match are shown in its results panel, best match first.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct CommandPalette {
+    /// The actions come first, so that they are shown before the files until something
+    /// is typed.
+    pub items: Vec<CommandPaletteItem>,
+    /// The indices of the `items` that match what the user typed, best match first, w/
+    /// the indices of the grapheme clusters of their labels that match.
+    pub results: Vec<(usize, Vec<usize>)>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CommandPaletteItem {
+    /// An action from the [r3bl_tui::global_action_registry], and the key sequence that
+    /// it is bound to (eg: `Ctrl+S`).
+    Action {
+        id: String,
+        description: String,
+        key_hint: String,
+    },
+    /// A file, and its path relative to the folder that edi was started in.
+    File {
+        path: PathBuf,
+        relative_path: String,
+    },
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FuzzyMatch {
+    /// The higher, the better the match.
+    pub score: i32,
+    /// The indices of the grapheme clusters of the text that match the query.
+    pub indices: Vec<usize>,
+}
+
+mod command_palette_impl {
+    use super::*;
+
+    impl CommandPalette {
+        /// All the `items` are in the results, until [CommandPalette::set_query] is
+        /// called.
+        pub fn new(items: Vec<CommandPaletteItem>) -> Self {
+            let mut it = Self {
+                items,
+                results: vec![],
+            };
+            it.set_query("");
+            it
+        }
+
+        /// Find the `items` that match the `query`, best match first. The ones that
+        /// match equally well stay in the order that they are in.
+        pub fn set_query(&mut self, query: &str) {
+            let mut results: Vec<(i32, usize, Vec<usize>)> = self
+                .items
+                .iter()
+                .enumerate()
+                .filter_map(|(index, item)| {
+                    let FuzzyMatch { score, indices } =
+                        fuzzy_match(query, item.get_text_to_match())?;
+                    let offset = UnicodeString::from(item.get_label_prefix())
+                        .grapheme_cluster_segment_count;
+                    let indices = indices.into_iter().map(|it| it + offset).collect();
+                    Some((score, index, indices))
+                })
+                .collect();
+            results.sort_by_key(|(score, _, _)| Reverse(*score));
+            results.truncate(COMMAND_PALETTE_MAX_RESULT_COUNT);
+            self.results = results
+                .into_iter()
+                .map(|(_, index, indices)| (index, indices))
+                .collect();
+        }
+
+        pub fn get_result_labels(&self) -> Vec<String> {
+            self.results
+                .iter()
+                .map(|(index, _)| self.items[*index].get_label())
+                .collect()
+        }
+
+        pub fn get_result_highlights(&self) -> Vec<Vec<usize>> {
+            self.results
+                .iter()
+                .map(|(_, indices)| indices.clone())
+                .collect()
+        }
+
+        /// The result at the `index` in the results panel, which is where the one that
+        /// was picked is (since labels aren't unique, eg: two actions w/ the same
+        /// description that aren't bound to any keys).
+        pub fn get_result(&self, index: usize) -> Option<&CommandPaletteItem> {
+            let (item_index, _) = self.results.get(index)?;
+            self.items.get(*item_index)
+        }
+    }
+
+    impl CommandPaletteItem {
+        pub fn new_file(root: &Path, path: PathBuf) -> Self {
+            let relative_path = path
+                .strip_prefix(root)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string();
+            Self::File {
+                path,
+                relative_path,
+            }
+        }
+
+        /// What is shown in the results panel, eg: `⚡ Save the file │ Ctrl+S` or
+        /// `📄 src/main.rs`.
+        pub fn get_label(&self) -> String {
+            let prefix = self.get_label_prefix();
+            match self {
+                Self::Action {
+                    description,
+                    key_hint,
+                    ..
+                } => format!("{prefix}{description} │ {key_hint}"),
+                Self::File { relative_path, .. } => format!("{prefix}{relative_path}"),
+            }
+        }
+
+        fn get_label_prefix(&self) -> &str {
+            match self {
+                Self::Action { .. } => "⚡ ",
+                Self::File { .. } => "📄 ",
+            }
+        }
+
+        /// The part of the label that the query is matched against.
+        fn get_text_to_match(&self) -> &str {
+            match self {
+                Self::Action { description, .. } => description,
+                Self::File { relative_path, .. } => relative_path,
+            }
+        }
+    }
+}
+
+const SCORE_MATCH: i32 = 16;
+const SCORE_CONSECUTIVE_MATCH: i32 = 16;
+const SCORE_WORD_START_MATCH: i32 = 12;
+const MAX_GAP_PENALTY: usize = 8;
+
+/// Returns `None` unless the grapheme clusters of the `query` appear in the `text` in
+/// the same order (ignoring case and the whitespace in the `query`), eg: `edmain` in
+/// `src/edi/main.rs`. Otherwise, the ones that are closest together are picked, and
+/// the more of them that are next to each other or at the start of a word, the higher
+/// the score.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<String> = UnicodeString::from(query)
+        .iter()
+        .filter(|it| !it.string.trim().is_empty())
+        .map(|it| it.string.to_lowercase())
+        .collect();
+    let text = UnicodeString::from(text);
+    let graphemes: Vec<&str> = text.iter().map(|it| it.string.as_str()).collect();
+    let lowercase_graphemes: Vec<String> =
+        graphemes.iter().map(|it| it.to_lowercase()).collect();
+
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            indices: vec![],
+        });
+    }
+
+    // Find where the first match ends, then match backwards from there to find where
+    // the shortest match that ends there starts.
+    let mut query_index = 0;
+    let end_index = lowercase_graphemes.iter().position(|it| {
+        if *it == query[query_index] {
+            query_index += 1;
+        }
+        query_index == query.len()
+    })?;
+
+    let mut indices = vec![];
+    for index in (0..=end_index).rev() {
+        if lowercase_graphemes[index] == query[query.len() - indices.len() - 1] {
+            indices.push(index);
+            if indices.len() == query.len() {
+                break;
+            }
+        }
+    }
+    indices.reverse();
+
+    let mut score = 0;
+    for (match_index, &index) in indices.iter().enumerate() {
+        score += SCORE_MATCH;
+        if is_word_start(&graphemes, index) {
+            score += SCORE_WORD_START_MATCH;
+        }
+        if match_index > 0 {
+            match index - indices[match_index - 1] - 1 {
+                0 => score += SCORE_CONSECUTIVE_MATCH,
+                gap => score -= gap.min(MAX_GAP_PENALTY) as i32,
+            }
+        }
+    }
+
+    Some(FuzzyMatch { score, indices })
+}
+
+/// Whether the grapheme cluster is at the start of the text, after a separator (eg: `/`
+/// or `_`), or is an uppercase letter after a lowercase one (eg: `M` in `AppMain`).
+fn is_word_start(graphemes: &[&str], index: usize) -> bool {
+    let Some(previous) = index.checked_sub(1).map(|it| graphemes[it]) else {
+        return true;
+    };
+    let is_separator = matches!(previous, "/" | "\\" | "_" | "-" | "." | " ");
+    let is_lowercase = |it: &str| it.chars().all(char::is_lowercase);
+    let is_uppercase = |it: &str| it.chars().all(char::is_uppercase);
+    is_separator || (is_lowercase(previous) && is_uppercase(graphemes[index]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_indices(query: &str, text: &str) -> Option<Vec<usize>> {
+        fuzzy_match(query, text).map(|it| it.indices)
+    }
+
+    fn get_score(query: &str, text: &str) -> i32 {
+        fuzzy_match(query, text).unwrap().score
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(get_indices("", "main.rs"), Some(vec![]));
+        assert_eq!(get_indices("main", "src/main.rs"), Some(vec![4, 5, 6, 7]));
+        assert_eq!(get_indices("MAIN", "src/Main.rs"), Some(vec![4, 5, 6, 7]));
+        assert_eq!(get_indices("sm rs", "src/main.rs"), Some(vec![0, 4, 9, 10]));
+        assert_eq!(get_indices("mian", "src/main.rs"), None);
+        assert_eq!(get_indices("main.rs", "main"), None);
+
+        // The matches are as close together as they can be.
+        assert_eq!(get_indices("ab", "a_a_ab"), Some(vec![4, 5]));
+
+        // Matches that are next to each other, or at the start of a word, score higher.
+        assert!(get_score("main", "src/main.rs") > get_score("main", "my_app/install"));
+        assert!(get_score("am", "AppMain") > get_score("am", "example"));
+        assert!(get_score("sf", "Save the file") > get_score("sf", "less fun"));
+    }
+
+    #[test]
+    fn test_command_palette() {
+        let root = PathBuf::from("/repo");
+        let mut command_palette = CommandPalette::new(vec![
+            CommandPaletteItem::Action {
+                id: "edi.save".to_string(),
+                description: "Save the file".to_string(),
+                key_hint: "Ctrl+S".to_string(),
+            },
+            CommandPaletteItem::new_file(&root, root.join("src/edi/mod.rs")),
+            CommandPaletteItem::new_file(&root, root.join("src/main.rs")),
+        ]);
+
+        // Everything matches until something is typed.
+        assert_eq!(
+            command_palette.get_result_labels(),
+            vec![
+                "⚡ Save the file │ Ctrl+S",
+                "📄 src/edi/mod.rs",
+                "📄 src/main.rs"
+            ]
+        );
+
+        // The best match comes first, and the highlights skip the icon.
+        command_palette.set_query("main");
+        assert_eq!(command_palette.get_result_labels(), vec!["📄 src/main.rs"]);
+        assert_eq!(
+            command_palette.get_result_highlights(),
+            vec![vec![6, 7, 8, 9]]
+        );
+
+        command_palette.set_query("s");
+        assert_eq!(
+            command_palette.get_result_labels(),
+            vec![
+                "⚡ Save the file │ Ctrl+S",
+                "📄 src/edi/mod.rs",
+                "📄 src/main.rs"
+            ]
+        );
+        command_palette.set_query("sfile");
+        assert_eq!(
+            command_palette.get_result(0),
+            Some(&command_palette.items[0])
+        );
+        assert_eq!(command_palette.get_result(1), None);
+    }
+
+    #[test]
+    fn test_command_palette_duplicate_labels() {
+        let new_action = |id: &str| CommandPaletteItem::Action {
+            id: id.to_string(),
+            description: "Do it".to_string(),
+            key_hint: "Unbound".to_string(),
+        };
+        let mut command_palette =
+            CommandPalette::new(vec![new_action("edi.first"), new_action("edi.second")]);
+        command_palette.set_query("do");
+        assert_eq!(
+            command_palette.get_result_labels(),
+            vec!["⚡ Do it │ Unbound", "⚡ Do it │ Unbound"]
+        );
+
+        // The results have the same label, but each index picks its own action.
+        assert_eq!(
+            command_palette.get_result(0),
+            Some(&new_action("edi.first"))
+        );
+        assert_eq!(
+            command_palette.get_result(1),
+            Some(&new_action("edi.second"))
+        );
+    }
+}
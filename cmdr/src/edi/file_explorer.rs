@@ -0,0 +1,1159 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::VecDeque,
+          ffi::OsStr,
+          fs,
+          path::{Path, PathBuf}};
+
+use glob::{MatchOptions, Pattern};
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::tui_style;
+use r3bl_tui::*;
+
+use crate::edi::{AppSignal, Id, State};
+
+/// Percent of the width of the window that the file explorer panel takes up.
+pub const FILE_EXPLORER_WIDTH_PERCENT: ChUnitPrimitiveType = 25;
+
+/// The tree of files in a folder (the one that edi was started in), which is shown in
+/// the file explorer panel next to the editor. See [FileExplorerComponent].
+///
+/// Folders are only read when they are expanded, so that a large tree doesn't have to
+/// be read up front. The files that are ignored by git (see [GitIgnore]) aren't shown.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FileExplorer {
+    pub root: PathBuf,
+    pub is_visible: bool,
+    /// The rows of the panel: the entries of the root folder, w/ the entries of each
+    /// expanded folder right after it.
+    pub entries: Vec<FileExplorerEntry>,
+    pub selected_index: usize,
+    pub gitignore: GitIgnore,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FileExplorerEntry {
+    pub path: PathBuf,
+    pub name: String,
+    /// How many folders deep it is, `0` for the entries of the root folder.
+    pub depth: usize,
+    /// Symlinks to folders are shown as folders.
+    pub is_dir: bool,
+    pub is_expanded: bool,
+}
+
+/// The patterns in `.gitignore` files, which hide the files that they match from the
+/// [FileExplorer]. Like git:
+/// - `*`, `?` and `[...]` wildcards, which don't match a `/`.
+/// - `**/` at the start (eg: `**/logs`) matches in any folder, `/**` at the end (eg:
+///   `logs/**`) matches everything inside a folder, and `/**/` matches zero or more
+///   folders.
+/// - `!` to negate a pattern, so that the files that it matches are shown again. A file
+///   can't be shown again if a folder that it is in is ignored.
+/// - A trailing `/` to only match folders.
+/// - A `/` anywhere else to match paths relative to the folder of the `.gitignore` file,
+///   instead of names in any folder below it.
+/// - The patterns of a `.gitignore` file only apply to the folder that it is in (and the
+///   ones below it), and take precedence over the patterns of the folders above it.
+///
+/// The `.git` folder is always hidden.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct GitIgnore {
+    rules: Vec<GitIgnoreRule>,
+    /// The folders whose `.gitignore` file (if any) has been read.
+    folders: Vec<PathBuf>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct GitIgnoreRule {
+    folder: PathBuf,
+    pattern: Pattern,
+    is_negated: bool,
+    is_dir_only: bool,
+    is_anchored: bool,
+}
+
+mod file_explorer_impl {
+    use super::*;
+
+    impl FileExplorer {
+        /// Nothing is read until the panel is shown (see [FileExplorer::show]).
+        pub fn new(root: PathBuf) -> Self {
+            Self {
+                root,
+                is_visible: false,
+                entries: vec![],
+                selected_index: 0,
+                gitignore: GitIgnore::default(),
+            }
+        }
+
+        /// Show the panel, w/ its entries read again (see [FileExplorer::refresh]).
+        pub fn show(&mut self) {
+            self.is_visible = true;
+            self.refresh();
+        }
+
+        pub fn hide(&mut self) { self.is_visible = false; }
+
+        /// Read the entries again, so that the files that have been created or deleted
+        /// since they were read show up. The expanded folders stay expanded, and the
+        /// selected entry stays selected (if it still exists).
+        pub fn refresh(&mut self) {
+            let expanded_paths: Vec<PathBuf> = self
+                .entries
+                .iter()
+                .filter(|it| it.is_expanded)
+                .map(|it| it.path.clone())
+                .collect();
+            let maybe_selected_path = self.get_selected().map(|it| it.path.clone());
+
+            self.gitignore = GitIgnore::new(&self.root);
+            self.entries = read_folder(&mut self.gitignore, &self.root, 0);
+            self.selected_index = 0;
+
+            // Folders come before the ones in them, so they are expanded first.
+            for path in expanded_paths {
+                if let Some(index) = self.find(&path) {
+                    self.expand(index);
+                }
+            }
+
+            if let Some(index) = maybe_selected_path.and_then(|it| self.find(&it)) {
+                self.selected_index = index;
+            }
+        }
+
+        pub fn get_selected(&self) -> Option<&FileExplorerEntry> {
+            self.entries.get(self.selected_index)
+        }
+
+        pub fn select(&mut self, index: usize) {
+            if index < self.entries.len() {
+                self.selected_index = index;
+            }
+        }
+
+        pub fn select_next(&mut self, count: usize) {
+            self.selected_index =
+                (self.selected_index + count).min(self.entries.len().saturating_sub(1));
+        }
+
+        pub fn select_previous(&mut self, count: usize) {
+            self.selected_index = self.selected_index.saturating_sub(count);
+        }
+
+        pub fn select_first(&mut self) { self.selected_index = 0; }
+
+        pub fn select_last(&mut self) {
+            self.selected_index = self.entries.len().saturating_sub(1);
+        }
+
+        /// Expand the selected folder, or select the first entry in it if it is already
+        /// expanded.
+        pub fn expand_selected(&mut self) {
+            let Some(entry) = self.get_selected() else {
+                return;
+            };
+            match (entry.is_dir, entry.is_expanded) {
+                (true, false) => self.expand(self.selected_index),
+                (true, true) => {
+                    let depth = entry.depth;
+                    if self
+                        .entries
+                        .get(self.selected_index + 1)
+                        .is_some_and(|it| it.depth > depth)
+                    {
+                        self.selected_index += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        /// Collapse the selected folder, or select the folder that the selected entry is
+        /// in if it isn't an expanded folder.
+        pub fn collapse_selected(&mut self) {
+            let Some(entry) = self.get_selected() else {
+                return;
+            };
+            if entry.is_expanded {
+                self.collapse(self.selected_index);
+                return;
+            }
+            let depth = entry.depth;
+            if let Some(index) = self.entries[..self.selected_index]
+                .iter()
+                .rposition(|it| it.depth < depth)
+            {
+                self.selected_index = index;
+            }
+        }
+
+        /// Expand or collapse the selected folder. If a file is selected, then its path
+        /// is returned, so that it can be opened.
+        pub fn toggle_selected(&mut self) -> Option<PathBuf> {
+            let entry = self.get_selected()?;
+            match (entry.is_dir, entry.is_expanded) {
+                (false, _) => return Some(entry.path.clone()),
+                (true, false) => self.expand(self.selected_index),
+                (true, true) => self.collapse(self.selected_index),
+            }
+            None
+        }
+
+        /// Returns the files in the root folder, and in all the folders below it, that
+        /// aren't ignored (up to `max_count` of them). The ones in the folders that are
+        /// closer to the root come first. Unlike the entries, these are read all at once.
+        pub fn list_files(&self, max_count: usize) -> Vec<PathBuf> {
+            let mut gitignore = GitIgnore::new(&self.root);
+            let mut files = vec![];
+            let mut folders = VecDeque::from([self.root.clone()]);
+            while let Some(folder) = folders.pop_front() {
+                for entry in read_folder(&mut gitignore, &folder, 0) {
+                    if entry.is_dir {
+                        // Symlinks to folders aren't followed, since they can loop.
+                        if !entry.path.is_symlink() {
+                            folders.push_back(entry.path);
+                        }
+                        continue;
+                    }
+                    files.push(entry.path);
+                    if files.len() == max_count {
+                        return files;
+                    }
+                }
+            }
+            files
+        }
+
+        fn find(&self, path: &Path) -> Option<usize> {
+            self.entries.iter().position(|it| it.path == path)
+        }
+
+        fn expand(&mut self, index: usize) {
+            let FileExplorerEntry { path, depth, .. } = self.entries[index].clone();
+            let children = read_folder(&mut self.gitignore, &path, depth + 1);
+            let child_count = children.len();
+            self.entries.splice(index + 1..index + 1, children);
+            self.entries[index].is_expanded = true;
+            if self.selected_index > index {
+                self.selected_index += child_count;
+            }
+        }
+
+        fn collapse(&mut self, index: usize) {
+            let depth = self.entries[index].depth;
+            let end_index = self.entries[index + 1..]
+                .iter()
+                .position(|it| it.depth <= depth)
+                .map_or(self.entries.len(), |it| index + 1 + it);
+            self.entries.drain(index + 1..end_index);
+            self.entries[index].is_expanded = false;
+            if self.selected_index >= end_index {
+                self.selected_index -= end_index - index - 1;
+            } else if self.selected_index > index {
+                self.selected_index = index;
+            }
+        }
+    }
+
+    /// Returns the entries in the folder that aren't ignored: folders first, then files,
+    /// each sorted by name.
+    fn read_folder(
+        gitignore: &mut GitIgnore,
+        folder: &Path,
+        depth: usize,
+    ) -> Vec<FileExplorerEntry> {
+        gitignore.add_gitignore_file(folder);
+        let Ok(read_dir) = fs::read_dir(folder) else {
+            return vec![];
+        };
+
+        let mut entries: Vec<FileExplorerEntry> = read_dir
+            .filter_map(|it| it.ok())
+            .filter_map(|dir_entry| {
+                let path = dir_entry.path();
+                let is_dir = path.is_dir();
+                if gitignore.is_ignored(&path, is_dir) {
+                    return None;
+                }
+                Some(FileExplorerEntry {
+                    name: dir_entry.file_name().to_string_lossy().to_string(),
+                    path,
+                    depth,
+                    is_dir,
+                    is_expanded: false,
+                })
+            })
+            .collect();
+
+        entries.sort_by_cached_key(|it| (!it.is_dir, it.name.to_lowercase()));
+        entries
+    }
+}
+
+mod gitignore_impl {
+    use super::*;
+
+    impl GitIgnore {
+        /// Starts w/ the `.gitignore` files in the folders above `root` (up to the root
+        /// of the git repo that it is in, if any), since they apply to it too.
+        pub fn new(root: &Path) -> Self {
+            let mut it = Self::default();
+            let Some(repo_root) = root.ancestors().find(|it| it.join(".git").exists())
+            else {
+                return it;
+            };
+            let mut folders: Vec<&Path> = root
+                .ancestors()
+                .skip(1)
+                .take_while(|it| it.starts_with(repo_root))
+                .collect();
+            folders.reverse();
+            for folder in folders {
+                it.add_gitignore_file(folder);
+            }
+            it
+        }
+
+        /// Add the patterns in the `.gitignore` file in the folder, if there is one (and
+        /// it hasn't been read already). They take precedence over the patterns of the
+        /// folders above it, as long as those are added first.
+        pub fn add_gitignore_file(&mut self, folder: &Path) {
+            if self.folders.iter().any(|it| it == folder) {
+                return;
+            }
+            self.folders.push(folder.to_path_buf());
+            if let Ok(content) = fs::read_to_string(folder.join(".gitignore")) {
+                self.add_patterns(folder, &content);
+            }
+        }
+
+        /// Add the patterns (one per line, in the `.gitignore` format) that apply to
+        /// the files in the folder.
+        pub fn add_patterns(&mut self, folder: &Path, content: &str) {
+            self.rules.extend(
+                content
+                    .lines()
+                    .filter_map(|line| GitIgnoreRule::try_parse(folder, line)),
+            );
+        }
+
+        /// The path is ignored if a folder that it is in is ignored. Otherwise the last
+        /// pattern that matches it decides.
+        pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+            path.ancestors()
+                .skip(1)
+                .any(|folder| self.is_matched(folder, true))
+                || self.is_matched(path, is_dir)
+        }
+
+        fn is_matched(&self, path: &Path, is_dir: bool) -> bool {
+            if path.file_name() == Some(OsStr::new(".git")) {
+                return true;
+            }
+            let mut is_ignored = false;
+            for rule in &self.rules {
+                if rule.matches(path, is_dir) {
+                    is_ignored = !rule.is_negated;
+                }
+            }
+            is_ignored
+        }
+    }
+
+    impl GitIgnoreRule {
+        /// Returns [None] for blank lines, comments and patterns that aren't valid.
+        fn try_parse(folder: &Path, line: &str) -> Option<Self> {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (is_negated, line) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            // Escapes a leading `#` or `!`.
+            let line = line.strip_prefix('\\').unwrap_or(line);
+            let (is_dir_only, line) = match line.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let is_anchored = line.contains('/');
+            let line = line.strip_prefix('/').unwrap_or(line);
+            // A `**` that isn't a whole folder name (eg: `foo**`) is the same as `*`.
+            let line = line
+                .split('/')
+                .map(|part| match part {
+                    "**" => part.to_string(),
+                    _ => collapse_stars(part),
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+
+            Some(Self {
+                folder: folder.to_path_buf(),
+                pattern: Pattern::new(&line).ok()?,
+                is_negated,
+                is_dir_only,
+                is_anchored,
+            })
+        }
+
+        fn matches(&self, path: &Path, is_dir: bool) -> bool {
+            if self.is_dir_only && !is_dir {
+                return false;
+            }
+            let Ok(relative_path) = path.strip_prefix(&self.folder) else {
+                return false;
+            };
+            // The folder of the `.gitignore` file itself.
+            if relative_path.as_os_str().is_empty() {
+                return false;
+            }
+            let options = MatchOptions {
+                case_sensitive: true,
+                require_literal_separator: true,
+                require_literal_leading_dot: false,
+            };
+            if self.is_anchored {
+                self.pattern.matches_path_with(relative_path, options)
+            } else {
+                path.file_name()
+                    .and_then(OsStr::to_str)
+                    .is_some_and(|name| self.pattern.matches_with(name, options))
+            }
+        }
+    }
+
+    fn collapse_stars(text: &str) -> String {
+        let mut acc = String::with_capacity(text.len());
+        for it in text.chars() {
+            if !(it == '*' && acc.ends_with('*')) {
+                acc.push(it);
+            }
+        }
+        acc
+    }
+}
+
+/// The ids of the [KeymapAction]s that the [FileExplorerComponent] handles, see
+/// [FileExplorerComponent::get_keymap_actions].
+pub mod file_explorer_actions {
+    pub const SCOPE: &str = "file_explorer";
+
+    pub const SELECT_PREVIOUS: &str = "file_explorer.select_previous";
+    pub const SELECT_NEXT: &str = "file_explorer.select_next";
+    pub const PAGE_UP: &str = "file_explorer.page_up";
+    pub const PAGE_DOWN: &str = "file_explorer.page_down";
+    pub const SELECT_FIRST: &str = "file_explorer.select_first";
+    pub const SELECT_LAST: &str = "file_explorer.select_last";
+    pub const EXPAND: &str = "file_explorer.expand";
+    pub const COLLAPSE: &str = "file_explorer.collapse";
+    pub const OPEN: &str = "file_explorer.open";
+    pub const FOCUS_EDITOR: &str = "file_explorer.focus_editor";
+}
+
+/// Shows the [State::file_explorer] in a panel, w/ the name of its root folder in the
+/// top row. Folders can be expanded and collapsed, and files are opened in the editor
+/// (which then gets the focus), either w/ the keyboard (see [file_explorer_actions]) or
+/// by clicking on them.
+pub struct FileExplorerComponent {
+    pub id: FlexBoxId,
+    keymap: Keymap,
+    /// Index of the entry in the 2nd row (right below the name of the root folder).
+    scroll_offset: usize,
+    /// The selected entry as of the last render. It is only scrolled into view when it
+    /// changes, so that scrolling w/ the mouse wheel isn't undone.
+    maybe_last_selected_index: Option<usize>,
+    /// Where the panel was last rendered, to tell which entry is clicked.
+    maybe_current_box: Option<FlexBox>,
+}
+
+mod file_explorer_component_impl {
+    use super::*;
+
+    impl FileExplorerComponent {
+        pub fn new_boxed(id: FlexBoxId) -> BoxedSafeComponent<State, AppSignal> {
+            Box::new(Self {
+                id,
+                keymap: Self::new_keymap(),
+                scroll_offset: 0,
+                maybe_last_selected_index: None,
+                maybe_current_box: None,
+            })
+        }
+
+        pub fn new_keymap() -> Keymap {
+            Keymap::new(
+                file_explorer_actions::SCOPE,
+                &[file_explorer_actions::SCOPE],
+            )
+        }
+
+        /// The actions that are registered in the [global_action_registry] for the file
+        /// explorer, w/ their default bindings.
+        pub fn get_keymap_actions() -> Vec<KeymapAction> {
+            use file_explorer_actions::*;
+            vec![
+                KeymapAction::new(SELECT_PREVIOUS, "Select the previous entry", &["Up"]),
+                KeymapAction::new(SELECT_NEXT, "Select the next entry", &["Down"]),
+                KeymapAction::new(PAGE_UP, "Select the entry a page up", &["PageUp"]),
+                KeymapAction::new(
+                    PAGE_DOWN,
+                    "Select the entry a page down",
+                    &["PageDown"],
+                ),
+                KeymapAction::new(SELECT_FIRST, "Select the first entry", &["Home"]),
+                KeymapAction::new(SELECT_LAST, "Select the last entry", &["End"]),
+                KeymapAction::new(
+                    EXPAND,
+                    "Expand the folder (or select the first entry in it)",
+                    &["Right"],
+                ),
+                KeymapAction::new(
+                    COLLAPSE,
+                    "Collapse the folder (or select the folder that it is in)",
+                    &["Left"],
+                ),
+                KeymapAction::new(
+                    OPEN,
+                    "Open the file, or expand / collapse the folder",
+                    &["Enter"],
+                ),
+                KeymapAction::new(FOCUS_EDITOR, "Go back to the editor", &["Esc"]),
+            ]
+        }
+
+        /// Number of entries that fit in the panel, below the name of the root folder.
+        fn get_viewport_height(&self) -> usize {
+            match self.maybe_current_box {
+                Some(current_box) => {
+                    ch!(@to_usize current_box.style_adjusted_bounds_size.row_count)
+                        .saturating_sub(1)
+                }
+                None => 0,
+            }
+        }
+
+        /// Returns the index of the entry at the position on the screen, if it is on
+        /// one of the rows of the panel, [None] if it isn't on the panel at all.
+        fn get_index_at(&self, position: Position) -> Option<Option<usize>> {
+            let current_box = self.maybe_current_box?;
+            let origin = current_box.style_adjusted_origin_pos;
+            let size = current_box.style_adjusted_bounds_size;
+            let is_on_panel = position.col_index >= origin.col_index
+                && position.col_index < origin.col_index + size.col_count
+                && position.row_index >= origin.row_index
+                && position.row_index < origin.row_index + size.row_count;
+            if !is_on_panel {
+                return None;
+            }
+            let row_index = ch!(@to_usize position.row_index - origin.row_index);
+            Some(row_index.checked_sub(1).map(|it| it + self.scroll_offset))
+        }
+
+        /// Open the selected file in the editor (which gets the focus), or expand /
+        /// collapse the selected folder.
+        fn open_selected(state: &mut State, has_focus: &mut HasFocus) {
+            if let Some(path) = state.file_explorer.toggle_selected() {
+                state.open_file(&path.to_string_lossy());
+                has_focus.set_id(FlexBoxId::from(Id::ComponentEditor));
+            }
+        }
+
+        fn handle_mouse_event(
+            &mut self,
+            state: &mut State,
+            mouse_input: MouseInput,
+            has_focus: &mut HasFocus,
+        ) -> EventPropagation {
+            let Some(maybe_index) = self.get_index_at(mouse_input.pos) else {
+                return EventPropagation::Propagate;
+            };
+            match mouse_input.kind {
+                MouseInputKind::MouseDown(Button::Left) => {
+                    has_focus.set_id(self.id);
+                    if let Some(index) =
+                        maybe_index.filter(|it| *it < state.file_explorer.entries.len())
+                    {
+                        state.file_explorer.select(index);
+                        Self::open_selected(state, has_focus);
+                    }
+                }
+                MouseInputKind::ScrollUp => {
+                    self.scroll_offset = self.scroll_offset.saturating_sub(1);
+                }
+                MouseInputKind::ScrollDown => {
+                    let max_scroll_offset = state
+                        .file_explorer
+                        .entries
+                        .len()
+                        .saturating_sub(self.get_viewport_height());
+                    self.scroll_offset = (self.scroll_offset + 1).min(max_scroll_offset);
+                }
+                _ => return EventPropagation::Consumed,
+            }
+            EventPropagation::ConsumedRender
+        }
+
+        /// Scroll the selected entry into view, if it has changed since the last render.
+        fn scroll_to_selected(&mut self, file_explorer: &FileExplorer, height: usize) {
+            let selected_index = file_explorer.selected_index;
+            if self.maybe_last_selected_index != Some(selected_index) {
+                if selected_index < self.scroll_offset {
+                    self.scroll_offset = selected_index;
+                } else if height > 0 && selected_index >= self.scroll_offset + height {
+                    self.scroll_offset = selected_index + 1 - height;
+                }
+                self.maybe_last_selected_index = Some(selected_index);
+            }
+            self.scroll_offset = self
+                .scroll_offset
+                .min(file_explorer.entries.len().saturating_sub(height));
+        }
+    }
+
+    impl Component<State, AppSignal> for FileExplorerComponent {
+        fn reset(&mut self) {}
+
+        fn get_id(&self) -> FlexBoxId { self.id }
+
+        fn handle_event(
+            &mut self,
+            global_data: &mut GlobalData<State, AppSignal>,
+            input_event: InputEvent,
+            has_focus: &mut HasFocus,
+        ) -> CommonResult<EventPropagation> {
+            let state = &mut global_data.state;
+
+            if let InputEvent::Mouse(mouse_input) = input_event {
+                return Ok(self.handle_mouse_event(state, mouse_input, has_focus));
+            }
+
+            let action_id = match self.keymap.resolve(&input_event) {
+                KeymapResult::Action(action_id) => action_id,
+                KeymapResult::Pending | KeymapResult::Cancelled => {
+                    return Ok(EventPropagation::Consumed);
+                }
+                KeymapResult::NotBound => return Ok(EventPropagation::Propagate),
+            };
+
+            let page_height = self.get_viewport_height().max(1);
+            let file_explorer = &mut state.file_explorer;
+            match action_id.as_str() {
+                file_explorer_actions::SELECT_PREVIOUS => {
+                    file_explorer.select_previous(1)
+                }
+                file_explorer_actions::SELECT_NEXT => file_explorer.select_next(1),
+                file_explorer_actions::PAGE_UP => {
+                    file_explorer.select_previous(page_height)
+                }
+                file_explorer_actions::PAGE_DOWN => {
+                    file_explorer.select_next(page_height)
+                }
+                file_explorer_actions::SELECT_FIRST => file_explorer.select_first(),
+                file_explorer_actions::SELECT_LAST => file_explorer.select_last(),
+                file_explorer_actions::EXPAND => file_explorer.expand_selected(),
+                file_explorer_actions::COLLAPSE => file_explorer.collapse_selected(),
+                file_explorer_actions::OPEN => Self::open_selected(state, has_focus),
+                file_explorer_actions::FOCUS_EDITOR => {
+                    has_focus.set_id(FlexBoxId::from(Id::ComponentEditor));
+                }
+                _ => return Ok(EventPropagation::Propagate),
+            }
+            Ok(EventPropagation::ConsumedRender)
+        }
+
+        fn render(
+            &mut self,
+            global_data: &mut GlobalData<State, AppSignal>,
+            current_box: FlexBox,
+            _surface_bounds: SurfaceBounds, /* Ignore this. */
+            has_focus: &mut HasFocus,
+        ) -> CommonResult<RenderPipeline> {
+            self.maybe_current_box = Some(current_box);
+            let file_explorer = &global_data.state.file_explorer;
+
+            let origin = current_box.style_adjusted_origin_pos;
+            let size = current_box.style_adjusted_bounds_size;
+            let height = self.get_viewport_height();
+            self.scroll_to_selected(file_explorer, height);
+
+            // The last column separates the panel from the editor.
+            let text_width = size.col_count - 1;
+            let is_focused = has_focus.does_current_box_have_focus(current_box);
+            let separator_style = tui_style!(
+                attrib: [dim]
+                color_fg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
+            );
+            let root_style = tui_style!(
+                attrib: [bold]
+            );
+            let folder_style = tui_style!(
+                color_fg: TuiColor::Basic(ANSIBasicColor::Blue)
+            );
+            let file_style = tui_style!();
+            let selected_style = if is_focused {
+                tui_style!(attrib: [bold, reverse])
+            } else {
+                tui_style!(attrib: [reverse])
+            };
+
+            let root_name = file_explorer
+                .root
+                .file_name()
+                .and_then(OsStr::to_str)
+                .unwrap_or("/");
+            let mut rows: Vec<(String, TuiStyle)> =
+                vec![(format!(" {root_name}"), root_style)];
+            if file_explorer.entries.is_empty() {
+                rows.push(("   (empty)".to_string(), tui_style!(attrib: [dim])));
+            }
+            for (index, entry) in file_explorer
+                .entries
+                .iter()
+                .enumerate()
+                .skip(self.scroll_offset)
+                .take(height)
+            {
+                let (marker, icon) = match (entry.is_dir, entry.is_expanded) {
+                    (true, true) => ("▾", "📂"),
+                    (true, false) => ("▸", "📁"),
+                    (false, _) => (" ", "📄"),
+                };
+                let indent = "  ".repeat(entry.depth);
+                let text = format!(" {indent}{marker} {icon} {}", entry.name);
+                let style = match (index == file_explorer.selected_index, entry.is_dir) {
+                    (true, _) => selected_style,
+                    (false, true) => folder_style,
+                    (false, false) => file_style,
+                };
+                rows.push((text, style));
+            }
+
+            let mut render_ops = render_ops!();
+            for row_index in 0..ch!(@to_usize size.row_count) {
+                let mut styled_texts: TuiStyledTexts = Default::default();
+                let (text, style) = rows
+                    .get(row_index)
+                    .cloned()
+                    .unwrap_or_else(|| (String::new(), file_style));
+                let text = UnicodeString::from(text);
+                let text =
+                    UnicodeString::from(text.truncate_end_to_fit_width(text_width));
+                styled_texts += tui_styled_text! { @style: style , @text: &text.pad_end_with_spaces_to_fit_width(" ", text_width)};
+                styled_texts += tui_styled_text! { @style: separator_style , @text: "│"};
+
+                render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                    origin,
+                    position!(col_index: 0, row_index: row_index),
+                ));
+                styled_texts.render_into(&mut render_ops);
+            }
+
+            let mut pipeline = render_pipeline!();
+            pipeline.push(ZOrder::Normal, render_ops);
+            Ok(pipeline)
+        }
+    }
+}
+
+/// Mouse events go to the component that they are on (instead of the focused one), and
+/// a click moves the focus to it. Returns [None] if the event should be routed to the
+/// focused component as usual.
+pub fn route_mouse_event(
+    global_data: &mut GlobalData<State, AppSignal>,
+    mouse_input: MouseInput,
+    component_registry_map: &mut ComponentRegistryMap<State, AppSignal>,
+    has_focus: &mut HasFocus,
+) -> CommonResult<Option<EventPropagation>> {
+    if has_focus.is_modal_set() || !global_data.state.file_explorer.is_visible {
+        return Ok(None);
+    }
+
+    let file_explorer_id = FlexBoxId::from(Id::ComponentFileExplorer);
+    if let Some(component) = ComponentRegistry::try_to_get_component_by_id(
+        component_registry_map,
+        file_explorer_id,
+    ) {
+        let event_propagation = component.handle_event(
+            global_data,
+            InputEvent::Mouse(mouse_input),
+            has_focus,
+        )?;
+        if event_propagation != EventPropagation::Propagate {
+            return Ok(Some(event_propagation));
+        }
+    }
+
+    // The event is on the editor.
+    if !has_focus.does_id_have_focus(file_explorer_id) {
+        return Ok(None);
+    }
+    let editor_id = FlexBoxId::from(Id::ComponentEditor);
+    if let MouseInputKind::MouseDown(_) = mouse_input.kind {
+        has_focus.set_id(editor_id);
+        return Ok(None);
+    }
+    match ComponentRegistry::try_to_get_component_by_id(component_registry_map, editor_id)
+    {
+        Some(component) => component
+            .handle_event(global_data, InputEvent::Mouse(mouse_input), has_focus)
+            .map(Some),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_temp_folder(files: &[&str]) -> PathBuf {
+        let folder = std::env::temp_dir().join(format!(
+            "{}_edi_file_explorer",
+            friendly_random_id::generate_friendly_random_id()
+        ));
+        for file in files {
+            let path = folder.join(file);
+            match file.strip_suffix('/') {
+                Some(_) => fs::create_dir_all(&path).unwrap(),
+                None => {
+                    fs::create_dir_all(path.parent().unwrap()).unwrap();
+                    fs::write(&path, *file).unwrap();
+                }
+            }
+        }
+        folder
+    }
+
+    fn get_rows(file_explorer: &FileExplorer) -> Vec<String> {
+        file_explorer
+            .entries
+            .iter()
+            .map(|it| format!("{}{}", "  ".repeat(it.depth), it.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_gitignore() {
+        let folder = PathBuf::from("/repo");
+        let mut gitignore = GitIgnore::default();
+        gitignore.add_patterns(
+            &folder,
+            "# Comment\n\n*.log\n!keep.log\ntarget/\n/build\ndocs/*.html\n",
+        );
+
+        let is_ignored = |gitignore: &GitIgnore, path: &str, is_dir: bool| {
+            gitignore.is_ignored(&folder.join(path), is_dir)
+        };
+        assert!(is_ignored(&gitignore, "debug.log", false));
+        assert!(is_ignored(&gitignore, "src/debug.log", false));
+        assert!(!is_ignored(&gitignore, "keep.log", false));
+        assert!(is_ignored(&gitignore, "target", true));
+        assert!(is_ignored(&gitignore, "src/target", true));
+        assert!(!is_ignored(&gitignore, "target", false));
+        assert!(is_ignored(&gitignore, "build", true));
+        assert!(!is_ignored(&gitignore, "src/build", true));
+        assert!(is_ignored(&gitignore, "docs/index.html", false));
+        assert!(!is_ignored(&gitignore, "docs/api/index.html", false));
+        assert!(!is_ignored(&gitignore, "main.rs", false));
+        assert!(is_ignored(&gitignore, ".git", true));
+
+        // The patterns of a folder below take precedence.
+        gitignore.add_patterns(&folder.join("src"), "!*.log\n");
+        assert!(!is_ignored(&gitignore, "src/debug.log", false));
+        assert!(is_ignored(&gitignore, "debug.log", false));
+    }
+
+    #[test]
+    fn test_gitignore_anchored_and_unanchored() {
+        let folder = PathBuf::from("/repo");
+        let mut gitignore = GitIgnore::default();
+        gitignore.add_patterns(&folder, "/todo.md\ntmp\nlogs/debug.log\ncache/\n");
+
+        let is_ignored =
+            |path: &str, is_dir: bool| gitignore.is_ignored(&folder.join(path), is_dir);
+        // A leading `/` only matches in the folder of the `.gitignore` file.
+        assert!(is_ignored("todo.md", false));
+        assert!(!is_ignored("src/todo.md", false));
+        // W/out any `/`, the name matches in any folder, files & folders alike.
+        assert!(is_ignored("tmp", false));
+        assert!(is_ignored("src/tmp", true));
+        assert!(is_ignored("src/tmp/main.rs", false));
+        // A `/` in the middle anchors the pattern too.
+        assert!(is_ignored("logs/debug.log", false));
+        assert!(!is_ignored("src/logs/debug.log", false));
+        // A trailing `/` doesn't anchor the pattern.
+        assert!(is_ignored("src/cache", true));
+        assert!(!is_ignored("src/cache", false));
+        // Paths outside of the folder aren't matched.
+        assert!(!gitignore.is_ignored(Path::new("/other/tmp"), false));
+    }
+
+    #[test]
+    fn test_gitignore_double_star() {
+        let folder = PathBuf::from("/repo");
+        let mut gitignore = GitIgnore::default();
+        gitignore.add_patterns(
+            &folder,
+            "**/generated\n**/fixtures/*.json\nout/**\ndocs/**/draft.md\nfoo**.txt\n",
+        );
+
+        let is_ignored =
+            |path: &str, is_dir: bool| gitignore.is_ignored(&folder.join(path), is_dir);
+        // `**/` at the start matches in any folder, including the top one.
+        assert!(is_ignored("generated", true));
+        assert!(is_ignored("src/api/generated", false));
+        assert!(is_ignored("fixtures/a.json", false));
+        assert!(is_ignored("tests/fixtures/a.json", false));
+        assert!(!is_ignored("tests/fixtures/nested/a.json", false));
+        // `/**` at the end matches everything inside the folder.
+        assert!(is_ignored("out/a.txt", false));
+        assert!(is_ignored("out/release/bin/app", false));
+        assert!(!is_ignored("out", true));
+        assert!(!is_ignored("src/out/a.txt", false));
+        // `/**/` matches zero or more folders.
+        assert!(is_ignored("docs/draft.md", false));
+        assert!(is_ignored("docs/a/b/draft.md", false));
+        assert!(!is_ignored("src/docs/draft.md", false));
+        // Any other `**` is the same as `*`.
+        assert!(is_ignored("foobar.txt", false));
+        assert!(is_ignored("src/foo.txt", false));
+    }
+
+    #[test]
+    fn test_gitignore_negation_under_ignored_folder() {
+        let folder = PathBuf::from("/repo");
+        let mut gitignore = GitIgnore::default();
+        gitignore.add_patterns(
+            &folder,
+            "build/\n!build/keep.txt\nlogs/*\n!logs/keep.log\n*.tmp\n!important.tmp\n",
+        );
+
+        let is_ignored =
+            |path: &str, is_dir: bool| gitignore.is_ignored(&folder.join(path), is_dir);
+        // The folder is ignored, so the files in it can't be shown again.
+        assert!(is_ignored("build", true));
+        assert!(is_ignored("build/keep.txt", false));
+        assert!(is_ignored("build/other.txt", false));
+        // Only the files in the folder are ignored, so they can be.
+        assert!(!is_ignored("logs", true));
+        assert!(is_ignored("logs/debug.log", false));
+        assert!(!is_ignored("logs/keep.log", false));
+        // A later pattern wins.
+        assert!(is_ignored("a.tmp", false));
+        assert!(!is_ignored("src/important.tmp", false));
+        // A folder that matches a file pattern ignores what is inside it too.
+        assert!(is_ignored("cache.tmp/important.tmp", false));
+    }
+
+    #[test]
+    fn test_gitignore_nested_files() {
+        let folder = make_temp_folder(&[
+            ".git/",
+            "app/.gitignore",
+            "app/src/main.rs",
+            "app/src/main.rs.bak",
+            "app/dist/index.js",
+            "app/lib/dist/index.js",
+            "app/debug.log",
+            "app/keep.log",
+            "lib/main.rs.bak",
+            "dist/index.js",
+        ]);
+        fs::write(folder.join(".gitignore"), "*.log\n*.bak\n").unwrap();
+        fs::write(folder.join("app/.gitignore"), "/dist\n!keep.log\n").unwrap();
+
+        // The patterns of `app/.gitignore` only apply to `app`, and its anchored ones are
+        // relative to it. It takes precedence over the `.gitignore` file above it.
+        let mut gitignore = GitIgnore::new(&folder.join("app"));
+        gitignore.add_gitignore_file(&folder.join("app"));
+        let is_ignored =
+            |path: &str, is_dir: bool| gitignore.is_ignored(&folder.join(path), is_dir);
+        assert!(is_ignored("app/src/main.rs.bak", false));
+        assert!(is_ignored("app/debug.log", false));
+        assert!(!is_ignored("app/keep.log", false));
+        assert!(is_ignored("app/dist", true));
+        assert!(!is_ignored("app/lib/dist", true));
+        assert!(!is_ignored("dist", true));
+        assert!(!is_ignored("app/src/main.rs", false));
+
+        // The file explorer reads the `.gitignore` files of the folders as it goes.
+        let mut file_explorer = FileExplorer::new(folder.clone());
+        assert_eq!(
+            file_explorer.list_files(usize::MAX),
+            vec![
+                folder.join(".gitignore"),
+                folder.join("app/.gitignore"),
+                folder.join("app/keep.log"),
+                folder.join("dist/index.js"),
+                folder.join("app/src/main.rs"),
+                folder.join("app/lib/dist/index.js"),
+            ]
+        );
+        file_explorer.show();
+        assert_eq!(
+            get_rows(&file_explorer),
+            vec!["app", "dist", "lib", ".gitignore"]
+        );
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_expand_and_collapse() {
+        let folder = make_temp_folder(&[
+            "b.md",
+            "A.md",
+            "src/main.rs",
+            "src/edi/mod.rs",
+            "docs/",
+            "target/debug.log",
+            ".gitignore",
+        ]);
+        fs::write(folder.join(".gitignore"), "target/\n").unwrap();
+
+        let mut file_explorer = FileExplorer::new(folder.clone());
+        assert!(file_explorer.entries.is_empty());
+        file_explorer.show();
+        assert_eq!(
+            get_rows(&file_explorer),
+            vec!["docs", "src", ".gitignore", "A.md", "b.md"]
+        );
+
+        // Expand src, and select the first entry in it.
+        file_explorer.select(1);
+        file_explorer.expand_selected();
+        file_explorer.expand_selected();
+        assert_eq!(
+            get_rows(&file_explorer),
+            vec![
+                "docs",
+                "src",
+                "  edi",
+                "  main.rs",
+                ".gitignore",
+                "A.md",
+                "b.md"
+            ]
+        );
+        assert_eq!(file_explorer.get_selected().unwrap().name, "edi");
+        assert_eq!(file_explorer.toggle_selected(), None);
+        file_explorer.select_next(1);
+        assert_eq!(file_explorer.get_selected().unwrap().name, "mod.rs");
+        assert_eq!(
+            file_explorer.toggle_selected(),
+            Some(folder.join("src/edi/mod.rs"))
+        );
+
+        // New files show up after a refresh, and the folders stay expanded.
+        fs::write(folder.join("src/lib.rs"), "").unwrap();
+        file_explorer.refresh();
+        assert_eq!(
+            get_rows(&file_explorer),
+            vec![
+                "docs",
+                "src",
+                "  edi",
+                "    mod.rs",
+                "  lib.rs",
+                "  main.rs",
+                ".gitignore",
+                "A.md",
+                "b.md"
+            ]
+        );
+        assert_eq!(file_explorer.get_selected().unwrap().name, "mod.rs");
+
+        // Collapse goes to the folder first, then collapses it.
+        file_explorer.collapse_selected();
+        assert_eq!(file_explorer.get_selected().unwrap().name, "edi");
+        file_explorer.select_previous(1);
+        file_explorer.collapse_selected();
+        assert_eq!(get_rows(&file_explorer).len(), 5);
+        assert_eq!(file_explorer.get_selected().unwrap().name, "src");
+
+        // The selection stays in bounds.
+        file_explorer.select_next(100);
+        assert_eq!(file_explorer.get_selected().unwrap().name, "b.md");
+        file_explorer.select(100);
+        assert_eq!(file_explorer.get_selected().unwrap().name, "b.md");
+        file_explorer.select_previous(100);
+        assert_eq!(file_explorer.get_selected().unwrap().name, "docs");
+
+        // An empty folder can't be expanded into.
+        file_explorer.expand_selected();
+        file_explorer.expand_selected();
+        assert_eq!(file_explorer.get_selected().unwrap().name, "docs");
+        assert!(file_explorer.get_selected().unwrap().is_expanded);
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_list_files() {
+        let folder = make_temp_folder(&[
+            "b.md",
+            "src/main.rs",
+            "src/edi/mod.rs",
+            "src/edi/notes.log",
+            "target/debug.log",
+            ".gitignore",
+            "src/.gitignore",
+        ]);
+        fs::write(
+            folder.join(".gitignore"),
+            "target/
+",
+        )
+        .unwrap();
+        fs::write(
+            folder.join("src/.gitignore"),
+            "*.log
+",
+        )
+        .unwrap();
+
+        // The files closer to the root come first, and the ignored ones are left out.
+        let file_explorer = FileExplorer::new(folder.clone());
+        let get_relative_paths = |files: Vec<PathBuf>| -> Vec<String> {
+            files
+                .iter()
+                .map(|it| it.strip_prefix(&folder).unwrap().display().to_string())
+                .collect()
+        };
+        assert_eq!(
+            get_relative_paths(file_explorer.list_files(100)),
+            vec![
+                ".gitignore",
+                "b.md",
+                "src/.gitignore",
+                "src/main.rs",
+                "src/edi/mod.rs"
+            ]
+        );
+        assert_eq!(
+            get_relative_paths(file_explorer.list_files(2)),
+            vec![".gitignore", "b.md"]
+        );
+
+        fs::remove_dir_all(folder).unwrap();
+    }
+}
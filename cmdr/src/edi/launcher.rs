@@ -15,25 +15,539 @@
  *   limitations under the License.
  */
 
-use r3bl_rs_utils_core::{throws, CommonResult};
+use r3bl_rs_utils_core::{log_error, throws, CommonResult};
 use r3bl_tui::*;
 
 use crate::edi::{constructor, AppMain};
 
-pub async fn run_app(maybe_file_path: Option<String>) -> CommonResult<()> {
+/// Open each of the files in `file_paths` in a tab, or a new file if there are none.
+pub async fn run_app(file_paths: Vec<String>) -> CommonResult<()> {
     throws!({
-        // Create a new state from the file path.
-        let state = constructor::new(&maybe_file_path);
+        // Use the theme in the user's config folder, if there is one. A broken theme file
+        // shouldn't keep the editor from starting.
+        if let Err(error) = global_md_theme::try_load_from_config_folder() {
+            log_error(format!("Could not load Markdown theme: {error:?}"));
+        }
+
+        // Same for the keymap file.
+        if let Err(error) = global_keymap_config::try_load_from_config_folder() {
+            log_error(format!("Could not load keymap: {error:?}"));
+        }
+
+        // Create a new state from the file paths.
+        let state = constructor::new(&file_paths);
 
         // Create a new app.
         let app = AppMain::new_boxed();
 
-        // Exit if these keys are pressed.
-        let exit_keys: Vec<InputEvent> = vec![InputEvent::Keyboard(
-            keypress! { @char ModifierKeysMask::new().with_ctrl(), 'q' },
-        )];
+        // The actions of the app are registered now, so chords in the keymap file that
+        // make other bindings unreachable can be reported.
+        for conflict in AppMain::get_keymap_conflicts() {
+            log_error(format!("Keymap conflict: {conflict}"));
+        }
+
+        // There are no exit keys, since the app handles them (see edi_actions::EXIT), so
+        // that it can ask first if there are unsaved changes.
+        let exit_keys: Vec<InputEvent> = vec![];
 
         // Create a window.
         TerminalWindow::main_event_loop(app, exit_keys, state).await?;
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use futures_util::{stream, StreamExt};
+    use r3bl_rs_utils_core::*;
+    use r3bl_tui::*;
+
+    use crate::{edi::{constructor, AppMain, FileExplorer, State},
+                report_analytics};
+
+    fn ctrl(it: char) -> InputEvent {
+        InputEvent::Keyboard(keypress! { @char ModifierKeysMask::new().with_ctrl(), it })
+    }
+
+    fn make_temp_file(name: &str, content: &str) -> String {
+        let file_path = format!(
+            "/tmp/{}_{name}",
+            friendly_random_id::generate_friendly_random_id()
+        );
+        std::fs::write(&file_path, content).unwrap();
+        file_path
+    }
+
+    fn any_frame_contains(
+        result: &HeadlessMainEventLoopResult<State>,
+        text: &str,
+    ) -> bool {
+        result.frames.iter().any(|frame| {
+            frame
+                .get_text_lines()
+                .iter()
+                .any(|line| line.contains(text))
+        })
+    }
+
+    #[test]
+    fn test_no_keymap_conflicts_by_default() {
+        // Register the actions of the app.
+        let _app = AppMain::new_boxed();
+        assert_eq!(AppMain::get_keymap_conflicts(), vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_edi_headless() {
+        let mut input_events: Vec<InputEvent> = "hello"
+            .chars()
+            .map(|it| InputEvent::Keyboard(keypress!(@char it)))
+            .collect();
+        input_events.push(InputEvent::Keyboard(keypress!(@special SpecialKey::Enter)));
+        input_events.push(InputEvent::Keyboard(keypress!(@char 'w')));
+
+        // Exit w/out saving, so the `z` is never typed. The `x` can't be typed in the
+        // dialog either.
+        input_events.push(ctrl('q'));
+        input_events.push(InputEvent::Keyboard(keypress!(@char 'x')));
+        input_events.push(InputEvent::Keyboard(keypress!(@special SpecialKey::Enter)));
+        input_events.push(InputEvent::Keyboard(keypress!(@char 'z')));
+
+        let result = TerminalWindow::main_event_loop_headless(
+            AppMain::new_boxed(),
+            vec![],
+            constructor::new(&[]),
+            size!(col_count: 80, row_count: 20),
+            stream::iter(input_events),
+        )
+        .await
+        .unwrap();
+
+        let editor_buffer = result.state.get_active_editor_buffer().unwrap();
+        assert_eq!(editor_buffer.get_as_string_with_newlines(), "hello\nw");
+        assert!(any_frame_contains(
+            &result,
+            "1 file isn't saved. Exit anyway?"
+        ));
+        assert!(any_frame_contains(&result, "Yes: Enter │ No: Esc"));
+        assert!(!any_frame_contains(&result, "xYes"));
+        assert!(!any_frame_contains(&result, "Escx"));
+
+        // The editor is painted w/ a 1 col left padding, below the buffer bar, and the
+        // status bar is at the bottom.
+        let last_frame = result.frames.last().unwrap().get_text_lines();
+        assert!(last_frame[0].starts_with(" Untitled* │"));
+        assert_eq!(last_frame[2], " hello");
+        assert!(last_frame[3].starts_with(" w"));
+        assert!(last_frame.last().unwrap().starts_with("edi"));
+        assert!(last_frame.last().unwrap().contains("● Modified"));
+    }
+
+    #[tokio::test]
+    async fn test_edi_headless_search_and_replace_titles() {
+        let chars = |text: &str| -> Vec<InputEvent> {
+            text.chars()
+                .map(|it| InputEvent::Keyboard(keypress!(@char it)))
+                .collect()
+        };
+        let enter = InputEvent::Keyboard(keypress!(@special SpecialKey::Enter));
+
+        // Search, then replace the matches of the search.
+        let mut input_events = chars("ab ab");
+        input_events.push(ctrl('f'));
+        input_events.extend(chars("ab"));
+        input_events.push(enter);
+        input_events.push(ctrl('h'));
+        input_events.extend(chars("c"));
+        input_events.push(enter);
+
+        let result = TerminalWindow::main_event_loop_headless(
+            AppMain::new_boxed(),
+            vec![],
+            constructor::new(&[]),
+            size!(col_count: 80, row_count: 20),
+            stream::iter(input_events),
+        )
+        .await
+        .unwrap();
+
+        let editor_buffer = result.state.get_active_editor_buffer().unwrap();
+        assert_eq!(editor_buffer.get_as_string_with_newlines(), "c c");
+
+        // The titles show the shortcuts from edi's keymap, and are updated as the user
+        // types.
+        assert!(any_frame_contains(
+            &result,
+            "Find │ [ ] Case: Alt+C │ [ ] Regex: Alt+R │ 2 matches"
+        ));
+        assert!(any_frame_contains(
+            &result,
+            "Replace \"ab\" │ All: Enter │ Next: Alt+Enter"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_edi_headless_save() {
+        // Don't report the saves.
+        report_analytics::disable();
+
+        let file_path = make_temp_file("save.md", "");
+        let missing_file_path = format!("{file_path}_missing/file.md");
+
+        let input_events = vec![
+            InputEvent::Keyboard(keypress!(@char 'a')),
+            ctrl('s'),
+            InputEvent::Keyboard(keypress!(@char 'b')),
+            // Switch to the buffer that can't be saved.
+            InputEvent::Keyboard(
+                keypress! { @special ModifierKeysMask::new().with_ctrl(), SpecialKey::PageDown },
+            ),
+            InputEvent::Keyboard(keypress!(@char 'c')),
+            ctrl('s'),
+        ];
+
+        let result = TerminalWindow::main_event_loop_headless(
+            AppMain::new_boxed(),
+            vec![],
+            constructor::new(&[file_path.clone(), missing_file_path]),
+            size!(col_count: 80, row_count: 20),
+            stream::iter(input_events),
+        )
+        .await
+        .unwrap();
+
+        // An empty file gets a final newline, like a new one.
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "a\n");
+        assert!(result.state.open_buffers[0].is_dirty());
+        assert!(any_frame_contains(&result, "Saved "));
+
+        // The error is shown in the status bar.
+        assert!(result.state.open_buffers[1].is_dirty());
+        let last_frame = result.frames.last().unwrap().get_text_lines();
+        assert!(last_frame.last().unwrap().contains("Could not save"));
+
+        std::fs::remove_file(file_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_edi_headless_file_changed_on_disk() {
+        let file_path = make_temp_file("changed.md", "one");
+        let state = constructor::new(std::slice::from_ref(&file_path));
+        std::fs::write(&file_path, "changed").unwrap();
+
+        // The first key press shows the dialog (and isn't typed), and `Enter` reloads the
+        // file.
+        let input_events = vec![
+            InputEvent::Keyboard(keypress!(@char 'x')),
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Enter)),
+        ];
+
+        let result = TerminalWindow::main_event_loop_headless(
+            AppMain::new_boxed(),
+            vec![],
+            state,
+            size!(col_count: 80, row_count: 20),
+            stream::iter(input_events),
+        )
+        .await
+        .unwrap();
+
+        assert!(any_frame_contains(&result, "changed on disk. Reload it?"));
+        let editor_buffer = result.state.get_active_editor_buffer().unwrap();
+        assert_eq!(editor_buffer.get_as_string_with_newlines(), "changed");
+        assert!(!result.state.open_buffers[0].is_dirty());
+        let last_frame = result.frames.last().unwrap().get_text_lines();
+        assert!(last_frame.last().unwrap().contains("Reloaded "));
+
+        std::fs::remove_file(file_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_edi_headless_file_changed_on_disk_while_typing() {
+        let file_path = make_temp_file("typing.md", "one");
+        let state = constructor::new(std::slice::from_ref(&file_path));
+
+        // The file is changed while the user is typing, which isn't interrupted, but it
+        // isn't overwritten when the buffer is saved.
+        let input_events = vec![
+            InputEvent::Keyboard(keypress!(@char 'a')),
+            InputEvent::Keyboard(keypress!(@char 'b')),
+            ctrl('s'),
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Esc)),
+        ];
+        let input_events = stream::iter(input_events).inspect({
+            let file_path = file_path.clone();
+            move |input_event| {
+                if *input_event == InputEvent::Keyboard(keypress!(@char 'b')) {
+                    std::fs::write(&file_path, "changed").unwrap();
+                }
+            }
+        });
+
+        let result = TerminalWindow::main_event_loop_headless(
+            AppMain::new_boxed(),
+            vec![],
+            state,
+            size!(col_count: 120, row_count: 20),
+            input_events,
+        )
+        .await
+        .unwrap();
+
+        let editor_buffer = result.state.get_active_editor_buffer().unwrap();
+        assert_eq!(editor_buffer.get_as_string_with_newlines(), "abone");
+        assert!(any_frame_contains(
+            &result,
+            "changed on disk. Reload it & lose your changes?"
+        ));
+        assert!(!any_frame_contains(&result, "Saved "));
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "changed");
+
+        std::fs::remove_file(file_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_edi_headless_multiple_buffers() {
+        let file_paths = vec![
+            make_temp_file("one.md", "one"),
+            make_temp_file("two.md", "two"),
+        ];
+        let next_buffer = InputEvent::Keyboard(
+            keypress! { @special ModifierKeysMask::new().with_ctrl(), SpecialKey::PageDown },
+        );
+        let close_buffer = ctrl('w');
+        let esc = InputEvent::Keyboard(keypress!(@special SpecialKey::Esc));
+
+        // Edit the 2nd buffer, try to close it (and cancel), then close the 1st one.
+        let input_events = vec![
+            next_buffer,
+            InputEvent::Keyboard(keypress!(@char 'x')),
+            close_buffer,
+            esc,
+            next_buffer,
+            close_buffer,
+        ];
+
+        let result = TerminalWindow::main_event_loop_headless(
+            AppMain::new_boxed(),
+            vec![],
+            constructor::new(&file_paths),
+            size!(col_count: 80, row_count: 20),
+            stream::iter(input_events),
+        )
+        .await
+        .unwrap();
+
+        let state = &result.state;
+        assert_eq!(state.open_buffers.len(), 1);
+        assert!(state.open_buffers[0].is_dirty());
+        assert_eq!(
+            state
+                .get_active_editor_buffer()
+                .unwrap()
+                .get_as_string_with_newlines(),
+            "xtwo"
+        );
+
+        // The close dialog was shown for the buffer w/ unsaved changes.
+        assert!(any_frame_contains(&result, "w/out saving?"));
+
+        let last_frame = result.frames.last().unwrap().get_text_lines();
+        assert!(last_frame[0].contains("_two.md* │"));
+        assert!(!last_frame[0].contains("_one.md"));
+
+        for file_path in file_paths {
+            std::fs::remove_file(file_path).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_edi_headless_file_format() {
+        let file_path = make_temp_file("crlf.md", "one\r\ntwo\r\n");
+        let invalid_file_path = make_temp_file("invalid.md", "");
+        std::fs::write(&invalid_file_path, b"caf\xE9").unwrap();
+
+        let alt_l = InputEvent::Keyboard(
+            keypress! { @char ModifierKeysMask::new().with_alt(), 'l' },
+        );
+        let next_buffer = InputEvent::Keyboard(
+            keypress! { @special ModifierKeysMask::new().with_ctrl(), SpecialKey::PageDown },
+        );
+
+        // Dismiss the warning about the 2nd buffer, convert the 1st one to LF and save
+        // it, then try to edit the 2nd one.
+        let input_events = vec![
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Right)),
+            alt_l,
+            ctrl('s'),
+            next_buffer,
+            InputEvent::Keyboard(keypress!(@char 'x')),
+        ];
+
+        let result = TerminalWindow::main_event_loop_headless(
+            AppMain::new_boxed(),
+            vec![],
+            constructor::new(&[file_path.clone(), invalid_file_path.clone()]),
+            size!(col_count: 120, row_count: 20),
+            stream::iter(input_events),
+        )
+        .await
+        .unwrap();
+
+        let first_frame = result.frames.first().unwrap().get_text_lines();
+        assert!(first_frame.last().unwrap().contains("isn't valid UTF-8"));
+
+        // The CRLF line endings are shown until they are converted.
+        assert!(any_frame_contains(&result, "CRLF │"));
+        assert!(any_frame_contains(&result, "Line endings: LF"));
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "one\ntwo\n");
+
+        // The invalid file can't be edited.
+        assert!(!result.state.open_buffers[1].is_dirty());
+        let last_frame = result.frames.last().unwrap().get_text_lines();
+        assert!(last_frame.last().unwrap().contains("Read only (not UTF-8)"));
+        assert!(last_frame[2].starts_with(" caf\u{FFFD}"));
+
+        std::fs::remove_file(file_path).unwrap();
+        std::fs::remove_file(invalid_file_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_edi_headless_file_explorer() {
+        let folder = std::path::PathBuf::from(format!(
+            "/tmp/{}_file_explorer",
+            friendly_random_id::generate_friendly_random_id()
+        ));
+        std::fs::create_dir_all(folder.join("docs")).unwrap();
+        std::fs::write(folder.join("docs/readme.md"), "readme").unwrap();
+        std::fs::write(folder.join("a.md"), "a").unwrap();
+
+        let key = |it: SpecialKey| InputEvent::Keyboard(keypress!(@special it));
+
+        // Open the explorer, expand the folder, and open the file in it. Then open the
+        // file below it by clicking on it. The editor has the focus then, so it takes
+        // 2 tries to hide the explorer.
+        let input_events = vec![
+            ctrl('b'),
+            key(SpecialKey::Right),
+            key(SpecialKey::Down),
+            key(SpecialKey::Enter),
+            InputEvent::Keyboard(keypress!(@char 'x')),
+            InputEvent::Mouse(MouseInput {
+                pos: position!(col_index: 2, row_index: 4),
+                kind: MouseInputKind::MouseDown(Button::Left),
+                maybe_modifier_keys: None,
+            }),
+            ctrl('b'),
+            ctrl('b'),
+        ];
+
+        let mut state = constructor::new(&[]);
+        state.file_explorer = FileExplorer::new(folder.clone());
+
+        let result = TerminalWindow::main_event_loop_headless(
+            AppMain::new_boxed(),
+            vec![],
+            state,
+            size!(col_count: 80, row_count: 20),
+            stream::iter(input_events),
+        )
+        .await
+        .unwrap();
+
+        // The 1st file replaced the empty buffer, and the editor got the focus.
+        let state = &result.state;
+        assert_eq!(state.open_buffers.len(), 2);
+        assert_eq!(
+            state.open_buffers[0]
+                .editor_buffer
+                .get_as_string_with_newlines(),
+            "xreadme"
+        );
+        assert_eq!(state.active_buffer_index, 1);
+        assert_eq!(
+            state
+                .get_active_editor_buffer()
+                .unwrap()
+                .get_as_string_with_newlines(),
+            "a"
+        );
+        assert!(!state.file_explorer.is_visible);
+
+        // The explorer is to the left of the editor, below the buffer bar.
+        assert!(any_frame_contains(&result, "   📄 a.md "));
+        assert!(any_frame_contains(&result, "     📄 readme.md "));
+        assert!(any_frame_contains(&result, " ▾ 📂 docs         │ xreadme"));
+        let last_frame = result.frames.last().unwrap().get_text_lines();
+        assert_eq!(last_frame[2], " a");
+
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_edi_headless_command_palette() {
+        let folder = std::path::PathBuf::from(format!(
+            "/tmp/{}_command_palette",
+            friendly_random_id::generate_friendly_random_id()
+        ));
+        std::fs::create_dir_all(folder.join("docs")).unwrap();
+        std::fs::write(folder.join("docs/readme.md"), "readme").unwrap();
+        std::fs::write(folder.join("a.md"), "a").unwrap();
+
+        let chars = |text: &str| -> Vec<InputEvent> {
+            text.chars()
+                .map(|it| InputEvent::Keyboard(keypress!(@char it)))
+                .collect()
+        };
+        let enter = InputEvent::Keyboard(keypress!(@special SpecialKey::Enter));
+
+        // Find & open a file, and type in it. Then run the action that shows the file
+        // explorer.
+        let mut input_events = vec![ctrl('p')];
+        input_events.extend(chars("rdme"));
+        input_events.push(enter);
+        input_events.extend(chars("x"));
+        input_events.push(ctrl('p'));
+        input_events.extend(chars("show explorer"));
+        input_events.push(enter);
+
+        let mut state = constructor::new(&[]);
+        state.file_explorer = FileExplorer::new(folder.clone());
+
+        let result = TerminalWindow::main_event_loop_headless(
+            AppMain::new_boxed(),
+            vec![],
+            state,
+            size!(col_count: 80, row_count: 20),
+            stream::iter(input_events),
+        )
+        .await
+        .unwrap();
+
+        // The file replaced the empty buffer, and the editor got the focus.
+        let state = &result.state;
+        assert_eq!(state.open_buffers.len(), 1);
+        assert_eq!(
+            state
+                .get_active_editor_buffer()
+                .unwrap()
+                .get_as_string_with_newlines(),
+            "xreadme"
+        );
+        assert!(state.file_explorer.is_visible);
+
+        // The palette lists the actions (w/ their shortcuts) before the files, until
+        // something is typed. Then the matches are shown.
+        assert!(any_frame_contains(&result, "Open a file or run an action"));
+        assert!(any_frame_contains(&result, "⚡ Save the file │ Ctrl+S"));
+        assert!(any_frame_contains(&result, "📄 docs/readme.md"));
+        assert!(any_frame_contains(
+            &result,
+            "⚡ Show the file explorer (or hide it, if it has the focus) │ Ctrl+B"
+        ));
+
+        // The action that was run shows the explorer next to the editor.
+        assert!(any_frame_contains(&result, " ▸ 📁 docs         │ xreadme"));
+
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+}
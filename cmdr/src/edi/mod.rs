@@ -17,10 +17,14 @@
 
 // Include.
 pub mod app_main;
+pub mod command_palette;
+pub mod file_explorer;
 pub mod launcher;
 pub mod state;
 
 // Reexport.
 pub use app_main::*;
+pub use command_palette::*;
+pub use file_explorer::*;
 pub use launcher::*;
 pub use state::*;
@@ -15,22 +15,97 @@
  *   limitations under the License.
  */
 
-use std::{collections::HashMap, ffi::OsStr, fmt::*, path::Path};
+use std::{collections::HashMap,
+          ffi::OsStr,
+          fmt::*,
+          path::{Path, PathBuf},
+          time::SystemTime};
 
+use r3bl_rs_utils_core::{CommonError, CommonErrorType, CommonResult};
 use r3bl_tui::*;
 
-use crate::edi::Id;
+use crate::edi::{CommandPalette, FileExplorer, Id};
 
 #[derive(Clone, PartialEq)]
 pub struct State {
-    pub editor_buffers: HashMap<FlexBoxId, EditorBuffer>,
+    /// The files that are open, one per tab in the buffer bar. There is always at least
+    /// one. The active one is shown in the [Id::ComponentEditor].
+    pub open_buffers: Vec<OpenBuffer>,
+    pub active_buffer_index: usize,
     pub dialog_buffers: HashMap<FlexBoxId, DialogBuffer>,
+    /// Options for the search dialog, which can be toggled while it is open.
+    pub search_options: SearchOptions,
+    /// The shortcuts that are shown in the titles of the search & replace dialogs.
+    pub search_key_hints: SearchKeyHints,
+    /// Shown in the status bar until the next key press.
+    pub maybe_status_message: Option<StatusMessage>,
+    /// The files in the folder that edi was started in, shown in the
+    /// [Id::ComponentFileExplorer] when it is visible.
+    pub file_explorer: FileExplorer,
+    /// The actions & files that the [Id::ComponentAutocompleteDialogCommandPalette]
+    /// finds, while it is open.
+    pub command_palette: CommandPalette,
+}
+
+/// The key sequences that the toggles of the search dialog, and the "replace the current
+/// match" action of the replace dialog, are bound to (eg: `Alt+C`). These are looked up
+/// in the keymap of [AppMain](crate::edi::AppMain) when one of the dialogs is shown, so
+/// that its title can be updated as the user types w/out doing that again.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SearchKeyHints {
+    pub toggle_case_sensitive: String,
+    pub toggle_regex: String,
+    pub replace_current: String,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum StatusMessage {
+    Info(String),
+    Error(String),
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for StatusMessage {
+    fn from(error: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        match error.downcast_ref::<CommonError>() {
+            Some(CommonError {
+                err_msg: Some(msg), ..
+            }) => Self::Error(msg.clone()),
+            _ => Self::Error(error.to_string()),
+        }
+    }
+}
+
+/// A file that is open in edi. Its path is the [EditorContent::maybe_file_path] of the
+/// `editor_buffer`, which is [None] for a new file that hasn't been saved yet.
+#[derive(Clone, PartialEq)]
+pub struct OpenBuffer {
+    pub editor_buffer: EditorBuffer,
+    /// The lines as of the last time that the file was loaded or saved, to tell if it
+    /// has unsaved changes. This is cheap to keep around, since it shares its memory w/
+    /// the lines in the `editor_buffer` that haven't changed.
+    pub saved_lines: EditorLines,
+    /// The [FileFormat] as of the last time that the file was loaded or saved, since
+    /// converting its line endings is also an unsaved change.
+    pub saved_file_format: FileFormat,
+    /// The file on disk as of the last time that it was loaded or saved, to tell if it
+    /// has been changed by another program. [None] if it didn't exist.
+    pub maybe_file_stamp: Option<FileStamp>,
+}
+
+/// Identifies a version of a file on disk. The modified time and length are cheap to
+/// check, and the hash of the content is only compared when they change (so that a file
+/// that is touched, but not changed, isn't reported as changed).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FileStamp {
+    pub maybe_modified: Option<SystemTime>,
+    pub len: u64,
+    pub content_hash: u64,
 }
 
 #[cfg(test)]
 mod state_tests {
     use r3bl_rs_utils_core::friendly_random_id;
-    use r3bl_tui::FlexBoxId;
+    use r3bl_tui::{FlexBoxId, HasEditorBuffers};
 
     use crate::edi::Id;
 
@@ -87,7 +162,7 @@ mod state_tests {
         std::fs::write(filename.clone(), content).unwrap();
 
         let content = super::file_utils::get_content(&Some(filename.clone()));
-        assert_eq!(content.len(), 2);
+        assert_eq!(content, b"This is a test.\nThis is only a test.");
 
         // Delete the file.
         std::fs::remove_file(filename).unwrap();
@@ -100,7 +175,6 @@ mod state_tests {
             "/tmp/{}_file.md",
             friendly_random_id::generate_friendly_random_id()
         );
-        let maybe_file_path = Some(filename.clone());
         println!("🍍🍎🍏filename: {}", filename);
 
         // Write some content to this file.
@@ -108,40 +182,301 @@ mod state_tests {
         std::fs::write(filename.clone(), content).unwrap();
 
         // Create a state.
-        let state = super::constructor::new(&maybe_file_path);
+        let state = super::constructor::new(std::slice::from_ref(&filename));
 
         // Check the state.
-        assert_eq!(state.editor_buffers.len(), 1);
+        assert_eq!(state.open_buffers.len(), 1);
         assert_eq!(state.dialog_buffers.len(), 0);
-        assert!(state
-            .editor_buffers
-            .contains_key(&FlexBoxId::from(Id::ComponentEditor)));
+        let editor_buffer = state.get_active_editor_buffer().unwrap();
+        assert_eq!(editor_buffer.editor_content.lines.len(), 2);
+        assert_eq!(editor_buffer.get_as_string_with_newlines(), content);
+
+        // Delete the file.
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_state_constructor_multiple_files() {
+        let filenames: Vec<String> = ["one", "two"]
+            .iter()
+            .map(|it| {
+                let filename = format!(
+                    "/tmp/{}_{it}.md",
+                    friendly_random_id::generate_friendly_random_id()
+                );
+                std::fs::write(&filename, *it).unwrap();
+                filename
+            })
+            .collect();
+
+        // The same file is only opened once, even w/ a different path.
+        let file_paths = [
+            filenames[0].clone(),
+            filenames[1].clone(),
+            filenames[0].clone(),
+            filenames[0].replace("/tmp/", "/tmp/../tmp/"),
+        ];
+        let mut state = super::constructor::new(&file_paths);
+
+        assert_eq!(state.open_buffers.len(), 2);
+        assert_eq!(state.active_buffer_index, 0);
         assert_eq!(
             state
-                .editor_buffers
-                .get(&FlexBoxId::from(Id::ComponentEditor))
+                .get_active_editor_buffer()
                 .unwrap()
-                .editor_content
-                .lines
-                .len(),
-            2
+                .get_as_string_with_newlines(),
+            "one"
         );
+
+        // The editor component shows the active buffer.
+        state.select_next_buffer();
         assert_eq!(
             state
-                .editor_buffers
-                .get(&FlexBoxId::from(Id::ComponentEditor))
+                .get_mut_editor_buffer(FlexBoxId::from(Id::ComponentEditor))
                 .unwrap()
-                .editor_content
-                .lines
-                .iter()
-                .map(|us| us.string.clone())
-                .collect::<Vec<String>>()
-                .join("\n"),
-            content
+                .get_as_string_with_newlines(),
+            "two"
         );
 
-        // Delete the file.
+        for filename in filenames {
+            std::fs::remove_file(filename).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_select_and_close_buffers() {
+        let mut state = super::constructor::new(&[]);
+        assert_eq!(state.open_buffers.len(), 1);
+        assert!(!state.open_buffers[0].is_dirty());
+        assert_eq!(state.open_buffers[0].get_display_name(), "Untitled");
+
+        state.open_buffers.push(super::OpenBuffer::new(&Some(
+            "/tmp/does_not_exist/foo.md".to_string(),
+        )));
+        assert_eq!(state.open_buffers[1].get_display_name(), "foo.md");
+
+        // Switching wraps around.
+        state.select_previous_buffer();
+        assert_eq!(state.active_buffer_index, 1);
+        state.select_next_buffer();
+        assert_eq!(state.active_buffer_index, 0);
+
+        // Only the buffer that is changed is dirty.
+        state
+            .get_mut_editor_buffer(FlexBoxId::from(Id::ComponentEditor))
+            .unwrap()
+            .set_lines(vec!["changed".to_string()]);
+        assert!(state.open_buffers[0].is_dirty());
+        assert!(!state.open_buffers[1].is_dirty());
+        assert_eq!(state.get_dirty_buffer_count(), 1);
+
+        // An untitled buffer can't be saved w/out a file path.
+        assert!(state.open_buffers[0].try_save().is_err());
+        assert!(state.open_buffers[0].is_dirty());
+
+        let filename = format!(
+            "/tmp/{}_file.md",
+            friendly_random_id::generate_friendly_random_id()
+        );
+        state.open_buffers[0]
+            .editor_buffer
+            .editor_content
+            .maybe_file_path = Some(filename.clone());
+        state.open_buffers[0].try_save().unwrap();
+        assert!(!state.open_buffers[0].is_dirty());
+        // A new file gets a final newline.
+        assert_eq!(std::fs::read_to_string(&filename).unwrap(), "changed\n");
         std::fs::remove_file(filename).unwrap();
+
+        // Closing the last buffer opens an empty one.
+        state.close_active_buffer();
+        assert_eq!(state.open_buffers.len(), 1);
+        assert_eq!(state.active_buffer_index, 0);
+        assert_eq!(state.open_buffers[0].get_display_name(), "foo.md");
+        state.close_active_buffer();
+        assert_eq!(state.open_buffers.len(), 1);
+        assert_eq!(state.open_buffers[0].get_display_name(), "Untitled");
+    }
+
+    fn make_temp_folder() -> std::path::PathBuf {
+        let folder = std::env::temp_dir().join(format!(
+            "{}_edi_save",
+            friendly_random_id::generate_friendly_random_id()
+        ));
+        std::fs::create_dir_all(&folder).unwrap();
+        folder
+    }
+
+    #[test]
+    fn test_try_save_content_to_file() {
+        let folder = make_temp_folder();
+        let file_path = folder.join("file.md").to_string_lossy().to_string();
+
+        // New file.
+        super::file_utils::try_save_content_to_file(&file_path, b"a\nb").unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "a\nb");
+
+        // Existing file.
+        let file_stamp =
+            super::file_utils::try_save_content_to_file(&file_path, b"c\r\nd").unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "c\r\nd");
+        assert_eq!(
+            file_stamp,
+            super::file_utils::try_get_file_stamp(&file_path).unwrap()
+        );
+
+        // No temporary files are left behind.
+        assert_eq!(std::fs::read_dir(&folder).unwrap().count(), 1);
+
+        // Errors are returned.
+        let result = super::file_utils::try_save_content_to_file(
+            &folder.join("missing/file.md").to_string_lossy(),
+            b"f",
+        );
+        assert!(result.is_err());
+        let super::StatusMessage::Error(msg) =
+            super::StatusMessage::from(result.unwrap_err())
+        else {
+            panic!("expected an error message");
+        };
+        assert!(msg.starts_with("Could not save"));
+
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_try_save_content_to_file_permissions_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let folder = make_temp_folder();
+        let file_path = folder.join("file.md").to_string_lossy().to_string();
+
+        // The permissions of an existing file are kept.
+        std::fs::write(&file_path, "a").unwrap();
+        std::fs::set_permissions(&file_path, std::fs::Permissions::from_mode(0o600))
+            .unwrap();
+        super::file_utils::try_save_content_to_file(&file_path, b"b").unwrap();
+        let metadata = std::fs::metadata(&file_path).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+
+        // The file that a symlink points to is replaced, not the symlink.
+        let link_path = folder.join("link.md");
+        std::os::unix::fs::symlink(&file_path, &link_path).unwrap();
+        super::file_utils::try_save_content_to_file(&link_path.to_string_lossy(), b"e")
+            .unwrap();
+        assert!(std::fs::symlink_metadata(&link_path)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "e");
+
+        // No temporary files are left behind.
+        assert_eq!(std::fs::read_dir(&folder).unwrap().count(), 2);
+
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_file_format_is_kept() {
+        let folder = make_temp_folder();
+        let file_path = folder.join("file.md").to_string_lossy().to_string();
+        std::fs::write(&file_path, b"\xEF\xBB\xBFa\r\nb").unwrap();
+
+        let mut open_buffer = super::OpenBuffer::new(&Some(file_path.clone()));
+        assert_eq!(
+            open_buffer.editor_buffer.get_as_string_with_newlines(),
+            "a\nb"
+        );
+        open_buffer
+            .editor_buffer
+            .set_lines(vec!["c".to_string(), "d".to_string()]);
+        open_buffer.try_save().unwrap();
+        assert_eq!(std::fs::read(&file_path).unwrap(), b"\xEF\xBB\xBFc\r\nd");
+
+        // Converting the line endings is an unsaved change.
+        assert_eq!(
+            open_buffer.try_convert_line_endings().unwrap(),
+            r3bl_tui::LineEnding::Lf
+        );
+        assert!(open_buffer.is_dirty());
+        open_buffer.try_save().unwrap();
+        assert!(!open_buffer.is_dirty());
+        assert_eq!(std::fs::read(&file_path).unwrap(), b"\xEF\xBB\xBFc\nd");
+
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_read_only() {
+        let folder = make_temp_folder();
+        let file_path = folder.join("file.md").to_string_lossy().to_string();
+        std::fs::write(&file_path, b"caf\xE9\n").unwrap();
+
+        let mut state = super::constructor::new(std::slice::from_ref(&file_path));
+        assert_eq!(
+            state.maybe_status_message,
+            Some(super::StatusMessage::Error(
+                "\"file.md\" isn't valid UTF-8, so it is read only".to_string()
+            ))
+        );
+
+        let open_buffer = state.get_mut_active_buffer().unwrap();
+        assert!(open_buffer.editor_buffer.is_read_only());
+        assert_eq!(
+            open_buffer.editor_buffer.get_as_string_with_newlines(),
+            "caf\u{FFFD}"
+        );
+
+        // The file isn't changed.
+        open_buffer
+            .editor_buffer
+            .set_lines(vec!["changed".to_string()]);
+        assert!(open_buffer.try_save().is_err());
+        assert!(open_buffer.try_convert_line_endings().is_err());
+        assert_eq!(std::fs::read(&file_path).unwrap(), b"caf\xE9\n");
+
+        std::fs::remove_dir_all(folder).unwrap();
+    }
+
+    #[test]
+    fn test_is_changed_on_disk() {
+        let folder = make_temp_folder();
+        let file_path = folder.join("file.md").to_string_lossy().to_string();
+        std::fs::write(&file_path, "one").unwrap();
+
+        let mut open_buffer = super::OpenBuffer::new(&Some(file_path.clone()));
+        assert!(!open_buffer.is_changed_on_disk());
+
+        // Writing the same content isn't a change.
+        std::fs::write(&file_path, "one").unwrap();
+        assert!(!open_buffer.is_changed_on_disk());
+
+        // Reload the changed file.
+        std::fs::write(&file_path, "two").unwrap();
+        assert!(open_buffer.is_changed_on_disk());
+        open_buffer.reload_from_disk();
+        assert!(!open_buffer.is_changed_on_disk());
+        assert!(!open_buffer.is_dirty());
+        assert_eq!(
+            open_buffer.editor_buffer.get_as_string_with_newlines(),
+            "two"
+        );
+
+        // Keep the buffer instead of the changed file.
+        std::fs::write(&file_path, "three!").unwrap();
+        assert!(open_buffer.is_changed_on_disk());
+        open_buffer.keep_instead_of_changes_on_disk();
+        assert!(!open_buffer.is_changed_on_disk());
+        assert!(open_buffer.is_dirty());
+        assert_eq!(
+            open_buffer.editor_buffer.get_as_string_with_newlines(),
+            "two"
+        );
+
+        // A deleted file isn't reported.
+        std::fs::remove_dir_all(folder).unwrap();
+        assert!(!open_buffer.is_changed_on_disk());
     }
 }
 
@@ -151,48 +486,307 @@ pub mod constructor {
     impl Default for State {
         fn default() -> Self {
             Self {
-                editor_buffers: create_hash_map_of_editor_buffers(&None),
+                open_buffers: vec![OpenBuffer::new(&None)],
+                active_buffer_index: 0,
                 dialog_buffers: Default::default(),
+                search_options: Default::default(),
+                search_key_hints: Default::default(),
+                maybe_status_message: None,
+                file_explorer: FileExplorer::new(
+                    std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+                ),
+                command_palette: Default::default(),
             }
         }
     }
 
-    pub fn new(maybe_file_path: &Option<String>) -> State {
-        match maybe_file_path {
-            Some(_) => State {
-                editor_buffers: create_hash_map_of_editor_buffers(maybe_file_path),
-                dialog_buffers: Default::default(),
-            },
-            None => State::default(),
+    /// Open each of the files in `file_paths` in a buffer. A file that doesn't exist is
+    /// created when its buffer is saved. A file is only opened once, even if it is in
+    /// `file_paths` more than once (see [file_utils::is_same_file]). If there are none,
+    /// then a single empty buffer is opened.
+    pub fn new(file_paths: &[String]) -> State {
+        let mut open_buffers: Vec<OpenBuffer> = vec![];
+        for file_path in file_paths {
+            if open_buffers.iter().any(|it| it.is_file(file_path)) {
+                continue;
+            }
+            open_buffers.push(OpenBuffer::new(&Some(file_path.clone())));
+        }
+
+        if open_buffers.is_empty() {
+            return State::default();
+        }
+
+        let maybe_status_message = open_buffers
+            .iter()
+            .find(|it| it.editor_buffer.is_read_only())
+            .map(|it| StatusMessage::Error(it.get_read_only_message()));
+
+        State {
+            open_buffers,
+            maybe_status_message,
+            ..Default::default()
         }
     }
 
-    fn create_hash_map_of_editor_buffers(
-        maybe_file_path: &Option<String>,
-    ) -> HashMap<FlexBoxId, EditorBuffer> {
-        let editor_buffer = {
+    impl OpenBuffer {
+        /// Load the file, if there is one. If it doesn't exist, then it is created when
+        /// the buffer is saved. If it isn't valid UTF-8, then the buffer is read only
+        /// (see [EditorBuffer::is_read_only]).
+        pub fn new(maybe_file_path: &Option<String>) -> Self {
             let mut editor_buffer = EditorBuffer::new_empty(
                 &Some(file_utils::get_file_extension(maybe_file_path)),
                 maybe_file_path,
             );
-            editor_buffer.set_lines(file_utils::get_content(maybe_file_path));
-            editor_buffer
-        };
+            let (content, maybe_file_stamp) =
+                file_utils::get_content_and_file_stamp(maybe_file_path);
+            editor_buffer.set_lines_from_file_bytes(&content);
+            Self {
+                saved_lines: editor_buffer.editor_content.lines.clone(),
+                saved_file_format: editor_buffer.get_file_format(),
+                maybe_file_stamp,
+                editor_buffer,
+            }
+        }
+    }
+}
 
-        {
-            let mut it = HashMap::new();
-            it.insert(FlexBoxId::from(Id::ComponentEditor), editor_buffer);
-            it
+mod open_buffers_impl {
+    use super::*;
+
+    impl State {
+        pub fn get_active_buffer(&self) -> Option<&OpenBuffer> {
+            self.open_buffers.get(self.active_buffer_index)
+        }
+
+        pub fn get_mut_active_buffer(&mut self) -> Option<&mut OpenBuffer> {
+            self.open_buffers.get_mut(self.active_buffer_index)
+        }
+
+        pub fn get_active_editor_buffer(&self) -> Option<&EditorBuffer> {
+            self.get_active_buffer().map(|it| &it.editor_buffer)
+        }
+
+        pub fn select_next_buffer(&mut self) {
+            self.active_buffer_index =
+                (self.active_buffer_index + 1) % self.open_buffers.len().max(1);
+        }
+
+        pub fn select_previous_buffer(&mut self) {
+            let len = self.open_buffers.len().max(1);
+            self.active_buffer_index = (self.active_buffer_index + len - 1) % len;
+        }
+
+        pub fn get_dirty_buffer_count(&self) -> usize {
+            self.open_buffers.iter().filter(|it| it.is_dirty()).count()
+        }
+
+        /// Close the active buffer (w/out saving it), and activate the one after it. If
+        /// it is the only buffer, then it is replaced w/ an empty one.
+        pub fn close_active_buffer(&mut self) {
+            if self.active_buffer_index < self.open_buffers.len() {
+                self.open_buffers.remove(self.active_buffer_index);
+            }
+            if self.open_buffers.is_empty() {
+                self.open_buffers.push(OpenBuffer::new(&None));
+            }
+            self.active_buffer_index =
+                self.active_buffer_index.min(self.open_buffers.len() - 1);
+        }
+
+        /// Activate the buffer that the file is open in, or open it in a new one (which
+        /// replaces the only buffer if that is a new file that is still empty).
+        pub fn open_file(&mut self, file_path: &str) {
+            if let Some(index) = self
+                .open_buffers
+                .iter()
+                .position(|it| it.is_file(file_path))
+            {
+                self.active_buffer_index = index;
+                return;
+            }
+
+            let open_buffer = OpenBuffer::new(&Some(file_path.to_string()));
+            if open_buffer.editor_buffer.is_read_only() {
+                self.maybe_status_message =
+                    Some(StatusMessage::Error(open_buffer.get_read_only_message()));
+            }
+
+            let is_only_buffer_unused = matches!(
+                self.open_buffers.as_slice(),
+                [it] if it.get_maybe_file_path().is_none()
+                    && it.editor_buffer.is_empty()
+                    && !it.is_dirty()
+            );
+            if is_only_buffer_unused {
+                self.open_buffers[0] = open_buffer;
+                self.active_buffer_index = 0;
+            } else {
+                self.open_buffers.push(open_buffer);
+                self.active_buffer_index = self.open_buffers.len() - 1;
+            }
+        }
+    }
+
+    impl OpenBuffer {
+        pub fn get_maybe_file_path(&self) -> &Option<String> {
+            &self.editor_buffer.editor_content.maybe_file_path
+        }
+
+        /// Returns true if the buffer is for the file at `file_path`, see
+        /// [file_utils::is_same_file].
+        pub fn is_file(&self, file_path: &str) -> bool {
+            self.get_maybe_file_path()
+                .as_deref()
+                .is_some_and(|it| file_utils::is_same_file(it, file_path))
+        }
+
+        /// Returns true if the buffer has changed since it was loaded or saved.
+        pub fn is_dirty(&self) -> bool {
+            self.editor_buffer.editor_content.lines != self.saved_lines
+                || self.editor_buffer.get_file_format() != self.saved_file_format
+        }
+
+        /// Save the buffer to its file (see [file_utils::try_save_content_to_file]), in
+        /// the [FileFormat] that it was loaded w/. A new buffer needs a file name (see
+        /// [EditorContent::maybe_file_path]) first, and a read only buffer can't be
+        /// saved.
+        pub fn try_save(&mut self) -> CommonResult<()> {
+            let Some(file_path) = self.get_maybe_file_path().clone() else {
+                return CommonError::new(
+                    CommonErrorType::InvalidState,
+                    &format!(
+                        "{:?} needs a file name to be saved",
+                        self.get_display_name()
+                    ),
+                );
+            };
+            if self.editor_buffer.is_read_only() {
+                return CommonError::new(
+                    CommonErrorType::IOError,
+                    &self.get_read_only_message(),
+                );
+            }
+            let bytes = self.editor_buffer.get_as_file_bytes();
+            let file_stamp = file_utils::try_save_content_to_file(&file_path, &bytes)?;
+            self.mark_saved(file_stamp);
+            Ok(())
+        }
+
+        /// Call this after the buffer is saved.
+        pub fn mark_saved(&mut self, file_stamp: FileStamp) {
+            self.saved_lines = self.editor_buffer.editor_content.lines.clone();
+            self.saved_file_format = self.editor_buffer.get_file_format();
+            self.maybe_file_stamp = Some(file_stamp);
+        }
+
+        /// Switch the line endings that the buffer is saved w/ between `\n` and `\r\n`,
+        /// and return the new ones. The buffer is dirty until it is saved.
+        pub fn try_convert_line_endings(&mut self) -> CommonResult<LineEnding> {
+            if self.editor_buffer.is_read_only() {
+                return CommonError::new(
+                    CommonErrorType::InvalidState,
+                    &self.get_read_only_message(),
+                );
+            }
+            let line_ending = self.editor_buffer.get_file_format().line_ending.toggle();
+            self.editor_buffer.set_line_ending(line_ending);
+            Ok(line_ending)
+        }
+
+        /// Explains why the buffer is read only.
+        pub fn get_read_only_message(&self) -> String {
+            format!(
+                "{:?} isn't valid UTF-8, so it is read only",
+                self.get_display_name()
+            )
+        }
+
+        /// Returns true if the file has been changed (or created) by another program
+        /// since it was loaded or saved. A file that has been deleted isn't reported,
+        /// since saving the buffer just creates it again.
+        pub fn is_changed_on_disk(&mut self) -> bool {
+            let Some(file_path) = self.get_maybe_file_path() else {
+                return false;
+            };
+            let Ok(metadata) = std::fs::metadata(file_path) else {
+                return false;
+            };
+
+            if let Some(file_stamp) = self.maybe_file_stamp {
+                if file_stamp.maybe_modified == metadata.modified().ok()
+                    && file_stamp.len == metadata.len()
+                {
+                    return false;
+                }
+            }
+
+            let Ok(new_file_stamp) = file_utils::try_get_file_stamp(file_path) else {
+                return false;
+            };
+            match self.maybe_file_stamp {
+                Some(file_stamp)
+                    if file_stamp.content_hash == new_file_stamp.content_hash =>
+                {
+                    // Only touched, so there's no need to hash it again next time.
+                    self.maybe_file_stamp = Some(new_file_stamp);
+                    false
+                }
+                _ => true,
+            }
+        }
+
+        /// Replace the buffer w/ the file on disk, which drops any unsaved changes.
+        pub fn reload_from_disk(&mut self) {
+            let maybe_file_path = self.get_maybe_file_path().clone();
+            *self = Self::new(&maybe_file_path);
+        }
+
+        /// Keep the buffer as it is, even though the file on disk has changed. It is
+        /// dirty from now on (unless it is changed to match the file on disk), and
+        /// saving it overwrites the file.
+        pub fn keep_instead_of_changes_on_disk(&mut self) {
+            let (content, maybe_file_stamp) =
+                file_utils::get_content_and_file_stamp(self.get_maybe_file_path());
+            let (file_format, lines) = FileFormat::decode(&content);
+            self.saved_lines = EditorLines::from(lines);
+            self.saved_file_format = file_format;
+            self.maybe_file_stamp = maybe_file_stamp;
+        }
+
+        /// The file name (w/out the folder) to show in the buffer bar, or `Untitled` for
+        /// a new file.
+        pub fn get_display_name(&self) -> String {
+            match self.get_maybe_file_path() {
+                Some(file_path) => Path::new(file_path)
+                    .file_name()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or(file_path)
+                    .to_string(),
+                None => "Untitled".to_string(),
+            }
         }
     }
 }
 
 pub mod file_utils {
+    use std::{hash::{DefaultHasher, Hash, Hasher},
+              io::Write};
+
     use crossterm::style::Stylize;
     use r3bl_rs_utils_core::{call_if_true, log_debug, log_error};
 
     use super::*;
-    use crate::{report_analytics, AnalyticsAction};
+
+    /// Returns true if both paths are for the same file (eg: `notes.md` and
+    /// `./notes.md`), by comparing their canonical paths. If either of them doesn't
+    /// exist, then the paths themselves are compared.
+    pub fn is_same_file(lhs: &str, rhs: &str) -> bool {
+        match (std::fs::canonicalize(lhs), std::fs::canonicalize(rhs)) {
+            (Ok(lhs), Ok(rhs)) => lhs == rhs,
+            _ => lhs == rhs,
+        }
+    }
 
     pub fn get_file_extension(maybe_file_path: &Option<String>) -> String {
         if let Some(file_path) = maybe_file_path {
@@ -209,10 +803,21 @@ pub mod file_utils {
         DEFAULT_SYN_HI_FILE_EXT.to_owned()
     }
 
-    pub fn get_content(maybe_file_path: &Option<String>) -> Vec<String> {
+    /// Returns the bytes of the file, which are decoded w/
+    /// [EditorBuffer::set_lines_from_file_bytes].
+    pub fn get_content(maybe_file_path: &Option<String>) -> Vec<u8> {
+        get_content_and_file_stamp(maybe_file_path).0
+    }
+
+    /// Returns the bytes of the file (like [get_content]), along w/ its [FileStamp],
+    /// which is made from the same bytes, so that the file is only read once. The
+    /// [FileStamp] is [None] if the file can't be read (eg: it doesn't exist).
+    pub fn get_content_and_file_stamp(
+        maybe_file_path: &Option<String>,
+    ) -> (Vec<u8>, Option<FileStamp>) {
         // Get the content if the file exists, and it can be read.
         if let Some(file_path) = maybe_file_path {
-            let result_file_read = std::fs::read_to_string(file_path);
+            let result_file_read = std::fs::read(file_path);
             match result_file_read {
                 Ok(content) => {
                     let msg = format!("Successfully read file: {file_path:?}")
@@ -221,7 +826,10 @@ pub mod file_utils {
                     call_if_true!(DEBUG_TUI_MOD, {
                         log_debug(format!("\n💾💾💾✅ {}", msg));
                     });
-                    return content.lines().map(|s| s.to_string()).collect();
+                    let maybe_file_stamp = std::fs::metadata(file_path)
+                        .ok()
+                        .map(|metadata| FileStamp::new(&metadata, &content));
+                    return (content, maybe_file_stamp);
                 }
                 Err(error) => {
                     let msg = format!("Failed to read file: {error:?}").red().to_string();
@@ -230,53 +838,117 @@ pub mod file_utils {
             }
         }
         // Otherwise, an empty vec is returned.
-        vec![]
+        (vec![], None)
     }
 
-    pub fn save_content_to_file(file_path: String, content: String) {
-        tokio::spawn(async move {
-            report_analytics::start_task_to_generate_event(
-                "".to_string(),
-                AnalyticsAction::EdiFileSave,
-            );
+    pub fn try_get_file_stamp(file_path: &str) -> CommonResult<FileStamp> {
+        match (std::fs::metadata(file_path), std::fs::read(file_path)) {
+            (Ok(metadata), Ok(bytes)) => Ok(FileStamp::new(&metadata, &bytes)),
+            (Err(error), _) | (_, Err(error)) => CommonError::new(
+                CommonErrorType::IOError,
+                &format!("Could not read {file_path:?}: {error}"),
+            ),
+        }
+    }
 
-            let result_file_write = std::fs::write(file_path.clone(), content);
-            match result_file_write {
-                Ok(_) => {
-                    let msg = format!("Successfully saved file: {file_path:?}")
-                        .green()
-                        .to_string();
-                    call_if_true!(DEBUG_TUI_MOD, {
-                        log_debug(format!("\n💾💾💾❌ {}", msg));
-                    });
-                }
-                Err(error) => {
-                    let msg = format!("Failed to save file: {error:?}").red().to_string();
-                    log_error(format!("\n💾💾💾✅ {}", msg));
-                }
+    /// Save the `bytes` to the file, and return its new [FileStamp]. This is done
+    /// atomically, so that the file is never left half written (eg: if the disk is
+    /// full): the bytes are written to a temporary file in the same folder, which then
+    /// replaces the file. If the file already exists, then:
+    /// - Its permissions are kept.
+    /// - If it is a symlink, then the file that it points to is replaced.
+    pub fn try_save_content_to_file(
+        file_path: &str,
+        bytes: &[u8],
+    ) -> CommonResult<FileStamp> {
+        let path =
+            std::fs::canonicalize(file_path).unwrap_or_else(|_| PathBuf::from(file_path));
+        let maybe_metadata = std::fs::metadata(&path).ok();
+
+        let file_name = path.file_name().and_then(OsStr::to_str).unwrap_or_default();
+        let temp_path =
+            path.with_file_name(format!(".{file_name}.{}.edi-save", std::process::id()));
+
+        let result_write = write_and_replace(
+            &temp_path,
+            &path,
+            bytes,
+            maybe_metadata.map(|it| it.permissions()),
+        );
+        let result_metadata = result_write.and_then(|_| std::fs::metadata(&path));
+
+        match result_metadata {
+            Ok(metadata) => {
+                let msg = format!("Successfully saved file: {file_path:?}")
+                    .green()
+                    .to_string();
+                call_if_true!(DEBUG_TUI_MOD, {
+                    log_debug(format!("\n💾💾💾✅ {}", msg));
+                });
+                Ok(FileStamp::new(&metadata, bytes))
+            }
+            Err(error) => {
+                std::fs::remove_file(&temp_path).ok();
+                let msg = format!("Failed to save file: {error:?}").red().to_string();
+                log_error(format!("\n💾💾💾❌ {}", msg));
+                CommonError::new(
+                    CommonErrorType::IOError,
+                    &format!("Could not save {file_path:?}: {error}"),
+                )
+            }
+        }
+    }
+
+    fn write_and_replace(
+        temp_path: &Path,
+        path: &Path,
+        bytes: &[u8],
+        maybe_permissions: Option<std::fs::Permissions>,
+    ) -> std::io::Result<()> {
+        let mut file = std::fs::File::create(temp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        if let Some(permissions) = maybe_permissions {
+            std::fs::set_permissions(temp_path, permissions)?;
+        }
+        std::fs::rename(temp_path, path)
+    }
+
+    impl FileStamp {
+        pub fn new(metadata: &std::fs::Metadata, bytes: &[u8]) -> Self {
+            let mut hasher = DefaultHasher::new();
+            bytes.hash(&mut hasher);
+            Self {
+                maybe_modified: metadata.modified().ok(),
+                len: metadata.len(),
+                content_hash: hasher.finish(),
             }
-        });
+        }
     }
 }
 
 mod impl_editor_support {
     use super::*;
 
+    /// Only the [Id::ComponentEditor] has an editor buffer, which is the active one in
+    /// [State::open_buffers].
     impl HasEditorBuffers for State {
         fn get_mut_editor_buffer(&mut self, id: FlexBoxId) -> Option<&mut EditorBuffer> {
-            if let Some(buffer) = self.editor_buffers.get_mut(&id) {
-                Some(buffer)
-            } else {
-                None
+            if id != FlexBoxId::from(Id::ComponentEditor) {
+                return None;
             }
+            self.get_mut_active_buffer().map(|it| &mut it.editor_buffer)
         }
 
         fn insert_editor_buffer(&mut self, id: FlexBoxId, buffer: EditorBuffer) {
-            self.editor_buffers.insert(id, buffer);
+            if let Some(editor_buffer) = self.get_mut_editor_buffer(id) {
+                *editor_buffer = buffer;
+            }
         }
 
         fn contains_editor_buffer(&self, id: FlexBoxId) -> bool {
-            self.editor_buffers.contains_key(&id)
+            id == FlexBoxId::from(Id::ComponentEditor)
+                && self.get_active_buffer().is_some()
         }
     }
 }
@@ -302,14 +974,26 @@ mod impl_debug_format {
         fn fmt(&self, f: &mut Formatter<'_>) -> Result { fmt(self, f) }
     }
 
+    impl Debug for OpenBuffer {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+            write! { f,
+                "OpenBuffer [ is_dirty: {:?}, editor_buffer: {:?} ]",
+                self.is_dirty(),
+                self.editor_buffer,
+            }
+        }
+    }
+
     fn fmt(this: &State, f: &mut Formatter<'_>) -> Result {
         write! { f,
             "\nState [\n\
             - dialog_buffers:\n{:?}\n\
-            - editor_buffers:\n{:?}\n\
+            - active_buffer_index: {:?}\n\
+            - open_buffers:\n{:?}\n\
             ]",
             this.dialog_buffers,
-            this.editor_buffers,
+            this.active_buffer_index,
+            this.open_buffers,
         }
     }
 }
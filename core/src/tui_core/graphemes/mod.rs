@@ -157,6 +157,7 @@ pub mod convert;
 pub mod range;
 pub mod result_types;
 pub mod unicode_string;
+pub mod word_boundary;
 
 // Re-export.
 pub use convert::*;
@@ -280,4 +280,38 @@ mod tests {
         assert_eq2! {acc[0].string, "Hi "};
         assert_eq2! {acc[1].string, "😃 📦 🙏🏽 👨🏾‍🤝‍👨🏿."};
     }
+
+    #[test]
+    fn test_unicode_string_word_boundaries() {
+        let u_s = UnicodeString::from("fn foo_bar(😃 café) {}");
+
+        let word_ranges: Vec<(usize, usize)> = u_s
+            .get_word_ranges()
+            .iter()
+            .map(|it| {
+                (
+                    ch!(@to_usize it.start_display_col_index),
+                    ch!(@to_usize it.end_display_col_index),
+                )
+            })
+            .collect();
+        assert_eq2!(word_ranges, vec![(0, 2), (3, 10), (14, 18)]);
+
+        // Moving left and right skips over whitespace and punctuation.
+        assert_eq2!(u_s.find_prev_word_start(ch!(14)), Some(ch!(3)));
+        assert_eq2!(u_s.find_prev_word_start(ch!(5)), Some(ch!(3)));
+        assert_eq2!(u_s.find_prev_word_start(ch!(0)), None);
+        assert_eq2!(u_s.find_next_word_end(ch!(10)), Some(ch!(18)));
+        assert_eq2!(u_s.find_next_word_end(ch!(18)), None);
+
+        assert_eq2!(
+            u_s.get_word_range_at_display_col(ch!(5)),
+            Some(SelectionRange::new(ch!(3), ch!(10)))
+        );
+        assert_eq2!(
+            u_s.get_word_range_at_display_col(ch!(18)),
+            Some(SelectionRange::new(ch!(14), ch!(18)))
+        );
+        assert_eq2!(u_s.get_word_range_at_display_col(ch!(12)), None);
+    }
 }
@@ -0,0 +1,84 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::*;
+
+impl UnicodeString {
+    /// Returns the display col ranges of the words in this string. Word boundaries are
+    /// found using [Unicode word segmentation](https://www.unicode.org/reports/tr29/),
+    /// and only the segments that contain at least one alphanumeric character are words
+    /// (ie, whitespace and punctuation are not).
+    pub fn get_word_ranges(&self) -> Vec<SelectionRange> {
+        self.string
+            .split_word_bound_indices()
+            .filter(|(_, word)| word.chars().any(char::is_alphanumeric))
+            .map(|(byte_index, word)| {
+                SelectionRange::new(
+                    self.display_col_index_at_byte_index(byte_index),
+                    self.display_col_index_at_byte_index(byte_index + word.len()),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the display col of the start of the word that is before `display_col`.
+    /// If `display_col` is in the middle of a word, then this is the start of that word.
+    pub fn find_prev_word_start(&self, display_col: ChUnit) -> Option<ChUnit> {
+        self.get_word_ranges()
+            .into_iter()
+            .map(|range| range.start_display_col_index)
+            .rfind(|start| *start < display_col)
+    }
+
+    /// Returns the display col of the end of the word that is after `display_col`. If
+    /// `display_col` is in the middle of a word, then this is the end of that word.
+    pub fn find_next_word_end(&self, display_col: ChUnit) -> Option<ChUnit> {
+        self.get_word_ranges()
+            .into_iter()
+            .map(|range| range.end_display_col_index)
+            .find(|end| *end > display_col)
+    }
+
+    /// Returns the range of the word that `display_col` is in, or that ends at it.
+    pub fn get_word_range_at_display_col(
+        &self,
+        display_col: ChUnit,
+    ) -> Option<SelectionRange> {
+        let word_ranges = self.get_word_ranges();
+        word_ranges
+            .iter()
+            .find(|range| {
+                range.start_display_col_index <= display_col
+                    && display_col < range.end_display_col_index
+            })
+            .or_else(|| {
+                word_ranges
+                    .iter()
+                    .find(|range| range.end_display_col_index == display_col)
+            })
+            .copied()
+    }
+
+    fn display_col_index_at_byte_index(&self, byte_index: usize) -> ChUnit {
+        self.iter()
+            .find(|segment| segment.byte_offset >= byte_index)
+            .map(|segment| segment.display_col_offset)
+            .unwrap_or(self.display_width)
+    }
+}
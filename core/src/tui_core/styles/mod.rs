@@ -18,11 +18,13 @@
 // Attach sources.
 pub mod hex_color_parser;
 pub mod tui_color;
+pub mod tui_hyperlink;
 pub mod tui_style;
 pub mod tui_stylesheet;
 
 // Re-export.
 pub use hex_color_parser::*;
 pub use tui_color::*;
+pub use tui_hyperlink::*;
 pub use tui_style::*;
 pub use tui_stylesheet::*;
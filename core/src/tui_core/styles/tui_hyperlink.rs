@@ -0,0 +1,282 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::HashMap,
+          fmt::{Debug, Formatter},
+          sync::{atomic::{AtomicI8, Ordering},
+                 Arc,
+                 RwLock}};
+
+use get_size::GetSize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A link that can be attached to a [TuiStyle](crate::TuiStyle), so that the text that
+/// is painted w/ that style becomes clickable in terminals that support [OSC 8
+/// hyperlinks](https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda).
+///
+/// [TuiStyle](crate::TuiStyle) is [Copy], so the url isn't stored in here. Instead it
+/// is interned in a [global_hyperlink_registry], and this only holds the `id` of the url
+/// in that registry. The same url gets the same `id` (until the registry is cleared),
+/// which is also used as the OSC 8 `id` parameter. This allows the terminal to treat text
+/// that is painted in many chunks (eg: when only parts of the screen are repainted, or a
+/// link is wrapped across lines) as a single link.
+///
+/// The `id` is only meaningful in this process, so the url is serialized instead.
+///
+/// ```rust
+/// use r3bl_rs_utils_core::TuiHyperlink;
+///
+/// let link = TuiHyperlink::new("https://r3bl.com");
+/// assert_eq!(link, TuiHyperlink::new("https://r3bl.com"));
+/// assert_eq!(link.get_url().as_deref(), Some("https://r3bl.com"));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Hash, GetSize)]
+pub struct TuiHyperlink {
+    pub id: u32,
+}
+
+/// Closes the link that was opened w/ [TuiHyperlink::get_osc8_start].
+pub const OSC8_END: &str = "\x1b]8;;\x1b\\";
+
+mod tui_hyperlink_impl {
+    use super::*;
+
+    impl TuiHyperlink {
+        /// Control characters are removed from the `url`, so that it can't be used to
+        /// inject escape sequences into the terminal.
+        pub fn new(url: &str) -> Self {
+            let url: String = url.chars().filter(|it| !it.is_control()).collect();
+            Self {
+                id: global_hyperlink_registry::intern(&url),
+            }
+        }
+
+        pub fn get_url(&self) -> Option<Arc<str>> {
+            global_hyperlink_registry::get(self.id)
+        }
+
+        /// Returns the OSC 8 escape sequence that opens this link. Text painted after this
+        /// (and before [OSC8_END]) is clickable.
+        pub fn get_osc8_start(&self) -> String {
+            format!(
+                "\x1b]8;id=r3bl-{};{}\x1b\\",
+                self.id,
+                self.get_url().as_deref().unwrap_or_default()
+            )
+        }
+
+        /// Wraps `text` in OSC 8 escape sequences if [global_hyperlink_support::detect]
+        /// is `true`, otherwise `text` is returned as is.
+        pub fn wrap_text(maybe_hyperlink: &Option<TuiHyperlink>, text: &str) -> String {
+            match maybe_hyperlink {
+                Some(hyperlink) if global_hyperlink_support::detect() => {
+                    format!("{}{text}{OSC8_END}", hyperlink.get_osc8_start())
+                }
+                _ => text.to_string(),
+            }
+        }
+    }
+
+    impl Serialize for TuiHyperlink {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.get_url().as_deref().unwrap_or_default())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for TuiHyperlink {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let url = String::deserialize(deserializer)?;
+            Ok(Self::new(&url))
+        }
+    }
+
+    impl Debug for TuiHyperlink {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "TuiHyperlink {{ id: {} | url: {:?} }}",
+                self.id,
+                self.get_url().as_deref().unwrap_or_default()
+            )
+        }
+    }
+}
+
+/// Holds the url for each [TuiHyperlink]. Urls can't be removed one by one, since any
+/// number of [TuiStyle](crate::TuiStyle)s (or pixel chars in an offscreen buffer) may
+/// still refer to them. Instead, all of them are removed once there are more than
+/// [MAX_URL_COUNT](global_hyperlink_registry::MAX_URL_COUNT) (eg: after typing many urls
+/// in the editor), and the [get_generation](global_hyperlink_registry::get_generation)
+/// changes. Ids are never reused, so a [TuiHyperlink] from before that has no url, rather
+/// than the wrong one. Anything that holds on to [TuiHyperlink]s (eg: a cache of
+/// highlighted text) should make new ones when the generation changes.
+pub mod global_hyperlink_registry {
+    use super::*;
+
+    pub const MAX_URL_COUNT: usize = 4096;
+
+    #[derive(Default)]
+    pub(super) struct Registry {
+        next_id: u32,
+        generation: usize,
+        urls: HashMap<u32, Arc<str>>,
+        ids: HashMap<Arc<str>, u32>,
+    }
+
+    impl Registry {
+        pub(super) fn intern(&mut self, url: &str) -> u32 {
+            if let Some(id) = self.ids.get(url) {
+                return *id;
+            }
+            if self.urls.len() >= MAX_URL_COUNT {
+                self.urls.clear();
+                self.ids.clear();
+                self.generation += 1;
+            }
+            let id = self.next_id;
+            self.next_id = self.next_id.wrapping_add(1);
+            let url: Arc<str> = Arc::from(url);
+            self.urls.insert(id, url.clone());
+            self.ids.insert(url, id);
+            id
+        }
+
+        pub(super) fn get(&self, id: u32) -> Option<Arc<str>> {
+            self.urls.get(&id).cloned()
+        }
+    }
+
+    static REGISTRY: RwLock<Option<Registry>> = RwLock::new(None);
+
+    pub(super) fn intern(url: &str) -> u32 {
+        if let Ok(guard) = REGISTRY.read() {
+            if let Some(id) = guard.as_ref().and_then(|it| it.ids.get(url)) {
+                return *id;
+            }
+        }
+
+        let mut guard = match REGISTRY.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        guard.get_or_insert_with(Registry::default).intern(url)
+    }
+
+    pub(super) fn get(id: u32) -> Option<Arc<str>> {
+        let guard = REGISTRY.read().ok()?;
+        guard.as_ref()?.get(id)
+    }
+
+    /// This changes every time the registry is cleared.
+    pub fn get_generation() -> usize {
+        match REGISTRY.read() {
+            Ok(guard) => guard.as_ref().map_or(0, |it| it.generation),
+            Err(poisoned) => poisoned.into_inner().as_ref().map_or(0, |it| it.generation),
+        }
+    }
+}
+
+/// Determines whether OSC 8 escape sequences are emitted for [TuiHyperlink]s. Most
+/// terminals that don't support them simply ignore them, so they are emitted unless the
+/// `TERM` environment variable says otherwise. Use
+/// [set_override](global_hyperlink_support::set_override) to change this.
+pub mod global_hyperlink_support {
+    use super::*;
+
+    const NOT_SET_VALUE: i8 = -1;
+
+    static HYPERLINK_SUPPORT_OVERRIDE: AtomicI8 = AtomicI8::new(NOT_SET_VALUE);
+
+    pub fn detect() -> bool {
+        match HYPERLINK_SUPPORT_OVERRIDE.load(Ordering::Acquire) {
+            NOT_SET_VALUE => examine_env_vars_to_determine_hyperlink_support(),
+            value => value == 1,
+        }
+    }
+
+    pub fn set_override(value: bool) {
+        HYPERLINK_SUPPORT_OVERRIDE.store(value as i8, Ordering::Release);
+    }
+
+    pub fn clear_override() {
+        HYPERLINK_SUPPORT_OVERRIDE.store(NOT_SET_VALUE, Ordering::Release);
+    }
+
+    /// The Linux console and dumb terminals print the escape sequences as text.
+    pub fn examine_env_vars_to_determine_hyperlink_support() -> bool {
+        !matches!(std::env::var("TERM").as_deref(), Ok("dumb") | Ok("linux"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_eq2;
+
+    #[test]
+    fn test_same_url_gets_same_id() {
+        let link_1 = TuiHyperlink::new("https://r3bl.com/test_same_url_gets_same_id");
+        let link_2 = TuiHyperlink::new("https://r3bl.com/test_same_url_gets_same_id");
+        let link_3 = TuiHyperlink::new("https://r3bl.com/test_same_url_gets_same_id/2");
+        assert_eq2!(link_1, link_2);
+        assert_ne!(link_1, link_3);
+        assert_eq2!(
+            link_3.get_url().as_deref(),
+            Some("https://r3bl.com/test_same_url_gets_same_id/2")
+        );
+    }
+
+    #[test]
+    fn test_serialize_url() {
+        let link = TuiHyperlink::new("https://r3bl.com/test_serialize_url");
+        let json = serde_json::to_string(&link).unwrap();
+        assert_eq2!(json, "\"https://r3bl.com/test_serialize_url\"");
+        assert_eq2!(serde_json::from_str::<TuiHyperlink>(&json).unwrap(), link);
+    }
+
+    #[test]
+    fn test_registry_is_cleared_when_full() {
+        use global_hyperlink_registry::{Registry, MAX_URL_COUNT};
+
+        let mut registry = Registry::default();
+        let id = registry.intern("https://r3bl.com");
+        assert_eq2!(registry.intern("https://r3bl.com"), id);
+        for it in 1..MAX_URL_COUNT {
+            registry.intern(&format!("https://r3bl.com/{it}"));
+        }
+        assert_eq2!(registry.get(id).as_deref(), Some("https://r3bl.com"));
+
+        // The old id doesn't refer to any url, and the url gets a new id.
+        let new_id = registry.intern("https://r3bl.com/new");
+        assert_eq2!(registry.get(id), None);
+        assert_ne!(registry.intern("https://r3bl.com"), id);
+        assert_eq2!(
+            registry.get(new_id).as_deref(),
+            Some("https://r3bl.com/new")
+        );
+    }
+
+    #[test]
+    fn test_control_chars_are_removed() {
+        let link = TuiHyperlink::new("https://r3bl.com/\x1b]8;;\x07evil");
+        assert_eq2!(link.get_url().as_deref(), Some("https://r3bl.com/]8;;evil"));
+        assert_eq2!(
+            link.get_osc8_start(),
+            format!("\x1b]8;id=r3bl-{};https://r3bl.com/]8;;evil\x1b\\", link.id)
+        );
+    }
+}
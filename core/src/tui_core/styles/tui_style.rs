@@ -80,6 +80,8 @@ pub struct TuiStyle {
     /// [`FlexBox` docs](https://docs.rs/r3bl_rs_utils/latest/r3bl_rs_utils/tui/layout/flex_box/struct.FlexBox.html).
     pub padding: Option<ChUnit>,
     pub lolcat: bool,
+    /// Makes the text painted w/ this style clickable in terminals that support it.
+    pub hyperlink: Option<TuiHyperlink>,
 }
 
 mod addition {
@@ -133,6 +135,9 @@ mod addition {
             if other.strikethrough {
                 new_style.strikethrough = other.strikethrough;
             }
+            if other.hyperlink.is_some() {
+                new_style.hyperlink = other.hyperlink;
+            }
         }
 
         // Aggregate paddings.
@@ -212,6 +217,10 @@ mod style_helpers {
                 msg_vec.push("str".to_string())
             }
 
+            if self.hyperlink.is_some() {
+                msg_vec.push("lnk".to_string())
+            }
+
             if self.color_fg.is_some() {
                 msg_vec.push("fg".to_string())
             }
@@ -268,6 +277,10 @@ mod style_helpers {
                 msg_vec.push("strikethrough".to_string())
             }
 
+            if let Some(hyperlink) = self.hyperlink {
+                msg_vec.push(format!("{hyperlink:?}"))
+            }
+
             write!(
                 f,
                 "Style {{ {} | fg: {:?} | bg: {:?} | padding: {:?} }}",
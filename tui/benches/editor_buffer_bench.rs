@@ -0,0 +1,190 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Benchmarks for editing and scrolling a large file in the editor. Run them w/:
+//! `cargo bench --bench editor_buffer_bench`.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use r3bl_rs_utils_core::*;
+use r3bl_tui::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+               *};
+
+/// About 30MB of text, which is the size of a large log file.
+const LINE_COUNT: usize = 300_000;
+
+fn get_viewport_size() -> Size { size!(col_count: 100, row_count: 40) }
+
+fn make_lines() -> Vec<String> {
+    (0..LINE_COUNT)
+        .map(|it| {
+            format!(
+                "{it:>8} 2024-06-29T12:34:56Z INFO r3bl_tui::editor: 😃 lorem ipsum dolor sit \
+                 amet, consectetur adipiscing elit"
+            )
+        })
+        .collect()
+}
+
+fn make_editor_buffer(lines: &[String]) -> EditorBuffer {
+    let mut editor_buffer =
+        EditorBuffer::new_empty(&Some("log".to_string()), &Some("bench.log".to_string()));
+    editor_buffer.set_lines(lines.to_vec());
+    editor_buffer
+}
+
+fn make_flex_box() -> FlexBox {
+    FlexBox {
+        style_adjusted_bounds_size: get_viewport_size(),
+        ..Default::default()
+    }
+}
+
+fn make_editor_engine() -> EditorEngine {
+    let mut editor_engine = EditorEngine::new(EditorEngineConfig::default());
+    editor_engine.current_box = (&make_flex_box()).into();
+    editor_engine
+}
+
+fn apply_key(
+    editor_buffer: &mut EditorBuffer,
+    editor_engine: &mut EditorEngine,
+    key: Key,
+) {
+    let _ = EditorEngineApi::apply_event(
+        editor_buffer,
+        editor_engine,
+        InputEvent::Keyboard(KeyPress::Plain { key }),
+        &mut TestClipboard::default(),
+    );
+}
+
+fn render(editor_buffer: &mut EditorBuffer, editor_engine: &mut EditorEngine) {
+    let pipeline = EditorEngineApi::render_engine(
+        editor_engine,
+        editor_buffer,
+        make_flex_box(),
+        &mut HasFocus::default(),
+        get_viewport_size(),
+    );
+    black_box(pipeline.ok());
+}
+
+/// Returns a buffer and engine w/ the caret in the middle of the file.
+fn make_editor_at_middle(lines: &[String]) -> (EditorBuffer, EditorEngine) {
+    let mut editor_buffer = make_editor_buffer(lines);
+    let mut editor_engine = make_editor_engine();
+    EditorEngineInternalApi::move_caret_to(
+        &mut editor_buffer,
+        &mut editor_engine,
+        position!(col_index: 9, row_index: LINE_COUNT / 2),
+    );
+    render(&mut editor_buffer, &mut editor_engine);
+    (editor_buffer, editor_engine)
+}
+
+fn bench_load(c: &mut Criterion) {
+    let lines = make_lines();
+    let mut group = c.benchmark_group("load");
+    group.sample_size(10);
+    group.bench_function("set_lines_and_render", |b| {
+        b.iter_batched(
+            || lines.clone(),
+            |lines| {
+                let mut editor_buffer = EditorBuffer::new_empty(
+                    &Some("log".to_string()),
+                    &Some("bench.log".to_string()),
+                );
+                editor_buffer.set_lines(lines);
+                render(&mut editor_buffer, &mut make_editor_engine());
+                editor_buffer
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+fn bench_edit(c: &mut Criterion) {
+    let lines = make_lines();
+    let mut group = c.benchmark_group("edit");
+
+    let (mut editor_buffer, mut editor_engine) = make_editor_at_middle(&lines);
+    group.bench_function("insert_char_and_render", |b| {
+        b.iter(|| {
+            apply_key(&mut editor_buffer, &mut editor_engine, Key::Character('x'));
+            render(&mut editor_buffer, &mut editor_engine);
+        })
+    });
+
+    let (mut editor_buffer, mut editor_engine) = make_editor_at_middle(&lines);
+    group.bench_function("insert_new_line_and_render", |b| {
+        b.iter(|| {
+            apply_key(
+                &mut editor_buffer,
+                &mut editor_engine,
+                Key::SpecialKey(SpecialKey::Enter),
+            );
+            render(&mut editor_buffer, &mut editor_engine);
+        })
+    });
+
+    let (mut editor_buffer, mut editor_engine) = make_editor_at_middle(&lines);
+    group.bench_function("delete_line_and_render", |b| {
+        b.iter(|| {
+            apply_key(
+                &mut editor_buffer,
+                &mut editor_engine,
+                Key::SpecialKey(SpecialKey::Backspace),
+            );
+            render(&mut editor_buffer, &mut editor_engine);
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_scroll(c: &mut Criterion) {
+    let lines = make_lines();
+    let mut group = c.benchmark_group("scroll");
+
+    let mut editor_buffer = make_editor_buffer(&lines);
+    let mut editor_engine = make_editor_engine();
+    group.bench_function("page_down_and_render", |b| {
+        b.iter(|| {
+            // Start over at the top when the end of the file is reached.
+            let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+            if ch!(@to_usize caret.row_index) + 1 >= LINE_COUNT {
+                EditorEngineInternalApi::move_caret_to(
+                    &mut editor_buffer,
+                    &mut editor_engine,
+                    position!(col_index: 0, row_index: 0),
+                );
+            }
+            apply_key(
+                &mut editor_buffer,
+                &mut editor_engine,
+                Key::SpecialKey(SpecialKey::PageDown),
+            );
+            render(&mut editor_buffer, &mut editor_engine);
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_load, bench_edit, bench_scroll);
+criterion_main!(benches);
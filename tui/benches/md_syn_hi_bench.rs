@@ -0,0 +1,103 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Benchmarks for parsing & highlighting a long Markdown note after each edit, using
+//! [try_parse_and_highlight] (the entire document) vs [MdSynHiCache] (only the blocks
+//! that changed). Run them w/: `cargo bench --bench md_syn_hi_bench`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use r3bl_rs_utils_core::*;
+use r3bl_tui::*;
+
+/// Each section is 12 lines, so this is a note that is 6000 lines long.
+const SECTION_COUNT: usize = 500;
+
+/// Row of the line that is edited, which is a line of text in the middle of the note.
+const EDITED_ROW_INDEX: usize = SECTION_COUNT / 2 * 12 + 2;
+
+fn make_lines() -> EditorLines {
+    let mut lines = vec![
+        US::new("@title: Long note"),
+        US::new("@tags: bench, markdown"),
+    ];
+    for it in 0..SECTION_COUNT {
+        lines.extend([
+            US::new(&format!("## Section {it}")),
+            US::new(""),
+            US::new("Some *italic* and **bold** text w/ `code` and a [link](https://r3bl.com)."),
+            US::new("- [ ] todo item"),
+            US::new("- [x] done item"),
+            US::new("1. ordered item"),
+            US::new("```rust"),
+            US::new("fn main() {"),
+            US::new("    println!(\"Hello, world!\");"),
+            US::new("}"),
+            US::new("```"),
+            US::new(""),
+        ]);
+    }
+    lines.into()
+}
+
+/// Type or delete a character at the end of the line of text in the middle of the note.
+fn edit(lines: &mut EditorLines) {
+    if let Some(line) = lines.get_mut(EDITED_ROW_INDEX) {
+        let mut text = line.string.clone();
+        if text.pop() != Some('x') {
+            text = format!("{}x", line.string);
+        }
+        *line = US::new(&text);
+    }
+}
+
+fn bench_edit(c: &mut Criterion) {
+    let editor_engine = EditorEngine::default();
+    let maybe_style = Some(TuiStyle::default());
+    let syntect_tuple = Some((&editor_engine.syntax_set, &editor_engine.theme));
+    let mut group = c.benchmark_group("md_syn_hi");
+    group.sample_size(10);
+
+    let mut lines = make_lines();
+    group.bench_function("full_after_edit", |b| {
+        b.iter(|| {
+            edit(&mut lines);
+            black_box(try_parse_and_highlight(&lines, &maybe_style, syntect_tuple).ok());
+        })
+    });
+
+    let mut lines = make_lines();
+    let mut md_syn_hi_cache = MdSynHiCache::default();
+    md_syn_hi_cache
+        .try_parse_and_highlight(&lines, &maybe_style, syntect_tuple)
+        .ok();
+    group.bench_function("incremental_after_edit", |b| {
+        b.iter(|| {
+            edit(&mut lines);
+            md_syn_hi_cache.invalidate(&lines);
+            black_box(
+                md_syn_hi_cache
+                    .try_parse_and_highlight(&lines, &maybe_style, syntect_tuple)
+                    .ok(),
+            );
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_edit);
+criterion_main!(benches);
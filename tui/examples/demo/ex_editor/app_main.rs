@@ -559,6 +559,11 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            soft_wrap: SoftWrapMode::Disable,
+            smart_list: SmartListMode::Disable,
+            auto_indent: AutoIndentMode::Disable,
+            tab_width: DEFAULT_TAB_WIDTH,
+            auto_pair: AutoPairMode::Disable,
         };
 
         let boxed_dialog_component = {
@@ -646,6 +651,11 @@ mod populate_component_registry {
             multiline_mode: LineMode::SingleLine,
             syntax_highlight: SyntaxHighlightMode::Disable,
             edit_mode: EditMode::ReadWrite,
+            soft_wrap: SoftWrapMode::Disable,
+            smart_list: SmartListMode::Disable,
+            auto_indent: AutoIndentMode::Disable,
+            tab_width: DEFAULT_TAB_WIDTH,
+            auto_pair: AutoPairMode::Disable,
         };
 
         let boxed_dialog_component = {
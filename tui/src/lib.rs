@@ -482,6 +482,11 @@
 //! default input event handler. And if nothing there matches this event, then it is simply
 //! dropped.
 //!
+//! Instead of matching [KeyPress]es directly, the [App] and its components can look them up in
+//! a [Keymap], which binds key sequences (including chords like `Ctrl+K Ctrl+S`) to named
+//! actions. The user can rebind these actions in a keymap file in their config folder, see
+//! [mod@keymap].
+//!
 //! ## Rendering and painting
 //! <a id="markdown-rendering-and-painting" name="rendering-and-painting"></a>
 //!
@@ -598,9 +603,10 @@
 //! | Y     | paint each `PixelChar` in `List<List<PixelChar>>` to stdout using `OffscreenBufferPainterImplCrossterm` |
 //! | Y     | save the `List<List<PixelChar>>` to `GlobalSharedState`                                                 |
 //!
-//! Currently only `crossterm` is supported for actually painting to the terminal. But this process
-//! is really simple making it very easy to swap out other terminal libraries such as `termion`, or
-//! even a GUI backend, or some other custom output driver.
+//! `crossterm` is used to actually paint to the terminal by default. `termion` can be used
+//! instead by enabling the `termion` feature (on Unix). This process is really simple making it
+//! very easy to swap out other terminal libraries, or even a GUI backend, or some other custom
+//! output driver.
 //!
 //! ## Subsequent render
 //! <a id="markdown-subsequent-render" name="subsequent-render"></a>
@@ -765,6 +771,8 @@
 //! ## Custom Markdown (MD) parsing and custom syntax highlighting
 //!
 //! The code for parsing and syntax highlighting is in [try_parse_and_highlight].
+//! The editor uses [MdSynHiCache] to only parse and highlight the blocks that have
+//! changed since the last render.
 //!
 //! A custom Markdown parser is provided to provide some extensions over the standard
 //! Markdown syntax. The parser code is in the [parse_markdown()] function. Here are some
@@ -785,6 +793,17 @@
 //! [EditorEngineApi::render_engine] to syntax highlight the text inside code blocks of
 //! Markdown documents.
 //!
+//! The colors come from the current [MdTheme] (see [global_md_theme]), which can be
+//! loaded from a JSON file.
+//! Links, images and auto links are clickable in terminals that support OSC 8
+//! hyperlinks, since their [r3bl_rs_utils_core::TuiStyle::hyperlink] is set.
+//!
+//! A parsed [MdDocument] can also be rendered outside of the editor, eg: by CLI tools
+//! that print or export notes. [render_md_document_to_ansi] renders it into a word
+//! wrapped string w/ ANSI escape sequences for a given terminal width, and
+//! [render_md_document_to_html] renders it into a standalone HTML document. Both use the
+//! same stylesheet and `syntect` code block highlighting as the editor.
+//!
 //! An alternative approach to doing this was considered using the crate `markdown-rs`, but we
 //! decided to implement our own parser using
 //! [`nom`](https://developerlife.com/2023/02/20/guide-to-nom-parsing/) since it was streaming
@@ -33,6 +33,13 @@ pub struct DialogBuffer {
     pub editor_buffer: EditorBuffer,
     pub title: String,
     pub maybe_results: Option<Vec<String>>,
+    /// For each of the `maybe_results`, the indices of its grapheme clusters that are
+    /// highlighted in the results panel (eg: the ones that match what the user typed).
+    /// The results that don't have an entry here aren't highlighted.
+    pub results_highlights: Vec<Vec<usize>>,
+    /// The index of the result in `maybe_results` that was picked w/ Enter, if any.
+    /// Results can have the same text, so this tells them apart.
+    pub maybe_selected_result_index: Option<usize>,
 }
 
 impl DialogBuffer {
@@ -54,6 +61,8 @@ impl DialogBuffer {
             ),
             title: Default::default(),
             maybe_results: None,
+            results_highlights: Default::default(),
+            maybe_selected_result_index: None,
         }
     }
 }
@@ -65,10 +74,14 @@ impl Debug for DialogBuffer {
           "\nDialogBuffer [      \n\
           ├ title: {}            \n\
           ├ maybe_results: {:?}  \n\
+          ├ results_highlights: {:?}  \n\
+          ├ maybe_selected_result_index: {:?}  \n\
           └ editor_buffer: {}  \n\
           ]",
           self.title,
           maybe_results,
+          self.results_highlights,
+          self.maybe_selected_result_index,
           self.editor_buffer.get_as_string_with_comma_instead_of_newlines()
         }
     }
@@ -21,44 +21,80 @@ use crate::*;
 
 /// By providing a conversion from [InputEvent] to [DialogEvent] it becomes easier to write event
 /// handlers that consume [InputEvent] and then process events in [DialogComponent] and
-/// [DialogEngine].
+/// [DialogEngine]. The key presses for each event can be changed, see [dialog_actions].
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DialogEvent {
+    /// [dialog_actions::ACCEPT], `Enter` by default.
     EnterPressed,
+    /// [dialog_actions::CANCEL], `Esc` by default.
     EscPressed,
+    /// [dialog_actions::SELECT_PREVIOUS], `Up` by default.
+    UpPressed,
+    /// [dialog_actions::SELECT_NEXT], `Down` by default.
+    DownPressed,
     None,
 }
 
+/// The ids of the [KeymapAction]s that the dialog handles, see
+/// [DialogEvent::get_keymap_actions].
+pub mod dialog_actions {
+    pub const SCOPE: &str = "dialog";
+
+    pub const ACCEPT: &str = "dialog.accept";
+    pub const CANCEL: &str = "dialog.cancel";
+    pub const SELECT_PREVIOUS: &str = "dialog.select_previous";
+    pub const SELECT_NEXT: &str = "dialog.select_next";
+}
+
 mod dialog_event_impl {
     use super::*;
 
     impl DialogEvent {
-        /// Tries to convert the given [InputEvent] into a [DialogEvent].
-        /// - Enter and Esc are also matched against to return [DialogEvent::EnterPressed] and
-        ///   [DialogEvent::EscPressed]
-        /// - Otherwise, [Err] is returned.
+        /// Converts the given [InputEvent] into a [DialogEvent] w/ the bindings of a new
+        /// [DialogEvent::new_keymap], so chords can't be matched. [DialogEngineApi] uses
+        /// [DialogEngine::keymap] instead.
+        /// - The key presses bound to [dialog_actions] return the matching event.
+        /// - Otherwise, [DialogEvent::None] is returned.
         pub fn from(input_event: InputEvent) -> Self {
-            if let InputEvent::Keyboard(keypress) = input_event {
-                match keypress {
-                    // Compare to `Enter`.
-                    KeyPress::Plain {
-                        key: Key::SpecialKey(SpecialKey::Enter),
-                    } => {
-                        return Self::EnterPressed;
-                    }
-
-                    // Compare to `Esc`.
-                    KeyPress::Plain {
-                        key: Key::SpecialKey(SpecialKey::Esc),
-                    } => {
-                        return Self::EscPressed;
-                    }
-
-                    _ => {}
-                }
+            match Self::new_keymap().resolve(&input_event) {
+                KeymapResult::Action(action_id) => Self::from_action_id(&action_id),
+                _ => Self::None,
+            }
+        }
+
+        /// Returns [DialogEvent::None] if the action isn't one of [dialog_actions].
+        pub fn from_action_id(action_id: &str) -> Self {
+            match action_id {
+                dialog_actions::ACCEPT => Self::EnterPressed,
+                dialog_actions::CANCEL => Self::EscPressed,
+                dialog_actions::SELECT_PREVIOUS => Self::UpPressed,
+                dialog_actions::SELECT_NEXT => Self::DownPressed,
+                _ => Self::None,
             }
+        }
 
-            Self::None
+        /// The [Keymap] that [DialogEngine::new] starts w/.
+        pub fn new_keymap() -> Keymap {
+            Keymap::new(dialog_actions::SCOPE, &[dialog_actions::SCOPE])
+        }
+
+        /// The actions that are registered in the [global_action_registry] for dialogs,
+        /// w/ their default bindings.
+        pub fn get_keymap_actions() -> Vec<KeymapAction> {
+            vec![
+                KeymapAction::new(dialog_actions::ACCEPT, "Accept", &["Enter"]),
+                KeymapAction::new(dialog_actions::CANCEL, "Cancel", &["Esc"]),
+                KeymapAction::new(
+                    dialog_actions::SELECT_PREVIOUS,
+                    "Select the previous result",
+                    &["Up"],
+                ),
+                KeymapAction::new(
+                    dialog_actions::SELECT_NEXT,
+                    "Select the next result",
+                    &["Down"],
+                ),
+            ]
         }
     }
 }
@@ -82,4 +118,26 @@ mod test_dialog_event {
         let dialog_event = DialogEvent::from(input_event);
         assert_eq2!(dialog_event, DialogEvent::EscPressed);
     }
+
+    #[test]
+    fn dialog_event_handles_up_down() {
+        let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Up));
+        assert_eq2!(DialogEvent::from(input_event), DialogEvent::UpPressed);
+        let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Down));
+        assert_eq2!(DialogEvent::from(input_event), DialogEvent::DownPressed);
+    }
+
+    #[test]
+    fn dialog_event_handles_other_keys() {
+        let input_event = InputEvent::Keyboard(keypress!(@char 'a'));
+        assert_eq2!(DialogEvent::from(input_event), DialogEvent::None);
+    }
+
+    #[test]
+    fn dialog_event_handles_every_action() {
+        for action in DialogEvent::get_keymap_actions() {
+            assert_eq2!(action.default_bindings.len(), 1);
+            assert_ne!(DialogEvent::from_action_id(&action.id), DialogEvent::None);
+        }
+    }
 }
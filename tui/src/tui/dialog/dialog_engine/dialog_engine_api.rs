@@ -159,9 +159,17 @@ impl DialogEngineApi {
         S: Debug + Default + Clone + Sync + Send + HasDialogBuffers,
         AS: Debug + Default + Clone + Sync + Send,
     {
+        let dialog_event = match dialog_engine.keymap.resolve(&input_event) {
+            KeymapResult::Action(action_id) => DialogEvent::from_action_id(&action_id),
+            KeymapResult::Pending | KeymapResult::Cancelled => {
+                return Ok(DialogEngineApplyResponse::Noop);
+            }
+            KeymapResult::NotBound => DialogEvent::None,
+        };
+
         // Was a dialog choice made?
         if let Some(choice) = internal_impl::try_handle_dialog_choice(
-            input_event,
+            &dialog_event,
             mut_state.get_mut_dialog_buffer(self_id),
             dialog_engine,
         ) {
@@ -171,7 +179,7 @@ impl DialogEngineApi {
 
         // Was up / down pressed to select autocomplete results & vert scroll the results panel?
         if let EventPropagation::ConsumedRender = internal_impl::try_handle_up_down(
-            input_event,
+            &dialog_event,
             mut_state.get_mut_dialog_buffer(self_id),
             dialog_engine,
         ) {
@@ -189,6 +197,7 @@ impl DialogEngineApi {
             }
         };
 
+        let old_text = dialog_buffer.editor_buffer.get_as_string_with_newlines();
         let result = EditorEngineApi::apply_event(
             &mut dialog_buffer.editor_buffer,
             &mut dialog_engine.editor_engine,
@@ -199,6 +208,16 @@ impl DialogEngineApi {
         match result {
             // If the editor engine applied the event, return the new editor buffer.
             EditorEngineApplyEventResult::Applied => {
+                // The results are about to change, so go back to the first one.
+                if let DialogEngineMode::ModalAutocomplete =
+                    dialog_engine.dialog_options.mode
+                {
+                    if dialog_buffer.editor_buffer.get_as_string_with_newlines()
+                        != old_text
+                    {
+                        dialog_engine.reset();
+                    }
+                }
                 Ok(DialogEngineApplyResponse::UpdateEditorBuffer)
             }
             _ =>
@@ -452,6 +471,7 @@ mod internal_impl {
                         origin_pos,
                         bounds_size,
                         results,
+                        &dialog_buffer.results_highlights,
                         dialog_engine,
                     );
                 };
@@ -465,6 +485,7 @@ mod internal_impl {
             origin_pos: &Position,
             bounds_size: &Size,
             results: &[String],
+            results_highlights: &[Vec<usize>],
             dialog_engine: &DialogEngine,
         ) {
             let col_start_index = ch!(1);
@@ -479,6 +500,10 @@ mod internal_impl {
 
             // Print results panel.
             for (row_index, item) in results.iter().enumerate() {
+                let highlights: &[usize] = match results_highlights.get(row_index) {
+                    Some(it) => it,
+                    None => &[],
+                };
                 let row_index = ch!(row_index);
 
                 // Skip rows that are above the scroll offset.
@@ -488,25 +513,6 @@ mod internal_impl {
 
                 rel_insertion_pos.add_row(1);
 
-                let text = UnicodeString::from(item.as_str());
-                let max_display_col_count = bounds_size.col_count - 2;
-                let clipped_text = if text.display_width > max_display_col_count {
-                    let snip_len = ch!(2); /* `..` */
-                    let postfix_len = ch!(5); /* last 5 characters */
-
-                    let lhs_start_index = ch!(0);
-                    let lhs_end_index = max_display_col_count - postfix_len - snip_len;
-                    let lhs = text.clip_to_width(lhs_start_index, lhs_end_index);
-
-                    let rhs_start_index = text.display_width - postfix_len;
-                    let rhs_end_index = text.display_width;
-                    let rhs = text.clip_to_width(rhs_start_index, rhs_end_index);
-
-                    format!("{lhs}..{rhs}")
-                } else {
-                    text.string
-                };
-
                 let max_display_row_count =
                     /* Viewport height: */ dialog_engine.dialog_options.result_panel_display_row_count +
                     /* Scroll offset: */ scroll_offset_row_index;
@@ -520,47 +526,109 @@ mod internal_impl {
                     rel_insertion_pos,
                 ));
 
-                // Set style to underline if selected row & paint.
-                match selected_row_index.eq(&row_index) {
+                // Set style to underline if selected row.
+                let maybe_style = dialog_engine.dialog_options.maybe_style_results_panel;
+                let style = match selected_row_index.eq(&row_index) {
                     // This is the selected row.
-                    true => {
-                        let my_selected_style = match dialog_engine
-                            .dialog_options
-                            .maybe_style_results_panel
-                        {
-                            // Update existing style.
-                            Some(style) => TuiStyle {
-                                underline: true,
-                                ..style
-                            },
-                            // No existing style, so create a new style w/ only underline.
-                            _ => TuiStyle {
-                                underline: true,
-                                ..Default::default()
-                            },
-                        }
-                        .into();
-                        // Paint the text for the row.
-                        ops.push(RenderOp::ApplyColors(my_selected_style));
-                        ops.push(RenderOp::PaintTextWithAttributes(
-                            clipped_text,
-                            my_selected_style,
-                        ));
-                    }
+                    true => match maybe_style {
+                        // Update existing style.
+                        Some(style) => TuiStyle {
+                            underline: true,
+                            ..style
+                        },
+                        // No existing style, so create a new style w/ only underline.
+                        _ => TuiStyle {
+                            underline: true,
+                            ..Default::default()
+                        },
+                    },
                     // Regular row, not selected.
-                    false => {
-                        // Paint the text for the row.
-                        ops.push(RenderOp::ApplyColors(
-                            dialog_engine.dialog_options.maybe_style_results_panel,
-                        ));
-                        ops.push(RenderOp::PaintTextWithAttributes(
-                            clipped_text,
-                            dialog_engine.dialog_options.maybe_style_results_panel,
-                        ));
+                    false => maybe_style.unwrap_or_default(),
+                };
+                let highlight_style = match dialog_engine
+                    .dialog_options
+                    .maybe_style_results_panel_highlight
+                {
+                    Some(highlight_style) => style + highlight_style,
+                    None => TuiStyle {
+                        bold: true,
+                        ..style
+                    },
+                };
+
+                // Paint the text for the row.
+                clip_and_style_result(
+                    &UnicodeString::from(item.as_str()),
+                    highlights,
+                    bounds_size.col_count - 2,
+                    style,
+                    highlight_style,
+                )
+                .render_into(ops);
+            }
+        }
+    }
+
+    /// If the `text` is wider than `max_display_col_count`, then its middle is
+    /// replaced w/ `..`. The grapheme clusters whose indices are in `highlights` get
+    /// the `highlight_style`, and the rest get the `style`.
+    pub fn clip_and_style_result(
+        text: &UnicodeString,
+        highlights: &[usize],
+        max_display_col_count: ChUnit,
+        style: TuiStyle,
+        highlight_style: TuiStyle,
+    ) -> TuiStyledTexts {
+        let snip_len = ch!(2); /* `..` */
+        let postfix_len = ch!(5); /* last 5 characters */
+        let is_clipped = text.display_width > max_display_col_count;
+        let lhs_end_col_index = max_display_col_count - postfix_len - snip_len;
+        let rhs_start_col_index = text.display_width - postfix_len;
+
+        let push_run = |styled_texts: &mut TuiStyledTexts,
+                        run: &mut String,
+                        is_run_highlighted: bool| {
+            if run.is_empty() {
+                return;
+            }
+            let style = if is_run_highlighted {
+                highlight_style
+            } else {
+                style
+            };
+            *styled_texts += tui_styled_text! { @style: style, @text: run };
+            run.clear();
+        };
+
+        let mut styled_texts: TuiStyledTexts = Default::default();
+        let mut run = String::new();
+        let mut is_run_highlighted = false;
+        let mut is_snip_painted = false;
+        for segment in text.iter() {
+            let is_in_lhs =
+                segment.display_col_offset + segment.unicode_width <= lhs_end_col_index;
+            let is_in_rhs = segment.display_col_offset >= rhs_start_col_index;
+            if is_clipped && !is_in_lhs && !is_in_rhs {
+                if !is_snip_painted {
+                    if is_run_highlighted {
+                        push_run(&mut styled_texts, &mut run, is_run_highlighted);
+                        is_run_highlighted = false;
                     }
+                    run.push_str("..");
+                    is_snip_painted = true;
                 }
+                continue;
             }
+            let is_highlighted = highlights.contains(&segment.logical_index);
+            if is_highlighted != is_run_highlighted {
+                push_run(&mut styled_texts, &mut run, is_run_highlighted);
+                is_run_highlighted = is_highlighted;
+            }
+            run.push_str(&segment.string);
         }
+        push_run(&mut styled_texts, &mut run, is_run_highlighted);
+
+        styled_texts
     }
 
     pub fn render_title(
@@ -743,14 +811,14 @@ mod internal_impl {
     }
 
     pub fn try_handle_dialog_choice(
-        input_event: InputEvent,
+        dialog_event: &DialogEvent,
         maybe_dialog_buffer: Option<&mut DialogBuffer>,
         dialog_engine: &mut DialogEngine,
     ) -> Option<DialogChoice> {
         // It is safe to unwrap the dialog buffer here (since it will have Some value).
         let dialog_buffer = { maybe_dialog_buffer? };
 
-        match DialogEvent::from(input_event) {
+        match dialog_event {
             // Handle Enter.
             DialogEvent::EnterPressed => match dialog_engine.dialog_options.mode {
                 DialogEngineMode::ModalSimple => {
@@ -764,15 +832,20 @@ mod internal_impl {
                     let selected_index = ch!(@to_usize dialog_engine.selected_row_index);
                     if let Some(results) = &dialog_buffer.maybe_results {
                         if let Some(selected_result) = results.get(selected_index) {
-                            return Some(DialogChoice::Yes(selected_result.clone()));
+                            let choice = DialogChoice::Yes(selected_result.clone());
+                            dialog_buffer.maybe_selected_result_index =
+                                Some(selected_index);
+                            return Some(choice);
                         }
                     }
+                    dialog_buffer.maybe_selected_result_index = None;
                     return Some(DialogChoice::No);
                 }
             },
 
             // Handle Esc.
             DialogEvent::EscPressed => {
+                dialog_buffer.maybe_selected_result_index = None;
                 return Some(DialogChoice::No);
             }
             _ => {}
@@ -781,7 +854,7 @@ mod internal_impl {
     }
 
     pub fn try_handle_up_down(
-        input_event: InputEvent,
+        dialog_event: &DialogEvent,
         maybe_dialog_buffer: Option<&mut DialogBuffer>,
         dialog_engine: &mut DialogEngine,
     ) -> EventPropagation {
@@ -795,9 +868,7 @@ mod internal_impl {
         };
 
         // Handle up arrow?
-        if input_event.matches(&[InputEvent::Keyboard(KeyPress::Plain {
-            key: Key::SpecialKey(SpecialKey::Up),
-        })]) {
+        if let DialogEvent::UpPressed = dialog_event {
             if dialog_engine.selected_row_index > ch!(0) {
                 dialog_engine.selected_row_index -= 1;
             }
@@ -810,9 +881,7 @@ mod internal_impl {
         }
 
         // Handle down arrow?
-        if input_event.matches(&[InputEvent::Keyboard(KeyPress::Plain {
-            key: Key::SpecialKey(SpecialKey::Down),
-        })]) {
+        if let DialogEvent::DownPressed = dialog_event {
             let max_abs_row_index = dialog_buffer.get_results_count() - ch!(1);
 
             let results_panel_viewport_height_row_count =
@@ -883,6 +952,94 @@ mod test_dialog_engine_api_render_engine {
     }
 }
 
+#[cfg(test)]
+mod test_dialog_engine_api_render_results_panel {
+    use r3bl_rs_utils_core::*;
+
+    use crate::{dialog_engine_api::internal_impl, *};
+
+    fn get_texts_and_is_highlighted(
+        styled_texts: &TuiStyledTexts,
+        highlight_style: TuiStyle,
+    ) -> Vec<(String, bool)> {
+        styled_texts
+            .iter()
+            .map(|it| {
+                (
+                    it.get_text().string.clone(),
+                    *it.get_style() == highlight_style,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn clip_and_style_result_highlights() {
+        let style = TuiStyle::default();
+        let highlight_style = TuiStyle {
+            bold: true,
+            ..Default::default()
+        };
+
+        let styled_texts = internal_impl::clip_and_style_result(
+            &UnicodeString::from("src/main.rs"),
+            &[0, 4, 5],
+            ch!(60),
+            style,
+            highlight_style,
+        );
+        assert_eq2!(
+            get_texts_and_is_highlighted(&styled_texts, highlight_style),
+            vec![
+                ("s".to_string(), true),
+                ("rc/".to_string(), false),
+                ("ma".to_string(), true),
+                ("in.rs".to_string(), false),
+            ]
+        );
+
+        // No highlights.
+        let styled_texts = internal_impl::clip_and_style_result(
+            &UnicodeString::from("src/main.rs"),
+            &[],
+            ch!(60),
+            style,
+            highlight_style,
+        );
+        assert_eq2!(
+            get_texts_and_is_highlighted(&styled_texts, highlight_style),
+            vec![("src/main.rs".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn clip_and_style_result_clipped() {
+        let style = TuiStyle::default();
+        let highlight_style = TuiStyle {
+            bold: true,
+            ..Default::default()
+        };
+
+        // The highlighted grapheme clusters that are snipped out aren't painted.
+        let styled_texts = internal_impl::clip_and_style_result(
+            &UnicodeString::from("abcdefghijklmnopqrstuvwxyz"),
+            &[1, 10, 25],
+            ch!(12),
+            style,
+            highlight_style,
+        );
+        assert_eq2!(
+            get_texts_and_is_highlighted(&styled_texts, highlight_style),
+            vec![
+                ("a".to_string(), false),
+                ("b".to_string(), true),
+                ("cde..vwxy".to_string(), false),
+                ("z".to_string(), true),
+            ]
+        );
+    }
+}
+
 #[cfg(test)]
 mod test_dialog_api_make_flex_box_for_dialog {
     use std::error::Error;
@@ -1100,6 +1257,112 @@ mod test_dialog_engine_api_apply_event {
         ));
     }
 
+    #[test]
+    fn apply_event_autocomplete_enter_records_selected_result_index() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.mode = DialogEngineMode::ModalAutocomplete;
+        let state = &mut mock_real_objects_for_dialog::create_state();
+        let dialog_buffer = state.get_mut_dialog_buffer(self_id).unwrap();
+        dialog_buffer.maybe_results = Some(vec!["same".to_string(), "same".to_string()]);
+
+        // The results have the same text, so only the index tells them apart.
+        dialog_engine.selected_row_index = ch!(1);
+        let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Enter));
+        let response = DialogEngineApi::apply_event::<
+            mock_real_objects_for_dialog::State,
+            (),
+        >(state, self_id, dialog_engine, input_event)
+        .unwrap();
+        assert!(matches!(
+            response,
+            DialogEngineApplyResponse::DialogChoice(DialogChoice::Yes(_))
+        ));
+        let dialog_buffer = state.get_mut_dialog_buffer(self_id).unwrap();
+        assert_eq2!(dialog_buffer.maybe_selected_result_index, Some(1));
+
+        let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Esc));
+        DialogEngineApi::apply_event::<mock_real_objects_for_dialog::State, ()>(
+            state,
+            self_id,
+            dialog_engine,
+            input_event,
+        )
+        .unwrap();
+        let dialog_buffer = state.get_mut_dialog_buffer(self_id).unwrap();
+        assert_eq2!(dialog_buffer.maybe_selected_result_index, None);
+    }
+
+    #[test]
+    fn apply_event_rebound_accept() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.keymap.set_bindings(
+            dialog_actions::ACCEPT,
+            vec![KeySequence::try_parse("Alt+Enter").unwrap()],
+        );
+        let state = &mut mock_real_objects_for_dialog::create_state();
+
+        // Enter isn't bound to accept anymore, so it goes to the editor instead.
+        let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Enter));
+        let response = DialogEngineApi::apply_event::<
+            mock_real_objects_for_dialog::State,
+            (),
+        >(state, self_id, dialog_engine, input_event)
+        .unwrap();
+        assert!(!matches!(
+            response,
+            DialogEngineApplyResponse::DialogChoice(_)
+        ));
+
+        let input_event = InputEvent::Keyboard(
+            keypress!(@special ModifierKeysMask::new().with_alt(), SpecialKey::Enter),
+        );
+        let response = DialogEngineApi::apply_event::<
+            mock_real_objects_for_dialog::State,
+            (),
+        >(state, self_id, dialog_engine, input_event)
+        .unwrap();
+        assert!(matches!(
+            response,
+            DialogEngineApplyResponse::DialogChoice(DialogChoice::Yes(_))
+        ));
+    }
+
+    #[test]
+    fn apply_event_autocomplete_resets_selection_when_text_changes() {
+        let self_id: FlexBoxId = FlexBoxId::from(0);
+        let dialog_engine = &mut mock_real_objects_for_dialog::make_dialog_engine();
+        dialog_engine.dialog_options.mode = DialogEngineMode::ModalAutocomplete;
+        let state = &mut mock_real_objects_for_dialog::create_state();
+
+        // Typing changes the results, so the first one is selected.
+        dialog_engine.selected_row_index = ch!(2);
+        dialog_engine.scroll_offset_row_index = ch!(1);
+        let input_event = InputEvent::Keyboard(keypress!(@char 'a'));
+        DialogEngineApi::apply_event::<mock_real_objects_for_dialog::State, ()>(
+            state,
+            self_id,
+            dialog_engine,
+            input_event,
+        )
+        .unwrap();
+        assert_eq2!(dialog_engine.selected_row_index, ch!(0));
+        assert_eq2!(dialog_engine.scroll_offset_row_index, ch!(0));
+
+        // Moving the caret doesn't.
+        dialog_engine.selected_row_index = ch!(2);
+        let input_event = InputEvent::Keyboard(keypress!(@special SpecialKey::Left));
+        DialogEngineApi::apply_event::<mock_real_objects_for_dialog::State, ()>(
+            state,
+            self_id,
+            dialog_engine,
+            input_event,
+        )
+        .unwrap();
+        assert_eq2!(dialog_engine.selected_row_index, ch!(2));
+    }
+
     #[test]
     fn apply_event_other_key() {
         let self_id: FlexBoxId = FlexBoxId::from(0);
@@ -31,7 +31,7 @@ use crate::*;
 /// In order to change the document, you can use the
 /// [DialogEngineApi::apply_event](DialogEngineApi::apply_event) method which takes [InputEvent] and
 /// tries to execute it against this buffer.
-#[derive(Clone, Default, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DialogEngine {
     pub dialog_options: DialogEngineConfigOptions,
     pub editor_engine: EditorEngine,
@@ -57,6 +57,25 @@ pub struct DialogEngine {
     pub maybe_surface_bounds: Option<SurfaceBounds>,
     pub selected_row_index: ChUnit,
     pub scroll_offset_row_index: ChUnit,
+    /// Turns key presses into [DialogEvent]s. The key presses that aren't bound to a
+    /// dialog action are passed to the [EditorEngine::keymap] of `editor_engine`.
+    #[serde(skip, default = "DialogEvent::new_keymap")]
+    pub keymap: Keymap,
+}
+
+impl Default for DialogEngine {
+    fn default() -> Self {
+        Self {
+            dialog_options: Default::default(),
+            editor_engine: Default::default(),
+            color_wheel: Default::default(),
+            maybe_flex_box: None,
+            maybe_surface_bounds: None,
+            selected_row_index: ch!(0),
+            scroll_offset_row_index: ch!(0),
+            keymap: DialogEvent::new_keymap(),
+        }
+    }
 }
 
 impl DialogEngine {
@@ -115,6 +134,9 @@ pub struct DialogEngineConfigOptions {
     pub maybe_style_title: Option<TuiStyle>,
     pub maybe_style_editor: Option<TuiStyle>,
     pub maybe_style_results_panel: Option<TuiStyle>,
+    /// Added to the style of a result for its grapheme clusters that are highlighted (see
+    /// [DialogBuffer::results_highlights]). If this is `None`, they are made bold.
+    pub maybe_style_results_panel_highlight: Option<TuiStyle>,
 }
 
 mod dialog_engine_config_options_impl {
@@ -131,6 +153,7 @@ mod dialog_engine_config_options_impl {
                 maybe_style_editor: None,
                 maybe_style_title: None,
                 maybe_style_results_panel: None,
+                maybe_style_results_panel_highlight: None,
             }
         }
     }
@@ -33,6 +33,8 @@ pub mod mock_real_objects_for_dialog {
             window_size,
             maybe_saved_offscreen_buffer,
             main_thread_channel_sender,
+            output_device: Default::default(),
+            maybe_recorded_frames: None,
         }
     }
 
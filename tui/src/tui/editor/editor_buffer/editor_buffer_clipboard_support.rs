@@ -18,7 +18,7 @@
 use std::error::Error;
 
 use crossterm::style::Stylize;
-use r3bl_rs_utils_core::{call_if_true, ch, log_debug, UnicodeString};
+use r3bl_rs_utils_core::{call_if_true, ch, log_debug};
 
 use super::*;
 use crate::*;
@@ -39,7 +39,7 @@ pub fn copy_to_clipboard(
     buffer: &EditorBuffer,
     clipboard_service_provider: &mut impl ClipboardService,
 ) {
-    let lines: &Vec<UnicodeString> = buffer.get_lines();
+    let lines: &EditorLines = buffer.get_lines();
     let selection_map = buffer.get_selection_map();
 
     // Initialize an empty string to store the copied text.
@@ -0,0 +1,301 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::{Display, Formatter};
+
+use get_size::GetSize;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// The UTF-8 byte order mark, which some (mostly Windows) programs put at the start of a
+/// file.
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// How the text of an [EditorBuffer] is stored in its file, apart from the lines
+/// themselves. It is detected when the file is loaded (see
+/// [EditorBuffer::set_lines_from_file_bytes]), so that saving the buffer (see
+/// [EditorBuffer::get_as_file_bytes]) doesn't change any of it.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, GetSize, Debug)]
+pub struct FileFormat {
+    pub line_ending: LineEnding,
+    /// The last line ends w/ a line ending.
+    pub has_final_newline: bool,
+    /// The file starts w/ the [UTF8_BOM].
+    pub has_bom: bool,
+    /// The file isn't valid UTF-8, so the bytes that aren't are shown as `�`. Saving the
+    /// buffer would replace them in the file, so it is read only (see
+    /// [EditorBuffer::is_read_only]).
+    pub is_lossy: bool,
+}
+
+/// `\n` line endings and a final newline, w/out a BOM, which is what a new file gets.
+impl Default for FileFormat {
+    fn default() -> Self {
+        Self {
+            line_ending: LineEnding::Lf,
+            has_final_newline: true,
+            has_bom: false,
+            is_lossy: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, GetSize, Debug, Default)]
+pub enum LineEnding {
+    /// `\n`, used on Linux and macOS.
+    #[default]
+    Lf,
+    /// `\r\n`, used on Windows.
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+
+    /// The other one, to convert the line endings of a file.
+    pub fn toggle(&self) -> Self {
+        match self {
+            LineEnding::Lf => LineEnding::CrLf,
+            LineEnding::CrLf => LineEnding::Lf,
+        }
+    }
+}
+
+impl Display for LineEnding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineEnding::Lf => write!(f, "LF"),
+            LineEnding::CrLf => write!(f, "CRLF"),
+        }
+    }
+}
+
+mod file_format_impl {
+    use super::*;
+
+    impl FileFormat {
+        /// Split the content of a file into lines, and detect its format. The line
+        /// ending of the first line is used for the whole file:
+        /// - If it is `\r\n`, then the `\r` at the end of every line is removed, so the
+        ///   lines that only end in `\n` are saved w/ `\r\n`.
+        /// - Otherwise the lines are only split at `\n`, so a `\r` is kept as it is.
+        pub fn decode(bytes: &[u8]) -> (Self, Vec<String>) {
+            let (has_bom, bytes) = match bytes.strip_prefix(UTF8_BOM) {
+                Some(rest) => (true, rest),
+                None => (false, bytes),
+            };
+
+            // This only allocates if there are bytes that aren't valid UTF-8.
+            let text = String::from_utf8_lossy(bytes);
+            let is_lossy = matches!(text, std::borrow::Cow::Owned(_));
+
+            if text.is_empty() {
+                let it = Self {
+                    has_bom,
+                    is_lossy,
+                    ..Default::default()
+                };
+                return (it, vec![]);
+            }
+
+            let line_ending = match text.find('\n') {
+                Some(index) if text[..index].ends_with('\r') => LineEnding::CrLf,
+                _ => LineEnding::Lf,
+            };
+            let has_final_newline = text.ends_with('\n');
+
+            let text = text.strip_suffix('\n').unwrap_or(&text);
+            let lines = text
+                .split('\n')
+                .map(|line| match line_ending {
+                    LineEnding::CrLf => line.strip_suffix('\r').unwrap_or(line),
+                    LineEnding::Lf => line,
+                })
+                .map(String::from)
+                .collect();
+
+            let it = Self {
+                line_ending,
+                has_final_newline,
+                has_bom,
+                is_lossy,
+            };
+            (it, lines)
+        }
+
+        /// Join the `lines` back into the content of a file, in this format.
+        pub fn encode(&self, lines: &EditorLines) -> Vec<u8> {
+            let line_ending = self.line_ending.as_str().as_bytes();
+
+            let mut bytes = vec![];
+            if self.has_bom {
+                bytes.extend_from_slice(UTF8_BOM);
+            }
+            for (index, line) in lines.iter_str().enumerate() {
+                if index > 0 {
+                    bytes.extend_from_slice(line_ending);
+                }
+                bytes.extend_from_slice(line.as_bytes());
+            }
+            if self.has_final_newline && !lines.is_empty() {
+                bytes.extend_from_slice(line_ending);
+            }
+            bytes
+        }
+    }
+
+    impl EditorBuffer {
+        /// Replace the lines w/ the content of a file (see [FileFormat::decode]), and
+        /// remember its format, so that [get_as_file_bytes](Self::get_as_file_bytes)
+        /// keeps it.
+        pub fn set_lines_from_file_bytes(&mut self, bytes: &[u8]) {
+            let (file_format, lines) = FileFormat::decode(bytes);
+            self.editor_content.file_format = file_format;
+            self.set_lines(lines);
+        }
+
+        /// The content to save to the file, in its [FileFormat].
+        pub fn get_as_file_bytes(&self) -> Vec<u8> {
+            self.editor_content.file_format.encode(self.get_lines())
+        }
+
+        pub fn get_file_format(&self) -> FileFormat { self.editor_content.file_format }
+
+        /// Change the line endings that the buffer is saved w/. The lines themselves
+        /// don't change.
+        pub fn set_line_ending(&mut self, line_ending: LineEnding) {
+            self.editor_content.file_format.line_ending = line_ending;
+        }
+
+        /// Returns true if the content of the buffer can't be changed, since its file
+        /// isn't valid UTF-8 (see [FileFormat::is_lossy]). Only the [EditorEvent]s that
+        /// are allowed in [EditMode::ReadOnly] are applied to it.
+        pub fn is_read_only(&self) -> bool { self.editor_content.file_format.is_lossy }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+
+    fn round_trip(bytes: &[u8]) -> (FileFormat, Vec<String>, Vec<u8>) {
+        let mut buffer = EditorBuffer::new_empty(&None, &None);
+        buffer.set_lines_from_file_bytes(bytes);
+        let lines = buffer
+            .get_lines()
+            .iter_str()
+            .map(|it| it.to_string())
+            .collect();
+        (buffer.get_file_format(), lines, buffer.get_as_file_bytes())
+    }
+
+    #[test]
+    fn test_lf() {
+        let (file_format, lines, bytes) = round_trip(b"one\ntwo\n");
+        assert_eq2!(file_format, FileFormat::default());
+        assert_eq2!(lines, vec!["one", "two"]);
+        assert_eq2!(bytes, b"one\ntwo\n");
+
+        // A `\r` isn't part of the line ending in an LF file.
+        let (file_format, lines, bytes) = round_trip(b"one\ntwo\r\n");
+        assert_eq2!(file_format.line_ending, LineEnding::Lf);
+        assert_eq2!(lines, vec!["one", "two\r"]);
+        assert_eq2!(bytes, b"one\ntwo\r\n");
+    }
+
+    #[test]
+    fn test_crlf() {
+        let (file_format, lines, bytes) = round_trip(b"one\r\ntwo\r\n\r\n");
+        assert_eq2!(
+            file_format,
+            FileFormat {
+                line_ending: LineEnding::CrLf,
+                ..Default::default()
+            }
+        );
+        assert_eq2!(lines, vec!["one", "two", ""]);
+        assert_eq2!(bytes, b"one\r\ntwo\r\n\r\n");
+
+        // Lines that only end in `\n` are converted.
+        let (_, lines, bytes) = round_trip(b"one\r\ntwo\nthree");
+        assert_eq2!(lines, vec!["one", "two", "three"]);
+        assert_eq2!(bytes, b"one\r\ntwo\r\nthree");
+    }
+
+    #[test]
+    fn test_final_newline() {
+        let (file_format, lines, bytes) = round_trip(b"one\ntwo");
+        assert!(!file_format.has_final_newline);
+        assert_eq2!(lines, vec!["one", "two"]);
+        assert_eq2!(bytes, b"one\ntwo");
+
+        let (file_format, lines, bytes) = round_trip(b"\n");
+        assert!(file_format.has_final_newline);
+        assert_eq2!(lines, vec![""]);
+        assert_eq2!(bytes, b"\n");
+
+        let (file_format, lines, bytes) = round_trip(b"");
+        assert_eq2!(file_format, FileFormat::default());
+        assert_eq2!(lines, Vec::<String>::new());
+        assert_eq2!(bytes, b"");
+    }
+
+    #[test]
+    fn test_bom() {
+        let (file_format, lines, bytes) = round_trip(b"\xEF\xBB\xBFone\r\ntwo");
+        assert!(file_format.has_bom);
+        assert_eq2!(file_format.line_ending, LineEnding::CrLf);
+        assert_eq2!(lines, vec!["one", "two"]);
+        assert_eq2!(bytes, b"\xEF\xBB\xBFone\r\ntwo");
+
+        let (file_format, lines, bytes) = round_trip(b"\xEF\xBB\xBF");
+        assert!(file_format.has_bom);
+        assert_eq2!(lines, Vec::<String>::new());
+        assert_eq2!(bytes, b"\xEF\xBB\xBF");
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut buffer = EditorBuffer::new_empty(&None, &None);
+        assert!(!buffer.is_read_only());
+
+        buffer.set_lines_from_file_bytes(b"caf\xE9\nok\n");
+        assert!(buffer.is_read_only());
+        assert!(buffer.get_file_format().is_lossy);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "caf\u{FFFD}\nok");
+    }
+
+    #[test]
+    fn test_set_line_ending() {
+        let mut buffer = EditorBuffer::new_empty(&None, &None);
+        buffer.set_lines_from_file_bytes(b"one\ntwo\n");
+        buffer.set_line_ending(LineEnding::Lf.toggle());
+        assert_eq2!(buffer.get_as_file_bytes(), b"one\r\ntwo\r\n");
+        assert_eq2!(buffer.get_file_format().line_ending.to_string(), "CRLF");
+
+        buffer.set_line_ending(LineEnding::CrLf.toggle());
+        assert_eq2!(buffer.get_as_file_bytes(), b"one\ntwo\n");
+    }
+}
@@ -0,0 +1,670 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::{Debug, Formatter, Result},
+          ops::{Index, IndexMut, Range},
+          sync::{Arc, OnceLock}};
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A chunk is split when it grows past this many lines.
+pub const EDITOR_LINES_CHUNK_MAX_LEN: usize = 1024;
+
+/// Chunks are filled up to this many lines when they are created, so that there is room
+/// to insert lines before they have to be split.
+pub const EDITOR_LINES_CHUNK_FILL_LEN: usize = EDITOR_LINES_CHUNK_MAX_LEN / 2;
+
+/// An edit that leaves a chunk w/ fewer lines than this merges it w/ its neighbors.
+pub const EDITOR_LINES_CHUNK_MIN_LEN: usize = EDITOR_LINES_CHUNK_MAX_LEN / 8;
+
+/// The lines of an [crate::EditorBuffer]. This has (most of) the API of a
+/// `Vec<UnicodeString>`, but it is built to handle large files.
+///
+/// 1. The lines are stored in chunks of up to [EDITOR_LINES_CHUNK_MAX_LEN] lines, so
+///    inserting or removing a line only moves the lines in its chunk (and not every line
+///    after it).
+/// 2. Chunks are reference counted, and copied on write. So cloning this (eg: to keep a
+///    snapshot for the undo history, or to hand the lines to a background task) is cheap,
+///    and the clone shares the memory of every chunk that neither of them changes.
+///    [EditorLines::get_common_prefix_len] and [EditorLines::get_common_suffix_len] skip
+///    over these shared chunks when comparing two snapshots.
+/// 3. A line is stored as plain text until its grapheme cluster metadata (the
+///    [UnicodeString]) is needed, eg: when it is rendered, or the caret moves on it. Use
+///    [EditorLines::get_str] and [EditorLines::iter_str] when only the text is needed, so
+///    that opening a large file doesn't segment every line in it.
+#[derive(Clone, Default)]
+pub struct EditorLines {
+    chunks: Vec<Arc<Vec<EditorLine>>>,
+    /// Row index of the first line of each chunk.
+    chunk_start_row_indices: Vec<usize>,
+    len: usize,
+}
+
+/// A single line in [EditorLines]. Its [UnicodeString] is computed from `maybe_text` the
+/// first time that it is accessed. Only the text of a line that is changed is dropped,
+/// since this can't be done through a shared reference w/out a lock. Segmented lines
+/// are the ones that are displayed or edited, so this is a small fraction of a large
+/// file.
+#[derive(Default)]
+struct EditorLine {
+    maybe_text: Option<Arc<str>>,
+    unicode_string: OnceLock<UnicodeString>,
+}
+
+impl EditorLine {
+    fn as_str(&self) -> &str {
+        match (self.unicode_string.get(), &self.maybe_text) {
+            (Some(it), _) => &it.string,
+            (None, Some(text)) => text,
+            (None, None) => "",
+        }
+    }
+
+    fn get(&self) -> &UnicodeString {
+        self.unicode_string.get_or_init(|| {
+            UnicodeString::from(self.maybe_text.as_deref().unwrap_or_default())
+        })
+    }
+
+    fn get_mut(&mut self) -> &mut UnicodeString {
+        self.get();
+        // The text is about to change, so it isn't needed anymore.
+        self.maybe_text = None;
+        // Safe to unwrap, since `get()` initializes it.
+        self.unicode_string.get_mut().unwrap()
+    }
+
+    fn into_unicode_string(mut self) -> UnicodeString {
+        self.get();
+        self.unicode_string.take().unwrap_or_default()
+    }
+}
+
+impl Clone for EditorLine {
+    fn clone(&self) -> Self {
+        match (self.unicode_string.get(), &self.maybe_text) {
+            (Some(it), _) => Self::from(it.clone()),
+            (None, maybe_text) => Self {
+                maybe_text: maybe_text.clone(),
+                unicode_string: OnceLock::new(),
+            },
+        }
+    }
+}
+
+impl From<String> for EditorLine {
+    fn from(text: String) -> Self {
+        Self {
+            maybe_text: Some(Arc::from(text)),
+            unicode_string: OnceLock::new(),
+        }
+    }
+}
+
+impl From<UnicodeString> for EditorLine {
+    fn from(unicode_string: UnicodeString) -> Self {
+        Self {
+            maybe_text: None,
+            unicode_string: OnceLock::from(unicode_string),
+        }
+    }
+}
+
+/// Split `lines` into chunks of [EDITOR_LINES_CHUNK_FILL_LEN] lines (unless they fit in
+/// a single chunk).
+fn make_chunks(lines: Vec<EditorLine>) -> Vec<Arc<Vec<EditorLine>>> {
+    if lines.is_empty() {
+        return vec![];
+    }
+    if lines.len() <= EDITOR_LINES_CHUNK_MAX_LEN {
+        return vec![Arc::new(lines)];
+    }
+
+    let mut chunks = vec![];
+    let mut lines = lines.into_iter().peekable();
+    while lines.peek().is_some() {
+        let chunk = lines.by_ref().take(EDITOR_LINES_CHUNK_FILL_LEN).collect();
+        chunks.push(Arc::new(chunk));
+    }
+    chunks
+}
+
+/// Unwrap the lines of `chunk`, or copy them if the chunk is shared.
+fn take_chunk(chunk: Arc<Vec<EditorLine>>) -> Vec<EditorLine> {
+    Arc::try_unwrap(chunk).unwrap_or_else(|it| (*it).clone())
+}
+
+mod construct {
+    use super::*;
+
+    impl EditorLines {
+        pub fn new() -> Self { Self::default() }
+
+        pub(super) fn from_editor_lines(lines: Vec<EditorLine>) -> Self {
+            let mut it = Self {
+                chunks: make_chunks(lines),
+                ..Default::default()
+            };
+            it.update_chunk_start_row_indices();
+            it
+        }
+
+        pub(super) fn update_chunk_start_row_indices(&mut self) {
+            self.chunk_start_row_indices.clear();
+            let mut row_index = 0;
+            for chunk in &self.chunks {
+                self.chunk_start_row_indices.push(row_index);
+                row_index += chunk.len();
+            }
+            self.len = row_index;
+        }
+    }
+
+    impl FromIterator<UnicodeString> for EditorLines {
+        fn from_iter<T: IntoIterator<Item = UnicodeString>>(iter: T) -> Self {
+            Self::from_editor_lines(iter.into_iter().map(EditorLine::from).collect())
+        }
+    }
+
+    /// The lines are not segmented into grapheme clusters until they are accessed.
+    impl FromIterator<String> for EditorLines {
+        fn from_iter<T: IntoIterator<Item = String>>(iter: T) -> Self {
+            Self::from_editor_lines(iter.into_iter().map(EditorLine::from).collect())
+        }
+    }
+
+    impl From<Vec<UnicodeString>> for EditorLines {
+        fn from(lines: Vec<UnicodeString>) -> Self { lines.into_iter().collect() }
+    }
+
+    impl From<Vec<String>> for EditorLines {
+        fn from(lines: Vec<String>) -> Self { lines.into_iter().collect() }
+    }
+
+    impl From<Vec<&str>> for EditorLines {
+        fn from(lines: Vec<&str>) -> Self {
+            lines.into_iter().map(String::from).collect()
+        }
+    }
+}
+
+mod access {
+    use super::*;
+
+    impl EditorLines {
+        pub fn len(&self) -> usize { self.len }
+
+        pub fn is_empty(&self) -> bool { self.len == 0 }
+
+        /// Returns the index of the chunk that holds the line at `row_index`, and the
+        /// index of the line in that chunk.
+        pub(super) fn locate(&self, row_index: usize) -> Option<(usize, usize)> {
+            if row_index >= self.len {
+                return None;
+            }
+            let chunk_index = self
+                .chunk_start_row_indices
+                .partition_point(|start_row_index| *start_row_index <= row_index)
+                - 1;
+            Some((
+                chunk_index,
+                row_index - self.chunk_start_row_indices[chunk_index],
+            ))
+        }
+
+        pub(super) fn get_line(&self, row_index: usize) -> Option<&EditorLine> {
+            let (chunk_index, offset) = self.locate(row_index)?;
+            self.chunks[chunk_index].get(offset)
+        }
+
+        pub fn get(&self, row_index: usize) -> Option<&UnicodeString> {
+            self.get_line(row_index).map(EditorLine::get)
+        }
+
+        /// Returns the text of the line at `row_index`, w/out computing its grapheme
+        /// cluster metadata.
+        pub fn get_str(&self, row_index: usize) -> Option<&str> {
+            self.get_line(row_index).map(EditorLine::as_str)
+        }
+
+        pub fn get_mut(&mut self, row_index: usize) -> Option<&mut UnicodeString> {
+            let (chunk_index, offset) = self.locate(row_index)?;
+            Arc::make_mut(&mut self.chunks[chunk_index])
+                .get_mut(offset)
+                .map(EditorLine::get_mut)
+        }
+
+        pub fn first(&self) -> Option<&UnicodeString> { self.get(0) }
+
+        pub fn last(&self) -> Option<&UnicodeString> {
+            self.get(self.len.checked_sub(1)?)
+        }
+
+        pub fn iter(&self) -> EditorLinesIter<'_, &UnicodeString> {
+            EditorLinesIter::new(self, EditorLine::get)
+        }
+
+        /// Iterate over the text of the lines, w/out computing their grapheme cluster
+        /// metadata.
+        pub fn iter_str(&self) -> EditorLinesIter<'_, &str> {
+            EditorLinesIter::new(self, EditorLine::as_str)
+        }
+
+        pub fn to_strings(&self) -> Vec<String> {
+            self.iter_str().map(|it| it.to_string()).collect()
+        }
+
+        /// Returns the number of lines that are the same at the start of `self` and
+        /// `other`. Chunks that they share are not compared line by line.
+        pub fn get_common_prefix_len(&self, other: &EditorLines) -> usize {
+            let mut row_index = 0;
+            for (lhs, rhs) in self.chunks.iter().zip(other.chunks.iter()) {
+                if !Arc::ptr_eq(lhs, rhs) {
+                    break;
+                }
+                row_index += lhs.len();
+            }
+
+            let max_len = std::cmp::min(self.len, other.len);
+            row_index
+                + (row_index..max_len)
+                    .take_while(|it| self.get_str(*it) == other.get_str(*it))
+                    .count()
+        }
+
+        /// Returns the number of lines that are the same at the end of `self` and
+        /// `other`, up to `max_len`. Chunks that they share are not compared line by line.
+        pub fn get_common_suffix_len(
+            &self,
+            other: &EditorLines,
+            max_len: usize,
+        ) -> usize {
+            let max_len = std::cmp::min(max_len, std::cmp::min(self.len, other.len));
+
+            let mut count = 0;
+            for (lhs, rhs) in self.chunks.iter().rev().zip(other.chunks.iter().rev()) {
+                if !Arc::ptr_eq(lhs, rhs) || count + lhs.len() > max_len {
+                    break;
+                }
+                count += lhs.len();
+            }
+
+            count
+                + (count..max_len)
+                    .take_while(|it| {
+                        self.get_str(self.len - 1 - *it)
+                            == other.get_str(other.len - 1 - *it)
+                    })
+                    .count()
+        }
+    }
+
+    impl Index<usize> for EditorLines {
+        type Output = UnicodeString;
+
+        fn index(&self, row_index: usize) -> &Self::Output {
+            match self.get(row_index) {
+                Some(it) => it,
+                None => panic!(
+                    "index {row_index} out of bounds for EditorLines of len {}",
+                    self.len
+                ),
+            }
+        }
+    }
+
+    impl IndexMut<usize> for EditorLines {
+        fn index_mut(&mut self, row_index: usize) -> &mut Self::Output {
+            let len = self.len;
+            match self.get_mut(row_index) {
+                Some(it) => it,
+                None => {
+                    panic!("index {row_index} out of bounds for EditorLines of len {len}")
+                }
+            }
+        }
+    }
+
+    impl<'a> IntoIterator for &'a EditorLines {
+        type Item = &'a UnicodeString;
+        type IntoIter = EditorLinesIter<'a, &'a UnicodeString>;
+
+        fn into_iter(self) -> Self::IntoIter { self.iter() }
+    }
+
+    impl PartialEq for EditorLines {
+        fn eq(&self, other: &Self) -> bool {
+            self.len == other.len && self.get_common_prefix_len(other) == self.len
+        }
+    }
+
+    impl PartialEq<Vec<UnicodeString>> for EditorLines {
+        fn eq(&self, other: &Vec<UnicodeString>) -> bool {
+            self.len == other.len()
+                && self
+                    .iter_str()
+                    .zip(other.iter())
+                    .all(|(lhs, rhs)| lhs == rhs.string)
+        }
+    }
+}
+
+/// Iterator over [EditorLines], returned by [EditorLines::iter] and
+/// [EditorLines::iter_str].
+pub struct EditorLinesIter<'a, T> {
+    lines: &'a EditorLines,
+    range: Range<usize>,
+    map: fn(&'a EditorLine) -> T,
+}
+
+impl<'a, T> EditorLinesIter<'a, T> {
+    fn new(lines: &'a EditorLines, map: fn(&'a EditorLine) -> T) -> Self {
+        Self {
+            lines,
+            range: 0..lines.len,
+            map,
+        }
+    }
+}
+
+impl<'a, T> Iterator for EditorLinesIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row_index = self.range.next()?;
+        self.lines.get_line(row_index).map(self.map)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.range.size_hint() }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let row_index = self.range.nth(n)?;
+        self.lines.get_line(row_index).map(self.map)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for EditorLinesIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let row_index = self.range.next_back()?;
+        self.lines.get_line(row_index).map(self.map)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for EditorLinesIter<'a, T> {}
+
+mod mutate {
+    use super::*;
+
+    impl EditorLines {
+        /// Replace the lines in `range` w/ `replace_with`, and return the lines that were
+        /// removed. Only the chunks that hold `range` are touched.
+        fn splice_impl(
+            &mut self,
+            range: Range<usize>,
+            replace_with: Vec<EditorLine>,
+        ) -> Vec<EditorLine> {
+            assert!(
+                range.start <= range.end && range.end <= self.len,
+                "range {range:?} out of bounds for EditorLines of len {}",
+                self.len
+            );
+
+            if self.chunks.is_empty() {
+                *self = Self::from_editor_lines(replace_with);
+                return vec![];
+            }
+
+            // The chunks that hold the start and the end of the range. Appending goes
+            // into the last chunk.
+            let last_chunk_index = self.chunks.len() - 1;
+            let (mut start_chunk_index, start_offset) = self
+                .locate(range.start)
+                .unwrap_or((last_chunk_index, self.chunks[last_chunk_index].len()));
+            let (end_chunk_index, end_offset) = if range.is_empty() {
+                (start_chunk_index, start_offset)
+            } else {
+                // Safe to unwrap, since the range is in bounds.
+                let (chunk_index, offset) = self.locate(range.end - 1).unwrap();
+                (chunk_index, offset + 1)
+            };
+
+            let mut old_chunks = self
+                .chunks
+                .drain(start_chunk_index..=end_chunk_index)
+                .map(take_chunk)
+                .collect::<Vec<_>>();
+
+            // Safe to unwrap, since at least one chunk was drained.
+            let mut lines_after_range =
+                old_chunks.last_mut().unwrap().split_off(end_offset);
+            let mut lines = std::mem::take(&mut old_chunks[0]);
+            let mut removed_lines = lines.split_off(start_offset);
+            for chunk in old_chunks.into_iter().skip(1) {
+                removed_lines.extend(chunk);
+            }
+
+            lines.extend(replace_with);
+            lines.append(&mut lines_after_range);
+
+            // Merge small chunks w/ their neighbors.
+            if lines.len() < EDITOR_LINES_CHUNK_MIN_LEN && start_chunk_index > 0 {
+                start_chunk_index -= 1;
+                let mut prev_lines = take_chunk(self.chunks.remove(start_chunk_index));
+                prev_lines.append(&mut lines);
+                lines = prev_lines;
+            }
+            if lines.len() < EDITOR_LINES_CHUNK_MIN_LEN
+                && start_chunk_index < self.chunks.len()
+            {
+                lines.extend(take_chunk(self.chunks.remove(start_chunk_index)));
+            }
+
+            self.chunks
+                .splice(start_chunk_index..start_chunk_index, make_chunks(lines));
+            self.update_chunk_start_row_indices();
+
+            removed_lines
+        }
+
+        /// Replace the lines in `range` w/ `replace_with`.
+        pub fn splice(
+            &mut self,
+            range: Range<usize>,
+            replace_with: impl IntoIterator<Item = UnicodeString>,
+        ) {
+            self.splice_impl(
+                range,
+                replace_with.into_iter().map(EditorLine::from).collect(),
+            );
+        }
+
+        pub fn insert(&mut self, row_index: usize, line: UnicodeString) {
+            self.splice_impl(row_index..row_index, vec![EditorLine::from(line)]);
+        }
+
+        pub fn push(&mut self, line: UnicodeString) { self.insert(self.len, line); }
+
+        pub fn remove(&mut self, row_index: usize) -> UnicodeString {
+            self.splice_impl(row_index..row_index + 1, vec![])
+                .pop()
+                .map(EditorLine::into_unicode_string)
+                .unwrap_or_default()
+        }
+
+        pub fn truncate(&mut self, len: usize) {
+            if len < self.len {
+                self.splice_impl(len..self.len, vec![]);
+            }
+        }
+
+        pub fn clear(&mut self) { *self = Self::default(); }
+    }
+}
+
+mod serde_and_get_size {
+    use super::*;
+
+    impl Serialize for EditorLines {
+        fn serialize<S: Serializer>(
+            &self,
+            serializer: S,
+        ) -> std::result::Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter_str())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for EditorLines {
+        fn deserialize<D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> std::result::Result<Self, D::Error> {
+            Vec::<String>::deserialize(deserializer).map(EditorLines::from)
+        }
+    }
+
+    impl GetSize for EditorLines {
+        fn get_heap_size(&self) -> usize {
+            let mut size = self.chunks.capacity()
+                * std::mem::size_of::<Arc<Vec<EditorLine>>>()
+                + self.chunk_start_row_indices.capacity() * std::mem::size_of::<usize>();
+            for chunk in &self.chunks {
+                size += chunk.capacity() * std::mem::size_of::<EditorLine>();
+                for line in chunk.iter() {
+                    size += line.maybe_text.as_ref().map_or(0, |it| it.len());
+                    size += line.unicode_string.get().map_or(0, |it| it.get_heap_size());
+                }
+            }
+            size
+        }
+    }
+
+    impl Debug for EditorLines {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+            f.debug_list().entries(self.iter_str()).finish()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_lines(count: usize) -> EditorLines {
+        (0..count).map(|it| it.to_string()).collect()
+    }
+
+    fn assert_lines(lines: &EditorLines, expected: &[String]) {
+        assert_eq!(lines.len(), expected.len());
+        assert_eq!(lines.iter_str().collect::<Vec<_>>(), expected);
+        assert_eq!(
+            lines
+                .iter()
+                .map(|it| it.string.as_str())
+                .collect::<Vec<_>>(),
+            expected
+        );
+        for chunk in &lines.chunks {
+            assert!(!chunk.is_empty() && chunk.len() <= EDITOR_LINES_CHUNK_MAX_LEN);
+        }
+    }
+
+    #[test]
+    fn test_lazy_segmentation() {
+        let lines: EditorLines = vec!["a😃b".to_string()].into();
+        assert!(lines.chunks[0][0].unicode_string.get().is_none());
+        assert_eq!(lines.get_str(0), Some("a😃b"));
+        assert!(lines.chunks[0][0].unicode_string.get().is_none());
+        assert_eq!(lines[0].display_width, ch!(4));
+        assert!(lines.chunks[0][0].unicode_string.get().is_some());
+        assert_eq!(lines.get_str(0), Some("a😃b"));
+
+        // The text is dropped once the line is changed.
+        let mut lines = lines;
+        assert!(lines.chunks[0][0].maybe_text.is_some());
+        lines[0] = UnicodeString::from("c");
+        assert!(lines.chunks[0][0].maybe_text.is_none());
+        assert_eq!(lines.get_str(0), Some("c"));
+    }
+
+    #[test]
+    fn test_edits_across_chunks() {
+        let count = EDITOR_LINES_CHUNK_MAX_LEN * 3;
+        let mut lines = make_lines(count);
+        let mut expected = (0..count).map(|it| it.to_string()).collect::<Vec<_>>();
+        assert!(lines.chunks.len() > 1);
+
+        // Insert until a chunk is split.
+        for it in 0..EDITOR_LINES_CHUNK_MAX_LEN {
+            lines.insert(10, UnicodeString::from(format!("new {it}")));
+            expected.insert(10, format!("new {it}"));
+        }
+        assert_lines(&lines, &expected);
+
+        // Remove a range that spans chunks.
+        let range = 100..EDITOR_LINES_CHUNK_MAX_LEN * 2;
+        lines.splice(range.clone(), vec![UnicodeString::from("x")]);
+        expected.splice(range, vec!["x".to_string()]);
+        assert_lines(&lines, &expected);
+
+        assert_eq!(lines.remove(5).string, expected.remove(5));
+        lines[7] = UnicodeString::from("seven");
+        expected[7] = "seven".to_string();
+        lines.push(UnicodeString::from("last"));
+        expected.push("last".to_string());
+        assert_lines(&lines, &expected);
+
+        lines.truncate(3);
+        expected.truncate(3);
+        assert_lines(&lines, &expected);
+        assert_eq!(
+            lines
+                .iter()
+                .rev()
+                .map(|it| it.string.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2", "1", "0"]
+        );
+    }
+
+    #[test]
+    fn test_clone_shares_chunks() {
+        let lines = make_lines(EDITOR_LINES_CHUNK_MAX_LEN * 4);
+        let mut new_lines = lines.clone();
+        new_lines[EDITOR_LINES_CHUNK_MAX_LEN] = UnicodeString::from("changed");
+
+        // Only the chunk w/ the changed line is copied.
+        let shared_chunk_count = lines
+            .chunks
+            .iter()
+            .zip(new_lines.chunks.iter())
+            .filter(|(lhs, rhs)| Arc::ptr_eq(lhs, rhs))
+            .count();
+        assert_eq!(shared_chunk_count, lines.chunks.len() - 1);
+
+        assert_eq!(
+            lines.get_common_prefix_len(&new_lines),
+            EDITOR_LINES_CHUNK_MAX_LEN
+        );
+        assert_eq!(
+            lines.get_common_suffix_len(&new_lines, lines.len()),
+            lines.len() - EDITOR_LINES_CHUNK_MAX_LEN - 1
+        );
+        assert_ne!(lines, new_lines);
+        assert_eq!(lines.get_str(EDITOR_LINES_CHUNK_MAX_LEN), Some("1024"));
+    }
+}
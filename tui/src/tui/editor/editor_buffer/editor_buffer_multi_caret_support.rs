@@ -0,0 +1,565 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// A caret in addition to the primary one (which is stored in
+/// [EditorContent::caret_display_position] and [EditorContent::selection_map]). Each
+/// one has its own selection.
+#[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Default, Debug)]
+pub struct EditorCaret {
+    /// [Scroll adjusted](CaretKind::ScrollAdjusted) position. Unlike the primary caret,
+    /// this doesn't depend on the scroll offset.
+    pub position: Position,
+    pub selection_map: SelectionMap,
+}
+
+pub mod multi_caret {
+    use super::*;
+
+    pub fn has_secondary_carets(editor_buffer: &EditorBuffer) -> bool {
+        !editor_buffer.editor_content.secondary_carets.is_empty()
+    }
+
+    /// Remove all the carets, except for the primary one.
+    pub fn clear(editor_buffer: &mut EditorBuffer) {
+        editor_buffer.editor_content.secondary_carets.clear();
+    }
+
+    /// Returns the (scroll adjusted) positions of all the carets, in document order.
+    pub fn get_positions(editor_buffer: &EditorBuffer) -> Vec<Position> {
+        let mut it = vec![editor_buffer.get_caret(CaretKind::ScrollAdjusted)];
+        it.extend(
+            editor_buffer
+                .editor_content
+                .secondary_carets
+                .iter()
+                .map(|caret| caret.position),
+        );
+        it.sort_by_key(|position| (position.row_index, position.col_index));
+        it
+    }
+
+    /// Add a caret on the line above the topmost caret, in the same column (or at the
+    /// end of the line if it is shorter).
+    pub fn add_caret_above(editor_buffer: &mut EditorBuffer) {
+        let positions = get_positions(editor_buffer);
+        let Some(topmost) = positions.first() else {
+            return;
+        };
+        if topmost.row_index == ch!(0) {
+            return;
+        }
+        add_caret_at_col_in_row(editor_buffer, *topmost, topmost.row_index - 1);
+    }
+
+    /// Add a caret on the line below the bottommost caret, in the same column (or at the
+    /// end of the line if it is shorter).
+    pub fn add_caret_below(editor_buffer: &mut EditorBuffer) {
+        let positions = get_positions(editor_buffer);
+        let Some(bottommost) = positions.last() else {
+            return;
+        };
+        if bottommost.row_index + 1 >= editor_buffer.len() {
+            return;
+        }
+        add_caret_at_col_in_row(editor_buffer, *bottommost, bottommost.row_index + 1);
+    }
+
+    fn add_caret_at_col_in_row(
+        editor_buffer: &mut EditorBuffer,
+        from: Position,
+        row_index: ChUnit,
+    ) {
+        let line_display_width = editor_buffer.get_line_display_width(row_index);
+        let position = position!(
+            col_index: std::cmp::min(from.col_index, line_display_width),
+            row_index: row_index
+        );
+        add_caret(editor_buffer, position, SelectionMap::default());
+    }
+
+    /// Works like "add selection to next find match" in other editors:
+    /// 1. If the primary caret doesn't have a selection, then select the word at the
+    ///    caret.
+    /// 2. Otherwise, find the next occurrence of the selected text (which must be on a
+    ///    single line) after the last caret that was added, and add a caret that selects
+    ///    it.
+    pub fn add_caret_at_next_match_of_selection(editor_buffer: &mut EditorBuffer) {
+        if !editor_buffer.has_selection() {
+            select_word_at_caret(editor_buffer);
+            return;
+        }
+
+        let selection_map = editor_buffer.get_selection_map();
+        if selection_map.map.len() != 1 {
+            return;
+        }
+        let Some(text) = selection_map
+            .get_selected_lines(editor_buffer)
+            .into_values()
+            .next()
+            .map(str::to_string)
+        else {
+            return;
+        };
+        if text.is_empty() {
+            return;
+        }
+
+        // Start searching after the last caret that was added.
+        let start = match editor_buffer.editor_content.secondary_carets.last() {
+            Some(caret) => caret.position,
+            None => editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+        };
+        let existing_positions = get_positions(editor_buffer);
+
+        let lines = editor_buffer.get_lines();
+        let row_count = lines.len();
+        let start_row_index = ch!(@to_usize start.row_index);
+        // Visit the rows starting at the caret, wrapping around to the top of the buffer,
+        // and ending back at the caret's row (for the matches that come before it).
+        for offset in 0..=row_count {
+            let row_index = (start_row_index + offset) % row_count.max(1);
+            let Some(line) = lines.get(row_index) else {
+                continue;
+            };
+            for (byte_index, _) in line.string.match_indices(&text) {
+                let range = SelectionRange::new(
+                    display_col_index_at_byte_index(line, byte_index),
+                    display_col_index_at_byte_index(line, byte_index + text.len()),
+                );
+                let is_after_start =
+                    offset > 0 || range.start_display_col_index >= start.col_index;
+                let is_before_start =
+                    offset == row_count && range.end_display_col_index < start.col_index;
+                if (offset < row_count && !is_after_start)
+                    || (offset == row_count && !is_before_start)
+                {
+                    continue;
+                }
+
+                let position = position!(
+                    col_index: range.end_display_col_index,
+                    row_index: ch!(row_index)
+                );
+                if existing_positions.contains(&position) {
+                    continue;
+                }
+
+                let selection_map = {
+                    let mut it = SelectionMap::default();
+                    it.insert(ch!(row_index), range, CaretMovementDirection::Right);
+                    it
+                };
+                add_caret(editor_buffer, position, selection_map);
+                return;
+            }
+        }
+    }
+
+    fn select_word_at_caret(editor_buffer: &mut EditorBuffer) {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let Some(line) = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret.row_index))
+        else {
+            return;
+        };
+
+        // The word that contains (or ends at) the caret.
+        let Some(range) = line.get_word_range_at_display_col(caret.col_index) else {
+            return;
+        };
+
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let (_, caret_raw, _, selection_map) = editor_buffer.get_mut();
+        selection_map.clear();
+        selection_map.insert(caret.row_index, range, CaretMovementDirection::Right);
+        // The selection is in the viewport, since the caret is.
+        caret_raw.col_index = range.end_display_col_index - scroll_offset.col_index;
+    }
+
+    fn add_caret(
+        editor_buffer: &mut EditorBuffer,
+        position: Position,
+        selection_map: SelectionMap,
+    ) {
+        if get_positions(editor_buffer).contains(&position) {
+            return;
+        }
+        editor_buffer
+            .editor_content
+            .secondary_carets
+            .push(EditorCaret {
+                position,
+                selection_map,
+            });
+    }
+
+    /// Run `apply` once for each caret, w/ that caret loaded into the primary caret's
+    /// place ([EditorContent::caret_display_position] and
+    /// [EditorContent::selection_map]), so that all the existing editing functions work
+    /// w/ multiple carets.
+    ///
+    /// The carets are visited from the end of the buffer to the start, so that an edit
+    /// can only change the text before the carets that were already visited. And since
+    /// the text after an edit doesn't change, the position of those carets relative to
+    /// the end of the buffer (and the end of their line) doesn't change either. So this
+    /// is what is remembered for them (see [CaretAnchor]), which takes care of adjusting
+    /// their positions for edits on the same line, and for lines being added or removed
+    /// above them.
+    ///
+    /// `viewport` is the size of the editor's viewport. It is used to pick a scroll offset
+    /// for each secondary caret, so that it is in the viewport while `apply` runs. The
+    /// scroll offset of the primary caret is kept.
+    pub fn for_each_caret(
+        editor_buffer: &mut EditorBuffer,
+        viewport: Size,
+        mut apply: impl FnMut(&mut EditorBuffer),
+    ) {
+        if !has_secondary_carets(editor_buffer) {
+            apply(editor_buffer);
+            return;
+        }
+
+        let scroll_offset = editor_buffer.get_scroll_offset();
+
+        let mut carets: Vec<(/* is_primary */ bool, EditorCaret)> = vec![(
+            true,
+            EditorCaret {
+                position: editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+                selection_map: std::mem::take(
+                    &mut editor_buffer.editor_content.selection_map,
+                ),
+            },
+        )];
+        carets.extend(
+            std::mem::take(&mut editor_buffer.editor_content.secondary_carets)
+                .into_iter()
+                .map(|caret| (false, caret)),
+        );
+        carets.sort_by_key(|(_, caret)| {
+            std::cmp::Reverse((caret.position.row_index, caret.position.col_index))
+        });
+
+        let mut anchors: Vec<(bool, CaretAnchor)> = vec![];
+        let mut primary_scroll_offset = scroll_offset;
+
+        for (is_primary, caret) in carets {
+            let caret_scroll_offset = if is_primary {
+                scroll_offset
+            } else {
+                get_scroll_offset_for(caret.position, scroll_offset, viewport)
+            };
+            load(editor_buffer, caret, caret_scroll_offset);
+
+            apply(editor_buffer);
+
+            if is_primary {
+                primary_scroll_offset = editor_buffer.get_scroll_offset();
+            }
+            let caret = EditorCaret {
+                position: editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+                selection_map: std::mem::take(
+                    &mut editor_buffer.editor_content.selection_map,
+                ),
+            };
+            anchors.push((
+                is_primary,
+                CaretAnchor::new(caret, editor_buffer.get_lines()),
+            ));
+        }
+
+        // Now that all the edits are done, turn the anchors back into positions.
+        let mut secondary_carets: Vec<EditorCaret> = vec![];
+        for (is_primary, anchor) in anchors.into_iter().rev() {
+            let caret = anchor.into_caret(editor_buffer.get_lines());
+            if is_primary {
+                load(editor_buffer, caret, primary_scroll_offset);
+                keep_caret_in_viewport(editor_buffer, viewport);
+            } else {
+                secondary_carets.push(caret);
+            }
+        }
+
+        // Carets that ended up in the same position (eg: after backspacing into each
+        // other) are merged.
+        let primary_position = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        secondary_carets.retain(|it| it.position != primary_position);
+        secondary_carets.dedup_by_key(|it| it.position);
+        editor_buffer.editor_content.secondary_carets = secondary_carets;
+    }
+
+    /// Make `caret` the primary caret.
+    fn load(
+        editor_buffer: &mut EditorBuffer,
+        caret: EditorCaret,
+        scroll_offset: ScrollOffset,
+    ) {
+        let (_, caret_raw, scroll_offset_mut, selection_map) = editor_buffer.get_mut();
+        *scroll_offset_mut = position!(
+            col_index: std::cmp::min(scroll_offset.col_index, caret.position.col_index),
+            row_index: std::cmp::min(scroll_offset.row_index, caret.position.row_index)
+        );
+        *caret_raw = position!(
+            col_index: caret.position.col_index - scroll_offset_mut.col_index,
+            row_index: caret.position.row_index - scroll_offset_mut.row_index
+        );
+        *selection_map = caret.selection_map;
+    }
+
+    /// Keep `scroll_offset` if `position` is in the viewport, otherwise scroll so that
+    /// it is at the top left of the viewport.
+    fn get_scroll_offset_for(
+        position: Position,
+        scroll_offset: ScrollOffset,
+        viewport: Size,
+    ) -> ScrollOffset {
+        let is_row_within_viewport = position.row_index >= scroll_offset.row_index
+            && position.row_index < scroll_offset.row_index + viewport.row_count;
+        let is_col_within_viewport = position.col_index >= scroll_offset.col_index
+            && position.col_index < scroll_offset.col_index + viewport.col_count;
+        position!(
+            col_index: if is_col_within_viewport { scroll_offset.col_index } else { position.col_index },
+            row_index: if is_row_within_viewport { scroll_offset.row_index } else { position.row_index }
+        )
+    }
+
+    /// Edits made at the other carets may have moved the primary caret out of the
+    /// viewport (eg: lines inserted above it).
+    fn keep_caret_in_viewport(editor_buffer: &mut EditorBuffer, viewport: Size) {
+        let (_, caret_raw, scroll_offset, _) = editor_buffer.get_mut();
+        if viewport.row_count > ch!(0) && caret_raw.row_index >= viewport.row_count {
+            let diff = caret_raw.row_index - viewport.row_count + 1;
+            scroll_offset.row_index += diff;
+            caret_raw.row_index -= diff;
+        }
+        if viewport.col_count > ch!(0) && caret_raw.col_index >= viewport.col_count {
+            let diff = caret_raw.col_index - viewport.col_count + 1;
+            scroll_offset.col_index += diff;
+            caret_raw.col_index -= diff;
+        }
+    }
+
+    fn display_col_index_at_byte_index(
+        line: &UnicodeString,
+        byte_index: usize,
+    ) -> ChUnit {
+        line.vec_segment
+            .iter()
+            .find(|it| it.byte_offset >= byte_index)
+            .map(|it| it.display_col_offset)
+            .unwrap_or(line.display_width)
+    }
+
+    /// The position of a caret (and its selection) relative to the end of the buffer:
+    /// the number of rows from the last row, and the number of display cols from the
+    /// end of the line. This doesn't change when text before it is edited.
+    struct CaretAnchor {
+        position: PositionFromEnd,
+        selection: Vec<(PositionFromEnd, /* end col */ ChUnit)>,
+        maybe_previous_direction: Option<CaretMovementDirection>,
+    }
+
+    #[derive(Clone, Copy)]
+    struct PositionFromEnd {
+        row_count: usize,
+        col_count: ChUnit,
+    }
+
+    impl PositionFromEnd {
+        fn new(row_index: ChUnit, col_index: ChUnit, lines: &EditorLines) -> Self {
+            let row_index = ch!(@to_usize row_index);
+            let line_display_width = lines
+                .get(row_index)
+                .map(|it| it.display_width)
+                .unwrap_or_default();
+            Self {
+                row_count: lines.len().saturating_sub(row_index),
+                col_count: line_display_width - col_index,
+            }
+        }
+
+        /// Returns the row index, and the display width of the line at that row.
+        fn row_index(&self, lines: &EditorLines) -> (ChUnit, ChUnit) {
+            let row_index = lines.len().saturating_sub(self.row_count);
+            let line_display_width = lines
+                .get(row_index)
+                .map(|it| it.display_width)
+                .unwrap_or_default();
+            (ch!(row_index), line_display_width)
+        }
+    }
+
+    impl CaretAnchor {
+        fn new(caret: EditorCaret, lines: &EditorLines) -> Self {
+            Self {
+                position: PositionFromEnd::new(
+                    caret.position.row_index,
+                    caret.position.col_index,
+                    lines,
+                ),
+                selection: caret
+                    .selection_map
+                    .iter()
+                    .map(|(row_index, range)| {
+                        let line_display_width = lines
+                            .get(ch!(@to_usize *row_index))
+                            .map(|it| it.display_width)
+                            .unwrap_or_default();
+                        (
+                            PositionFromEnd::new(
+                                *row_index,
+                                range.start_display_col_index,
+                                lines,
+                            ),
+                            line_display_width - range.end_display_col_index,
+                        )
+                    })
+                    .collect(),
+                maybe_previous_direction: caret.selection_map.maybe_previous_direction,
+            }
+        }
+
+        fn into_caret(self, lines: &EditorLines) -> EditorCaret {
+            let (row_index, line_display_width) = self.position.row_index(lines);
+            let mut selection_map = SelectionMap {
+                maybe_previous_direction: self.maybe_previous_direction,
+                ..Default::default()
+            };
+            for (start, end_col_count) in self.selection {
+                let (row_index, line_display_width) = start.row_index(lines);
+                selection_map.map.insert(
+                    row_index,
+                    SelectionRange::new(
+                        line_display_width - start.col_count,
+                        line_display_width - end_col_count,
+                    ),
+                );
+            }
+            EditorCaret {
+                position: position!(
+                    col_index: line_display_width - self.position.col_count,
+                    row_index: row_index
+                ),
+                selection_map,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_editor::mock_real_objects_for_editor::make_editor_buffer_with_lines;
+
+    fn positions(buffer: &EditorBuffer) -> Vec<(usize, usize)> {
+        multi_caret::get_positions(buffer)
+            .iter()
+            .map(|it| (ch!(@to_usize it.row_index), ch!(@to_usize it.col_index)))
+            .collect()
+    }
+
+    #[test]
+    fn test_add_caret_above_and_below() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["abcdef", "abcdef", "ab"]);
+        let (_, caret, _, _) = buffer.get_mut();
+        *caret = position!(col_index: 4, row_index: 1);
+
+        // The caret can't go past the end of the line.
+        multi_caret::add_caret_below(&mut buffer);
+        assert_eq2!(positions(&buffer), vec![(1, 4), (2, 2)]);
+
+        multi_caret::add_caret_above(&mut buffer);
+        assert_eq2!(positions(&buffer), vec![(0, 4), (1, 4), (2, 2)]);
+
+        // There are no more rows above or below.
+        multi_caret::add_caret_above(&mut buffer);
+        multi_caret::add_caret_below(&mut buffer);
+        assert_eq2!(positions(&buffer).len(), 3);
+
+        multi_caret::clear(&mut buffer);
+        assert!(!multi_caret::has_secondary_carets(&buffer));
+    }
+
+    #[test]
+    fn test_for_each_caret_adjusts_positions_on_same_line() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["ab ab ab"]);
+        buffer.editor_content.secondary_carets = vec![
+            EditorCaret {
+                position: position!(col_index: 3, row_index: 0),
+                ..Default::default()
+            },
+            EditorCaret {
+                position: position!(col_index: 6, row_index: 0),
+                ..Default::default()
+            },
+        ];
+
+        // Insert "x" at each caret, and move it past the inserted text.
+        multi_caret::for_each_caret(
+            &mut buffer,
+            size!(col_count: 80, row_count: 10),
+            |buffer| {
+                let caret = buffer.get_caret(CaretKind::ScrollAdjusted);
+                let row_index = ch!(@to_usize caret.row_index);
+                let line = buffer.get_lines()[row_index].clone();
+                let (new_line, _) = line
+                    .insert_char_at_display_col(caret.col_index, "x")
+                    .unwrap();
+                let (lines, caret, _, _) = buffer.get_mut();
+                lines[row_index] = new_line;
+                caret.col_index += 1;
+            },
+        );
+
+        assert_eq2!(buffer.get_as_string_with_newlines(), "xab xab xab");
+        assert_eq2!(positions(&buffer), vec![(0, 1), (0, 5), (0, 9)]);
+    }
+
+    #[test]
+    fn test_add_caret_at_next_match_of_selection() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["foo bar", "foo", "bar foo"]);
+
+        // Select the word at the caret first.
+        multi_caret::add_caret_at_next_match_of_selection(&mut buffer);
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(3)))
+        );
+        assert_eq2!(positions(&buffer), vec![(0, 3)]);
+
+        multi_caret::add_caret_at_next_match_of_selection(&mut buffer);
+        multi_caret::add_caret_at_next_match_of_selection(&mut buffer);
+        assert_eq2!(positions(&buffer), vec![(0, 3), (1, 3), (2, 7)]);
+        assert_eq2!(
+            buffer.editor_content.secondary_carets[1]
+                .selection_map
+                .get(ch!(2)),
+            Some(&SelectionRange::new(ch!(4), ch!(7)))
+        );
+
+        // All the occurrences have a caret.
+        multi_caret::add_caret_at_next_match_of_selection(&mut buffer);
+        assert_eq2!(positions(&buffer).len(), 3);
+    }
+}
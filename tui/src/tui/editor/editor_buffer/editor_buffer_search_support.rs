@@ -0,0 +1,639 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use get_size::GetSize;
+use r3bl_rs_utils_core::*;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Options that control how the query passed to [search::set_query] is matched.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, GetSize, Default, Debug)]
+pub struct SearchOptions {
+    /// Upper and lower case letters only match themselves.
+    pub is_case_sensitive: bool,
+    /// The query is a regular expression (using the syntax of the [regex] crate) instead
+    /// of plain text.
+    pub is_regex: bool,
+}
+
+/// A single match of the search query. Matches never span multiple lines.
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, GetSize, Debug)]
+pub struct SearchMatch {
+    pub row_index: ChUnit,
+    /// Display col indices of the match, used to render it and to move the caret.
+    pub range: SelectionRange,
+    /// Byte indices of the match in the line, used to replace it.
+    pub start_byte_index: usize,
+    pub end_byte_index: usize,
+}
+
+/// Which match to move the caret to, see [search::find_match].
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, GetSize, Debug)]
+pub enum SearchJump {
+    /// The first match after the caret, wrapping around to the top of the buffer.
+    Next,
+    /// The last match before the caret, wrapping around to the bottom of the buffer.
+    Previous,
+    /// The first match that starts at or after the given (scroll adjusted) position,
+    /// wrapping around to the top of the buffer.
+    AtOrAfter(Position),
+}
+
+/// Search state of an [EditorBuffer]. All the matches in the buffer are kept up to date
+/// as the content changes, so that they can be highlighted when rendering.
+///
+/// Since this is part of the [EditorBuffer] (and not the [EditorEngine]), a search can be
+/// driven from outside the editor component, eg: from a dialog. Moving the caret to a
+/// match may require scrolling, which needs the viewport. So [search::request_jump] only
+/// records the jump, and it is performed the next time the editor is rendered (see
+/// [EditorEngineInternalApi::apply_pending_search_jump]).
+#[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Default, Debug)]
+pub struct EditorBufferSearch {
+    query: String,
+    options: SearchOptions,
+    matches: Vec<SearchMatch>,
+    /// Set if the query is not a valid regular expression.
+    maybe_error: Option<String>,
+    maybe_pending_jump: Option<SearchJump>,
+}
+
+impl EditorBufferSearch {
+    pub fn get_query(&self) -> &str { &self.query }
+
+    pub fn get_options(&self) -> SearchOptions { self.options }
+
+    pub fn get_matches(&self) -> &Vec<SearchMatch> { &self.matches }
+
+    pub fn get_maybe_error(&self) -> Option<&str> { self.maybe_error.as_deref() }
+}
+
+pub mod search {
+    use super::*;
+
+    /// Start a new search (or change the current one) and move the caret to the first
+    /// match at or after it. This is meant to be called on every keystroke in a search
+    /// bar, for incremental search.
+    pub fn set_query(
+        editor_buffer: &mut EditorBuffer,
+        query: &str,
+        options: SearchOptions,
+    ) {
+        editor_buffer.search.query = query.to_string();
+        editor_buffer.search.options = options;
+        update_matches(editor_buffer);
+
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        request_jump(editor_buffer, SearchJump::AtOrAfter(caret));
+    }
+
+    /// Stop searching, which removes all the highlighted matches.
+    pub fn clear(editor_buffer: &mut EditorBuffer) {
+        editor_buffer.search = EditorBufferSearch::default();
+    }
+
+    pub fn is_active(editor_buffer: &EditorBuffer) -> bool {
+        !editor_buffer.search.query.is_empty()
+    }
+
+    /// Find all the matches of the query in the buffer. This has to be called whenever
+    /// the content is replaced, which [EditorBuffer::set_lines] takes care of. Edits
+    /// only need [update_matches_in_rows].
+    pub fn update_matches(editor_buffer: &mut EditorBuffer) {
+        let search = &mut editor_buffer.search;
+        search.matches.clear();
+        search.maybe_error = None;
+
+        if search.query.is_empty() {
+            return;
+        }
+
+        let regex = match build_regex(&search.query, search.options) {
+            Ok(it) => it,
+            Err(error) => {
+                search.maybe_error = Some(error.to_string());
+                return;
+            }
+        };
+
+        let lines = &editor_buffer.editor_content.lines;
+        search.matches = find_matches_in_rows(&regex, lines, 0..lines.len());
+    }
+
+    /// Update the matches after the `row_count_before` rows starting at `start_row`
+    /// were replaced w/ `row_count_after` rows, by only searching those rows (so that
+    /// editing a large buffer doesn't search all of it). The rows after them are moved
+    /// down (or up). This has to be called whenever the content changes, which
+    /// [history::push], [history::undo] and [history::redo] take care of.
+    pub fn update_matches_in_rows(
+        editor_buffer: &mut EditorBuffer,
+        start_row: usize,
+        row_count_before: usize,
+        row_count_after: usize,
+    ) {
+        let search = &mut editor_buffer.search;
+        if search.query.is_empty() || search.maybe_error.is_some() {
+            return;
+        }
+        let Ok(regex) = build_regex(&search.query, search.options) else {
+            return;
+        };
+
+        // Matches are sorted by row, so the ones in the edited rows are contiguous.
+        let row_of = |it: &SearchMatch| ch!(@to_usize it.row_index);
+        let start = search.matches.partition_point(|it| row_of(it) < start_row);
+        let end = search
+            .matches
+            .partition_point(|it| row_of(it) < start_row + row_count_before);
+
+        for it in &mut search.matches[end..] {
+            it.row_index = ch!(row_of(it) + row_count_after - row_count_before);
+        }
+        let new_matches = find_matches_in_rows(
+            &regex,
+            &editor_buffer.editor_content.lines,
+            start_row..start_row + row_count_after,
+        );
+        search.matches.splice(start..end, new_matches);
+    }
+
+    fn find_matches_in_rows(
+        regex: &Regex,
+        lines: &EditorLines,
+        row_indices: std::ops::Range<usize>,
+    ) -> Vec<SearchMatch> {
+        let mut matches = vec![];
+        for row_index in row_indices {
+            let Some(line_str) = lines.get_str(row_index) else {
+                break;
+            };
+            for it in regex.find_iter(line_str) {
+                // Skip empty matches (eg: `^` or `a*`), there is nothing to highlight.
+                if it.is_empty() {
+                    continue;
+                }
+                // Only lines w/ matches need their grapheme cluster metadata.
+                let line = &lines[row_index];
+                matches.push(SearchMatch {
+                    row_index: ch!(row_index),
+                    range: SelectionRange::new(
+                        start_display_col_index(line, it.start()),
+                        end_display_col_index(line, it.end()),
+                    ),
+                    start_byte_index: it.start(),
+                    end_byte_index: it.end(),
+                });
+            }
+        }
+        matches
+    }
+
+    /// Returns the match that `jump` refers to, w/out moving the caret.
+    pub fn find_match(
+        editor_buffer: &EditorBuffer,
+        jump: SearchJump,
+    ) -> Option<SearchMatch> {
+        let matches = &editor_buffer.search.matches;
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let caret = (caret.row_index, caret.col_index);
+        let start_of =
+            |it: &SearchMatch| (it.row_index, it.range.start_display_col_index);
+
+        match jump {
+            SearchJump::Next => matches
+                .iter()
+                .find(|it| start_of(it) > caret)
+                .or(matches.first()),
+            SearchJump::Previous => matches
+                .iter()
+                .rev()
+                .find(|it| start_of(it) < caret)
+                .or(matches.last()),
+            SearchJump::AtOrAfter(position) => {
+                let position = (position.row_index, position.col_index);
+                matches
+                    .iter()
+                    .find(|it| start_of(it) >= position)
+                    .or(matches.first())
+            }
+        }
+        .copied()
+    }
+
+    /// The match that starts at the caret (if any) is the current match.
+    pub fn get_current_match_index(editor_buffer: &EditorBuffer) -> Option<usize> {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        editor_buffer.search.matches.iter().position(|it| {
+            it.row_index == caret.row_index
+                && it.range.start_display_col_index == caret.col_index
+        })
+    }
+
+    pub fn request_jump(editor_buffer: &mut EditorBuffer, jump: SearchJump) {
+        editor_buffer.search.maybe_pending_jump = Some(jump);
+    }
+
+    pub fn take_pending_jump(editor_buffer: &mut EditorBuffer) -> Option<SearchJump> {
+        editor_buffer.search.maybe_pending_jump.take()
+    }
+
+    /// Replace the current match (or the first one after the caret, if the caret isn't
+    /// at a match) w/ `replacement`, and then request a jump to the following match.
+    /// When the query is a regular expression, `$1`, `$name`, etc. in `replacement` are
+    /// expanded to the text of the capture groups. This is recorded as a single undo
+    /// step. Returns `false` if there was nothing to replace.
+    pub fn replace_current(editor_buffer: &mut EditorBuffer, replacement: &str) -> bool {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let Some(search_match) = find_match(editor_buffer, SearchJump::AtOrAfter(caret))
+        else {
+            return false;
+        };
+        let Ok(regex) =
+            build_regex(&editor_buffer.search.query, editor_buffer.search.options)
+        else {
+            return false;
+        };
+
+        let row_index = ch!(@to_usize search_match.row_index);
+        let Some(line) = editor_buffer.get_lines().get(row_index) else {
+            return false;
+        };
+        let Some(new_text) = expand_replacement(
+            &regex,
+            &line.string,
+            &search_match,
+            replacement,
+            editor_buffer.search.options,
+        ) else {
+            return false;
+        };
+        let new_line = format!(
+            "{}{}{}",
+            &line.string[..search_match.start_byte_index],
+            new_text,
+            &line.string[search_match.end_byte_index..]
+        );
+
+        begin_change(editor_buffer);
+        let (lines, _, _, _) = editor_buffer.get_mut();
+        lines[row_index] = UnicodeString::from(new_line);
+        history::push(editor_buffer);
+
+        // Continue w/ the match after the replaced text.
+        let end_display_col_index = search_match.range.start_display_col_index
+            + UnicodeString::from(new_text).display_width;
+        request_jump(
+            editor_buffer,
+            SearchJump::AtOrAfter(position!(
+                col_index: end_display_col_index,
+                row_index: search_match.row_index
+            )),
+        );
+
+        true
+    }
+
+    /// Replace all the matches w/ `replacement` (see [replace_current] for the syntax).
+    /// This is recorded as a single undo step. Returns the number of matches replaced.
+    pub fn replace_all(editor_buffer: &mut EditorBuffer, replacement: &str) -> usize {
+        let matches = editor_buffer.search.matches.clone();
+        if matches.is_empty() {
+            return 0;
+        }
+        let options = editor_buffer.search.options;
+        let Ok(regex) = build_regex(&editor_buffer.search.query, options) else {
+            return 0;
+        };
+
+        begin_change(editor_buffer);
+
+        let mut count = 0;
+        let (lines, caret, scroll_offset, _) = editor_buffer.get_mut();
+        for matches_in_row in matches.chunk_by(|a, b| a.row_index == b.row_index) {
+            let row_index = ch!(@to_usize matches_in_row[0].row_index);
+            let Some(line) = lines.get(row_index) else {
+                continue;
+            };
+
+            let mut new_line = String::new();
+            let mut last_byte_index = 0;
+            for search_match in matches_in_row {
+                let Some(new_text) = expand_replacement(
+                    &regex,
+                    &line.string,
+                    search_match,
+                    replacement,
+                    options,
+                ) else {
+                    continue;
+                };
+                new_line.push_str(
+                    &line.string[last_byte_index..search_match.start_byte_index],
+                );
+                new_line.push_str(&new_text);
+                last_byte_index = search_match.end_byte_index;
+                count += 1;
+            }
+            new_line.push_str(&line.string[last_byte_index..]);
+
+            lines[row_index] = UnicodeString::from(new_line);
+        }
+
+        // The line the caret is on may have gotten shorter. If the caret is now past its
+        // end, move it to the start of the line (which is always in the viewport).
+        let caret_row_index =
+            EditorBuffer::calc_scroll_adj_caret_row(caret, scroll_offset);
+        let caret_col_index =
+            EditorBuffer::calc_scroll_adj_caret_col(caret, scroll_offset);
+        if let Some(line) = lines.get(caret_row_index) {
+            if ch!(caret_col_index) > line.display_width {
+                caret.col_index = ch!(0);
+                scroll_offset.col_index = ch!(0);
+            }
+        }
+
+        history::push(editor_buffer);
+
+        count
+    }
+
+    /// Make sure that the replacement can be undone, just like an [EditorEvent] that is
+    /// applied by [EditorEngineApi::apply_event].
+    fn begin_change(editor_buffer: &mut EditorBuffer) {
+        if editor_buffer.history.is_empty() {
+            history::push(editor_buffer);
+        }
+        history::mark_caret_before_change(editor_buffer);
+    }
+
+    fn build_regex(query: &str, options: SearchOptions) -> Result<Regex, regex::Error> {
+        let pattern = if options.is_regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!options.is_case_sensitive)
+            .build()
+    }
+
+    fn expand_replacement(
+        regex: &Regex,
+        line: &str,
+        search_match: &SearchMatch,
+        replacement: &str,
+        options: SearchOptions,
+    ) -> Option<String> {
+        if !options.is_regex {
+            return Some(replacement.to_string());
+        }
+
+        // Run the regex again, from the start of the match, to get the capture groups.
+        let captures = regex.captures_at(line, search_match.start_byte_index)?;
+        if captures.get(0)?.start() != search_match.start_byte_index {
+            return None;
+        }
+
+        let mut it = String::new();
+        captures.expand(replacement, &mut it);
+        Some(it)
+    }
+
+    /// Display col at which the grapheme cluster containing `byte_index` starts.
+    fn start_display_col_index(line: &UnicodeString, byte_index: usize) -> ChUnit {
+        line.vec_segment
+            .iter()
+            .find(|it| byte_index < it.byte_offset + it.byte_size)
+            .map(|it| it.display_col_offset)
+            .unwrap_or(line.display_width)
+    }
+
+    /// Display col right after the grapheme cluster containing the byte before
+    /// `byte_index`.
+    fn end_display_col_index(line: &UnicodeString, byte_index: usize) -> ChUnit {
+        line.vec_segment
+            .iter()
+            .find(|it| byte_index <= it.byte_offset + it.byte_size)
+            .map(|it| it.display_col_offset + it.unicode_width)
+            .unwrap_or(line.display_width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_editor::mock_real_objects_for_editor::make_editor_buffer_with_lines;
+
+    fn ranges(buffer: &EditorBuffer) -> Vec<(usize, usize, usize)> {
+        buffer
+            .search
+            .get_matches()
+            .iter()
+            .map(|it| {
+                (
+                    ch!(@to_usize it.row_index),
+                    ch!(@to_usize it.range.start_display_col_index),
+                    ch!(@to_usize it.range.end_display_col_index),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_literal_search_is_case_insensitive_by_default() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["Foo foo", "bar", "a.foo"]);
+
+        search::set_query(&mut buffer, "foo", SearchOptions::default());
+        assert_eq2!(ranges(&buffer), vec![(0, 0, 3), (0, 4, 7), (2, 2, 5)]);
+
+        let options = SearchOptions {
+            is_case_sensitive: true,
+            ..Default::default()
+        };
+        search::set_query(&mut buffer, "foo", options);
+        assert_eq2!(ranges(&buffer), vec![(0, 4, 7), (2, 2, 5)]);
+
+        // The `.` is not a wildcard.
+        search::set_query(&mut buffer, ".", SearchOptions::default());
+        assert_eq2!(ranges(&buffer), vec![(2, 1, 2)]);
+
+        search::clear(&mut buffer);
+        assert!(!search::is_active(&buffer));
+        assert!(buffer.search.get_matches().is_empty());
+    }
+
+    #[test]
+    fn test_regex_search() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["abc 123", "x 45"]);
+        let options = SearchOptions {
+            is_regex: true,
+            ..Default::default()
+        };
+
+        search::set_query(&mut buffer, r"\d+", options);
+        assert_eq2!(ranges(&buffer), vec![(0, 4, 7), (1, 2, 4)]);
+
+        // Empty matches are skipped.
+        search::set_query(&mut buffer, r"\d*", options);
+        assert_eq2!(ranges(&buffer), vec![(0, 4, 7), (1, 2, 4)]);
+
+        // Invalid regex.
+        search::set_query(&mut buffer, r"(\d", options);
+        assert!(buffer.search.get_maybe_error().is_some());
+        assert!(buffer.search.get_matches().is_empty());
+    }
+
+    #[test]
+    fn test_search_uses_display_col_indices() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["😃 hi 😃 hi"]);
+
+        search::set_query(&mut buffer, "hi", SearchOptions::default());
+        assert_eq2!(ranges(&buffer), vec![(0, 3, 5), (0, 9, 11)]);
+
+        search::set_query(&mut buffer, "😃", SearchOptions::default());
+        assert_eq2!(ranges(&buffer), vec![(0, 0, 2), (0, 6, 8)]);
+    }
+
+    #[test]
+    fn test_matches_are_updated_in_edited_rows() {
+        let mut buffer =
+            make_editor_buffer_with_lines(vec!["foo", "bar", "bar foo", "x"]);
+        search::set_query(&mut buffer, "foo", SearchOptions::default());
+        history::push(&mut buffer);
+        assert_eq2!(ranges(&buffer), vec![(0, 0, 3), (2, 4, 7)]);
+
+        // Insert rows, which moves the matches after them down.
+        let (lines, _, _, _) = buffer.get_mut();
+        lines[1] = UnicodeString::from("foo foo");
+        lines.insert(2, UnicodeString::from("a foo"));
+        history::push(&mut buffer);
+        assert_eq2!(
+            ranges(&buffer),
+            vec![(0, 0, 3), (1, 0, 3), (1, 4, 7), (2, 2, 5), (3, 4, 7)]
+        );
+
+        // Remove rows, which moves the matches after them up.
+        let (lines, _, _, _) = buffer.get_mut();
+        lines.remove(0);
+        lines.remove(0);
+        history::push(&mut buffer);
+        assert_eq2!(ranges(&buffer), vec![(0, 2, 5), (1, 4, 7)]);
+
+        // Undo and redo.
+        history::undo(&mut buffer);
+        assert_eq2!(
+            ranges(&buffer),
+            vec![(0, 0, 3), (1, 0, 3), (1, 4, 7), (2, 2, 5), (3, 4, 7)]
+        );
+        history::undo(&mut buffer);
+        assert_eq2!(ranges(&buffer), vec![(0, 0, 3), (2, 4, 7)]);
+        history::redo(&mut buffer);
+        history::redo(&mut buffer);
+        assert_eq2!(ranges(&buffer), vec![(0, 2, 5), (1, 4, 7)]);
+    }
+
+    #[test]
+    fn test_find_match_wraps_around() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["ab ab", "ab"]);
+        search::set_query(&mut buffer, "ab", SearchOptions::default());
+
+        // Caret is at the start of the first match.
+        let start = |it: Option<SearchMatch>| {
+            let it = it.unwrap();
+            (
+                ch!(@to_usize it.row_index),
+                ch!(@to_usize it.range.start_display_col_index),
+            )
+        };
+        assert_eq2!(search::get_current_match_index(&buffer), Some(0));
+        assert_eq2!(start(search::find_match(&buffer, SearchJump::Next)), (0, 3));
+        assert_eq2!(
+            start(search::find_match(&buffer, SearchJump::Previous)),
+            (1, 0)
+        );
+        assert_eq2!(
+            start(search::find_match(
+                &buffer,
+                SearchJump::AtOrAfter(position!(col_index: 1, row_index: 1))
+            )),
+            (0, 0)
+        );
+        assert_eq2!(
+            search::take_pending_jump(&mut buffer),
+            Some(SearchJump::AtOrAfter(position!(col_index: 0, row_index: 0)))
+        );
+        assert_eq2!(search::take_pending_jump(&mut buffer), None);
+    }
+
+    #[test]
+    fn test_replace_current_can_be_undone() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["one two one"]);
+        search::set_query(&mut buffer, "one", SearchOptions::default());
+
+        assert!(search::replace_current(&mut buffer, "1"));
+        assert_eq2!(buffer.get_as_string_with_newlines(), "1 two one");
+        // The matches are updated, and the next jump is to the following match.
+        assert_eq2!(ranges(&buffer), vec![(0, 6, 9)]);
+        assert_eq2!(
+            search::take_pending_jump(&mut buffer),
+            Some(SearchJump::AtOrAfter(position!(col_index: 1, row_index: 0)))
+        );
+
+        history::undo(&mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "one two one");
+        assert_eq2!(ranges(&buffer), vec![(0, 0, 3), (0, 8, 11)]);
+
+        history::redo(&mut buffer);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "1 two one");
+    }
+
+    #[test]
+    fn test_replace_all_expands_capture_groups() {
+        let mut buffer =
+            make_editor_buffer_with_lines(vec!["a=1, b=2", "nothing", "c=3"]);
+        let options = SearchOptions {
+            is_regex: true,
+            ..Default::default()
+        };
+        search::set_query(&mut buffer, r"(\w)=(\d)", options);
+
+        assert_eq2!(search::replace_all(&mut buffer, "$2:$1"), 3);
+        assert_eq2!(
+            buffer.get_as_string_with_newlines(),
+            "1:a, 2:b\nnothing\n3:c"
+        );
+        assert!(buffer.search.get_matches().is_empty());
+
+        // All the replacements are undone in one step.
+        history::undo(&mut buffer);
+        assert_eq2!(
+            buffer.get_as_string_with_newlines(),
+            "a=1, b=2\nnothing\nc=3"
+        );
+        assert_eq2!(buffer.search.get_matches().len(), 3);
+    }
+
+    #[test]
+    fn test_literal_replacement_is_not_expanded() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["cost"]);
+        search::set_query(&mut buffer, "cost", SearchOptions::default());
+
+        assert_eq2!(search::replace_all(&mut buffer, "$1"), 1);
+        assert_eq2!(buffer.get_as_string_with_newlines(), "$1");
+    }
+}
@@ -73,7 +73,8 @@ use crate::*;
 ///
 /// ## `lines`
 ///
-/// A list of lines representing the document being edited.
+/// A list of lines representing the document being edited. See [EditorLines] for how
+/// they are stored, so that large files can be edited.
 ///
 /// ## `caret_display_position`
 ///
@@ -165,38 +166,116 @@ use crate::*;
 /// in the map represents a row of text in the buffer.
 /// - The row index is the key.
 /// - The value is the [SelectionRange].
+///
+/// ## `secondary_carets`
+///
+/// The fields above describe the primary caret. When there are multiple carets, the
+/// other ones are stored in [EditorContent::secondary_carets], each w/ its own
+/// [SelectionMap]. Their positions are scroll adjusted. Editing events are applied to
+/// each caret in turn (see [multi_caret::for_each_caret]).
+///
+/// ## `file_format`
+///
+/// The [FileFormat] of the file that the buffer was loaded from (w/
+/// [set_lines_from_file_bytes](EditorBuffer::set_lines_from_file_bytes)), so that it
+/// can be saved the same way (w/ [get_as_file_bytes](EditorBuffer::get_as_file_bytes)).
+/// If the file isn't valid UTF-8, then the buffer is [read
+/// only](EditorBuffer::is_read_only).
+///
+/// ## `search`
+///
+/// The [EditorBufferSearch] holds the current search query and all of its matches in the
+/// buffer. Use the functions in [search] to work with it.
 #[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Default)]
 pub struct EditorBuffer {
     pub editor_content: EditorContent,
     pub history: EditorBufferHistory,
     pub render_cache: HashMap<String, RenderOps>,
+    pub search: EditorBufferSearch,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Default)]
 pub struct EditorContent {
-    pub lines: Vec<UnicodeString>,
+    pub lines: EditorLines,
     pub caret_display_position: Position,
     pub scroll_offset: ScrollOffset,
     pub maybe_file_extension: Option<String>,
     pub maybe_file_path: Option<String>,
     pub selection_map: SelectionMap,
+    /// Carets in addition to the primary one above. Use the functions in [multi_caret]
+    /// to work with them.
+    pub secondary_carets: Vec<EditorCaret>,
+    /// Scroll adjusted position of the caret, when the viewport has been scrolled w/ the
+    /// mouse wheel so that it isn't visible. When this is set, `caret_display_position`
+    /// is not used. See [mouse] for more details.
+    pub maybe_caret_outside_viewport: Option<Position>,
+    /// Line endings, final newline and BOM of the file that the lines were loaded from,
+    /// which are kept when it is saved.
+    pub file_format: FileFormat,
 }
 
+/// Undo / redo history for an [EditorBuffer].
+///
+/// Instead of storing a full copy of [EditorContent] for every version, this stores a
+/// list of [EditorHistoryEdit]s. Each one only holds the text that was actually
+/// inserted or deleted, along w/ the caret, scroll offset and selection before and
+/// after the edit. Consecutive typing (see [history::push_and_coalesce_typing]) is
+/// merged into a single undo step.
+///
+/// In order to compute the delta for a change, a snapshot of the lines as of the last
+/// [history::push] is kept around. Since [EditorLines] is copied on write, the snapshot
+/// shares its memory w/ the content, except for the chunks of lines that have changed
+/// since then. So the memory cost of the history is mostly the size of the edits.
 #[derive(Clone, PartialEq, Serialize, Deserialize, GetSize)]
 pub struct EditorBufferHistory {
-    versions: Vec<EditorContent>,
+    edits: Vec<EditorHistoryEdit>,
+    /// Index of the last applied edit in `edits`, `-1` if none are applied.
     current_index: isize,
+    /// Lines as of the last push, undo or redo. [None] if the history has not been
+    /// initialized yet.
+    maybe_committed_lines: Option<EditorLines>,
+    /// Caret, scroll offset and selection before the change that is about to be pushed.
+    caret_state_before_change: EditorHistoryCaretState,
 }
 
 impl Default for EditorBufferHistory {
     fn default() -> Self {
         Self {
-            versions: vec![],
+            edits: vec![],
             current_index: -1,
+            maybe_committed_lines: None,
+            caret_state_before_change: EditorHistoryCaretState::default(),
         }
     }
 }
 
+/// Snapshot of everything besides the lines that undo / redo has to restore.
+#[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Default, Debug)]
+pub struct EditorHistoryCaretState {
+    pub caret_display_position: Position,
+    pub scroll_offset: ScrollOffset,
+    pub selection_map: SelectionMap,
+    pub secondary_carets: Vec<EditorCaret>,
+}
+
+/// A single undo step. The edited region spans `row_count_before` rows starting at
+/// `start_row` before the edit, and `row_count_after` rows after it. Joining the rows of
+/// the region w/ `\n` gives a string in which `deleted_text` was replaced by
+/// `inserted_text` at `byte_offset`.
+#[derive(Clone, PartialEq, Serialize, Deserialize, GetSize, Debug)]
+pub struct EditorHistoryEdit {
+    pub start_row: usize,
+    pub row_count_before: usize,
+    pub row_count_after: usize,
+    pub byte_offset: usize,
+    pub deleted_text: String,
+    pub inserted_text: String,
+    pub caret_state_before: EditorHistoryCaretState,
+    pub caret_state_after: EditorHistoryCaretState,
+    /// Whether subsequent typing can be merged into this edit.
+    pub is_typing: bool,
+}
+
 pub mod history {
     use super::*;
 
@@ -208,22 +287,80 @@ pub mod history {
         editor_buffer.history = EditorBufferHistory::default();
     }
 
-    pub fn push(editor_buffer: &mut EditorBuffer) {
+    /// Remember the caret, scroll offset and selection, so that undoing the next change
+    /// that is pushed restores them. Call this before applying an [EditorEvent].
+    pub fn mark_caret_before_change(editor_buffer: &mut EditorBuffer) {
+        editor_buffer.history.caret_state_before_change =
+            EditorHistoryCaretState::from(&editor_buffer.editor_content);
+    }
+
+    /// Record the change made to the content since the last push as a single undo step.
+    /// If the history is empty, then the current content becomes the starting point and
+    /// nothing can be undone past it. If there is no change, nothing is recorded.
+    pub fn push(editor_buffer: &mut EditorBuffer) { push_impl(editor_buffer, false); }
+
+    /// Same as [push], except that if the change is typing on a single line that
+    /// continues right where the previous undo step (also typing) left off, it is merged
+    /// into that step. A new step is started after whitespace is typed, so undo removes a
+    /// word at a time.
+    pub fn push_and_coalesce_typing(editor_buffer: &mut EditorBuffer) {
+        push_impl(editor_buffer, true);
+    }
+
+    fn push_impl(editor_buffer: &mut EditorBuffer, is_typing: bool) {
         // Invalidate the content cache, since the content just changed.
         cache::clear(editor_buffer);
 
-        let content_copy = editor_buffer.editor_content.clone();
+        let caret_state_after =
+            EditorHistoryCaretState::from(&editor_buffer.editor_content);
+
+        let Some(committed_lines) = editor_buffer.history.maybe_committed_lines.as_ref()
+        else {
+            // Initialize the history.
+            editor_buffer.history.maybe_committed_lines =
+                Some(editor_buffer.editor_content.lines.clone());
+            editor_buffer.history.caret_state_before_change = caret_state_after;
+
+            // Keep the search matches in sync w/ the content.
+            search::update_matches(editor_buffer);
+            return;
+        };
+
+        let caret_state_before =
+            std::mem::take(&mut editor_buffer.history.caret_state_before_change);
+        editor_buffer.history.caret_state_before_change = caret_state_after.clone();
+
+        let Some(edit) = delta::diff(
+            committed_lines,
+            &editor_buffer.editor_content.lines,
+            caret_state_before,
+            caret_state_after,
+            is_typing,
+        ) else {
+            // Nothing changed (eg: copy, or a cut w/ no selection).
+            return;
+        };
+
+        // Keep the search matches in sync w/ the content, by only searching the rows
+        // that were edited.
+        search::update_matches_in_rows(
+            editor_buffer,
+            edit.start_row,
+            edit.row_count_before,
+            edit.row_count_after,
+        );
+
+        // Keep the committed lines in sync w/ the content.
+        editor_buffer.history.maybe_committed_lines =
+            Some(editor_buffer.editor_content.lines.clone());
 
         // Delete the history from the current version index to the end.
-        if let Some(current_index) = editor_buffer.history.get_current_index() {
-            editor_buffer
-                .history
-                .versions
-                .truncate(convert_isize_to_usize(current_index + 1));
-        }
+        editor_buffer.history.edits.truncate(convert_isize_to_usize(
+            editor_buffer.history.current_index + 1,
+        ));
 
         // Normal history insertion.
-        editor_buffer.history.push_content(content_copy);
+        editor_buffer.history.push_edit(edit);
 
         if DEBUG_TUI_COPY_PASTE {
             log_debug(format!(
@@ -234,13 +371,14 @@ pub mod history {
     }
 
     pub fn undo(editor_buffer: &mut EditorBuffer) {
+        // Make sure that any changes that weren't pushed can be undone too.
+        push(editor_buffer);
+
         // Invalidate the content cache, since the content just changed.
         cache::clear(editor_buffer);
 
-        let retain_caret_position = editor_buffer.editor_content.caret_display_position;
-        if let Some(content) = editor_buffer.history.previous_content() {
-            editor_buffer.editor_content = content;
-            editor_buffer.editor_content.caret_display_position = retain_caret_position;
+        if let Some(edit) = editor_buffer.history.previous_edit() {
+            apply_edit(editor_buffer, &edit, delta::Direction::Reverse);
         }
 
         if DEBUG_TUI_COPY_PASTE {
@@ -252,8 +390,8 @@ pub mod history {
         // Invalidate the content cache, since the content just changed.
         cache::clear(editor_buffer);
 
-        if let Some(content) = editor_buffer.history.next_content() {
-            editor_buffer.editor_content = content;
+        if let Some(edit) = editor_buffer.history.next_edit() {
+            apply_edit(editor_buffer, &edit, delta::Direction::Forward);
         }
 
         if DEBUG_TUI_COPY_PASTE {
@@ -261,96 +399,267 @@ pub mod history {
         }
     }
 
-    impl EditorBufferHistory {
-        pub(crate) fn is_empty(&self) -> bool { self.versions.is_empty() }
+    fn apply_edit(
+        editor_buffer: &mut EditorBuffer,
+        edit: &EditorHistoryEdit,
+        direction: delta::Direction,
+    ) {
+        delta::apply(&mut editor_buffer.editor_content.lines, edit, direction);
 
-        fn get_last_index(&self) -> Option<ChUnit> {
-            if self.is_empty() {
-                None
-            } else {
-                Some(ch!(self.versions.len()) - 1)
+        // Keep the committed lines in sync w/ the content.
+        editor_buffer.history.maybe_committed_lines =
+            Some(editor_buffer.editor_content.lines.clone());
+
+        let caret_state = match direction {
+            delta::Direction::Forward => &edit.caret_state_after,
+            delta::Direction::Reverse => &edit.caret_state_before,
+        };
+        caret_state.restore(&mut editor_buffer.editor_content);
+        mark_caret_before_change(editor_buffer);
+
+        // Keep the search matches in sync w/ the content, by only searching the rows
+        // that were edited.
+        let (row_count_before, row_count_after) = match direction {
+            delta::Direction::Forward => (edit.row_count_before, edit.row_count_after),
+            delta::Direction::Reverse => (edit.row_count_after, edit.row_count_before),
+        };
+        search::update_matches_in_rows(
+            editor_buffer,
+            edit.start_row,
+            row_count_before,
+            row_count_after,
+        );
+    }
+
+    impl From<&EditorContent> for EditorHistoryCaretState {
+        fn from(content: &EditorContent) -> Self {
+            Self {
+                caret_display_position: content.caret_display_position,
+                scroll_offset: content.scroll_offset,
+                selection_map: content.selection_map.clone(),
+                secondary_carets: content.secondary_carets.clone(),
             }
         }
+    }
 
-        fn get_current_index(&self) -> Option<isize> {
-            if self.is_empty() {
-                None
-            } else {
-                Some(self.current_index)
-            }
+    impl EditorHistoryCaretState {
+        fn restore(&self, content: &mut EditorContent) {
+            content.caret_display_position = self.caret_display_position;
+            content.scroll_offset = self.scroll_offset;
+            content.selection_map = self.selection_map.clone();
+            content.secondary_carets = self.secondary_carets.clone();
+        }
+    }
+
+    impl EditorBufferHistory {
+        pub(crate) fn is_empty(&self) -> bool {
+            self.edits.is_empty() && self.maybe_committed_lines.is_none()
         }
 
-        fn increment_index(&mut self) {
-            // Don't do anything if is empty.
-            if let Some(max_index) = self.get_last_index() {
-                // Make sure it doesn't go past the end.
-                if self.current_index == ch!(@to_isize max_index) {
+        pub fn get_edits(&self) -> &Vec<EditorHistoryEdit> { &self.edits }
+
+        pub fn get_current_index(&self) -> isize { self.current_index }
+
+        fn push_edit(&mut self, edit: EditorHistoryEdit) {
+            if let Some(last_edit) = self.edits.last_mut() {
+                if last_edit.can_coalesce_with(&edit) {
+                    last_edit.inserted_text.push_str(&edit.inserted_text);
+                    last_edit.caret_state_after = edit.caret_state_after;
                     return;
                 }
-                // Increment index.
-                self.current_index += 1;
             }
+            self.edits.push(edit);
+            self.current_index = ch!(@to_isize ch!(self.edits.len()) - 1);
         }
 
-        fn decrement_index(&mut self) {
-            // Don't do anything if is empty.
-            if let Some(current_index) = self.get_current_index() {
-                // Make sure it doesn't go past the start.
-                if current_index == 0 {
-                    return;
-                }
-                // Decrement index.
-                self.current_index -= 1;
+        fn previous_edit(&mut self) -> Option<EditorHistoryEdit> {
+            // At start of history.
+            if self.current_index < 0 {
+                return None;
             }
+
+            let it = self
+                .edits
+                .get(convert_isize_to_usize(self.current_index))
+                .cloned();
+
+            // Decrement index.
+            self.current_index -= 1;
+
+            it
+        }
+
+        fn next_edit(&mut self) -> Option<EditorHistoryEdit> {
+            // At end of history.
+            let next_index = convert_isize_to_usize(self.current_index + 1);
+            let it = self.edits.get(next_index).cloned()?;
+
+            // Increment index.
+            self.current_index += 1;
+
+            Some(it)
         }
+    }
 
-        fn push_content(&mut self, content: EditorContent) {
-            self.versions.push(content);
-            self.increment_index();
+    impl EditorHistoryEdit {
+        fn is_single_line_insert(&self) -> bool {
+            self.row_count_before == 1
+                && self.row_count_after == 1
+                && self.deleted_text.is_empty()
         }
 
-        fn previous_content(&mut self) -> Option<EditorContent> {
-            if self.is_empty() {
-                None
-            } else {
-                // At start of history.
-                if self.current_index == -1 {
-                    return None;
-                }
+        fn can_coalesce_with(&self, next: &EditorHistoryEdit) -> bool {
+            let ends_word = self.inserted_text.ends_with(|it: char| it.is_whitespace())
+                && !next
+                    .inserted_text
+                    .starts_with(|it: char| it.is_whitespace());
+
+            self.is_typing
+                && next.is_typing
+                && self.is_single_line_insert()
+                && next.is_single_line_insert()
+                && self.start_row == next.start_row
+                && self.byte_offset + self.inserted_text.len() == next.byte_offset
+                && !ends_word
+        }
+    }
+}
 
-                // Decrement index.
-                self.decrement_index();
+/// Compute and apply [EditorHistoryEdit]s.
+mod delta {
+    use super::*;
 
-                // Return item at index.
-                let it = self
-                    .versions
-                    .get(convert_isize_to_usize(self.current_index))
-                    .cloned();
-                it
-            }
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum Direction {
+        Forward,
+        Reverse,
+    }
+
+    /// Returns [None] if `old_lines` and `new_lines` have the same content.
+    pub fn diff(
+        old_lines: &EditorLines,
+        new_lines: &EditorLines,
+        caret_state_before: EditorHistoryCaretState,
+        caret_state_after: EditorHistoryCaretState,
+        is_typing: bool,
+    ) -> Option<EditorHistoryEdit> {
+        // Find the rows that are the same at the start and the end.
+        let max_common_row_count = old_lines.len().min(new_lines.len());
+        let common_prefix_row_count = old_lines.get_common_prefix_len(new_lines);
+        let common_suffix_row_count = old_lines.get_common_suffix_len(
+            new_lines,
+            max_common_row_count - common_prefix_row_count,
+        );
+
+        let start_row = common_prefix_row_count;
+        let row_count_before =
+            old_lines.len() - common_prefix_row_count - common_suffix_row_count;
+        let row_count_after =
+            new_lines.len() - common_prefix_row_count - common_suffix_row_count;
+
+        if row_count_before == 0 && row_count_after == 0 {
+            return None;
         }
 
-        fn next_content(&mut self) -> Option<EditorContent> {
-            if self.is_empty() {
-                None
-            } else {
-                // At end of versions.
-                if let Some(max_index) = self.get_last_index() {
-                    let max_index = ch!(@to_isize max_index);
-                    if self.current_index == max_index {
-                        return None;
-                    }
-                }
+        let join_rows = |lines: &EditorLines, row_count: usize| {
+            lines
+                .iter_str()
+                .skip(start_row)
+                .take(row_count)
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let old_text = join_rows(old_lines, row_count_before);
+        let new_text = join_rows(new_lines, row_count_after);
+
+        // Only keep the text that actually changed in the region.
+        let prefix_len = common_prefix_byte_len(&old_text, &new_text);
+        let suffix_len =
+            common_suffix_byte_len(&old_text[prefix_len..], &new_text[prefix_len..]);
+
+        Some(EditorHistoryEdit {
+            start_row,
+            row_count_before,
+            row_count_after,
+            byte_offset: prefix_len,
+            deleted_text: old_text[prefix_len..old_text.len() - suffix_len].to_string(),
+            inserted_text: new_text[prefix_len..new_text.len() - suffix_len].to_string(),
+            caret_state_before,
+            caret_state_after,
+            is_typing,
+        })
+    }
+
+    fn common_prefix_byte_len(lhs: &str, rhs: &str) -> usize {
+        lhs.char_indices()
+            .zip(rhs.chars())
+            .find(|((_, lhs_char), rhs_char)| lhs_char != rhs_char)
+            .map(|((byte_index, _), _)| byte_index)
+            .unwrap_or(lhs.len().min(rhs.len()))
+    }
 
-                // Increment index.
-                self.increment_index();
+    fn common_suffix_byte_len(lhs: &str, rhs: &str) -> usize {
+        lhs.chars()
+            .rev()
+            .zip(rhs.chars().rev())
+            .take_while(|(lhs_char, rhs_char)| lhs_char == rhs_char)
+            .map(|(it, _)| it.len_utf8())
+            .sum()
+    }
 
-                // Return item at index.
-                self.versions
-                    .get(convert_isize_to_usize(self.current_index))
-                    .cloned()
-            }
+    /// Returns the rows that replace the edited region (`region_rows`).
+    fn replace_region<'a>(
+        region_rows: impl Iterator<Item = &'a str>,
+        edit: &EditorHistoryEdit,
+        direction: Direction,
+    ) -> Vec<String> {
+        let (remove_text, insert_text, result_row_count) = match direction {
+            Direction::Forward => (
+                &edit.deleted_text,
+                &edit.inserted_text,
+                edit.row_count_after,
+            ),
+            Direction::Reverse => (
+                &edit.inserted_text,
+                &edit.deleted_text,
+                edit.row_count_before,
+            ),
+        };
+
+        if result_row_count == 0 {
+            return vec![];
         }
+
+        let mut text = region_rows.collect::<Vec<_>>().join("\n");
+        text.replace_range(
+            edit.byte_offset..edit.byte_offset + remove_text.len(),
+            insert_text,
+        );
+        text.split('\n').map(String::from).collect()
+    }
+
+    fn region_row_count(edit: &EditorHistoryEdit, direction: Direction) -> usize {
+        match direction {
+            Direction::Forward => edit.row_count_before,
+            Direction::Reverse => edit.row_count_after,
+        }
+    }
+
+    pub fn apply(
+        lines: &mut EditorLines,
+        edit: &EditorHistoryEdit,
+        direction: Direction,
+    ) {
+        let row_count = region_row_count(edit, direction);
+        let new_rows = replace_region(
+            lines.iter_str().skip(edit.start_row).take(row_count),
+            edit,
+            direction,
+        );
+        lines.splice(
+            edit.start_row..edit.start_row + row_count,
+            new_rows.into_iter().map(UnicodeString::from),
+        );
     }
 }
 
@@ -358,160 +667,280 @@ pub mod history {
 mod history_tests {
     use super::*;
 
+    fn lines_of(editor_buffer: &EditorBuffer) -> Vec<String> {
+        editor_buffer
+            .get_lines()
+            .iter()
+            .map(|it| it.string.clone())
+            .collect()
+    }
+
     #[test]
     fn test_push_default() {
         let mut editor_buffer = EditorBuffer::default();
-        let content = editor_buffer.editor_content.clone();
 
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
-
-        let history_stack = editor_buffer.history.versions;
-        assert_eq2!(history_stack.len(), 1);
-        assert_eq2!(history_stack[0], content);
+        assert_eq2!(editor_buffer.history.current_index, -1);
+        assert_eq2!(editor_buffer.history.edits.len(), 0);
+        assert_eq2!(editor_buffer.history.is_empty(), false);
     }
 
     #[test]
     fn test_push_with_contents() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")].into();
+        history::push(&mut editor_buffer);
+        assert_eq2!(editor_buffer.history.current_index, -1);
+
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("abXc")].into();
         history::push(&mut editor_buffer);
         assert_eq2!(editor_buffer.history.current_index, 0);
 
-        let history_stack = editor_buffer.history.versions;
-        assert_eq2!(history_stack.len(), 1);
-        assert_eq2!(history_stack[0].lines.len(), 1);
-        assert_eq2!(history_stack[0].lines[0].string, "abc");
+        // Only the changed text is stored.
+        let edits = editor_buffer.history.edits;
+        assert_eq2!(edits.len(), 1);
+        assert_eq2!(edits[0].start_row, 0);
+        assert_eq2!(edits[0].byte_offset, 2);
+        assert_eq2!(edits[0].deleted_text, "");
+        assert_eq2!(edits[0].inserted_text, "X");
+    }
+
+    #[test]
+    fn test_push_without_change_is_ignored() {
+        let mut editor_buffer = EditorBuffer::default();
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")].into();
+        history::push(&mut editor_buffer);
+        history::push(&mut editor_buffer);
+        history::push(&mut editor_buffer);
+        assert_eq2!(editor_buffer.history.current_index, -1);
+        assert_eq2!(editor_buffer.history.edits.len(), 0);
     }
 
     #[test]
     fn test_push_and_drop_future_redos() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 1);
+        assert_eq2!(editor_buffer.history.current_index, 0);
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("ghi")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("ghi")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 2);
+        assert_eq2!(editor_buffer.history.current_index, 1);
 
         // Do two undos.
         history::undo(&mut editor_buffer);
         history::undo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), vec!["abc"]);
 
         // Push new content. Should drop future redos.
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("xyz")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("xyz")].into();
         history::push(&mut editor_buffer);
 
-        let history = editor_buffer.history;
-        assert_eq2!(history.current_index, 1);
+        let history = &editor_buffer.history;
+        assert_eq2!(history.current_index, 0);
+        assert_eq2!(history.edits.len(), 1);
+        assert_eq2!(history.edits[0].deleted_text, "abc");
+        assert_eq2!(history.edits[0].inserted_text, "xyz");
 
-        let history_stack = history.versions;
-        assert_eq2!(history_stack.len(), 2);
-        assert_eq2!(history_stack[0].lines.len(), 1);
-        assert_eq2!(history_stack[0].lines[0].string, "abc");
-        assert_eq2!(history_stack[1].lines.len(), 1);
-        assert_eq2!(history_stack[1].lines[0].string, "xyz");
+        // Nothing to redo.
+        history::redo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), vec!["xyz"]);
     }
 
     #[test]
     fn test_single_undo() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
 
-        // Undo.
+        // Undo, can't go past the start of the history.
         history::undo(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
+        assert_eq2!(editor_buffer.history.current_index, -1);
+        assert_eq2!(lines_of(&editor_buffer), vec!["abc"]);
     }
 
     #[test]
     fn test_many_undo() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 1);
+        assert_eq2!(editor_buffer.history.current_index, 0);
         let copy_of_editor_content = editor_buffer.editor_content.clone();
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("ghi")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("ghi")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 2);
+        assert_eq2!(editor_buffer.history.current_index, 1);
 
         // Undo.
         history::undo(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 1);
+        assert_eq2!(editor_buffer.history.current_index, 0);
         assert_eq2!(editor_buffer.editor_content, copy_of_editor_content);
 
-        let history_stack = editor_buffer.history.versions;
-        assert_eq2!(history_stack.len(), 3);
-        assert_eq2!(history_stack[0].lines.len(), 1);
-        assert_eq2!(history_stack[0].lines[0].string, "abc");
-        assert_eq2!(history_stack[1].lines.len(), 1);
-        assert_eq2!(history_stack[1].lines[0].string, "def");
-        assert_eq2!(history_stack[2].lines.len(), 1);
-        assert_eq2!(history_stack[2].lines[0].string, "ghi");
+        // Undo.
+        history::undo(&mut editor_buffer);
+        assert_eq2!(editor_buffer.history.current_index, -1);
+        assert_eq2!(lines_of(&editor_buffer), vec!["abc"]);
+
+        assert_eq2!(editor_buffer.history.edits.len(), 2);
     }
 
     #[test]
     fn test_multiple_undos() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 1);
+        assert_eq2!(editor_buffer.history.current_index, 0);
 
         // Undo multiple times.
         history::undo(&mut editor_buffer);
         history::undo(&mut editor_buffer);
         history::undo(&mut editor_buffer);
 
-        assert_eq2!(editor_buffer.history.current_index, 0);
+        assert_eq2!(editor_buffer.history.current_index, -1);
+        assert_eq2!(lines_of(&editor_buffer), vec!["abc"]);
     }
 
     #[test]
     fn test_undo_and_multiple_redos() {
         let mut editor_buffer = EditorBuffer::default();
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("abc")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
 
-        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")];
+        editor_buffer.editor_content.lines = vec![UnicodeString::from("def")].into();
         history::push(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 1);
+        assert_eq2!(editor_buffer.history.current_index, 0);
         let snapshot_content = editor_buffer.editor_content.clone();
 
         // Undo.
         history::undo(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 0);
+        assert_eq2!(editor_buffer.history.current_index, -1);
 
         // Redo.
         history::redo(&mut editor_buffer);
-        assert_eq2!(editor_buffer.history.current_index, 1);
+        assert_eq2!(editor_buffer.history.current_index, 0);
 
         // Current state.
         assert_eq2!(editor_buffer.editor_content, snapshot_content);
 
         // Redo.
         history::redo(&mut editor_buffer);
+        assert_eq2!(editor_buffer.history.current_index, 0);
+        assert_eq2!(editor_buffer.editor_content, snapshot_content);
+    }
+
+    #[test]
+    fn test_undo_redo_multiline_and_caret() {
+        let mut editor_buffer = EditorBuffer::default();
+        editor_buffer.editor_content.lines = vec!["one", "two", "three"].into();
+        history::push(&mut editor_buffer);
+
+        // Split "two" into two lines, and remove "three".
+        history::mark_caret_before_change(&mut editor_buffer);
+        editor_buffer.editor_content.lines = vec!["one", "t", "wo"].into();
+        editor_buffer.editor_content.caret_display_position =
+            position!(col_index: 0, row_index: 2);
+        history::push(&mut editor_buffer);
+
+        let edit = &editor_buffer.history.edits[0];
+        assert_eq2!(edit.start_row, 1);
+        assert_eq2!(edit.row_count_before, 2);
+        assert_eq2!(edit.row_count_after, 2);
+
+        history::undo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), vec!["one", "two", "three"]);
+        assert_eq2!(
+            editor_buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        history::redo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), vec!["one", "t", "wo"]);
+        assert_eq2!(
+            editor_buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 0, row_index: 2)
+        );
+    }
+
+    #[test]
+    fn test_undo_redo_add_and_remove_lines() {
+        let mut editor_buffer = EditorBuffer::default();
+        editor_buffer.editor_content.lines = vec!["a", "b"].into();
+        history::push(&mut editor_buffer);
+
+        editor_buffer.editor_content.lines = vec!["a", "b", ""].into();
+        history::push(&mut editor_buffer);
+
+        editor_buffer.editor_content.lines = vec!["b", ""].into();
+        history::push(&mut editor_buffer);
+
+        editor_buffer.editor_content.lines = EditorLines::new();
+        history::push(&mut editor_buffer);
+
+        history::undo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), vec!["b", ""]);
+        history::undo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), vec!["a", "b", ""]);
+        history::undo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), vec!["a", "b"]);
 
-        let history_stack = editor_buffer.history.versions;
-        assert_eq2!(history_stack.len(), 2);
-        assert_eq2!(history_stack[0].lines.len(), 1);
-        assert_eq2!(history_stack[0].lines[0].string, "abc");
-        assert_eq2!(history_stack[1].lines.len(), 1);
-        assert_eq2!(history_stack[1].lines[0].string, "def");
+        history::redo(&mut editor_buffer);
+        history::redo(&mut editor_buffer);
+        history::redo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_coalesce_typing() {
+        let mut editor_buffer = EditorBuffer::default();
+        editor_buffer.editor_content.lines = vec![""].into();
+        history::push(&mut editor_buffer);
+
+        for text in ["h", "hi", "hi ", "hi 😃", "hi 😃!"] {
+            editor_buffer.editor_content.lines = vec![text].into();
+            history::push_and_coalesce_typing(&mut editor_buffer);
+        }
+
+        // One undo step per word.
+        let edits = &editor_buffer.history.edits;
+        assert_eq2!(edits.len(), 2);
+        assert_eq2!(edits[0].inserted_text, "hi ");
+        assert_eq2!(edits[1].inserted_text, "😃!");
+
+        history::undo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), vec!["hi "]);
+        history::undo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), vec![""]);
+        history::redo(&mut editor_buffer);
+        history::redo(&mut editor_buffer);
+        assert_eq2!(lines_of(&editor_buffer), vec!["hi 😃!"]);
+    }
+
+    #[test]
+    fn test_coalesce_typing_stops_at_non_typing_edit() {
+        let mut editor_buffer = EditorBuffer::default();
+        editor_buffer.editor_content.lines = vec![""].into();
+        history::push(&mut editor_buffer);
+
+        editor_buffer.editor_content.lines = vec!["a"].into();
+        history::push_and_coalesce_typing(&mut editor_buffer);
+
+        // Eg: a paste.
+        editor_buffer.editor_content.lines = vec!["ab"].into();
+        history::push(&mut editor_buffer);
+
+        editor_buffer.editor_content.lines = vec!["abc"].into();
+        history::push_and_coalesce_typing(&mut editor_buffer);
+
+        assert_eq2!(editor_buffer.history.edits.len(), 3);
     }
 }
 
@@ -535,7 +964,7 @@ mod constructor {
 
             Self {
                 editor_content: EditorContent {
-                    lines: vec![UnicodeString::default()],
+                    lines: vec![UnicodeString::default()].into(),
                     maybe_file_extension: maybe_file_extension.clone(),
                     maybe_file_path: maybe_file_path.clone(),
                     ..Default::default()
@@ -551,9 +980,21 @@ pub mod cache {
 
     pub fn clear(editor_buffer: &mut EditorBuffer) { editor_buffer.render_cache.clear(); }
 
-    /// Cache key is combination of scroll_offset and window_size.
-    fn generate_key(editor_buffer: &EditorBuffer, window_size: Size) -> String {
-        format!("{}{}", editor_buffer.get_scroll_offset(), window_size,)
+    /// Cache key is combination of scroll_offset, window_size and where the editor is
+    /// rendered (since the render ops are positioned in its box, which moves when the
+    /// layout around it changes).
+    pub fn generate_key(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        window_size: Size,
+    ) -> String {
+        format!(
+            "{}{}{}{}",
+            editor_buffer.get_scroll_offset(),
+            window_size,
+            editor_engine.current_box.style_adjusted_origin_pos,
+            editor_engine.current_box.style_adjusted_bounds_size,
+        )
     }
 
     /// Render the content of the editor buffer to the screen from the cache if the content
@@ -562,6 +1003,7 @@ pub mod cache {
     /// The cache miss occurs if
     /// - Scroll Offset changes
     /// - Window size changes
+    /// - The box that the editor is rendered in moves or is resized
     /// - Content of the editor changes
     pub fn render_content(
         editor_buffer: &mut EditorBuffer,
@@ -570,7 +1012,7 @@ pub mod cache {
         has_focus: &mut HasFocus,
         render_ops: &mut RenderOps,
     ) {
-        let key = generate_key(editor_buffer, window_size);
+        let key = generate_key(editor_buffer, editor_engine, window_size);
         if let Some(cached_output) = editor_buffer.render_cache.get(&key) {
             // Cache hit
             *render_ops = cached_output.clone();
@@ -581,14 +1023,14 @@ pub mod cache {
         // - Content has been modified.
         // - Scroll Offset or Window size has been modified.
         editor_buffer.render_cache.clear();
-        let render_args = RenderArgs {
+        let mut render_args = RenderArgs {
             editor_engine,
             editor_buffer,
             has_focus,
         };
 
         // Re-render content, generate & write to render_ops.
-        EditorEngineApi::render_content(&render_args, render_ops);
+        EditorEngineApi::render_content(&mut render_args, render_ops);
 
         // Snapshot the render_ops in the cache.
         editor_buffer.render_cache.insert(key, render_ops.clone());
@@ -634,40 +1076,35 @@ pub mod access_and_mutate {
             }
         }
 
-        pub fn get_lines(&self) -> &Vec<UnicodeString> { &self.editor_content.lines }
+        pub fn get_lines(&self) -> &EditorLines { &self.editor_content.lines }
 
         pub fn get_as_string_with_comma_instead_of_newlines(&self) -> String {
-            self.get_lines()
-                .iter()
-                .map(|it| it.string.clone())
-                .collect::<Vec<String>>()
-                .join(", ")
+            self.get_lines().iter_str().collect::<Vec<_>>().join(", ")
         }
 
         pub fn get_as_string_with_newlines(&self) -> String {
-            self.get_lines()
-                .iter()
-                .map(|it| it.string.clone())
-                .collect::<Vec<String>>()
-                .join("\n")
+            self.get_lines().iter_str().collect::<Vec<_>>().join("\n")
         }
 
         pub fn set_lines(&mut self, lines: Vec<String>) {
-            // Set lines.
-            self.editor_content.lines =
-                lines.into_iter().map(UnicodeString::from).collect();
+            // Set lines. They are segmented into grapheme clusters lazily.
+            self.editor_content.lines = EditorLines::from(lines);
 
             // Reset caret.
             self.editor_content.caret_display_position = Position::default();
 
             // Reset scroll_offset.
             self.editor_content.scroll_offset = ScrollOffset::default();
+            self.editor_content.maybe_caret_outside_viewport = None;
 
             // Empty the content render cache.
             cache::clear(self);
 
             // Reset undo/redo history.
             history::clear(self);
+
+            // Find the search matches in the new lines.
+            search::update_matches(self);
         }
 
         /// Returns the current caret position in two variants:
@@ -678,6 +1115,11 @@ pub mod access_and_mutate {
             match kind {
                 CaretKind::Raw => self.editor_content.caret_display_position,
                 CaretKind::ScrollAdjusted => {
+                    if let Some(position) =
+                        self.editor_content.maybe_caret_outside_viewport
+                    {
+                        return position;
+                    }
                     position! {
                       col_index: Self::calc_scroll_adj_caret_col(&self.editor_content.caret_display_position, &self.editor_content.scroll_offset),
                       row_index: Self::calc_scroll_adj_caret_row(&self.editor_content.caret_display_position, &self.editor_content.scroll_offset)
@@ -707,7 +1149,7 @@ pub mod access_and_mutate {
         }
 
         /// Returns:
-        /// 1. /* lines */ &mut `Vec<UnicodeString>`,
+        /// 1. /* lines */ &mut [EditorLines],
         /// 2. /* caret */ &mut Position,
         /// 3. /* scroll_offset */ &mut ScrollOffset,
         ///
@@ -718,7 +1160,7 @@ pub mod access_and_mutate {
         pub fn get_mut(
             &mut self,
         ) -> (
-            /* lines */ &mut Vec<UnicodeString>,
+            /* lines */ &mut EditorLines,
             /* caret */ &mut Position,
             /* scroll_offset */ &mut ScrollOffset,
             /* selection_map */ &mut SelectionMap,
@@ -785,11 +1227,11 @@ pub mod debug_format_helpers {
             write! {
                 f,
                 "\n\tEditorBufferHistory [                           \n \
-                \t├ stack: {0}, size: {1}                            \n \
+                \t├ edits: {0}, size: {1}                            \n \
                 \t└ index: {2}                                       \n \
                 \t]",
-                /* 0 */ self.versions.len(),
-                /* 1 */ self.versions.get_heap_size(),
+                /* 0 */ self.edits.len(),
+                /* 1 */ self.get_heap_size(),
                 /* 2 */ self.current_index
             }
         }
@@ -17,11 +17,19 @@
 
 // Attach.
 pub mod editor_buffer_clipboard_support;
+pub mod editor_buffer_file_format;
+pub mod editor_buffer_lines;
+pub mod editor_buffer_multi_caret_support;
+pub mod editor_buffer_search_support;
 pub mod editor_buffer_selection_support;
 pub mod editor_buffer_struct;
 pub mod selection_map;
 
 // Re-export.
+pub use editor_buffer_file_format::*;
+pub use editor_buffer_lines::*;
+pub use editor_buffer_multi_caret_support::*;
+pub use editor_buffer_search_support::*;
 pub use editor_buffer_selection_support::*;
 pub use editor_buffer_struct::*;
 pub use selection_map::*;
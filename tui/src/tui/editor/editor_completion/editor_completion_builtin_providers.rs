@@ -0,0 +1,168 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::HashMap, path::PathBuf};
+
+use async_trait::async_trait;
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Completes the word at the caret w/ the other words in the buffer. Words that occur
+/// more often are ranked higher.
+#[derive(Clone, Debug, Default)]
+pub struct WordCompletionProvider;
+
+#[async_trait]
+impl CompletionProvider for WordCompletionProvider {
+    async fn complete(&self, request: &CompletionRequest) -> Vec<CompletionItem> {
+        let Some((replace_range, prefix)) = request.get_word_prefix() else {
+            return vec![];
+        };
+
+        let mut word_count_map: HashMap<&str, u32> = HashMap::new();
+        for (row_index, line) in request.lines.iter().enumerate() {
+            for word_range in line.get_word_ranges() {
+                // Skip the word that is being typed.
+                let is_word_at_caret = ch!(row_index) == request.caret.row_index
+                    && word_range.start_display_col_index
+                        == replace_range.start_display_col_index;
+                if is_word_at_caret {
+                    continue;
+                }
+                let word = line.clip_to_range(word_range);
+                *word_count_map.entry(word).or_default() += 1;
+            }
+        }
+
+        word_count_map
+            .into_iter()
+            .filter(|(word, _)| *word != prefix)
+            .filter_map(|(word, count)| {
+                let score = get_match_score(word, prefix)?;
+                let mut it = CompletionItem::new(
+                    word,
+                    replace_range,
+                    score * 1000 + std::cmp::min(count, 999),
+                );
+                it.maybe_detail = Some("word".to_string());
+                Some(it)
+            })
+            .collect()
+    }
+}
+
+/// Completes file paths. This kicks in when the text before the caret (up to the
+/// previous whitespace, quote, or bracket) contains a `/`, and offers the entries in
+/// that directory that match what has been typed after the last `/`. Relative paths are
+/// resolved against the directory of the file being edited, or else `maybe_base_dir`,
+/// or else the current directory. Hidden entries are only offered if the name being
+/// typed starts w/ a `.`.
+#[derive(Clone, Debug, Default)]
+pub struct FilePathCompletionProvider {
+    pub maybe_base_dir: Option<PathBuf>,
+}
+
+impl FilePathCompletionProvider {
+    /// Characters that can't be part of a path being typed.
+    const PATH_DELIMITERS: &'static str = "\"'`()<>[]{},;=|";
+
+    /// Returns the path that is being typed before the caret, if any.
+    fn get_path_before_caret(text_before_caret: &str) -> Option<&str> {
+        let path = text_before_caret
+            .rsplit(|it: char| it.is_whitespace() || Self::PATH_DELIMITERS.contains(it))
+            .next()?;
+        if path.contains('/') {
+            Some(path)
+        } else {
+            None
+        }
+    }
+
+    fn resolve_dir(&self, request: &CompletionRequest, dir: &str) -> PathBuf {
+        if dir.starts_with('/') {
+            return PathBuf::from(dir);
+        }
+        if let Some(home_relative_dir) = dir.strip_prefix('~') {
+            if let Ok(home_dir) = std::env::var("HOME") {
+                return PathBuf::from(format!("{home_dir}{home_relative_dir}"));
+            }
+        }
+
+        let maybe_file_dir = request
+            .maybe_file_path
+            .as_ref()
+            .and_then(|file_path| PathBuf::from(file_path).parent().map(PathBuf::from));
+        let base_dir = match (maybe_file_dir, &self.maybe_base_dir) {
+            (Some(file_dir), _) if !file_dir.as_os_str().is_empty() => file_dir,
+            (_, Some(base_dir)) => base_dir.clone(),
+            _ => std::env::current_dir().unwrap_or_default(),
+        };
+        base_dir.join(dir)
+    }
+}
+
+#[async_trait]
+impl CompletionProvider for FilePathCompletionProvider {
+    async fn complete(&self, request: &CompletionRequest) -> Vec<CompletionItem> {
+        let Some(path) = Self::get_path_before_caret(request.get_text_before_caret())
+        else {
+            return vec![];
+        };
+
+        // Safe to unwrap, since `path` contains a `/`.
+        let (dir, name_prefix) = path.rsplit_once('/').unwrap();
+        let dir = self.resolve_dir(request, &format!("{dir}/"));
+
+        // Only the name after the last `/` is replaced.
+        let name_prefix_width = UnicodeString::from(name_prefix).display_width;
+        let replace_range = SelectionRange::new(
+            request.caret.col_index - name_prefix_width,
+            request.caret.col_index,
+        );
+
+        let Ok(mut read_dir) = tokio::fs::read_dir(&dir).await else {
+            return vec![];
+        };
+
+        let mut it = vec![];
+        while let Ok(Some(entry)) = read_dir.next_entry().await {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') && !name_prefix.starts_with('.') {
+                continue;
+            }
+            let Some(score) = get_match_score(&name, name_prefix) else {
+                continue;
+            };
+            let is_dir = entry
+                .file_type()
+                .await
+                .map(|file_type| file_type.is_dir())
+                .unwrap_or(false);
+
+            let mut item = CompletionItem::new(&name, replace_range, score);
+            if is_dir {
+                item.insert_text = format!("{name}/");
+                item.label = item.insert_text.clone();
+            }
+            item.maybe_detail = Some(if is_dir { "dir" } else { "file" }.to_string());
+            it.push(item);
+        }
+
+        it
+    }
+}
@@ -0,0 +1,474 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::Debug,
+          sync::{Arc, Mutex, MutexGuard}};
+
+use r3bl_rs_utils_core::*;
+use r3bl_rs_utils_macro::tui_style;
+use tokio::{sync::mpsc::Sender, task::JoinHandle};
+
+use crate::*;
+
+/// Max number of items that the popup shows at once. It scrolls to show the rest.
+pub const COMPLETION_POPUP_MAX_ROW_COUNT: usize = 8;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompletionState {
+    pub items: Vec<CompletionItem>,
+    pub selected_index: usize,
+    /// Incremented by each request and dismissal, so that the results of a request that
+    /// is no longer current are dropped.
+    pub generation: usize,
+    /// Whether completions have been requested and not dismissed since.
+    pub is_active: bool,
+    /// Scroll adjusted position of the caret when `items` were requested.
+    pub maybe_anchor: Option<Position>,
+}
+
+/// What [EditorCompletion::handle_event] did w/ an [InputEvent].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompletionEventResult {
+    /// The selected item was inserted into the buffer.
+    Accepted,
+    /// The event was used to navigate or dismiss the popup.
+    Consumed,
+    /// The event is bound to [completion_actions::REQUEST], so completions should be
+    /// requested w/ [EditorCompletion::request].
+    Request,
+    /// The event should be passed on to the editor.
+    NotConsumed,
+}
+
+/// The ids of the [KeymapAction]s that the completion popup handles, see
+/// [EditorCompletion::get_keymap_actions].
+pub mod completion_actions {
+    pub const SCOPE: &str = "completion";
+
+    pub const REQUEST: &str = "completion.request";
+    pub const NEXT: &str = "completion.next";
+    pub const PREV: &str = "completion.prev";
+    pub const ACCEPT: &str = "completion.accept";
+    pub const DISMISS: &str = "completion.dismiss";
+}
+
+/// The completion popup of an [EditorComponent], and the [CompletionProvider]s that
+/// fill it. The state is shared w/ the background task that asks the providers.
+///
+/// - Press `Ctrl + Space` to request completions.
+/// - While the popup is open, `Up` and `Down` select an item, `Enter` or `Tab` accept
+///   it, and `Esc` dismisses the popup.
+/// - Typing or deleting text updates the completions. Any other edit or caret movement
+///   dismisses the popup, and so does moving the caret before the start of the text
+///   being completed.
+///
+/// These are the default bindings of the [completion_actions], which can be changed in
+/// the [Keymap]. The key presses that aren't bound to them go to the editor, even while
+/// the popup is open.
+#[derive(Clone, Debug)]
+pub struct EditorCompletion {
+    pub providers: Vec<SharedCompletionProvider>,
+    pub state: Arc<Mutex<CompletionState>>,
+    /// Turns key presses into [completion_actions]. It sees them before the
+    /// [EditorEngine::keymap].
+    pub keymap: Keymap,
+}
+
+impl Default for EditorCompletion {
+    fn default() -> Self {
+        Self {
+            providers: vec![],
+            state: Default::default(),
+            keymap: Self::new_keymap(),
+        }
+    }
+}
+
+impl EditorCompletion {
+    pub fn new(providers: Vec<SharedCompletionProvider>) -> Self {
+        Self {
+            providers,
+            ..Default::default()
+        }
+    }
+
+    /// The [Keymap] that [EditorCompletion::new] starts w/.
+    pub fn new_keymap() -> Keymap {
+        Keymap::new(completion_actions::SCOPE, &[completion_actions::SCOPE])
+    }
+
+    /// The actions that are registered in the [global_action_registry] for the
+    /// completion popup, w/ their default bindings.
+    pub fn get_keymap_actions() -> Vec<KeymapAction> {
+        use completion_actions::*;
+        vec![
+            KeymapAction::new(REQUEST, "Show completions", &["Ctrl+Space"]),
+            KeymapAction::new(NEXT, "Select the next completion", &["Down"]),
+            KeymapAction::new(PREV, "Select the previous completion", &["Up"]),
+            KeymapAction::new(
+                ACCEPT,
+                "Insert the selected completion",
+                &["Enter", "Tab"],
+            ),
+            KeymapAction::new(DISMISS, "Close the completions", &["Esc"]),
+        ]
+    }
+
+    fn lock(state: &Mutex<CompletionState>) -> MutexGuard<'_, CompletionState> {
+        state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn get_state(&self) -> CompletionState { Self::lock(&self.state).clone() }
+
+    pub fn is_enabled(&self) -> bool { !self.providers.is_empty() }
+
+    pub fn is_active(&self) -> bool { Self::lock(&self.state).is_active }
+
+    /// Whether the popup is showing items.
+    pub fn is_open(&self) -> bool {
+        let state = Self::lock(&self.state);
+        state.is_active && !state.items.is_empty()
+    }
+
+    /// Ask the providers for completions at the caret, in a background task. When they
+    /// are in, the popup is filled (or closed if there are none), and a
+    /// [TerminalWindowMainThreadSignal::Render] is sent so that it gets painted. The
+    /// returned handle can be awaited to wait for this. Nothing is requested if the
+    /// editor is [read only](EditorEngine::is_read_only), since the completions couldn't
+    /// be accepted.
+    pub fn request<AS>(
+        &self,
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        self_id: FlexBoxId,
+        main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
+    ) -> Option<JoinHandle<()>>
+    where
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        if editor_engine.is_read_only(editor_buffer) {
+            return None;
+        }
+
+        let request = CompletionRequest::new(editor_buffer);
+        let generation = {
+            let mut state = Self::lock(&self.state);
+            state.generation += 1;
+            state.is_active = true;
+            state.generation
+        };
+
+        let providers = self.providers.clone();
+        let state = self.state.clone();
+        Some(tokio::spawn(async move {
+            let items = collect_completions(&providers, &request).await;
+            {
+                let mut state = Self::lock(&state);
+                if state.generation != generation {
+                    return;
+                }
+                state.is_active = !items.is_empty();
+                state.items = items;
+                state.selected_index = 0;
+                state.maybe_anchor = Some(request.caret);
+            }
+            let _ = main_thread_channel_sender
+                .send(TerminalWindowMainThreadSignal::Render(Some(self_id)))
+                .await;
+        }))
+    }
+
+    /// Close the popup, and drop the results of any request that is still running.
+    pub fn dismiss(&self) {
+        let mut state = Self::lock(&self.state);
+        state.generation += 1;
+        state.is_active = false;
+        state.items.clear();
+        state.selected_index = 0;
+        state.maybe_anchor = None;
+    }
+
+    /// Handle the key presses that are bound to the [completion_actions] (w/
+    /// [EditorCompletion::keymap]). The ones that navigate, accept, and dismiss the popup
+    /// are only used while it is open. Nothing is consumed if there are no
+    /// [EditorCompletion::providers].
+    pub fn handle_event(
+        &mut self,
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        input_event: &InputEvent,
+    ) -> CompletionEventResult {
+        if !self.is_enabled() {
+            return CompletionEventResult::NotConsumed;
+        }
+
+        let action_id = match self.keymap.resolve(input_event) {
+            KeymapResult::Action(action_id) => action_id,
+            KeymapResult::Pending | KeymapResult::Cancelled => {
+                return CompletionEventResult::Consumed;
+            }
+            KeymapResult::NotBound => return CompletionEventResult::NotConsumed,
+        };
+
+        use completion_actions::*;
+        match action_id.as_str() {
+            REQUEST => CompletionEventResult::Request,
+            _ if !self.is_open() => CompletionEventResult::NotConsumed,
+            PREV => {
+                self.select_prev();
+                CompletionEventResult::Consumed
+            }
+            NEXT => {
+                self.select_next();
+                CompletionEventResult::Consumed
+            }
+            ACCEPT => match self.accept(editor_buffer, editor_engine) {
+                Some(_) => CompletionEventResult::Accepted,
+                None => CompletionEventResult::Consumed,
+            },
+            DISMISS => {
+                self.dismiss();
+                CompletionEventResult::Consumed
+            }
+            _ => CompletionEventResult::NotConsumed,
+        }
+    }
+
+    /// Call this after an [InputEvent] has been applied to the editor (w/
+    /// [EditorEngineApi::apply_event]) while completions are active. Typing or deleting
+    /// text requests completions again, and anything else dismisses the popup. Returns
+    /// the handle of the new request, if any.
+    pub fn update_after_editor_event<AS>(
+        &self,
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        self_id: FlexBoxId,
+        main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
+    ) -> Option<JoinHandle<()>>
+    where
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        if !self.is_active() {
+            return None;
+        }
+
+        let is_typing = matches!(
+            editor_engine.maybe_last_editor_event,
+            Some(EditorEvent::InsertChar(_) | EditorEvent::Backspace)
+        );
+        if is_typing && !self.is_caret_before_anchor(editor_buffer) {
+            self.request(
+                editor_buffer,
+                editor_engine,
+                self_id,
+                main_thread_channel_sender,
+            )
+        } else {
+            self.dismiss();
+            None
+        }
+    }
+
+    /// Whether the caret has left the row that completions were requested on, or has
+    /// moved before the start of the text that is being completed.
+    fn is_caret_before_anchor(&self, editor_buffer: &EditorBuffer) -> bool {
+        let state = Self::lock(&self.state);
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        match (state.maybe_anchor, state.items.first()) {
+            (Some(anchor), Some(item)) => {
+                caret.row_index != anchor.row_index
+                    || caret.col_index < item.replace_range.start_display_col_index
+            }
+            (Some(anchor), None) => caret.row_index != anchor.row_index,
+            _ => false,
+        }
+    }
+
+    pub fn select_prev(&self) {
+        let mut state = Self::lock(&self.state);
+        let item_count = state.items.len();
+        if item_count > 0 {
+            state.selected_index = (state.selected_index + item_count - 1) % item_count;
+        }
+    }
+
+    pub fn select_next(&self) {
+        let mut state = Self::lock(&self.state);
+        let item_count = state.items.len();
+        if item_count > 0 {
+            state.selected_index = (state.selected_index + 1) % item_count;
+        }
+    }
+
+    /// Replace the text that is being completed w/ the selected item, and close the
+    /// popup. Since the items may have been requested a few keystrokes ago, the end of
+    /// the replaced range is moved along w/ the caret. The popup is just closed (and
+    /// `None` is returned) if the editor is [read only](EditorEngine::is_read_only).
+    pub fn accept(
+        &self,
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        let (item, anchor) = {
+            let state = Self::lock(&self.state);
+            (
+                state.items.get(state.selected_index)?.clone(),
+                state.maybe_anchor?,
+            )
+        };
+        self.dismiss();
+
+        if editor_engine.is_read_only(editor_buffer) {
+            return None;
+        }
+
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        if caret.row_index != anchor.row_index {
+            return None;
+        }
+        let mut replace_range = item.replace_range;
+        replace_range.end_display_col_index = if caret.col_index >= anchor.col_index {
+            replace_range.end_display_col_index + (caret.col_index - anchor.col_index)
+        } else {
+            replace_range.end_display_col_index - (anchor.col_index - caret.col_index)
+        };
+        if replace_range.end_display_col_index < replace_range.start_display_col_index {
+            return None;
+        }
+
+        multi_caret::clear(editor_buffer);
+        editor_buffer.clear_selection();
+
+        if editor_buffer.history.is_empty() {
+            history::push(editor_buffer);
+        }
+        history::mark_caret_before_change(editor_buffer);
+        EditorEngineInternalApi::replace_range_in_caret_line(
+            editor_buffer,
+            editor_engine,
+            replace_range,
+            &item.insert_text,
+        );
+        history::push(editor_buffer);
+
+        Some(())
+    }
+
+    /// Paint the popup below the caret (or above it, if there isn't enough room), lined
+    /// up w/ the start of the text that is being completed.
+    pub fn render(
+        &self,
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        window_size: Size,
+    ) -> Option<RenderOps> {
+        let state = Self::lock(&self.state);
+        if !state.is_active || state.items.is_empty() {
+            return None;
+        }
+
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let caret_screen_position = EditorEngineApi::get_caret_screen_position(
+            editor_buffer,
+            editor_engine,
+            caret,
+        )?;
+        let origin = editor_engine.current_box.style_adjusted_origin_pos;
+
+        let replace_start_col_index =
+            state.items[0].replace_range.start_display_col_index;
+        let col_index = origin.col_index + caret_screen_position.col_index
+            - (caret.col_index - replace_start_col_index);
+
+        let row_count = std::cmp::min(state.items.len(), COMPLETION_POPUP_MAX_ROW_COUNT);
+        let caret_row_index = origin.row_index + caret_screen_position.row_index;
+        let row_index = if caret_row_index + 1 + ch!(row_count) <= window_size.row_count {
+            caret_row_index + 1
+        } else {
+            caret_row_index - ch!(row_count)
+        };
+
+        // Each row is the label, followed by the detail (right aligned).
+        let label_width = state
+            .items
+            .iter()
+            .map(|item| UnicodeString::from(item.label.as_str()).display_width)
+            .max()
+            .unwrap_or_default();
+        let detail_width = state
+            .items
+            .iter()
+            .filter_map(|item| item.maybe_detail.as_ref())
+            .map(|detail| UnicodeString::from(detail.as_str()).display_width)
+            .max()
+            .unwrap_or_default();
+        let width = std::cmp::min(
+            label_width + detail_width + 4, /* Padding. */
+            window_size.col_count - col_index,
+        );
+
+        // Scroll the selected item into view.
+        let first_index = (state.selected_index + 1).saturating_sub(row_count);
+
+        let mut ops = render_ops!();
+        for (offset, (index, item)) in state
+            .items
+            .iter()
+            .enumerate()
+            .skip(first_index)
+            .take(row_count)
+            .enumerate()
+        {
+            let label = UnicodeString::from(item.label.as_str())
+                .pad_end_with_spaces_to_fit_width(SPACER, label_width);
+            let detail = item.maybe_detail.clone().unwrap_or_default();
+            let detail_pad_width =
+                detail_width - UnicodeString::from(detail.as_str()).display_width;
+            let detail =
+                format!("{}{detail}", SPACER.repeat(ch!(@to_usize detail_pad_width)));
+            let text = UnicodeString::from(format!(" {label}  {detail} "));
+            let text = UnicodeString::from(text.truncate_end_to_fit_width(width))
+                .pad_end_with_spaces_to_fit_width(SPACER, width);
+
+            let style = if index == state.selected_index {
+                tui_style! {
+                    attrib: [bold]
+                    color_fg: TuiColor::Basic(ANSIBasicColor::Black)
+                    color_bg: TuiColor::Basic(ANSIBasicColor::Cyan)
+                }
+            } else {
+                tui_style! {
+                    color_fg: TuiColor::Basic(ANSIBasicColor::White)
+                    color_bg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
+                }
+            };
+
+            ops.push(RenderOp::ResetColor);
+            ops.push(RenderOp::MoveCursorPositionAbs(position!(
+                col_index: col_index,
+                row_index: row_index + ch!(offset)
+            )));
+            ops.push(RenderOp::ApplyColors(Some(style)));
+            ops.push(RenderOp::PaintTextWithAttributes(text, Some(style)));
+        }
+        ops.push(RenderOp::ResetColor);
+
+        Some(ops)
+    }
+}
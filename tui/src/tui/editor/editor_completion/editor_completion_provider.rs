@@ -0,0 +1,160 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::HashSet, fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Max number of items that [collect_completions] returns.
+pub const MAX_COMPLETION_ITEM_COUNT: usize = 50;
+
+/// A snapshot of the [EditorBuffer] at the time that completions are requested. Since
+/// [CompletionProvider]s run in a background task, they don't get access to the buffer
+/// itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompletionRequest {
+    pub lines: EditorLines,
+    /// Scroll adjusted position of the caret.
+    pub caret: Position,
+    pub maybe_file_path: Option<String>,
+}
+
+impl CompletionRequest {
+    pub fn new(editor_buffer: &EditorBuffer) -> Self {
+        Self {
+            lines: editor_buffer.get_lines().clone(),
+            caret: editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+            maybe_file_path: editor_buffer.editor_content.maybe_file_path.clone(),
+        }
+    }
+
+    pub fn get_line_at_caret(&self) -> Option<&UnicodeString> {
+        self.lines.get(ch!(@to_usize self.caret.row_index))
+    }
+
+    /// Returns the text in the caret's line that is before the caret.
+    pub fn get_text_before_caret(&self) -> &str {
+        match self.get_line_at_caret() {
+            Some(line) => line.clip_to_width(ch!(0), self.caret.col_index),
+            None => "",
+        }
+    }
+
+    /// Returns the part of the word at the caret that is before the caret (what has been
+    /// typed so far), along w/ its range. This is the range that completions of the word
+    /// replace.
+    pub fn get_word_prefix(&self) -> Option<(SelectionRange, &str)> {
+        let line = self.get_line_at_caret()?;
+        let word_range = line.get_word_range_at_display_col(self.caret.col_index)?;
+        if word_range.start_display_col_index >= self.caret.col_index {
+            return None;
+        }
+        let range =
+            SelectionRange::new(word_range.start_display_col_index, self.caret.col_index);
+        Some((range, line.clip_to_range(range)))
+    }
+}
+
+/// A completion that is offered in the popup. When it is accepted, the text in
+/// `replace_range` (in the caret's line) is replaced w/ `insert_text`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompletionItem {
+    /// What is shown in the popup.
+    pub label: String,
+    pub insert_text: String,
+    pub replace_range: SelectionRange,
+    /// Items w/ a higher score are shown first.
+    pub score: u32,
+    /// Shown next to the label, eg: where the item came from.
+    pub maybe_detail: Option<String>,
+}
+
+impl CompletionItem {
+    pub fn new(label: &str, replace_range: SelectionRange, score: u32) -> Self {
+        Self {
+            label: label.to_string(),
+            insert_text: label.to_string(),
+            replace_range,
+            score,
+            maybe_detail: None,
+        }
+    }
+}
+
+/// Returns how well `candidate` matches what has been typed so far (`prefix`), or
+/// [None] if it doesn't match at all. Case sensitive prefix matches are the best, then
+/// case insensitive ones, then ones that contain `prefix` somewhere else.
+pub fn get_match_score(candidate: &str, prefix: &str) -> Option<u32> {
+    let candidate_lowercase = candidate.to_lowercase();
+    let prefix_lowercase = prefix.to_lowercase();
+    if candidate.starts_with(prefix) {
+        Some(3)
+    } else if candidate_lowercase.starts_with(&prefix_lowercase) {
+        Some(2)
+    } else if candidate_lowercase.contains(&prefix_lowercase) {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// Implement this to offer completions in an [EditorComponent]. Providers are
+/// registered w/
+/// [with_completion_providers](EditorComponent::with_completion_providers), and they are
+/// all asked (concurrently) when the user requests completions.
+#[async_trait]
+pub trait CompletionProvider: Debug + Send + Sync {
+    /// Returns the completions for the caret in `request`, in any order. Return an empty
+    /// [Vec] if there aren't any.
+    async fn complete(&self, request: &CompletionRequest) -> Vec<CompletionItem>;
+}
+
+pub type SharedCompletionProvider = Arc<dyn CompletionProvider>;
+
+/// Ask all the `providers` for completions, and merge the results. They are sorted by
+/// score (highest first) and then by label, and duplicates (the same text replacing the
+/// same range) are removed.
+pub async fn collect_completions(
+    providers: &[SharedCompletionProvider],
+    request: &CompletionRequest,
+) -> Vec<CompletionItem> {
+    let results =
+        join_all(providers.iter().map(|provider| provider.complete(request))).await;
+
+    let mut items: Vec<CompletionItem> = results.into_iter().flatten().collect();
+    items.sort_by(|lhs, rhs| {
+        rhs.score
+            .cmp(&lhs.score)
+            .then_with(|| lhs.label.cmp(&rhs.label))
+    });
+
+    let mut seen = HashSet::new();
+    items.retain(|item| {
+        seen.insert((
+            item.insert_text.clone(),
+            item.replace_range.start_display_col_index,
+            item.replace_range.end_display_col_index,
+        ))
+    });
+    items.truncate(MAX_COMPLETION_ITEM_COUNT);
+
+    items
+}
@@ -43,6 +43,7 @@ where
     pub editor_engine: EditorEngine,
     pub id: FlexBoxId,
     pub on_editor_buffer_change_handler: Option<OnEditorBufferChangeFn<AS>>,
+    pub completion: EditorCompletion,
     _phantom: std::marker::PhantomData<S>,
 }
 
@@ -75,7 +76,7 @@ pub mod editor_component_impl_component_trait {
     impl<S, AS> Component<S, AS> for EditorComponent<S, AS>
     where
         S: HasEditorBuffers + Default + Clone + Debug + Sync + Send,
-        AS: Debug + Default + Clone + Sync + Send,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
     {
         fn reset(&mut self) {}
 
@@ -97,7 +98,10 @@ pub mod editor_component_impl_component_trait {
             let GlobalData { state, .. } = global_data;
 
             let EditorComponentData {
-                editor_engine, id, ..
+                editor_engine,
+                id,
+                completion,
+                ..
             } = &mut self.data;
 
             let self_id = *id;
@@ -107,13 +111,26 @@ pub mod editor_component_impl_component_trait {
                     state, self_id,
                 );
 
-            EditorEngineApi::render_engine(
+            let mut pipeline = EditorEngineApi::render_engine(
                 editor_engine,
                 editor_buffer,
                 current_box,
                 has_focus,
                 global_data.window_size,
-            )
+            )?;
+
+            // Paint the completion popup on top of everything else.
+            if has_focus.does_id_have_focus(self_id) {
+                if let Some(render_ops) = completion.render(
+                    editor_buffer,
+                    editor_engine,
+                    global_data.window_size,
+                ) {
+                    pipeline.push(ZOrder::Glass, render_ops);
+                }
+            }
+
+            Ok(pipeline)
         }
 
         /// This shim simply calls [EditorEngineApi::apply_event](EditorEngineApi::apply_event) w/
@@ -137,6 +154,7 @@ pub mod editor_component_impl_component_trait {
                     editor_engine,
                     id,
                     on_editor_buffer_change_handler,
+                    completion,
                     ..
                 } = &mut self.data;
 
@@ -147,6 +165,36 @@ pub mod editor_component_impl_component_trait {
                         state, self_id,
                     );
 
+                // The completion popup gets first dibs on the `input_event`.
+                match completion.handle_event(
+                    mut_editor_buffer,
+                    editor_engine,
+                    &input_event,
+                ) {
+                    CompletionEventResult::Accepted => {
+                        if let Some(on_change_handler) = on_editor_buffer_change_handler {
+                            on_change_handler(
+                                self_id,
+                                global_data.main_thread_channel_sender.clone(),
+                            );
+                        }
+                        return Ok(EventPropagation::ConsumedRender);
+                    }
+                    CompletionEventResult::Consumed => {
+                        return Ok(EventPropagation::ConsumedRender);
+                    }
+                    CompletionEventResult::Request => {
+                        completion.request(
+                            mut_editor_buffer,
+                            editor_engine,
+                            self_id,
+                            global_data.main_thread_channel_sender.clone(),
+                        );
+                        return Ok(EventPropagation::Consumed);
+                    }
+                    CompletionEventResult::NotConsumed => {}
+                }
+
                 // BOOKM: Editor component processes input event here
                 // Try to apply the `input_event` to `editor_engine` to decide whether to
                 // fire action.
@@ -157,6 +205,13 @@ pub mod editor_component_impl_component_trait {
                     &mut SystemClipboard,
                 )?;
 
+                completion.update_after_editor_event(
+                    mut_editor_buffer,
+                    editor_engine,
+                    self_id,
+                    global_data.main_thread_channel_sender.clone(),
+                );
+
                 match result {
                     EditorEngineApplyEventResult::Applied => {
                         if let Some(on_change_handler) = on_editor_buffer_change_handler {
@@ -171,6 +226,7 @@ pub mod editor_component_impl_component_trait {
                         // Optional: handle any `input_event` not consumed by `editor_engine`.
                         EventPropagation::Propagate
                     }
+                    EditorEngineApplyEventResult::Consumed => EventPropagation::Consumed,
                 }
             });
         }
@@ -210,5 +266,22 @@ pub mod constructor {
             let it = EditorComponent::new(id, config_options, on_buffer_change);
             Box::new(it)
         }
+
+        /// Register the [CompletionProvider]s that are asked for completions when the
+        /// user presses `Ctrl + Space`. See [EditorCompletion] for how the popup works.
+        pub fn with_completion_providers(
+            mut self,
+            providers: Vec<SharedCompletionProvider>,
+        ) -> Self {
+            self.data.completion = EditorCompletion::new(providers);
+            self
+        }
+
+        /// Show the key bindings of these actions (eg: to save & exit) when the buffer is
+        /// empty. See [EditorEmptyStateHint] for more details.
+        pub fn with_empty_state_hint(mut self, hint: EditorEmptyStateHint) -> Self {
+            self.data.editor_engine.empty_state_hint = hint;
+            self
+        }
     }
 }
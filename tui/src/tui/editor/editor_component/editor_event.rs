@@ -15,7 +15,8 @@
  *   limitations under the License.
  */
 
-use std::fmt::Debug;
+use std::{fmt::Debug,
+          sync::{Mutex, OnceLock}};
 
 use crossterm::style::Stylize;
 use get_size::GetSize;
@@ -28,13 +29,22 @@ use crate::{editor_buffer_clipboard_support::ClipboardService, *};
 ///
 /// By providing a conversion from [InputEvent] to [EditorEvent] it becomes easier to write event
 /// handlers that consume [InputEvent] and then execute [EditorEvent] on an [EditorBuffer].
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EditorEvent {
     InsertChar(char),
     InsertString(String),
     InsertNewLine,
     Delete,
     Backspace,
+    /// Delete from the start of the word to the left of the caret up to the caret.
+    BackspaceWord,
+    /// Delete from the caret up to the end of the word to the right of it.
+    DeleteWord,
+    /// Indent the selected lines, the Markdown list item at the caret, or insert spaces
+    /// up to the next tab stop. See [indent].
+    Indent,
+    /// Outdent the selected lines, or the line at the caret. See [indent].
+    Outdent,
     Home,
     End,
     PageDown,
@@ -47,14 +57,21 @@ pub enum EditorEvent {
     Cut,
     Undo,
     Redo,
+    Search(SearchAction),
+    MultiCaret(MultiCaretAction),
+    Mouse(MouseAction),
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SelectionAction {
     OneCharLeft,
     OneCharRight,
     OneLineUp,
     OneLineDown,
+    OneWordLeft,
+    OneWordRight,
+    /// Select the word at the caret.
+    Word,
     PageUp,
     PageDown,
     Home,
@@ -63,17 +80,111 @@ pub enum SelectionAction {
     Esc,
 }
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, GetSize)]
+/// Mouse events, see [mouse] for more details. The positions are where the mouse is on
+/// the screen (not in the buffer).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MouseAction {
+    /// Move the caret. Clicking twice on the same position selects a word.
+    Click(Position),
+    /// Select from the last click.
+    Drag(Position),
+    /// Scroll the viewport w/out moving the caret.
+    ScrollUp(Position),
+    ScrollDown(Position),
+}
+
+/// Find and replace, see [search] for more details. Replacing text is recorded in the undo
+/// history.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SearchAction {
+    /// Start (or update) an incremental search, and move the caret to the first match at
+    /// or after it.
+    SetQuery(String, SearchOptions),
+    NextMatch,
+    PreviousMatch,
+    ReplaceCurrent(String),
+    ReplaceAll(String),
+    Clear,
+}
+
+/// Add or remove carets, see [multi_caret] for more details.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MultiCaretAction {
+    /// Add a caret on the line above the topmost caret.
+    AddAbove,
+    /// Add a caret on the line below the bottommost caret.
+    AddBelow,
+    /// Select the word at the caret, or if there is a selection already, add a caret
+    /// that selects the next occurrence of it.
+    AddAtNextMatch,
+    /// Remove all the carets, except for the primary one.
+    Clear,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, GetSize)]
 pub enum CaretDirection {
     Up,
     Down,
     Left,
     Right,
+    /// Move to the start of the word to the left of the caret.
+    WordLeft,
+    /// Move to the end of the word to the right of the caret.
+    WordRight,
+}
+
+/// The ids of the [KeymapAction]s that the editor handles, see [EditorEvent::get_keymap_actions].
+pub mod editor_actions {
+    pub const SCOPE: &str = "editor";
+
+    pub const UNDO: &str = "editor.undo";
+    pub const REDO: &str = "editor.redo";
+    pub const FIND_NEXT: &str = "editor.find_next";
+    pub const FIND_PREVIOUS: &str = "editor.find_previous";
+    pub const ADD_CARET_ABOVE: &str = "editor.add_caret_above";
+    pub const ADD_CARET_BELOW: &str = "editor.add_caret_below";
+    pub const ADD_CARET_AT_NEXT_MATCH: &str = "editor.add_caret_at_next_match";
+    pub const MOVE_UP: &str = "editor.move_up";
+    pub const MOVE_DOWN: &str = "editor.move_down";
+    pub const MOVE_LEFT: &str = "editor.move_left";
+    pub const MOVE_RIGHT: &str = "editor.move_right";
+    pub const MOVE_WORD_LEFT: &str = "editor.move_word_left";
+    pub const MOVE_WORD_RIGHT: &str = "editor.move_word_right";
+    pub const HOME: &str = "editor.home";
+    pub const END: &str = "editor.end";
+    pub const PAGE_UP: &str = "editor.page_up";
+    pub const PAGE_DOWN: &str = "editor.page_down";
+    pub const SELECT_LEFT: &str = "editor.select_left";
+    pub const SELECT_RIGHT: &str = "editor.select_right";
+    pub const SELECT_UP: &str = "editor.select_up";
+    pub const SELECT_DOWN: &str = "editor.select_down";
+    pub const SELECT_WORD_LEFT: &str = "editor.select_word_left";
+    pub const SELECT_WORD_RIGHT: &str = "editor.select_word_right";
+    pub const SELECT_HOME: &str = "editor.select_home";
+    pub const SELECT_END: &str = "editor.select_end";
+    pub const SELECT_PAGE_UP: &str = "editor.select_page_up";
+    pub const SELECT_PAGE_DOWN: &str = "editor.select_page_down";
+    pub const SELECT_ALL: &str = "editor.select_all";
+    pub const ESC: &str = "editor.esc";
+    pub const COPY: &str = "editor.copy";
+    pub const CUT: &str = "editor.cut";
+    pub const PASTE: &str = "editor.paste";
+    pub const NEW_LINE: &str = "editor.new_line";
+    pub const DELETE: &str = "editor.delete";
+    pub const DELETE_WORD: &str = "editor.delete_word";
+    pub const BACKSPACE: &str = "editor.backspace";
+    pub const BACKSPACE_WORD: &str = "editor.backspace_word";
+    pub const INDENT: &str = "editor.indent";
+    pub const OUTDENT: &str = "editor.outdent";
 }
 
 impl TryFrom<InputEvent> for EditorEvent {
     type Error = String;
 
+    /// Uses the bindings of a shared [EditorEvent::new_keymap], which is created once,
+    /// and only updated when the keymap file or the registered actions change. So the
+    /// overrides of an [EditorEngine::keymap] aren't used, and chords can't be matched.
+    /// [EditorEngineApi::apply_event] uses [EditorEngine::keymap] instead.
     fn try_from(input_event: InputEvent) -> Result<Self, Self::Error> {
         call_if_true!(DEBUG_TUI_COPY_PASTE, {
             log_debug(format!(
@@ -82,211 +193,272 @@ impl TryFrom<InputEvent> for EditorEvent {
             ));
         });
 
-        match input_event {
-            // Undo, redo events.
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('z'),
-                mask:
-                    ModifierKeysMask {
-                        ctrl_key_state: KeyState::Pressed,
-                        shift_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Undo),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('y'),
-                mask:
-                    ModifierKeysMask {
-                        ctrl_key_state: KeyState::Pressed,
-                        shift_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Redo),
-
-            // Selection events.
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Right),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::OneCharRight)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Left),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::OneCharLeft)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Down),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::OneLineDown)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Up),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::OneLineUp)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::PageUp),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::PageUp)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::PageDown),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::PageDown)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::Home),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::Home)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::SpecialKey(SpecialKey::End),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::Pressed,
-                        ctrl_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::End)),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('a'),
-                mask:
-                    ModifierKeysMask {
-                        shift_key_state: KeyState::NotPressed,
-                        ctrl_key_state: KeyState::Pressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Select(SelectionAction::All)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Esc),
-            }) => Ok(EditorEvent::Select(SelectionAction::Esc)),
-
-            //  Clipboard events.
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('c'),
-                mask:
-                    ModifierKeysMask {
-                        ctrl_key_state: KeyState::Pressed,
-                        shift_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Copy),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('x'),
-                mask:
-                    ModifierKeysMask {
-                        ctrl_key_state: KeyState::Pressed,
-                        shift_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Cut),
-
-            InputEvent::Keyboard(KeyPress::WithModifiers {
-                key: Key::Character('v'),
-                mask:
-                    ModifierKeysMask {
-                        ctrl_key_state: KeyState::Pressed,
-                        shift_key_state: KeyState::NotPressed,
-                        alt_key_state: KeyState::NotPressed,
-                    },
-            }) => Ok(EditorEvent::Paste),
-
-            // Other events.
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::PageDown),
-            }) => Ok(EditorEvent::PageDown),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::PageUp),
-            }) => Ok(EditorEvent::PageUp),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Home),
-            }) => Ok(EditorEvent::Home),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::End),
-            }) => Ok(EditorEvent::End),
-
-            InputEvent::Resize(size) => Ok(EditorEvent::Resize(size)),
-
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::Character(character),
-            }) => Ok(Self::InsertChar(character)),
+        static KEYMAP: OnceLock<Mutex<Keymap>> = OnceLock::new();
+        let keymap_result = {
+            let mut keymap = match KEYMAP
+                .get_or_init(|| Mutex::new(EditorEvent::new_keymap()))
+                .lock()
+            {
+                Ok(it) => it,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let it = keymap.resolve(&input_event);
+            keymap.cancel_pending_key_presses();
+            it
+        };
 
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Enter),
-            }) => Ok(Self::InsertNewLine),
+        let maybe_editor_event = match keymap_result {
+            KeymapResult::Action(action_id) => {
+                EditorEvent::try_from_action_id(&action_id)
+            }
+            _ => EditorEvent::try_from_unbound_input_event(input_event),
+        };
 
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Delete),
-            }) => Ok(Self::Delete),
+        maybe_editor_event.ok_or_else(|| format!("Invalid input event: {input_event:?}"))
+    }
+}
 
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Backspace),
-            }) => Ok(Self::Backspace),
+mod editor_event_keymap_impl {
+    use super::*;
 
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Up),
-            }) => Ok(Self::MoveCaret(CaretDirection::Up)),
+    impl EditorEvent {
+        /// The [Keymap] that [EditorEngine::new] starts w/.
+        pub fn new_keymap() -> Keymap {
+            Keymap::new(editor_actions::SCOPE, &[editor_actions::SCOPE])
+        }
 
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Down),
-            }) => Ok(Self::MoveCaret(CaretDirection::Down)),
+        /// The actions that are registered in the [global_action_registry] for the
+        /// editor, w/ their default bindings.
+        pub fn get_keymap_actions() -> Vec<KeymapAction> {
+            use editor_actions::*;
+            vec![
+                KeymapAction::new(UNDO, "Undo", &["Ctrl+Z"]),
+                KeymapAction::new(REDO, "Redo", &["Ctrl+Y"]),
+                KeymapAction::new(FIND_NEXT, "Go to the next search match", &["F3"]),
+                KeymapAction::new(
+                    FIND_PREVIOUS,
+                    "Go to the previous search match",
+                    &["Shift+F3"],
+                ),
+                KeymapAction::new(ADD_CARET_ABOVE, "Add a caret above", &["Ctrl+Alt+Up"]),
+                KeymapAction::new(
+                    ADD_CARET_BELOW,
+                    "Add a caret below",
+                    &["Ctrl+Alt+Down"],
+                ),
+                KeymapAction::new(
+                    ADD_CARET_AT_NEXT_MATCH,
+                    "Add a caret at the next match of the selection",
+                    &["Ctrl+D"],
+                ),
+                KeymapAction::new(MOVE_UP, "Move the caret up", &["Up"]),
+                KeymapAction::new(MOVE_DOWN, "Move the caret down", &["Down"]),
+                KeymapAction::new(MOVE_LEFT, "Move the caret left", &["Left"]),
+                KeymapAction::new(MOVE_RIGHT, "Move the caret right", &["Right"]),
+                KeymapAction::new(
+                    MOVE_WORD_LEFT,
+                    "Move the caret one word left",
+                    &["Ctrl+Left"],
+                ),
+                KeymapAction::new(
+                    MOVE_WORD_RIGHT,
+                    "Move the caret one word right",
+                    &["Ctrl+Right"],
+                ),
+                KeymapAction::new(
+                    HOME,
+                    "Move the caret to the start of the line",
+                    &["Home"],
+                ),
+                KeymapAction::new(END, "Move the caret to the end of the line", &["End"]),
+                KeymapAction::new(PAGE_UP, "Move the caret one page up", &["PageUp"]),
+                KeymapAction::new(
+                    PAGE_DOWN,
+                    "Move the caret one page down",
+                    &["PageDown"],
+                ),
+                KeymapAction::new(SELECT_LEFT, "Select one char left", &["Shift+Left"]),
+                KeymapAction::new(
+                    SELECT_RIGHT,
+                    "Select one char right",
+                    &["Shift+Right"],
+                ),
+                KeymapAction::new(SELECT_UP, "Select one line up", &["Shift+Up"]),
+                KeymapAction::new(SELECT_DOWN, "Select one line down", &["Shift+Down"]),
+                KeymapAction::new(
+                    SELECT_WORD_LEFT,
+                    "Select one word left",
+                    &["Ctrl+Shift+Left"],
+                ),
+                KeymapAction::new(
+                    SELECT_WORD_RIGHT,
+                    "Select one word right",
+                    &["Ctrl+Shift+Right"],
+                ),
+                KeymapAction::new(
+                    SELECT_HOME,
+                    "Select to the start of the line",
+                    &["Shift+Home"],
+                ),
+                KeymapAction::new(
+                    SELECT_END,
+                    "Select to the end of the line",
+                    &["Shift+End"],
+                ),
+                KeymapAction::new(
+                    SELECT_PAGE_UP,
+                    "Select one page up",
+                    &["Shift+PageUp"],
+                ),
+                KeymapAction::new(
+                    SELECT_PAGE_DOWN,
+                    "Select one page down",
+                    &["Shift+PageDown"],
+                ),
+                KeymapAction::new(SELECT_ALL, "Select all", &["Ctrl+A"]),
+                KeymapAction::new(ESC, "Clear the selection", &["Esc"]),
+                KeymapAction::new(COPY, "Copy", &["Ctrl+C"]),
+                KeymapAction::new(CUT, "Cut", &["Ctrl+X"]),
+                KeymapAction::new(PASTE, "Paste", &["Ctrl+V"]),
+                KeymapAction::new(NEW_LINE, "Insert a new line", &["Enter"]),
+                KeymapAction::new(DELETE, "Delete the char at the caret", &["Delete"]),
+                KeymapAction::new(
+                    DELETE_WORD,
+                    "Delete up to the end of the word",
+                    &["Ctrl+Delete"],
+                ),
+                KeymapAction::new(
+                    BACKSPACE,
+                    "Delete the char before the caret",
+                    &["Backspace"],
+                ),
+                // Some terminals send Alt+Backspace for Ctrl+Backspace.
+                KeymapAction::new(
+                    BACKSPACE_WORD,
+                    "Delete from the start of the word",
+                    &["Ctrl+Backspace", "Alt+Backspace", "Ctrl+Alt+Backspace"],
+                ),
+                KeymapAction::new(INDENT, "Indent", &["Tab"]),
+                // Terminals report Shift+Tab as BackTab w/ the shift modifier.
+                KeymapAction::new(OUTDENT, "Outdent", &["Shift+Tab", "BackTab"]),
+            ]
+        }
 
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Left),
-            }) => Ok(Self::MoveCaret(CaretDirection::Left)),
+        /// Returns [None] if the action isn't one of [editor_actions].
+        pub fn try_from_action_id(action_id: &str) -> Option<Self> {
+            use editor_actions::*;
+            let it = match action_id {
+                UNDO => EditorEvent::Undo,
+                REDO => EditorEvent::Redo,
+                FIND_NEXT => EditorEvent::Search(SearchAction::NextMatch),
+                FIND_PREVIOUS => EditorEvent::Search(SearchAction::PreviousMatch),
+                ADD_CARET_ABOVE => EditorEvent::MultiCaret(MultiCaretAction::AddAbove),
+                ADD_CARET_BELOW => EditorEvent::MultiCaret(MultiCaretAction::AddBelow),
+                ADD_CARET_AT_NEXT_MATCH => {
+                    EditorEvent::MultiCaret(MultiCaretAction::AddAtNextMatch)
+                }
+                MOVE_UP => EditorEvent::MoveCaret(CaretDirection::Up),
+                MOVE_DOWN => EditorEvent::MoveCaret(CaretDirection::Down),
+                MOVE_LEFT => EditorEvent::MoveCaret(CaretDirection::Left),
+                MOVE_RIGHT => EditorEvent::MoveCaret(CaretDirection::Right),
+                MOVE_WORD_LEFT => EditorEvent::MoveCaret(CaretDirection::WordLeft),
+                MOVE_WORD_RIGHT => EditorEvent::MoveCaret(CaretDirection::WordRight),
+                HOME => EditorEvent::Home,
+                END => EditorEvent::End,
+                PAGE_UP => EditorEvent::PageUp,
+                PAGE_DOWN => EditorEvent::PageDown,
+                SELECT_LEFT => EditorEvent::Select(SelectionAction::OneCharLeft),
+                SELECT_RIGHT => EditorEvent::Select(SelectionAction::OneCharRight),
+                SELECT_UP => EditorEvent::Select(SelectionAction::OneLineUp),
+                SELECT_DOWN => EditorEvent::Select(SelectionAction::OneLineDown),
+                SELECT_WORD_LEFT => EditorEvent::Select(SelectionAction::OneWordLeft),
+                SELECT_WORD_RIGHT => EditorEvent::Select(SelectionAction::OneWordRight),
+                SELECT_HOME => EditorEvent::Select(SelectionAction::Home),
+                SELECT_END => EditorEvent::Select(SelectionAction::End),
+                SELECT_PAGE_UP => EditorEvent::Select(SelectionAction::PageUp),
+                SELECT_PAGE_DOWN => EditorEvent::Select(SelectionAction::PageDown),
+                SELECT_ALL => EditorEvent::Select(SelectionAction::All),
+                ESC => EditorEvent::Select(SelectionAction::Esc),
+                COPY => EditorEvent::Copy,
+                CUT => EditorEvent::Cut,
+                PASTE => EditorEvent::Paste,
+                NEW_LINE => EditorEvent::InsertNewLine,
+                DELETE => EditorEvent::Delete,
+                DELETE_WORD => EditorEvent::DeleteWord,
+                BACKSPACE => EditorEvent::Backspace,
+                BACKSPACE_WORD => EditorEvent::BackspaceWord,
+                INDENT => EditorEvent::Indent,
+                OUTDENT => EditorEvent::Outdent,
+                _ => return None,
+            };
+            Some(it)
+        }
 
-            InputEvent::Keyboard(KeyPress::Plain {
-                key: Key::SpecialKey(SpecialKey::Right),
-            }) => Ok(Self::MoveCaret(CaretDirection::Right)),
+        /// The events that can't be rebound: typing, mouse events and resizing.
+        pub fn try_from_unbound_input_event(input_event: InputEvent) -> Option<Self> {
+            let it = match input_event {
+                InputEvent::Mouse(MouseInput {
+                    pos,
+                    kind: MouseInputKind::MouseDown(Button::Left),
+                    maybe_modifier_keys: None,
+                }) => EditorEvent::Mouse(MouseAction::Click(pos)),
+
+                InputEvent::Mouse(MouseInput {
+                    pos,
+                    kind: MouseInputKind::MouseDrag(Button::Left),
+                    ..
+                }) => EditorEvent::Mouse(MouseAction::Drag(pos)),
+
+                InputEvent::Mouse(MouseInput {
+                    pos,
+                    kind: MouseInputKind::ScrollUp,
+                    ..
+                }) => EditorEvent::Mouse(MouseAction::ScrollUp(pos)),
+
+                InputEvent::Mouse(MouseInput {
+                    pos,
+                    kind: MouseInputKind::ScrollDown,
+                    ..
+                }) => EditorEvent::Mouse(MouseAction::ScrollDown(pos)),
+
+                InputEvent::Resize(size) => EditorEvent::Resize(size),
+
+                InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::Character(character),
+                }) => EditorEvent::InsertChar(character),
+
+                _ => return None,
+            };
+            Some(it)
+        }
 
-            _ => Err(format!("Invalid input event: {input_event:?}")),
+        /// The events that don't change the content, which are the only ones that are
+        /// applied in [EditMode::ReadOnly].
+        pub fn is_allowed_in_read_only_mode(&self) -> bool {
+            matches!(
+                self,
+                EditorEvent::Mouse(_)
+                    | EditorEvent::MoveCaret(_)
+                    | EditorEvent::Home
+                    | EditorEvent::End
+                    | EditorEvent::PageUp
+                    | EditorEvent::PageDown
+                    | EditorEvent::Search(
+                        SearchAction::NextMatch | SearchAction::PreviousMatch
+                    )
+            )
         }
     }
 }
 
 impl EditorEvent {
+    pub fn is_mouse_scroll(&self) -> bool {
+        matches!(
+            self,
+            EditorEvent::Mouse(MouseAction::ScrollUp(_) | MouseAction::ScrollDown(_))
+        )
+    }
+
     fn delete_text_if_selected(
         editor_engine: &mut EditorEngine,
         editor_buffer: &mut EditorBuffer,
@@ -303,13 +475,160 @@ impl EditorEvent {
         );
     }
 
+    /// Events that edit the text or move the caret are applied to every caret (see
+    /// [multi_caret::for_each_caret]). Events that only make sense for a single caret
+    /// (eg: page up / down, select all, search, cut) remove all the carets except for
+    /// the primary one first.
     pub fn apply_editor_event(
         editor_engine: &mut EditorEngine,
         editor_buffer: &mut EditorBuffer,
         editor_event: EditorEvent,
         clipboard_service_provider: &mut impl ClipboardService,
+    ) {
+        // If the caret has been scrolled out of view, then bring it back before doing
+        // anything else w/ it.
+        if !editor_event.is_mouse_scroll() {
+            mouse::scroll_to_caret(editor_buffer, editor_engine);
+        }
+
+        let is_list_structure_edit = matches!(
+            editor_event,
+            EditorEvent::InsertNewLine
+                | EditorEvent::Delete
+                | EditorEvent::Backspace
+                | EditorEvent::BackspaceWord
+                | EditorEvent::DeleteWord
+                | EditorEvent::Indent
+                | EditorEvent::Outdent
+                | EditorEvent::Paste
+                | EditorEvent::Cut
+        );
+
+        match editor_event {
+            EditorEvent::Mouse(mouse_action) => match mouse_action {
+                MouseAction::Click(screen_position) => {
+                    multi_caret::clear(editor_buffer);
+                    mouse::click(editor_buffer, editor_engine, screen_position);
+                }
+                MouseAction::Drag(screen_position) => {
+                    multi_caret::clear(editor_buffer);
+                    mouse::drag(editor_buffer, editor_engine, screen_position);
+                }
+                MouseAction::ScrollUp(_) => {
+                    mouse::scroll(
+                        editor_buffer,
+                        editor_engine,
+                        true,
+                        MOUSE_WHEEL_SCROLL_ROW_COUNT,
+                    );
+                }
+                MouseAction::ScrollDown(_) => {
+                    mouse::scroll(
+                        editor_buffer,
+                        editor_engine,
+                        false,
+                        MOUSE_WHEEL_SCROLL_ROW_COUNT,
+                    );
+                }
+            },
+
+            EditorEvent::MultiCaret(multi_caret_action) => match multi_caret_action {
+                MultiCaretAction::AddAbove => multi_caret::add_caret_above(editor_buffer),
+                MultiCaretAction::AddBelow => multi_caret::add_caret_below(editor_buffer),
+                MultiCaretAction::AddAtNextMatch => {
+                    multi_caret::add_caret_at_next_match_of_selection(editor_buffer)
+                }
+                MultiCaretAction::Clear => multi_caret::clear(editor_buffer),
+            },
+
+            EditorEvent::InsertChar(_)
+            | EditorEvent::InsertString(_)
+            | EditorEvent::InsertNewLine
+            | EditorEvent::Delete
+            | EditorEvent::Backspace
+            | EditorEvent::BackspaceWord
+            | EditorEvent::DeleteWord
+            | EditorEvent::MoveCaret(_)
+            | EditorEvent::Home
+            | EditorEvent::End
+            | EditorEvent::Paste
+            | EditorEvent::Select(
+                SelectionAction::OneCharLeft
+                | SelectionAction::OneCharRight
+                | SelectionAction::OneLineUp
+                | SelectionAction::OneLineDown
+                | SelectionAction::OneWordLeft
+                | SelectionAction::OneWordRight
+                | SelectionAction::Word
+                | SelectionAction::Home
+                | SelectionAction::End,
+            ) => {
+                let viewport = size!(
+                    col_count: editor_engine.viewport_width(),
+                    row_count: editor_engine.viewport_height()
+                );
+                multi_caret::for_each_caret(editor_buffer, viewport, |editor_buffer| {
+                    Self::apply_editor_event_to_caret(
+                        editor_engine,
+                        editor_buffer,
+                        editor_event.clone(),
+                        clipboard_service_provider,
+                    );
+                });
+            }
+
+            EditorEvent::PageUp
+            | EditorEvent::PageDown
+            | EditorEvent::Indent
+            | EditorEvent::Outdent
+            | EditorEvent::Select(_)
+            | EditorEvent::Search(_)
+            | EditorEvent::Cut => {
+                multi_caret::clear(editor_buffer);
+                Self::apply_editor_event_to_caret(
+                    editor_engine,
+                    editor_buffer,
+                    editor_event,
+                    clipboard_service_provider,
+                );
+            }
+
+            EditorEvent::Undo
+            | EditorEvent::Redo
+            | EditorEvent::Copy
+            | EditorEvent::Resize(_) => {
+                Self::apply_editor_event_to_caret(
+                    editor_engine,
+                    editor_buffer,
+                    editor_event,
+                    clipboard_service_provider,
+                );
+            }
+        }
+
+        // Keep ordered lists numbered after items are added, removed, or moved.
+        if is_list_structure_edit && smart_list::is_enabled(editor_buffer, editor_engine)
+        {
+            smart_list::renumber_list_at_caret(editor_buffer, editor_engine);
+        }
+
+        // The functions that edit the text scroll horizontally when the caret goes past
+        // the viewport, which isn't needed w/ soft wrapping.
+        if soft_wrap::is_enabled(editor_engine) {
+            soft_wrap::validate_scroll(editor_buffer, editor_engine);
+        }
+    }
+
+    fn apply_editor_event_to_caret(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+        editor_event: EditorEvent,
+        clipboard_service_provider: &mut impl ClipboardService,
     ) {
         match editor_event {
+            // Handled by apply_editor_event().
+            EditorEvent::MultiCaret(_) | EditorEvent::Mouse(_) => {}
+
             EditorEvent::Undo => {
                 history::undo(editor_buffer);
             }
@@ -318,23 +637,77 @@ impl EditorEvent {
                 history::redo(editor_buffer);
             }
 
-            EditorEvent::InsertChar(character) => {
-                Self::delete_text_if_selected(editor_engine, editor_buffer);
-                EditorEngineInternalApi::insert_str_at_caret(
-                    EditorArgsMut {
+            EditorEvent::Search(search_action) => match search_action {
+                SearchAction::SetQuery(query, options) => {
+                    search::set_query(editor_buffer, &query, options);
+                    EditorEngineInternalApi::apply_pending_search_jump(
                         editor_buffer,
                         editor_engine,
-                    },
-                    &String::from(character),
-                )
+                    );
+                }
+                SearchAction::NextMatch => {
+                    EditorEngineInternalApi::jump_to_search_match(
+                        editor_buffer,
+                        editor_engine,
+                        SearchJump::Next,
+                    );
+                }
+                SearchAction::PreviousMatch => {
+                    EditorEngineInternalApi::jump_to_search_match(
+                        editor_buffer,
+                        editor_engine,
+                        SearchJump::Previous,
+                    );
+                }
+                SearchAction::ReplaceCurrent(replacement) => {
+                    EditorEngineInternalApi::replace_search_match(
+                        editor_buffer,
+                        editor_engine,
+                        &replacement,
+                    );
+                }
+                SearchAction::ReplaceAll(replacement) => {
+                    EditorEngineInternalApi::replace_all_search_matches(
+                        editor_buffer,
+                        &replacement,
+                    );
+                }
+                SearchAction::Clear => {
+                    search::clear(editor_buffer);
+                }
+            },
+
+            EditorEvent::InsertChar(character) => {
+                Self::delete_text_if_selected(editor_engine, editor_buffer);
+                let is_auto_paired = auto_pair::is_enabled(editor_engine)
+                    && auto_pair::insert_char(editor_buffer, editor_engine, character);
+                if !is_auto_paired {
+                    EditorEngineInternalApi::insert_str_at_caret(
+                        EditorArgsMut {
+                            editor_buffer,
+                            editor_engine,
+                        },
+                        &String::from(character),
+                    )
+                }
             }
 
             EditorEvent::InsertNewLine => {
                 Self::delete_text_if_selected(editor_engine, editor_buffer);
-                EditorEngineInternalApi::insert_new_line_at_caret(EditorArgsMut {
-                    editor_buffer,
-                    editor_engine,
-                });
+                let is_list_continued =
+                    smart_list::is_enabled(editor_buffer, editor_engine)
+                        && smart_list::insert_new_line(editor_buffer, editor_engine);
+                if !is_list_continued {
+                    indent::insert_new_line(editor_buffer, editor_engine);
+                }
+            }
+
+            EditorEvent::Indent => {
+                indent::indent_or_outdent(editor_buffer, editor_engine, false);
+            }
+
+            EditorEvent::Outdent => {
+                indent::indent_or_outdent(editor_buffer, editor_engine, true);
             }
 
             EditorEvent::Delete => {
@@ -356,13 +729,33 @@ impl EditorEvent {
 
             EditorEvent::Backspace => {
                 if editor_buffer.get_selection_map().is_empty() {
-                    // There is no selection and we want to backspace a single character.
-                    EditorEngineInternalApi::backspace_at_caret(
+                    // There is no selection and we want to backspace a single character
+                    // (or an empty pair of brackets or quotes).
+                    let is_pair_deleted = auto_pair::is_enabled(editor_engine)
+                        && auto_pair::backspace(editor_buffer, editor_engine);
+                    if !is_pair_deleted {
+                        EditorEngineInternalApi::backspace_at_caret(
+                            editor_buffer,
+                            editor_engine,
+                        );
+                    }
+                } else {
+                    // The text is selected and we want to delete the entire selected text.
+                    EditorEngineInternalApi::delete_selected(
+                        editor_buffer,
+                        editor_engine,
+                        DeleteSelectionWith::Backspace,
+                    );
+                }
+            }
+
+            EditorEvent::BackspaceWord => {
+                if editor_buffer.get_selection_map().is_empty() {
+                    EditorEngineInternalApi::backspace_word_at_caret(
                         editor_buffer,
                         editor_engine,
                     );
                 } else {
-                    // The text is selected and we want to delete the entire selected text.
                     EditorEngineInternalApi::delete_selected(
                         editor_buffer,
                         editor_engine,
@@ -371,6 +764,21 @@ impl EditorEvent {
                 }
             }
 
+            EditorEvent::DeleteWord => {
+                if editor_buffer.get_selection_map().is_empty() {
+                    EditorEngineInternalApi::delete_word_at_caret(
+                        editor_buffer,
+                        editor_engine,
+                    );
+                } else {
+                    EditorEngineInternalApi::delete_selected(
+                        editor_buffer,
+                        editor_engine,
+                        DeleteSelectionWith::Delete,
+                    );
+                }
+            }
+
             EditorEvent::MoveCaret(direction) => {
                 match direction {
                     CaretDirection::Left => EditorEngineInternalApi::left(
@@ -393,6 +801,16 @@ impl EditorEvent {
                         editor_engine,
                         SelectMode::Disabled,
                     ),
+                    CaretDirection::WordLeft => EditorEngineInternalApi::word_left(
+                        editor_buffer,
+                        editor_engine,
+                        SelectMode::Disabled,
+                    ),
+                    CaretDirection::WordRight => EditorEngineInternalApi::word_right(
+                        editor_buffer,
+                        editor_engine,
+                        SelectMode::Disabled,
+                    ),
                 };
             }
 
@@ -476,6 +894,23 @@ impl EditorEvent {
                         SelectMode::Enabled,
                     );
                 }
+                SelectionAction::OneWordLeft => {
+                    EditorEngineInternalApi::word_left(
+                        editor_buffer,
+                        editor_engine,
+                        SelectMode::Enabled,
+                    );
+                }
+                SelectionAction::OneWordRight => {
+                    EditorEngineInternalApi::word_right(
+                        editor_buffer,
+                        editor_engine,
+                        SelectMode::Enabled,
+                    );
+                }
+                SelectionAction::Word => {
+                    EditorEngineInternalApi::select_word(editor_buffer, editor_engine);
+                }
                 SelectionAction::PageUp => {
                     EditorEngineInternalApi::page_up(
                         editor_buffer,
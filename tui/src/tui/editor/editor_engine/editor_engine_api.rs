@@ -15,6 +15,8 @@
  *   limitations under the License.
  */
 
+use std::cmp;
+
 use crossterm::style::Stylize;
 use r3bl_rs_utils_core::*;
 use r3bl_rs_utils_macro::tui_style;
@@ -27,51 +29,66 @@ pub struct EditorEngineApi;
 
 impl EditorEngineApi {
     /// Event based interface for the editor. This converts the [InputEvent] into an
-    /// [EditorEvent] and then executes it. Returns a new [EditorBuffer] if the operation
-    /// was applied otherwise returns [None].
+    /// [EditorEvent] (using the [EditorEngine::keymap]) and then executes it. Returns a
+    /// new [EditorBuffer] if the operation was applied otherwise returns [None].
     pub fn apply_event(
         editor_buffer: &mut EditorBuffer,
         editor_engine: &mut EditorEngine,
         input_event: InputEvent,
         clipboard_service_provider: &mut impl ClipboardService,
     ) -> CommonResult<EditorEngineApplyEventResult> {
-        let editor_config = &editor_engine.config_options;
-
-        if let EditMode::ReadOnly = editor_config.edit_mode {
-            if !input_event.matches_any_of_these_keypresses(&[
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Up),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Down),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Left),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Right),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::Home),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::End),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::PageUp),
-                },
-                KeyPress::Plain {
-                    key: Key::SpecialKey(SpecialKey::PageDown),
-                },
-            ]) {
+        let maybe_editor_event = match editor_engine.keymap.resolve(&input_event) {
+            KeymapResult::Action(action_id) => {
+                EditorEvent::try_from_action_id(&action_id)
+            }
+            KeymapResult::Pending | KeymapResult::Cancelled => {
+                editor_engine.maybe_last_editor_event = None;
+                return Ok(EditorEngineApplyEventResult::Consumed);
+            }
+            KeymapResult::NotBound => {
+                EditorEvent::try_from_unbound_input_event(input_event)
+            }
+        };
+        editor_engine.maybe_last_editor_event = maybe_editor_event.clone();
+
+        if let Some(editor_event) = maybe_editor_event {
+            // Only the events that don't change the content are allowed in read only mode,
+            // or in a buffer that is read only.
+            if editor_engine.is_read_only(editor_buffer)
+                && !editor_event.is_allowed_in_read_only_mode()
+            {
                 return Ok(EditorEngineApplyEventResult::NotApplied);
             }
-        }
 
-        if let Ok(editor_event) = EditorEvent::try_from(input_event) {
+            // Clicks and scrolls outside the editor are meant for something else.
+            if let EditorEvent::Mouse(
+                MouseAction::Click(screen_position)
+                | MouseAction::ScrollUp(screen_position)
+                | MouseAction::ScrollDown(screen_position),
+            ) = editor_event
+            {
+                if !mouse::is_in_viewport(editor_engine, screen_position) {
+                    return Ok(EditorEngineApplyEventResult::NotApplied);
+                }
+            }
+
+            // Single line editors leave Tab and Shift+Tab for something else (eg: moving
+            // focus).
+            if let (EditorEvent::Indent | EditorEvent::Outdent, LineMode::SingleLine) =
+                (&editor_event, &editor_engine.config_options.multiline_mode)
+            {
+                return Ok(EditorEngineApplyEventResult::NotApplied);
+            }
+
+            // So that undo restores the caret to where it actually is.
+            if !editor_event.is_mouse_scroll() {
+                mouse::scroll_to_caret(editor_buffer, editor_engine);
+            }
+
             if editor_buffer.history.is_empty() {
                 history::push(editor_buffer);
             }
+            history::mark_caret_before_change(editor_buffer);
 
             EditorEvent::apply_editor_event(
                 editor_engine,
@@ -80,9 +97,14 @@ impl EditorEngineApi {
                 clipboard_service_provider,
             );
 
+            // Only the Markdown blocks that this event changed are parsed again.
+            editor_engine
+                .md_syn_hi_cache
+                .invalidate(editor_buffer.get_lines());
+
             match editor_event {
                 EditorEvent::InsertChar(_) => {
-                    history::push(editor_buffer);
+                    history::push_and_coalesce_typing(editor_buffer);
                 }
                 EditorEvent::InsertString(_) => {
                     history::push(editor_buffer);
@@ -96,6 +118,18 @@ impl EditorEngineApi {
                 EditorEvent::Backspace => {
                     history::push(editor_buffer);
                 }
+                EditorEvent::BackspaceWord => {
+                    history::push(editor_buffer);
+                }
+                EditorEvent::DeleteWord => {
+                    history::push(editor_buffer);
+                }
+                EditorEvent::Indent => {
+                    history::push(editor_buffer);
+                }
+                EditorEvent::Outdent => {
+                    history::push(editor_buffer);
+                }
                 EditorEvent::Copy => {
                     history::push(editor_buffer);
                 }
@@ -123,6 +157,23 @@ impl EditorEngineApi {
         throws_with_return!({
             editor_engine.current_box = current_box.into();
 
+            // The theme may have changed since the last render.
+            if editor_engine.update_md_theme() {
+                cache::clear(editor_buffer);
+            }
+
+            // A jump to a search match may have been requested w/out access to the
+            // viewport (eg: from a search dialog), so it can only be done now.
+            EditorEngineInternalApi::apply_pending_search_jump(
+                editor_buffer,
+                editor_engine,
+            );
+
+            // The viewport width may have changed, which changes how the lines wrap.
+            if soft_wrap::is_enabled(editor_engine) {
+                soft_wrap::validate_scroll(editor_buffer, editor_engine);
+            }
+
             if editor_buffer.is_empty() {
                 EditorEngineApi::render_empty_state(RenderArgs {
                     editor_buffer,
@@ -140,6 +191,14 @@ impl EditorEngineApi {
                     &mut render_ops,
                 );
 
+                EditorEngineApi::render_search_matches(
+                    RenderArgs {
+                        editor_buffer,
+                        editor_engine,
+                        has_focus,
+                    },
+                    &mut render_ops,
+                );
                 EditorEngineApi::render_selection(
                     RenderArgs {
                         editor_buffer,
@@ -164,7 +223,7 @@ impl EditorEngineApi {
         })
     }
 
-    pub fn render_content(render_args: &RenderArgs<'_>, render_ops: &mut RenderOps) {
+    pub fn render_content(render_args: &mut RenderArgs<'_>, render_ops: &mut RenderOps) {
         let RenderArgs {
             editor_buffer,
             editor_engine,
@@ -241,6 +300,112 @@ impl EditorEngineApi {
         };
     }
 
+    /// Highlight the matches of the current search (if any) that are in the viewport. The
+    /// match at the caret is highlighted differently from the rest.
+    fn render_search_matches(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
+        let RenderArgs {
+            editor_buffer,
+            editor_engine,
+            ..
+        } = render_args;
+
+        if !search::is_active(editor_buffer) {
+            return;
+        }
+
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+        let maybe_current_match_index = search::get_current_match_index(editor_buffer);
+        let matches = editor_buffer.search.get_matches();
+
+        if soft_wrap::is_enabled(editor_engine) {
+            let visible_rows = soft_wrap::get_visible_rows(editor_buffer, editor_engine);
+            let Some(last_visible_row) = visible_rows.last() else {
+                return;
+            };
+            for (index, search_match) in matches.iter().enumerate() {
+                if search_match.row_index < scroll_offset.row_index
+                    || search_match.row_index > last_visible_row.row_index
+                {
+                    continue;
+                }
+                let style = if Some(index) == maybe_current_match_index {
+                    get_search_current_match_style()
+                } else {
+                    get_search_match_style()
+                };
+                Self::render_soft_wrapped_range(
+                    editor_buffer,
+                    editor_engine,
+                    &visible_rows,
+                    (search_match.row_index, search_match.range),
+                    style,
+                    render_ops,
+                );
+            }
+            return;
+        }
+
+        // Matches are sorted by row, so skip the ones above the viewport.
+        let first_index =
+            matches.partition_point(|it| it.row_index < scroll_offset.row_index);
+
+        for (index, search_match) in matches.iter().enumerate().skip(first_index) {
+            if search_match.row_index >= scroll_offset.row_index + viewport_height {
+                break;
+            }
+
+            let Some(line) = editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize search_match.row_index))
+            else {
+                continue;
+            };
+
+            // Clip the match to the viewport horizontally.
+            let visible_range = SelectionRange::new(
+                cmp::max(
+                    search_match.range.start_display_col_index,
+                    scroll_offset.col_index,
+                ),
+                cmp::min(
+                    search_match.range.end_display_col_index,
+                    scroll_offset.col_index + viewport_width,
+                ),
+            );
+            if visible_range.start_display_col_index
+                >= visible_range.end_display_col_index
+            {
+                continue;
+            }
+            let text = line.clip_to_range(visible_range);
+            if text.is_empty() {
+                continue;
+            }
+
+            // Convert scroll adjusted to raw.
+            let position = position!(
+                col_index: visible_range.start_display_col_index - scroll_offset.col_index,
+                row_index: search_match.row_index - scroll_offset.row_index
+            );
+
+            let style = if Some(index) == maybe_current_match_index {
+                get_search_current_match_style()
+            } else {
+                get_search_match_style()
+            };
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                position,
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(text.to_string(), None));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
     // BOOKM: Render selection
     fn render_selection(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
         let RenderArgs {
@@ -249,14 +414,56 @@ impl EditorEngineApi {
             ..
         } = render_args;
 
-        for (row_index, range_of_display_col_indices) in
-            editor_buffer.get_selection_map().iter()
-        {
+        Self::render_selection_map(
+            editor_buffer,
+            editor_engine,
+            editor_buffer.get_selection_map(),
+            render_ops,
+        );
+        for caret in &editor_buffer.editor_content.secondary_carets {
+            Self::render_selection_map(
+                editor_buffer,
+                editor_engine,
+                &caret.selection_map,
+                render_ops,
+            );
+        }
+    }
+
+    fn render_selection_map(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        selection_map: &SelectionMap,
+        render_ops: &mut RenderOps,
+    ) {
+        if soft_wrap::is_enabled(editor_engine) {
+            let visible_rows = soft_wrap::get_visible_rows(editor_buffer, editor_engine);
+            for (row_index, range) in selection_map.iter() {
+                Self::render_soft_wrapped_range(
+                    editor_buffer,
+                    editor_engine,
+                    &visible_rows,
+                    (*row_index, *range),
+                    get_selection_style(),
+                    render_ops,
+                );
+            }
+            return;
+        }
+
+        for (row_index, range_of_display_col_indices) in selection_map.iter() {
             let row_index = *row_index;
             let lines = editor_buffer.get_lines();
 
             let scroll_offset = editor_buffer.get_scroll_offset();
 
+            // Skip the rows that are not in the viewport.
+            if row_index < scroll_offset.row_index
+                || row_index >= scroll_offset.row_index + editor_engine.viewport_height()
+            {
+                continue;
+            }
+
             if let Some(line) = lines.get(ch!(@to_usize *row_index)) {
                 // Take the scroll_offset into account when "slicing" the selection.
                 let selection = match range_of_display_col_indices
@@ -329,6 +536,90 @@ impl EditorEngineApi {
         }
     }
 
+    /// Paint the `range` of display cols of the line at `row_index` w/ `style`, when soft
+    /// wrapping is enabled. The range may span multiple visual rows.
+    fn render_soft_wrapped_range(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        visible_rows: &[VisualRow],
+        (row_index, range): (ChUnit, SelectionRange),
+        style: TuiStyle,
+        render_ops: &mut RenderOps,
+    ) {
+        let Some(line) = editor_buffer.get_lines().get(ch!(@to_usize row_index)) else {
+            return;
+        };
+
+        for (screen_row_index, visual_row) in visible_rows.iter().enumerate() {
+            if visual_row.row_index != row_index {
+                continue;
+            }
+
+            // Clip the range to the visual row.
+            let visible_range = SelectionRange::new(
+                cmp::max(
+                    range.start_display_col_index,
+                    visual_row.range.start_display_col_index,
+                ),
+                cmp::min(
+                    range.end_display_col_index,
+                    visual_row.range.end_display_col_index,
+                ),
+            );
+            if visible_range.start_display_col_index
+                >= visible_range.end_display_col_index
+            {
+                continue;
+            }
+            let text = line.clip_to_range(visible_range);
+            if text.is_empty() {
+                continue;
+            }
+
+            let position = position!(
+                col_index: visible_range.start_display_col_index
+                    - visual_row.range.start_display_col_index,
+                row_index: ch!(screen_row_index)
+            );
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                position,
+            ));
+            render_ops.push(RenderOp::ApplyColors(Some(style)));
+            render_ops.push(RenderOp::PaintTextWithAttributes(text.to_string(), None));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    /// Returns the position of the caret on the screen, or [None] if it isn't visible
+    /// (which can only happen w/ soft wrapping).
+    pub fn get_caret_screen_position(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        position: Position,
+    ) -> Option<Position> {
+        if soft_wrap::is_enabled(editor_engine) {
+            let visible_rows = soft_wrap::get_visible_rows(editor_buffer, editor_engine);
+            return soft_wrap::find_screen_position(&visible_rows, position);
+        }
+
+        // The caret may have been scrolled out of view w/ the mouse wheel.
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        if position.row_index < scroll_offset.row_index
+            || position.row_index
+                >= scroll_offset.row_index + editor_engine.viewport_height()
+        {
+            return None;
+        }
+
+        // Convert scroll adjusted to raw.
+        Some(position!(
+            col_index: position.col_index - scroll_offset.col_index,
+            row_index: position.row_index - scroll_offset.row_index
+        ))
+    }
+
     fn render_caret(render_args: RenderArgs<'_>, render_ops: &mut RenderOps) {
         let RenderArgs {
             editor_buffer,
@@ -337,6 +628,10 @@ impl EditorEngineApi {
         } = render_args;
 
         if has_focus.does_id_have_focus(editor_engine.current_box.id) {
+            // Paint the secondary carets first, since the terminal cursor is left at
+            // the primary caret.
+            Self::render_secondary_carets(editor_buffer, editor_engine, render_ops);
+
             let str_at_caret: String = if let Some(UnicodeStringSegmentSliceResult {
                 unicode_string_seg: str_seg,
                 ..
@@ -348,9 +643,17 @@ impl EditorEngineApi {
                 DEFAULT_CURSOR_CHAR.into()
             };
 
+            let Some(caret_screen_position) = Self::get_caret_screen_position(
+                editor_buffer,
+                editor_engine,
+                editor_buffer.get_caret(CaretKind::ScrollAdjusted),
+            ) else {
+                return;
+            };
+
             render_ops.push(RenderOp::MoveCursorPositionRelTo(
                 editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
+                caret_screen_position,
             ));
             render_ops.push(RenderOp::PaintTextWithAttributes(
                 str_at_caret,
@@ -358,7 +661,53 @@ impl EditorEngineApi {
             ));
             render_ops.push(RenderOp::MoveCursorPositionRelTo(
                 editor_engine.current_box.style_adjusted_origin_pos,
-                editor_buffer.get_caret(CaretKind::Raw),
+                caret_screen_position,
+            ));
+            render_ops.push(RenderOp::ResetColor);
+        }
+    }
+
+    fn render_secondary_carets(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        render_ops: &mut RenderOps,
+    ) {
+        let scroll_offset = editor_buffer.get_scroll_offset();
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+
+        for caret in &editor_buffer.editor_content.secondary_carets {
+            let position = caret.position;
+
+            // Skip the carets that are not in the viewport.
+            if !soft_wrap::is_enabled(editor_engine)
+                && (position.row_index < scroll_offset.row_index
+                    || position.row_index >= scroll_offset.row_index + viewport_height
+                    || position.col_index < scroll_offset.col_index
+                    || position.col_index >= scroll_offset.col_index + viewport_width)
+            {
+                continue;
+            }
+            let Some(raw_position) =
+                Self::get_caret_screen_position(editor_buffer, editor_engine, position)
+            else {
+                continue;
+            };
+
+            let str_at_caret: String = editor_buffer
+                .get_lines()
+                .get(ch!(@to_usize position.row_index))
+                .and_then(|line| line.get_string_at_display_col_index(position.col_index))
+                .map(|it| it.unicode_string_seg.string)
+                .unwrap_or_else(|| DEFAULT_CURSOR_CHAR.into());
+
+            render_ops.push(RenderOp::MoveCursorPositionRelTo(
+                editor_engine.current_box.style_adjusted_origin_pos,
+                raw_position,
+            ));
+            render_ops.push(RenderOp::PaintTextWithAttributes(
+                str_at_caret,
+                tui_style! { attrib: [reverse] }.into(),
             ));
             render_ops.push(RenderOp::ResetColor);
         }
@@ -392,25 +741,27 @@ impl EditorEngineApi {
                 RenderOp::ResetColor
             };
 
-            // Paint line 2.
-            render_pipeline! {
-              @push_into pipeline
-              at ZOrder::Normal
-              =>
-                RenderOp::MoveCursorPositionRelTo(
-                    editor_engine.current_box.style_adjusted_origin_pos,
-                    content_cursor_pos.add_row_with_bounds(
-                        ch!(1),
-                        editor_engine.current_box.style_adjusted_bounds_size.row_count
-                    )
-                ),
-                RenderOp::ApplyColors(tui_style! {
-                    attrib: [dim]
-                    color_fg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
-                }.into()),
-                RenderOp::PaintTextWithAttributes("🧭 Ctrl+S: Save your work. Ctrl+Q: Exit the app.".into(), None),
-                RenderOp::ResetColor
-            };
+            // Paint line 2, w/ the current bindings of the actions in the hint.
+            if let Some(hint) = editor_engine.empty_state_hint.get_text() {
+                render_pipeline! {
+                  @push_into pipeline
+                  at ZOrder::Normal
+                  =>
+                    RenderOp::MoveCursorPositionRelTo(
+                        editor_engine.current_box.style_adjusted_origin_pos,
+                        content_cursor_pos.add_row_with_bounds(
+                            ch!(1),
+                            editor_engine.current_box.style_adjusted_bounds_size.row_count
+                        )
+                    ),
+                    RenderOp::ApplyColors(tui_style! {
+                        attrib: [dim]
+                        color_fg: TuiColor::Basic(ANSIBasicColor::DarkGrey)
+                    }.into()),
+                    RenderOp::PaintTextWithAttributes(format!("🧭 {hint}"), None),
+                    RenderOp::ResetColor
+                };
+            }
         }
 
         pipeline
@@ -420,6 +771,55 @@ impl EditorEngineApi {
 pub enum EditorEngineApplyEventResult {
     Applied,
     NotApplied,
+    /// The event didn't change anything, but it isn't meant for anything else either (eg:
+    /// the first key press of a chord, see [Keymap]).
+    Consumed,
+}
+
+/// The part of a line in the buffer that is painted on a row of the screen. W/out soft
+/// wrapping, this is the line clipped to the viewport using the scroll offset. W/ soft
+/// wrapping, a line can be painted on multiple rows (see [soft_wrap]).
+#[derive(Clone, Copy, Debug)]
+struct ContentRow {
+    screen_row_index: ChUnit,
+    /// Index of the line in the buffer.
+    row_index: usize,
+    col_index: ChUnit,
+    col_count: ChUnit,
+}
+
+fn get_content_rows(
+    editor_buffer: &EditorBuffer,
+    editor_engine: &EditorEngine,
+    max_display_row_count: ChUnit,
+    max_display_col_count: ChUnit,
+) -> Vec<ContentRow> {
+    if soft_wrap::is_enabled(editor_engine) {
+        return soft_wrap::get_visible_rows(editor_buffer, editor_engine)
+            .into_iter()
+            .enumerate()
+            .map(|(screen_row_index, visual_row)| ContentRow {
+                screen_row_index: ch!(screen_row_index),
+                row_index: ch!(@to_usize visual_row.row_index),
+                col_index: visual_row.range.start_display_col_index,
+                col_count: visual_row.range.end_display_col_index
+                    - visual_row.range.start_display_col_index,
+            })
+            .collect();
+    }
+
+    let scroll_offset = editor_buffer.get_scroll_offset();
+    let row_count = editor_buffer.len();
+    // Note that this goes up to (and including) the max row.
+    (0..=ch!(@to_usize max_display_row_count))
+        .map(|screen_row_index| ContentRow {
+            screen_row_index: ch!(screen_row_index),
+            row_index: ch!(@to_usize scroll_offset.row_index) + screen_row_index,
+            col_index: scroll_offset.col_index,
+            col_count: max_display_col_count,
+        })
+        .take_while(|it| it.row_index < ch!(@to_usize row_count))
+        .collect()
 }
 
 mod syn_hi_r3bl_path {
@@ -427,15 +827,16 @@ mod syn_hi_r3bl_path {
 
     /// Try convert [Vec] of [US] to [MdDocument]:
     /// - Step 1: Get the lines from the buffer using
-    ///           [editor_buffer.get_lines()](EditorBuffer::get_lines()).
+    ///   [editor_buffer.get_lines()](EditorBuffer::get_lines()).
     /// - Step 2: Convert the lines into a [List] of [StyleUSSpanLine] using
-    ///           [try_parse_and_highlight()]. If this fails then take the path of no
-    ///           syntax highlighting else take the path of syntax highlighting.
+    ///   [MdSynHiCache::try_parse_and_highlight()], which only parses the blocks that have
+    ///   changed since the last render. If this fails then take the path of no syntax
+    ///   highlighting else take the path of syntax highlighting.
     pub fn render_content(
         editor_buffer: &&EditorBuffer,
         max_display_row_count: ChUnit,
         render_ops: &mut RenderOps,
-        editor_engine: &&mut EditorEngine,
+        editor_engine: &mut EditorEngine,
         max_display_col_count: ChUnit,
     ) {
         // Try to parse the Vec<US> into an MDDocument & render it.
@@ -459,16 +860,26 @@ mod syn_hi_r3bl_path {
         editor_buffer: &&EditorBuffer,
         max_display_row_count: ChUnit,
         render_ops: &mut RenderOps,
-        editor_engine: &&mut EditorEngine,
+        editor_engine: &mut EditorEngine,
         max_display_col_count: ChUnit,
     ) -> CommonResult<()> {
         throws!({
-            let lines = try_parse_and_highlight(
+            let EditorEngine {
+                current_box,
+                syntax_set,
+                theme,
+                md_syn_hi_cache,
+                ..
+            } = editor_engine;
+            md_syn_hi_cache.try_parse_and_highlight(
                 editor_buffer.get_lines(),
-                &editor_engine.current_box.get_computed_style(),
-                Some((&editor_engine.syntax_set, &editor_engine.theme)),
+                &current_box.get_computed_style(),
+                Some((syntax_set, theme)),
             )?;
 
+            let editor_engine = &*editor_engine;
+            let lines = &editor_engine.md_syn_hi_cache;
+
             call_if_true!(DEBUG_TUI_SYN_HI, {
                 log_debug(format!(
                     "\n🎯🎯🎯\neditor_buffer.lines.len(): {} vs md_document.lines.len(): {}\n{}\n{}🎯🎯🎯",
@@ -479,43 +890,33 @@ mod syn_hi_r3bl_path {
                 ));
             });
 
-            for (row_index, line) in lines
-                .iter()
-                .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-                .enumerate()
-            {
-                // Clip the content to max rows.
-                if ch!(row_index) > max_display_row_count {
+            for content_row in get_content_rows(
+                editor_buffer,
+                editor_engine,
+                max_display_row_count,
+                max_display_col_count,
+            ) {
+                let Some(line) = lines.get(content_row.row_index) else {
                     break;
-                }
+                };
 
-                render_single_line(
-                    line,
-                    editor_buffer,
-                    editor_engine,
-                    row_index,
-                    max_display_col_count,
-                    render_ops,
-                );
+                render_single_line(line, editor_engine, content_row, render_ops);
             }
         });
     }
 
     fn render_single_line(
         line: &List<StyleUSSpan>,
-        editor_buffer: &&EditorBuffer,
-        editor_engine: &&mut EditorEngine,
-        row_index: usize,
-        max_display_col_count: ChUnit,
+        editor_engine: &EditorEngine,
+        content_row: ContentRow,
         render_ops: &mut RenderOps,
     ) {
         render_ops.push(RenderOp::MoveCursorPositionRelTo(
             editor_engine.current_box.style_adjusted_origin_pos,
-            position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
+            position! { col_index: 0 , row_index: content_row.screen_row_index },
         ));
-        let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
         let styled_texts: TuiStyledTexts =
-            line.clip(scroll_offset_col, max_display_col_count);
+            line.clip(content_row.col_index, content_row.col_count);
         styled_texts.render_into(render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
@@ -531,41 +932,37 @@ mod syn_hi_syntect_path {
         editor_engine: &&mut EditorEngine,
         max_display_col_count: ChUnit,
     ) {
-        // Paint each line in the buffer (skipping the scroll_offset.row).
-        // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
-        for (row_index, line) in editor_buffer
-            .get_lines()
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-            .enumerate()
-        {
-            // Clip the content to max rows.
-            if ch!(row_index) > max_display_row_count {
+        // Paint each line in the buffer that is in the viewport.
+        for content_row in get_content_rows(
+            editor_buffer,
+            editor_engine,
+            max_display_row_count,
+            max_display_col_count,
+        ) {
+            let Some(line) = editor_buffer.get_lines().get(content_row.row_index) else {
                 break;
-            }
+            };
 
             render_single_line(
                 render_ops,
-                row_index,
+                content_row,
                 editor_engine,
                 editor_buffer,
                 line,
-                max_display_col_count,
             );
         }
     }
 
     fn render_single_line(
         render_ops: &mut RenderOps,
-        row_index: usize,
+        content_row: ContentRow,
         editor_engine: &&mut EditorEngine,
         editor_buffer: &&EditorBuffer,
         line: &UnicodeString,
-        max_display_col_count: ChUnit,
     ) {
         render_ops.push(RenderOp::MoveCursorPositionRelTo(
             editor_engine.current_box.style_adjusted_origin_pos,
-            position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
+            position! { col_index: 0 , row_index: content_row.screen_row_index },
         ));
 
         let it =
@@ -576,8 +973,7 @@ mod syn_hi_syntect_path {
             Some(syntect_highlighted_line) => {
                 render_line_with_syntect(
                     syntect_highlighted_line,
-                    editor_buffer,
-                    max_display_col_count,
+                    content_row,
                     render_ops,
                 );
             }
@@ -585,8 +981,7 @@ mod syn_hi_syntect_path {
             None => {
                 no_syn_hi_path::render_line_no_syntax_highlight(
                     line,
-                    editor_buffer,
-                    max_display_col_count,
+                    content_row,
                     render_ops,
                     editor_engine,
                 );
@@ -596,17 +991,15 @@ mod syn_hi_syntect_path {
 
     fn render_line_with_syntect(
         syntect_highlighted_line: Vec<(syntect::highlighting::Style, &str)>,
-        editor_buffer: &&EditorBuffer,
-        max_display_col_count: ChUnit,
+        content_row: ContentRow,
         render_ops: &mut RenderOps,
     ) {
-        let scroll_offset_col = editor_buffer.get_scroll_offset().col_index;
         let list: List<StyleUSSpan> =
             syntect_to_styled_text_conversion::from_syntect_to_tui(
                 syntect_highlighted_line,
             );
         let styled_texts: TuiStyledTexts =
-            list.clip(scroll_offset_col, max_display_col_count);
+            list.clip(content_row.col_index, content_row.col_count);
         styled_texts.render_into(render_ops);
         render_ops.push(RenderOp::ResetColor);
     }
@@ -641,47 +1034,35 @@ mod no_syn_hi_path {
         editor_engine: &&mut EditorEngine,
         max_display_col_count: ChUnit,
     ) {
-        // Paint each line in the buffer (skipping the scroll_offset.row).
-        // https://doc.rust-lang.org/std/iter/trait.Iterator.html#method.skip
-        for (row_index, line) in editor_buffer
-            .get_lines()
-            .iter()
-            .skip(ch!(@to_usize editor_buffer.get_scroll_offset().row_index))
-            .enumerate()
-        {
-            // Clip the content to max rows.
-            if ch!(row_index) > max_display_row_count {
+        // Paint each line in the buffer that is in the viewport.
+        for content_row in get_content_rows(
+            editor_buffer,
+            editor_engine,
+            max_display_row_count,
+            max_display_col_count,
+        ) {
+            let Some(line) = editor_buffer.get_lines().get(content_row.row_index) else {
                 break;
-            }
+            };
 
-            render_single_line(
-                render_ops,
-                row_index,
-                editor_engine,
-                editor_buffer,
-                line,
-                max_display_col_count,
-            );
+            render_single_line(render_ops, content_row, editor_engine, line);
         }
     }
 
     fn render_single_line(
         render_ops: &mut RenderOps,
-        row_index: usize,
+        content_row: ContentRow,
         editor_engine: &&mut EditorEngine,
-        editor_buffer: &&EditorBuffer,
         line: &UnicodeString,
-        max_display_col_count: ChUnit,
     ) {
         render_ops.push(RenderOp::MoveCursorPositionRelTo(
             editor_engine.current_box.style_adjusted_origin_pos,
-            position! { col_index: 0 , row_index: ch!(@to_usize row_index) },
+            position! { col_index: 0 , row_index: content_row.screen_row_index },
         ));
 
         no_syn_hi_path::render_line_no_syntax_highlight(
             line,
-            editor_buffer,
-            max_display_col_count,
+            content_row,
             render_ops,
             editor_engine,
         );
@@ -690,16 +1071,13 @@ mod no_syn_hi_path {
     /// This is used as a fallback by other render paths.
     pub fn render_line_no_syntax_highlight(
         line: &UnicodeString,
-        editor_buffer: &&EditorBuffer,
-        max_display_col_count: ChUnit,
+        content_row: ContentRow,
         render_ops: &mut RenderOps,
         editor_engine: &&mut EditorEngine,
     ) {
-        let scroll_offset_col_index = editor_buffer.get_scroll_offset().col_index;
-
-        // Clip the content [scroll_offset.col .. max cols].
+        // Clip the content [col_index .. col_index + col_count].
         let truncated_line =
-            line.clip_to_width(scroll_offset_col_index, max_display_col_count);
+            line.clip_to_width(content_row.col_index, content_row.col_count);
 
         render_ops.push(RenderOp::ApplyColors(
             editor_engine.current_box.get_computed_style(),
@@ -743,7 +1121,13 @@ mod test_cache {
             has_focus,
             render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
 
         // Render the caret to screen. This should not change the content and result in a cache hit.
         EditorEngineApi::render_caret(
@@ -775,7 +1159,13 @@ mod test_cache {
             has_focus,
             render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
 
         // Render the selection of text to screen. This should not change the content and result in a cache hit.
         EditorEngineApi::render_selection(
@@ -795,6 +1185,25 @@ mod test_cache {
         );
         test_cache_hit(editor_buffer, &mut cache);
 
+        // Moving the box that the editor is rendered in should invalidate the cache and
+        // result in a cache miss.
+        editor_engine.current_box.style_adjusted_origin_pos =
+            position!(col_index: 10, row_index: 1);
+        cache::render_content(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            has_focus,
+            render_ops,
+        );
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
+
         // Change in scroll_offset should invalidate the cache and result in a cache miss.
         editor_buffer.editor_content.scroll_offset = ScrollOffset {
             col_index: ch!(1),
@@ -807,7 +1216,13 @@ mod test_cache {
             has_focus,
             render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
 
         // Change in content should invalidate the cache and result in a cache miss.
         editor_buffer.set_lines(vec!["r3bl".to_string()]);
@@ -818,17 +1233,24 @@ mod test_cache {
             has_focus,
             render_ops,
         );
-        test_cache_miss(editor_buffer, window_size, render_ops, &mut cache);
+        test_cache_miss(
+            editor_buffer,
+            editor_engine,
+            window_size,
+            render_ops,
+            &mut cache,
+        );
     }
 
     fn test_cache_miss(
         editor_buffer: &mut EditorBuffer,
+        editor_engine: &EditorEngine,
         window_size: Size,
         render_ops: &mut RenderOps,
         cache: &mut HashMap<String, RenderOps>,
     ) {
         cache.clear(); // invalidating cache
-        let key = format!("{}{}", editor_buffer.get_scroll_offset(), window_size); // generating key
+        let key = cache::generate_key(editor_buffer, editor_engine, window_size); // generating key
         cache.insert(key, render_ops.clone()); // enter the new entry into cache
         assert_eq2!(editor_buffer.render_cache, cache.clone());
     }
@@ -0,0 +1,170 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// The opening and closing chars that are auto paired.
+pub const AUTO_PAIRS: [(char, char); 6] = [
+    ('(', ')'),
+    ('[', ']'),
+    ('{', '}'),
+    ('"', '"'),
+    ('\'', '\''),
+    ('`', '`'),
+];
+
+/// Brackets and quotes are auto paired when this is enabled w/
+/// [EditorEngineConfig::auto_pair].
+/// - Typing an opening char also inserts the closing one after the caret, if the caret
+///   is at the end of the line, or before whitespace or a closing char. Quotes are not
+///   paired right after a word (so that apostrophes can be typed).
+/// - Typing a closing char right before the same char skips over it.
+/// - Backspace between an empty pair deletes both chars.
+pub mod auto_pair {
+    use super::*;
+
+    pub fn is_enabled(editor_engine: &EditorEngine) -> bool {
+        editor_engine.config_options.auto_pair == AutoPairMode::Enable
+    }
+
+    fn is_closing_char(character: char) -> bool {
+        AUTO_PAIRS.iter().any(|(_, closing)| *closing == character)
+    }
+
+    /// Returns the chars right before and right after the caret.
+    fn get_chars_around_caret(
+        editor_buffer: &EditorBuffer,
+    ) -> (Option<char>, Option<char>) {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let Some(line) = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret.row_index))
+        else {
+            return (None, None);
+        };
+
+        let maybe_prev_char = line
+            .iter()
+            .find(|segment| {
+                segment.display_col_offset + segment.unicode_width == caret.col_index
+            })
+            .and_then(|segment| segment.string.chars().last());
+        let maybe_next_char = line
+            .iter()
+            .find(|segment| segment.display_col_offset == caret.col_index)
+            .and_then(|segment| segment.string.chars().next());
+
+        (maybe_prev_char, maybe_next_char)
+    }
+
+    fn move_caret_by_one_col(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        is_left: bool,
+    ) {
+        let mut caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        if is_left {
+            caret.col_index -= 1;
+        } else {
+            caret.col_index += 1;
+        }
+        EditorEngineInternalApi::move_caret_to(editor_buffer, editor_engine, caret);
+    }
+
+    /// Type `character`, pairing it or skipping over it if needed. Returns `false` if it
+    /// should be inserted as usual.
+    pub fn insert_char(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        character: char,
+    ) -> bool {
+        if editor_buffer.has_selection() {
+            return false;
+        }
+
+        let (maybe_prev_char, maybe_next_char) = get_chars_around_caret(editor_buffer);
+
+        // Skip over the closing char.
+        if is_closing_char(character) && maybe_next_char == Some(character) {
+            move_caret_by_one_col(editor_buffer, editor_engine, false);
+            return true;
+        }
+
+        let Some((_, closing)) =
+            AUTO_PAIRS.iter().find(|(opening, _)| *opening == character)
+        else {
+            return false;
+        };
+
+        let is_next_char_ok = match maybe_next_char {
+            Some(next_char) => next_char.is_whitespace() || is_closing_char(next_char),
+            None => true,
+        };
+        let is_quote = character == *closing;
+        let is_prev_char_ok = match maybe_prev_char {
+            Some(prev_char) if is_quote => {
+                !prev_char.is_alphanumeric() && prev_char != character
+            }
+            _ => true,
+        };
+        if !is_next_char_ok || !is_prev_char_ok {
+            return false;
+        }
+
+        EditorEngineInternalApi::insert_str_at_caret(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            &format!("{character}{closing}"),
+        );
+        move_caret_by_one_col(editor_buffer, editor_engine, true);
+
+        true
+    }
+
+    /// Delete both chars if the caret is between an empty pair. Returns `false` if it
+    /// isn't, and backspace should work as usual.
+    pub fn backspace(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> bool {
+        if editor_buffer.has_selection() {
+            return false;
+        }
+
+        let (Some(prev_char), Some(next_char)) = get_chars_around_caret(editor_buffer)
+        else {
+            return false;
+        };
+        if !AUTO_PAIRS.contains(&(prev_char, next_char)) {
+            return false;
+        }
+
+        let col_index = editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index;
+        EditorEngineInternalApi::replace_range_in_caret_line(
+            editor_buffer,
+            editor_engine,
+            SelectionRange::new(col_index - 1, col_index + 1),
+            "",
+        );
+
+        true
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Indenting w/ spaces.
+/// - A new line gets the leading whitespace of the line that it is split from (see
+///   [EditorEngineConfig::auto_indent]).
+/// - Tab inserts spaces up to the next tab stop, and Shift+Tab outdents the caret's line.
+///   Both move by [EditorEngineConfig::tab_width] cols.
+/// - When text is selected, Tab / Shift+Tab indent / outdent all of the selected lines.
+/// - Markdown list items are indented by [smart_list] instead.
+pub mod indent {
+    use super::*;
+
+    pub fn get_leading_whitespace(line: &str) -> &str {
+        &line[..line.len() - line.trim_start().len()]
+    }
+
+    fn get_tab_width(editor_engine: &EditorEngine) -> usize {
+        std::cmp::max(editor_engine.config_options.tab_width, 1)
+    }
+
+    /// Split the line at the caret, and indent the new line like the line it was split
+    /// from (up to the caret, if it is in the leading whitespace).
+    pub fn insert_new_line(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let indent = match editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret.row_index))
+        {
+            Some(line)
+                if editor_engine.config_options.auto_indent == AutoIndentMode::Enable =>
+            {
+                let leading_whitespace = get_leading_whitespace(&line.string);
+                UnicodeString::from(leading_whitespace)
+                    .clip_to_width(ch!(0), caret.col_index)
+                    .to_string()
+            }
+            _ => String::new(),
+        };
+
+        EditorEngineInternalApi::insert_new_line_at_caret(EditorArgsMut {
+            editor_buffer,
+            editor_engine,
+        });
+        if !indent.is_empty() {
+            EditorEngineInternalApi::insert_str_at_caret(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                &indent,
+            );
+        }
+    }
+
+    /// Handle Tab (or Shift+Tab if `is_outdent`).
+    pub fn indent_or_outdent(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        is_outdent: bool,
+    ) {
+        if editor_buffer.has_selection() {
+            let row_indices = editor_buffer.get_selection_map().get_ordered_indices();
+            indent_lines(editor_buffer, editor_engine, &row_indices, is_outdent);
+            return;
+        }
+
+        if smart_list::is_enabled(editor_buffer, editor_engine)
+            && smart_list::indent(editor_buffer, editor_engine, is_outdent)
+        {
+            return;
+        }
+
+        if is_outdent {
+            let row_index = editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+            indent_lines(editor_buffer, editor_engine, &[row_index], true);
+        } else {
+            insert_tab(editor_buffer, editor_engine);
+        }
+    }
+
+    /// Insert spaces up to the next tab stop.
+    pub fn insert_tab(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) {
+        let tab_width = get_tab_width(editor_engine);
+        let col_index =
+            ch!(@to_usize editor_buffer.get_caret(CaretKind::ScrollAdjusted).col_index);
+        let space_count = tab_width - col_index % tab_width;
+        EditorEngineInternalApi::insert_str_at_caret(
+            EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            },
+            &SPACER.repeat(space_count),
+        );
+    }
+
+    /// Indent (or outdent) the lines at `row_indices` by
+    /// [EditorEngineConfig::tab_width] cols. Empty lines aren't indented, and outdenting
+    /// only removes leading whitespace. The caret and the selection move along w/ the
+    /// text.
+    pub fn indent_lines(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        row_indices: &[ChUnit],
+        is_outdent: bool,
+    ) -> Option<()> {
+        let tab_width = get_tab_width(editor_engine);
+
+        // The new text of each line that changes, and how many cols it moved by.
+        let mut changed_lines: Vec<(ChUnit, String, ChUnit)> = vec![];
+        for row_index in row_indices {
+            let Some(line) = editor_buffer.get_lines().get(ch!(@to_usize *row_index))
+            else {
+                continue;
+            };
+            if is_outdent {
+                let leading_whitespace = get_leading_whitespace(&line.string);
+                let removed_byte_count = if leading_whitespace.starts_with('\t') {
+                    1
+                } else {
+                    std::cmp::min(
+                        leading_whitespace
+                            .chars()
+                            .take_while(|it| *it == ' ')
+                            .count(),
+                        tab_width,
+                    )
+                };
+                if removed_byte_count > 0 {
+                    changed_lines.push((
+                        *row_index,
+                        line.string[removed_byte_count..].to_string(),
+                        ch!(removed_byte_count),
+                    ));
+                }
+            } else if !line.string.is_empty() {
+                changed_lines.push((
+                    *row_index,
+                    format!("{}{}", SPACER.repeat(tab_width), line.string),
+                    ch!(tab_width),
+                ));
+            }
+        }
+
+        if changed_lines.is_empty() {
+            return None;
+        }
+
+        let shift = |col_index: ChUnit, delta: ChUnit| {
+            if is_outdent {
+                col_index - delta
+            } else {
+                col_index + delta
+            }
+        };
+
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let mut new_caret = caret;
+
+        {
+            let (lines, _, _, selection_map) = editor_buffer.get_mut();
+            for (row_index, new_line, delta) in &changed_lines {
+                lines[ch!(@to_usize *row_index)] = new_line.as_str().into();
+                if let Some(range) = selection_map.map.get_mut(row_index) {
+                    range.start_display_col_index =
+                        shift(range.start_display_col_index, *delta);
+                    range.end_display_col_index =
+                        shift(range.end_display_col_index, *delta);
+                }
+                if *row_index == caret.row_index {
+                    new_caret.col_index = shift(caret.col_index, *delta);
+                }
+            }
+        }
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |_, _, _| {},
+        );
+        EditorEngineInternalApi::move_caret_to(editor_buffer, editor_engine, new_caret);
+
+        None
+    }
+}
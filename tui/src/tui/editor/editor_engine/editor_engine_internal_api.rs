@@ -32,6 +32,9 @@ impl EditorEngineInternalApi {
         engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
+        if soft_wrap::is_enabled(engine) {
+            return soft_wrap_caret_mut::up(buffer, engine, select_mode);
+        }
         caret_mut::up(buffer, engine, select_mode)
     }
 
@@ -56,6 +59,9 @@ impl EditorEngineInternalApi {
         engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
+        if soft_wrap::is_enabled(engine) {
+            return soft_wrap_caret_mut::down(buffer, engine, select_mode);
+        }
         caret_mut::down(buffer, engine, select_mode)
     }
 
@@ -64,6 +70,9 @@ impl EditorEngineInternalApi {
         engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
+        if soft_wrap::is_enabled(engine) {
+            return soft_wrap_caret_mut::page_up(buffer, engine, select_mode);
+        }
         caret_mut::page_up(buffer, engine, select_mode)
     }
 
@@ -72,6 +81,9 @@ impl EditorEngineInternalApi {
         engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
+        if soft_wrap::is_enabled(engine) {
+            return soft_wrap_caret_mut::page_down(buffer, engine, select_mode);
+        }
         caret_mut::page_down(buffer, engine, select_mode)
     }
 
@@ -80,6 +92,9 @@ impl EditorEngineInternalApi {
         engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
+        if soft_wrap::is_enabled(engine) {
+            return soft_wrap_caret_mut::to_start_of_line(buffer, engine, select_mode);
+        }
         caret_mut::to_start_of_line(buffer, engine, select_mode)
     }
 
@@ -88,9 +103,35 @@ impl EditorEngineInternalApi {
         engine: &mut EditorEngine,
         select_mode: SelectMode,
     ) -> Option<()> {
+        if soft_wrap::is_enabled(engine) {
+            return soft_wrap_caret_mut::to_end_of_line(buffer, engine, select_mode);
+        }
         caret_mut::to_end_of_line(buffer, engine, select_mode)
     }
 
+    pub fn word_left(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        caret_mut::word_left(buffer, engine, select_mode)
+    }
+
+    pub fn word_right(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        caret_mut::word_right(buffer, engine, select_mode)
+    }
+
+    pub fn select_word(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        caret_mut::select_word(buffer, engine)
+    }
+
     pub fn select_all(buffer: &mut EditorBuffer, select_mode: SelectMode) -> Option<()> {
         caret_mut::select_all(buffer, select_mode)
     }
@@ -100,9 +141,51 @@ impl EditorEngineInternalApi {
     }
 
     pub fn validate_scroll(args: EditorArgsMut<'_>) {
+        if soft_wrap::is_enabled(args.editor_engine) {
+            soft_wrap::validate_scroll(args.editor_buffer, args.editor_engine);
+            return;
+        }
         scroll_editor_buffer::validate_scroll(args);
     }
 
+    pub fn move_caret_to(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        position: Position,
+    ) -> Option<()> {
+        caret_mut::to_position(buffer, engine, position)
+    }
+
+    pub fn jump_to_search_match(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        jump: SearchJump,
+    ) -> Option<()> {
+        search_mut::jump_to_match(buffer, engine, jump)
+    }
+
+    pub fn apply_pending_search_jump(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        search_mut::apply_pending_jump(buffer, engine)
+    }
+
+    pub fn replace_search_match(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        replacement: &str,
+    ) -> Option<()> {
+        search_mut::replace_current(buffer, engine, replacement)
+    }
+
+    pub fn replace_all_search_matches(
+        buffer: &mut EditorBuffer,
+        replacement: &str,
+    ) -> Option<()> {
+        search_mut::replace_all(buffer, replacement)
+    }
+
     pub fn string_at_caret(
         buffer: &EditorBuffer,
         engine: &EditorEngine,
@@ -151,6 +234,29 @@ impl EditorEngineInternalApi {
         content_mut::backspace_at_caret(buffer, engine)
     }
 
+    pub fn backspace_word_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::backspace_word_at_caret(buffer, engine)
+    }
+
+    pub fn delete_word_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        content_mut::delete_word_at_caret(buffer, engine)
+    }
+
+    pub fn replace_range_in_caret_line(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        range: SelectionRange,
+        text: &str,
+    ) -> Option<()> {
+        content_mut::replace_range_in_caret_line(buffer, engine, range, text)
+    }
+
     pub fn copy_editor_selection_to_clipboard(
         buffer: &EditorBuffer,
         clipboard: &mut impl ClipboardService,
@@ -639,6 +745,168 @@ mod caret_mut {
         None
     }
 
+    /// Move the caret to the start of the word to the left of it (see
+    /// [UnicodeString::find_prev_word_start]). If there are no words to the left of the
+    /// caret, then move it to the start of the line, and from there to the end of the
+    /// previous line.
+    pub fn word_left(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret.row_index))?;
+
+        match line.find_prev_word_start(caret.col_index) {
+            Some(col_index) => {
+                to_col_in_line(editor_buffer, editor_engine, select_mode, col_index)
+            }
+            None if caret.col_index > ch!(0) => {
+                to_col_in_line(editor_buffer, editor_engine, select_mode, ch!(0))
+            }
+            None => left(editor_buffer, editor_engine, select_mode),
+        }
+    }
+
+    /// Move the caret to the end of the word to the right of it (see
+    /// [UnicodeString::find_next_word_end]). If there are no words to the right of the
+    /// caret, then move it to the end of the line, and from there to the start of the
+    /// next line.
+    pub fn word_right(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret.row_index))?;
+        let line_display_width = line.display_width;
+
+        match line.find_next_word_end(caret.col_index) {
+            Some(col_index) => {
+                to_col_in_line(editor_buffer, editor_engine, select_mode, col_index)
+            }
+            None if caret.col_index < line_display_width => to_col_in_line(
+                editor_buffer,
+                editor_engine,
+                select_mode,
+                line_display_width,
+            ),
+            None => right(editor_buffer, editor_engine, select_mode),
+        }
+    }
+
+    /// Select the word at the caret (or the one that ends at it), and move the caret to
+    /// its end.
+    pub fn select_word(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let range = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret.row_index))?
+            .get_word_range_at_display_col(caret.col_index)?;
+
+        editor_buffer.clear_selection();
+        to_col_in_line(
+            editor_buffer,
+            editor_engine,
+            SelectMode::Disabled,
+            range.start_display_col_index,
+        );
+        to_col_in_line(
+            editor_buffer,
+            editor_engine,
+            SelectMode::Enabled,
+            range.end_display_col_index,
+        )
+    }
+
+    /// Convenience function for calling [left] or [right] repeatedly, until the caret is
+    /// at `col_index` in the same line. This takes care of scrolling and (depending on
+    /// [SelectMode]) the selection.
+    pub fn to_col_in_line(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+        col_index: ChUnit,
+    ) -> Option<()> {
+        loop {
+            let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+            match caret.col_index.cmp(&col_index) {
+                Ordering::Greater => {
+                    left(editor_buffer, editor_engine, select_mode);
+                }
+                Ordering::Less => {
+                    right(editor_buffer, editor_engine, select_mode);
+                }
+                Ordering::Equal => break,
+            }
+            // Don't loop forever if the caret can't move, or moved to another line.
+            let new_caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+            if new_caret == caret || new_caret.row_index != caret.row_index {
+                break;
+            }
+        }
+
+        None
+    }
+
+    /// Move the caret to the given (scroll adjusted) position. If the position is not in
+    /// the viewport, then scroll so that it is. Vertically, the position ends up in the
+    /// middle of the viewport, so that the lines around it are visible too.
+    pub fn to_position(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        position: Position,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+
+        // The caret is placed explicitly, so it is no longer scrolled out of view.
+        editor_buffer.editor_content.maybe_caret_outside_viewport = None;
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |_, caret, scroll_offset| {
+                let is_row_within_viewport = position.row_index
+                    >= scroll_offset.row_index
+                    && position.row_index < scroll_offset.row_index + viewport_height;
+                if !is_row_within_viewport {
+                    scroll_offset.row_index = position.row_index - viewport_height / 2;
+                }
+                caret.row_index = position.row_index - scroll_offset.row_index;
+
+                let is_col_within_viewport = position.col_index
+                    >= scroll_offset.col_index
+                    && position.col_index < scroll_offset.col_index + viewport_width;
+                if !is_col_within_viewport {
+                    scroll_offset.col_index = if position.col_index < viewport_width {
+                        ch!(0)
+                    } else {
+                        position.col_index - viewport_width + ch!(1)
+                    };
+                }
+                caret.col_index = position.col_index - scroll_offset.col_index;
+            },
+        );
+
+        None
+    }
+
     pub fn clear_selection(editor_buffer: &mut EditorBuffer) -> Option<()> {
         editor_buffer.clear_selection();
 
@@ -938,6 +1206,297 @@ mod caret_mut {
     }
 }
 
+/// Caret movement that is used instead of [caret_mut] when [soft_wrap] is enabled. Up,
+/// down, home, end, page up and page down work on visual rows instead of lines.
+mod soft_wrap_caret_mut {
+    use super::*;
+
+    pub fn up(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        move_by_visual_rows(
+            editor_buffer,
+            editor_engine,
+            select_mode,
+            ch!(1),
+            CaretDirection::Up,
+        )
+    }
+
+    pub fn down(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        move_by_visual_rows(
+            editor_buffer,
+            editor_engine,
+            select_mode,
+            ch!(1),
+            CaretDirection::Down,
+        )
+    }
+
+    pub fn page_up(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        let viewport_height = editor_engine.viewport_height();
+        move_by_visual_rows(
+            editor_buffer,
+            editor_engine,
+            select_mode,
+            viewport_height,
+            CaretDirection::Up,
+        )
+    }
+
+    pub fn page_down(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        let viewport_height = editor_engine.viewport_height();
+        move_by_visual_rows(
+            editor_buffer,
+            editor_engine,
+            select_mode,
+            viewport_height,
+            CaretDirection::Down,
+        )
+    }
+
+    /// Move to the start of the visual row that the caret is on.
+    pub fn to_start_of_line(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let (_, visual_rows, visual_row_index) =
+            get_visual_rows_at_caret(editor_buffer, editor_engine)?;
+        let col_index = visual_rows[visual_row_index].start_display_col_index;
+        move_caret_in_line(editor_buffer, editor_engine, select_mode, col_index);
+
+        None
+    }
+
+    /// Move to the end of the visual row that the caret is on.
+    pub fn to_end_of_line(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+
+        let (line, visual_rows, visual_row_index) =
+            get_visual_rows_at_caret(editor_buffer, editor_engine)?;
+        let col_index = soft_wrap::get_col_index_in_visual_row(
+            &line,
+            &visual_rows,
+            visual_row_index,
+            editor_engine.viewport_width(),
+        );
+        move_caret_in_line(editor_buffer, editor_engine, select_mode, col_index);
+
+        None
+    }
+
+    fn get_visual_rows_at_caret(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+    ) -> Option<(UnicodeString, Vec<SelectionRange>, usize)> {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret.row_index))?
+            .clone();
+        let visual_rows = soft_wrap::wrap_line(&line, editor_engine.viewport_width());
+        let visual_row_index =
+            soft_wrap::find_visual_row_index(&visual_rows, caret.col_index);
+        Some((line, visual_rows, visual_row_index))
+    }
+
+    /// Move the caret to `col_index` in the same line. When [SelectMode::Enabled] is used,
+    /// this is done one grapheme cluster at a time, so that the selection is updated
+    /// just like it is for <kbd>Shift + Left</kbd> and <kbd>Shift + Right</kbd>.
+    fn move_caret_in_line(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+        col_index: ChUnit,
+    ) {
+        match select_mode {
+            SelectMode::Enabled => {
+                caret_mut::to_col_in_line(
+                    editor_buffer,
+                    editor_engine,
+                    select_mode,
+                    col_index,
+                );
+            }
+            SelectMode::Disabled => {
+                let row_index =
+                    editor_buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+                set_caret(
+                    editor_buffer,
+                    editor_engine,
+                    position!(col_index: col_index, row_index: row_index),
+                );
+            }
+        }
+        soft_wrap::validate_scroll(editor_buffer, editor_engine);
+    }
+
+    fn move_by_visual_rows(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        select_mode: SelectMode,
+        visual_row_count: ChUnit,
+        direction: CaretDirection,
+    ) -> Option<()> {
+        empty_check_early_return!(editor_buffer, @None);
+        multiline_disabled_check_early_return!(editor_engine, @None);
+
+        // This is only set if select_mode is enabled.
+        let maybe_previous_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        for _ in 0..visual_row_count.value {
+            if !move_by_one_visual_row(editor_buffer, editor_engine, &direction) {
+                break;
+            }
+        }
+        soft_wrap::validate_scroll(editor_buffer, editor_engine);
+
+        // This is only set if select_mode is enabled.
+        let maybe_current_caret_display_position =
+            select_mode.get_caret_display_position(editor_buffer);
+
+        // This is only runs if select_mode is enabled. Moving between the visual rows of
+        // a line is just like moving left or right in it.
+        match (
+            maybe_previous_caret_display_position,
+            maybe_current_caret_display_position,
+        ) {
+            (Some(previous), Some(current))
+                if previous.row_index == current.row_index =>
+            {
+                select_mode.handle_selection_single_line_caret_movement(
+                    editor_buffer,
+                    maybe_previous_caret_display_position,
+                    maybe_current_caret_display_position,
+                );
+            }
+            _ => {
+                select_mode.update_selection_based_on_caret_movement_in_multiple_lines(
+                    editor_buffer,
+                    maybe_previous_caret_display_position,
+                    maybe_current_caret_display_position,
+                );
+            }
+        }
+
+        None
+    }
+
+    /// Returns `false` if the caret is on the first (or last) visual row of the buffer,
+    /// in which case it is moved to the start (or end) of the line instead.
+    fn move_by_one_visual_row(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        direction: &CaretDirection,
+    ) -> bool {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let Some((line, visual_rows, visual_row_index)) =
+            get_visual_rows_at_caret(editor_buffer, editor_engine)
+        else {
+            return false;
+        };
+        let col_offset =
+            caret.col_index - visual_rows[visual_row_index].start_display_col_index;
+        let viewport_width = editor_engine.viewport_width();
+        let row_count = editor_buffer.len();
+
+        // Find the line and the visual row in it to move to.
+        let (row_index, line, visual_rows, visual_row_index) = match direction {
+            CaretDirection::Up if visual_row_index > 0 => {
+                (caret.row_index, line, visual_rows, visual_row_index - 1)
+            }
+            CaretDirection::Up if caret.row_index > ch!(0) => {
+                let row_index = caret.row_index - 1;
+                let line = editor_buffer.get_lines()[ch!(@to_usize row_index)].clone();
+                let visual_rows = soft_wrap::wrap_line(&line, viewport_width);
+                let visual_row_index = visual_rows.len() - 1;
+                (row_index, line, visual_rows, visual_row_index)
+            }
+            CaretDirection::Down if visual_row_index + 1 < visual_rows.len() => {
+                (caret.row_index, line, visual_rows, visual_row_index + 1)
+            }
+            CaretDirection::Down if caret.row_index + 1 < row_count => {
+                let row_index = caret.row_index + 1;
+                let line = editor_buffer.get_lines()[ch!(@to_usize row_index)].clone();
+                let visual_rows = soft_wrap::wrap_line(&line, viewport_width);
+                (row_index, line, visual_rows, 0)
+            }
+            CaretDirection::Up => {
+                let position = position!(col_index: 0, row_index: caret.row_index);
+                set_caret(editor_buffer, editor_engine, position);
+                return false;
+            }
+            _ => {
+                let position = position!(
+                    col_index: line.display_width,
+                    row_index: caret.row_index
+                );
+                set_caret(editor_buffer, editor_engine, position);
+                return false;
+            }
+        };
+
+        let col_index = soft_wrap::get_col_index_in_visual_row(
+            &line,
+            &visual_rows,
+            visual_row_index,
+            col_offset,
+        );
+        set_caret(
+            editor_buffer,
+            editor_engine,
+            position!(col_index: col_index, row_index: row_index),
+        );
+        true
+    }
+
+    /// Move the caret to the given (scroll adjusted) position. There is no horizontal
+    /// scrolling w/ soft wrapping, and [soft_wrap::validate_scroll] takes care of the
+    /// vertical scrolling.
+    fn set_caret(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        position: Position,
+    ) {
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |_, caret, scroll_offset| {
+                scroll_offset.col_index = ch!(0);
+                if position.row_index < scroll_offset.row_index {
+                    scroll_offset.row_index = position.row_index;
+                }
+                *caret = position!(
+                    col_index: position.col_index,
+                    row_index: position.row_index - scroll_offset.row_index
+                );
+            },
+        );
+    }
+}
+
 mod content_get {
     use super::*;
 
@@ -1456,6 +2015,119 @@ mod content_mut {
         }
     }
 
+    /// Delete the text from the start of the word to the left of the caret (see
+    /// [caret_mut::word_left]) up to the caret. At the start of a line, this works just
+    /// like backspace.
+    pub fn backspace_word_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+
+        let end_col_index = buffer.get_caret(CaretKind::ScrollAdjusted).col_index;
+        if end_col_index == ch!(0) {
+            return backspace_at_caret(buffer, engine);
+        }
+
+        // This takes care of scrolling.
+        caret_mut::word_left(buffer, engine, SelectMode::Disabled);
+        let caret = buffer.get_caret(CaretKind::ScrollAdjusted);
+        remove_cols_in_line(
+            buffer,
+            engine,
+            caret.row_index,
+            caret.col_index,
+            end_col_index,
+        )
+    }
+
+    /// Delete the text from the caret up to the end of the word to the right of it (see
+    /// [caret_mut::word_right]). At the end of a line, this works just like delete.
+    pub fn delete_word_at_caret(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+
+        let caret = buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line = buffer.get_lines().get(ch!(@to_usize caret.row_index))?;
+        if caret.col_index == line.display_width {
+            return delete_at_caret(buffer, engine);
+        }
+
+        let end_col_index = line
+            .find_next_word_end(caret.col_index)
+            .unwrap_or(line.display_width);
+        remove_cols_in_line(
+            buffer,
+            engine,
+            caret.row_index,
+            caret.col_index,
+            end_col_index,
+        )
+    }
+
+    /// Replace the display cols in `range` in the caret's line w/ `text`, and put the
+    /// caret after it.
+    pub fn replace_range_in_caret_line(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        range: SelectionRange,
+        text: &str,
+    ) -> Option<()> {
+        empty_check_early_return!(buffer, @None);
+
+        let row_index = buffer.get_caret(CaretKind::ScrollAdjusted).row_index;
+        caret_mut::to_col_in_line(
+            buffer,
+            engine,
+            SelectMode::Disabled,
+            range.start_display_col_index,
+        );
+        remove_cols_in_line(
+            buffer,
+            engine,
+            row_index,
+            range.start_display_col_index,
+            range.end_display_col_index,
+        );
+        insert_str_at_caret(
+            EditorArgsMut {
+                editor_buffer: buffer,
+                editor_engine: engine,
+            },
+            text,
+        );
+
+        None
+    }
+
+    /// Remove the display cols `start_col_index..end_col_index` from the line at
+    /// `row_index`. The caret is not moved.
+    fn remove_cols_in_line(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        row_index: ChUnit,
+        start_col_index: ChUnit,
+        end_col_index: ChUnit,
+    ) -> Option<()> {
+        let line = buffer.get_lines().get(ch!(@to_usize row_index))?;
+        let mut remaining_text = String::new();
+        remaining_text.push_str(line.clip_to_width(ch!(0), start_col_index));
+        remaining_text.push_str(line.clip_to_width(end_col_index, line.display_width));
+
+        validate_editor_buffer_change::apply_change(
+            buffer,
+            engine,
+            |lines, _caret, _scroll_offset| {
+                let _ =
+                    replace(&mut lines[ch!(@to_usize row_index)], remaining_text.into());
+            },
+        );
+
+        None
+    }
+
     pub fn delete_selected(
         buffer: &mut EditorBuffer,
         engine: &mut EditorEngine,
@@ -1665,7 +2337,7 @@ pub mod validate_editor_buffer_change {
         editor_buffer: &mut EditorBuffer,
         editor_engine: &mut EditorEngine,
         mutator: impl FnOnce(
-            /* EditorBuffer::lines */ &mut Vec<UnicodeString>,
+            /* EditorBuffer::lines */ &mut EditorLines,
             /* EditorBuffer::caret */ &mut Position,
             /* EditorEngine::scroll_offset */ &mut ScrollOffset,
         ),
@@ -1818,6 +2490,55 @@ pub mod validate_editor_buffer_change {
     }
 }
 
+/// Functions in [search] that move the caret, which needs the viewport.
+mod search_mut {
+    use super::*;
+
+    pub fn jump_to_match(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        jump: SearchJump,
+    ) -> Option<()> {
+        let search_match = search::find_match(editor_buffer, jump)?;
+        caret_mut::to_position(
+            editor_buffer,
+            editor_engine,
+            position!(
+                col_index: search_match.range.start_display_col_index,
+                row_index: search_match.row_index
+            ),
+        )
+    }
+
+    pub fn apply_pending_jump(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        let jump = search::take_pending_jump(editor_buffer)?;
+        jump_to_match(editor_buffer, editor_engine, jump)
+    }
+
+    pub fn replace_current(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        replacement: &str,
+    ) -> Option<()> {
+        if !search::replace_current(editor_buffer, replacement) {
+            return None;
+        }
+        apply_pending_jump(editor_buffer, editor_engine)
+    }
+
+    pub fn replace_all(
+        editor_buffer: &mut EditorBuffer,
+        replacement: &str,
+    ) -> Option<()> {
+        search::replace_all(editor_buffer, replacement);
+
+        None
+    }
+}
+
 mod scroll_editor_buffer {
     use super::*;
 
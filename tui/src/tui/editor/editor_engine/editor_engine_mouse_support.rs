@@ -0,0 +1,279 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::time::{Duration, Instant};
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// Two clicks on the same position that are closer together than this are a double
+/// click.
+pub const DOUBLE_CLICK_DURATION: Duration = Duration::from_millis(500);
+
+/// Number of rows that each notch of the mouse wheel scrolls.
+pub const MOUSE_WHEEL_SCROLL_ROW_COUNT: u16 = 3;
+
+/// Mouse related state that [EditorEngine] keeps in between events.
+#[derive(Clone, Debug, Default)]
+pub struct EditorMouseState {
+    /// When the last click happened, and where (scroll adjusted). Used to detect double
+    /// clicks.
+    pub maybe_last_click: Option<(Instant, Position)>,
+    /// Scroll adjusted position of the last click, which is where a drag selects from.
+    pub maybe_drag_anchor: Option<Position>,
+}
+
+/// Mouse events are reported in screen coordinates. The functions in here map them to
+/// positions in the buffer (using [EditorEngine::current_box] and the scroll offset) and
+/// apply them:
+/// - Click to move the caret, double click to select a word.
+/// - Drag to select.
+/// - Wheel to scroll the viewport w/out moving the caret. When the caret is scrolled out
+///   of the viewport, its (scroll adjusted) position is kept in
+///   [EditorContent::maybe_caret_outside_viewport], and the next event that isn't a
+///   scroll brings it back into view (see [scroll_to_caret](mouse::scroll_to_caret)).
+pub mod mouse {
+    use super::*;
+
+    /// Whether `screen_position` is inside the editor's box.
+    pub fn is_in_viewport(
+        editor_engine: &EditorEngine,
+        screen_position: Position,
+    ) -> bool {
+        let origin = editor_engine.current_box.style_adjusted_origin_pos;
+        screen_position.col_index >= origin.col_index
+            && screen_position.row_index >= origin.row_index
+            && screen_position.col_index
+                < origin.col_index + editor_engine.viewport_width()
+            && screen_position.row_index
+                < origin.row_index + editor_engine.viewport_height()
+    }
+
+    /// Convert `screen_position` to a scroll adjusted position in the buffer. Positions
+    /// outside the editor's box (eg: when dragging past its edges) are clamped to it.
+    /// The col is snapped to the closest grapheme cluster boundary, so that clicking on
+    /// the right half of a wide character puts the caret after it.
+    pub fn find_buffer_position(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+        screen_position: Position,
+    ) -> Option<Position> {
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = editor_engine.viewport_height();
+        if editor_buffer.is_empty()
+            || viewport_width == ch!(0)
+            || viewport_height == ch!(0)
+        {
+            return None;
+        }
+
+        let origin = editor_engine.current_box.style_adjusted_origin_pos;
+        let col_offset = std::cmp::min(
+            screen_position.col_index - origin.col_index,
+            viewport_width - 1,
+        );
+        let row_offset = std::cmp::min(
+            screen_position.row_index - origin.row_index,
+            viewport_height - 1,
+        );
+
+        // The last col that the caret can be at is the end of the line, or for a visual
+        // row that is not the last one of its line, the start of its last grapheme
+        // cluster (the end of the row is the start of the next one).
+        let (row_index, col_index, max_col_index) =
+            if soft_wrap::is_enabled(editor_engine) {
+                let visible_rows =
+                    soft_wrap::get_visible_rows(editor_buffer, editor_engine);
+                let visual_row = visible_rows
+                    .get(ch!(@to_usize row_offset))
+                    .or(visible_rows.last())?;
+                let range = visual_row.range;
+                let line = editor_buffer
+                    .get_lines()
+                    .get(ch!(@to_usize visual_row.row_index))?;
+                let visual_rows = soft_wrap::wrap_line(line, viewport_width);
+                let max_col_index = soft_wrap::get_col_index_in_visual_row(
+                    line,
+                    &visual_rows,
+                    soft_wrap::find_visual_row_index(
+                        &visual_rows,
+                        range.start_display_col_index,
+                    ),
+                    range.end_display_col_index - range.start_display_col_index,
+                );
+                (
+                    visual_row.row_index,
+                    range.start_display_col_index + col_offset,
+                    max_col_index,
+                )
+            } else {
+                let scroll_offset = editor_buffer.get_scroll_offset();
+                let row_index = std::cmp::min(
+                    scroll_offset.row_index + row_offset,
+                    editor_buffer.len() - 1,
+                );
+                (
+                    row_index,
+                    scroll_offset.col_index + col_offset,
+                    editor_buffer.get_line_display_width(row_index),
+                )
+            };
+
+        let line = editor_buffer.get_lines().get(ch!(@to_usize row_index))?;
+        let col_index = match line.at_display_col_index(col_index) {
+            Some(segment) if col_index < max_col_index => {
+                // Round to the closest edge of the grapheme cluster.
+                let offset_in_segment = col_index - segment.display_col_offset;
+                if offset_in_segment * 2 < segment.unicode_width {
+                    segment.display_col_offset
+                } else {
+                    segment.display_col_offset + segment.unicode_width
+                }
+            }
+            _ => max_col_index,
+        };
+
+        Some(position!(
+            col_index: std::cmp::min(col_index, max_col_index),
+            row_index: row_index
+        ))
+    }
+
+    /// Move the caret to `screen_position`, and clear the selection. If this is the
+    /// second click on the same position in a short while, then the word at the caret is
+    /// selected.
+    pub fn click(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        screen_position: Position,
+    ) -> Option<()> {
+        let position =
+            find_buffer_position(editor_buffer, editor_engine, screen_position)?;
+
+        editor_buffer.clear_selection();
+        EditorEngineInternalApi::move_caret_to(editor_buffer, editor_engine, position);
+
+        let now = Instant::now();
+        let is_double_click = matches!(
+            editor_engine.mouse_state.maybe_last_click,
+            Some((instant, last_position))
+                if last_position == position
+                    && now.duration_since(instant) < DOUBLE_CLICK_DURATION
+        );
+
+        if is_double_click {
+            EditorEngineInternalApi::select_word(editor_buffer, editor_engine);
+            // A third click starts over.
+            editor_engine.mouse_state.maybe_last_click = None;
+        } else {
+            editor_engine.mouse_state.maybe_last_click = Some((now, position));
+        }
+        editor_engine.mouse_state.maybe_drag_anchor = Some(position);
+
+        None
+    }
+
+    /// Move the caret to `screen_position`, and select the text between it and the
+    /// position of the last click.
+    pub fn drag(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        screen_position: Position,
+    ) -> Option<()> {
+        let anchor = editor_engine.mouse_state.maybe_drag_anchor?;
+        let position =
+            find_buffer_position(editor_buffer, editor_engine, screen_position)?;
+
+        // Don't treat the release of a drag as the first click of a double click.
+        editor_engine.mouse_state.maybe_last_click = None;
+
+        EditorEngineInternalApi::move_caret_to(editor_buffer, editor_engine, position);
+
+        editor_buffer.clear_selection();
+        if anchor.row_index == position.row_index {
+            SelectMode::Enabled.handle_selection_single_line_caret_movement(
+                editor_buffer,
+                Some(anchor),
+                Some(position),
+            );
+        } else {
+            SelectMode::Enabled
+                .update_selection_based_on_caret_movement_in_multiple_lines(
+                    editor_buffer,
+                    Some(anchor),
+                    Some(position),
+                );
+        }
+
+        None
+    }
+
+    /// Scroll the viewport by `row_count` rows (up if `is_up`), w/out moving the caret.
+    /// The last line can be scrolled up to the top of the viewport.
+    pub fn scroll(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        is_up: bool,
+        row_count: u16,
+    ) -> Option<()> {
+        if editor_buffer.is_empty() {
+            return None;
+        }
+
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let max_row_index = editor_buffer.len() - 1;
+        let mut scroll_offset = editor_buffer.get_scroll_offset();
+        scroll_offset.row_index = if is_up {
+            scroll_offset.row_index - row_count
+        } else {
+            std::cmp::min(scroll_offset.row_index + row_count, max_row_index)
+        };
+
+        let content = &mut editor_buffer.editor_content;
+        content.scroll_offset = scroll_offset;
+        content.maybe_caret_outside_viewport = None;
+        content.caret_display_position = position!(
+            col_index: caret.col_index - scroll_offset.col_index,
+            row_index: caret.row_index - scroll_offset.row_index
+        );
+
+        let is_caret_visible = if soft_wrap::is_enabled(editor_engine) {
+            let visible_rows = soft_wrap::get_visible_rows(editor_buffer, editor_engine);
+            soft_wrap::find_screen_position(&visible_rows, caret).is_some()
+        } else {
+            caret.row_index >= scroll_offset.row_index
+                && caret.row_index
+                    < scroll_offset.row_index + editor_engine.viewport_height()
+        };
+        if !is_caret_visible {
+            editor_buffer.editor_content.maybe_caret_outside_viewport = Some(caret);
+        }
+
+        None
+    }
+
+    /// If the viewport has been scrolled so that the caret isn't visible, then scroll
+    /// back to it.
+    pub fn scroll_to_caret(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        let caret = editor_buffer.editor_content.maybe_caret_outside_viewport?;
+        EditorEngineInternalApi::move_caret_to(editor_buffer, editor_engine, caret)
+    }
+}
@@ -0,0 +1,360 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::{Display, Formatter, Result};
+
+use r3bl_rs_utils_core::*;
+
+use crate::{constants::*, *};
+
+/// The part of a Markdown list item line that comes before its content, eg: the
+/// `"  1. [ ] "` in `"  1. [ ] buy milk"`. Lines are recognized w/ the same rules as
+/// [parse_smart_list].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ListItemPrefix {
+    /// Spaces before the bullet.
+    pub indent: usize,
+    pub bullet_kind: BulletKind,
+    /// [None] if the item doesn't have a checkbox, otherwise whether it is checked.
+    pub maybe_checked: Option<bool>,
+}
+
+impl ListItemPrefix {
+    /// Returns the prefix of `line`, and its content (the text after the prefix), if it is
+    /// the first line of a list item.
+    pub fn parse(line: &str) -> Option<(Self, &str)> {
+        let (_, smart_list_ir) = parse_smart_list(line).ok()?;
+        let content = smart_list_ir.content_lines.first()?.content;
+
+        let checked = format!("{CHECKED}{SPACE}");
+        let unchecked = format!("{UNCHECKED}{SPACE}");
+        let (maybe_checked, content) = if let Some(it) = content.strip_prefix(&checked) {
+            (Some(true), it)
+        } else if let Some(it) = content.strip_prefix(&unchecked) {
+            (Some(false), it)
+        } else {
+            (None, content)
+        };
+
+        let prefix = Self {
+            indent: smart_list_ir.indent,
+            bullet_kind: smart_list_ir.bullet_kind,
+            maybe_checked,
+        };
+        Some((prefix, content))
+    }
+
+    /// Eg: `"- "` or `"1. "`.
+    pub fn get_bullet_str(&self) -> String {
+        match self.bullet_kind {
+            BulletKind::Ordered(number) => {
+                format!("{number}{ORDERED_LIST_PARTIAL_PREFIX}")
+            }
+            BulletKind::Unordered => UNORDERED_LIST_PREFIX.to_string(),
+        }
+    }
+
+    /// Display width of the prefix (which is all ASCII).
+    pub fn get_width(&self) -> ChUnit { ch!(self.to_string().len()) }
+
+    /// Returns the prefix of the item that follows this one: same indent and bullet, the
+    /// next number, and an unchecked checkbox (if this item has a checkbox).
+    pub fn get_next(&self) -> Self {
+        Self {
+            indent: self.indent,
+            bullet_kind: match self.bullet_kind {
+                BulletKind::Ordered(number) => BulletKind::Ordered(number + 1),
+                BulletKind::Unordered => BulletKind::Unordered,
+            },
+            maybe_checked: self.maybe_checked.map(|_| false),
+        }
+    }
+}
+
+impl Display for ListItemPrefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}{}", SPACE.repeat(self.indent), self.get_bullet_str())?;
+        match self.maybe_checked {
+            Some(true) => write!(f, "{CHECKED}{SPACE}"),
+            Some(false) => write!(f, "{UNCHECKED}{SPACE}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Editing Markdown lists the way GitHub's web editor does. This is enabled w/
+/// [EditorEngineConfig::smart_list], and only applies to Markdown buffers.
+/// - Enter on a list item starts a new item w/ the same indent and bullet (the next
+///   number for ordered lists, and an unchecked checkbox for task lists). Enter on an
+///   empty item ends the list.
+/// - Tab / Shift+Tab indent / outdent the list item at the caret.
+/// - Ordered lists are renumbered after edits that add, remove, or move items.
+pub mod smart_list {
+    use super::*;
+
+    pub fn is_enabled(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+    ) -> bool {
+        editor_engine.config_options.smart_list == SmartListMode::Enable
+            && editor_engine.config_options.multiline_mode == LineMode::MultiLine
+            && editor_buffer.is_file_extension_default()
+    }
+
+    /// Returns the list item prefix of the caret's line, if it is a list item.
+    pub fn get_list_item_prefix_at_caret(
+        editor_buffer: &EditorBuffer,
+    ) -> Option<ListItemPrefix> {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let line = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret.row_index))?;
+        ListItemPrefix::parse(&line.string).map(|(prefix, _)| prefix)
+    }
+
+    /// Continue (or end) the list if the caret is in the content of a list item. Returns
+    /// `false` if it isn't, and the new line should be inserted as usual.
+    pub fn insert_new_line(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> bool {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let Some(line) = editor_buffer
+            .get_lines()
+            .get(ch!(@to_usize caret.row_index))
+        else {
+            return false;
+        };
+        let line_width = line.display_width;
+        let Some((prefix, content)) = ListItemPrefix::parse(&line.string) else {
+            return false;
+        };
+        if caret.col_index < prefix.get_width() {
+            return false;
+        }
+
+        if content.trim().is_empty() {
+            // End the list, by removing the bullet of this empty item.
+            EditorEngineInternalApi::replace_range_in_caret_line(
+                editor_buffer,
+                editor_engine,
+                SelectionRange::new(ch!(0), line_width),
+                "",
+            );
+        } else {
+            EditorEngineInternalApi::insert_new_line_at_caret(EditorArgsMut {
+                editor_buffer,
+                editor_engine,
+            });
+            EditorEngineInternalApi::insert_str_at_caret(
+                EditorArgsMut {
+                    editor_buffer,
+                    editor_engine,
+                },
+                &prefix.get_next().to_string(),
+            );
+        }
+
+        true
+    }
+
+    /// Indent (or outdent) the list item at the caret by [LIST_PREFIX_BASE_WIDTH]. An
+    /// ordered item starts over at 1, and then gets renumbered to fit into the list that
+    /// it moves into. Returns `false` if the caret isn't on a list item.
+    pub fn indent(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        is_outdent: bool,
+    ) -> bool {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let row_index = ch!(@to_usize caret.row_index);
+        let Some(line) = editor_buffer.get_lines().get(row_index) else {
+            return false;
+        };
+        let Some((prefix, content)) = ListItemPrefix::parse(&line.string) else {
+            return false;
+        };
+        if is_outdent && prefix.indent < LIST_PREFIX_BASE_WIDTH {
+            return true;
+        }
+
+        let new_prefix = ListItemPrefix {
+            indent: if is_outdent {
+                prefix.indent - LIST_PREFIX_BASE_WIDTH
+            } else {
+                prefix.indent + LIST_PREFIX_BASE_WIDTH
+            },
+            bullet_kind: match prefix.bullet_kind {
+                BulletKind::Ordered(_) => BulletKind::Ordered(1),
+                BulletKind::Unordered => BulletKind::Unordered,
+            },
+            ..prefix
+        };
+        let new_line = format!("{new_prefix}{content}");
+
+        // Keep the caret on the same char (or at the start of the content, if it was in
+        // the prefix).
+        let new_caret_col_index = std::cmp::max(caret.col_index, prefix.get_width())
+            + new_prefix.get_width()
+            - prefix.get_width();
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                lines[row_index] = new_line.into();
+            },
+        );
+        EditorEngineInternalApi::move_caret_to(
+            editor_buffer,
+            editor_engine,
+            position!(col_index: new_caret_col_index, row_index: caret.row_index),
+        );
+
+        true
+    }
+
+    /// Whether the line belongs to a list: it is a list item, or the (indented)
+    /// continuation of one.
+    fn is_in_list(line: &str) -> bool {
+        ListItemPrefix::parse(line).is_some()
+            || (line.starts_with(SPACE_CHAR) && !line.trim().is_empty())
+    }
+
+    /// Renumber the ordered lists (at every indent) in the list that the caret is in (or
+    /// that ends on the line above it). Each run of ordered items at the same indent is
+    /// numbered consecutively, starting w/ the number of its first item.
+    pub fn renumber_list_at_caret(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+    ) -> Option<()> {
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let lines = editor_buffer.get_lines();
+
+        let caret_row_index = ch!(@to_usize caret.row_index);
+        let row_index = if lines
+            .get(caret_row_index)
+            .is_some_and(|line| is_in_list(&line.string))
+        {
+            caret_row_index
+        } else {
+            caret_row_index.checked_sub(1)?
+        };
+        if !is_in_list(&lines.get(row_index)?.string) {
+            return None;
+        }
+
+        let mut start_row_index = row_index;
+        while start_row_index > 0 && is_in_list(&lines[start_row_index - 1].string) {
+            start_row_index -= 1;
+        }
+        let mut end_row_index = row_index;
+        while end_row_index + 1 < lines.len()
+            && is_in_list(&lines[end_row_index + 1].string)
+        {
+            end_row_index += 1;
+        }
+
+        // The indent of each nesting level, and the number of the next ordered item at it
+        // (or [None] if the last item at it is unordered).
+        let mut levels: Vec<(usize, Option<usize>)> = vec![];
+        let mut changed_lines: Vec<(usize, String)> = vec![];
+        let mut caret_col_delta: isize = 0;
+
+        for (row_index, line) in lines
+            .iter()
+            .enumerate()
+            .take(end_row_index + 1)
+            .skip(start_row_index)
+        {
+            let Some((prefix, _)) = ListItemPrefix::parse(&line.string) else {
+                continue;
+            };
+            while levels
+                .last()
+                .is_some_and(|(indent, _)| *indent > prefix.indent)
+            {
+                levels.pop();
+            }
+            if !levels
+                .last()
+                .is_some_and(|(indent, _)| *indent == prefix.indent)
+            {
+                levels.push((prefix.indent, None));
+            }
+            let level = levels.last_mut()?;
+
+            let BulletKind::Ordered(number) = prefix.bullet_kind else {
+                level.1 = None;
+                continue;
+            };
+            let expected_number = level.1.unwrap_or(number);
+            level.1 = Some(expected_number + 1);
+            if expected_number == number {
+                continue;
+            }
+
+            let new_prefix = ListItemPrefix {
+                bullet_kind: BulletKind::Ordered(expected_number),
+                ..prefix
+            };
+            let bullet_end_byte_index = prefix.indent + prefix.get_bullet_str().len();
+            changed_lines.push((
+                row_index,
+                format!(
+                    "{}{}{}",
+                    SPACE.repeat(prefix.indent),
+                    new_prefix.get_bullet_str(),
+                    &line.string[bullet_end_byte_index..]
+                ),
+            ));
+            if row_index == caret_row_index && caret.col_index > ch!(prefix.indent) {
+                caret_col_delta = new_prefix.get_bullet_str().len() as isize
+                    - prefix.get_bullet_str().len() as isize;
+            }
+        }
+
+        if changed_lines.is_empty() {
+            return None;
+        }
+
+        validate_editor_buffer_change::apply_change(
+            editor_buffer,
+            editor_engine,
+            |lines, _, _| {
+                for (row_index, new_line) in changed_lines {
+                    lines[row_index] = new_line.into();
+                }
+            },
+        );
+
+        if caret_col_delta != 0 {
+            let col_index = if caret_col_delta > 0 {
+                caret.col_index + ch!(caret_col_delta as usize)
+            } else {
+                caret.col_index - ch!(-caret_col_delta as usize)
+            };
+            EditorEngineInternalApi::move_caret_to(
+                editor_buffer,
+                editor_engine,
+                position!(col_index: col_index, row_index: caret.row_index),
+            );
+        }
+
+        None
+    }
+}
@@ -0,0 +1,346 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+/// A row on the screen when [SoftWrapMode::Enable] is used. Each line in the buffer is
+/// broken into one or more of these.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VisualRow {
+    /// Index of the line in the buffer.
+    pub row_index: ChUnit,
+    /// The display cols of the line that are on this row.
+    pub range: SelectionRange,
+    /// Whether this is the last visual row of the line.
+    pub is_last_in_line: bool,
+}
+
+/// Soft wrapping breaks the lines that are wider than the viewport into multiple visual
+/// rows, at word boundaries when possible. The caret and the selection still use
+/// positions in the (unwrapped) lines, and the scroll offset is the index of the first
+/// line that is shown (its col is always `0`). The functions in here are used to map
+/// between the two.
+pub mod soft_wrap {
+    use super::*;
+
+    pub fn is_enabled(editor_engine: &EditorEngine) -> bool {
+        matches!(editor_engine.config_options.soft_wrap, SoftWrapMode::Enable)
+            && matches!(
+                editor_engine.config_options.multiline_mode,
+                LineMode::MultiLine
+            )
+            && editor_engine.viewport_width() > ch!(0)
+    }
+
+    /// Break the `line` into ranges of display cols that fit in `max_display_col_count`.
+    /// - Lines are broken after whitespace when possible, otherwise at the grapheme
+    ///   cluster that doesn't fit (eg: for a word that is wider than the viewport).
+    /// - Wide grapheme clusters are never split across rows.
+    /// - If the last row is full, an empty row is added after it, so that there is
+    ///   somewhere to put the caret when it is at the end of the line.
+    pub fn wrap_line(
+        line: &UnicodeString,
+        max_display_col_count: ChUnit,
+    ) -> Vec<SelectionRange> {
+        if max_display_col_count == ch!(0) {
+            return vec![SelectionRange::new(ch!(0), line.display_width)];
+        }
+
+        let mut it = vec![];
+        let mut row_start = ch!(0);
+        let mut row_end = ch!(0);
+        // Display col right after the last whitespace in the current row.
+        let mut maybe_break_col: Option<ChUnit> = None;
+
+        for segment in line.iter() {
+            let segment_start = segment.display_col_offset;
+            let segment_end = segment_start + segment.unicode_width;
+
+            while segment_end - row_start > max_display_col_count && row_end > row_start {
+                let break_col = match maybe_break_col {
+                    Some(col) if col > row_start => col,
+                    _ => segment_start,
+                };
+                it.push(SelectionRange::new(row_start, break_col));
+                row_start = break_col;
+                maybe_break_col = None;
+            }
+
+            row_end = segment_end;
+            if segment.string.chars().all(char::is_whitespace) {
+                maybe_break_col = Some(segment_end);
+            }
+        }
+
+        it.push(SelectionRange::new(row_start, line.display_width));
+        if line.display_width - row_start >= max_display_col_count {
+            it.push(SelectionRange::new(line.display_width, line.display_width));
+        }
+
+        it
+    }
+
+    /// Returns the index of the visual row (in `visual_rows`, which is returned by
+    /// [wrap_line]) that `col_index` is on. A col that is at the boundary of two rows is
+    /// on the second one.
+    pub fn find_visual_row_index(
+        visual_rows: &[SelectionRange],
+        col_index: ChUnit,
+    ) -> usize {
+        visual_rows
+            .iter()
+            .rposition(|it| it.start_display_col_index <= col_index)
+            .unwrap_or(0)
+    }
+
+    /// Returns the display col in `line` that is `col_offset` cols from the start of the
+    /// visual row at `visual_row_index`, snapped to the start of a grapheme cluster. If
+    /// the visual row is shorter than that, then this is the end of the row, which for
+    /// all but the last row is the start of its last grapheme cluster (the end of the
+    /// row is the start of the next one).
+    pub fn get_col_index_in_visual_row(
+        line: &UnicodeString,
+        visual_rows: &[SelectionRange],
+        visual_row_index: usize,
+        col_offset: ChUnit,
+    ) -> ChUnit {
+        let Some(range) = visual_rows.get(visual_row_index) else {
+            return line.display_width;
+        };
+        let is_last = visual_row_index + 1 == visual_rows.len();
+        let target_col_index = range.start_display_col_index + col_offset;
+
+        if target_col_index >= range.end_display_col_index && is_last {
+            return range.end_display_col_index;
+        }
+
+        line.iter()
+            .map(|segment| segment.display_col_offset)
+            .rfind(|col_index| {
+                *col_index >= range.start_display_col_index
+                    && *col_index < range.end_display_col_index
+                    && *col_index <= target_col_index
+            })
+            .unwrap_or(range.start_display_col_index)
+    }
+
+    /// Returns the visual rows that fit in the viewport, starting at the scroll offset.
+    /// The index of each one is its row on the screen.
+    pub fn get_visible_rows(
+        editor_buffer: &EditorBuffer,
+        editor_engine: &EditorEngine,
+    ) -> Vec<VisualRow> {
+        let viewport_width = editor_engine.viewport_width();
+        let viewport_height = ch!(@to_usize editor_engine.viewport_height());
+        let scroll_offset = editor_buffer.get_scroll_offset();
+
+        let mut it = vec![];
+        for (row_index, line) in editor_buffer
+            .get_lines()
+            .iter()
+            .enumerate()
+            .skip(ch!(@to_usize scroll_offset.row_index))
+        {
+            let visual_rows = wrap_line(line, viewport_width);
+            let last_index = visual_rows.len() - 1;
+            for (index, range) in visual_rows.into_iter().enumerate() {
+                if it.len() >= viewport_height {
+                    return it;
+                }
+                it.push(VisualRow {
+                    row_index: ch!(row_index),
+                    range,
+                    is_last_in_line: index == last_index,
+                });
+            }
+        }
+        it
+    }
+
+    /// Convert the (scroll adjusted) `position` to a position on the screen, using the
+    /// `visible_rows` returned by [get_visible_rows]. Returns [None] if it is not
+    /// visible.
+    pub fn find_screen_position(
+        visible_rows: &[VisualRow],
+        position: Position,
+    ) -> Option<Position> {
+        visible_rows
+            .iter()
+            .position(|it| {
+                it.row_index == position.row_index
+                    && it.range.start_display_col_index <= position.col_index
+                    && (position.col_index < it.range.end_display_col_index
+                        || (position.col_index == it.range.end_display_col_index
+                            && it.is_last_in_line))
+            })
+            .map(|screen_row_index| {
+                let range = visible_rows[screen_row_index].range;
+                position!(
+                    col_index: position.col_index - range.start_display_col_index,
+                    row_index: ch!(screen_row_index)
+                )
+            })
+    }
+
+    /// Make sure that the caret is visible: the horizontal scroll offset is reset, and
+    /// the vertical one is changed so that the visual row w/ the caret is in the
+    /// viewport. The raw caret position is then updated to match. This does nothing when
+    /// the caret has been scrolled out of view w/ the mouse wheel (see [mouse]).
+    pub fn validate_scroll(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &EditorEngine,
+    ) {
+        if editor_buffer
+            .editor_content
+            .maybe_caret_outside_viewport
+            .is_some()
+        {
+            return;
+        }
+
+        let caret = editor_buffer.get_caret(CaretKind::ScrollAdjusted);
+        let mut scroll_offset = editor_buffer.get_scroll_offset();
+        scroll_offset.col_index = ch!(0);
+
+        if caret.row_index < scroll_offset.row_index {
+            scroll_offset.row_index = caret.row_index;
+        } else {
+            let viewport_width = editor_engine.viewport_width();
+            let viewport_height = ch!(@to_usize editor_engine.viewport_height());
+            let lines = editor_buffer.get_lines();
+
+            // Number of visual rows taken by each line from the scroll offset to the
+            // caret (inclusive). For the caret's line, only the rows up to the caret.
+            let mut visual_row_counts: Vec<usize> = (ch!(@to_usize scroll_offset
+                .row_index)
+                ..ch!(@to_usize caret.row_index))
+                .map(|row_index| {
+                    lines
+                        .get(row_index)
+                        .map(|line| wrap_line(line, viewport_width).len())
+                        .unwrap_or(1)
+                })
+                .collect();
+            if let Some(line) = lines.get(ch!(@to_usize caret.row_index)) {
+                let visual_rows = wrap_line(line, viewport_width);
+                visual_row_counts
+                    .push(find_visual_row_index(&visual_rows, caret.col_index) + 1);
+            }
+
+            let mut total: usize = visual_row_counts.iter().sum();
+            for count in visual_row_counts
+                .iter()
+                .take(visual_row_counts.len().saturating_sub(1))
+            {
+                if total <= viewport_height {
+                    break;
+                }
+                total -= count;
+                scroll_offset.row_index += 1;
+            }
+        }
+
+        let (_, caret_raw, scroll_offset_mut, _) = editor_buffer.get_mut();
+        *scroll_offset_mut = scroll_offset;
+        *caret_raw = position!(
+            col_index: caret.col_index,
+            row_index: caret.row_index - scroll_offset.row_index
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(it: Vec<SelectionRange>) -> Vec<(usize, usize)> {
+        it.iter()
+            .map(|it| {
+                (
+                    ch!(@to_usize it.start_display_col_index),
+                    ch!(@to_usize it.end_display_col_index),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wrap_line_at_word_boundaries() {
+        let line = UnicodeString::from("hello big world");
+        assert_eq2!(
+            ranges(soft_wrap::wrap_line(&line, ch!(10))),
+            vec![(0, 10), (10, 15)]
+        );
+        assert_eq2!(
+            ranges(soft_wrap::wrap_line(&line, ch!(8))),
+            vec![(0, 6), (6, 10), (10, 15)]
+        );
+
+        // A word that is wider than the viewport is broken anywhere.
+        let line = UnicodeString::from("abcdefgh ij");
+        assert_eq2!(
+            ranges(soft_wrap::wrap_line(&line, ch!(3))),
+            vec![(0, 3), (3, 6), (6, 9), (9, 11)]
+        );
+
+        // The last row is full, so there is an empty row for the caret.
+        let line = UnicodeString::from("abc");
+        assert_eq2!(
+            ranges(soft_wrap::wrap_line(&line, ch!(3))),
+            vec![(0, 3), (3, 3)]
+        );
+        assert_eq2!(
+            ranges(soft_wrap::wrap_line(&UnicodeString::from(""), ch!(3))),
+            vec![(0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_wrap_line_does_not_split_wide_chars() {
+        // Each of these is 2 display cols wide.
+        let line = UnicodeString::from("😃😃😃");
+        assert_eq2!(
+            ranges(soft_wrap::wrap_line(&line, ch!(5))),
+            vec![(0, 4), (4, 6)]
+        );
+    }
+
+    #[test]
+    fn test_col_index_in_visual_row() {
+        let line = UnicodeString::from("hello big world");
+        let visual_rows = soft_wrap::wrap_line(&line, ch!(8));
+        assert_eq2!(soft_wrap::find_visual_row_index(&visual_rows, ch!(6)), 1);
+        assert_eq2!(soft_wrap::find_visual_row_index(&visual_rows, ch!(15)), 2);
+
+        // Past the end of a row that is not the last one.
+        assert_eq2!(
+            soft_wrap::get_col_index_in_visual_row(&line, &visual_rows, 1, ch!(7)),
+            ch!(9)
+        );
+        // Past the end of the last row.
+        assert_eq2!(
+            soft_wrap::get_col_index_in_visual_row(&line, &visual_rows, 2, ch!(7)),
+            ch!(15)
+        );
+        assert_eq2!(
+            soft_wrap::get_col_index_in_visual_row(&line, &visual_rows, 2, ch!(2)),
+            ch!(12)
+        );
+    }
+}
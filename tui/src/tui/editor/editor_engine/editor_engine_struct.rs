@@ -42,8 +42,31 @@ pub struct EditorEngine {
     pub config_options: EditorEngineConfig,
     /// Syntax highlighting support. This is a very heavy object to create, re-use it.
     pub syntax_set: SyntaxSet,
-    /// Syntax highlighting support. This is a very heavy object to create, re-use it.
+    /// Syntax highlighting support. This is a very heavy object to create, re-use it. It
+    /// comes from the current [MdTheme], see [EditorEngine::update_md_theme].
     pub theme: Theme,
+    /// The [global_md_theme::get_generation] that `theme` comes from.
+    #[serde(skip)]
+    pub md_theme_generation: usize,
+    /// Used to detect double clicks and drags. See [mouse] for more details.
+    #[serde(skip)]
+    pub mouse_state: EditorMouseState,
+    /// Used to only parse & highlight the Markdown blocks that have changed. See
+    /// [MdSynHiCache] for more details.
+    #[serde(skip)]
+    pub md_syn_hi_cache: MdSynHiCache,
+    /// Turns key presses into [EditorEvent]s. Replace it (or change its bindings) to give
+    /// this editor different bindings than the other ones, see [Keymap].
+    #[serde(skip, default = "EditorEvent::new_keymap")]
+    pub keymap: Keymap,
+    /// The [EditorEvent] that the last [InputEvent] passed to
+    /// [EditorEngineApi::apply_event] was turned into (w/ `keymap`), if any.
+    #[serde(skip)]
+    pub maybe_last_editor_event: Option<EditorEvent>,
+    /// The key bindings that are shown when the buffer is empty, see
+    /// [EditorEmptyStateHint].
+    #[serde(skip)]
+    pub empty_state_hint: EditorEmptyStateHint,
 }
 
 impl Default for EditorEngine {
@@ -58,8 +81,26 @@ impl EditorEngine {
             current_box: Default::default(),
             config_options,
             syntax_set: SyntaxSet::load_defaults_newlines(),
-            theme: try_load_r3bl_theme().unwrap_or_else(|_| load_default_theme()),
+            md_theme_generation: global_md_theme::get_generation(),
+            theme: global_md_theme::get().load_syntect_theme(),
+            mouse_state: Default::default(),
+            md_syn_hi_cache: Default::default(),
+            keymap: EditorEvent::new_keymap(),
+            maybe_last_editor_event: None,
+            empty_state_hint: Default::default(),
+        }
+    }
+
+    /// Load the syntect [Theme] from the current [MdTheme], if it has changed since it was
+    /// last loaded. Returns true if it has changed.
+    pub fn update_md_theme(&mut self) -> bool {
+        let md_theme_generation = global_md_theme::get_generation();
+        if self.md_theme_generation == md_theme_generation {
+            return false;
         }
+        self.theme = global_md_theme::get().load_syntect_theme();
+        self.md_theme_generation = md_theme_generation;
+        true
     }
 
     pub fn viewport_width(&self) -> ChUnit {
@@ -69,6 +110,60 @@ impl EditorEngine {
     pub fn viewport_height(&self) -> ChUnit {
         self.current_box.style_adjusted_bounds_size.row_count
     }
+
+    /// Whether the content of the `editor_buffer` can't be changed, either because this
+    /// editor is in [EditMode::ReadOnly], or because the `editor_buffer` is [read
+    /// only](EditorBuffer::is_read_only).
+    pub fn is_read_only(&self, editor_buffer: &EditorBuffer) -> bool {
+        self.config_options.edit_mode == EditMode::ReadOnly
+            || editor_buffer.is_read_only()
+    }
+}
+
+/// The actions (eg: to save & exit) whose key bindings are shown below the prompt to start
+/// typing, when the buffer is empty. The bindings come from `keymap` (eg: the app's
+/// [Keymap]) when the hint is rendered, so they change when the actions are rebound.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EditorEmptyStateHint {
+    pub keymap: Keymap,
+    /// Pairs of action id & what it does, eg: `("edi.save", "Save your work.")`.
+    pub actions: Vec<(String, String)>,
+}
+
+mod editor_empty_state_hint_impl {
+    use super::*;
+
+    impl EditorEmptyStateHint {
+        pub fn new(keymap: Keymap, actions: &[(&str, &str)]) -> Self {
+            Self {
+                keymap,
+                actions: actions
+                    .iter()
+                    .map(|(id, text)| (id.to_string(), text.to_string()))
+                    .collect(),
+            }
+        }
+
+        /// Returns the hint, eg: `Ctrl+S: Save your work. Ctrl+Q: Exit the app.`, w/ the
+        /// first binding of each action. The actions that aren't bound are left out, and
+        /// [None] is returned if none of them are.
+        pub fn get_text(&mut self) -> Option<String> {
+            let Self { keymap, actions } = self;
+            let parts: Vec<String> = actions
+                .iter()
+                .filter_map(|(action_id, text)| {
+                    let key_sequence =
+                        keymap.get_bindings(action_id).into_iter().next()?;
+                    Some(format!("{key_sequence}: {text}"))
+                })
+                .collect();
+            if parts.is_empty() {
+                None
+            } else {
+                Some(parts.join(" "))
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -76,6 +171,16 @@ pub struct EditorEngineConfig {
     pub multiline_mode: LineMode,
     pub syntax_highlight: SyntaxHighlightMode,
     pub edit_mode: EditMode,
+    /// Only used w/ [LineMode::MultiLine]. See [soft_wrap] for more details.
+    pub soft_wrap: SoftWrapMode,
+    /// Only used w/ Markdown buffers. See [smart_list] for more details.
+    pub smart_list: SmartListMode,
+    /// See [indent] for more details.
+    pub auto_indent: AutoIndentMode,
+    /// Number of spaces that Tab inserts, and that lines are indented / outdented by.
+    pub tab_width: usize,
+    /// See [auto_pair] for more details.
+    pub auto_pair: AutoPairMode,
 }
 
 mod editor_engine_config_options_impl {
@@ -87,6 +192,11 @@ mod editor_engine_config_options_impl {
                 multiline_mode: LineMode::MultiLine,
                 syntax_highlight: SyntaxHighlightMode::Enable,
                 edit_mode: EditMode::ReadWrite,
+                soft_wrap: SoftWrapMode::Disable,
+                smart_list: SmartListMode::Disable,
+                auto_indent: AutoIndentMode::Enable,
+                tab_width: DEFAULT_TAB_WIDTH,
+                auto_pair: AutoPairMode::Disable,
             }
         }
     }
@@ -109,3 +219,75 @@ pub enum SyntaxHighlightMode {
     Disable,
     Enable,
 }
+
+/// When enabled, lines that are wider than the viewport are broken into multiple rows
+/// on the screen, instead of scrolling horizontally.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SoftWrapMode {
+    Disable,
+    Enable,
+}
+
+/// When enabled, Enter, Tab and Shift+Tab continue, indent and outdent Markdown list
+/// items, and ordered lists are kept numbered.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SmartListMode {
+    Disable,
+    Enable,
+}
+
+/// When enabled, a new line gets the leading whitespace of the line that it is split
+/// from.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoIndentMode {
+    Disable,
+    Enable,
+}
+
+/// When enabled, typing an opening bracket or quote also inserts the closing one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AutoPairMode {
+    Disable,
+    Enable,
+}
+
+/// Default for [EditorEngineConfig::tab_width].
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_state_hint_uses_current_bindings() {
+        let mut hint = EditorEmptyStateHint::new(
+            EditorEvent::new_keymap(),
+            &[
+                (editor_actions::UNDO, "Undo."),
+                ("editor.not_an_action", "Nothing."),
+                (editor_actions::REDO, "Redo."),
+            ],
+        );
+        assert_eq!(
+            hint.get_text(),
+            Some("Ctrl+Z: Undo. Ctrl+Y: Redo.".to_string())
+        );
+
+        // Rebinding an action changes the hint.
+        hint.keymap.set_bindings(
+            editor_actions::UNDO,
+            vec![KeySequence::try_parse("Alt+U").unwrap()],
+        );
+        assert_eq!(
+            hint.get_text(),
+            Some("Alt+U: Undo. Ctrl+Y: Redo.".to_string())
+        );
+
+        // Unbound actions are left out.
+        hint.keymap.set_bindings(editor_actions::UNDO, vec![]);
+        hint.keymap.set_bindings(editor_actions::REDO, vec![]);
+        assert_eq!(hint.get_text(), None);
+
+        assert_eq!(EditorEmptyStateHint::default().get_text(), None);
+    }
+}
@@ -17,10 +17,20 @@
 
 // Attach.
 pub mod editor_engine_api;
+pub mod editor_engine_auto_pair_support;
+pub mod editor_engine_indent_support;
 pub mod editor_engine_internal_api;
+pub mod editor_engine_mouse_support;
+pub mod editor_engine_smart_list_support;
+pub mod editor_engine_soft_wrap_support;
 pub mod editor_engine_struct;
 
 // Re-export.
 pub use editor_engine_api::*;
+pub use editor_engine_auto_pair_support::*;
+pub use editor_engine_indent_support::*;
 pub use editor_engine_internal_api::*;
+pub use editor_engine_mouse_support::*;
+pub use editor_engine_smart_list_support::*;
+pub use editor_engine_soft_wrap_support::*;
 pub use editor_engine_struct::*;
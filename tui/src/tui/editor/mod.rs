@@ -17,11 +17,13 @@
 
 // Attach.
 pub mod editor_buffer;
+pub mod editor_completion;
 pub mod editor_component;
 pub mod editor_engine;
 
 // Re-export.
 pub use editor_buffer::*;
+pub use editor_completion::*;
 pub use editor_component::*;
 pub use editor_engine::*;
 
@@ -1635,7 +1635,8 @@ pub mod mock_real_objects_for_editor {
     use r3bl_rs_utils_core::*;
     use tokio::sync::mpsc;
 
-    use crate::*;
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                *};
 
     pub fn make_global_data<S, AS>(window_size: Option<Size>) -> GlobalData<S, AS>
     where
@@ -1649,6 +1650,8 @@ pub mod mock_real_objects_for_editor {
             maybe_saved_offscreen_buffer: Default::default(),
             main_thread_channel_sender: sender,
             state: Default::default(),
+            output_device: Default::default(),
+            maybe_recorded_frames: None,
         }
     }
 
@@ -1677,6 +1680,34 @@ pub mod mock_real_objects_for_editor {
             ..Default::default()
         }
     }
+
+    pub fn make_editor_buffer_with_lines(lines: Vec<&str>) -> EditorBuffer {
+        let mut editor_buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        editor_buffer.set_lines(lines.into_iter().map(String::from).collect());
+        editor_buffer
+    }
+
+    /// Apply the `input_events` to the editor one after the other (w/ a [TestClipboard]),
+    /// and return the result of the last one.
+    pub fn apply_input_events(
+        editor_buffer: &mut EditorBuffer,
+        editor_engine: &mut EditorEngine,
+        input_events: Vec<InputEvent>,
+    ) -> EditorEngineApplyEventResult {
+        let mut test_clipboard = TestClipboard::default();
+        let mut result = EditorEngineApplyEventResult::NotApplied;
+        for input_event in input_events {
+            result = EditorEngineApi::apply_event(
+                editor_buffer,
+                editor_engine,
+                input_event,
+                &mut test_clipboard,
+            )
+            .unwrap();
+        }
+        result
+    }
 }
 
 #[cfg(test)]
@@ -2165,3 +2196,1839 @@ mod clipboard_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod search_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                *};
+
+    fn make_lines() -> Vec<String> {
+        (0..30)
+            .map(|row_index| match row_index {
+                2 => "needle".to_string(),
+                25 => format!("{}needle", " ".repeat(20)),
+                _ => format!("line {row_index}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_jump_to_match_scrolls_viewport() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut test_clipboard = TestClipboard::default();
+        buffer.set_lines(make_lines());
+
+        // Move caret past the first match, so the search finds the second one.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::Search(SearchAction::SetQuery(
+                    "NEEDLE".to_string(),
+                    SearchOptions::default(),
+                )),
+            ],
+            &mut test_clipboard,
+        );
+        assert_eq2!(buffer.search.get_matches().len(), 2);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 20, row_index: 25)
+        );
+        // The match is in the middle of the 10 row viewport, and at its right edge.
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 11, row_index: 20)
+        );
+
+        // F3 wraps around to the first match.
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::FunctionKey(FunctionKey::F3),
+            }),
+            &mut test_clipboard,
+        )
+        .unwrap();
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 0, row_index: 2)
+        );
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
+
+        // Shift + F3 wraps around to the last match.
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::FunctionKey(FunctionKey::F3),
+                mask: ModifierKeysMask::new().with_shift(),
+            }),
+            &mut test_clipboard,
+        )
+        .unwrap();
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 20, row_index: 25)
+        );
+    }
+
+    #[test]
+    fn test_replace_goes_through_undo_history() {
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut test_clipboard = TestClipboard::default();
+        buffer.set_lines(make_lines());
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![
+                EditorEvent::Search(SearchAction::SetQuery(
+                    "needle".to_string(),
+                    SearchOptions::default(),
+                )),
+                EditorEvent::Search(SearchAction::ReplaceCurrent("pin".to_string())),
+            ],
+            &mut test_clipboard,
+        );
+        assert_eq2!(buffer.get_lines()[2], UnicodeString::from("pin"));
+        // The caret moved on to the next match.
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 20, row_index: 25)
+        );
+
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Search(SearchAction::ReplaceAll(
+                "pin".to_string(),
+            ))],
+            &mut test_clipboard,
+        );
+        assert_eq2!(
+            buffer.get_lines()[25],
+            UnicodeString::from(format!("{}pin", " ".repeat(20)))
+        );
+        assert!(buffer.search.get_matches().is_empty());
+
+        // Undo each replacement.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Undo],
+            &mut test_clipboard,
+        );
+        assert_eq2!(buffer.search.get_matches().len(), 1);
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Undo],
+            &mut test_clipboard,
+        );
+        assert_eq2!(
+            buffer.get_as_string_with_newlines(),
+            make_lines().join("\n")
+        );
+
+        // Clearing the search removes the matches.
+        EditorEvent::apply_editor_events::<(), ()>(
+            &mut engine,
+            &mut buffer,
+            vec![EditorEvent::Search(SearchAction::Clear)],
+            &mut test_clipboard,
+        );
+        assert!(!search::is_active(&buffer));
+    }
+}
+
+#[cfg(test)]
+mod multi_caret_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::{apply_input_events,
+                                               make_editor_buffer_with_lines},
+                *};
+    use crate::*;
+
+    fn ctrl_alt(key: SpecialKey) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::SpecialKey(key),
+            mask: ModifierKeysMask {
+                ctrl_key_state: KeyState::Pressed,
+                shift_key_state: KeyState::NotPressed,
+                alt_key_state: KeyState::Pressed,
+            },
+        })
+    }
+
+    fn plain(key: Key) -> InputEvent { InputEvent::Keyboard(KeyPress::Plain { key }) }
+
+    fn positions(buffer: &EditorBuffer) -> Vec<Position> {
+        multi_caret::get_positions(buffer)
+    }
+
+    #[test]
+    fn test_column_edit_and_undo() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["abc", "abc", "abc"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![
+                ctrl_alt(SpecialKey::Down),
+                ctrl_alt(SpecialKey::Down),
+                plain(Key::Character('x')),
+                plain(Key::Character('y')),
+            ],
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "xyabc\nxyabc\nxyabc");
+        assert_eq2!(
+            positions(&buffer),
+            vec![
+                position!(col_index: 2, row_index: 0),
+                position!(col_index: 2, row_index: 1),
+                position!(col_index: 2, row_index: 2),
+            ]
+        );
+
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![plain(Key::SpecialKey(SpecialKey::Backspace))],
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "xabc\nxabc\nxabc");
+
+        // Undo restores the text and all the carets.
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('z'),
+                mask: ModifierKeysMask {
+                    ctrl_key_state: KeyState::Pressed,
+                    shift_key_state: KeyState::NotPressed,
+                    alt_key_state: KeyState::NotPressed,
+                },
+            })],
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "xyabc\nxyabc\nxyabc");
+        assert_eq2!(positions(&buffer).len(), 3);
+
+        // Esc removes the secondary carets.
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![plain(Key::SpecialKey(SpecialKey::Esc))],
+        );
+        assert_eq2!(
+            positions(&buffer),
+            vec![position!(col_index: 2, row_index: 0)]
+        );
+    }
+
+    #[test]
+    fn test_new_line_with_carets_on_same_line() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["ab cd"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let (_, caret, _, _) = buffer.get_mut();
+        *caret = position!(col_index: 2, row_index: 0);
+        buffer.editor_content.secondary_carets = vec![EditorCaret {
+            position: position!(col_index: 5, row_index: 0),
+            ..Default::default()
+        }];
+
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![plain(Key::SpecialKey(SpecialKey::Enter))],
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "ab\n cd\n");
+        assert_eq2!(
+            positions(&buffer),
+            vec![
+                position!(col_index: 0, row_index: 1),
+                position!(col_index: 0, row_index: 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_carets_that_collide_are_merged() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["ab", "ab"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![
+                ctrl_alt(SpecialKey::Down),
+                plain(Key::SpecialKey(SpecialKey::Up)),
+            ],
+        );
+        assert_eq2!(
+            positions(&buffer),
+            vec![position!(col_index: 0, row_index: 0)]
+        );
+    }
+
+    #[test]
+    fn test_replace_all_occurrences_of_word() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["foo bar foo", "foo"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let ctrl_d = InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character('d'),
+            mask: ModifierKeysMask {
+                ctrl_key_state: KeyState::Pressed,
+                shift_key_state: KeyState::NotPressed,
+                alt_key_state: KeyState::NotPressed,
+            },
+        });
+
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![ctrl_d, ctrl_d, ctrl_d, plain(Key::Character('z'))],
+        );
+        assert_eq2!(buffer.get_as_string_with_newlines(), "z bar z\nz");
+        assert_eq2!(
+            positions(&buffer),
+            vec![
+                position!(col_index: 1, row_index: 0),
+                position!(col_index: 7, row_index: 0),
+                position!(col_index: 1, row_index: 1),
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod soft_wrap_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::*;
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                *};
+
+    fn make_buffer() -> EditorBuffer {
+        mock_real_objects_for_editor::make_editor_buffer_with_lines(vec![
+            "hello big world",
+            "abc",
+            "0123456789abc",
+            "x",
+        ])
+    }
+
+    /// The viewport is 10 cols wide and 3 rows high.
+    fn make_engine(syntax_highlight: SyntaxHighlightMode) -> EditorEngine {
+        EditorEngine {
+            config_options: EditorEngineConfig {
+                soft_wrap: SoftWrapMode::Enable,
+                syntax_highlight,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine_with_bounds(
+                size!(col_count: 10, row_count: 3),
+            )
+        }
+    }
+
+    fn apply(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        events: Vec<EditorEvent>,
+    ) {
+        EditorEvent::apply_editor_events::<(), ()>(
+            engine,
+            buffer,
+            events,
+            &mut TestClipboard::default(),
+        );
+    }
+
+    fn caret(buffer: &EditorBuffer) -> Position {
+        buffer.get_caret(CaretKind::ScrollAdjusted)
+    }
+
+    #[test]
+    fn test_up_down_move_between_visual_rows() {
+        let mut buffer = make_buffer();
+        let mut engine = make_engine(SyntaxHighlightMode::Enable);
+
+        apply(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+            ],
+        );
+        // "hello big " is on the first row, and "world" on the second.
+        assert_eq2!(caret(&buffer), position!(col_index: 13, row_index: 0));
+
+        apply(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::MoveCaret(CaretDirection::Down)],
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 3, row_index: 1));
+
+        apply(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+            ],
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 13, row_index: 2));
+        // The 2 visual rows of the first line were scrolled out of the viewport.
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 1)
+        );
+        assert_eq2!(
+            buffer.get_caret(CaretKind::Raw),
+            position!(col_index: 13, row_index: 1)
+        );
+
+        apply(&mut buffer, &mut engine, vec![EditorEvent::PageUp]);
+        assert_eq2!(caret(&buffer), position!(col_index: 13, row_index: 0));
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 0)
+        );
+    }
+
+    #[test]
+    fn test_home_end_move_within_visual_row() {
+        let mut buffer = make_buffer();
+        let mut engine = make_engine(SyntaxHighlightMode::Enable);
+
+        apply(&mut buffer, &mut engine, vec![EditorEvent::End]);
+        // The end of the first row is before the space that the line wraps at.
+        assert_eq2!(caret(&buffer), position!(col_index: 9, row_index: 0));
+
+        apply(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::Home,
+            ],
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 10, row_index: 0));
+
+        apply(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::Select(SelectionAction::End)],
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 15, row_index: 0));
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(10), ch!(15)))
+        );
+    }
+
+    #[test]
+    fn test_select_down_within_line() {
+        let mut buffer = make_buffer();
+        let mut engine = make_engine(SyntaxHighlightMode::Enable);
+
+        apply(
+            &mut buffer,
+            &mut engine,
+            vec![
+                EditorEvent::MoveCaret(CaretDirection::Right),
+                EditorEvent::Select(SelectionAction::OneLineDown),
+            ],
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 11, row_index: 0));
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(1), ch!(11)))
+        );
+    }
+
+    #[test]
+    fn test_typing_past_viewport_does_not_scroll_horizontally() {
+        let mut buffer = make_buffer();
+        let mut engine = make_engine(SyntaxHighlightMode::Enable);
+
+        apply(
+            &mut buffer,
+            &mut engine,
+            vec![
+                // The first line takes up 2 visual rows.
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::MoveCaret(CaretDirection::Down),
+                EditorEvent::End,
+                EditorEvent::InsertString("defghijklm".to_string()),
+            ],
+        );
+        assert_eq2!(buffer.get_lines()[1], UnicodeString::from("abcdefghijklm"));
+        assert_eq2!(caret(&buffer), position!(col_index: 13, row_index: 1));
+        // Both lines wrap, so the first one is scrolled out of the viewport.
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 1)
+        );
+    }
+
+    #[test]
+    fn test_validate_scroll_without_a_line_at_the_caret() {
+        let engine = make_engine(SyntaxHighlightMode::Disable);
+
+        // There are no lines at all.
+        let mut buffer = EditorBuffer::default();
+        soft_wrap::validate_scroll(&mut buffer, &engine);
+        assert_eq2!(caret(&buffer), position!(col_index: 0, row_index: 0));
+
+        // The caret and the scroll offset are past the last line.
+        let mut buffer = make_buffer();
+        {
+            let (_, caret_raw, scroll_offset, _) = buffer.get_mut();
+            *caret_raw = position!(col_index: 0, row_index: 0);
+            *scroll_offset = position!(col_index: 0, row_index: 4);
+        }
+        soft_wrap::validate_scroll(&mut buffer, &engine);
+        assert_eq2!(caret(&buffer), position!(col_index: 0, row_index: 4));
+        assert_eq2!(
+            buffer.get_scroll_offset(),
+            position!(col_index: 0, row_index: 4)
+        );
+    }
+
+    #[test]
+    fn test_click_on_visual_row() {
+        let mut buffer = make_buffer();
+        let mut engine = make_engine(SyntaxHighlightMode::Enable);
+
+        apply(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::Mouse(MouseAction::Click(
+                position!(col_index: 2, row_index: 1),
+            ))],
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 12, row_index: 0));
+
+        // Past the end of a visual row that is not the last one of its line.
+        apply(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::Mouse(MouseAction::Click(
+                position!(col_index: 9, row_index: 0),
+            ))],
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 9, row_index: 0));
+
+        apply(
+            &mut buffer,
+            &mut engine,
+            vec![EditorEvent::Mouse(MouseAction::Click(
+                position!(col_index: 9, row_index: 2),
+            ))],
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 3, row_index: 1));
+    }
+
+    #[test]
+    fn test_render_wrapped_lines() {
+        let buffer = make_buffer();
+        let mut engine = make_engine(SyntaxHighlightMode::Disable);
+        let mut render_ops = render_ops!();
+        EditorEngineApi::render_content(
+            &mut RenderArgs {
+                editor_buffer: &buffer,
+                editor_engine: &mut engine,
+                has_focus: &mut HasFocus::default(),
+            },
+            &mut render_ops,
+        );
+
+        // Pair up each painted text w/ the row that it is painted on.
+        let mut painted_rows = vec![];
+        let mut row_index = ch!(0);
+        for render_op in render_ops.iter() {
+            match render_op {
+                RenderOp::MoveCursorPositionRelTo(_, position) => {
+                    row_index = position.row_index;
+                }
+                RenderOp::PaintTextWithAttributes(text, _) => {
+                    painted_rows.push((ch!(@to_usize row_index), text.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        assert_eq2!(
+            painted_rows,
+            vec![
+                (0, "hello big ".to_string()),
+                (1, "world".to_string()),
+                (2, "abc".to_string()),
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod word_movement_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::{apply_input_events,
+                                               make_editor_buffer_with_lines},
+                *};
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                *};
+
+    fn with_mask(key: SpecialKey, mask: ModifierKeysMask) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::SpecialKey(key),
+            mask,
+        })
+    }
+
+    fn ctrl(key: SpecialKey) -> InputEvent {
+        with_mask(key, ModifierKeysMask::new().with_ctrl())
+    }
+
+    fn ctrl_shift(key: SpecialKey) -> InputEvent {
+        with_mask(key, ModifierKeysMask::new().with_ctrl().with_shift())
+    }
+
+    fn caret(buffer: &EditorBuffer) -> Position {
+        buffer.get_caret(CaretKind::ScrollAdjusted)
+    }
+
+    #[test]
+    fn test_word_left_and_right() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["let foo_bar = 1;", "  baz"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Right)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 3, row_index: 0));
+
+        // `foo_bar` is a single word.
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Right)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 11, row_index: 0));
+
+        // Punctuation is skipped.
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Right)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 15, row_index: 0));
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Right)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 16, row_index: 0));
+
+        // Wraps to the next line.
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Right)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 0, row_index: 1));
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Right)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 5, row_index: 1));
+
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Left)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 2, row_index: 1));
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Left)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 0, row_index: 1));
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Left)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 16, row_index: 0));
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Left)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 14, row_index: 0));
+    }
+
+    #[test]
+    fn test_word_movement_over_wide_chars() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["😀 héllo wörld"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Right)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 8, row_index: 0));
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Right)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 14, row_index: 0));
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Left)]);
+        assert_eq2!(caret(&buffer), position!(col_index: 9, row_index: 0));
+    }
+
+    #[test]
+    fn test_select_words() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["one two three"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![ctrl_shift(SpecialKey::Right), ctrl_shift(SpecialKey::Right)],
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(7)))
+        );
+
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl_shift(SpecialKey::Left)]);
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(4)))
+        );
+    }
+
+    #[test]
+    fn test_select_word_at_caret() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["one two three"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        buffer.get_mut().1.col_index = ch!(5);
+
+        EditorEvent::apply_editor_event(
+            &mut engine,
+            &mut buffer,
+            EditorEvent::Select(SelectionAction::Word),
+            &mut TestClipboard::default(),
+        );
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(4), ch!(7)))
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 7, row_index: 0));
+    }
+
+    #[test]
+    fn test_delete_words_and_undo() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["one two", "three"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        // Ctrl+Backspace at the end of the line.
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![
+                InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::SpecialKey(SpecialKey::End),
+                }),
+                ctrl(SpecialKey::Backspace),
+            ],
+        );
+        assert_eq2!(
+            buffer.get_as_string_with_comma_instead_of_newlines(),
+            "one , three"
+        );
+
+        // Alt+Backspace does the same thing.
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![with_mask(
+                SpecialKey::Backspace,
+                ModifierKeysMask::new().with_alt(),
+            )],
+        );
+        assert_eq2!(
+            buffer.get_as_string_with_comma_instead_of_newlines(),
+            ", three"
+        );
+
+        // Ctrl+Delete at the end of the line joins it w/ the next one.
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Delete)]);
+        assert_eq2!(
+            buffer.get_as_string_with_comma_instead_of_newlines(),
+            "three"
+        );
+        apply_input_events(&mut buffer, &mut engine, vec![ctrl(SpecialKey::Delete)]);
+        assert_eq2!(buffer.get_as_string_with_comma_instead_of_newlines(), "");
+
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('z'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            })],
+        );
+        assert_eq2!(
+            buffer.get_as_string_with_comma_instead_of_newlines(),
+            "three"
+        );
+    }
+}
+
+#[cfg(test)]
+mod mouse_tests {
+    use r3bl_rs_utils_core::*;
+
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                test_editor::mock_real_objects_for_editor::{apply_input_events,
+                                                            make_editor_buffer_with_lines},
+                *};
+
+    /// The editor's box is at col 2, row 1 of the screen, and is 10 cols by 3 rows.
+    fn make_engine() -> EditorEngine {
+        let flex_box = FlexBox {
+            style_adjusted_bounds_size: size!( col_count: 10, row_count: 3 ),
+            style_adjusted_origin_pos: position!( col_index: 2, row_index: 1 ),
+            ..Default::default()
+        };
+        EditorEngine {
+            current_box: (&flex_box).into(),
+            ..Default::default()
+        }
+    }
+
+    fn apply_mouse_event(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        kind: MouseInputKind,
+        pos: Position,
+    ) -> EditorEngineApplyEventResult {
+        apply_input_events(
+            buffer,
+            engine,
+            vec![InputEvent::Mouse(MouseInput {
+                pos,
+                kind,
+                maybe_modifier_keys: None,
+            })],
+        )
+    }
+
+    fn caret(buffer: &EditorBuffer) -> Position {
+        buffer.get_caret(CaretKind::ScrollAdjusted)
+    }
+
+    #[test]
+    fn test_click_moves_caret() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["abc", "a😀b"]);
+        let mut engine = make_engine();
+        let click = MouseInputKind::MouseDown(Button::Left);
+
+        apply_mouse_event(
+            &mut buffer,
+            &mut engine,
+            click,
+            position!(col_index: 3, row_index: 1),
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 1, row_index: 0));
+
+        // Past the end of the line.
+        apply_mouse_event(
+            &mut buffer,
+            &mut engine,
+            click,
+            position!(col_index: 9, row_index: 1),
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 3, row_index: 0));
+
+        // Left and right half of a wide char.
+        apply_mouse_event(
+            &mut buffer,
+            &mut engine,
+            click,
+            position!(col_index: 3, row_index: 2),
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 1, row_index: 1));
+        apply_mouse_event(
+            &mut buffer,
+            &mut engine,
+            click,
+            position!(col_index: 4, row_index: 2),
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 3, row_index: 1));
+
+        // Outside the editor.
+        let result = apply_mouse_event(
+            &mut buffer,
+            &mut engine,
+            click,
+            position!(col_index: 0, row_index: 0),
+        );
+        assert!(matches!(result, EditorEngineApplyEventResult::NotApplied));
+        assert_eq2!(caret(&buffer), position!(col_index: 3, row_index: 1));
+    }
+
+    #[test]
+    fn test_drag_selects() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["abcd", "efgh", "ijkl"]);
+        let mut engine = make_engine();
+
+        apply_mouse_event(
+            &mut buffer,
+            &mut engine,
+            MouseInputKind::MouseDown(Button::Left),
+            position!(col_index: 3, row_index: 1),
+        );
+        apply_mouse_event(
+            &mut buffer,
+            &mut engine,
+            MouseInputKind::MouseDrag(Button::Left),
+            position!(col_index: 4, row_index: 3),
+        );
+
+        assert_eq2!(caret(&buffer), position!(col_index: 2, row_index: 2));
+        let selection_map = buffer.get_selection_map();
+        assert_eq2!(
+            selection_map.get(ch!(0)),
+            Some(&SelectionRange::new(ch!(1), ch!(4)))
+        );
+        assert_eq2!(
+            selection_map.get(ch!(1)),
+            Some(&SelectionRange::new(ch!(0), ch!(4)))
+        );
+        assert_eq2!(
+            selection_map.get(ch!(2)),
+            Some(&SelectionRange::new(ch!(0), ch!(2)))
+        );
+
+        // Dragging back to the same line.
+        apply_mouse_event(
+            &mut buffer,
+            &mut engine,
+            MouseInputKind::MouseDrag(Button::Left),
+            position!(col_index: 2, row_index: 1),
+        );
+        assert_eq2!(caret(&buffer), position!(col_index: 0, row_index: 0));
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(0), ch!(1)))
+        );
+        assert_eq2!(buffer.get_selection_map().get(ch!(1)), None);
+    }
+
+    #[test]
+    fn test_double_click_selects_word() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["one two"]);
+        let mut engine = make_engine();
+        let click = MouseInputKind::MouseDown(Button::Left);
+        let pos = position!(col_index: 7, row_index: 1);
+
+        apply_mouse_event(&mut buffer, &mut engine, click, pos);
+        assert!(buffer.get_selection_map().is_empty());
+
+        apply_mouse_event(&mut buffer, &mut engine, click, pos);
+        assert_eq2!(
+            buffer.get_selection_map().get(ch!(0)),
+            Some(&SelectionRange::new(ch!(4), ch!(7)))
+        );
+    }
+
+    #[test]
+    fn test_wheel_scrolls_without_moving_caret() {
+        let lines: Vec<String> = (0..10).map(|it| format!("line {it}")).collect();
+        let mut buffer =
+            make_editor_buffer_with_lines(lines.iter().map(String::as_str).collect());
+        let mut engine = make_engine();
+        let pos = position!(col_index: 3, row_index: 1);
+
+        apply_mouse_event(&mut buffer, &mut engine, MouseInputKind::ScrollDown, pos);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(3));
+        assert_eq2!(caret(&buffer), position!(col_index: 0, row_index: 0));
+
+        apply_mouse_event(&mut buffer, &mut engine, MouseInputKind::ScrollUp, pos);
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(0));
+        assert_eq2!(caret(&buffer), position!(col_index: 0, row_index: 0));
+
+        // Can't scroll past the last line.
+        for _ in 0..5 {
+            apply_mouse_event(&mut buffer, &mut engine, MouseInputKind::ScrollDown, pos);
+        }
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(9));
+
+        // Typing scrolls back to the caret.
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::Character('x'),
+            }),
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+        assert_eq2!(buffer.get_lines()[0].string, "xline 0");
+        assert_eq2!(caret(&buffer), position!(col_index: 1, row_index: 0));
+        assert_eq2!(buffer.get_scroll_offset().row_index, ch!(0));
+    }
+}
+
+#[cfg(test)]
+mod completion_tests {
+    use std::sync::Arc;
+
+    use r3bl_rs_utils_core::*;
+    use tokio::sync::mpsc;
+
+    use super::{mock_real_objects_for_editor::{apply_input_events,
+                                               make_editor_buffer_with_lines},
+                *};
+    use crate::*;
+
+    fn make_completion() -> EditorCompletion {
+        EditorCompletion::new(vec![Arc::new(WordCompletionProvider)])
+    }
+
+    fn get_labels(completion: &EditorCompletion) -> Vec<String> {
+        completion
+            .get_state()
+            .items
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+    }
+
+    fn key(special_key: SpecialKey) -> InputEvent {
+        InputEvent::Keyboard(KeyPress::Plain {
+            key: Key::SpecialKey(special_key),
+        })
+    }
+
+    #[test]
+    fn test_get_match_score() {
+        assert_eq!(get_match_score("hello", "he"), Some(3));
+        assert_eq!(get_match_score("Hello", "he"), Some(2));
+        assert_eq!(get_match_score("othello", "he"), Some(1));
+        assert_eq!(get_match_score("world", "he"), None);
+    }
+
+    #[tokio::test]
+    async fn test_word_completion_provider() {
+        let mut buffer =
+            make_editor_buffer_with_lines(vec!["hello help world", "hello", "he"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 2),
+        );
+
+        let request = CompletionRequest::new(&buffer);
+        assert_eq!(
+            request.get_word_prefix(),
+            Some((SelectionRange::new(ch!(0), ch!(2)), "he"))
+        );
+
+        let items =
+            collect_completions(&[Arc::new(WordCompletionProvider)], &request).await;
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_str()).collect();
+        // "hello" occurs more often than "help", and "world" doesn't match.
+        assert_eq!(labels, vec!["hello", "help"]);
+        assert_eq!(items[0].replace_range, SelectionRange::new(ch!(0), ch!(2)));
+    }
+
+    #[tokio::test]
+    async fn test_file_path_completion_provider() {
+        let dir = std::env::temp_dir()
+            .join(format!("r3bl_tui_completion_test_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("alps")).unwrap();
+        std::fs::write(dir.join("alpha.txt"), "").unwrap();
+        std::fs::write(dir.join(".alpine"), "").unwrap();
+        std::fs::write(dir.join("beta.txt"), "").unwrap();
+
+        let line = format!("see {}/al", dir.display());
+        let mut buffer = make_editor_buffer_with_lines(vec![line.as_str()]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: ch!(line.len()), row_index: 0),
+        );
+
+        let request = CompletionRequest::new(&buffer);
+        let items = collect_completions(
+            &[Arc::new(FilePathCompletionProvider::default())],
+            &request,
+        )
+        .await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["alpha.txt", "alps/"]);
+        // Only the name after the last `/` is replaced.
+        assert_eq!(
+            items[0].replace_range,
+            SelectionRange::new(ch!(line.len() - 2), ch!(line.len()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_navigate_and_accept() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["hello help", "he"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 1),
+        );
+
+        let mut completion = make_completion();
+        let (sender, mut receiver) =
+            mpsc::channel::<TerminalWindowMainThreadSignal<()>>(4);
+        completion
+            .request(&buffer, &engine, FlexBoxId::from(0), sender)
+            .unwrap()
+            .await
+            .unwrap();
+
+        // The popup is filled and a render is requested.
+        assert!(completion.is_open());
+        assert_eq!(get_labels(&completion), vec!["hello", "help"]);
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(TerminalWindowMainThreadSignal::Render(_))
+        ));
+
+        // Selection wraps around.
+        let result =
+            completion.handle_event(&mut buffer, &mut engine, &key(SpecialKey::Up));
+        assert_eq!(result, CompletionEventResult::Consumed);
+        assert_eq!(completion.get_state().selected_index, 1);
+
+        // Other keys are for the editor.
+        let result =
+            completion.handle_event(&mut buffer, &mut engine, &key(SpecialKey::Left));
+        assert_eq!(result, CompletionEventResult::NotConsumed);
+
+        let result =
+            completion.handle_event(&mut buffer, &mut engine, &key(SpecialKey::Enter));
+        assert_eq!(result, CompletionEventResult::Accepted);
+        assert!(!completion.is_open());
+        assert_eq!(buffer.get_lines()[1].string, "help");
+        assert_eq!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 4, row_index: 1)
+        );
+
+        // Accepting can be undone.
+        apply_input_events(
+            &mut buffer,
+            &mut engine,
+            vec![InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('z'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            })],
+        );
+        assert_eq!(buffer.get_lines()[1].string, "he");
+    }
+
+    #[tokio::test]
+    async fn test_keymap() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["hello help", "he"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 1),
+        );
+
+        let mut completion = make_completion();
+        let ctrl_space = InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character(' '),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        });
+        assert_eq!(
+            completion.handle_event(&mut buffer, &mut engine, &ctrl_space),
+            CompletionEventResult::Request
+        );
+        // The popup isn't open, so the other actions are for the editor.
+        assert_eq!(
+            completion.handle_event(&mut buffer, &mut engine, &key(SpecialKey::Tab)),
+            CompletionEventResult::NotConsumed
+        );
+
+        // Rebind accept, so that `Tab` goes to the editor while the popup is open.
+        completion.keymap.set_bindings(
+            completion_actions::ACCEPT,
+            vec![KeySequence::try_parse("Ctrl+Y").unwrap()],
+        );
+        let (sender, _receiver) = mpsc::channel::<TerminalWindowMainThreadSignal<()>>(4);
+        completion
+            .request(&buffer, &engine, FlexBoxId::from(0), sender)
+            .unwrap()
+            .await
+            .unwrap();
+        assert!(completion.is_open());
+        assert_eq!(
+            completion.handle_event(&mut buffer, &mut engine, &key(SpecialKey::Tab)),
+            CompletionEventResult::NotConsumed
+        );
+        let ctrl_y = InputEvent::Keyboard(KeyPress::WithModifiers {
+            key: Key::Character('y'),
+            mask: ModifierKeysMask::new().with_ctrl(),
+        });
+        assert_eq!(
+            completion.handle_event(&mut buffer, &mut engine, &ctrl_y),
+            CompletionEventResult::Accepted
+        );
+        assert_eq!(buffer.get_lines()[1].string, "hello");
+
+        // The actions are registered, so that they can be listed.
+        assert!(global_action_registry::get(completion_actions::REQUEST).is_some());
+
+        // W/out providers, nothing is consumed.
+        let mut completion = EditorCompletion::default();
+        assert_eq!(
+            completion.handle_event(&mut buffer, &mut engine, &ctrl_space),
+            CompletionEventResult::NotConsumed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_typing_updates_and_moving_dismisses() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["hello help", "he"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 1),
+        );
+
+        let completion = make_completion();
+        let (sender, _receiver) = mpsc::channel::<TerminalWindowMainThreadSignal<()>>(4);
+        let id = FlexBoxId::from(0);
+        completion
+            .request(&buffer, &engine, id, sender.clone())
+            .unwrap()
+            .await
+            .unwrap();
+
+        // Typing requests completions again.
+        let input_event = InputEvent::Keyboard(keypress!(@char 'l'));
+        apply_input_events(&mut buffer, &mut engine, vec![input_event]);
+        completion
+            .update_after_editor_event(&buffer, &engine, id, sender.clone())
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(get_labels(&completion), vec!["hello", "help"]);
+
+        // Moving the caret dismisses the popup.
+        let input_event = key(SpecialKey::Left);
+        apply_input_events(&mut buffer, &mut engine, vec![input_event]);
+        let maybe_handle =
+            completion.update_after_editor_event(&buffer, &engine, id, sender);
+        assert!(maybe_handle.is_none());
+        assert!(!completion.is_active());
+    }
+
+    #[tokio::test]
+    async fn test_accept_before_results_are_updated() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["hello help", "he"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 1),
+        );
+
+        let completion = make_completion();
+        let (sender, _receiver) = mpsc::channel::<TerminalWindowMainThreadSignal<()>>(4);
+        let id = FlexBoxId::from(0);
+        completion
+            .request(&buffer, &engine, id, sender.clone())
+            .unwrap()
+            .await
+            .unwrap();
+
+        // Type a char, and accept before the new results are in. The char that was typed
+        // is replaced as well.
+        let input_event = InputEvent::Keyboard(keypress!(@char 'l'));
+        apply_input_events(&mut buffer, &mut engine, vec![input_event]);
+        let handle = completion
+            .update_after_editor_event(&buffer, &engine, id, sender)
+            .unwrap();
+        completion.accept(&mut buffer, &mut engine);
+        handle.await.unwrap();
+
+        assert_eq!(buffer.get_lines()[1].string, "hello");
+        // The stale results are dropped.
+        assert!(!completion.is_active());
+    }
+
+    #[tokio::test]
+    async fn test_read_only() {
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        let mut completion = make_completion();
+        let (sender, _receiver) = mpsc::channel::<TerminalWindowMainThreadSignal<()>>(4);
+        let id = FlexBoxId::from(0);
+
+        // A buffer that isn't valid UTF-8 is read only, so nothing is requested.
+        let mut buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        buffer.set_lines_from_file_bytes(b"hello help\nhe\xff");
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 1),
+        );
+        assert!(completion
+            .request(&buffer, &engine, id, sender.clone())
+            .is_none());
+        assert!(!completion.is_active());
+
+        // The popup is open when the editor is made read only, so accepting just closes
+        // it.
+        let mut buffer = make_editor_buffer_with_lines(vec!["hello help", "he"]);
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 1),
+        );
+        completion
+            .request(&buffer, &engine, id, sender)
+            .unwrap()
+            .await
+            .unwrap();
+        assert!(completion.is_open());
+
+        engine.config_options.edit_mode = EditMode::ReadOnly;
+        let result =
+            completion.handle_event(&mut buffer, &mut engine, &key(SpecialKey::Enter));
+        assert_eq!(result, CompletionEventResult::Consumed);
+        assert!(!completion.is_open());
+        assert_eq!(buffer.get_lines()[1].string, "he");
+    }
+}
+
+#[cfg(test)]
+mod smart_list_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::{apply_input_events,
+                                               make_editor_buffer_with_lines},
+                *};
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                *};
+
+    fn make_engine() -> EditorEngine {
+        EditorEngine {
+            config_options: EditorEngineConfig {
+                smart_list: SmartListMode::Enable,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        }
+    }
+
+    fn press(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        special_key: SpecialKey,
+    ) -> EditorEngineApplyEventResult {
+        apply_input_events(
+            buffer,
+            engine,
+            vec![InputEvent::Keyboard(KeyPress::Plain {
+                key: Key::SpecialKey(special_key),
+            })],
+        )
+    }
+
+    fn get_lines(buffer: &EditorBuffer) -> Vec<&str> {
+        buffer
+            .get_lines()
+            .iter()
+            .map(|line| line.string.as_str())
+            .collect()
+    }
+
+    fn caret(buffer: &EditorBuffer) -> Position {
+        buffer.get_caret(CaretKind::ScrollAdjusted)
+    }
+
+    #[test]
+    fn test_parse_list_item_prefix() {
+        assert_eq!(
+            ListItemPrefix::parse("- foo"),
+            Some((
+                ListItemPrefix {
+                    indent: 0,
+                    bullet_kind: BulletKind::Unordered,
+                    maybe_checked: None
+                },
+                "foo"
+            ))
+        );
+
+        let (prefix, content) = ListItemPrefix::parse("  12. [x] done").unwrap();
+        assert_eq!(prefix.indent, 2);
+        assert_eq!(prefix.bullet_kind, BulletKind::Ordered(12));
+        assert_eq!(prefix.maybe_checked, Some(true));
+        assert_eq!(content, "done");
+        assert_eq!(prefix.to_string(), "  12. [x] ");
+        assert_eq!(prefix.get_next().to_string(), "  13. [ ] ");
+
+        assert_eq!(ListItemPrefix::parse("- ").map(|(_, it)| it), Some(""));
+        assert_eq!(ListItemPrefix::parse("foo"), None);
+        assert_eq!(ListItemPrefix::parse("-foo"), None);
+    }
+
+    #[test]
+    fn test_enter_continues_list() {
+        // Unordered w/ a checkbox.
+        let mut buffer = make_editor_buffer_with_lines(vec!["- [x] a"]);
+        let mut engine = make_engine();
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 7, row_index: 0),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::Enter);
+        assert_eq!(get_lines(&buffer), vec!["- [x] a", "- [ ] "]);
+        assert_eq!(caret(&buffer), position!(col_index: 6, row_index: 1));
+
+        // Ordered, in the middle of the list. The items below are renumbered.
+        let mut buffer = make_editor_buffer_with_lines(vec!["1. a", "2. b"]);
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 4, row_index: 0),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::Enter);
+        assert_eq!(get_lines(&buffer), vec!["1. a", "2. ", "3. b"]);
+        assert_eq!(caret(&buffer), position!(col_index: 3, row_index: 1));
+
+        // Enter in the prefix just splits the line.
+        let mut buffer = make_editor_buffer_with_lines(vec!["- a"]);
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 0, row_index: 0),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::Enter);
+        assert_eq!(get_lines(&buffer), vec!["", "- a"]);
+    }
+
+    #[test]
+    fn test_enter_on_empty_item_ends_list() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["- a", "- "]);
+        let mut engine = make_engine();
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 1),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::Enter);
+        assert_eq!(get_lines(&buffer), vec!["- a", ""]);
+        assert_eq!(caret(&buffer), position!(col_index: 0, row_index: 1));
+    }
+
+    #[test]
+    fn test_tab_indents_and_outdents() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["1. a", "2. b", "3. c"]);
+        let mut engine = make_engine();
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 4, row_index: 1),
+        );
+
+        // The indented item starts a nested list, and the rest are renumbered.
+        press(&mut buffer, &mut engine, SpecialKey::Tab);
+        assert_eq!(get_lines(&buffer), vec!["1. a", "  1. b", "2. c"]);
+        assert_eq!(caret(&buffer), position!(col_index: 6, row_index: 1));
+
+        press(&mut buffer, &mut engine, SpecialKey::BackTab);
+        assert_eq!(get_lines(&buffer), vec!["1. a", "2. b", "3. c"]);
+        assert_eq!(caret(&buffer), position!(col_index: 4, row_index: 1));
+
+        // Undo restores the indent.
+        EditorEngineApi::apply_event(
+            &mut buffer,
+            &mut engine,
+            InputEvent::Keyboard(KeyPress::WithModifiers {
+                key: Key::Character('z'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            }),
+            &mut TestClipboard::default(),
+        )
+        .unwrap();
+        assert_eq!(get_lines(&buffer), vec!["1. a", "  1. b", "2. c"]);
+    }
+
+    #[test]
+    fn test_smart_list_disabled() {
+        // Tab inserts spaces, and Enter doesn't continue the list.
+        let mut buffer = make_editor_buffer_with_lines(vec!["- foo"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        press(&mut buffer, &mut engine, SpecialKey::Tab);
+        assert_eq!(get_lines(&buffer), vec!["    - foo"]);
+        press(&mut buffer, &mut engine, SpecialKey::BackTab);
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 5, row_index: 0),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::Enter);
+        assert_eq!(get_lines(&buffer), vec!["- foo", ""]);
+    }
+
+    #[test]
+    fn test_renumber_after_removing_item() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["1. a", "2. b", "3. c"]);
+        let mut engine = make_engine();
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 0, row_index: 1),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::Backspace);
+        assert_eq!(get_lines(&buffer), vec!["1. a2. b", "2. c"]);
+    }
+}
+
+#[cfg(test)]
+mod indent_and_auto_pair_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::{apply_input_events,
+                                               make_editor_buffer_with_lines},
+                *};
+    use crate::*;
+
+    fn make_engine(config_options: EditorEngineConfig) -> EditorEngine {
+        EditorEngine {
+            config_options,
+            ..mock_real_objects_for_editor::make_editor_engine()
+        }
+    }
+
+    fn apply(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        key_press: KeyPress,
+    ) -> EditorEngineApplyEventResult {
+        apply_input_events(buffer, engine, vec![InputEvent::Keyboard(key_press)])
+    }
+
+    fn press(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        special_key: SpecialKey,
+    ) -> EditorEngineApplyEventResult {
+        apply(
+            buffer,
+            engine,
+            KeyPress::Plain {
+                key: Key::SpecialKey(special_key),
+            },
+        )
+    }
+
+    fn type_str(buffer: &mut EditorBuffer, engine: &mut EditorEngine, text: &str) {
+        for character in text.chars() {
+            apply(buffer, engine, keypress!(@char character));
+        }
+    }
+
+    fn get_lines(buffer: &EditorBuffer) -> Vec<&str> {
+        buffer
+            .get_lines()
+            .iter()
+            .map(|line| line.string.as_str())
+            .collect()
+    }
+
+    fn caret(buffer: &EditorBuffer) -> Position {
+        buffer.get_caret(CaretKind::ScrollAdjusted)
+    }
+
+    #[test]
+    fn test_auto_indent() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["    foo"]);
+        let mut engine = make_engine(EditorEngineConfig::default());
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 7, row_index: 0),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::Enter);
+        assert_eq!(get_lines(&buffer), vec!["    foo", "    "]);
+        assert_eq!(caret(&buffer), position!(col_index: 4, row_index: 1));
+
+        // In the middle of the leading whitespace, the indent is kept as is.
+        let mut buffer = make_editor_buffer_with_lines(vec!["    foo"]);
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 0),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::Enter);
+        assert_eq!(get_lines(&buffer), vec!["  ", "    foo"]);
+        assert_eq!(caret(&buffer), position!(col_index: 2, row_index: 1));
+
+        // Disabled.
+        let mut buffer = make_editor_buffer_with_lines(vec!["    foo"]);
+        let mut engine = make_engine(EditorEngineConfig {
+            auto_indent: AutoIndentMode::Disable,
+            ..Default::default()
+        });
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 7, row_index: 0),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::Enter);
+        assert_eq!(get_lines(&buffer), vec!["    foo", ""]);
+    }
+
+    #[test]
+    fn test_tab_inserts_spaces_to_next_tab_stop() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["abcd"]);
+        let mut engine = make_engine(EditorEngineConfig {
+            tab_width: 3,
+            ..Default::default()
+        });
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 2, row_index: 0),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::Tab);
+        assert_eq!(get_lines(&buffer), vec!["ab cd"]);
+        assert_eq!(caret(&buffer), position!(col_index: 3, row_index: 0));
+        press(&mut buffer, &mut engine, SpecialKey::Tab);
+        assert_eq!(get_lines(&buffer), vec!["ab    cd"]);
+        assert_eq!(caret(&buffer), position!(col_index: 6, row_index: 0));
+
+        // Shift+Tab outdents the line.
+        press(&mut buffer, &mut engine, SpecialKey::BackTab);
+        assert_eq!(get_lines(&buffer), vec!["ab    cd"]);
+        let mut buffer = make_editor_buffer_with_lines(vec!["     x"]);
+        EditorEngineInternalApi::move_caret_to(
+            &mut buffer,
+            &mut engine,
+            position!(col_index: 6, row_index: 0),
+        );
+        press(&mut buffer, &mut engine, SpecialKey::BackTab);
+        assert_eq!(get_lines(&buffer), vec!["  x"]);
+        assert_eq!(caret(&buffer), position!(col_index: 3, row_index: 0));
+
+        // Single line editors leave Tab alone.
+        let mut engine = make_engine(EditorEngineConfig {
+            multiline_mode: LineMode::SingleLine,
+            ..Default::default()
+        });
+        let result = press(&mut buffer, &mut engine, SpecialKey::Tab);
+        assert!(matches!(result, EditorEngineApplyEventResult::NotApplied));
+    }
+
+    #[test]
+    fn test_block_indent_and_outdent() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["a", "", "  b"]);
+        let mut engine = make_engine(EditorEngineConfig::default());
+        apply(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::Character('a'),
+                mask: ModifierKeysMask::new().with_ctrl(),
+            },
+        );
+
+        // Empty lines are left alone, and the selection stays.
+        press(&mut buffer, &mut engine, SpecialKey::Tab);
+        assert_eq!(get_lines(&buffer), vec!["    a", "", "      b"]);
+        assert!(buffer.has_selection());
+
+        press(&mut buffer, &mut engine, SpecialKey::BackTab);
+        // This is how terminals report Shift+Tab.
+        apply(
+            &mut buffer,
+            &mut engine,
+            KeyPress::WithModifiers {
+                key: Key::SpecialKey(SpecialKey::BackTab),
+                mask: ModifierKeysMask::new().with_shift(),
+            },
+        );
+        assert_eq!(get_lines(&buffer), vec!["a", "", "b"]);
+        assert!(buffer.has_selection());
+    }
+
+    #[test]
+    fn test_auto_pair() {
+        let mut buffer = make_editor_buffer_with_lines(vec![""]);
+        let mut engine = make_engine(EditorEngineConfig {
+            auto_pair: AutoPairMode::Enable,
+            ..Default::default()
+        });
+
+        // Pair, and skip over the closing char.
+        type_str(&mut buffer, &mut engine, "f(");
+        assert_eq!(get_lines(&buffer), vec!["f()"]);
+        assert_eq!(caret(&buffer), position!(col_index: 2, row_index: 0));
+        type_str(&mut buffer, &mut engine, "[\"x");
+        assert_eq!(get_lines(&buffer), vec!["f([\"x\"])"]);
+        type_str(&mut buffer, &mut engine, "\"])");
+        assert_eq!(get_lines(&buffer), vec!["f([\"x\"])"]);
+        assert_eq!(caret(&buffer), position!(col_index: 8, row_index: 0));
+
+        // Quotes after a word are not paired.
+        type_str(&mut buffer, &mut engine, " don't");
+        assert_eq!(get_lines(&buffer), vec!["f([\"x\"]) don't"]);
+
+        // Nor is anything before a word.
+        let mut buffer = make_editor_buffer_with_lines(vec!["x"]);
+        type_str(&mut buffer, &mut engine, "(");
+        assert_eq!(get_lines(&buffer), vec!["(x"]);
+
+        // Backspace deletes an empty pair.
+        let mut buffer = make_editor_buffer_with_lines(vec![""]);
+        type_str(&mut buffer, &mut engine, "`");
+        assert_eq!(get_lines(&buffer), vec!["``"]);
+        press(&mut buffer, &mut engine, SpecialKey::Backspace);
+        assert_eq!(get_lines(&buffer), vec![""]);
+    }
+}
+
+#[cfg(test)]
+mod keymap_tests {
+    use r3bl_rs_utils_core::*;
+
+    use super::{mock_real_objects_for_editor::{apply_input_events,
+                                               make_editor_buffer_with_lines},
+                *};
+    use crate::*;
+
+    fn apply(
+        buffer: &mut EditorBuffer,
+        engine: &mut EditorEngine,
+        key_sequence: &str,
+    ) -> EditorEngineApplyEventResult {
+        let key_presses = KeySequence::try_parse(key_sequence).unwrap().key_presses;
+        apply_input_events(
+            buffer,
+            engine,
+            key_presses.into_iter().map(InputEvent::Keyboard).collect(),
+        )
+    }
+
+    fn get_lines(buffer: &EditorBuffer) -> Vec<&str> {
+        buffer
+            .get_lines()
+            .iter()
+            .map(|line| line.string.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_every_action_is_an_editor_event() {
+        for action in EditorEvent::get_keymap_actions() {
+            assert_eq2!(action.get_scope(), editor_actions::SCOPE);
+            assert!(!action.default_bindings.is_empty(), "{}", action.id);
+            assert!(EditorEvent::try_from_action_id(&action.id).is_some());
+        }
+    }
+
+    #[test]
+    fn test_try_from_input_event() {
+        let event = |it: &str| {
+            let key_press = KeySequence::try_parse(it).unwrap().key_presses[0];
+            EditorEvent::try_from(InputEvent::Keyboard(key_press))
+        };
+        assert!(event("Ctrl+Z") == Ok(EditorEvent::Undo));
+        assert!(
+            event("Shift+F3") == Ok(EditorEvent::Search(SearchAction::PreviousMatch))
+        );
+        assert!(event("Alt+Backspace") == Ok(EditorEvent::BackspaceWord));
+        assert!(event("x") == Ok(EditorEvent::InsertChar('x')));
+        assert!(event("Ctrl+Q").is_err());
+    }
+
+    #[test]
+    fn test_rebind_to_chord() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["abc"]);
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+        engine.keymap.set_bindings(
+            editor_actions::SELECT_ALL,
+            vec![KeySequence::try_parse("Ctrl+K Ctrl+A").unwrap()],
+        );
+
+        // The old binding doesn't select anything anymore.
+        assert!(matches!(
+            apply(&mut buffer, &mut engine, "Ctrl+A"),
+            EditorEngineApplyEventResult::NotApplied
+        ));
+
+        assert!(matches!(
+            apply(&mut buffer, &mut engine, "Ctrl+K"),
+            EditorEngineApplyEventResult::Consumed
+        ));
+        assert!(engine.maybe_last_editor_event.is_none());
+        assert!(matches!(
+            apply(&mut buffer, &mut engine, "Ctrl+A"),
+            EditorEngineApplyEventResult::Applied
+        ));
+        assert!(
+            engine.maybe_last_editor_event
+                == Some(EditorEvent::Select(SelectionAction::All))
+        );
+        apply(&mut buffer, &mut engine, "x");
+        assert_eq2!(get_lines(&buffer), vec!["x"]);
+
+        // A chord that isn't finished doesn't type the key that cancels it.
+        apply(&mut buffer, &mut engine, "Ctrl+K");
+        assert!(matches!(
+            apply(&mut buffer, &mut engine, "y"),
+            EditorEngineApplyEventResult::Consumed
+        ));
+        assert_eq2!(get_lines(&buffer), vec!["x"]);
+    }
+
+    #[test]
+    fn test_read_only_mode() {
+        let mut buffer = make_editor_buffer_with_lines(vec!["abc"]);
+        let mut engine = EditorEngine {
+            config_options: EditorEngineConfig {
+                edit_mode: EditMode::ReadOnly,
+                ..Default::default()
+            },
+            ..mock_real_objects_for_editor::make_editor_engine()
+        };
+
+        assert!(matches!(
+            apply(&mut buffer, &mut engine, "Right"),
+            EditorEngineApplyEventResult::Applied
+        ));
+        assert!(matches!(
+            apply(&mut buffer, &mut engine, "x"),
+            EditorEngineApplyEventResult::NotApplied
+        ));
+        assert!(matches!(
+            apply(&mut buffer, &mut engine, "Ctrl+V"),
+            EditorEngineApplyEventResult::NotApplied
+        ));
+        assert_eq2!(get_lines(&buffer), vec!["abc"]);
+        assert_eq2!(
+            buffer.get_caret(CaretKind::ScrollAdjusted),
+            position!(col_index: 1, row_index: 0)
+        );
+    }
+    #[test]
+    fn test_read_only_buffer() {
+        let mut buffer = EditorBuffer::new_empty(&None, &None);
+        buffer.set_lines_from_file_bytes(b"ab\xFF");
+        let mut engine = mock_real_objects_for_editor::make_editor_engine();
+
+        assert!(matches!(
+            apply(&mut buffer, &mut engine, "Right"),
+            EditorEngineApplyEventResult::Applied
+        ));
+        assert!(matches!(
+            apply(&mut buffer, &mut engine, "x"),
+            EditorEngineApplyEventResult::NotApplied
+        ));
+        assert_eq2!(get_lines(&buffer), vec!["ab\u{FFFD}"]);
+    }
+}
@@ -0,0 +1,340 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::{Display, Formatter};
+
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// One or more [KeyPress]es that have to be typed one after the other to trigger an
+/// action. A sequence w/ more than one key press is a chord, eg: `Ctrl+K Ctrl+S`.
+///
+/// In the keymap file a sequence is written as a string. The key presses are separated
+/// by spaces, and each key press is made up of optional modifiers (`Ctrl`, `Alt`,
+/// `Shift`) followed by a key, separated by `+`. Names are not case sensitive.
+/// - Keys: a single character, `Space`, `Enter`, `Esc`, `Tab`, `BackTab`, `Backspace`,
+///   `Delete`, `Insert`, `Up`, `Down`, `Left`, `Right`, `Home`, `End`, `PageUp`,
+///   `PageDown`, `F1` to `F12`.
+/// - Characters w/ `Ctrl` or `Alt` are lower case, unless `Shift` is pressed too.
+///   `Shift` w/ just a character is the same as the upper case character.
+/// - `Shift+Tab` is what terminals report when Shift and Tab are pressed.
+///
+/// ```rust
+/// use r3bl_tui::*;
+///
+/// let sequence = KeySequence::try_parse("Ctrl+K Ctrl+S").unwrap();
+/// assert_eq!(sequence.key_presses.len(), 2);
+/// assert_eq!(
+///     sequence.key_presses[1],
+///     keypress! { @char ModifierKeysMask::new().with_ctrl(), 's' }
+/// );
+/// assert_eq!(sequence.to_string(), "Ctrl+K Ctrl+S");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct KeySequence {
+    pub key_presses: Vec<KeyPress>,
+}
+
+mod key_sequence_impl {
+    use super::*;
+
+    impl KeySequence {
+        pub fn new(key_presses: Vec<KeyPress>) -> Self { Self { key_presses } }
+
+        pub fn try_parse(text: &str) -> CommonResult<Self> {
+            let mut key_presses = vec![];
+            for token in text.split_whitespace() {
+                key_presses.push(try_parse_key_press(token)?);
+            }
+            if key_presses.is_empty() {
+                return CommonError::new(
+                    CommonErrorType::InvalidValue,
+                    "Key sequence is empty",
+                );
+            }
+            Ok(Self { key_presses })
+        }
+
+        /// Returns true if `key_presses` is the start of this sequence, but not all of it.
+        pub fn is_longer_and_starts_with(&self, key_presses: &[KeyPress]) -> bool {
+            self.key_presses.len() > key_presses.len()
+                && self.key_presses.starts_with(key_presses)
+        }
+    }
+
+    impl From<KeyPress> for KeySequence {
+        fn from(key_press: KeyPress) -> Self { Self::new(vec![key_press]) }
+    }
+
+    impl TryFrom<String> for KeySequence {
+        type Error = String;
+
+        fn try_from(text: String) -> Result<Self, Self::Error> {
+            Self::try_parse(&text).map_err(|error| {
+                match error.downcast_ref::<CommonError>() {
+                    Some(CommonError {
+                        err_msg: Some(msg), ..
+                    }) => msg.clone(),
+                    _ => error.to_string(),
+                }
+            })
+        }
+    }
+
+    impl From<KeySequence> for String {
+        fn from(sequence: KeySequence) -> Self { sequence.to_string() }
+    }
+
+    impl Display for KeySequence {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            let it = self
+                .key_presses
+                .iter()
+                .map(format_key_press)
+                .collect::<Vec<_>>()
+                .join(" ");
+            write!(f, "{it}")
+        }
+    }
+}
+
+fn try_parse_key_press(token: &str) -> CommonResult<KeyPress> {
+    let (modifiers, key) = if token == "+" {
+        ("", "+")
+    } else if let Some(modifiers) = token.strip_suffix("++") {
+        (modifiers, "+")
+    } else {
+        match token.rsplit_once('+') {
+            Some((modifiers, key)) => (modifiers, key),
+            None => ("", token),
+        }
+    };
+
+    let mut mask = ModifierKeysMask::new();
+    for modifier in modifiers.split('+').filter(|it| !it.is_empty()) {
+        mask = match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" => mask.with_ctrl(),
+            "alt" => mask.with_alt(),
+            "shift" => mask.with_shift(),
+            _ => {
+                return CommonError::new(
+                    CommonErrorType::InvalidValue,
+                    &format!("Unknown modifier {modifier:?} in key press {token:?}"),
+                )
+            }
+        };
+    }
+
+    let Some(key) = try_parse_key(key) else {
+        return CommonError::new(
+            CommonErrorType::InvalidValue,
+            &format!("Unknown key in key press {token:?}"),
+        );
+    };
+
+    Ok(make_key_press(key, mask))
+}
+
+fn try_parse_key(text: &str) -> Option<Key> {
+    let mut chars = text.chars();
+    if let (Some(character), None) = (chars.next(), chars.next()) {
+        return Some(Key::Character(character));
+    }
+
+    let key = match text.to_lowercase().as_str() {
+        "space" => Key::Character(' '),
+        "backspace" => Key::SpecialKey(SpecialKey::Backspace),
+        "enter" | "return" => Key::SpecialKey(SpecialKey::Enter),
+        "left" => Key::SpecialKey(SpecialKey::Left),
+        "right" => Key::SpecialKey(SpecialKey::Right),
+        "up" => Key::SpecialKey(SpecialKey::Up),
+        "down" => Key::SpecialKey(SpecialKey::Down),
+        "home" => Key::SpecialKey(SpecialKey::Home),
+        "end" => Key::SpecialKey(SpecialKey::End),
+        "pageup" | "pgup" => Key::SpecialKey(SpecialKey::PageUp),
+        "pagedown" | "pgdn" => Key::SpecialKey(SpecialKey::PageDown),
+        "tab" => Key::SpecialKey(SpecialKey::Tab),
+        "backtab" => Key::SpecialKey(SpecialKey::BackTab),
+        "delete" | "del" => Key::SpecialKey(SpecialKey::Delete),
+        "insert" | "ins" => Key::SpecialKey(SpecialKey::Insert),
+        "esc" | "escape" => Key::SpecialKey(SpecialKey::Esc),
+        "f1" => Key::FunctionKey(FunctionKey::F1),
+        "f2" => Key::FunctionKey(FunctionKey::F2),
+        "f3" => Key::FunctionKey(FunctionKey::F3),
+        "f4" => Key::FunctionKey(FunctionKey::F4),
+        "f5" => Key::FunctionKey(FunctionKey::F5),
+        "f6" => Key::FunctionKey(FunctionKey::F6),
+        "f7" => Key::FunctionKey(FunctionKey::F7),
+        "f8" => Key::FunctionKey(FunctionKey::F8),
+        "f9" => Key::FunctionKey(FunctionKey::F9),
+        "f10" => Key::FunctionKey(FunctionKey::F10),
+        "f11" => Key::FunctionKey(FunctionKey::F11),
+        "f12" => Key::FunctionKey(FunctionKey::F12),
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Builds the [KeyPress] that the terminal backends produce for `key` & `mask`, see
+/// [convert_key_event].
+fn make_key_press(key: Key, mut mask: ModifierKeysMask) -> KeyPress {
+    let is_shift = mask.shift_key_state == KeyState::Pressed;
+    let is_ctrl_or_alt = mask.ctrl_key_state == KeyState::Pressed
+        || mask.alt_key_state == KeyState::Pressed;
+
+    let key = match key {
+        Key::Character(character) if is_shift => {
+            Key::Character(character.to_uppercase().next().unwrap_or(character))
+        }
+        Key::Character(character) if is_ctrl_or_alt => {
+            Key::Character(character.to_lowercase().next().unwrap_or(character))
+        }
+        Key::SpecialKey(SpecialKey::Tab) if is_shift => {
+            Key::SpecialKey(SpecialKey::BackTab)
+        }
+        _ => key,
+    };
+
+    // Shift is dropped from character keys that don't have any other modifiers.
+    if let (Key::Character(_), false) = (key, is_ctrl_or_alt) {
+        mask.shift_key_state = KeyState::NotPressed;
+    }
+
+    if mask == ModifierKeysMask::new() {
+        KeyPress::Plain { key }
+    } else {
+        KeyPress::WithModifiers { key, mask }
+    }
+}
+
+fn format_key_press(key_press: &KeyPress) -> String {
+    let (key, mask) = match key_press {
+        KeyPress::Plain { key } => (*key, ModifierKeysMask::new()),
+        KeyPress::WithModifiers { key, mask } => (*key, *mask),
+    };
+
+    let mut it = String::new();
+    if mask.ctrl_key_state == KeyState::Pressed {
+        it.push_str("Ctrl+");
+    }
+    if mask.alt_key_state == KeyState::Pressed {
+        it.push_str("Alt+");
+    }
+    if mask.shift_key_state == KeyState::Pressed {
+        it.push_str("Shift+");
+    }
+
+    let is_shift = mask.shift_key_state == KeyState::Pressed;
+    match key {
+        Key::Character(' ') => it.push_str("Space"),
+        Key::Character(character) if it.is_empty() => it.push(character),
+        Key::Character(character) => it.extend(character.to_uppercase()),
+        Key::SpecialKey(SpecialKey::BackTab) if is_shift => it.push_str("Tab"),
+        Key::SpecialKey(special_key) => it.push_str(&format!("{special_key:?}")),
+        Key::FunctionKey(function_key) => it.push_str(&format!("{function_key:?}")),
+        Key::KittyKeyboardProtocol(enhanced) => it.push_str(&format!("{enhanced:?}")),
+    }
+    it
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Vec<KeyPress> {
+        KeySequence::try_parse(text).unwrap().key_presses
+    }
+
+    #[test]
+    fn test_parse_key_presses() {
+        assert_eq2!(parse("a"), vec![keypress! { @char 'a' }]);
+        assert_eq2!(parse("A"), vec![keypress! { @char 'A' }]);
+        assert_eq2!(parse("Shift+a"), vec![keypress! { @char 'A' }]);
+        assert_eq2!(parse("space"), vec![keypress! { @char ' ' }]);
+        assert_eq2!(
+            parse("Ctrl+S"),
+            vec![keypress! { @char ModifierKeysMask::new().with_ctrl(), 's' }]
+        );
+        assert_eq2!(
+            parse("ctrl+shift+s"),
+            vec![
+                keypress! { @char ModifierKeysMask::new().with_ctrl().with_shift(), 'S' }
+            ]
+        );
+        assert_eq2!(
+            parse("Ctrl++"),
+            vec![keypress! { @char ModifierKeysMask::new().with_ctrl(), '+' }]
+        );
+        assert_eq2!(parse("+"), vec![keypress! { @char '+' }]);
+        assert_eq2!(
+            parse("Alt+Enter"),
+            vec![
+                keypress! { @special ModifierKeysMask::new().with_alt(), SpecialKey::Enter }
+            ]
+        );
+        assert_eq2!(
+            parse("Shift+Tab"),
+            vec![
+                keypress! { @special ModifierKeysMask::new().with_shift(), SpecialKey::BackTab }
+            ]
+        );
+        assert_eq2!(parse("F3"), vec![keypress! { @fn FunctionKey::F3 }]);
+        assert_eq2!(
+            parse("Ctrl+K  Ctrl+S"),
+            vec![
+                keypress! { @char ModifierKeysMask::new().with_ctrl(), 'k' },
+                keypress! { @char ModifierKeysMask::new().with_ctrl(), 's' },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(KeySequence::try_parse("").is_err());
+        assert!(KeySequence::try_parse("Hyper+a").is_err());
+        assert!(KeySequence::try_parse("Ctrl+Foo").is_err());
+        assert!(serde_json::from_str::<KeySequence>("\"Ctrl+\"").is_err());
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for text in [
+            "a",
+            "A",
+            "Space",
+            "Ctrl+S",
+            "Ctrl+Shift+S",
+            "Ctrl+Alt+Up",
+            "Shift+Tab",
+            "BackTab",
+            "Shift+F3",
+            "Ctrl++",
+            "Ctrl+K Ctrl+S",
+        ] {
+            let sequence = KeySequence::try_parse(text).unwrap();
+            assert_eq2!(sequence.to_string(), text);
+            let json = serde_json::to_string(&sequence).unwrap();
+            assert_eq2!(
+                serde_json::from_str::<KeySequence>(&json).unwrap(),
+                sequence
+            );
+        }
+    }
+}
@@ -0,0 +1,173 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::sync::{atomic::{AtomicUsize, Ordering},
+                RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// A named action that key sequences can be bound to, eg: `editor.undo`. The part of the
+/// `id` before the first `.` is its scope, which is used by a [Keymap] to only pick the
+/// actions that its component handles.
+///
+/// Actions are registered in the [global_action_registry], so that they can be listed
+/// (eg: to show them in a help screen) and rebound in the [KeymapConfig] file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeymapAction {
+    pub id: String,
+    pub description: String,
+    pub default_bindings: Vec<KeySequence>,
+}
+
+mod keymap_action_impl {
+    use super::*;
+
+    impl KeymapAction {
+        /// The `default_bindings` are parsed w/ [KeySequence::try_parse], and the ones
+        /// that aren't valid are left out.
+        pub fn new(id: &str, description: &str, default_bindings: &[&str]) -> Self {
+            Self {
+                id: id.to_string(),
+                description: description.to_string(),
+                default_bindings: default_bindings
+                    .iter()
+                    .filter_map(|it| KeySequence::try_parse(it).ok())
+                    .collect(),
+            }
+        }
+
+        pub fn get_scope(&self) -> &str {
+            match self.id.split_once('.') {
+                Some((scope, _)) => scope,
+                None => &self.id,
+            }
+        }
+    }
+}
+
+/// Holds every [KeymapAction] that can be bound to a key sequence. It starts out w/ the
+/// actions of the components in this crate (see [EditorEvent::get_keymap_actions],
+/// [EditorCompletion::get_keymap_actions] and [DialogEvent::get_keymap_actions]), and
+/// apps add their own w/
+/// [register](global_action_registry::register).
+pub mod global_action_registry {
+    use super::*;
+
+    static ACTIONS: RwLock<Option<Vec<KeymapAction>>> = RwLock::new(None);
+    static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+    /// Add the `actions`. An action that is already registered (w/ the same id) is
+    /// replaced.
+    pub fn register(actions: Vec<KeymapAction>) {
+        if let Ok(mut guard) = ACTIONS.write() {
+            let registered = guard.get_or_insert_with(get_built_in_actions);
+            for action in actions {
+                match registered.iter_mut().find(|it| it.id == action.id) {
+                    Some(it) => *it = action,
+                    None => registered.push(action),
+                }
+            }
+        }
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Returns the actions in the order that they were registered in.
+    pub fn get_all() -> Vec<KeymapAction> {
+        if let Ok(guard) = ACTIONS.read() {
+            if let Some(actions) = guard.as_ref() {
+                return actions.clone();
+            }
+        }
+        get_built_in_actions()
+    }
+
+    pub fn get(action_id: &str) -> Option<KeymapAction> {
+        get_all().into_iter().find(|it| it.id == action_id)
+    }
+
+    /// This changes every time an action is registered, so that a [Keymap] knows when to
+    /// update its bindings.
+    pub fn get_generation() -> usize { GENERATION.load(Ordering::SeqCst) }
+
+    fn get_built_in_actions() -> Vec<KeymapAction> {
+        let mut it = EditorEvent::get_keymap_actions();
+        it.extend(EditorCompletion::get_keymap_actions());
+        it.extend(DialogEvent::get_keymap_actions());
+        it
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+    use serial_test::serial;
+
+    use super::*;
+
+    #[test]
+    fn test_get_scope() {
+        assert_eq2!(
+            KeymapAction::new("editor.undo", "", &[]).get_scope(),
+            "editor"
+        );
+        assert_eq2!(KeymapAction::new("undo", "", &[]).get_scope(), "undo");
+    }
+
+    #[serial]
+    #[test]
+    fn test_register() {
+        assert!(global_action_registry::get(editor_actions::UNDO).is_some());
+        assert!(global_action_registry::get(dialog_actions::ACCEPT).is_some());
+
+        let generation = global_action_registry::get_generation();
+        global_action_registry::register(vec![KeymapAction::new(
+            "test_register.action",
+            "Test action",
+            &["Ctrl+K Ctrl+T", "not a key"],
+        )]);
+        assert_ne!(generation, global_action_registry::get_generation());
+
+        let action = global_action_registry::get("test_register.action").unwrap();
+        assert_eq2!(action.description, "Test action");
+        assert_eq2!(
+            action.default_bindings,
+            vec![KeySequence::try_parse("Ctrl+K Ctrl+T").unwrap()]
+        );
+
+        // Registering it again replaces it.
+        global_action_registry::register(vec![KeymapAction::new(
+            "test_register.action",
+            "Changed",
+            &[],
+        )]);
+        let all = global_action_registry::get_all();
+        assert_eq2!(
+            all.iter()
+                .filter(|it| it.id == "test_register.action")
+                .count(),
+            1
+        );
+        assert_eq2!(
+            global_action_registry::get("test_register.action")
+                .unwrap()
+                .description,
+            "Changed"
+        );
+    }
+}
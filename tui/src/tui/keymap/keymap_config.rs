@@ -0,0 +1,215 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::HashMap,
+          path::{Path, PathBuf},
+          sync::{atomic::{AtomicUsize, Ordering},
+                 Arc,
+                 OnceLock,
+                 RwLock}};
+
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// Name of the keymap file in [KeymapConfig::try_get_config_file_path].
+pub const KEYMAP_FILE_NAME: &str = "keymap.json";
+
+/// The key sequences that the user has bound to actions, which replace the
+/// [KeymapAction::default_bindings]. See [mod@crate::keymap] for the file format.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct KeymapConfig {
+    /// Action id -> key sequences, for every component.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub bindings: HashMap<String, Vec<KeySequence>>,
+    /// Component name -> action id -> key sequences, only for the [Keymap]s that have
+    /// that [component_name](Keymap::component_name).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub components: HashMap<String, HashMap<String, Vec<KeySequence>>>,
+}
+
+mod keymap_config_impl {
+    use super::*;
+
+    impl KeymapConfig {
+        pub fn try_from_json_str(json: &str) -> CommonResult<Self> {
+            match serde_json::from_str::<Self>(json) {
+                Ok(it) => Ok(it),
+                Err(error) => CommonError::new(
+                    CommonErrorType::ParsingError,
+                    &format!("Keymap is not valid: {error}"),
+                ),
+            }
+        }
+
+        pub fn try_load_from_file(path: &Path) -> CommonResult<Self> {
+            match std::fs::read_to_string(path) {
+                Ok(json) => Self::try_from_json_str(&json),
+                Err(error) => CommonError::new(
+                    CommonErrorType::IOError,
+                    &format!("Could not read keymap file {path:?}: {error}"),
+                ),
+            }
+        }
+
+        /// Returns the path of the keymap file in the user's config folder, eg:
+        /// `~/.config/r3bl-tui/keymap.json` on Linux.
+        pub fn try_get_config_file_path() -> Option<PathBuf> {
+            Some(
+                dirs::config_dir()?
+                    .join(MD_THEME_CONFIG_FOLDER_NAME)
+                    .join(KEYMAP_FILE_NAME),
+            )
+        }
+
+        /// Load the keymap file in the user's config folder. Returns [None] if there
+        /// isn't one.
+        pub fn try_load_from_config_folder() -> CommonResult<Option<Self>> {
+            let Some(path) = Self::try_get_config_file_path() else {
+                return Ok(None);
+            };
+            if !path.exists() {
+                return Ok(None);
+            }
+            Self::try_load_from_file(&path).map(Some)
+        }
+
+        /// Returns the key sequences bound to `action_id` for the component, or [None]
+        /// if they aren't in this config (in which case the defaults are used).
+        pub fn get_bindings(
+            &self,
+            component_name: &str,
+            action_id: &str,
+        ) -> Option<&Vec<KeySequence>> {
+            self.components
+                .get(component_name)
+                .and_then(|it| it.get(action_id))
+                .or_else(|| self.bindings.get(action_id))
+        }
+    }
+}
+
+/// Holds the [KeymapConfig] that every [Keymap] uses. It is empty (so the default
+/// bindings are used) unless it is changed w/ [set](global_keymap_config::set).
+pub mod global_keymap_config {
+    use super::*;
+
+    static KEYMAP_CONFIG: RwLock<Option<Arc<KeymapConfig>>> = RwLock::new(None);
+    static DEFAULT_KEYMAP_CONFIG: OnceLock<Arc<KeymapConfig>> = OnceLock::new();
+    static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+    pub fn get() -> Arc<KeymapConfig> {
+        if let Ok(guard) = KEYMAP_CONFIG.read() {
+            if let Some(keymap_config) = guard.as_ref() {
+                return keymap_config.clone();
+            }
+        }
+        DEFAULT_KEYMAP_CONFIG.get_or_init(Default::default).clone()
+    }
+
+    pub fn set(keymap_config: KeymapConfig) { replace(Some(Arc::new(keymap_config))); }
+
+    /// Go back to the default bindings.
+    pub fn clear() { replace(None); }
+
+    /// Use the keymap file in the user's config folder (see
+    /// [KeymapConfig::try_load_from_config_folder]), if there is one.
+    pub fn try_load_from_config_folder() -> CommonResult<()> {
+        if let Some(keymap_config) = KeymapConfig::try_load_from_config_folder()? {
+            set(keymap_config);
+        }
+        Ok(())
+    }
+
+    /// This changes every time the config changes, so that a [Keymap] knows when to
+    /// update its bindings.
+    pub fn get_generation() -> usize { GENERATION.load(Ordering::SeqCst) }
+
+    fn replace(maybe_keymap_config: Option<Arc<KeymapConfig>>) {
+        if let Ok(mut guard) = KEYMAP_CONFIG.write() {
+            *guard = maybe_keymap_config;
+        }
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_json_str() {
+        let keymap_config = KeymapConfig::try_from_json_str(
+            r#"{
+                "bindings": {
+                    "editor.undo": ["Ctrl+K Ctrl+Z", "Alt+Backspace"],
+                    "editor.redo": []
+                },
+                "components": {
+                    "dialog": { "editor.undo": ["F1"] }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq2!(
+            keymap_config.get_bindings("editor", editor_actions::UNDO),
+            Some(&vec![
+                KeySequence::try_parse("Ctrl+K Ctrl+Z").unwrap(),
+                KeySequence::try_parse("Alt+Backspace").unwrap(),
+            ])
+        );
+        assert_eq2!(
+            keymap_config.get_bindings("dialog", editor_actions::UNDO),
+            Some(&vec![KeySequence::try_parse("F1").unwrap()])
+        );
+        assert_eq2!(
+            keymap_config.get_bindings("dialog", editor_actions::REDO),
+            Some(&vec![])
+        );
+        assert_eq2!(
+            keymap_config.get_bindings("editor", editor_actions::COPY),
+            None
+        );
+    }
+
+    #[test]
+    fn test_try_from_json_str_errors() {
+        assert!(KeymapConfig::try_from_json_str("{").is_err());
+        assert!(KeymapConfig::try_from_json_str(r#"{ "binding": {} }"#).is_err());
+        assert!(KeymapConfig::try_from_json_str(
+            r#"{ "bindings": { "editor.undo": ["Ctrl+Foo"] } }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_try_load_from_file() {
+        let path = std::env::temp_dir().join("r3bl_tui_test_keymap_config.json");
+        std::fs::write(&path, r#"{ "bindings": { "editor.undo": ["F2"] } }"#).unwrap();
+        let keymap_config = KeymapConfig::try_load_from_file(&path).unwrap();
+        assert_eq2!(
+            keymap_config.get_bindings("editor", editor_actions::UNDO),
+            Some(&vec![KeySequence::try_parse("F2").unwrap()])
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(KeymapConfig::try_load_from_file(&path).is_err());
+    }
+}
@@ -0,0 +1,394 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{collections::HashMap,
+          fmt::{Display, Formatter}};
+
+use crate::*;
+
+/// Turns the key presses that a component receives into the ids of the [KeymapAction]s
+/// that they are bound to. Each component that handles key presses owns one of these
+/// (eg: [EditorEngine::keymap], [DialogEngine::keymap]), since it has to remember the
+/// key presses of a chord that has been started, but not finished.
+///
+/// The bindings of an action (that is in one of the `scopes`) come from the first one
+/// of these that has them:
+/// 1. [set_bindings](Keymap::set_bindings), to override them in code.
+/// 2. The `components` section of the [KeymapConfig] for the `component_name`.
+/// 3. The `bindings` section of the [KeymapConfig].
+/// 4. [KeymapAction::default_bindings].
+///
+/// The bindings are updated when the [global_action_registry] or the
+/// [global_keymap_config] change.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Keymap {
+    pub component_name: String,
+    pub scopes: Vec<String>,
+    overrides: HashMap<String, Vec<KeySequence>>,
+    bindings: Vec<(KeySequence, String)>,
+    /// The [global_action_registry::get_generation] and
+    /// [global_keymap_config::get_generation] that `bindings` come from.
+    maybe_generation: Option<(usize, usize)>,
+    pending_key_presses: Vec<KeyPress>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeymapResult {
+    /// The id of the action that the key sequence is bound to.
+    Action(String),
+    /// The key press started (or continued) a chord, and more key presses are needed.
+    Pending,
+    /// The key press doesn't finish the chord that was started, so the chord is
+    /// cancelled. The key press is meant to be consumed.
+    Cancelled,
+    /// Not a key press, or one that isn't bound to anything.
+    NotBound,
+}
+
+/// Two bindings where the key sequence of one is the start of a chord of the other, eg:
+/// `Ctrl+K` and `Ctrl+K Ctrl+Z`. In a [Keymap] the chord wins, and when the bindings are in
+/// different [Keymap]s, the one that sees the key press first wins. Either way one of them
+/// can't be triggered. See [Keymap::get_conflicts].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeymapConflict {
+    pub key_sequence: KeySequence,
+    pub action_id: String,
+    pub chord: KeySequence,
+    pub chord_action_id: String,
+}
+
+impl Display for KeymapConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` ({}) is the start of the chord `{}` ({})",
+            self.key_sequence, self.action_id, self.chord, self.chord_action_id
+        )
+    }
+}
+
+mod keymap_impl {
+    use super::*;
+
+    impl Keymap {
+        pub fn new(component_name: &str, scopes: &[&str]) -> Self {
+            Self {
+                component_name: component_name.to_string(),
+                scopes: scopes.iter().map(|it| it.to_string()).collect(),
+                ..Default::default()
+            }
+        }
+
+        /// Override the bindings of the action for this component only. An empty
+        /// `key_sequences` unbinds it.
+        pub fn set_bindings(&mut self, action_id: &str, key_sequences: Vec<KeySequence>) {
+            self.overrides.insert(action_id.to_string(), key_sequences);
+            self.maybe_generation = None;
+        }
+
+        /// If a key press is bound to an action, and also starts a chord, then the chord
+        /// wins (so the action can't be triggered by that key press alone).
+        pub fn resolve(&mut self, input_event: &InputEvent) -> KeymapResult {
+            let InputEvent::Keyboard(key_press) = input_event else {
+                return KeymapResult::NotBound;
+            };
+
+            self.update_bindings();
+
+            let mut key_presses = std::mem::take(&mut self.pending_key_presses);
+            let is_chord_started = !key_presses.is_empty();
+            key_presses.push(*key_press);
+
+            if self
+                .bindings
+                .iter()
+                .any(|(sequence, _)| sequence.is_longer_and_starts_with(&key_presses))
+            {
+                self.pending_key_presses = key_presses;
+                return KeymapResult::Pending;
+            }
+
+            match self
+                .bindings
+                .iter()
+                .find(|(sequence, _)| sequence.key_presses == key_presses)
+            {
+                Some((_, action_id)) => KeymapResult::Action(action_id.clone()),
+                None if is_chord_started => KeymapResult::Cancelled,
+                None => KeymapResult::NotBound,
+            }
+        }
+
+        /// The key presses of the chord that has been started, if any.
+        pub fn get_pending_key_presses(&self) -> &[KeyPress] { &self.pending_key_presses }
+
+        pub fn cancel_pending_key_presses(&mut self) { self.pending_key_presses.clear(); }
+
+        pub fn get_bindings(&mut self, action_id: &str) -> Vec<KeySequence> {
+            self.get_all_bindings()
+                .iter()
+                .filter(|(_, it)| it == action_id)
+                .map(|(sequence, _)| sequence.clone())
+                .collect()
+        }
+
+        /// Every key sequence, and the id of the action that it is bound to.
+        pub fn get_all_bindings(&mut self) -> &[(KeySequence, String)] {
+            self.update_bindings();
+            &self.bindings
+        }
+
+        /// Returns the [KeymapConflict]s between the bindings of the `keymaps`, which
+        /// should be the ones that see the same key presses (eg: the one of an app, and
+        /// the one of the component that has focus). Call this after the keymap file is
+        /// loaded, since a chord in it can make another binding unreachable.
+        pub fn get_conflicts(keymaps: &mut [Keymap]) -> Vec<KeymapConflict> {
+            let mut bindings: Vec<(KeySequence, String)> = vec![];
+            for keymap in keymaps.iter_mut() {
+                bindings.extend(keymap.get_all_bindings().iter().cloned());
+            }
+
+            let mut acc = vec![];
+            for (key_sequence, action_id) in &bindings {
+                for (chord, chord_action_id) in &bindings {
+                    let conflict = KeymapConflict {
+                        key_sequence: key_sequence.clone(),
+                        action_id: action_id.clone(),
+                        chord: chord.clone(),
+                        chord_action_id: chord_action_id.clone(),
+                    };
+                    if chord.is_longer_and_starts_with(&key_sequence.key_presses)
+                        && !acc.contains(&conflict)
+                    {
+                        acc.push(conflict);
+                    }
+                }
+            }
+            acc
+        }
+
+        fn update_bindings(&mut self) {
+            let generation = (
+                global_action_registry::get_generation(),
+                global_keymap_config::get_generation(),
+            );
+            if self.maybe_generation == Some(generation) {
+                return;
+            }
+
+            let keymap_config = global_keymap_config::get();
+            let actions = global_action_registry::get_all();
+
+            let mut bindings = vec![];
+            for scope in &self.scopes {
+                for action in actions.iter().filter(|it| it.get_scope() == scope) {
+                    let key_sequences = self
+                        .overrides
+                        .get(&action.id)
+                        .or_else(|| {
+                            keymap_config.get_bindings(&self.component_name, &action.id)
+                        })
+                        .unwrap_or(&action.default_bindings);
+                    for key_sequence in key_sequences {
+                        bindings.push((key_sequence.clone(), action.id.clone()));
+                    }
+                }
+            }
+
+            self.bindings = bindings;
+            self.maybe_generation = Some(generation);
+            self.pending_key_presses.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::*;
+    use serial_test::serial;
+
+    use super::*;
+
+    fn key(text: &str) -> InputEvent {
+        let sequence = KeySequence::try_parse(text).unwrap();
+        InputEvent::Keyboard(sequence.key_presses[0])
+    }
+
+    fn action(id: &str) -> KeymapResult { KeymapResult::Action(id.to_string()) }
+
+    #[serial]
+    #[test]
+    fn test_resolve_default_bindings() {
+        let mut keymap = Keymap::new("editor", &[editor_actions::SCOPE]);
+
+        assert_eq2!(keymap.resolve(&key("Ctrl+Z")), action(editor_actions::UNDO));
+        assert_eq2!(
+            keymap.resolve(&key("Shift+Tab")),
+            action(editor_actions::OUTDENT)
+        );
+        assert_eq2!(
+            keymap.resolve(&key("BackTab")),
+            action(editor_actions::OUTDENT)
+        );
+        assert_eq2!(keymap.resolve(&key("a")), KeymapResult::NotBound);
+        assert_eq2!(
+            keymap.resolve(&key("Enter")),
+            action(editor_actions::NEW_LINE)
+        );
+
+        // Actions in other scopes aren't used.
+        assert_eq2!(
+            keymap.get_bindings(dialog_actions::ACCEPT),
+            Vec::<KeySequence>::new()
+        );
+        assert_eq2!(
+            keymap.resolve(&InputEvent::Resize(size!(col_count: 1, row_count: 1))),
+            KeymapResult::NotBound
+        );
+    }
+
+    #[serial]
+    #[test]
+    fn test_resolve_chords() {
+        let mut keymap = Keymap::new("editor", &[editor_actions::SCOPE]);
+        keymap.set_bindings(
+            editor_actions::UNDO,
+            vec![KeySequence::try_parse("Ctrl+K Ctrl+Z").unwrap()],
+        );
+
+        // The old binding is gone.
+        assert_eq2!(keymap.resolve(&key("Ctrl+Z")), KeymapResult::NotBound);
+
+        assert_eq2!(keymap.resolve(&key("Ctrl+K")), KeymapResult::Pending);
+        assert_eq2!(keymap.get_pending_key_presses().len(), 1);
+        assert_eq2!(keymap.resolve(&key("Ctrl+Z")), action(editor_actions::UNDO));
+        assert_eq2!(keymap.get_pending_key_presses().len(), 0);
+
+        // A key press that doesn't finish the chord cancels it, and is not used as is.
+        assert_eq2!(keymap.resolve(&key("Ctrl+K")), KeymapResult::Pending);
+        assert_eq2!(keymap.resolve(&key("Ctrl+C")), KeymapResult::Cancelled);
+        assert_eq2!(keymap.resolve(&key("Ctrl+C")), action(editor_actions::COPY));
+
+        // Other events don't cancel a chord.
+        assert_eq2!(keymap.resolve(&key("Ctrl+K")), KeymapResult::Pending);
+        assert_eq2!(
+            keymap.resolve(&InputEvent::Resize(size!(col_count: 1, row_count: 1))),
+            KeymapResult::NotBound
+        );
+        assert_eq2!(keymap.resolve(&key("Ctrl+Z")), action(editor_actions::UNDO));
+    }
+
+    #[serial]
+    #[test]
+    fn test_get_conflicts() {
+        let sequence = |it: &str| KeySequence::try_parse(it).unwrap();
+        let mut editor_keymap = Keymap::new("editor", &[editor_actions::SCOPE]);
+        let mut dialog_keymap = Keymap::new("dialog", &[dialog_actions::SCOPE]);
+        assert_eq2!(
+            Keymap::get_conflicts(&mut [editor_keymap.clone(), dialog_keymap.clone()]),
+            vec![]
+        );
+
+        editor_keymap.set_bindings(editor_actions::UNDO, vec![sequence("Ctrl+K Ctrl+Z")]);
+        dialog_keymap.set_bindings(dialog_actions::ACCEPT, vec![sequence("Ctrl+K")]);
+        let conflicts = Keymap::get_conflicts(&mut [editor_keymap, dialog_keymap]);
+        assert_eq2!(
+            conflicts,
+            vec![KeymapConflict {
+                key_sequence: sequence("Ctrl+K"),
+                action_id: dialog_actions::ACCEPT.to_string(),
+                chord: sequence("Ctrl+K Ctrl+Z"),
+                chord_action_id: editor_actions::UNDO.to_string(),
+            }]
+        );
+        assert_eq2!(
+            conflicts[0].to_string(),
+            "`Ctrl+K` (dialog.accept) is the start of the chord `Ctrl+K Ctrl+Z` (editor.undo)"
+        );
+    }
+
+    /// Only test actions are rebound, since other tests use the default bindings.
+    #[serial]
+    #[test]
+    fn test_resolve_w_keymap_config() {
+        global_action_registry::register(vec![
+            KeymapAction::new("test_keymap_config.undo", "Undo", &["Ctrl+Z"]),
+            KeymapAction::new("test_keymap_config.redo", "Redo", &["Ctrl+Y"]),
+        ]);
+        global_keymap_config::set(
+            KeymapConfig::try_from_json_str(
+                r#"{
+                    "bindings": {
+                        "test_keymap_config.undo": ["F2"],
+                        "test_keymap_config.redo": []
+                    },
+                    "components": {
+                        "other_editor": { "test_keymap_config.undo": ["F4"] }
+                    }
+                }"#,
+            )
+            .unwrap(),
+        );
+
+        let mut keymap = Keymap::new("editor", &["test_keymap_config"]);
+        assert_eq2!(
+            keymap.resolve(&key("F2")),
+            action("test_keymap_config.undo")
+        );
+        assert_eq2!(keymap.resolve(&key("Ctrl+Z")), KeymapResult::NotBound);
+        assert_eq2!(keymap.resolve(&key("Ctrl+Y")), KeymapResult::NotBound);
+
+        let mut other_keymap = Keymap::new("other_editor", &["test_keymap_config"]);
+        assert_eq2!(
+            other_keymap.resolve(&key("F4")),
+            action("test_keymap_config.undo")
+        );
+        assert_eq2!(other_keymap.resolve(&key("F2")), KeymapResult::NotBound);
+
+        // The bindings are updated when the config changes.
+        global_keymap_config::clear();
+        assert_eq2!(
+            keymap.resolve(&key("Ctrl+Z")),
+            action("test_keymap_config.undo")
+        );
+        assert_eq2!(keymap.resolve(&key("F2")), KeymapResult::NotBound);
+    }
+
+    #[serial]
+    #[test]
+    fn test_resolve_registered_actions() {
+        let mut keymap = Keymap::new("test_app", &["test_resolve"]);
+        assert_eq2!(keymap.resolve(&key("Ctrl+K")), KeymapResult::NotBound);
+
+        global_action_registry::register(vec![KeymapAction::new(
+            "test_resolve.action",
+            "Test action",
+            &["Ctrl+K"],
+        )]);
+        assert_eq2!(
+            keymap.resolve(&key("Ctrl+K")),
+            action("test_resolve.action")
+        );
+        assert_eq2!(
+            keymap.get_all_bindings().to_vec(),
+            vec![(
+                KeySequence::try_parse("Ctrl+K").unwrap(),
+                "test_resolve.action".to_string()
+            )]
+        );
+    }
+}
@@ -0,0 +1,61 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Key bindings that can be changed. Instead of matching [crate::KeyPress]es directly,
+//! components bind key sequences to named actions ([KeymapAction]), and use a [Keymap] to
+//! find out which action a key press triggers:
+//! 1. The actions are registered in the [global_action_registry], along w/ their default
+//!    bindings. The ones for the [crate::editor] and [crate::dialog] components are always
+//!    there, and apps register their own.
+//! 2. The user can rebind them in a keymap file, see [global_keymap_config].
+//! 3. A component can override them in code, see [Keymap::set_bindings].
+//!
+//! # Keymap file format
+//!
+//! The keymap file is `keymap.json` in the same folder as the Markdown theme file (see
+//! [KeymapConfig::try_get_config_file_path]). It only has the actions whose bindings are
+//! changed, and an empty list unbinds an action:
+//!
+//! ```json
+//! {
+//!   "bindings": {
+//!     "editor.undo": ["Ctrl+Z", "Ctrl+K Ctrl+Z"],
+//!     "editor.select_all": []
+//!   },
+//!   "components": {
+//!     "dialog": { "dialog.accept": ["Enter", "Alt+Enter"] }
+//!   }
+//! }
+//! ```
+//!
+//! - `bindings` apply to every component, and `components` only apply to the [Keymap]s w/
+//!   that [component_name](Keymap::component_name).
+//! - See [KeySequence] for how to write key sequences. A sequence w/ more than one key
+//!   press is a chord, eg: `Ctrl+K Ctrl+Z`.
+//! - The action ids are listed by [global_action_registry::get_all].
+
+// Attach.
+pub mod key_sequence;
+pub mod keymap_action;
+pub mod keymap_config;
+pub mod keymap_struct;
+
+// Re-export.
+pub use key_sequence::*;
+pub use keymap_action::*;
+pub use keymap_config::*;
+pub use keymap_struct::*;
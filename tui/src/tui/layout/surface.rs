@@ -173,12 +173,17 @@ impl PerformPositioningAndSizing for Surface {
           LayoutErrorType::ErrorCalculatingNextBoxPos
         };
 
-        let new_pos: Position = current_insertion_pos + allocated_size;
-
-        // Adjust `new_pos` using Direction.
+        // Move `current_insertion_pos` past the allocated size, in the Direction of the
+        // layout only (the other axis stays where the container is).
         let new_pos: Position = match current_box.dir {
-            LayoutDirection::Vertical => new_pos * (0, 1),
-            LayoutDirection::Horizontal => new_pos * (1, 0),
+            LayoutDirection::Vertical => position!(
+                col_index: current_insertion_pos.col_index,
+                row_index: current_insertion_pos.row_index + allocated_size.row_count
+            ),
+            LayoutDirection::Horizontal => position!(
+                col_index: current_insertion_pos.col_index + allocated_size.col_count,
+                row_index: current_insertion_pos.row_index
+            ),
         };
 
         // Update the box_cursor_pos of the current layout.
@@ -186,6 +186,46 @@ mod tests {
         }
     }
 
+    /// The columns of a horizontal container start in the row that it starts in, even if
+    /// that isn't the top row (eg: when there is a bar above it).
+    #[test]
+    fn test_surface_2_col_not_at_top() -> CommonResult<()> {
+        throws!({
+            let mut surface = Surface::default();
+
+            surface.surface_start(SurfaceProps {
+                pos: position!(col_index: 0, row_index: 1),
+                size: size!(col_count:100, row_count:20),
+            })?;
+
+            box_start! {
+              in:                     surface,
+              id:                     FlexBoxId::from(0),
+              dir:                    LayoutDirection::Horizontal,
+              requested_size_percent: requested_size_percent!(width:100, height:100),
+              styles:                 []
+            }
+            for (id, width_pc, col_index) in [(1, 25, 0), (2, 75, 25)] {
+                box_start! {
+                  in:                     surface,
+                  id:                     FlexBoxId::from(id),
+                  dir:                    LayoutDirection::Vertical,
+                  requested_size_percent: requested_size_percent!(width:width_pc, height:100),
+                  styles:                 []
+                }
+                let layout_item = surface.stack_of_boxes.last().unwrap();
+                assert_eq2!(
+                    layout_item.origin_pos,
+                    position!(col_index: col_index, row_index: 1)
+                );
+                box_end!(in: surface);
+            }
+            box_end!(in: surface);
+
+            surface.surface_end()?;
+        });
+    }
+
     /// Create a stylesheet containing styles using DSL.
     fn dsl_stylesheet() -> CommonResult<TuiStylesheet> {
         throws_with_return!({
@@ -0,0 +1,94 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{bytes::complete::*, combinator::*, sequence::*, IResult};
+
+use crate::*;
+
+/// Sample inputs: `"[^1]: This is a *footnote*.\n"`, `"[^note]: text"`.
+///
+/// The text of the footnote is a single line, which is parsed into fragments.
+#[rustfmt::skip]
+pub fn parse_block_footnote_definition(
+    input: &str,
+) -> IResult<&str, FootnoteDefinitionData<'_>> {
+    let (remainder, (id, text)) = tuple((
+        /* id, eg: `[^1]: ` */
+        delimited(
+            tag(LEFT_FOOTNOTE),
+            verify(take_till1(|it: char| it == RIGHT_BRACKET_CHAR || it == NEW_LINE_CHAR), is_valid_footnote_id),
+            tag(DEFINITION_SEPARATOR),
+        ),
+        /* text until the end of the line */
+        terminated(
+            take_till(|it: char| it == NEW_LINE_CHAR),
+            /* ends with (discarded) */ opt(tag(NEW_LINE)),
+        ),
+    ))(input)?;
+
+    let (_, fragments) =
+        parse_block_markdown_text_with_checkbox_policy_with_or_without_new_line(
+            text,
+            CheckboxParsePolicy::IgnoreCheckbox,
+        )?;
+
+    Ok((remainder, FootnoteDefinitionData { id, fragments }))
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_block_footnote_definition() {
+        assert_eq2!(
+            parse_block_footnote_definition("[^1]: This is *bold*.\nfoo"),
+            Ok((
+                "foo",
+                FootnoteDefinitionData {
+                    id: "1",
+                    fragments: list![
+                        MdLineFragment::Plain("This is "),
+                        MdLineFragment::Bold("bold"),
+                        MdLineFragment::Plain("."),
+                    ],
+                }
+            ))
+        );
+        assert_eq2!(
+            parse_block_footnote_definition("[^note]: "),
+            Ok((
+                "",
+                FootnoteDefinitionData {
+                    id: "note",
+                    fragments: list![],
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_block_footnote_definition_invalid() {
+        assert!(parse_block_footnote_definition("[^1] text\n").is_err());
+        assert!(parse_block_footnote_definition("[^]: text\n").is_err());
+        assert!(parse_block_footnote_definition("[^a b]: text\n").is_err());
+        assert!(parse_block_footnote_definition("[1]: https://r3bl.com\n").is_err());
+    }
+}
@@ -0,0 +1,80 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{bytes::complete::*, combinator::*, sequence::*, IResult};
+
+use crate::*;
+
+/// Sample inputs: "---\n", "***\n", "___\n", "- - -\n", "-----".
+///
+/// A line w/ at least 3 of the same char in [HORIZONTAL_RULE_CHARS], which may have
+/// spaces between them. The output is the entire line.
+#[rustfmt::skip]
+pub fn parse_block_horizontal_rule(input: &str) -> IResult<&str, &str> {
+    terminated(
+        verify(take_till(|it: char| it == NEW_LINE_CHAR), is_horizontal_rule),
+        /* ends with (discarded) */ opt(tag(NEW_LINE)),
+    )(input)
+}
+
+fn is_horizontal_rule(line: &str) -> bool {
+    let Some(rule_char) = line.chars().next() else {
+        return false;
+    };
+    if !HORIZONTAL_RULE_CHARS.contains(&rule_char) {
+        return false;
+    }
+    let mut count = 0;
+    for it in line.chars() {
+        match it {
+            SPACE_CHAR => {}
+            _ if it == rule_char => count += 1,
+            _ => return false,
+        }
+    }
+    count >= HORIZONTAL_RULE_MIN_CHAR_COUNT
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_block_horizontal_rule() {
+        assert_eq2!(parse_block_horizontal_rule("---\n"), Ok(("", "---")));
+        assert_eq2!(parse_block_horizontal_rule("***"), Ok(("", "***")));
+        assert_eq2!(parse_block_horizontal_rule("___\nfoo"), Ok(("foo", "___")));
+        assert_eq2!(parse_block_horizontal_rule("- - - \n"), Ok(("", "- - - ")));
+        assert_eq2!(
+            parse_block_horizontal_rule("--------"),
+            Ok(("", "--------"))
+        );
+    }
+
+    #[test]
+    fn test_parse_block_horizontal_rule_invalid() {
+        assert!(parse_block_horizontal_rule("--\n").is_err());
+        assert!(parse_block_horizontal_rule("-*-\n").is_err());
+        assert!(parse_block_horizontal_rule("--- foo\n").is_err());
+        assert!(parse_block_horizontal_rule(" ---\n").is_err());
+        assert!(parse_block_horizontal_rule("***bold***\n").is_err());
+        assert!(parse_block_horizontal_rule("").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{bytes::complete::*, combinator::*, sequence::*, IResult};
+
+use crate::*;
+
+/// Sample inputs: `"[r3bl]: https://r3bl.com\n"`, `"[R3BL site]: https://r3bl.com"`.
+///
+/// The line must be just the definition, w/ nothing after the url. Otherwise it is
+/// parsed as text. Footnote definitions (eg: "[^1]: text") are parsed by
+/// [parse_block_footnote_definition()].
+#[rustfmt::skip]
+pub fn parse_block_link_definition(input: &str) -> IResult<&str, LinkDefinitionData<'_>> {
+    let (remainder, (id, url)) = tuple((
+        /* id, eg: `[r3bl]: ` */
+        delimited(
+            tag(LEFT_BRACKET),
+            verify(take_till1(|it: char| it == RIGHT_BRACKET_CHAR || it == NEW_LINE_CHAR), is_valid_link_definition_id),
+            tag(DEFINITION_SEPARATOR),
+        ),
+        /* url until the end of the line */
+        terminated(
+            verify(take_till1(|it: char| it == NEW_LINE_CHAR), |it: &str| !it.contains(char::is_whitespace)),
+            /* ends with (discarded) */ opt(tag(NEW_LINE)),
+        ),
+    ))(input)?;
+
+    Ok((remainder, LinkDefinitionData { id, url }))
+}
+
+fn is_valid_link_definition_id(id: &str) -> bool {
+    !id.starts_with(FOOTNOTE_CARET) && !id.trim().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_block_link_definition() {
+        assert_eq2!(
+            parse_block_link_definition("[r3bl]: https://r3bl.com\nfoo"),
+            Ok((
+                "foo",
+                LinkDefinitionData {
+                    id: "r3bl",
+                    url: "https://r3bl.com"
+                }
+            ))
+        );
+        assert_eq2!(
+            parse_block_link_definition("[R3BL site]: https://r3bl.com"),
+            Ok((
+                "",
+                LinkDefinitionData {
+                    id: "R3BL site",
+                    url: "https://r3bl.com"
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_block_link_definition_invalid() {
+        assert!(parse_block_link_definition("[r3bl]: https://r3bl.com foo\n").is_err());
+        assert!(parse_block_link_definition("[r3bl]:https://r3bl.com\n").is_err());
+        assert!(parse_block_link_definition("[r3bl]: \n").is_err());
+        assert!(parse_block_link_definition("[]: https://r3bl.com\n").is_err());
+        assert!(parse_block_link_definition("[^1]: footnote\n").is_err());
+        assert!(parse_block_link_definition("[r3bl](https://r3bl.com)\n").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{bytes::complete::*,
+          character::complete::*,
+          combinator::*,
+          multi::*,
+          sequence::*,
+          IResult};
+
+use crate::*;
+
+/// Sample inputs:
+/// One line:    "> quote\n"
+/// Multi line:  "> quote\n> more quote\n"
+/// Nested:      "> quote\n> > nested quote\n>> also nested\n"
+/// Empty line:  "> quote\n>\n> more quote\n"
+///
+/// Each line has to start w/ `>`, the quote ends at the first line that doesn't.
+pub fn parse_block_quote(input: &str) -> IResult<&str, BlockQuoteLines<'_>> {
+    let (remainder, lines) = many1(parse_block_quote_line)(input)?;
+    Ok((remainder, List::from(lines)))
+}
+
+#[rustfmt::skip]
+fn parse_block_quote_line(input: &str) -> IResult<&str, BlockQuoteLine<'_>> {
+    let (remainder, (prefix, text)) = tuple((
+        /* prefix, eg: `> > ` */
+        recognize(many1(terminated(char(BLOCK_QUOTE_CHAR), opt(char(SPACE_CHAR))))),
+        /* text until the end of the line */
+        terminated(
+            take_till(|it: char| it == NEW_LINE_CHAR),
+            /* ends with (discarded) */ opt(tag(NEW_LINE)),
+        ),
+    ))(input)?;
+
+    let (_, fragments) =
+        parse_block_markdown_text_with_checkbox_policy_with_or_without_new_line(
+            text,
+            CheckboxParsePolicy::IgnoreCheckbox,
+        )?;
+
+    let depth = prefix.matches(BLOCK_QUOTE_CHAR).count();
+
+    Ok((
+        remainder,
+        BlockQuoteLine {
+            depth,
+            prefix,
+            fragments,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_block_quote_one_line() {
+        let (remainder, lines) = parse_block_quote("> foo *bar*\n").unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            lines,
+            list![BlockQuoteLine {
+                depth: 1,
+                prefix: "> ",
+                fragments: list![
+                    MdLineFragment::Plain("foo "),
+                    MdLineFragment::Bold("bar")
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_block_quote_nested() {
+        let input = ["> foo", "> > bar", ">>baz", ">", "after"].join("\n");
+        let (remainder, lines) = parse_block_quote(&input).unwrap();
+        assert_eq2!(remainder, "after");
+        assert_eq2!(
+            lines,
+            list![
+                BlockQuoteLine {
+                    depth: 1,
+                    prefix: "> ",
+                    fragments: list![MdLineFragment::Plain("foo")],
+                },
+                BlockQuoteLine {
+                    depth: 2,
+                    prefix: "> > ",
+                    fragments: list![MdLineFragment::Plain("bar")],
+                },
+                BlockQuoteLine {
+                    depth: 2,
+                    prefix: ">>",
+                    fragments: list![MdLineFragment::Plain("baz")],
+                },
+                BlockQuoteLine {
+                    depth: 1,
+                    prefix: ">",
+                    fragments: list![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_block_quote_invalid() {
+        assert!(parse_block_quote("foo > bar\n").is_err());
+        assert!(parse_block_quote(" > foo\n").is_err());
+        assert!(parse_block_quote("").is_err());
+    }
+}
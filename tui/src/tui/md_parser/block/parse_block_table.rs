@@ -0,0 +1,254 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use constants::*;
+use nom::{bytes::complete::*,
+          combinator::*,
+          error::{Error, ErrorKind},
+          multi::*,
+          sequence::*,
+          IResult};
+
+use crate::*;
+
+/// Sample input:
+///
+/// ```text
+/// | Name | Count |
+/// |:-----|------:|
+/// | foo  |     1 |
+/// | bar  |     2 |
+/// ```
+///
+/// 1. The first line is the header row.
+/// 2. The second line is the alignment row, which must have the same number of cells as
+///    the header row. Otherwise this isn't a table.
+/// 3. The rest of the lines are body rows, the table ends at the first line that isn't a
+///    row.
+///
+/// Each row has to start and end w/ `|`. A `|` in the text of a cell has to be escaped
+/// (`\|`).
+pub fn parse_block_table(input: &str) -> IResult<&str, TableData<'_>> {
+    let (remainder, header_cells) = parse_table_row_line(input)?;
+    let (remainder, alignment_cells) = parse_table_row_line(remainder)?;
+
+    // The alignment row must match the header row.
+    if alignment_cells.len() != header_cells.len() {
+        return Err(nom::Err::Error(Error::new(remainder, ErrorKind::Verify)));
+    }
+    let mut alignment_row = List::with_capacity(alignment_cells.len());
+    for cell in alignment_cells {
+        let Some(alignment) = parse_table_column_alignment(cell) else {
+            return Err(nom::Err::Error(Error::new(remainder, ErrorKind::Verify)));
+        };
+        alignment_row.push((alignment, cell));
+    }
+
+    let (remainder, body_cells) = many0(parse_table_row_line)(remainder)?;
+
+    let header_row = convert_cells_into_table_row(header_cells)?;
+    let mut body_rows = List::with_capacity(body_cells.len());
+    for cells in body_cells {
+        body_rows.push(convert_cells_into_table_row(cells)?);
+    }
+
+    Ok((
+        remainder,
+        TableData {
+            header_row,
+            alignment_row,
+            body_rows,
+        },
+    ))
+}
+
+/// Parse a line that starts and ends w/ `|`, and split it into the text of each cell.
+#[rustfmt::skip]
+fn parse_table_row_line(input: &str) -> IResult<&str, Vec<&str>> {
+    map(
+        terminated(
+            verify(
+                take_till(|it: char| it == NEW_LINE_CHAR),
+                |line: &str| {
+                    line.len() > 1
+                        && line.starts_with(TABLE_PIPE)
+                        && line.ends_with(TABLE_PIPE)
+                        && !line.ends_with(&format!("{BACKSLASH_CHAR}{TABLE_PIPE}"))
+                },
+            ),
+            /* ends with (discarded) */ opt(tag(NEW_LINE)),
+        ),
+        split_table_row_into_cells,
+    )(input)
+}
+
+/// Split a row (eg: `| foo | bar \| baz |`) into the text of each cell (eg: `[" foo ",
+/// " bar \| baz "]`). The row must start and end w/ `|`.
+pub fn split_table_row_into_cells(line: &str) -> Vec<&str> {
+    let inner = &line[TABLE_PIPE.len()..line.len() - TABLE_PIPE.len()];
+    let mut acc = vec![];
+    let mut cell_start = 0;
+    let mut is_escaped = false;
+    for (index, ch) in inner.char_indices() {
+        if ch == TABLE_PIPE_CHAR && !is_escaped {
+            acc.push(&inner[cell_start..index]);
+            cell_start = index + TABLE_PIPE.len();
+        }
+        is_escaped = ch == BACKSLASH_CHAR && !is_escaped;
+    }
+    acc.push(&inner[cell_start..]);
+    acc
+}
+
+/// Sample inputs: `---`, `:--`, ` :-: `, `--:`.
+pub fn parse_table_column_alignment(cell: &str) -> Option<TableColumnAlignment> {
+    let cell = cell.trim_matches(SPACE_CHAR);
+    let starts_with_colon = cell.starts_with(TABLE_ALIGNMENT_COLON_CHAR);
+    let ends_with_colon = cell.len() > 1 && cell.ends_with(TABLE_ALIGNMENT_COLON_CHAR);
+    let dashes = cell.trim_matches(TABLE_ALIGNMENT_COLON_CHAR);
+    if dashes.len() + starts_with_colon as usize + ends_with_colon as usize != cell.len()
+        || dashes.is_empty()
+        || !dashes.chars().all(|it| it == TABLE_ALIGNMENT_DASH_CHAR)
+    {
+        return None;
+    }
+    Some(match (starts_with_colon, ends_with_colon) {
+        (true, true) => TableColumnAlignment::Center,
+        (true, false) => TableColumnAlignment::Left,
+        (false, true) => TableColumnAlignment::Right,
+        (false, false) => TableColumnAlignment::None,
+    })
+}
+
+fn convert_cells_into_table_row(
+    cells: Vec<&str>,
+) -> Result<TableRow<'_>, nom::Err<Error<&str>>> {
+    let mut acc = List::with_capacity(cells.len());
+    for cell in cells {
+        let (_, fragments) =
+            parse_block_markdown_text_with_checkbox_policy_with_or_without_new_line(
+                cell,
+                CheckboxParsePolicy::IgnoreCheckbox,
+            )?;
+        acc.push(fragments);
+    }
+    Ok(acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_split_table_row_into_cells() {
+        assert_eq2!(split_table_row_into_cells("| a | b |"), vec![" a ", " b "]);
+        assert_eq2!(split_table_row_into_cells("||"), vec![""]);
+        assert_eq2!(split_table_row_into_cells("|a||b|"), vec!["a", "", "b"]);
+        assert_eq2!(
+            split_table_row_into_cells(r"| a \| b | c |"),
+            vec![r" a \| b ", " c "]
+        );
+        assert_eq2!(
+            split_table_row_into_cells(r"| a \\| b |"),
+            vec![r" a \\", " b "]
+        );
+    }
+
+    #[test]
+    fn test_parse_table_column_alignment() {
+        assert_eq2!(
+            parse_table_column_alignment("---"),
+            Some(TableColumnAlignment::None)
+        );
+        assert_eq2!(
+            parse_table_column_alignment(" :-- "),
+            Some(TableColumnAlignment::Left)
+        );
+        assert_eq2!(
+            parse_table_column_alignment(":-:"),
+            Some(TableColumnAlignment::Center)
+        );
+        assert_eq2!(
+            parse_table_column_alignment("--:"),
+            Some(TableColumnAlignment::Right)
+        );
+        assert_eq2!(parse_table_column_alignment(":"), None);
+        assert_eq2!(parse_table_column_alignment("::"), None);
+        assert_eq2!(parse_table_column_alignment("-a-"), None);
+        assert_eq2!(parse_table_column_alignment(""), None);
+        assert_eq2!(parse_table_column_alignment(":--::"), None);
+    }
+
+    #[test]
+    fn test_parse_block_table() {
+        let input = [
+            "| Name | *Count* |",
+            "|:-----|------:|",
+            "| foo  | 1 |",
+            "|| `2` |",
+            "after",
+        ]
+        .join("\n");
+        let (remainder, table) = parse_block_table(&input).unwrap();
+        assert_eq2!(remainder, "after");
+        assert_eq2!(
+            table,
+            TableData {
+                header_row: list![
+                    list![MdLineFragment::Plain(" Name ")],
+                    list![
+                        MdLineFragment::Plain(" "),
+                        MdLineFragment::Bold("Count"),
+                        MdLineFragment::Plain(" "),
+                    ],
+                ],
+                alignment_row: list![
+                    (TableColumnAlignment::Left, ":-----"),
+                    (TableColumnAlignment::Right, "------:"),
+                ],
+                body_rows: list![
+                    list![
+                        list![MdLineFragment::Plain(" foo  ")],
+                        list![MdLineFragment::Plain(" 1 ")],
+                    ],
+                    list![
+                        list![],
+                        list![
+                            MdLineFragment::Plain(" "),
+                            MdLineFragment::InlineCode("2"),
+                            MdLineFragment::Plain(" "),
+                        ],
+                    ],
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_block_table_invalid() {
+        // No alignment row.
+        assert!(parse_block_table("| a | b |\n| c | d |\n").is_err());
+        // The alignment row doesn't match the header row.
+        assert!(parse_block_table("| a | b |\n|---|\n").is_err());
+        // No trailing pipe.
+        assert!(parse_block_table("| a | b\n|---|---|\n").is_err());
+        // Only the header row.
+        assert!(parse_block_table("| a | b |").is_err());
+    }
+}
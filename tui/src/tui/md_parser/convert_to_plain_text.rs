@@ -17,10 +17,26 @@
 
 //! This module is responsible for converting all the [MdLineFragment] into plain text w/out any
 //! formatting.
+//!
+//! [PrettyPrintDebug] converts each block or fragment on its own, so reference links and
+//! footnote references are left as is. [convert_md_document_to_plain_text] resolves them
+//! w/ the definitions in the document (see [MdLinkResolver]).
 
 use super::*;
 use crate::{constants::*, *};
 
+/// Convert each block of `document` into plain text, and join them w/ new lines. Reference
+/// links are converted into links (eg: `[text](url)`), and footnote references into
+/// their number and text (eg: `[1: text]`), if they are defined in the document.
+pub fn convert_md_document_to_plain_text(document: &MdDocument<'_>) -> String {
+    let resolver = MdLinkResolver::new(document);
+    document
+        .iter()
+        .map(|block| block.to_plain_text(&resolver))
+        .collect::<Vec<String>>()
+        .join(NEW_LINE)
+}
+
 impl<'a> PrettyPrintDebug for MdDocument<'a> {
     fn pretty_print_debug(&self) -> String {
         let mut it = vec![];
@@ -33,9 +49,17 @@ impl<'a> PrettyPrintDebug for MdDocument<'a> {
 
 impl<'a> PrettyPrintDebug for List<MdLineFragment<'a>> {
     fn pretty_print_debug(&self) -> String {
+        self.to_plain_text(&MdLinkResolver::default())
+    }
+}
+
+impl<'a> List<MdLineFragment<'a>> {
+    /// Same as [PrettyPrintDebug::pretty_print_debug], except that the links are resolved
+    /// w/ `resolver`.
+    pub fn to_plain_text(&self, resolver: &MdLinkResolver<'_>) -> String {
         self.inner
             .iter()
-            .map(|fragment| fragment.pretty_print_debug())
+            .map(|fragment| fragment.to_plain_text(resolver))
             .collect::<Vec<String>>()
             .join("")
     }
@@ -43,6 +67,14 @@ impl<'a> PrettyPrintDebug for List<MdLineFragment<'a>> {
 
 impl<'a> PrettyPrintDebug for MdBlock<'a> {
     fn pretty_print_debug(&self) -> String {
+        self.to_plain_text(&MdLinkResolver::default())
+    }
+}
+
+impl<'a> MdBlock<'a> {
+    /// Same as [PrettyPrintDebug::pretty_print_debug], except that the links are resolved
+    /// w/ `resolver`.
+    pub fn to_plain_text(&self, resolver: &MdLinkResolver<'_>) -> String {
         match self {
             MdBlock::Heading(heading_data) => {
                 format!(
@@ -51,7 +83,7 @@ impl<'a> PrettyPrintDebug for MdBlock<'a> {
                     heading_data.text,
                 )
             }
-            MdBlock::Text(fragments) => fragments.pretty_print_debug(),
+            MdBlock::Text(fragments) => fragments.to_plain_text(resolver),
             MdBlock::CodeBlock(list_codeblock_line) => {
                 let line_count = list_codeblock_line.len();
                 let lang = {
@@ -66,13 +98,46 @@ impl<'a> PrettyPrintDebug for MdBlock<'a> {
             MdBlock::Tags(tags) => format!("tags: {}", tags.join(", ")),
             MdBlock::Date(date) => format!("title: {}", date),
             MdBlock::Authors(authors) => format!("tags: {}", authors.join(", ")),
+            MdBlock::HorizontalRule(rule) => rule.to_string(),
+            MdBlock::BlockQuote(block_quote_lines) => block_quote_lines
+                .iter()
+                .map(|line| {
+                    format!("{}{}", line.prefix, line.fragments.to_plain_text(resolver))
+                })
+                .collect::<Vec<String>>()
+                .join(NEW_LINE),
+            MdBlock::Table(table_data) => {
+                let mut acc = vec![generate_table_row_with_resolver(
+                    &table_data.header_row,
+                    resolver,
+                )];
+                acc.push(generate_table_row_from_cells(
+                    table_data
+                        .alignment_row
+                        .iter()
+                        .map(|(_, cell)| cell.to_string()),
+                ));
+                for row in table_data.body_rows.iter() {
+                    acc.push(generate_table_row_with_resolver(row, resolver));
+                }
+                acc.join(NEW_LINE)
+            }
+            MdBlock::LinkDefinition(LinkDefinitionData { id, url }) => {
+                format!("{LEFT_BRACKET}{id}{DEFINITION_SEPARATOR}{url}")
+            }
+            MdBlock::FootnoteDefinition(FootnoteDefinitionData { id, fragments }) => {
+                format!(
+                    "{LEFT_FOOTNOTE}{id}{DEFINITION_SEPARATOR}{}",
+                    fragments.to_plain_text(resolver)
+                )
+            }
             MdBlock::SmartList((list_lines, _bullet_kind, _indent)) => format!(
                 "[  {}  ]",
                 list_lines
                     .iter()
                     .map(|fragments_in_one_line| format!(
                         "┊{}┊",
-                        fragments_in_one_line.pretty_print_debug()
+                        fragments_in_one_line.to_plain_text(resolver)
                     ))
                     .collect::<Vec<String>>()
                     .join(" → ")
@@ -95,6 +160,14 @@ impl PrettyPrintDebug for HeadingLevel {
 
 impl PrettyPrintDebug for MdLineFragment<'_> {
     fn pretty_print_debug(&self) -> String {
+        self.to_plain_text(&MdLinkResolver::default())
+    }
+}
+
+impl MdLineFragment<'_> {
+    /// Same as [PrettyPrintDebug::pretty_print_debug], except that reference links and
+    /// footnote references that `resolver` can resolve contain their url or text.
+    pub fn to_plain_text(&self, resolver: &MdLinkResolver<'_>) -> String {
         let it: String = match self {
             MdLineFragment::Plain(text) => text.to_string(),
             MdLineFragment::Link(HyperlinkData { text, url }) => {
@@ -110,9 +183,40 @@ impl PrettyPrintDebug for MdLineFragment<'_> {
                     "{LEFT_IMAGE}{alt_text}{RIGHT_IMAGE}{LEFT_PARENTHESIS}{url}{RIGHT_PARENTHESIS}"
                 )
             }
+            MdLineFragment::ReferenceLink(ReferenceLinkData { text, id }) => {
+                match resolver.resolve(self) {
+                    Some(HyperlinkData { text, url }) => format!(
+                        "{LEFT_BRACKET}{text}{RIGHT_BRACKET}{LEFT_PARENTHESIS}{url}{RIGHT_PARENTHESIS}"
+                    ),
+                    None => format!(
+                        "{LEFT_BRACKET}{text}{RIGHT_BRACKET}{LEFT_BRACKET}{id}{RIGHT_BRACKET}"
+                    ),
+                }
+            }
+            MdLineFragment::AutoLink(AutoLinkData { url, is_bracketed }) => {
+                if *is_bracketed {
+                    format!("{LEFT_ANGLE_BRACKET}{url}{RIGHT_ANGLE_BRACKET}")
+                } else {
+                    url.to_string()
+                }
+            }
+            MdLineFragment::FootnoteReference(id) => match (
+                resolver.get_footnote_number(id),
+                resolver.get_footnote_text(id),
+            ) {
+                // The text of the footnote isn't resolved, since it could refer to itself.
+                (Some(number), Some(fragments)) => format!(
+                    "{LEFT_BRACKET}{number}{COLON}{SPACE}{}{RIGHT_BRACKET}",
+                    fragments.pretty_print_debug()
+                ),
+                _ => format!("{LEFT_FOOTNOTE}{id}{RIGHT_BRACKET}"),
+            },
             MdLineFragment::Bold(text) => format!("{STAR}{text}{STAR}"),
             MdLineFragment::Italic(text) => format!("{UNDERSCORE}{text}{UNDERSCORE}"),
             MdLineFragment::InlineCode(text) => format!("{BACK_TICK}{text}{BACK_TICK}"),
+            MdLineFragment::Strikethrough(text) => {
+                format!("{TILDE_TILDE}{text}{TILDE_TILDE}")
+            }
             MdLineFragment::Checkbox(is_checked) => {
                 (if *is_checked { CHECKED } else { UNCHECKED }).to_string()
             }
@@ -130,6 +234,27 @@ impl PrettyPrintDebug for MdLineFragment<'_> {
     }
 }
 
+/// Generate a row of a table, eg: `| foo | bar |`.
+pub fn generate_table_row(row: &TableRow<'_>) -> String {
+    generate_table_row_with_resolver(row, &MdLinkResolver::default())
+}
+
+fn generate_table_row_with_resolver(
+    row: &TableRow<'_>,
+    resolver: &MdLinkResolver<'_>,
+) -> String {
+    generate_table_row_from_cells(row.iter().map(|cell| cell.to_plain_text(resolver)))
+}
+
+fn generate_table_row_from_cells(cells: impl Iterator<Item = String>) -> String {
+    let mut acc = TABLE_PIPE.to_string();
+    for cell in cells {
+        acc.push_str(&cell);
+        acc.push_str(TABLE_PIPE);
+    }
+    acc
+}
+
 pub fn generate_ordered_list_item_bullet(
     indent: &usize,
     number: &usize,
@@ -206,10 +331,61 @@ mod to_plain_text_tests {
             MdLineFragment::InlineCode("Hello World").pretty_print_debug(),
             "`Hello World`"
         );
+        assert_eq2!(
+            MdLineFragment::Strikethrough("Hello World").pretty_print_debug(),
+            "~~Hello World~~"
+        );
         assert_eq2!(MdLineFragment::Checkbox(true).pretty_print_debug(), "[x]");
         assert_eq2!(MdLineFragment::Checkbox(false).pretty_print_debug(), "[ ]");
     }
 
+    #[test]
+    fn test_block_to_plain_text() {
+        let input = [
+            "> quote",
+            "> > nested *quote*",
+            "---",
+            "| a | ~~b~~ |",
+            "|:--|---:|",
+            "|| c |",
+        ];
+        let text = input.join("\n");
+        let (remainder, blocks) = parse_markdown(&text).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 3);
+        assert_eq2!(blocks[0].pretty_print_debug(), input[0..2].join("\n"));
+        assert_eq2!(blocks[1].pretty_print_debug(), input[2]);
+        assert_eq2!(blocks[2].pretty_print_debug(), input[3..].join("\n"));
+    }
+
+    #[test]
+    fn test_document_to_plain_text() {
+        let input = [
+            "[R3BL][site] and [Missing][nope][^1] [^2]",
+            "| [Site][] |",
+            "|---|",
+            "[site]: https://r3bl.com",
+            "[^1]: The *footnote*.",
+        ];
+        let text = input.join("\n");
+        let (remainder, document) = parse_markdown(&text).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            convert_md_document_to_plain_text(&document),
+            [
+                "[R3BL](https://r3bl.com) and [Missing][nope][1: The *footnote*.] [^2]",
+                "| [Site](https://r3bl.com) |",
+                "|---|",
+                "[site]: https://r3bl.com",
+                "[^1]: The *footnote*.",
+            ]
+            .join("\n")
+        );
+
+        // W/out a resolver, the references are left as is.
+        assert_eq2!(document[0].pretty_print_debug(), input[0]);
+    }
+
     #[test]
     fn test_level_to_plain_text() {
         assert_eq2!(HeadingLevel { level: 1 }.pretty_print_debug(), "# ");
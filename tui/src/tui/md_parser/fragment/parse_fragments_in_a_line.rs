@@ -63,18 +63,28 @@ pub fn parse_inline_fragments_until_eol_or_eoi(
         CheckboxParsePolicy::IgnoreCheckbox => alt((
             map(parse_fragment_starts_with_underscore_err_on_new_line,  MdLineFragment::Italic),
             map(parse_fragment_starts_with_star_err_on_new_line,        MdLineFragment::Bold),
+            map(parse_fragment_starts_with_tilde_tilde_err_on_new_line, MdLineFragment::Strikethrough),
             map(parse_fragment_starts_with_backtick_err_on_new_line,    MdLineFragment::InlineCode),
             map(parse_fragment_starts_with_left_image_err_on_new_line,  MdLineFragment::Image),
+            map(parse_fragment_starts_with_left_footnote_err_on_new_line, MdLineFragment::FootnoteReference),
             map(parse_fragment_starts_with_left_link_err_on_new_line,   MdLineFragment::Link),
+            map(parse_fragment_starts_with_left_reference_link_err_on_new_line, MdLineFragment::ReferenceLink),
+            map(parse_fragment_starts_with_left_angle_bracket_err_on_new_line, |it| MdLineFragment::AutoLink(AutoLinkData::new(it, true))),
+            map(parse_fragment_starts_with_http_err_on_new_line,        |it| MdLineFragment::AutoLink(AutoLinkData::new(it, false))),
             map(parse_fragment_starts_with_checkbox_into_str,           MdLineFragment::Plain), // This line is different.
             map(parse_fragment_plain_text_no_new_line,                  MdLineFragment::Plain),
         ))(input),
         CheckboxParsePolicy::ParseCheckbox => alt((
             map(parse_fragment_starts_with_underscore_err_on_new_line,  MdLineFragment::Italic),
             map(parse_fragment_starts_with_star_err_on_new_line,        MdLineFragment::Bold),
+            map(parse_fragment_starts_with_tilde_tilde_err_on_new_line, MdLineFragment::Strikethrough),
             map(parse_fragment_starts_with_backtick_err_on_new_line,    MdLineFragment::InlineCode),
             map(parse_fragment_starts_with_left_image_err_on_new_line,  MdLineFragment::Image),
+            map(parse_fragment_starts_with_left_footnote_err_on_new_line, MdLineFragment::FootnoteReference),
             map(parse_fragment_starts_with_left_link_err_on_new_line,   MdLineFragment::Link),
+            map(parse_fragment_starts_with_left_reference_link_err_on_new_line, MdLineFragment::ReferenceLink),
+            map(parse_fragment_starts_with_left_angle_bracket_err_on_new_line, |it| MdLineFragment::AutoLink(AutoLinkData::new(it, true))),
+            map(parse_fragment_starts_with_http_err_on_new_line,        |it| MdLineFragment::AutoLink(AutoLinkData::new(it, false))),
             map(parse_fragment_starts_with_checkbox_checkbox_into_bool, MdLineFragment::Checkbox), // This line is different.
             map(parse_fragment_plain_text_no_new_line,                  MdLineFragment::Plain),
         ))(input)
@@ -357,6 +367,127 @@ mod tests_parse_fragment {
         );
     }
 
+    #[test]
+    fn test_parse_fragment_footnote_reference() {
+        assert_eq2!(
+            parse_fragment_starts_with_left_footnote_err_on_new_line("[^1] foo"),
+            Ok((/*rem*/ " foo", /*output*/ "1"))
+        );
+        assert!(
+            parse_fragment_starts_with_left_footnote_err_on_new_line("[^] foo").is_err()
+        );
+        assert!(
+            parse_fragment_starts_with_left_footnote_err_on_new_line("[^a b]").is_err()
+        );
+        assert!(parse_fragment_starts_with_left_footnote_err_on_new_line("[1]").is_err());
+    }
+
+    #[test]
+    fn test_parse_fragment_reference_link() {
+        assert_eq2!(
+            parse_fragment_starts_with_left_reference_link_err_on_new_line(
+                "[R3BL][site] foo"
+            ),
+            Ok((
+                /*rem*/ " foo",
+                /*output*/ ReferenceLinkData::new("R3BL", "site")
+            ))
+        );
+        assert_eq2!(
+            parse_fragment_starts_with_left_reference_link_err_on_new_line("[R3BL][]")
+                .map(|(_, it)| it.get_id()),
+            Ok("R3BL")
+        );
+        assert!(
+            parse_fragment_starts_with_left_reference_link_err_on_new_line(
+                "[R3BL] [site]"
+            )
+            .is_err()
+        );
+        assert!(
+            parse_fragment_starts_with_left_reference_link_err_on_new_line("[R3BL](url)")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_fragment_auto_link() {
+        assert_eq2!(
+            parse_fragment_starts_with_left_angle_bracket_err_on_new_line(
+                "<https://r3bl.com> foo"
+            ),
+            Ok((/*rem*/ " foo", /*output*/ "https://r3bl.com"))
+        );
+        assert_eq2!(
+            parse_fragment_starts_with_left_angle_bracket_err_on_new_line(
+                "<mailto:foo@r3bl.com>"
+            ),
+            Ok(("", "mailto:foo@r3bl.com"))
+        );
+        assert!(
+            parse_fragment_starts_with_left_angle_bracket_err_on_new_line("<r3bl.com>")
+                .is_err()
+        );
+        assert!(
+            parse_fragment_starts_with_left_angle_bracket_err_on_new_line(
+                "<https://r3bl.com foo>"
+            )
+            .is_err()
+        );
+        assert!(
+            parse_fragment_starts_with_left_angle_bracket_err_on_new_line("< b >")
+                .is_err()
+        );
+
+        assert_eq2!(
+            parse_fragment_starts_with_http_err_on_new_line("https://r3bl.com foo"),
+            Ok((/*rem*/ " foo", /*output*/ "https://r3bl.com"))
+        );
+        assert_eq2!(
+            parse_fragment_starts_with_http_err_on_new_line("http://r3bl.com/a?b=c."),
+            Ok((".", "http://r3bl.com/a?b=c"))
+        );
+        assert_eq2!(
+            parse_fragment_starts_with_http_err_on_new_line("https://r3bl.com/a).\nfoo"),
+            Ok((").\nfoo", "https://r3bl.com/a"))
+        );
+        assert_eq2!(
+            parse_fragment_starts_with_http_err_on_new_line(
+                "https://en.wikipedia.org/wiki/Rust_(language)"
+            ),
+            Ok(("", "https://en.wikipedia.org/wiki/Rust_(language)"))
+        );
+        assert_eq2!(
+            parse_fragment_starts_with_http_err_on_new_line("https://r3bl.com[^1]"),
+            Ok(("[^1]", "https://r3bl.com"))
+        );
+        assert!(parse_fragment_starts_with_http_err_on_new_line("https://").is_err());
+        assert!(parse_fragment_starts_with_http_err_on_new_line("https://.").is_err());
+        assert!(parse_fragment_starts_with_http_err_on_new_line("r3bl.com").is_err());
+    }
+
+    #[test]
+    fn test_parse_fragment_plaintext_auto_link_prefix() {
+        assert_eq2!(
+            parse_fragment_plain_text_no_new_line("see https://r3bl.com"),
+            Ok((/*rem*/ "https://r3bl.com", /*output*/ "see "))
+        );
+        assert_eq2!(
+            parse_fragment_plain_text_no_new_line("a < b"),
+            Ok(("< b", "a "))
+        );
+        // Not an auto link, so only the prefix is plain text, and the rest of the line is
+        // parsed again.
+        assert_eq2!(
+            parse_fragment_plain_text_no_new_line("< b *c*"),
+            Ok((" b *c*", "<"))
+        );
+        assert_eq2!(
+            parse_fragment_plain_text_no_new_line("https:// *c*"),
+            Ok((" *c*", "https://"))
+        );
+    }
+
     #[test]
     fn test_parse_fragment_plaintext_unicode() {
         let result = parse_fragment_plain_text_no_new_line("- straight😃\n");
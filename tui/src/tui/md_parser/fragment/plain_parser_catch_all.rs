@@ -88,14 +88,15 @@ pub fn parse_fragment_plain_text_no_new_line(input: &str) -> IResult<&str, &str>
         // special case above will be triggered.
 
         // `tag_tuple` replaces the following:
-        // `( tag(UNDERSCORE), tag(STAR), tag(BACK_TICK), tag(LEFT_IMAGE), tag(LEFT_BRACKET), tag(NEW_LINE) )`
+        // `( tag(UNDERSCORE), tag(STAR), tag(BACK_TICK), tag(TILDE_TILDE), tag(LEFT_IMAGE), tag(LEFT_BRACKET),
+        //    tag(LEFT_ANGLE_BRACKET), tag(HTTPS_PREFIX), tag(HTTP_PREFIX), tag(NEW_LINE) )`
         let tag_vec = get_sp_char_set_3()
             .into_iter()
             .map(tag::<&str, &str, nom::error::Error<&str>>)
             .collect::<Vec<_>>();
         let tag_tuple = {
-            assert_eq!(tag_vec.len(), 6);
-            tuple6(&tag_vec)
+            assert_eq!(tag_vec.len(), 10);
+            tuple10(&tag_vec)
         };
 
         let it = recognize(
@@ -129,8 +130,25 @@ pub fn parse_fragment_plain_text_no_new_line(input: &str) -> IResult<&str, &str>
     // If the input starts with any of these special characters, take till the first new
     // line. Since the specialized parsers did not match the input.
 
+    // # Edge case -> Auto link case:
+    // The input starts with `<`, `https://` or `http://` but it isn't an auto link, since
+    // the specialized parsers did not match it. Return just this prefix as plain text,
+    // and the remainder gets a chance to be parsed by the specialized parsers.
+    if let Some(special_str) = check_input_starts_with(input, &get_sp_char_set_4()) {
+        let (rem, output) = tag(special_str)(input)?;
+        call_if_true!(DEBUG_MD_PARSER_STDOUT, {
+            println!(
+                "{} edge case -> auto link case :: rem: {:?}, output: {:?}",
+                "▲▲".blue(),
+                rem,
+                output
+            );
+        });
+        return Ok((rem, output));
+    }
+
     // # Edge case -> Special case:
-    // Check for single UNDERSCORE, STAR, BACK_TICK. until the first new line. This is
+    // Check for single UNDERSCORE, STAR, BACK_TICK, TILDE_TILDE. until the first new line. This is
     // to handle the case with
     // [specialized_parser_delim_matchers::take_starts_with_delim_no_new_line()] where
     // there is no closing delim found.
@@ -181,7 +199,9 @@ pub fn parse_fragment_plain_text_no_new_line(input: &str) -> IResult<&str, &str>
 /// this parser's `Edge case -> Special case` will take care of it by splitting the input,
 /// and returning the first part as plain text, and the remainder as the input to be parsed
 /// by the specialized parsers.
-pub fn get_sp_char_set_1<'a>() -> [&'a str; 3] { [UNDERSCORE, STAR, BACK_TICK] }
+pub fn get_sp_char_set_1<'a>() -> [&'a str; 4] {
+    [UNDERSCORE, STAR, BACK_TICK, TILDE_TILDE]
+}
 
 /// This is a special set of chars called `set_2`. This is used to detect the `Edge case
 /// -> Normal case` where the input starts with any of these special characters, and the
@@ -189,10 +209,11 @@ pub fn get_sp_char_set_1<'a>() -> [&'a str; 3] { [UNDERSCORE, STAR, BACK_TICK] }
 /// following are true:
 /// 1. input is in [get_sp_char_set_1()] and,
 /// 2. count is 1.
-pub fn get_sp_char_set_2<'a>() -> [&'a str; 5] {
+pub fn get_sp_char_set_2<'a>() -> [&'a str; 9] {
     get_sp_char_set_1()
         .iter()
         .chain([LEFT_IMAGE, LEFT_BRACKET].iter())
+        .chain(get_sp_char_set_4().iter())
         .copied()
         .collect::<Vec<_>>()
         .try_into()
@@ -205,7 +226,7 @@ pub fn get_sp_char_set_2<'a>() -> [&'a str; 5] {
 /// there. This returns the chunk until the first special character as
 /// [MdLineFragment::Plain], and the remainder of the input gets a chance to be parsed by
 /// the specialized parsers.
-pub fn get_sp_char_set_3<'a>() -> [&'a str; 6] {
+pub fn get_sp_char_set_3<'a>() -> [&'a str; 10] {
     get_sp_char_set_2()
         .iter()
         .chain([NEW_LINE].iter())
@@ -215,6 +236,15 @@ pub fn get_sp_char_set_3<'a>() -> [&'a str; 6] {
         .unwrap()
 }
 
+/// This is a special set of chars called `set_4`. These are the prefixes of auto links,
+/// eg: `<https://r3bl.com>` or `https://r3bl.com`. If the input starts with any of them
+/// and it isn't an auto link, then this parser's `Edge case -> Auto link case` returns
+/// just the prefix as plain text, so that the rest of the line is still parsed by the
+/// specialized parsers.
+pub fn get_sp_char_set_4<'a>() -> [&'a str; 3] {
+    [LEFT_ANGLE_BRACKET, HTTPS_PREFIX, HTTP_PREFIX]
+}
+
 pub fn check_input_starts_with<'a>(
     input: &'a str,
     char_set: &[&'a str],
@@ -229,3 +259,9 @@ pub fn tuple5<T>(a: &[T]) -> (&T, &T, &T, &T, &T) { (&a[0], &a[1], &a[2], &a[3],
 pub fn tuple6<T>(a: &[T]) -> (&T, &T, &T, &T, &T, &T) {
     (&a[0], &a[1], &a[2], &a[3], &a[4], &a[5])
 }
+#[allow(clippy::type_complexity)]
+pub fn tuple10<T>(a: &[T]) -> (&T, &T, &T, &T, &T, &T, &T, &T, &T, &T) {
+    (
+        &a[0], &a[1], &a[2], &a[3], &a[4], &a[5], &a[6], &a[7], &a[8], &a[9],
+    )
+}
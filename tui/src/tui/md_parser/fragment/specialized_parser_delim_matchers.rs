@@ -15,7 +15,8 @@
  *   limitations under the License.
  */
 
-//! For use with specialized parsers for: [UNDERSCORE], [STAR], and [BACK_TICK].
+//! For use with specialized parsers for: [UNDERSCORE], [STAR], [BACK_TICK], and
+//! [TILDE_TILDE].
 //! See: [parse_fragment_plain_text_no_new_line()].
 //!
 //! To see this in action, set the [DEBUG_MD_PARSER_STDOUT] to true, and run all the tests
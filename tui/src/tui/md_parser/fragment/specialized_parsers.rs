@@ -17,7 +17,7 @@
 
 use constants::*;
 use crossterm::style::Stylize;
-use nom::{branch::*, bytes::complete::*, combinator::*, multi::*, IResult};
+use nom::{branch::*, bytes::complete::*, combinator::*, multi::*, sequence::*, IResult};
 use r3bl_rs_utils_core::call_if_true;
 
 use crate::*;
@@ -36,6 +36,15 @@ pub fn parse_fragment_starts_with_star_err_on_new_line(
     specialized_parser_delim_matchers::take_starts_with_delim_no_new_line(input, STAR)
 }
 
+pub fn parse_fragment_starts_with_tilde_tilde_err_on_new_line(
+    input: &str,
+) -> IResult<&str, &str> {
+    specialized_parser_delim_matchers::take_starts_with_delim_no_new_line(
+        input,
+        TILDE_TILDE,
+    )
+}
+
 pub fn parse_fragment_starts_with_backtick_err_on_new_line(
     input: &str,
 ) -> IResult<&str, &str> {
@@ -177,6 +186,173 @@ pub fn parse_fragment_starts_with_left_link_err_on_new_line(
     it
 }
 
+/// Parse a footnote reference, eg: `[^1]` or `[^note]`. The id can't be empty or contain
+/// whitespace.
+pub fn parse_fragment_starts_with_left_footnote_err_on_new_line(
+    input: &str,
+) -> IResult<&str, &str> {
+    let it = verify(
+        |it| take_text_between_delims_err_on_new_line(it, LEFT_FOOTNOTE, RIGHT_BRACKET),
+        is_valid_footnote_id,
+    )(input);
+    call_if_true!(DEBUG_MD_PARSER_STDOUT, {
+        println!(
+            "{} specialized parser for footnote reference: {:?}",
+            if it.is_err() {
+                "⬢⬢".red()
+            } else {
+                "▲▲".blue()
+            },
+            it
+        );
+    });
+    it
+}
+
+/// Parse a reference link, eg: `[text][id]` or `[text][]`. The url is not known here, it
+/// comes from a [MdBlock::LinkDefinition] which is resolved by [MdLinkResolver].
+pub fn parse_fragment_starts_with_left_reference_link_err_on_new_line(
+    input: &str,
+) -> IResult<&str, ReferenceLinkData<'_>> {
+    let it = map(
+        pair(
+            |it| {
+                take_text_between_delims_err_on_new_line(it, LEFT_BRACKET, RIGHT_BRACKET)
+            },
+            |it| {
+                take_text_between_delims_err_on_new_line(it, LEFT_BRACKET, RIGHT_BRACKET)
+            },
+        ),
+        |(text, id)| ReferenceLinkData::new(text, id),
+    )(input);
+    call_if_true!(DEBUG_MD_PARSER_STDOUT, {
+        println!(
+            "{} specialized parser for reference link: {:?}",
+            if it.is_err() {
+                "⬢⬢".red()
+            } else {
+                "▲▲".blue()
+            },
+            it
+        );
+    });
+    it
+}
+
+/// Parse an auto link in angle brackets, eg: `<https://r3bl.com>`. The url must have a
+/// scheme, and it can't contain whitespace.
+pub fn parse_fragment_starts_with_left_angle_bracket_err_on_new_line(
+    input: &str,
+) -> IResult<&str, &str> {
+    let it = verify(
+        |it| {
+            take_text_between_delims_err_on_new_line(
+                it,
+                LEFT_ANGLE_BRACKET,
+                RIGHT_ANGLE_BRACKET,
+            )
+        },
+        is_valid_auto_link_url,
+    )(input);
+    call_if_true!(DEBUG_MD_PARSER_STDOUT, {
+        println!(
+            "{} specialized parser for bracketed auto link: {:?}",
+            if it.is_err() {
+                "⬢⬢".red()
+            } else {
+                "▲▲".blue()
+            },
+            it
+        );
+    });
+    it
+}
+
+/// Parse a bare url, eg: `https://r3bl.com`. It ends at the first whitespace, angle
+/// bracket, or `[` (eg: a footnote reference right after the url). Trailing punctuation (see [AUTO_LINK_TRAILING_PUNCTUATION]) and an unbalanced
+/// trailing `)` are not part of the url, eg: `(see https://r3bl.com).`.
+pub fn parse_fragment_starts_with_http_err_on_new_line(
+    input: &str,
+) -> IResult<&str, &str> {
+    let (_, scheme) = alt((tag(HTTPS_PREFIX), tag(HTTP_PREFIX)))(input)?;
+
+    let end = input
+        .find(|it: char| {
+            it.is_whitespace()
+                || it == LEFT_ANGLE_BRACKET_CHAR
+                || it == RIGHT_ANGLE_BRACKET_CHAR
+                || it == LEFT_BRACKET_CHAR
+        })
+        .unwrap_or(input.len());
+    let mut url = &input[..end];
+    loop {
+        let trimmed = url.trim_end_matches(AUTO_LINK_TRAILING_PUNCTUATION);
+        let trimmed = match trimmed.strip_suffix(RIGHT_PARENTHESIS) {
+            Some(it)
+                if trimmed.matches(LEFT_PARENTHESIS).count()
+                    < trimmed.matches(RIGHT_PARENTHESIS).count() =>
+            {
+                it
+            }
+            _ => trimmed,
+        };
+        if trimmed.len() == url.len() {
+            break;
+        }
+        url = trimmed;
+    }
+
+    let it = if url.len() > scheme.len() {
+        Ok((&input[url.len()..], url))
+    } else {
+        Err(nom::Err::Error(nom::error::Error {
+            input,
+            code: nom::error::ErrorKind::Verify,
+        }))
+    };
+    call_if_true!(DEBUG_MD_PARSER_STDOUT, {
+        println!(
+            "{} specialized parser for bare auto link: {:?}",
+            if it.is_err() {
+                "⬢⬢".red()
+            } else {
+                "▲▲".blue()
+            },
+            it
+        );
+    });
+    it
+}
+
+/// A footnote id can't be empty or contain whitespace, eg: `1` or `note`.
+pub fn is_valid_footnote_id(id: &str) -> bool {
+    !id.is_empty() && !id.contains(char::is_whitespace)
+}
+
+/// An auto link url must start w/ a scheme (eg: `https:` or `mailto:`) and can't contain
+/// whitespace or angle brackets.
+pub fn is_valid_auto_link_url(url: &str) -> bool {
+    if url.contains(|it: char| {
+        it.is_whitespace()
+            || it == LEFT_ANGLE_BRACKET_CHAR
+            || it == RIGHT_ANGLE_BRACKET_CHAR
+    }) {
+        return false;
+    }
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return false;
+    };
+    let mut scheme_chars = scheme.chars();
+    let starts_with_letter = scheme_chars
+        .next()
+        .map(|it| it.is_ascii_alphabetic())
+        .unwrap_or(false);
+    starts_with_letter
+        && scheme.len() >= 2
+        && scheme_chars.all(|it| it.is_ascii_alphanumeric() || "+.-".contains(it))
+        && !rest.is_empty()
+}
+
 /// Checkboxes are tricky since they begin with "[" which is also used for hyperlinks and images.
 /// So some extra hint is need from the code calling this parser to let it know whether to parse
 /// a checkbox into plain text, or into a boolean.
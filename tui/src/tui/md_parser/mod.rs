@@ -37,10 +37,12 @@
 //!    applications which are not standard Markdown can be found in
 //!    [mod@parse_metadata_kv] and [mod@parse_metadata_kcsv].
 //! 4. All the parsers that are related to parsing the main "blocks" of Markdown, such as
-//!    order lists, unordered lists, code blocks, text blocks, heading blocks, can be
-//!    found in [mod@block].
+//!    order lists, unordered lists, code blocks, text blocks, heading blocks, block
+//!    quotes, tables, horizontal rules, can be found in [mod@block].
 //! 5. All the parsers that are related to parsing a single line of Markdown text, such as
 //!    links, bold, italic, etc. can be found [mod@fragment].
+//! 6. Reference links, auto links and footnotes are resolved into [HyperlinkData] w/ the
+//!    definitions in the document by [MdLinkResolver] in [mod@resolve_links].
 //!
 //! ## Video and blog post on this
 //!
@@ -74,8 +76,13 @@
 //!     │    │     parse_authors_list()                                 Authors       │
 //!     │    │     parse_date_value()                                   Date          │
 //!     │    │     parse_block_heading_opt_eol()                        Heading       │
+//!     │    │     parse_block_horizontal_rule()                        HorizontalRule│
 //!     │    │     parse_block_smart_list()                             SmartList     │
 //!     │    │     parse_block_code()                                   CodeBlock     │
+//!     │    │     parse_block_quote()                                  BlockQuote    │
+//!     │    │     parse_block_table()                                  Table         │
+//!     │    │     parse_block_footnote_definition()                    Footnote...   │
+//!     │    │     parse_block_link_definition()                        LinkDefinition│
 //!     │    │     parse_block_markdown_text_with_or_without_new_line() Text          │
 //!     │    │   )                                                                    │
 //!     ▼    │ }                                                                      │
@@ -118,9 +125,14 @@
 //!             └─► alt(                                                          MdLineFragment variant
 //!                  ▲ parse_fragment_starts_with_underscore_err_on_new_line()      Italic
 //!                  │ parse_fragment_starts_with_star_err_on_new_line()            Bold
+//!                  │ parse_fragment_starts_with_tilde_tilde_err_on_new_line()     Strikethrough
 //!     specialized  │ parse_fragment_starts_with_backtick_err_on_new_line()        InlineCode
 //!     parsers ────►│ parse_fragment_starts_with_left_image_err_on_new_line()      Image
+//!                  │ parse_fragment_starts_with_left_footnote_err_on_new_line()   FootnoteReference
 //!                  │ parse_fragment_starts_with_left_link_err_on_new_line()       Link
+//!                  │ parse_fragment_starts_with_left_reference_link_err_on_new_line() ReferenceLink
+//!                  │ parse_fragment_starts_with_left_angle_bracket_err_on_new_line() AutoLink
+//!                  │ parse_fragment_starts_with_http_err_on_new_line()            AutoLink
 //!                  │ parse_fragment_starts_with_checkbox_into_str()               Plain
 //!                  ▼ parse_fragment_starts_with_checkbox_checkbox_into_bool()     Checkbox
 //!     catch all────► parse_fragment_plain_text_no_new_line()                      Plain
@@ -137,13 +149,17 @@
 //! parser is the last one in the chain and it simply consumes the rest of the input and
 //! turns it into a `MdBlock::Text`. This parser is the most complicated because it has to
 //! deal with all the edge cases and exit cases that other parsers have not dealt with.
-//! Such as special characters like `` ` ``, `*`, `_`, etc. They are all listed here:
+//! Such as special characters like `` ` ``, `*`, `_`, `~~`, etc. They are all listed
+//! here:
 //!
 //! - If the input does not start with a special char in this [get_sp_char_set_2()], then
 //!   this is the "Normal case". In this case the input is split at the first occurrence
 //!   of a special char in [get_sp_char_set_3()]. The "before" part is
 //!   [MdLineFragment::Plain] and the "after" part is parsed again by a more specific
 //!   parser.
+//! - If the input starts with an auto link prefix in [get_sp_char_set_4()] (eg: `<` or
+//!   `https://`), then the behavior is "Edge case -> Auto link case". Only the prefix is
+//!   turned into a [MdLineFragment::Plain], and the rest is parsed again.
 //! - If the input starts with a special char in this [get_sp_char_set_2()] and it is not
 //!   in the [get_sp_char_set_1()] with only 1 occurrence, then the behavior is different
 //!   "Edge case -> Normal case". Otherwise the behavior is "Edge case -> Special case".
@@ -166,6 +182,7 @@ pub mod convert_to_plain_text;
 pub mod extended;
 pub mod fragment;
 pub mod parse_markdown;
+pub mod resolve_links;
 pub mod types;
 
 pub use atomics::*;
@@ -174,4 +191,5 @@ pub use convert_to_plain_text::*;
 pub use extended::*;
 pub use fragment::*;
 pub use parse_markdown::*;
+pub use resolve_links::*;
 pub use types::*;
@@ -36,28 +36,48 @@ use crate::{constants::*, *};
 ///    The parsers in [mod@parse_block_smart_list] file handle this.
 /// 5. Code block (which contains string slices of the language & code). The parsers in
 ///    [mod@parse_block_code] file handle this.
-/// 6. line (which contains a [MdLineFragments]). The parsers in [mod@fragment] handle
-///    this.
-#[rustfmt::skip]
+/// 6. Horizontal rule (eg: `---`). The parser in [mod@parse_block_horizontal_rule]
+///    handles this.
+/// 7. Block quote (which contains a [BlockQuoteLine] for each line, w/ its nesting
+///    depth). The parser in [mod@parse_block_quote] handles this.
+/// 8. Table (which contains the header row, alignment row, and body rows). The parsers
+///    in [mod@parse_block_table] handle this.
+/// 9. Footnote definition (eg: `[^1]: text`) & link definition (eg: `[id]: url`). The
+///    parsers in [mod@parse_block_footnote_definition] and
+///    [mod@parse_block_link_definition] handle these. Use [MdLinkResolver] to resolve the
+///    reference links & footnote references in the document w/ them.
+/// 10. line (which contains a [MdLineFragments]). The parsers in [mod@fragment] handle
+///     this.
 pub fn parse_markdown(input: &str) -> IResult<&str, MdDocument<'_>> {
-    let (input, output) = many0(
-        // NOTE: The ordering of the parsers below matters.
-        alt((
-            map(parse_title_value,                                  MdBlock::Title),
-            map(parse_tags_list,                                    MdBlock::Tags),
-            map(parse_authors_list,                                 MdBlock::Authors),
-            map(parse_date_value,                                   MdBlock::Date),
-            map(parse_block_heading_opt_eol,                        MdBlock::Heading),
-            map(parse_block_smart_list,                             MdBlock::SmartList),
-            map(parse_block_code,                                   MdBlock::CodeBlock),
-            map(parse_block_markdown_text_with_or_without_new_line, MdBlock::Text),
-        )),
-    )(input)?;
+    let (input, output) = many0(parse_markdown_block)(input)?;
 
     let it = List::from(output);
     Ok((input, it))
 }
 
+/// Parses a single [MdBlock] from the start of `input`. [parse_markdown()] applies this
+/// repeatedly to parse the entire document, and [MdSynHiCache] uses it to only parse the
+/// blocks that have changed.
+#[rustfmt::skip]
+pub fn parse_markdown_block(input: &str) -> IResult<&str, MdBlock<'_>> {
+    // NOTE: The ordering of the parsers below matters.
+    alt((
+        map(parse_title_value,                                  MdBlock::Title),
+        map(parse_tags_list,                                    MdBlock::Tags),
+        map(parse_authors_list,                                 MdBlock::Authors),
+        map(parse_date_value,                                   MdBlock::Date),
+        map(parse_block_heading_opt_eol,                        MdBlock::Heading),
+        map(parse_block_horizontal_rule,                        MdBlock::HorizontalRule),
+        map(parse_block_smart_list,                             MdBlock::SmartList),
+        map(parse_block_code,                                   MdBlock::CodeBlock),
+        map(parse_block_quote,                                  MdBlock::BlockQuote),
+        map(parse_block_table,                                  MdBlock::Table),
+        map(parse_block_footnote_definition,                    MdBlock::FootnoteDefinition),
+        map(parse_block_link_definition,                        MdBlock::LinkDefinition),
+        map(parse_block_markdown_text_with_or_without_new_line, MdBlock::Text),
+    ))(input)
+}
+
 // key: TAGS, value: CSV parser.
 fn parse_tags_list(input: &str) -> IResult<&str, List<&str>> {
     parse_csv_opt_eol(TAGS, input)
@@ -157,6 +177,128 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_markdown_quote_table_rule_strikethrough() {
+        let input = [
+            "> quote ~~old~~",
+            "> > nested",
+            "- - -",
+            "| a | b |",
+            "|---|:-:|",
+            "| c | d |",
+            "",
+            "| not | a table |",
+            "***",
+            "- item",
+        ]
+        .join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(blocks.len(), 7);
+        assert_eq2!(
+            blocks[0],
+            MdBlock::BlockQuote(list![
+                BlockQuoteLine {
+                    depth: 1,
+                    prefix: "> ",
+                    fragments: list![
+                        MdLineFragment::Plain("quote "),
+                        MdLineFragment::Strikethrough("old"),
+                    ],
+                },
+                BlockQuoteLine {
+                    depth: 2,
+                    prefix: "> > ",
+                    fragments: list![MdLineFragment::Plain("nested")],
+                },
+            ])
+        );
+        assert_eq2!(blocks[1], MdBlock::HorizontalRule("- - -"));
+        assert_eq2!(
+            blocks[2],
+            MdBlock::Table(TableData {
+                header_row: list![
+                    list![MdLineFragment::Plain(" a ")],
+                    list![MdLineFragment::Plain(" b ")],
+                ],
+                alignment_row: list![
+                    (TableColumnAlignment::None, "---"),
+                    (TableColumnAlignment::Center, ":-:"),
+                ],
+                body_rows: list![list![
+                    list![MdLineFragment::Plain(" c ")],
+                    list![MdLineFragment::Plain(" d ")],
+                ]],
+            })
+        );
+        assert_eq2!(blocks[3], MdBlock::Text(list![])); // Empty line.
+
+        // Not a table, since there is no alignment row.
+        assert_eq2!(
+            blocks[4],
+            MdBlock::Text(list![MdLineFragment::Plain("| not | a table |")])
+        );
+        assert_eq2!(blocks[5], MdBlock::HorizontalRule("***"));
+        assert!(matches!(blocks[6], MdBlock::SmartList(_)));
+    }
+
+    #[test]
+    fn test_parse_markdown_links_and_footnotes() {
+        let input = [
+            "See [r3bl][site], <https://github.com/r3bl-org> & https://r3bl.com. Footnote[^1]",
+            "[site]: https://r3bl.com",
+            "[^1]: A *footnote*.",
+            "[not]: a definition",
+        ]
+        .join("\n");
+        let (remainder, blocks) = parse_markdown(&input).unwrap();
+        assert_eq2!(remainder, "");
+        assert_eq2!(
+            blocks,
+            list![
+                MdBlock::Text(list![
+                    MdLineFragment::Plain("See "),
+                    MdLineFragment::ReferenceLink(ReferenceLinkData::new("r3bl", "site")),
+                    MdLineFragment::Plain(", "),
+                    MdLineFragment::AutoLink(AutoLinkData::new(
+                        "https://github.com/r3bl-org",
+                        true
+                    )),
+                    MdLineFragment::Plain(" & "),
+                    MdLineFragment::AutoLink(AutoLinkData::new(
+                        "https://r3bl.com",
+                        false
+                    )),
+                    MdLineFragment::Plain(". Footnote"),
+                    MdLineFragment::FootnoteReference("1"),
+                ]),
+                MdBlock::LinkDefinition(LinkDefinitionData {
+                    id: "site",
+                    url: "https://r3bl.com"
+                }),
+                MdBlock::FootnoteDefinition(FootnoteDefinitionData {
+                    id: "1",
+                    fragments: list![
+                        MdLineFragment::Plain("A "),
+                        MdLineFragment::Bold("footnote"),
+                        MdLineFragment::Plain("."),
+                    ],
+                }),
+                MdBlock::Text(list![MdLineFragment::Plain("[not]: a definition")]),
+            ]
+        );
+
+        // Converting the blocks back to text round trips the input.
+        assert_eq2!(
+            blocks
+                .iter()
+                .map(|it| it.pretty_print_debug())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            input
+        );
+    }
+
     #[test]
     fn test_parse_markdown_valid() {
         let input = vec![
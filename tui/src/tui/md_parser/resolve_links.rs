@@ -0,0 +1,285 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Reference links (eg: `[text][id]`) and footnote references (eg: `[^1]`) are
+//! defined in other blocks of the [MdDocument] (eg: `[id]: url` and `[^1]: text`).
+//! [MdLinkResolver] collects these definitions from a parsed document, so that every kind
+//! of link can be turned into a [HyperlinkData], just like [MdLineFragment::Link].
+
+use std::collections::HashMap;
+
+use crate::*;
+
+/// A reference link id or footnote id that is used in a [MdBlock], whose definition can
+/// be in another block. See [get_md_block_references].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MdLinkReference {
+    /// The id of a [MdLineFragment::ReferenceLink].
+    Link(String),
+    /// The id of a [MdLineFragment::FootnoteReference].
+    Footnote(String),
+}
+
+/// Resolves the links in a [MdDocument] w/ the [MdBlock::LinkDefinition]s and
+/// [MdBlock::FootnoteDefinition]s in it. Definitions can be anywhere in the document,
+/// and if an id is defined more than once, the first definition wins. Ids are case
+/// insensitive, eg: `[R3BL][Site]` uses `[site]: https://r3bl.com`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MdLinkResolver<'a> {
+    link_definitions: HashMap<String, &'a str>,
+    /// Footnote ids in the order they are defined, along w/ their text.
+    footnote_definitions: Vec<(&'a str, &'a MdLineFragments<'a>)>,
+}
+
+impl<'a> MdLinkResolver<'a> {
+    pub fn new(document: &'a MdDocument<'a>) -> Self {
+        let mut it = Self::default();
+        for block in document.iter() {
+            match block {
+                MdBlock::LinkDefinition(LinkDefinitionData { id, url }) => {
+                    it.link_definitions.entry(normalize_id(id)).or_insert(url);
+                }
+                MdBlock::FootnoteDefinition(FootnoteDefinitionData { id, fragments })
+                    if it.get_footnote_number(id).is_none() =>
+                {
+                    it.footnote_definitions.push((id, fragments));
+                }
+                _ => {}
+            }
+        }
+        it
+    }
+
+    /// Returns the url for the given link definition id, if it is defined.
+    pub fn get_link_url(&self, id: &str) -> Option<&'a str> {
+        self.link_definitions.get(&normalize_id(id)).copied()
+    }
+
+    /// Returns the [HyperlinkData] for [MdLineFragment::Link], [MdLineFragment::Image],
+    /// [MdLineFragment::AutoLink], and [MdLineFragment::ReferenceLink] (if its id is
+    /// defined). All other fragments return [None].
+    pub fn resolve(&self, fragment: &MdLineFragment<'a>) -> Option<HyperlinkData<'a>> {
+        match fragment {
+            MdLineFragment::Link(it) | MdLineFragment::Image(it) => Some(it.clone()),
+            MdLineFragment::AutoLink(AutoLinkData { url, .. }) => {
+                Some(HyperlinkData::new(url, url))
+            }
+            MdLineFragment::ReferenceLink(it) => self
+                .get_link_url(it.get_id())
+                .map(|url| HyperlinkData::new(it.text, url)),
+            _ => None,
+        }
+    }
+
+    /// Returns the url for the footnote w/ the given id, if it is defined. This is the
+    /// anchor that [render_md_document_to_html] renders the footnote w/.
+    pub fn get_footnote_url(&self, id: &str) -> Option<String> {
+        self.get_footnote_number(id)
+            .map(|_| format!("#md-footnote-{id}"))
+    }
+
+    /// Returns the url that `reference` resolves to, if its definition is in the
+    /// document.
+    pub fn resolve_reference(&self, reference: &MdLinkReference) -> Option<String> {
+        match reference {
+            MdLinkReference::Link(id) => self.get_link_url(id).map(String::from),
+            MdLinkReference::Footnote(id) => self.get_footnote_url(id),
+        }
+    }
+
+    /// Footnotes are numbered (starting at 1) in the order they are defined in the
+    /// document. Returns [None] if the footnote isn't defined.
+    pub fn get_footnote_number(&self, id: &str) -> Option<usize> {
+        self.footnote_definitions
+            .iter()
+            .position(|(it, _)| *it == id)
+            .map(|index| index + 1)
+    }
+
+    /// Returns the text of the footnote w/ the given id, if it is defined.
+    pub fn get_footnote_text(&self, id: &str) -> Option<&'a MdLineFragments<'a>> {
+        self.footnote_definitions
+            .iter()
+            .find(|(it, _)| *it == id)
+            .map(|(_, fragments)| *fragments)
+    }
+
+    /// All the footnote definitions in the order they are defined, ie, by their number.
+    pub fn get_footnote_definitions(
+        &self,
+    ) -> impl Iterator<Item = (&'a str, &'a MdLineFragments<'a>)> + '_ {
+        self.footnote_definitions.iter().copied()
+    }
+}
+
+/// Returns the reference links and footnote references in `block`, in the order that they
+/// appear in it. These are the ids that the block depends on, which are defined in
+/// other blocks.
+pub fn get_md_block_references(block: &MdBlock<'_>) -> Vec<MdLinkReference> {
+    let lines: Vec<&MdLineFragments<'_>> = match block {
+        MdBlock::Text(fragments)
+        | MdBlock::FootnoteDefinition(FootnoteDefinitionData { fragments, .. }) => {
+            vec![fragments]
+        }
+        MdBlock::SmartList((lines, _, _)) => lines.iter().collect(),
+        MdBlock::BlockQuote(lines) => lines.iter().map(|it| &it.fragments).collect(),
+        MdBlock::Table(TableData {
+            header_row,
+            body_rows,
+            ..
+        }) => header_row
+            .iter()
+            .chain(body_rows.iter().flat_map(|row| row.iter()))
+            .collect(),
+        _ => vec![],
+    };
+
+    lines
+        .into_iter()
+        .flat_map(|fragments| fragments.iter())
+        .filter_map(|fragment| match fragment {
+            MdLineFragment::ReferenceLink(it) => {
+                Some(MdLinkReference::Link(it.get_id().to_string()))
+            }
+            MdLineFragment::FootnoteReference(id) => {
+                Some(MdLinkReference::Footnote(id.to_string()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Link definition ids are matched case insensitively, and runs of whitespace are the
+/// same as a single space.
+fn normalize_id(id: &str) -> String {
+    id.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(constants::SPACE)
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_links() {
+        let input = [
+            "[Link](https://r3bl.com) ![Image](img.png) <https://r3bl.com/a> https://r3bl.com/b",
+            "[R3BL][site] [Site][] [Missing][nope] [^1]",
+            "[ SITE ]: https://r3bl.com/site",
+            "[site]: https://ignored.com",
+        ]
+        .join("\n");
+        let (_, document) = parse_markdown(&input).unwrap();
+        let resolver = MdLinkResolver::new(&document);
+
+        let MdBlock::Text(first_line) = &document[0] else {
+            panic!("expected text");
+        };
+        let MdBlock::Text(second_line) = &document[1] else {
+            panic!("expected text");
+        };
+
+        let resolved = first_line
+            .iter()
+            .chain(second_line.iter())
+            .filter_map(|it| resolver.resolve(it))
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            resolved,
+            vec![
+                HyperlinkData::new("Link", "https://r3bl.com"),
+                HyperlinkData::new("Image", "img.png"),
+                HyperlinkData::new("https://r3bl.com/a", "https://r3bl.com/a"),
+                HyperlinkData::new("https://r3bl.com/b", "https://r3bl.com/b"),
+                HyperlinkData::new("R3BL", "https://r3bl.com/site"),
+                HyperlinkData::new("Site", "https://r3bl.com/site"),
+            ]
+        );
+        assert_eq2!(resolver.get_link_url("nope"), None);
+    }
+
+    #[test]
+    fn test_resolve_footnotes() {
+        let input = [
+            "Foo[^b] bar[^a].",
+            "[^b]: *First*",
+            "[^a]: Second",
+            "[^b]: Ignored",
+        ]
+        .join("\n");
+        let (_, document) = parse_markdown(&input).unwrap();
+        let resolver = MdLinkResolver::new(&document);
+
+        assert_eq2!(resolver.get_footnote_number("b"), Some(1));
+        assert_eq2!(resolver.get_footnote_number("a"), Some(2));
+        assert_eq2!(resolver.get_footnote_number("c"), None);
+        assert_eq2!(
+            resolver.get_footnote_text("b"),
+            Some(&list![MdLineFragment::Bold("First")])
+        );
+        assert_eq2!(
+            resolver
+                .get_footnote_definitions()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            vec!["b", "a"]
+        );
+        assert_eq2!(
+            resolver.resolve(&MdLineFragment::FootnoteReference("a")),
+            None
+        );
+        assert_eq2!(
+            resolver.get_footnote_url("a"),
+            Some("#md-footnote-a".to_string())
+        );
+        assert_eq2!(resolver.get_footnote_url("c"), None);
+    }
+
+    #[test]
+    fn test_get_md_block_references() {
+        let input = [
+            "- [R3BL][Site] and [^1]",
+            "> [^2] [Link](https://r3bl.com)",
+            "| [a][] | b |",
+            "|---|---|",
+            "| [^3] | |",
+        ]
+        .join("\n");
+        let (_, document) = parse_markdown(&input).unwrap();
+        let references = document
+            .iter()
+            .flat_map(get_md_block_references)
+            .collect::<Vec<_>>();
+        assert_eq2!(
+            references,
+            vec![
+                MdLinkReference::Link("Site".to_string()),
+                MdLinkReference::Footnote("1".to_string()),
+                MdLinkReference::Footnote("2".to_string()),
+                MdLinkReference::Link("a".to_string()),
+                MdLinkReference::Footnote("3".to_string()),
+            ]
+        );
+
+        let resolver = MdLinkResolver::new(&document);
+        assert_eq2!(resolver.resolve_reference(&references[0]), None);
+    }
+}
@@ -56,6 +56,11 @@ pub enum MdBlock<'a> {
     Date(&'a str),
     Tags(List<&'a str>),
     Authors(List<&'a str>),
+    BlockQuote(BlockQuoteLines<'a>),
+    Table(TableData<'a>),
+    HorizontalRule(&'a str),
+    LinkDefinition(LinkDefinitionData<'a>),
+    FootnoteDefinition(FootnoteDefinitionData<'a>),
 }
 
 /// These are things that show up in a single line of Markdown text [MdLineFragments]. They do not
@@ -75,17 +80,129 @@ pub enum MdLineFragment<'a> {
     Bold(&'a str),
     Italic(&'a str),
     InlineCode(&'a str),
+    Strikethrough(&'a str),
     Link(HyperlinkData<'a>),
     Image(HyperlinkData<'a>),
+    /// `[text][id]` or `[text][]`, the url comes from a [MdBlock::LinkDefinition]. Use
+    /// [crate::MdLinkResolver] to get its [HyperlinkData].
+    ReferenceLink(ReferenceLinkData<'a>),
+    /// `<https://r3bl.com>` or a bare url, eg: `https://r3bl.com`.
+    AutoLink(AutoLinkData<'a>),
+    /// `[^id]`, the text comes from a [MdBlock::FootnoteDefinition].
+    FootnoteReference(&'a str),
     Checkbox(bool),
 }
 
+/// A single line of a block quote, eg: `> > some text`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockQuoteLine<'a> {
+    /// The number of `>` chars in the prefix, which is 1 for a top level quote, 2 for a
+    /// quote that is nested in it, etc.
+    pub depth: usize,
+    /// The prefix, eg: `> > `, which is kept so that the line can be displayed w/ the
+    /// same width.
+    pub prefix: &'a str,
+    pub fragments: MdLineFragments<'a>,
+}
+
+/// Alias for [List] of [BlockQuoteLine].
+pub type BlockQuoteLines<'a> = List<BlockQuoteLine<'a>>;
+
+/// A pipe table, eg:
+///
+/// ```text
+/// | Name | Count |
+/// |------|------:|
+/// | foo  |     1 |
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableData<'a> {
+    pub header_row: TableRow<'a>,
+    /// Each cell of the alignment row, along w/ its text (eg: `------:`).
+    pub alignment_row: List<(TableColumnAlignment, &'a str)>,
+    pub body_rows: List<TableRow<'a>>,
+}
+
+/// Each cell in a row of a table. The text of a cell is all the text between its pipes
+/// (including the spaces around it).
+pub type TableRow<'a> = List<MdLineFragments<'a>>;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TableColumnAlignment {
+    /// `---`
+    None,
+    /// `:--`
+    Left,
+    /// `:-:`
+    Center,
+    /// `--:`
+    Right,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct HyperlinkData<'a> {
     pub text: &'a str,
     pub url: &'a str,
 }
 
+/// `[text][id]`, or `[text][]` in which case `id` is empty and the text is used as the
+/// id.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReferenceLinkData<'a> {
+    pub text: &'a str,
+    pub id: &'a str,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AutoLinkData<'a> {
+    pub url: &'a str,
+    /// True for `<url>`, and false for a bare url.
+    pub is_bracketed: bool,
+}
+
+/// `[id]: url`, which is the url for the [MdLineFragment::ReferenceLink]s w/ the same id.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkDefinitionData<'a> {
+    pub id: &'a str,
+    pub url: &'a str,
+}
+
+/// `[^id]: text`, which is the text for the [MdLineFragment::FootnoteReference]s w/ the
+/// same id.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FootnoteDefinitionData<'a> {
+    pub id: &'a str,
+    pub fragments: MdLineFragments<'a>,
+}
+
+mod reference_link_data_impl {
+    use super::*;
+
+    impl<'a> ReferenceLinkData<'a> {
+        pub fn new(text: &'a str, id: &'a str) -> Self { Self { text, id } }
+
+        /// The id that is used to look up the [LinkDefinitionData], which is the text
+        /// for `[text][]`.
+        pub fn get_id(&self) -> &'a str {
+            if self.id.is_empty() {
+                self.text
+            } else {
+                self.id
+            }
+        }
+    }
+}
+
+mod auto_link_data_impl {
+    use super::*;
+
+    impl<'a> AutoLinkData<'a> {
+        pub fn new(url: &'a str, is_bracketed: bool) -> Self {
+            Self { url, is_bracketed }
+        }
+    }
+}
+
 mod hyperlink_data_impl {
     use super::*;
 
@@ -143,6 +260,8 @@ pub mod constants {
     pub const BACK_TICK: &str = "`";
     pub const LEFT_BRACKET: &str = "[";
     pub const RIGHT_BRACKET: &str = "]";
+    pub const RIGHT_BRACKET_CHAR: char = ']';
+    pub const LEFT_BRACKET_CHAR: char = '[';
     pub const LEFT_PARENTHESIS: &str = "(";
     pub const RIGHT_PARENTHESIS: &str = ")";
     pub const LEFT_IMAGE: &str = "![";
@@ -156,6 +275,35 @@ pub mod constants {
     pub const CHECKED_OUTPUT: &str = "┊✔┊";
     pub const UNCHECKED_OUTPUT: &str = "┊┈┊";
     pub const EXCLAMATION: &str = "!";
+    pub const LEFT_FOOTNOTE: &str = "[^";
+    pub const FOOTNOTE_CARET: &str = "^";
+    pub const LEFT_ANGLE_BRACKET: &str = "<";
+    pub const RIGHT_ANGLE_BRACKET: &str = ">";
+    pub const LEFT_ANGLE_BRACKET_CHAR: char = '<';
+    pub const RIGHT_ANGLE_BRACKET_CHAR: char = '>';
+    pub const DEFINITION_SEPARATOR: &str = "]: ";
+    pub const HTTP_PREFIX: &str = "http://";
+    pub const HTTPS_PREFIX: &str = "https://";
+    /// These are not part of a bare url when they are at the end of it, eg: the `.` in
+    /// `See https://r3bl.com.`.
+    pub const AUTO_LINK_TRAILING_PUNCTUATION: [char; 8] =
+        ['.', ',', ':', ';', '!', '?', '\'', '"'];
+    pub const TILDE_TILDE: &str = "~~";
+    pub const BLOCK_QUOTE_CHAR: char = '>';
+    pub const TABLE_PIPE: &str = "|";
+    pub const TABLE_PIPE_CHAR: char = '|';
+    pub const TABLE_ALIGNMENT_COLON_CHAR: char = ':';
+    pub const TABLE_ALIGNMENT_DASH_CHAR: char = '-';
+    pub const BACKSLASH_CHAR: char = '\\';
+    /// Any of these chars can be used for a horizontal rule, eg: `---`, `***` or `___`.
+    pub const HORIZONTAL_RULE_CHARS: [char; 3] = ['-', '*', '_'];
+    pub const HORIZONTAL_RULE_MIN_CHAR_COUNT: usize = 3;
+
+    /// Only for output to terminal.
+    pub const BLOCK_QUOTE_DISPLAY: &str = "┃";
+
+    /// Only for output to terminal.
+    pub const HORIZONTAL_RULE_DISPLAY: &str = "─";
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -0,0 +1,33 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! This module holds the renderers that turn a [crate::MdDocument] (produced by
+//! [crate::md_parser]) into text that can be used outside of the [crate::editor]:
+//! 1. [mod@render_html] - A standalone HTML document, eg: to export notes.
+//! 2. [mod@render_ansi] - A word wrapped [String] w/ ANSI escape sequences, eg: to print
+//!    notes to the terminal.
+//!
+//! Both of them use the same stylesheet as the [crate::editor] (from the current
+//! [crate::MdTheme]), and syntect to highlight code blocks.
+
+// Attach.
+pub mod render_ansi;
+pub mod render_html;
+
+// Re-export.
+pub use render_ansi::*;
+pub use render_html::*;
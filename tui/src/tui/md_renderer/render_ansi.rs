@@ -0,0 +1,469 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Renders a [MdDocument] into a [String] w/ ANSI escape sequences, that is word wrapped
+//! to fit in a given display width. This uses the same styles as the [editor] (see
+//! [StyleUSSpanLines::from_block]), along w/ syntect to highlight code blocks. This is
+//! useful for CLI tools that print Markdown to the terminal (eg: like `cat`) w/out using
+//! the [editor] component.
+//!
+//! Reference links and footnote references are resolved w/ [MdLinkResolver], just like in
+//! [render_md_document_to_html], so they get a hyperlink if they are defined in the
+//! document. Ones that aren't defined are rendered as is.
+
+use r3bl_ansi_color::AnsiStyledText;
+use r3bl_rs_utils_core::*;
+use syntect::{highlighting::Theme, parsing::SyntaxSet};
+
+use crate::*;
+
+/// Parse the `input` Markdown text, and render it w/ [render_md_document_to_ansi].
+pub fn try_parse_and_render_markdown_to_ansi(
+    input: &str,
+    max_display_width: ChUnit,
+    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+) -> CommonResult<String> {
+    match parse_markdown(input) {
+        Ok((_remainder, document)) => Ok(render_md_document_to_ansi(
+            &document,
+            max_display_width,
+            maybe_syntect_tuple,
+        )),
+        Err(_) => CommonError::new_err_with_only_type(CommonErrorType::ParsingError),
+    }
+}
+
+/// Each line of each [MdBlock] is styled just like in the [editor], and then word wrapped
+/// so that no line is wider than `max_display_width`. Each line in the output ends w/ a
+/// new line.
+///
+/// When a line of a smart list or a block quote is wrapped, the lines after the first
+/// one are indented, so that they line up w/ the text after the bullet or quote marker.
+pub fn render_md_document_to_ansi(
+    document: &MdDocument<'_>,
+    max_display_width: ChUnit,
+    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+) -> String {
+    let resolver = MdLinkResolver::new(document);
+    let mut acc = String::new();
+
+    for block in document.iter() {
+        let lines =
+            StyleUSSpanLines::from_block(block, &resolver, &None, maybe_syntect_tuple);
+        for line in lines.iter() {
+            let continuation_prefix = get_continuation_prefix(block, line);
+            for wrapped_line in
+                wrap_style_us_span_line(line, max_display_width, &continuation_prefix)
+            {
+                acc.push_str(&convert_style_us_span_line_into_ansi(&wrapped_line));
+                acc.push_str(constants::NEW_LINE);
+            }
+        }
+    }
+
+    acc
+}
+
+/// The first span of each line of a smart list is its bullet, and that of a block quote
+/// is its quote marker.
+fn get_continuation_prefix(
+    block: &MdBlock<'_>,
+    line: &StyleUSSpanLine,
+) -> StyleUSSpanLine {
+    let Some(first_span) = line.first() else {
+        return StyleUSSpanLine::default();
+    };
+    match block {
+        MdBlock::SmartList(_) => {
+            let width = ch!(@to_usize first_span.text.display_width);
+            List::from(vec![StyleUSSpan::new(
+                first_span.style,
+                US::from(constants::SPACE.repeat(width)),
+            )])
+        }
+        MdBlock::BlockQuote(_) => List::from(vec![first_span.clone()]),
+        _ => StyleUSSpanLine::default(),
+    }
+}
+
+/// Convert each [StyleUSSpan] in the `line` into an [AnsiStyledText] and join them.
+pub fn convert_style_us_span_line_into_ansi(line: &StyleUSSpanLine) -> String {
+    line.iter()
+        .map(|span| {
+            let style = convert_tui_style_into_r3bl_ansi_styles(&span.style);
+            let text = AnsiStyledText {
+                text: &span.text.string,
+                style: &style,
+            }
+            .to_string();
+            TuiHyperlink::wrap_text(&span.style.hyperlink, &text)
+        })
+        .collect()
+}
+
+pub fn convert_tui_style_into_r3bl_ansi_styles(
+    style: &TuiStyle,
+) -> Vec<r3bl_ansi_color::Style> {
+    use color_wheel_color_converter::convert_tui_color_into_r3bl_ansi_color;
+    use r3bl_ansi_color::Style;
+
+    let mut acc = vec![];
+    if let Some(color_fg) = style.color_fg {
+        acc.push(Style::Foreground(convert_tui_color_into_r3bl_ansi_color(
+            color_fg,
+        )));
+    }
+    if let Some(color_bg) = style.color_bg {
+        acc.push(Style::Background(convert_tui_color_into_r3bl_ansi_color(
+            color_bg,
+        )));
+    }
+    for (is_set, it) in [
+        (style.bold, Style::Bold),
+        (style.dim, Style::Dim),
+        (style.italic, Style::Italic),
+        (style.underline, Style::Underline),
+        (style.reverse, Style::Invert),
+        (style.hidden, Style::Hidden),
+        (style.strikethrough, Style::Strikethrough),
+    ] {
+        if is_set {
+            acc.push(it);
+        }
+    }
+    acc
+}
+
+/// A run of whitespace or non whitespace grapheme clusters in a line, which may span
+/// many [StyleUSSpan]s, eg: `*bold*` is a single word made of 3 spans.
+#[derive(Debug, Default)]
+struct Word {
+    is_whitespace: bool,
+    spans: Vec<StyleUSSpan>,
+    display_width: usize,
+}
+
+impl Word {
+    fn push(&mut self, style: TuiStyle, grapheme_cluster: &str, display_width: usize) {
+        self.display_width += display_width;
+        match self.spans.last_mut() {
+            Some(last) if last.style == style => {
+                last.text = US::from(format!("{}{}", last.text.string, grapheme_cluster));
+            }
+            _ => self
+                .spans
+                .push(StyleUSSpan::new(style, US::from(grapheme_cluster))),
+        }
+    }
+}
+
+fn split_into_words(line: &StyleUSSpanLine) -> Vec<Word> {
+    let mut acc: Vec<Word> = vec![];
+    for span in line.iter() {
+        for segment in span.text.vec_segment.iter() {
+            let is_whitespace = segment.string.chars().all(char::is_whitespace);
+            let display_width = ch!(@to_usize segment.unicode_width);
+            match acc.last_mut() {
+                Some(word) if word.is_whitespace == is_whitespace => {
+                    word.push(span.style, &segment.string, display_width);
+                }
+                _ => {
+                    let mut word = Word {
+                        is_whitespace,
+                        ..Default::default()
+                    };
+                    word.push(span.style, &segment.string, display_width);
+                    acc.push(word);
+                }
+            }
+        }
+    }
+    acc
+}
+
+/// Word wrap the `line` so that each line is at most `max_display_width` wide. Each line
+/// after the first one starts w/ the `continuation_prefix`. Whitespace at the start of
+/// the first line is kept (since it is indentation), but whitespace where a line is
+/// wrapped, or at the end of the line, is dropped. Words that don't fit in a line by
+/// themselves are split.
+pub fn wrap_style_us_span_line(
+    line: &StyleUSSpanLine,
+    max_display_width: ChUnit,
+    continuation_prefix: &StyleUSSpanLine,
+) -> Vec<StyleUSSpanLine> {
+    let max_display_width = ch!(@to_usize max_display_width).max(1);
+    let prefix_display_width = ch!(@to_usize continuation_prefix.display_width());
+    // Don't use the prefix if there's no room left for the text.
+    let continuation_prefix = if prefix_display_width < max_display_width {
+        continuation_prefix.clone()
+    } else {
+        StyleUSSpanLine::default()
+    };
+
+    let mut acc_lines: Vec<StyleUSSpanLine> = vec![];
+    let mut current_line = StyleUSSpanLine::default();
+    let mut current_width = 0;
+    // True when nothing but the prefix has been added to the current line.
+    let mut is_only_prefix = true;
+    // Whitespace is only added to a line when the word after it fits in the line too.
+    let mut maybe_pending_whitespace: Option<Word> = None;
+
+    for word in split_into_words(line) {
+        if word.is_whitespace {
+            if acc_lines.is_empty() && is_only_prefix {
+                // Indentation at the start of the first line.
+                if current_width + word.display_width <= max_display_width {
+                    current_width += word.display_width;
+                    current_line += word.spans;
+                }
+            } else {
+                maybe_pending_whitespace = Some(word);
+            }
+            continue;
+        }
+
+        let pending_whitespace_width = maybe_pending_whitespace
+            .as_ref()
+            .map(|it| it.display_width)
+            .unwrap_or(0);
+        if current_width + pending_whitespace_width + word.display_width
+            <= max_display_width
+        {
+            if let Some(whitespace) = maybe_pending_whitespace.take() {
+                current_line += whitespace.spans;
+            }
+            current_line += word.spans;
+            current_width += pending_whitespace_width + word.display_width;
+            is_only_prefix = false;
+            continue;
+        }
+
+        // Wrap the line. Whitespace where the line is wrapped is dropped.
+        maybe_pending_whitespace = None;
+        if !is_only_prefix {
+            acc_lines.push(current_line);
+            current_line = continuation_prefix.clone();
+            current_width = prefix_display_width;
+            is_only_prefix = true;
+        }
+
+        if current_width + word.display_width <= max_display_width {
+            current_line += word.spans;
+            current_width += word.display_width;
+            is_only_prefix = false;
+            continue;
+        }
+
+        // Split the word since it doesn't fit in a line by itself.
+        for span in word.spans {
+            for segment in span.text.vec_segment.iter() {
+                let display_width = ch!(@to_usize segment.unicode_width);
+                if current_width + display_width > max_display_width && !is_only_prefix {
+                    acc_lines.push(current_line);
+                    current_line = continuation_prefix.clone();
+                    current_width = prefix_display_width;
+                }
+                current_line += StyleUSSpan::new(span.style, US::from(&segment.string));
+                current_width += display_width;
+                is_only_prefix = false;
+            }
+        }
+    }
+
+    if !is_only_prefix || acc_lines.is_empty() {
+        acc_lines.push(current_line);
+    }
+
+    acc_lines
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+    use r3bl_rs_utils_macro::tui_style;
+    use serial_test::serial;
+
+    use super::*;
+
+    fn get_plain_text_lines(lines: &[StyleUSSpanLine]) -> Vec<String> {
+        lines.iter().map(|line| line.get_plain_text()).collect()
+    }
+
+    /// Remove the ANSI escape sequences (eg: `ESC[1m`, and OSC 8 hyperlinks) from the
+    /// `text`.
+    fn strip_ansi(text: &str) -> String {
+        let mut acc = String::new();
+        let mut chars = text.chars().peekable();
+        while let Some(it) = chars.next() {
+            match (it, chars.peek()) {
+                ('\x1b', Some(']')) => {
+                    while let Some(it) = chars.next() {
+                        if it == '\x1b' && chars.next_if_eq(&'\\').is_some() {
+                            break;
+                        }
+                    }
+                }
+                ('\x1b', _) => {
+                    for it in chars.by_ref() {
+                        if it == 'm' {
+                            break;
+                        }
+                    }
+                }
+                _ => acc.push(it),
+            }
+        }
+        acc
+    }
+
+    #[test]
+    fn test_wrap_style_us_span_line() {
+        let bold = tui_style! { attrib: [bold] };
+        let line: StyleUSSpanLine = List::from(vec![
+            StyleUSSpan::new(TuiStyle::default(), US::from("  The quick ")),
+            StyleUSSpan::new(bold, US::from("brown")),
+            StyleUSSpan::new(TuiStyle::default(), US::from("! fox jumps")),
+        ]);
+
+        // Fits in one line.
+        let lines = wrap_style_us_span_line(&line, ch!(80), &List::default());
+        assert_eq2!(
+            get_plain_text_lines(&lines),
+            vec!["  The quick brown! fox jumps"]
+        );
+
+        // Leading whitespace is kept, but not whitespace where the line wraps. A word can
+        // be made of many spans.
+        let lines = wrap_style_us_span_line(&line, ch!(12), &List::default());
+        assert_eq2!(
+            get_plain_text_lines(&lines),
+            vec!["  The quick", "brown! fox", "jumps"]
+        );
+        assert_eq2!(lines[1].inner[0], StyleUSSpan::new(bold, US::from("brown")));
+
+        // The lines after the first one start w/ the prefix.
+        let prefix = List::from(vec![StyleUSSpan::new(bold, US::from("> "))]);
+        let lines = wrap_style_us_span_line(&line, ch!(12), &prefix);
+        assert_eq2!(
+            get_plain_text_lines(&lines),
+            vec!["  The quick", "> brown! fox", "> jumps"]
+        );
+
+        // Words that are too wide are split.
+        let line: StyleUSSpanLine = List::from(vec![StyleUSSpan::new(
+            TuiStyle::default(),
+            US::from("a 😃😃😃😃"),
+        )]);
+        let lines = wrap_style_us_span_line(&line, ch!(5), &List::default());
+        assert_eq2!(get_plain_text_lines(&lines), vec!["a", "😃😃", "😃😃"]);
+
+        // Empty line.
+        let lines = wrap_style_us_span_line(&List::default(), ch!(5), &List::default());
+        assert_eq2!(get_plain_text_lines(&lines), vec![""]);
+    }
+
+    #[test]
+    fn test_convert_tui_style_into_r3bl_ansi_styles() {
+        let style = tui_style! {
+            attrib: [bold, dim]
+            color_fg: TuiColor::Rgb(RgbValue::from_u8(1, 2, 3))
+        };
+        assert_eq2!(
+            convert_tui_style_into_r3bl_ansi_styles(&style),
+            vec![
+                r3bl_ansi_color::Style::Foreground(r3bl_ansi_color::Color::Rgb(1, 2, 3)),
+                r3bl_ansi_color::Style::Bold,
+                r3bl_ansi_color::Style::Dim,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_md_document_to_ansi() {
+        let input = [
+            "# Heading",
+            "This line is *too* long to fit",
+            "",
+            "- A list item that wraps",
+            "> A quote that wraps",
+        ]
+        .join("\n");
+        let output =
+            try_parse_and_render_markdown_to_ansi(&input, ch!(16), None).unwrap();
+
+        assert!(output.contains("\x1b["));
+        assert!(output.ends_with('\n'));
+        assert_eq2!(
+            strip_ansi(&output).lines().collect::<Vec<_>>(),
+            vec![
+                "# Heading",
+                "This line is",
+                "*too* long to",
+                "fit",
+                "",
+                "─┤A list item",
+                "  that wraps",
+                "┃ A quote that",
+                "┃ wraps",
+            ]
+        );
+    }
+
+    #[serial]
+    #[test]
+    fn test_render_hyperlinks_to_ansi() {
+        let input = "See [R3BL](https://r3bl.com) and [docs][d]";
+        let osc8_start = TuiHyperlink::new("https://r3bl.com").get_osc8_start();
+
+        global_hyperlink_support::set_override(true);
+        let output = try_parse_and_render_markdown_to_ansi(input, ch!(80), None).unwrap();
+        assert!(output.contains(&osc8_start));
+        assert_eq2!(output.matches(OSC8_END).count(), 2);
+        assert_eq2!(strip_ansi(&output), format!("{input}\n"));
+
+        global_hyperlink_support::set_override(false);
+        let output = try_parse_and_render_markdown_to_ansi(input, ch!(80), None).unwrap();
+        assert!(!output.contains(OSC8_END));
+
+        global_hyperlink_support::clear_override();
+    }
+
+    #[serial]
+    #[test]
+    fn test_render_reference_links_and_footnotes_to_ansi() {
+        let input = [
+            "[R3BL][site] [Missing][nope] note[^1] [^2]",
+            "[site]: https://r3bl.com/site",
+            "[^1]: A footnote",
+        ]
+        .join("\n");
+        let link_osc8_start = TuiHyperlink::new("https://r3bl.com/site").get_osc8_start();
+        let footnote_osc8_start = TuiHyperlink::new("#md-footnote-1").get_osc8_start();
+
+        global_hyperlink_support::set_override(true);
+        let output =
+            try_parse_and_render_markdown_to_ansi(&input, ch!(80), None).unwrap();
+        // The text and id of the reference link, and the id and url of its definition.
+        assert_eq2!(output.matches(&link_osc8_start).count(), 4);
+        assert_eq2!(output.matches(&footnote_osc8_start).count(), 1);
+        // The ids that aren't defined don't get a hyperlink.
+        assert_eq2!(output.matches(OSC8_END).count(), 5);
+        assert_eq2!(strip_ansi(&output), format!("{input}\n"));
+
+        global_hyperlink_support::clear_override();
+    }
+}
@@ -0,0 +1,663 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Renders a [MdDocument] into a standalone HTML document. The CSS comes from the current
+//! [MdTheme] (see [global_md_theme]), which is the same stylesheet that is used to
+//! highlight Markdown in the [editor], and code blocks are highlighted w/ syntect.
+//!
+//! Reference links, auto links and footnotes are resolved w/ [MdLinkResolver], and the
+//! footnotes are listed at the end of the document.
+
+use r3bl_ansi_color::TransformColor;
+use r3bl_rs_utils_core::*;
+use syntect::{highlighting::Theme, parsing::SyntaxSet};
+
+use crate::{constants::*, *};
+
+/// Parse the `input` Markdown text, and render it w/ [render_md_document_to_html].
+pub fn try_parse_and_render_markdown_to_html(
+    input: &str,
+    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+) -> CommonResult<String> {
+    match parse_markdown(input) {
+        Ok((_remainder, document)) => {
+            Ok(render_md_document_to_html(&document, maybe_syntect_tuple))
+        }
+        Err(_) => CommonError::new_err_with_only_type(CommonErrorType::ParsingError),
+    }
+}
+
+/// Consecutive lines of text are rolled up into a paragraph, and consecutive smart list
+/// items into a (nested) list. The [MdBlock::Title] is also used for the `<title>` of the
+/// HTML document.
+pub fn render_md_document_to_html(
+    document: &MdDocument<'_>,
+    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+) -> String {
+    let title = document
+        .iter()
+        .find_map(|block| match block {
+            MdBlock::Title(title) => Some(*title),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    let mut renderer = HtmlRenderer::new(document, maybe_syntect_tuple);
+    for block in document.iter() {
+        renderer.render_block(block);
+    }
+    let body = renderer.finish();
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        generate_md_theme_css(maybe_syntect_tuple.map(|(_, theme)| theme)),
+        body
+    )
+}
+
+/// Generate the CSS for the HTML elements that Markdown is rendered into, from the
+/// current [MdTheme]. If a syntect `Theme` is given, then its background color is used
+/// for the page and code blocks.
+pub fn generate_md_theme_css(maybe_syntect_theme: Option<&Theme>) -> String {
+    let mut acc = String::new();
+
+    let maybe_background = maybe_syntect_theme
+        .and_then(|theme| theme.settings.background)
+        .map(|it| format!("background-color: #{:02x}{:02x}{:02x}", it.r, it.g, it.b));
+    if let Some(background) = &maybe_background {
+        acc.push_str(&format!("html {{ {background} }}\n"));
+    }
+
+    for (selector, style) in [
+        ("body", get_foreground_style()),
+        ("strong", get_bold_style()),
+        ("em", get_italic_style()),
+        ("code", get_inline_code_style()),
+        ("pre code", get_code_block_content_style()),
+        ("del", get_strikethrough_style()),
+        ("a", get_link_text_style()),
+        ("li::marker", get_list_bullet_style()),
+        ("blockquote", get_block_quote_text_style()),
+        ("th", get_table_header_style()),
+        (".md-title", get_metadata_title_value_style()),
+        (
+            ".md-tags, .md-authors, .md-date",
+            get_metadata_tags_values_style(),
+        ),
+        (".md-footnote-back-ref", get_foreground_dim_style()),
+    ] {
+        acc.push_str(&format!(
+            "{selector} {{ {} }}\n",
+            convert_tui_style_into_css(&style)
+        ));
+    }
+
+    for (selector, style, property) in [
+        (
+            "blockquote",
+            get_block_quote_marker_style(),
+            "border-left: 3px solid",
+        ),
+        (
+            "table, th, td",
+            get_table_border_style(),
+            "border: 1px solid",
+        ),
+        (
+            "hr",
+            get_horizontal_rule_style(),
+            "border: 0; border-top: 1px solid",
+        ),
+    ] {
+        let color = style
+            .color_fg
+            .and_then(convert_tui_color_into_css)
+            .unwrap_or_else(|| "currentcolor".to_string());
+        acc.push_str(&format!("{selector} {{ {property} {color} }}\n"));
+    }
+    acc.push_str("blockquote { margin-left: 0; padding-left: 1em }\n");
+    acc.push_str("table { border-collapse: collapse }\n");
+    acc.push_str("th, td { padding: 0.25em 0.5em }\n");
+
+    // Each heading level uses its gradient from the theme.
+    let md_theme = global_md_theme::get();
+    for level in 1..=6 {
+        let Some(gradient) = md_theme.get_heading_gradient(level) else {
+            continue;
+        };
+        let Some(first_stop) = gradient.stops.first() else {
+            continue;
+        };
+        acc.push_str(&format!("h{level} {{ color: {first_stop}"));
+        if gradient.stops.len() > 1 {
+            acc.push_str(&format!(
+                "; background: linear-gradient(to right, {}); -webkit-background-clip: text; background-clip: text; -webkit-text-fill-color: transparent",
+                gradient.stops.join(", ")
+            ));
+        }
+        acc.push_str(" }\n");
+    }
+
+    if let Some(background) = &maybe_background {
+        acc.push_str(&format!("pre {{ {background}; padding: 0.5em }}\n"));
+    }
+
+    acc
+}
+
+/// Convert the colors and attributes of the `style` into CSS declarations, eg:
+/// `color: #ff0000; font-weight: bold`.
+pub fn convert_tui_style_into_css(style: &TuiStyle) -> String {
+    let mut acc = vec![];
+    if let Some(color) = style.color_fg.and_then(convert_tui_color_into_css) {
+        acc.push(format!("color: {color}"));
+    }
+    if let Some(color) = style.color_bg.and_then(convert_tui_color_into_css) {
+        acc.push(format!("background-color: {color}"));
+    }
+    if style.bold {
+        acc.push("font-weight: bold".to_string());
+    }
+    if style.italic {
+        acc.push("font-style: italic".to_string());
+    }
+    if style.dim {
+        acc.push("opacity: 0.6".to_string());
+    }
+    match (style.underline, style.strikethrough) {
+        (true, true) => acc.push("text-decoration: underline line-through".to_string()),
+        (true, false) => acc.push("text-decoration: underline".to_string()),
+        (false, true) => acc.push("text-decoration: line-through".to_string()),
+        (false, false) => {}
+    }
+    if style.hidden {
+        acc.push("visibility: hidden".to_string());
+    }
+    acc.join("; ")
+}
+
+/// [TuiColor::Reset] doesn't have a CSS color, since it is the default color.
+pub fn convert_tui_color_into_css(color: TuiColor) -> Option<String> {
+    if let TuiColor::Reset = color {
+        return None;
+    }
+    let rgb = color_wheel_color_converter::convert_tui_color_into_r3bl_ansi_color(color)
+        .as_rgb();
+    Some(format!("#{:02x}{:02x}{:02x}", rgb.red, rgb.green, rgb.blue))
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut acc = String::with_capacity(text.len());
+    for it in text.chars() {
+        match it {
+            '&' => acc.push_str("&amp;"),
+            '<' => acc.push_str("&lt;"),
+            '>' => acc.push_str("&gt;"),
+            '"' => acc.push_str("&quot;"),
+            '\'' => acc.push_str("&#39;"),
+            _ => acc.push(it),
+        }
+    }
+    acc
+}
+
+struct HtmlRenderer<'a, 'b> {
+    resolver: MdLinkResolver<'a>,
+    maybe_syntect_tuple: Option<(&'b SyntaxSet, &'b Theme)>,
+    acc: String,
+    is_paragraph_open: bool,
+    /// The indent & kind of each list that is open, each w/ an open `<li>`.
+    open_lists: Vec<(usize, BulletKind)>,
+}
+
+impl<'a, 'b> HtmlRenderer<'a, 'b> {
+    fn new(
+        document: &'a MdDocument<'a>,
+        maybe_syntect_tuple: Option<(&'b SyntaxSet, &'b Theme)>,
+    ) -> Self {
+        Self {
+            resolver: MdLinkResolver::new(document),
+            maybe_syntect_tuple,
+            acc: String::new(),
+            is_paragraph_open: false,
+            open_lists: vec![],
+        }
+    }
+
+    fn render_block(&mut self, block: &MdBlock<'a>) {
+        if !matches!(block, MdBlock::Text(_)) {
+            self.close_paragraph();
+        }
+        if !matches!(block, MdBlock::SmartList(_)) {
+            self.close_lists(None);
+        }
+
+        match block {
+            MdBlock::Title(title) => {
+                self.acc.push_str(&format!(
+                    "<p class=\"md-title\">{}</p>\n",
+                    escape_html(title)
+                ));
+            }
+            MdBlock::Date(date) => {
+                self.acc.push_str(&format!(
+                    "<p class=\"md-date\">{}</p>\n",
+                    escape_html(date)
+                ));
+            }
+            MdBlock::Tags(tags) => {
+                self.acc.push_str(&format!(
+                    "<p class=\"md-tags\">{}</p>\n",
+                    escape_html(&tags.join(", "))
+                ));
+            }
+            MdBlock::Authors(authors) => {
+                self.acc.push_str(&format!(
+                    "<p class=\"md-authors\">{}</p>\n",
+                    escape_html(&authors.join(", "))
+                ));
+            }
+            MdBlock::Heading(heading_data) => {
+                let level = heading_data.heading_level.level.clamp(1, 6);
+                self.acc.push_str(&format!(
+                    "<h{level}>{}</h{level}>\n",
+                    escape_html(heading_data.text)
+                ));
+            }
+            MdBlock::Text(fragments) => {
+                if fragments.is_empty() {
+                    self.close_paragraph();
+                    return;
+                }
+                if self.is_paragraph_open {
+                    self.acc.push_str(NEW_LINE);
+                } else {
+                    self.acc.push_str("<p>");
+                    self.is_paragraph_open = true;
+                }
+                self.render_fragments(fragments);
+            }
+            MdBlock::SmartList((lines, bullet_kind, indent)) => {
+                self.open_list_item(*indent, *bullet_kind);
+                for (index, line) in lines.iter().enumerate() {
+                    if index > 0 {
+                        self.acc.push_str(NEW_LINE);
+                    }
+                    self.render_fragments(line);
+                }
+            }
+            MdBlock::CodeBlock(code_block_lines) => {
+                self.render_code_block(code_block_lines)
+            }
+            MdBlock::BlockQuote(block_quote_lines) => {
+                self.render_block_quote(block_quote_lines)
+            }
+            MdBlock::Table(table_data) => self.render_table(table_data),
+            MdBlock::HorizontalRule(_) => self.acc.push_str("<hr>\n"),
+            // These are rendered where they are used.
+            MdBlock::LinkDefinition(_) | MdBlock::FootnoteDefinition(_) => {}
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.close_paragraph();
+        self.close_lists(None);
+
+        let footnote_definitions =
+            self.resolver.get_footnote_definitions().collect::<Vec<_>>();
+        if !footnote_definitions.is_empty() {
+            self.acc
+                .push_str("<section class=\"md-footnotes\">\n<hr>\n<ol>\n");
+            for (id, fragments) in footnote_definitions {
+                let id = escape_html(id);
+                self.acc.push_str(&format!("<li id=\"md-footnote-{id}\">"));
+                self.render_fragments(fragments);
+                self.acc.push_str(&format!(
+                    " <a class=\"md-footnote-back-ref\" href=\"#md-footnote-ref-{id}\">↩</a></li>\n"
+                ));
+            }
+            self.acc.push_str("</ol>\n</section>\n");
+        }
+
+        self.acc
+    }
+
+    fn close_paragraph(&mut self) {
+        if self.is_paragraph_open {
+            self.acc.push_str("</p>\n");
+            self.is_paragraph_open = false;
+        }
+    }
+
+    fn get_list_tags(bullet_kind: BulletKind) -> (String, &'static str) {
+        match bullet_kind {
+            BulletKind::Ordered(1) => ("<ol>".to_string(), "</ol>"),
+            BulletKind::Ordered(start) => (format!("<ol start=\"{start}\">"), "</ol>"),
+            BulletKind::Unordered => ("<ul>".to_string(), "</ul>"),
+        }
+    }
+
+    /// Close the lists that are indented more than `indent`, or all of them if it is
+    /// [None].
+    fn close_lists(&mut self, maybe_indent: Option<usize>) {
+        while let Some((open_indent, bullet_kind)) = self.open_lists.last().copied() {
+            if let Some(indent) = maybe_indent {
+                if open_indent <= indent {
+                    break;
+                }
+            }
+            self.acc.push_str("</li>\n");
+            self.acc.push_str(Self::get_list_tags(bullet_kind).1);
+            self.acc.push_str(NEW_LINE);
+            self.open_lists.pop();
+        }
+    }
+
+    fn open_list_item(&mut self, indent: usize, bullet_kind: BulletKind) {
+        self.close_lists(Some(indent));
+
+        let is_same_list = match self.open_lists.last() {
+            Some((open_indent, BulletKind::Ordered(_))) if *open_indent == indent => {
+                matches!(bullet_kind, BulletKind::Ordered(_))
+            }
+            Some((open_indent, BulletKind::Unordered)) if *open_indent == indent => {
+                matches!(bullet_kind, BulletKind::Unordered)
+            }
+            _ => false,
+        };
+
+        if is_same_list {
+            self.acc.push_str("</li>\n");
+        } else {
+            // A list of a different kind at the same indent.
+            if matches!(self.open_lists.last(), Some((it, _)) if *it == indent) {
+                self.close_lists(indent.checked_sub(1));
+            }
+            self.acc.push_str(&Self::get_list_tags(bullet_kind).0);
+            self.acc.push_str(NEW_LINE);
+            self.open_lists.push((indent, bullet_kind));
+        }
+        self.acc.push_str("<li>");
+    }
+
+    fn render_code_block(&mut self, code_block_lines: &CodeBlockLines<'_>) {
+        let maybe_language = code_block_lines.first().and_then(|it| it.language);
+        match maybe_language {
+            Some(language) => self.acc.push_str(&format!(
+                "<pre><code class=\"language-{}\">",
+                escape_html(language)
+            )),
+            None => self.acc.push_str("<pre><code>"),
+        }
+
+        // Same as the editor, syntect is used if the language is known.
+        let highlighted_lines = StyleUSSpanLines::from_block_codeblock(
+            code_block_lines,
+            &None,
+            self.maybe_syntect_tuple,
+        );
+        for (code_block_line, highlighted_line) in
+            code_block_lines.iter().zip(highlighted_lines.iter())
+        {
+            if let CodeBlockLineContent::Text(_) = code_block_line.content {
+                for span in highlighted_line.iter() {
+                    self.render_span(span);
+                }
+                self.acc.push_str(NEW_LINE);
+            }
+        }
+
+        self.acc.push_str("</code></pre>\n");
+    }
+
+    fn render_span(&mut self, span: &StyleUSSpan) {
+        let text = escape_html(span.text.string.trim_end_matches(NEW_LINE_CHAR));
+        let css = convert_tui_style_into_css(&span.style);
+        if css.is_empty() {
+            self.acc.push_str(&text);
+        } else {
+            self.acc
+                .push_str(&format!("<span style=\"{css}\">{text}</span>"));
+        }
+    }
+
+    fn render_block_quote(&mut self, block_quote_lines: &BlockQuoteLines<'_>) {
+        let mut depth = 0;
+        for line in block_quote_lines.iter() {
+            while depth < line.depth {
+                self.acc.push_str("<blockquote>\n");
+                depth += 1;
+            }
+            while depth > line.depth {
+                self.acc.push_str("</blockquote>\n");
+                depth -= 1;
+            }
+            if !line.fragments.is_empty() {
+                self.acc.push_str("<p>");
+                self.render_fragments(&line.fragments);
+                self.acc.push_str("</p>\n");
+            }
+        }
+        for _ in 0..depth {
+            self.acc.push_str("</blockquote>\n");
+        }
+    }
+
+    fn render_table(&mut self, table_data: &TableData<'_>) {
+        let get_alignment_attr = |index: usize| match table_data.alignment_row.get(index)
+        {
+            Some((TableColumnAlignment::Left, _)) => " style=\"text-align: left\"",
+            Some((TableColumnAlignment::Center, _)) => " style=\"text-align: center\"",
+            Some((TableColumnAlignment::Right, _)) => " style=\"text-align: right\"",
+            _ => "",
+        };
+
+        self.acc.push_str("<table>\n<thead>\n<tr>");
+        for (index, cell) in table_data.header_row.iter().enumerate() {
+            self.acc
+                .push_str(&format!("<th{}>", get_alignment_attr(index)));
+            self.render_fragments(cell);
+            self.acc.push_str("</th>");
+        }
+        self.acc.push_str("</tr>\n</thead>\n<tbody>\n");
+        for row in table_data.body_rows.iter() {
+            self.acc.push_str("<tr>");
+            for (index, cell) in row.iter().enumerate() {
+                self.acc
+                    .push_str(&format!("<td{}>", get_alignment_attr(index)));
+                self.render_fragments(cell);
+                self.acc.push_str("</td>");
+            }
+            self.acc.push_str("</tr>\n");
+        }
+        self.acc.push_str("</tbody>\n</table>\n");
+    }
+
+    fn render_fragments(&mut self, fragments: &MdLineFragments<'_>) {
+        for fragment in fragments.iter() {
+            self.render_fragment(fragment);
+        }
+    }
+
+    fn render_fragment(&mut self, fragment: &MdLineFragment<'_>) {
+        let it = match fragment {
+            MdLineFragment::Plain(text) => escape_html(text),
+            MdLineFragment::Bold(text) => {
+                format!("<strong>{}</strong>", escape_html(text))
+            }
+            MdLineFragment::Italic(text) => format!("<em>{}</em>", escape_html(text)),
+            MdLineFragment::InlineCode(text) => {
+                format!("<code>{}</code>", escape_html(text))
+            }
+            MdLineFragment::Strikethrough(text) => {
+                format!("<del>{}</del>", escape_html(text))
+            }
+            MdLineFragment::Image(HyperlinkData { text, url }) => format!(
+                "<img src=\"{}\" alt=\"{}\">",
+                escape_html(url),
+                escape_html(text)
+            ),
+            MdLineFragment::Link(_)
+            | MdLineFragment::ReferenceLink(_)
+            | MdLineFragment::AutoLink(_) => match self.resolver.resolve(fragment) {
+                Some(HyperlinkData { text, url }) => {
+                    format!("<a href=\"{}\">{}</a>", escape_html(url), escape_html(text))
+                }
+                // The reference link isn't defined, so show it as is.
+                None => escape_html(&fragment.pretty_print_debug()),
+            },
+            MdLineFragment::FootnoteReference(id) => {
+                match self.resolver.get_footnote_number(id) {
+                    Some(number) => {
+                        let id = escape_html(id);
+                        format!(
+                            "<sup><a id=\"md-footnote-ref-{id}\" href=\"#md-footnote-{id}\">{number}</a></sup>"
+                        )
+                    }
+                    None => escape_html(&fragment.pretty_print_debug()),
+                }
+            }
+            MdLineFragment::Checkbox(is_checked) => if *is_checked {
+                "<input type=\"checkbox\" disabled checked>"
+            } else {
+                "<input type=\"checkbox\" disabled>"
+            }
+            .to_string(),
+            // The bullets are rendered by the `<ol>` or `<ul>` list.
+            MdLineFragment::OrderedListBullet { .. }
+            | MdLineFragment::UnorderedListBullet { .. } => String::new(),
+        };
+        self.acc.push_str(&it);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use r3bl_rs_utils_core::assert_eq2;
+    use r3bl_rs_utils_macro::tui_style;
+
+    use super::*;
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq2!(
+            escape_html("<a href=\"x\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_convert_tui_style_into_css() {
+        let style = tui_style! {
+            attrib: [bold, italic]
+            color_fg: TuiColor::Rgb(RgbValue::from_u8(255, 0, 16))
+        };
+        assert_eq2!(
+            convert_tui_style_into_css(&style),
+            "color: #ff0010; font-weight: bold; font-style: italic"
+        );
+        assert_eq2!(convert_tui_style_into_css(&TuiStyle::default()), "");
+        assert_eq2!(convert_tui_color_into_css(TuiColor::Reset), None);
+    }
+
+    #[test]
+    fn test_render_md_document_to_html() {
+        let input = [
+            "@title: Notes & <more>",
+            "# Heading",
+            "first *line*",
+            "second [link](https://r3bl.com) & [ref][site][^1]",
+            "",
+            "- item 1",
+            "  - nested",
+            "- [x] done",
+            "1. one",
+            "2. two",
+            "> quote",
+            "> > nested quote",
+            "| a | b |",
+            "|:--|--:|",
+            "| c | `d` |",
+            "---",
+            "```",
+            "if a < b {}",
+            "```",
+            "<https://r3bl.com/a> and [missing][nope]",
+            "[site]: https://r3bl.com/site",
+            "[^1]: The ~~footnote~~.",
+        ]
+        .join("\n");
+        let html = try_parse_and_render_markdown_to_html(&input, None).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>\n<html>\n<head>\n"));
+        assert!(html.ends_with("</body>\n</html>\n"));
+        assert!(html.contains("<title>Notes &amp; &lt;more&gt;</title>"));
+        assert!(html.contains("<p class=\"md-title\">Notes &amp; &lt;more&gt;</p>\n"));
+        assert!(html.contains("<h1>Heading</h1>\n"));
+
+        // Consecutive lines of text are a paragraph.
+        assert!(html.contains(
+            "<p>first <strong>line</strong>\nsecond <a href=\"https://r3bl.com\">link</a> &amp; <a href=\"https://r3bl.com/site\">ref</a><sup><a id=\"md-footnote-ref-1\" href=\"#md-footnote-1\">1</a></sup></p>\n"
+        ));
+
+        // Lists.
+        assert!(html.contains(
+            "<ul>\n<li>item 1<ul>\n<li>nested</li>\n</ul>\n</li>\n<li><input type=\"checkbox\" disabled checked> done</li>\n</ul>\n<ol>\n<li>one</li>\n<li>two</li>\n</ol>\n"
+        ));
+
+        // Block quote.
+        assert!(html.contains(
+            "<blockquote>\n<p>quote</p>\n<blockquote>\n<p>nested quote</p>\n</blockquote>\n</blockquote>\n"
+        ));
+
+        // Table.
+        assert!(html.contains("<th style=\"text-align: left\"> a </th>"));
+        assert!(html.contains("<td style=\"text-align: right\"> <code>d</code> </td>"));
+
+        // Horizontal rule and code block.
+        assert!(html.contains("<hr>\n<pre><code>"));
+        assert!(html.contains("if a &lt; b {}</span>\n</code></pre>\n"));
+
+        // Auto links, and reference links that aren't defined.
+        assert!(html.contains(
+            "<p><a href=\"https://r3bl.com/a\">https://r3bl.com/a</a> and [missing][nope]</p>\n"
+        ));
+
+        // Footnotes are at the end, and definitions aren't rendered where they are.
+        assert!(!html.contains("[site]"));
+        assert!(html.contains(
+            "<section class=\"md-footnotes\">\n<hr>\n<ol>\n<li id=\"md-footnote-1\">The <del>footnote</del>. <a class=\"md-footnote-back-ref\" href=\"#md-footnote-ref-1\">↩</a></li>\n</ol>\n</section>\n</body>"
+        ));
+    }
+
+    #[test]
+    fn test_render_code_block_w_syntect() {
+        let syntax_set = SyntaxSet::load_defaults_newlines();
+        let theme = try_load_r3bl_theme().unwrap();
+        let input = ["```rust", "let a = 1;", "```"].join("\n");
+        let html =
+            try_parse_and_render_markdown_to_html(&input, Some((&syntax_set, &theme)))
+                .unwrap();
+
+        assert!(
+            html.contains("<pre><code class=\"language-rust\"><span style=\"color: #")
+        );
+        assert!(html.contains("let a = 1;</span>\n</code></pre>"));
+        assert!(html.contains("pre { background-color: #"));
+    }
+}
@@ -56,9 +56,11 @@ pub mod animator;
 pub mod color_wheel;
 pub mod dialog;
 pub mod editor;
+pub mod keymap;
 pub mod layout;
 pub mod lolcat;
 pub mod md_parser;
+pub mod md_renderer;
 pub mod misc_types;
 pub mod rsx;
 pub mod syntax_highlighting;
@@ -70,9 +72,11 @@ pub use animator::*;
 pub use color_wheel::*;
 pub use dialog::*;
 pub use editor::*;
+pub use keymap::*;
 pub use layout::*;
 pub use lolcat::*;
 pub use md_parser::*;
+pub use md_renderer::*;
 pub use misc_types::*;
 pub use rsx::*;
 pub use syntax_highlighting::*;
@@ -0,0 +1,796 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Incremental version of [try_parse_and_highlight], which the [editor] uses to render
+//! Markdown. Instead of parsing & highlighting the entire document on every render, the
+//! [StyleUSSpanLines] for each block are cached, and only the blocks that an edit touched
+//! are parsed again.
+//!
+//! # How blocks are tracked
+//!
+//! [parse_markdown()] parses one [MdBlock] after another, and each block only depends on
+//! the text from where it starts. So each cached block records the rows that it was
+//! parsed from. Most blocks end at the end of a row, when they don't (eg: a code block
+//! end tag that is followed by some text) then the blocks up to the end of that row are
+//! cached together.
+//!
+//! To find out where it ends, a block may look at the row after it (eg: a smart list
+//! checks whether the next row continues it). And a code block start tag that doesn't
+//! have an end tag is parsed as text, after looking at all the rows after it.
+//!
+//! # Invalidation
+//!
+//! [MdSynHiCache::invalidate] is called after each [EditorEvent] is applied. It compares
+//! the lines w/ the ones that were last highlighted, which is cheap since [EditorLines]
+//! share the chunks that haven't changed. Then it drops the cached blocks that contain
+//! the changed rows, or whose next row changed, or that depend on the rest of the
+//! document. The blocks after the changed rows are kept, and moved by the number of rows
+//! that were inserted or removed.
+//!
+//! Reference links and footnote references are resolved w/ the definitions in all the
+//! blocks (see [MdLinkResolver]). Each cached block records the definitions in it, and
+//! the url that each of its references was resolved to. When the definitions change, the
+//! blocks whose references resolve to a different url are dropped, and parsed again.
+//!
+//! # Parsing the gaps
+//!
+//! [MdSynHiCache::try_parse_and_highlight] parses the rows that aren't covered by a
+//! cached block, until it gets to the start of the next cached block. It only joins a
+//! few rows (in addition to the gap) into the text that is parsed. If a block gets too
+//! close to the end of this text to be sure where it ends, then it is parsed again w/
+//! twice as many rows.
+
+use r3bl_rs_utils_core::*;
+use syntect::{highlighting::Theme, parsing::SyntaxSet};
+
+use crate::{constants::*, *};
+
+/// Minimum number of rows that are joined into the text that is parsed to fill a gap.
+const MIN_WINDOW_ROW_COUNT: usize = 64;
+
+/// Number of rows after a block that are looked at to find out where it ends.
+const LOOKAHEAD_ROW_COUNT: usize = 1;
+
+/// Cached [StyleUSSpanLines] for each block of a Markdown document. See the [module
+/// docs](self) for details.
+#[derive(Clone, Debug, Default)]
+pub struct MdSynHiCache {
+    /// The lines that the blocks were parsed from. This is cheap to clone since the
+    /// chunks are shared w/ the [EditorBuffer].
+    maybe_source_lines: Option<EditorLines>,
+    maybe_current_box_computed_style: Option<TuiStyle>,
+    is_syntect_enabled: bool,
+    /// The [global_md_theme::get_generation] that the blocks were highlighted w/.
+    md_theme_generation: usize,
+    /// The [global_hyperlink_registry::get_generation] that the blocks were highlighted
+    /// w/, since their [TuiHyperlink]s don't have a url after it changes.
+    hyperlink_generation: usize,
+    /// The text of the definitions in all the `blocks`, which their references were
+    /// resolved w/.
+    definitions: String,
+    /// Sorted by `start_row`. There are gaps where rows have to be parsed again.
+    blocks: Vec<MdSynHiCacheBlock>,
+    /// Index of the first line of each block in `blocks`, followed by the total number
+    /// of lines.
+    line_index_offsets: Vec<usize>,
+    /// Number of rows that the last call to [MdSynHiCache::try_parse_and_highlight]
+    /// parsed.
+    last_parsed_row_count: usize,
+}
+
+/// One or more [MdBlock]s which start at the beginning of `start_row` and end at the end
+/// of a row.
+#[derive(Clone, Debug)]
+struct MdSynHiCacheBlock {
+    start_row: usize,
+    row_count: usize,
+    /// True if this block looked at all the rows after it, ie: a code block start tag
+    /// that doesn't have an end tag.
+    depends_on_rest_of_document: bool,
+    /// The text of the [MdBlock::LinkDefinition]s and [MdBlock::FootnoteDefinition]s in
+    /// this block.
+    definitions: String,
+    /// The reference links and footnote references in this block, along w/ the url that
+    /// each one was resolved to.
+    references: Vec<(MdLinkReference, Option<String>)>,
+    lines: StyleUSSpanLines,
+}
+
+impl MdSynHiCacheBlock {
+    fn end_row(&self) -> usize { self.start_row + self.row_count }
+}
+
+impl MdSynHiCache {
+    /// Drop all the cached blocks. This happens automatically when the [MdTheme] changes
+    /// (or the [global_hyperlink_registry] is cleared), but it has to be called if the
+    /// syntect [Theme] is changed some other way.
+    pub fn clear(&mut self) { *self = Self::default(); }
+
+    /// Drop the cached blocks that are affected by the difference between
+    /// `editor_text_lines` and the lines that were last highlighted. This does nothing if
+    /// nothing has been highlighted yet.
+    pub fn invalidate(&mut self, editor_text_lines: &EditorLines) {
+        let Some(old_lines) = self.maybe_source_lines.as_ref() else {
+            return;
+        };
+
+        let common_prefix_row_count = old_lines.get_common_prefix_len(editor_text_lines);
+        if common_prefix_row_count == old_lines.len()
+            && common_prefix_row_count == editor_text_lines.len()
+        {
+            return;
+        }
+        let max_common_row_count = old_lines.len().min(editor_text_lines.len());
+        let common_suffix_row_count = old_lines.get_common_suffix_len(
+            editor_text_lines,
+            max_common_row_count - common_prefix_row_count,
+        );
+
+        let changed_start_row = common_prefix_row_count;
+        let old_changed_end_row = old_lines.len() - common_suffix_row_count;
+        let new_changed_end_row = editor_text_lines.len() - common_suffix_row_count;
+
+        self.blocks.retain_mut(|block| {
+            if block.start_row >= old_changed_end_row {
+                block.start_row =
+                    block.start_row - old_changed_end_row + new_changed_end_row;
+                return true;
+            }
+            block.end_row() + LOOKAHEAD_ROW_COUNT <= changed_start_row
+                && !block.depends_on_rest_of_document
+        });
+        self.update_line_index_offsets();
+
+        self.maybe_source_lines = Some(editor_text_lines.clone());
+    }
+
+    /// Same as [try_parse_and_highlight], except that the result is stored in `self`
+    /// (use [MdSynHiCache::get] to access it), and only the blocks that aren't cached are
+    /// parsed.
+    pub fn try_parse_and_highlight(
+        &mut self,
+        editor_text_lines: &EditorLines,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    ) -> CommonResult<()> {
+        let is_syntect_enabled = maybe_syntect_tuple.is_some();
+        let md_theme_generation = global_md_theme::get_generation();
+        let hyperlink_generation = global_hyperlink_registry::get_generation();
+        if self.maybe_current_box_computed_style != *maybe_current_box_computed_style
+            || self.is_syntect_enabled != is_syntect_enabled
+            || self.md_theme_generation != md_theme_generation
+            || self.hyperlink_generation != hyperlink_generation
+        {
+            self.clear();
+        }
+        self.maybe_current_box_computed_style = *maybe_current_box_computed_style;
+        self.is_syntect_enabled = is_syntect_enabled;
+        self.md_theme_generation = md_theme_generation;
+        self.hyperlink_generation = hyperlink_generation;
+
+        self.invalidate(editor_text_lines);
+        self.maybe_source_lines = Some(editor_text_lines.clone());
+
+        self.last_parsed_row_count = 0;
+        if self.has_gaps(editor_text_lines) {
+            self.parse_gaps_and_update(
+                editor_text_lines,
+                maybe_current_box_computed_style,
+                maybe_syntect_tuple,
+            )?;
+            // Parsing the gaps again doesn't change the definitions.
+            if self.invalidate_references() {
+                self.parse_gaps_and_update(
+                    editor_text_lines,
+                    maybe_current_box_computed_style,
+                    maybe_syntect_tuple,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Number of [StyleUSSpanLine]s, which is the same as the number of rows, unless a
+    /// block ends in the middle of a row.
+    pub fn len(&self) -> usize { self.line_index_offsets.last().copied().unwrap_or(0) }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    pub fn get(&self, line_index: usize) -> Option<&StyleUSSpanLine> {
+        let block_index = self
+            .line_index_offsets
+            .partition_point(|it| *it <= line_index)
+            .checked_sub(1)?;
+        let block = self.blocks.get(block_index)?;
+        block
+            .lines
+            .get(line_index - self.line_index_offsets[block_index])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StyleUSSpanLine> {
+        self.blocks.iter().flat_map(|block| block.lines.iter())
+    }
+
+    fn has_gaps(&self, editor_text_lines: &EditorLines) -> bool {
+        let mut row = 0;
+        for block in &self.blocks {
+            if block.start_row != row {
+                return true;
+            }
+            row = block.end_row();
+        }
+        row != editor_text_lines.len()
+    }
+
+    fn update_line_index_offsets(&mut self) {
+        self.line_index_offsets.clear();
+        let mut line_index = 0;
+        for block in &self.blocks {
+            self.line_index_offsets.push(line_index);
+            line_index += block.lines.len();
+        }
+        self.line_index_offsets.push(line_index);
+    }
+
+    fn parse_gaps_and_update(
+        &mut self,
+        editor_text_lines: &EditorLines,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    ) -> CommonResult<()> {
+        let definitions = self.definitions.clone();
+        let definitions_document = parse_definitions(&definitions);
+        if let Err(error) = self.parse_gaps(
+            editor_text_lines,
+            &MdLinkResolver::new(&definitions_document),
+            maybe_current_box_computed_style,
+            maybe_syntect_tuple,
+        ) {
+            self.clear();
+            return Err(error);
+        }
+        self.update_line_index_offsets();
+        Ok(())
+    }
+
+    /// Collect the definitions from all the blocks. If they changed, then drop the blocks
+    /// that have a reference which resolves to a different url now. Returns true if any
+    /// block was dropped.
+    fn invalidate_references(&mut self) -> bool {
+        let definitions = self
+            .blocks
+            .iter()
+            .map(|it| it.definitions.as_str())
+            .collect::<String>();
+        if definitions == self.definitions {
+            return false;
+        }
+
+        let definitions_document = parse_definitions(&definitions);
+        let resolver = MdLinkResolver::new(&definitions_document);
+        let block_count = self.blocks.len();
+        self.blocks.retain(|block| {
+            block
+                .references
+                .iter()
+                .all(|(reference, url)| resolver.resolve_reference(reference) == *url)
+        });
+        self.update_line_index_offsets();
+
+        self.definitions = definitions;
+        self.blocks.len() != block_count
+    }
+
+    fn parse_gaps(
+        &mut self,
+        editor_text_lines: &EditorLines,
+        resolver: &MdLinkResolver<'_>,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+        maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+    ) -> CommonResult<()> {
+        let row_count = editor_text_lines.len();
+        let mut old_blocks = std::mem::take(&mut self.blocks).into_iter().peekable();
+        let mut window_row_count = MIN_WINDOW_ROW_COUNT;
+        let mut row = 0;
+
+        'outer: while row < row_count {
+            // Drop the cached blocks that a block which was just parsed has grown over.
+            while old_blocks.next_if(|it| it.start_row < row).is_some() {}
+
+            if let Some(block) = old_blocks.next_if(|it| it.start_row == row) {
+                row = block.end_row();
+                self.blocks.push(block);
+                continue;
+            }
+
+            let gap_end_row = old_blocks.peek().map_or(row_count, |it| it.start_row);
+            let window_end_row = row_count
+                .min((row + window_row_count).max(gap_end_row + LOOKAHEAD_ROW_COUNT));
+            let is_end_of_document = window_end_row == row_count;
+            let window_text = join_rows(editor_text_lines, row, window_end_row);
+
+            let window_start_row = row;
+            let mut input = window_text.as_str();
+            while row < gap_end_row {
+                let Some((remainder, block)) = parse_block(
+                    input,
+                    row,
+                    resolver,
+                    maybe_current_box_computed_style,
+                    maybe_syntect_tuple,
+                )?
+                else {
+                    // Same as [parse_markdown], the rest of the document isn't
+                    // highlighted.
+                    break 'outer;
+                };
+
+                let is_end_known = is_end_of_document
+                    || (!block.depends_on_rest_of_document
+                        && block.end_row() + LOOKAHEAD_ROW_COUNT <= window_end_row);
+                if !is_end_known {
+                    break;
+                }
+
+                self.last_parsed_row_count += block.row_count;
+                row = block.end_row();
+                input = remainder;
+                self.blocks.push(block);
+            }
+
+            // Parse the block again w/ more rows, if it needed them.
+            window_row_count = if row == window_start_row {
+                window_row_count * 2
+            } else {
+                MIN_WINDOW_ROW_COUNT
+            };
+        }
+
+        Ok(())
+    }
+}
+
+impl PrettyPrintDebug for MdSynHiCache {
+    fn pretty_print_debug(&self) -> String {
+        self.iter()
+            .map(|line| line.pretty_print_debug())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Join `[start_row, end_row)` into a string the same way as [try_parse_and_highlight].
+fn join_rows(
+    editor_text_lines: &EditorLines,
+    start_row: usize,
+    end_row: usize,
+) -> String {
+    let mut acc = String::new();
+    for row_index in start_row..end_row {
+        if let Some(line) = editor_text_lines.get_str(row_index) {
+            acc.push_str(line);
+        }
+        acc.push_str(NEW_LINE);
+    }
+    acc
+}
+
+/// Parse the text of the definitions that [MdSynHiCacheBlock] collects, which are each on
+/// their own line.
+fn parse_definitions(definitions: &str) -> MdDocument<'_> {
+    match parse_markdown(definitions) {
+        Ok((_remainder, document)) => document,
+        Err(_) => List::from(vec![]),
+    }
+}
+
+/// Parse the [MdBlock]s from the start of `input` up to the end of a row. Returns
+/// [None] if `input` can't be parsed.
+fn parse_block<'a>(
+    input: &'a str,
+    start_row: usize,
+    resolver: &MdLinkResolver<'_>,
+    maybe_current_box_computed_style: &Option<TuiStyle>,
+    maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
+) -> CommonResult<Option<(&'a str, MdSynHiCacheBlock)>> {
+    let mut remainder = input;
+    let mut lines = StyleUSSpanLines::default();
+    let mut depends_on_rest_of_document = false;
+    let mut definitions = String::new();
+    let mut references = vec![];
+
+    loop {
+        let Ok((new_remainder, md_block)) = parse_markdown_block(remainder) else {
+            return Ok(None);
+        };
+
+        // Same as the check that `many0()` does in [parse_markdown].
+        if new_remainder.len() == remainder.len() {
+            return CommonError::new_err_with_only_type(CommonErrorType::ParsingError);
+        }
+
+        if remainder.starts_with(CODE_BLOCK_START_PARTIAL)
+            && !matches!(md_block, MdBlock::CodeBlock(_))
+        {
+            depends_on_rest_of_document = true;
+        }
+
+        let md_block_text = &remainder[..remainder.len() - new_remainder.len()];
+        if matches!(
+            md_block,
+            MdBlock::LinkDefinition(_) | MdBlock::FootnoteDefinition(_)
+        ) {
+            definitions.push_str(md_block_text);
+            if !md_block_text.ends_with(NEW_LINE) {
+                definitions.push_str(NEW_LINE);
+            }
+        }
+        references.extend(get_md_block_references(&md_block).into_iter().map(
+            |reference| {
+                let url = resolver.resolve_reference(&reference);
+                (reference, url)
+            },
+        ));
+
+        lines.inner.extend(
+            StyleUSSpanLines::from_block(
+                &md_block,
+                resolver,
+                maybe_current_box_computed_style,
+                maybe_syntect_tuple,
+            )
+            .inner,
+        );
+
+        let is_end_of_row = md_block_text.ends_with(NEW_LINE) || new_remainder.is_empty();
+        remainder = new_remainder;
+        if is_end_of_row {
+            break;
+        }
+    }
+
+    let consumed = &input[..input.len() - remainder.len()];
+    let block = MdSynHiCacheBlock {
+        start_row,
+        row_count: consumed.matches(NEW_LINE).count(),
+        depends_on_rest_of_document,
+        definitions,
+        references,
+        lines,
+    };
+
+    Ok(Some((remainder, block)))
+}
+
+#[cfg(test)]
+mod tests_md_syn_hi_cache {
+    use r3bl_rs_utils_macro::tui_style;
+
+    use super::*;
+
+    fn get_test_lines() -> Vec<&'static str> {
+        vec![
+            "@title: Something",
+            "@tags: tag1, tag2",
+            "# Foobar",
+            "",
+            "Foobar is a *Python* library.",
+            "- ul1",
+            "  ul1 continued",
+            "- ul2",
+            "1. ol1",
+            "```rust",
+            "fn main() {}",
+            "```",
+            "## Installation",
+            "Use [pip](https://pip.pypa.io/en/stable/) to install foobar.",
+            "```bash",
+            "pip install foobar",
+            "```",
+            "end",
+        ]
+    }
+
+    fn make_lines(lines: &[&str]) -> EditorLines {
+        lines
+            .iter()
+            .map(|it| US::new(it))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    /// Check that `cache` has the same lines as [try_parse_and_highlight].
+    fn assert_same_as_full_parse(
+        cache: &mut MdSynHiCache,
+        editor_text_lines: &EditorLines,
+        editor_engine: &EditorEngine,
+    ) {
+        let maybe_style = Some(TuiStyle::default());
+        let syntect_tuple = Some((&editor_engine.syntax_set, &editor_engine.theme));
+
+        cache
+            .try_parse_and_highlight(editor_text_lines, &maybe_style, syntect_tuple)
+            .unwrap();
+        let expected =
+            try_parse_and_highlight(editor_text_lines, &maybe_style, syntect_tuple)
+                .unwrap();
+
+        assert_eq2!(cache.len(), expected.len());
+        assert_eq2!(
+            cache.iter().collect::<Vec<_>>(),
+            expected.iter().collect::<Vec<_>>()
+        );
+        for (line_index, line) in expected.iter().enumerate() {
+            assert_eq2!(cache.get(line_index), Some(line));
+        }
+        assert_eq2!(cache.get(expected.len()), None);
+    }
+
+    fn set_line(editor_text_lines: &mut EditorLines, row_index: usize, text: &str) {
+        *editor_text_lines.get_mut(row_index).unwrap() = US::new(text);
+    }
+
+    #[test]
+    fn test_edits_match_full_parse() {
+        let editor_engine = EditorEngine::default();
+        let mut cache = MdSynHiCache::default();
+        let mut lines = make_lines(&get_test_lines());
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.last_parsed_row_count, lines.len());
+
+        // Nothing changed.
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.last_parsed_row_count, 0);
+
+        // Edit a line of text.
+        set_line(&mut lines, 4, "Foobar is a **Python** library.");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Edit a line in a code block.
+        set_line(&mut lines, 10, "fn main() { println!(\"hi\"); }");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Continue a list on the next line.
+        lines.insert(8, US::new("  ul2 continued"));
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Remove the end tag of a code block, so that it is parsed as text.
+        lines.remove(12);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Put it back.
+        lines.insert(12, US::new("```"));
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Start a code block at the top, that ends at the next end tag.
+        lines.insert(3, US::new("```"));
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        lines.remove(3);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // End tag that is followed by text on the same line.
+        set_line(&mut lines, 12, "```foo");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        set_line(&mut lines, 12, "```");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Delete a few lines in the middle.
+        lines.splice(5..9, vec![]);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Change the metadata.
+        set_line(&mut lines, 0, "@title: Something else");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Add a line at the end & then empty the document.
+        lines.push(US::new("- new list"));
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        lines.clear();
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.is_empty(), true);
+    }
+
+    #[test]
+    fn test_quote_and_table_edits_match_full_parse() {
+        let editor_engine = EditorEngine::default();
+        let mut cache = MdSynHiCache::default();
+        let mut lines = make_lines(&[
+            "> quote",
+            "> > nested",
+            "after quote",
+            "| a | b |",
+            "| c | d |",
+            "---",
+            "end",
+        ]);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Continue the quote on the next line.
+        set_line(&mut lines, 2, "> after quote");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Add an alignment row after the header row, so that the rows become a table.
+        lines.insert(4, US::new("|---|--:|"));
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Make the rule a row of the table.
+        set_line(&mut lines, 6, "|---|");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        set_line(&mut lines, 6, "---");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Break the alignment row.
+        set_line(&mut lines, 4, "|---|--:");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        lines.remove(4);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+    }
+
+    #[test]
+    fn test_link_definition_edits_match_full_parse() {
+        let editor_engine = EditorEngine::default();
+        let mut cache = MdSynHiCache::default();
+        let mut lines = make_lines(&[
+            "See [r3bl][site] and https://r3bl.com[^1]",
+            "[site]: https://r3bl.com",
+            "[^1]: A *footnote*",
+        ]);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Break, and then fix, the definitions.
+        set_line(&mut lines, 1, "[site]: https://r3bl.com foo");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        set_line(&mut lines, 2, "[^1] A *footnote*");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        set_line(&mut lines, 1, "[site]: https://r3bl.com");
+        set_line(&mut lines, 2, "[^1]: A *footnote*");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Turn the bare url into a bracketed one.
+        set_line(&mut lines, 0, "See [r3bl][site] and <https://r3bl.com[^1]");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        set_line(&mut lines, 0, "See [r3bl][site] and <https://r3bl.com>[^1]");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+    }
+
+    #[test]
+    fn test_definition_edits_invalidate_references() {
+        let editor_engine = EditorEngine::default();
+        let mut cache = MdSynHiCache::default();
+        let mut test_lines = vec!["See [r3bl][site][^1]"];
+        test_lines.extend(get_test_lines().repeat(10));
+        test_lines.extend(["[^1]: A *footnote*", "[site]: https://r3bl.com"]);
+        let mut lines = make_lines(&test_lines);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        let get_hyperlinks = |cache: &MdSynHiCache| {
+            cache
+                .get(0)
+                .unwrap()
+                .iter()
+                .filter_map(|it| it.style.hyperlink)
+                .collect::<Vec<_>>()
+        };
+        assert_eq2!(
+            get_hyperlinks(&cache),
+            vec![
+                TuiHyperlink::new("https://r3bl.com"),
+                TuiHyperlink::new("https://r3bl.com"),
+                TuiHyperlink::new("#md-footnote-1"),
+            ]
+        );
+
+        // Change the url. The definition (and the row before it) are parsed, and then
+        // the row that references it.
+        let last_row_index = lines.len() - 1;
+        set_line(&mut lines, last_row_index, "[site]: https://r3bl.com/new");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.last_parsed_row_count, 3);
+        assert_eq2!(
+            get_hyperlinks(&cache)[0],
+            TuiHyperlink::new("https://r3bl.com/new")
+        );
+
+        // Remove the footnote definition.
+        lines.remove(last_row_index - 1);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(get_hyperlinks(&cache).len(), 2);
+
+        // The first definition of an id wins. So one after it only parses the rows
+        // around it, and one before it changes what the references resolve to.
+        lines.push(US::new("[SITE]: https://ignored.com"));
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.last_parsed_row_count, 2);
+        lines.insert(5, US::new("[Site]: https://r3bl.com/first"));
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(
+            get_hyperlinks(&cache)[0],
+            TuiHyperlink::new("https://r3bl.com/first")
+        );
+    }
+
+    #[test]
+    fn test_only_changed_blocks_are_parsed() {
+        let editor_engine = EditorEngine::default();
+        let mut cache = MdSynHiCache::default();
+        let mut lines = make_lines(&get_test_lines().repeat(100));
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.last_parsed_row_count, lines.len());
+
+        // Edit a line of text, the row before it is also parsed since its block may have
+        // looked at this row.
+        set_line(&mut lines, 904, "Foobar is a library.");
+        cache.invalidate(&lines);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.last_parsed_row_count, 2);
+
+        // Insert a line in a code block, the entire code block is parsed.
+        lines.insert(910, US::new("let x = 1;"));
+        cache.invalidate(&lines);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.last_parsed_row_count, 4);
+    }
+
+    #[test]
+    fn test_long_code_block() {
+        let editor_engine = EditorEngine::default();
+        let mut cache = MdSynHiCache::default();
+        let mut lines = vec!["# Code"];
+        lines.extend(["text"; 500]);
+        lines.extend(["```", "end"]);
+        let mut lines = make_lines(&lines);
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        // Start a code block that is longer than the rows that are parsed at first.
+        set_line(&mut lines, 0, "```rust");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.last_parsed_row_count, 502);
+
+        set_line(&mut lines, 200, "let x = 1;");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.last_parsed_row_count, 502);
+
+        // Without an end tag, the start tag depends on the rest of the document.
+        set_line(&mut lines, 501, "``");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        set_line(&mut lines, 502, "more");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+        assert_eq2!(cache.last_parsed_row_count, 3);
+
+        set_line(&mut lines, 501, "```");
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+    }
+
+    #[test]
+    fn test_style_change_clears_cache() {
+        let editor_engine = EditorEngine::default();
+        let mut cache = MdSynHiCache::default();
+        let lines = make_lines(&get_test_lines());
+        assert_same_as_full_parse(&mut cache, &lines, &editor_engine);
+
+        let maybe_style = Some(tui_style! { attrib: [bold] });
+        cache
+            .try_parse_and_highlight(&lines, &maybe_style, None)
+            .unwrap();
+        assert_eq2!(cache.last_parsed_row_count, lines.len());
+        let expected = try_parse_and_highlight(&lines, &maybe_style, None).unwrap();
+        assert_eq2!(
+            cache.iter().collect::<Vec<_>>(),
+            expected.iter().collect::<Vec<_>>()
+        );
+    }
+}
@@ -32,18 +32,18 @@ use crate::{constants::*, *};
 /// - `editor_text` - The text that the user has typed into the editor.
 /// - `current_box_computed_style` - The computed style of the box that the editor is in.
 pub fn try_parse_and_highlight(
-    editor_text_lines: &Vec<US>,
+    editor_text_lines: &EditorLines,
     maybe_current_box_computed_style: &Option<TuiStyle>,
     maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
 ) -> CommonResult<StyleUSSpanLines> {
     // Convert the editor text into a string.
     let editor_text_to_string = {
-        let mut line_to_str_acc = Vec::<&str>::new();
-        for line in editor_text_lines {
-            line_to_str_acc.push(line.string.as_str());
-            line_to_str_acc.push("\n");
+        let mut acc = String::new();
+        for line in editor_text_lines.iter_str() {
+            acc.push_str(line);
+            acc.push('\n');
         }
-        line_to_str_acc.join("")
+        acc
     };
 
     // BOOKM: Parse markdown from editor and render it
@@ -67,7 +67,8 @@ mod tests_try_parse_and_highlight {
     #[test]
     fn from_vec_us() -> CommonResult<()> {
         throws!({
-            let editor_text_lines = vec![US::new("Hello"), US::new("World")];
+            let editor_text_lines: EditorLines =
+                vec![US::new("Hello"), US::new("World")].into();
             let current_box_computed_style = tui_style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
@@ -119,10 +120,12 @@ impl StyleUSSpanLines {
         maybe_current_box_computed_style: &Option<TuiStyle>,
         maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
     ) -> Self {
+        let resolver = MdLinkResolver::new(document);
         let mut lines = StyleUSSpanLines::default();
         for block in document.iter() {
             let block_to_lines = StyleUSSpanLines::from_block(
                 block,
+                &resolver,
                 maybe_current_box_computed_style,
                 maybe_syntect_tuple,
             );
@@ -294,6 +297,7 @@ impl StyleUSSpanLines {
 
     pub fn from_block_smart_list(
         input_ul_lines: &Lines<'_>,
+        resolver: &MdLinkResolver<'_>,
         maybe_current_box_computed_style: &Option<TuiStyle>,
     ) -> Self {
         let mut acc_lines_output = StyleUSSpanLines::default();
@@ -304,6 +308,7 @@ impl StyleUSSpanLines {
 
             let postfix_span_list = StyleUSSpanLine::from_fragments(
                 input_line,
+                resolver,
                 maybe_current_box_computed_style,
             );
 
@@ -315,11 +320,100 @@ impl StyleUSSpanLines {
         acc_lines_output
     }
 
-    /// Each [MdBlock] needs to be translated into a line. The [MdBlock::CodeBlock] is
-    /// the only block that needs to be translated into multiple lines. This is why the return type
-    /// is a [StyleUSSpanLines] (and not a single line).
+    /// Each `>` in the prefix of a line is displayed as [BLOCK_QUOTE_DISPLAY], so that
+    /// each level of nesting shows up as a bar.
+    pub fn from_block_quote(
+        block_quote_lines: &BlockQuoteLines<'_>,
+        resolver: &MdLinkResolver<'_>,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+    ) -> Self {
+        let mut acc_lines_output = StyleUSSpanLines::default();
+
+        for block_quote_line in block_quote_lines.iter() {
+            let mut acc_line_output = StyleUSSpanLine::default();
+
+            let prefix = block_quote_line
+                .prefix
+                .replace(BLOCK_QUOTE_CHAR, BLOCK_QUOTE_DISPLAY);
+            acc_line_output += StyleUSSpan::new(
+                maybe_current_box_computed_style.unwrap_or_default()
+                    + get_block_quote_marker_style(),
+                US::from(prefix),
+            );
+
+            acc_line_output += StyleUSSpanLine::from_fragments_with_plain_text_style(
+                &block_quote_line.fragments,
+                resolver,
+                maybe_current_box_computed_style,
+                get_block_quote_text_style(),
+            );
+
+            acc_lines_output += acc_line_output;
+        }
+
+        acc_lines_output
+    }
+
+    /// Each row of the table is a line. The cells are not padded or aligned, so that each
+    /// line is the same width as the text that it comes from.
+    pub fn from_block_table(
+        table_data: &TableData<'_>,
+        resolver: &MdLinkResolver<'_>,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+    ) -> Self {
+        let border_style = maybe_current_box_computed_style.unwrap_or_default()
+            + get_table_border_style();
+        let border_span = StyleUSSpan::new(border_style, US::from(TABLE_PIPE));
+        let mut acc_lines_output = StyleUSSpanLines::default();
+
+        // Header row.
+        let mut acc_line_output = StyleUSSpanLine::default();
+        for cell in table_data.header_row.iter() {
+            acc_line_output += border_span.clone();
+            acc_line_output += StyleUSSpanLine::from_fragments_with_plain_text_style(
+                cell,
+                resolver,
+                maybe_current_box_computed_style,
+                get_table_header_style(),
+            );
+        }
+        acc_line_output += border_span.clone();
+        acc_lines_output += acc_line_output;
+
+        // Alignment row.
+        let mut acc_line_output = StyleUSSpanLine::default();
+        for (_alignment, cell) in table_data.alignment_row.iter() {
+            acc_line_output += border_span.clone();
+            acc_line_output += StyleUSSpan::new(border_style, US::from(*cell));
+        }
+        acc_line_output += border_span.clone();
+        acc_lines_output += acc_line_output;
+
+        // Body rows.
+        for row in table_data.body_rows.iter() {
+            let mut acc_line_output = StyleUSSpanLine::default();
+            for cell in row.iter() {
+                acc_line_output += border_span.clone();
+                acc_line_output += StyleUSSpanLine::from_fragments(
+                    cell,
+                    resolver,
+                    maybe_current_box_computed_style,
+                );
+            }
+            acc_line_output += border_span.clone();
+            acc_lines_output += acc_line_output;
+        }
+
+        acc_lines_output
+    }
+
+    /// Each [MdBlock] needs to be translated into a line. The [MdBlock::CodeBlock],
+    /// [MdBlock::SmartList], [MdBlock::BlockQuote] and [MdBlock::Table] blocks need to
+    /// be translated into multiple lines. This is why the return type is a
+    /// [StyleUSSpanLines] (and not a single line).
     pub fn from_block(
         block: &MdBlock<'_>,
+        resolver: &MdLinkResolver<'_>,
         maybe_current_box_computed_style: &Option<TuiStyle>,
         maybe_syntect_tuple: Option<(&SyntaxSet, &Theme)>,
     ) -> Self {
@@ -363,12 +457,14 @@ impl StyleUSSpanLines {
             MdBlock::Text(fragments_in_one_line) => {
                 lines.push(StyleUSSpanLine::from_fragments(
                     fragments_in_one_line,
+                    resolver,
                     maybe_current_box_computed_style,
                 ))
             }
             MdBlock::SmartList((list_lines, _bullet_kind, _indent)) => {
                 lines += StyleUSSpanLines::from_block_smart_list(
                     list_lines,
+                    resolver,
                     maybe_current_box_computed_style,
                 );
             }
@@ -379,6 +475,55 @@ impl StyleUSSpanLines {
                     maybe_syntect_tuple,
                 );
             }
+            MdBlock::BlockQuote(block_quote_lines) => {
+                lines += StyleUSSpanLines::from_block_quote(
+                    block_quote_lines,
+                    resolver,
+                    maybe_current_box_computed_style,
+                );
+            }
+            MdBlock::Table(table_data) => {
+                lines += StyleUSSpanLines::from_block_table(
+                    table_data,
+                    resolver,
+                    maybe_current_box_computed_style,
+                );
+            }
+            MdBlock::HorizontalRule(rule) => {
+                lines.push(StyleUSSpanLine::from_horizontal_rule(
+                    rule,
+                    maybe_current_box_computed_style,
+                ));
+            }
+            MdBlock::LinkDefinition(LinkDefinitionData { id, url }) => {
+                lines.push(List::from(StyleUSSpan::format_link_parts(
+                    &[
+                        (LinkPart::Delim, LEFT_BRACKET),
+                        (LinkPart::Text, id),
+                        (LinkPart::Delim, DEFINITION_SEPARATOR),
+                        (LinkPart::Url, url),
+                    ],
+                    Some(TuiHyperlink::new(url)),
+                    maybe_current_box_computed_style,
+                )));
+            }
+            MdBlock::FootnoteDefinition(FootnoteDefinitionData { id, fragments }) => {
+                let mut line = List::from(StyleUSSpan::format_link_parts(
+                    &[
+                        (LinkPart::Delim, LEFT_FOOTNOTE),
+                        (LinkPart::Text, id),
+                        (LinkPart::Delim, DEFINITION_SEPARATOR),
+                    ],
+                    None,
+                    maybe_current_box_computed_style,
+                ));
+                line += StyleUSSpanLine::from_fragments(
+                    fragments,
+                    resolver,
+                    maybe_current_box_computed_style,
+                );
+                lines.push(line);
+            }
         }
 
         lines
@@ -390,7 +535,45 @@ enum HyperlinkType {
     Link,
 }
 
+/// The parts of a link that are styled differently, eg: in `[text][id]` the brackets are
+/// [LinkPart::Delim], `text` is [LinkPart::Text], and `id` is [LinkPart::Url] since it
+/// stands in for the url.
+enum LinkPart {
+    Delim,
+    Text,
+    Url,
+}
+
 impl StyleUSSpan {
+    /// Reference links, auto links, footnotes and their definitions are styled just like
+    /// [MdLineFragment::Link], whether or not they can be resolved by [MdLinkResolver].
+    /// Only the ones that are resolved get a `maybe_hyperlink`, which is the only part of
+    /// the styling of a block that depends on the rest of the document (see
+    /// [MdSynHiCache] for how this is invalidated).
+    fn format_link_parts(
+        parts: &[(LinkPart, &str)],
+        maybe_hyperlink: Option<TuiHyperlink>,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+    ) -> Vec<Self> {
+        let hyperlink_style = TuiStyle {
+            hyperlink: maybe_hyperlink,
+            ..Default::default()
+        };
+        parts
+            .iter()
+            .filter(|(_, text)| !text.is_empty())
+            .map(|(part, text)| {
+                let style = maybe_current_box_computed_style.unwrap_or_default()
+                    + match part {
+                        LinkPart::Delim => get_foreground_dim_style(),
+                        LinkPart::Text => get_link_text_style() + hyperlink_style,
+                        LinkPart::Url => get_link_url_style() + hyperlink_style,
+                    };
+                StyleUSSpan::new(style, US::from(*text))
+            })
+            .collect()
+    }
+
     fn format_hyperlink_data(
         link_data: &HyperlinkData<'_>,
         maybe_current_box_computed_style: &Option<TuiStyle>,
@@ -402,11 +585,19 @@ impl StyleUSSpan {
         let base_style = maybe_current_box_computed_style.unwrap_or_default()
             + get_foreground_dim_style();
 
-        let link_text_style =
-            maybe_current_box_computed_style.unwrap_or_default() + get_link_text_style();
+        // The link text and url are clickable.
+        let hyperlink_style = TuiStyle {
+            hyperlink: Some(TuiHyperlink::new(link_data.url)),
+            ..Default::default()
+        };
+
+        let link_text_style = maybe_current_box_computed_style.unwrap_or_default()
+            + get_link_text_style()
+            + hyperlink_style;
 
-        let link_url_style =
-            maybe_current_box_computed_style.unwrap_or_default() + get_link_url_style();
+        let link_url_style = maybe_current_box_computed_style.unwrap_or_default()
+            + get_link_url_style()
+            + hyperlink_style;
 
         vec![
             // [link_text] or ![link_text]
@@ -441,6 +632,7 @@ impl StyleUSSpan {
     ///    [StyleUSSpanLine::from_block](StyleUSSpanLine::from_block).
     pub fn from_fragment(
         fragment: &MdLineFragment<'_>,
+        resolver: &MdLinkResolver<'_>,
         maybe_current_box_computed_style: &Option<TuiStyle>,
     ) -> Vec<Self> {
         match fragment {
@@ -532,6 +724,24 @@ impl StyleUSSpan {
                 ),
             ],
 
+            MdLineFragment::Strikethrough(strikethrough_text) => vec![
+                StyleUSSpan::new(
+                    maybe_current_box_computed_style.unwrap_or_default()
+                        + get_foreground_dim_style(),
+                    US::from(TILDE_TILDE),
+                ),
+                StyleUSSpan::new(
+                    maybe_current_box_computed_style.unwrap_or_default()
+                        + get_strikethrough_style(),
+                    US::from(*strikethrough_text),
+                ),
+                StyleUSSpan::new(
+                    maybe_current_box_computed_style.unwrap_or_default()
+                        + get_foreground_dim_style(),
+                    US::from(TILDE_TILDE),
+                ),
+            ],
+
             MdLineFragment::Link(link_data) => Self::format_hyperlink_data(
                 link_data,
                 maybe_current_box_computed_style,
@@ -544,6 +754,53 @@ impl StyleUSSpan {
                 HyperlinkType::Image,
             ),
 
+            MdLineFragment::ReferenceLink(it @ ReferenceLinkData { text, id }) => {
+                Self::format_link_parts(
+                    &[
+                        (LinkPart::Delim, LEFT_BRACKET),
+                        (LinkPart::Text, text),
+                        (LinkPart::Delim, RIGHT_BRACKET),
+                        (LinkPart::Delim, LEFT_BRACKET),
+                        (LinkPart::Url, id),
+                        (LinkPart::Delim, RIGHT_BRACKET),
+                    ],
+                    resolver.get_link_url(it.get_id()).map(TuiHyperlink::new),
+                    maybe_current_box_computed_style,
+                )
+            }
+
+            MdLineFragment::AutoLink(AutoLinkData { url, is_bracketed }) => {
+                if *is_bracketed {
+                    Self::format_link_parts(
+                        &[
+                            (LinkPart::Delim, LEFT_ANGLE_BRACKET),
+                            (LinkPart::Url, url),
+                            (LinkPart::Delim, RIGHT_ANGLE_BRACKET),
+                        ],
+                        Some(TuiHyperlink::new(url)),
+                        maybe_current_box_computed_style,
+                    )
+                } else {
+                    Self::format_link_parts(
+                        &[(LinkPart::Url, url)],
+                        Some(TuiHyperlink::new(url)),
+                        maybe_current_box_computed_style,
+                    )
+                }
+            }
+
+            MdLineFragment::FootnoteReference(id) => Self::format_link_parts(
+                &[
+                    (LinkPart::Delim, LEFT_FOOTNOTE),
+                    (LinkPart::Text, id),
+                    (LinkPart::Delim, RIGHT_BRACKET),
+                ],
+                resolver
+                    .get_footnote_url(id)
+                    .map(|url| TuiHyperlink::new(&url)),
+                maybe_current_box_computed_style,
+            ),
+
             MdLineFragment::Checkbox(done) => {
                 vec![if *done {
                     StyleUSSpan::new(
@@ -578,19 +835,66 @@ impl PrettyPrintDebug for StyleUSSpanLine {
 impl StyleUSSpanLine {
     pub fn from_fragments(
         fragments_in_one_line: &FragmentsInOneLine<'_>,
+        resolver: &MdLinkResolver<'_>,
         maybe_current_box_computed_style: &Option<TuiStyle>,
     ) -> Self {
         let mut acc = vec![];
 
         for fragment in fragments_in_one_line.iter() {
-            let vec_spans =
-                StyleUSSpan::from_fragment(fragment, maybe_current_box_computed_style);
+            let vec_spans = StyleUSSpan::from_fragment(
+                fragment,
+                resolver,
+                maybe_current_box_computed_style,
+            );
             acc.extend(vec_spans);
         }
 
         List { inner: acc }
     }
 
+    /// Same as [StyleUSSpanLine::from_fragments], except that [MdLineFragment::Plain]
+    /// text uses `plain_text_style` instead of [get_foreground_style].
+    pub fn from_fragments_with_plain_text_style(
+        fragments_in_one_line: &FragmentsInOneLine<'_>,
+        resolver: &MdLinkResolver<'_>,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+        plain_text_style: TuiStyle,
+    ) -> Self {
+        let mut acc = vec![];
+
+        for fragment in fragments_in_one_line.iter() {
+            match fragment {
+                MdLineFragment::Plain(plain_text) => acc.push(StyleUSSpan::new(
+                    maybe_current_box_computed_style.unwrap_or_default()
+                        + plain_text_style,
+                    US::from(*plain_text),
+                )),
+                _ => acc.extend(StyleUSSpan::from_fragment(
+                    fragment,
+                    resolver,
+                    maybe_current_box_computed_style,
+                )),
+            }
+        }
+
+        List { inner: acc }
+    }
+
+    /// Each char of the rule (eg: `- - -`) is displayed as [HORIZONTAL_RULE_DISPLAY], so
+    /// that it is a solid line w/ the same width.
+    pub fn from_horizontal_rule(
+        rule: &str,
+        maybe_current_box_computed_style: &Option<TuiStyle>,
+    ) -> Self {
+        let mut line = StyleUSSpanLine::default();
+        line += StyleUSSpan::new(
+            maybe_current_box_computed_style.unwrap_or_default()
+                + get_horizontal_rule_style(),
+            US::from(HORIZONTAL_RULE_DISPLAY.repeat(rule.chars().count())),
+        );
+        line
+    }
+
     /// This is a sample [HeadingData] that needs to be converted into a [StyleUSSpanLine].
     ///
     /// ```text
@@ -670,7 +974,11 @@ mod tests_style_us_span_lines_from {
             let style = tui_style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &MdLinkResolver::default(),
+                &Some(style),
+            );
 
             assert_eq2!(actual.len(), 1);
 
@@ -691,7 +999,11 @@ mod tests_style_us_span_lines_from {
             let style = tui_style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &MdLinkResolver::default(),
+                &Some(style),
+            );
 
             assert_eq2!(actual.len(), 1);
 
@@ -715,7 +1027,11 @@ mod tests_style_us_span_lines_from {
             let style = tui_style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &MdLinkResolver::default(),
+                &Some(style),
+            );
 
             assert_eq2!(actual.len(), 6);
 
@@ -750,7 +1066,15 @@ mod tests_style_us_span_lines_from {
             let style = tui_style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &MdLinkResolver::default(),
+                &Some(style),
+            );
+            let hyperlink_style = TuiStyle {
+                hyperlink: Some(TuiHyperlink::new("https://r3bl.com")),
+                ..Default::default()
+            };
 
             assert_eq2!(actual.len(), 6);
 
@@ -789,7 +1113,8 @@ mod tests_style_us_span_lines_from {
                             + tui_style! {
                                 color_fg: actual_style_color_fg
                                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
-                            },
+                            }
+                            + hyperlink_style,
                         US::from("R3BL")
                     )
                 )
@@ -852,7 +1177,8 @@ mod tests_style_us_span_lines_from {
                                 attrib: [underline]
                                 color_fg: actual_style_color_fg
                                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
-                            },
+                            }
+                            + hyperlink_style,
                         US::from("https://r3bl.com")
                     )
                 );
@@ -889,7 +1215,11 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &MdLinkResolver::default(),
+                &Some(style),
+            );
 
             // println!("{}", List::from(actual.clone())..pretty_print_debug());
 
@@ -914,7 +1244,11 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &MdLinkResolver::default(),
+                &Some(style),
+            );
 
             // println!("{}", List::from(actual.clone())..pretty_print_debug());
 
@@ -939,7 +1273,11 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &MdLinkResolver::default(),
+                &Some(style),
+            );
 
             // println!("{}", List::from(actual.clone())..pretty_print_debug());
 
@@ -957,13 +1295,44 @@ mod tests_style_us_span_lines_from {
             );
         }
 
+        #[test]
+        fn test_strikethrough() {
+            let fragment = MdLineFragment::Strikethrough("Foobar");
+            let style = tui_style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &MdLinkResolver::default(),
+                &Some(style),
+            );
+
+            assert_eq2!(
+                actual,
+                vec![
+                    StyleUSSpan::new(style + get_foreground_dim_style(), US::from("~~")),
+                    StyleUSSpan::new(
+                        style + get_strikethrough_style(),
+                        US::from("Foobar")
+                    ),
+                    StyleUSSpan::new(style + get_foreground_dim_style(), US::from("~~")),
+                ]
+            );
+            assert!(get_strikethrough_style().strikethrough);
+        }
+
         #[test]
         fn test_plain() {
             let fragment = MdLineFragment::Plain("Foobar");
             let style = tui_style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let actual = StyleUSSpan::from_fragment(&fragment, &Some(style));
+            let actual = StyleUSSpan::from_fragment(
+                &fragment,
+                &MdLinkResolver::default(),
+                &Some(style),
+            );
             let expected = vec![StyleUSSpan::new(
                 style + get_foreground_style(),
                 US::from("Foobar"),
@@ -986,7 +1355,12 @@ mod tests_style_us_span_lines_from {
                 let style = tui_style! {
                     color_bg: TuiColor::Basic(ANSIBasicColor::Red)
                 };
-                let lines = StyleUSSpanLines::from_block(&tags, &Some(style), None);
+                let lines = StyleUSSpanLines::from_block(
+                    &tags,
+                    &MdLinkResolver::default(),
+                    &Some(style),
+                    None,
+                );
                 let line_0 = &lines.inner[0];
                 let mut iter = line_0.inner.iter();
 
@@ -1040,7 +1414,12 @@ mod tests_style_us_span_lines_from {
             let style = tui_style! {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
-            let lines = StyleUSSpanLines::from_block(&title, &Some(style), None);
+            let lines = StyleUSSpanLines::from_block(
+                &title,
+                &MdLinkResolver::default(),
+                &Some(style),
+                None,
+            );
             // println!("{}", lines..pretty_print_debug());
 
             let line_0 = &lines.inner[0];
@@ -1091,8 +1470,12 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let lines =
-                StyleUSSpanLines::from_block(&codeblock_block, &Some(style), None);
+            let lines = StyleUSSpanLines::from_block(
+                &codeblock_block,
+                &MdLinkResolver::default(),
+                &Some(style),
+                None,
+            );
 
             let line_0 = &lines.inner[0];
             // println!("{}", line_0..pretty_print_debug());
@@ -1135,8 +1518,12 @@ mod tests_style_us_span_lines_from {
                 let ol_block_1 = &doc[0];
                 {
                     // println!("{:#?}", ol_block_1);
-                    let lines =
-                        StyleUSSpanLines::from_block(ol_block_1, &Some(style), None);
+                    let lines = StyleUSSpanLines::from_block(
+                        ol_block_1,
+                        &MdLinkResolver::default(),
+                        &Some(style),
+                        None,
+                    );
 
                     let line_0 = &lines.inner[0];
                     // println!("{}", line_0..pretty_print_debug());
@@ -1156,8 +1543,12 @@ mod tests_style_us_span_lines_from {
                 let ol_block_2 = &doc[1];
                 {
                     // println!("{:#?}", ol_block_2);
-                    let lines =
-                        StyleUSSpanLines::from_block(ol_block_2, &Some(style), None);
+                    let lines = StyleUSSpanLines::from_block(
+                        ol_block_2,
+                        &MdLinkResolver::default(),
+                        &Some(style),
+                        None,
+                    );
 
                     let line_0 = &lines.inner[0];
                     // println!("{}", line_0..pretty_print_debug());
@@ -1188,8 +1579,12 @@ mod tests_style_us_span_lines_from {
                 // First smart list.
                 {
                     let ul_block_0 = &doc[0];
-                    let lines =
-                        StyleUSSpanLines::from_block(ul_block_0, &Some(style), None);
+                    let lines = StyleUSSpanLines::from_block(
+                        ul_block_0,
+                        &MdLinkResolver::default(),
+                        &Some(style),
+                        None,
+                    );
                     let line_0 = &lines.inner[0];
                     assert_eq2!(
                         line_0.inner[0],
@@ -1204,8 +1599,12 @@ mod tests_style_us_span_lines_from {
                 // Second smart list.
                 {
                     let ul_block_1 = &doc[1];
-                    let lines =
-                        StyleUSSpanLines::from_block(ul_block_1, &Some(style), None);
+                    let lines = StyleUSSpanLines::from_block(
+                        ul_block_1,
+                        &MdLinkResolver::default(),
+                        &Some(style),
+                        None,
+                    );
                     let line_0 = &lines.inner[0];
                     assert_eq2!(
                         line_0.inner[0],
@@ -1219,6 +1618,271 @@ mod tests_style_us_span_lines_from {
             });
         }
 
+        #[test]
+        fn test_block_quote() {
+            let (_, doc) = parse_markdown("> foo *bar*\n> > baz\n").unwrap();
+            let style = tui_style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let lines = StyleUSSpanLines::from_block(
+                &doc[0],
+                &MdLinkResolver::default(),
+                &Some(style),
+                None,
+            );
+            assert_eq2!(lines.len(), 2);
+
+            let line_0 = &lines.inner[0];
+            assert_eq2!(
+                line_0.inner[0],
+                StyleUSSpan::new(style + get_block_quote_marker_style(), US::from("┃ "))
+            );
+            assert_eq2!(
+                line_0.inner[1],
+                StyleUSSpan::new(style + get_block_quote_text_style(), US::from("foo "))
+            );
+            assert_eq2!(
+                line_0.inner[3],
+                StyleUSSpan::new(style + get_bold_style(), US::from("bar"))
+            );
+
+            let line_1 = &lines.inner[1];
+            assert_eq2!(
+                line_1.inner[0],
+                StyleUSSpan::new(
+                    style + get_block_quote_marker_style(),
+                    US::from("┃ ┃ ")
+                )
+            );
+        }
+
+        #[test]
+        fn test_block_table() {
+            let input = ["| a | b |", "|---|--:|", "| `c` | d |", ""].join("\n");
+            let (_, doc) = parse_markdown(&input).unwrap();
+            let style = tui_style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let lines = StyleUSSpanLines::from_block(
+                &doc[0],
+                &MdLinkResolver::default(),
+                &Some(style),
+                None,
+            );
+            assert_eq2!(lines.len(), 3);
+
+            // Each line is the same as the text that it comes from.
+            for (line, expected) in lines.iter().zip(input.lines()) {
+                let text = line
+                    .iter()
+                    .map(|span| span.text.string.clone())
+                    .collect::<String>();
+                assert_eq2!(text, expected);
+            }
+
+            let border =
+                StyleUSSpan::new(style + get_table_border_style(), US::from("|"));
+            assert_eq2!(lines.inner[0].inner[0], border);
+            assert_eq2!(
+                lines.inner[0].inner[1],
+                StyleUSSpan::new(style + get_table_header_style(), US::from(" a "))
+            );
+            assert_eq2!(
+                lines.inner[1].inner[3],
+                StyleUSSpan::new(style + get_table_border_style(), US::from("--:"))
+            );
+            assert_eq2!(
+                lines.inner[2].inner[1],
+                StyleUSSpan::new(style + get_foreground_style(), US::from(" "))
+            );
+            assert_eq2!(
+                lines.inner[2].inner[3],
+                StyleUSSpan::new(style + get_inline_code_style(), US::from("c"))
+            );
+            assert_eq2!(lines.inner[2].inner.last().unwrap(), &border);
+        }
+
+        #[test]
+        fn test_block_horizontal_rule() {
+            let rule_block = MdBlock::HorizontalRule("- - -");
+            let style = tui_style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let lines = StyleUSSpanLines::from_block(
+                &rule_block,
+                &MdLinkResolver::default(),
+                &Some(style),
+                None,
+            );
+            assert_eq2!(lines.len(), 1);
+            assert_eq2!(
+                lines.inner[0].inner,
+                vec![StyleUSSpan::new(
+                    style + get_horizontal_rule_style(),
+                    US::from("─────")
+                )]
+            );
+        }
+
+        #[test]
+        fn test_reference_link_auto_link_and_footnote() {
+            let input = "[R3BL][site] <https://r3bl.com> https://r3bl.com[^1]";
+            let definitions = "[site]: https://r3bl.com/site\n[^1]: Note";
+            let (_, doc) = parse_markdown(input).unwrap();
+            let style = tui_style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            let lines = StyleUSSpanLines::from_block(
+                &doc[0],
+                &MdLinkResolver::default(),
+                &Some(style),
+                None,
+            );
+            assert_eq2!(lines.len(), 1);
+
+            let dim = |it: &str| {
+                StyleUSSpan::new(style + get_foreground_dim_style(), US::from(it))
+            };
+            let text =
+                |it: &str| StyleUSSpan::new(style + get_link_text_style(), US::from(it));
+            let url =
+                |it: &str| StyleUSSpan::new(style + get_link_url_style(), US::from(it));
+            let plain =
+                |it: &str| StyleUSSpan::new(style + get_foreground_style(), US::from(it));
+            let clickable_url = |it: &str| {
+                let hyperlink_style = TuiStyle {
+                    hyperlink: Some(TuiHyperlink::new(it)),
+                    ..Default::default()
+                };
+                StyleUSSpan::new(
+                    style + get_link_url_style() + hyperlink_style,
+                    US::from(it),
+                )
+            };
+            let with_hyperlink = |span: StyleUSSpan, url: &str| {
+                let hyperlink_style = TuiStyle {
+                    hyperlink: Some(TuiHyperlink::new(url)),
+                    ..Default::default()
+                };
+                StyleUSSpan::new(span.style + hyperlink_style, span.text)
+            };
+
+            // Unresolved links are styled the same as resolved ones, but they aren't
+            // clickable.
+            assert_eq2!(
+                lines.inner[0].inner,
+                vec![
+                    dim("["),
+                    text("R3BL"),
+                    dim("]"),
+                    dim("["),
+                    url("site"),
+                    dim("]"),
+                    plain(" "),
+                    dim("<"),
+                    clickable_url("https://r3bl.com"),
+                    dim(">"),
+                    plain(" "),
+                    clickable_url("https://r3bl.com"),
+                    dim("[^"),
+                    text("1"),
+                    dim("]"),
+                ]
+            );
+
+            // Resolved w/ the definitions in the rest of the document.
+            let document_text = format!("{input}\n{definitions}");
+            let (_, doc) = parse_markdown(&document_text).unwrap();
+            let lines = StyleUSSpanLines::from_block(
+                &doc[0],
+                &MdLinkResolver::new(&doc),
+                &Some(style),
+                None,
+            );
+            assert_eq2!(
+                lines.inner[0].inner,
+                vec![
+                    dim("["),
+                    with_hyperlink(text("R3BL"), "https://r3bl.com/site"),
+                    dim("]"),
+                    dim("["),
+                    with_hyperlink(url("site"), "https://r3bl.com/site"),
+                    dim("]"),
+                    plain(" "),
+                    dim("<"),
+                    clickable_url("https://r3bl.com"),
+                    dim(">"),
+                    plain(" "),
+                    clickable_url("https://r3bl.com"),
+                    dim("[^"),
+                    with_hyperlink(text("1"), "#md-footnote-1"),
+                    dim("]"),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_block_link_and_footnote_definitions() {
+            let input = ["[site]: https://r3bl.com", "[^1]: *foo*"].join("\n");
+            let (_, doc) = parse_markdown(&input).unwrap();
+            let style = tui_style! {
+                color_bg: TuiColor::Basic(ANSIBasicColor::Red)
+            };
+
+            // Each line is the same as the text that it comes from.
+            for (block, expected) in doc.iter().zip(input.lines()) {
+                let lines = StyleUSSpanLines::from_block(
+                    block,
+                    &MdLinkResolver::default(),
+                    &Some(style),
+                    None,
+                );
+                assert_eq2!(lines.len(), 1);
+                let text = lines.inner[0]
+                    .iter()
+                    .map(|span| span.text.string.clone())
+                    .collect::<String>();
+                assert_eq2!(text, expected);
+            }
+
+            let lines = StyleUSSpanLines::from_block(
+                &doc[0],
+                &MdLinkResolver::default(),
+                &Some(style),
+                None,
+            );
+            assert_eq2!(
+                lines.inner[0].inner[3],
+                StyleUSSpan::new(
+                    style
+                        + get_link_url_style()
+                        + TuiStyle {
+                            hyperlink: Some(TuiHyperlink::new("https://r3bl.com")),
+                            ..Default::default()
+                        },
+                    US::from("https://r3bl.com")
+                )
+            );
+            let lines = StyleUSSpanLines::from_block(
+                &doc[1],
+                &MdLinkResolver::default(),
+                &Some(style),
+                None,
+            );
+            assert_eq2!(
+                lines.inner[0].inner[1],
+                StyleUSSpan::new(style + get_link_text_style(), US::from("1"))
+            );
+            assert_eq2!(
+                lines.inner[0].inner[4],
+                StyleUSSpan::new(style + get_bold_style(), US::from("foo"))
+            );
+        }
+
         #[test]
         fn test_block_text() {
             let text_block = MdBlock::Text(list![MdLineFragment::Plain("Foobar")]);
@@ -1226,7 +1890,12 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             };
 
-            let lines = StyleUSSpanLines::from_block(&text_block, &Some(style), None);
+            let lines = StyleUSSpanLines::from_block(
+                &text_block,
+                &MdLinkResolver::default(),
+                &Some(style),
+                None,
+            );
             // println!("{}", lines..pretty_print_debug());
 
             let line_0 = &lines.inner[0];
@@ -1246,7 +1915,12 @@ mod tests_style_us_span_lines_from {
                 color_bg: TuiColor::Basic(ANSIBasicColor::Red)
             });
 
-            let lines = StyleUSSpanLines::from_block(&heading_block, &maybe_style, None);
+            let lines = StyleUSSpanLines::from_block(
+                &heading_block,
+                &MdLinkResolver::default(),
+                &maybe_style,
+                None,
+            );
             // println!("{}", lines..pretty_print_debug());
 
             // There should just be 1 line.
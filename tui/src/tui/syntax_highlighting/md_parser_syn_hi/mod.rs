@@ -21,11 +21,18 @@
 //! 2. [crate::syntax_highlighting] - Responsible for converting a [crate::MdDocument] into a list
 //!    of tuples of [r3bl_rs_utils_core::TuiStyle] and [String].
 //! 3. [crate::editor] - Responsible for displaying the [crate::MdDocument] to the user.
+//!
+//! Reference links (`[text][id]`) and footnote references (`[^1]`) are resolved w/
+//! [crate::MdLinkResolver], and they are clickable if their definition is in the
+//! document. [md_parser_syn_hi_cache] keeps track of the definitions that each cached
+//! block depends on.
 
 // Attach.
 pub mod md_parser_stylesheet;
+pub mod md_parser_syn_hi_cache;
 pub mod md_parser_syn_hi_impl;
 
 // Re-export.
 pub use md_parser_stylesheet::*;
+pub use md_parser_syn_hi_cache::*;
 pub use md_parser_syn_hi_impl::*;
@@ -0,0 +1,621 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Themes for Markdown syntax highlighting and the editor (eg: selection and search
+//! matches). The functions in [md_parser_stylesheet](mod@crate::md_parser_stylesheet)
+//! get their styles from the current theme, see [global_md_theme].
+//!
+//! # Theme file format
+//!
+//! A theme is a JSON file. The built-in themes ([MdTheme::dark] & [MdTheme::light]) are in
+//! the `assets` folder, and they contain every setting. A theme file can also start from
+//! a built-in theme by setting `base`, and only contain the settings that it changes:
+//!
+//! ```json
+//! {
+//!   "base": "light",
+//!   "syntect_theme": "Solarized (light)",
+//!   "styles": {
+//!     "bold": { "fg": "#ff0000" },
+//!     "link_url": { "fg": { "truecolor": "#0000ff", "ansi256": 21, "grayscale": "Blue" } }
+//!   }
+//! }
+//! ```
+//!
+//! - `styles` has a style for each Markdown element, along w/ `selection`,
+//!   `search_match` and `search_current_match`. A style has `fg` and `bg` colors, and
+//!   `bold`, `italic`, `dim`, `underline`, `reverse` and `strikethrough` flags.
+//! - `heading_gradients` has the colors for each heading level (the last one is used for
+//!   the levels after it). `stops` are used w/ truecolor, and `ansi256` is the name of an
+//!   [Ansi256GradientIndex].
+//! - `syntect_theme` is used to highlight code blocks. It is `r3bl` (the embedded theme),
+//!   the name of one of syntect's default themes (eg: `InspiredGitHub`), or the path to a
+//!   `.tmTheme` file.
+//!
+//! # Colors and [ColorSupport]
+//!
+//! A color is one of the following (see [MdThemeColor]):
+//! 1. A hex color (eg: `"#c1b3d0"`), which is converted to the closest color that the
+//!    terminal supports when it is painted.
+//! 2. An [ANSIBasicColor] (eg: `"Black"`), which is used w/ any terminal.
+//! 3. A color for each [ColorSupport], eg: `{ "truecolor": "#c1b3d0", "ansi256": 244,
+//!    "grayscale": "White" }`. The `grayscale` color is also used when there is no color
+//!    support. Any of these can be left out, in which case the next one is used.
+
+use std::{path::{Path, PathBuf},
+          sync::{atomic::{AtomicUsize, Ordering},
+                 Arc,
+                 OnceLock,
+                 RwLock}};
+
+use r3bl_ansi_color::{global_color_support, ColorSupport};
+use r3bl_rs_utils_core::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use syntect::highlighting::{Theme, ThemeSet};
+
+use crate::*;
+
+/// Name of the theme file in [MdTheme::try_get_config_file_path].
+pub const MD_THEME_FILE_NAME: &str = "md_theme.json";
+
+/// Name of the folder (in the user's config folder) that has the theme file.
+pub const MD_THEME_CONFIG_FOLDER_NAME: &str = "r3bl-tui";
+
+/// Name of the embedded syntect theme, see [try_load_r3bl_theme].
+pub const R3BL_SYNTECT_THEME_NAME: &str = "r3bl";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MdTheme {
+    pub name: String,
+    pub syntect_theme: String,
+    pub styles: MdThemeStyles,
+    pub heading_gradients: Vec<MdThemeGradient>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MdThemeStyles {
+    pub selection: MdThemeStyle,
+    pub search_match: MdThemeStyle,
+    pub search_current_match: MdThemeStyle,
+    pub foreground: MdThemeStyle,
+    pub foreground_dim: MdThemeStyle,
+    pub bold: MdThemeStyle,
+    pub italic: MdThemeStyle,
+    pub bold_italic: MdThemeStyle,
+    pub inline_code: MdThemeStyle,
+    pub link_text: MdThemeStyle,
+    pub link_url: MdThemeStyle,
+    pub checkbox_checked: MdThemeStyle,
+    pub checkbox_unchecked: MdThemeStyle,
+    pub list_bullet: MdThemeStyle,
+    pub code_block_lang: MdThemeStyle,
+    pub code_block_content: MdThemeStyle,
+    pub metadata_title_marker: MdThemeStyle,
+    pub metadata_title_value: MdThemeStyle,
+    pub metadata_tags_marker: MdThemeStyle,
+    pub metadata_tags_values: MdThemeStyle,
+    pub strikethrough: MdThemeStyle,
+    pub block_quote_marker: MdThemeStyle,
+    pub block_quote_text: MdThemeStyle,
+    pub table_border: MdThemeStyle,
+    pub table_header: MdThemeStyle,
+    pub horizontal_rule: MdThemeStyle,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct MdThemeStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fg: Option<MdThemeColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bg: Option<MdThemeColor>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub bold: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub italic: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub dim: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub underline: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub reverse: bool,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub strikethrough: bool,
+}
+
+fn is_false(it: &bool) -> bool { !*it }
+
+/// See the [module docs](self) for details.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum MdThemeColor {
+    Basic(ANSIBasicColor),
+    Hex(String),
+    ForEachColorSupport {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        truecolor: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ansi256: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        grayscale: Option<ANSIBasicColor>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MdThemeGradient {
+    pub stops: Vec<String>,
+    pub ansi256: Ansi256GradientIndex,
+}
+
+mod md_theme_impl {
+    use super::*;
+
+    impl Default for MdTheme {
+        fn default() -> Self { Self::dark() }
+    }
+
+    impl MdTheme {
+        /// The built-in theme for terminals w/ a dark background. This is the default.
+        pub fn dark() -> Self {
+            Self::try_from_json_str(include_str!("assets/md_theme_dark.json"))
+                .expect("The built-in dark theme is valid")
+        }
+
+        /// The built-in theme for terminals w/ a light background.
+        pub fn light() -> Self {
+            Self::try_from_json_str(include_str!("assets/md_theme_light.json"))
+                .expect("The built-in light theme is valid")
+        }
+
+        /// Returns the built-in theme w/ the given `name` (`dark` or `light`).
+        pub fn try_get_built_in(name: &str) -> Option<Self> {
+            match name {
+                "dark" => Some(Self::dark()),
+                "light" => Some(Self::light()),
+                _ => None,
+            }
+        }
+
+        /// Parse a theme. If it has a `base`, then its settings are applied on top of
+        /// that built-in theme.
+        pub fn try_from_json_str(json: &str) -> CommonResult<Self> {
+            let Ok(mut value) = serde_json::from_str::<Value>(json) else {
+                return CommonError::new(
+                    CommonErrorType::ParsingError,
+                    "Theme is not valid JSON",
+                );
+            };
+
+            if let Some(base) = value.get("base").cloned() {
+                let Some(base_theme) = base.as_str().and_then(Self::try_get_built_in)
+                else {
+                    return CommonError::new(
+                        CommonErrorType::InvalidValue,
+                        &format!("Unknown base theme: {base}"),
+                    );
+                };
+                let Ok(mut base_value) = serde_json::to_value(base_theme) else {
+                    return CommonError::new_err_with_only_type(
+                        CommonErrorType::InvalidState,
+                    );
+                };
+                if let Value::Object(map) = &mut value {
+                    map.remove("base");
+                }
+                merge_json_values(&mut base_value, value);
+                value = base_value;
+            }
+
+            let md_theme = match serde_json::from_value::<Self>(value) {
+                Ok(it) => it,
+                Err(error) => {
+                    return CommonError::new(
+                        CommonErrorType::ParsingError,
+                        &format!("Invalid theme: {error}"),
+                    )
+                }
+            };
+            md_theme.validate()?;
+            Ok(md_theme)
+        }
+
+        pub fn try_load_from_file(path: &Path) -> CommonResult<Self> {
+            match std::fs::read_to_string(path) {
+                Ok(json) => Self::try_from_json_str(&json),
+                Err(error) => CommonError::new(
+                    CommonErrorType::IOError,
+                    &format!("Could not read theme file {path:?}: {error}"),
+                ),
+            }
+        }
+
+        /// Returns the path of the theme file in the user's config folder, eg:
+        /// `~/.config/r3bl-tui/md_theme.json` on Linux.
+        pub fn try_get_config_file_path() -> Option<PathBuf> {
+            Some(
+                dirs::config_dir()?
+                    .join(MD_THEME_CONFIG_FOLDER_NAME)
+                    .join(MD_THEME_FILE_NAME),
+            )
+        }
+
+        /// Load the theme file in the user's config folder. Returns [None] if there isn't
+        /// one.
+        pub fn try_load_from_config_folder() -> CommonResult<Option<Self>> {
+            let Some(path) = Self::try_get_config_file_path() else {
+                return Ok(None);
+            };
+            if !path.exists() {
+                return Ok(None);
+            }
+            Self::try_load_from_file(&path).map(Some)
+        }
+
+        /// Load the syntect [Theme] that is used to highlight code blocks. If it can't be
+        /// loaded then the embedded one is used.
+        pub fn load_syntect_theme(&self) -> Theme {
+            let name = self.syntect_theme.as_str();
+
+            if name != R3BL_SYNTECT_THEME_NAME {
+                if let Some(theme) = ThemeSet::load_defaults().themes.remove(name) {
+                    return theme;
+                }
+                if let Ok(theme) = ThemeSet::get_theme(name) {
+                    return theme;
+                }
+            }
+
+            try_load_r3bl_theme().unwrap_or_else(|_| load_default_theme())
+        }
+
+        /// Returns the gradient for the given heading level, which starts at 1.
+        pub fn get_heading_gradient(&self, level: usize) -> Option<&MdThemeGradient> {
+            let index = level.saturating_sub(1);
+            self.heading_gradients
+                .get(index)
+                .or(self.heading_gradients.last())
+        }
+
+        /// Make sure that all the hex colors are valid, so that they don't cause a panic
+        /// when they are used.
+        fn validate(&self) -> CommonResult<()> {
+            let styles = &self.styles;
+            for style in [
+                &styles.selection,
+                &styles.search_match,
+                &styles.search_current_match,
+                &styles.foreground,
+                &styles.foreground_dim,
+                &styles.bold,
+                &styles.italic,
+                &styles.bold_italic,
+                &styles.inline_code,
+                &styles.link_text,
+                &styles.link_url,
+                &styles.checkbox_checked,
+                &styles.checkbox_unchecked,
+                &styles.list_bullet,
+                &styles.code_block_lang,
+                &styles.code_block_content,
+                &styles.metadata_title_marker,
+                &styles.metadata_title_value,
+                &styles.metadata_tags_marker,
+                &styles.metadata_tags_values,
+                &styles.strikethrough,
+                &styles.block_quote_marker,
+                &styles.block_quote_text,
+                &styles.table_border,
+                &styles.table_header,
+                &styles.horizontal_rule,
+            ] {
+                for color in [&style.fg, &style.bg].into_iter().flatten() {
+                    match color {
+                        MdThemeColor::Basic(_) => {}
+                        MdThemeColor::Hex(hex) => validate_hex(hex)?,
+                        MdThemeColor::ForEachColorSupport { truecolor, .. } => {
+                            if let Some(hex) = truecolor {
+                                validate_hex(hex)?;
+                            }
+                        }
+                    }
+                }
+            }
+
+            for gradient in &self.heading_gradients {
+                for hex in &gradient.stops {
+                    validate_hex(hex)?;
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    fn validate_hex(hex: &str) -> CommonResult<()> {
+        RgbValue::try_from_hex_color(hex).map(|_| ())
+    }
+
+    /// Objects are merged key by key, and everything else in `overrides` replaces what is
+    /// in `base`.
+    fn merge_json_values(base: &mut Value, overrides: Value) {
+        match (base, overrides) {
+            (Value::Object(base_map), Value::Object(overrides_map)) => {
+                for (key, value) in overrides_map {
+                    match base_map.get_mut(&key) {
+                        Some(base_value) => merge_json_values(base_value, value),
+                        None => {
+                            base_map.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (base, overrides) => *base = overrides,
+        }
+    }
+}
+
+mod md_theme_style_impl {
+    use super::*;
+
+    impl MdThemeStyle {
+        /// The colors are picked based on [global_color_support::detect].
+        pub fn to_tui_style(&self) -> TuiStyle {
+            TuiStyle {
+                // Same as the styles made w/ the `tui_style!` macro, which don't have one.
+                id: u8::MAX,
+                bold: self.bold,
+                italic: self.italic,
+                dim: self.dim,
+                underline: self.underline,
+                reverse: self.reverse,
+                strikethrough: self.strikethrough,
+                color_fg: self.fg.as_ref().map(MdThemeColor::to_tui_color),
+                color_bg: self.bg.as_ref().map(MdThemeColor::to_tui_color),
+                ..Default::default()
+            }
+        }
+    }
+
+    impl MdThemeColor {
+        /// The colors are picked based on [global_color_support::detect].
+        pub fn to_tui_color(&self) -> TuiColor {
+            match self {
+                MdThemeColor::Basic(it) => TuiColor::Basic(*it),
+                MdThemeColor::Hex(hex) => TuiColor::Rgb(RgbValue::from_hex(hex)),
+                MdThemeColor::ForEachColorSupport {
+                    truecolor,
+                    ansi256,
+                    grayscale,
+                } => {
+                    let truecolor = truecolor
+                        .as_ref()
+                        .map(|it| TuiColor::Rgb(RgbValue::from_hex(it)));
+                    let ansi256 = ansi256.map(|it| TuiColor::Ansi(AnsiValue::new(it)));
+                    let grayscale = grayscale.map(TuiColor::Basic);
+
+                    let maybe_color = match global_color_support::detect() {
+                        ColorSupport::Truecolor => truecolor.or(ansi256).or(grayscale),
+                        ColorSupport::Ansi256 => ansi256.or(truecolor).or(grayscale),
+                        _ => grayscale.or(ansi256).or(truecolor),
+                    };
+                    maybe_color.unwrap_or(TuiColor::Reset)
+                }
+            }
+        }
+    }
+}
+
+/// The theme that is currently used by the functions in
+/// [md_parser_stylesheet](mod@crate::md_parser_stylesheet), and the [EditorEngine]. This
+/// is [MdTheme::dark] unless it is changed w/ [set](global_md_theme::set).
+pub mod global_md_theme {
+    use super::*;
+
+    static MD_THEME: RwLock<Option<Arc<MdTheme>>> = RwLock::new(None);
+    static DEFAULT_MD_THEME: OnceLock<Arc<MdTheme>> = OnceLock::new();
+    static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+    pub fn get() -> Arc<MdTheme> {
+        if let Ok(guard) = MD_THEME.read() {
+            if let Some(md_theme) = guard.as_ref() {
+                return md_theme.clone();
+            }
+        }
+        DEFAULT_MD_THEME
+            .get_or_init(|| Arc::new(MdTheme::dark()))
+            .clone()
+    }
+
+    pub fn set(md_theme: MdTheme) { replace(Some(Arc::new(md_theme))); }
+
+    /// Go back to the default theme.
+    pub fn clear() { replace(None); }
+
+    /// Use the theme file in the user's config folder (see
+    /// [MdTheme::try_load_from_config_folder]), if there is one.
+    pub fn try_load_from_config_folder() -> CommonResult<()> {
+        if let Some(md_theme) = MdTheme::try_load_from_config_folder()? {
+            set(md_theme);
+        }
+        Ok(())
+    }
+
+    /// This changes every time the theme changes, so that anything that depends on it
+    /// (eg: [MdSynHiCache]) knows when to update.
+    pub fn get_generation() -> usize { GENERATION.load(Ordering::SeqCst) }
+
+    fn replace(maybe_md_theme: Option<Arc<MdTheme>>) {
+        if let Ok(mut guard) = MD_THEME.write() {
+            *guard = maybe_md_theme;
+        }
+        GENERATION.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests_md_theme {
+    use r3bl_rs_utils_core::assert_eq2;
+    use r3bl_rs_utils_macro::tui_style;
+    use serial_test::serial;
+
+    use super::*;
+
+    #[test]
+    fn test_built_in_themes() {
+        for name in ["dark", "light"] {
+            let md_theme = MdTheme::try_get_built_in(name).unwrap();
+            assert_eq2!(md_theme.name, name);
+            assert_eq2!(md_theme.heading_gradients.len(), 6);
+        }
+        assert!(MdTheme::try_get_built_in("unknown").is_none());
+        assert_eq2!(MdTheme::default(), MdTheme::dark());
+    }
+
+    #[serial]
+    #[test]
+    fn test_dark_theme_color_support() {
+        let bold = &MdTheme::dark().styles.bold;
+
+        global_color_support::set_override(ColorSupport::Truecolor);
+        assert_eq2!(
+            bold.to_tui_style(),
+            tui_style! {
+                attrib: [bold]
+                color_fg: TuiColor::Rgb(RgbValue::from_hex("#dacd24"))
+            }
+        );
+
+        global_color_support::set_override(ColorSupport::Ansi256);
+        assert_eq2!(
+            bold.to_tui_style().color_fg,
+            Some(TuiColor::Ansi(AnsiValue::new(226)))
+        );
+
+        global_color_support::set_override(ColorSupport::Grayscale);
+        assert_eq2!(
+            bold.to_tui_style().color_fg,
+            Some(TuiColor::Basic(ANSIBasicColor::Yellow))
+        );
+
+        // There is no ansi256 color for this one, so the truecolor one is used.
+        global_color_support::set_override(ColorSupport::Ansi256);
+        assert_eq2!(
+            MdTheme::dark().styles.list_bullet.to_tui_style().color_fg,
+            Some(TuiColor::Rgb(RgbValue::from_hex("#f8f8a6")))
+        );
+
+        global_color_support::clear_override();
+    }
+
+    #[test]
+    fn test_base_theme() {
+        let md_theme = MdTheme::try_from_json_str(
+            r##"{
+                "base": "light",
+                "name": "custom",
+                "styles": { "bold": { "fg": "#ff0000" } }
+            }"##,
+        )
+        .unwrap();
+        let light = MdTheme::light();
+
+        assert_eq2!(md_theme.name, "custom");
+        assert_eq2!(md_theme.syntect_theme, light.syntect_theme);
+        assert_eq2!(
+            md_theme.styles.bold.fg,
+            Some(MdThemeColor::Hex("#ff0000".to_string()))
+        );
+        // The rest of the bold style, and the other styles come from the base.
+        assert!(md_theme.styles.bold.bold);
+        assert_eq2!(md_theme.styles.italic, light.styles.italic);
+        assert_eq2!(md_theme.heading_gradients, light.heading_gradients);
+    }
+
+    #[test]
+    fn test_invalid_themes() {
+        // Not JSON.
+        assert!(MdTheme::try_from_json_str("{").is_err());
+        // Unknown base theme.
+        assert!(MdTheme::try_from_json_str(r#"{ "base": "unknown" }"#).is_err());
+        // Missing settings w/out a base.
+        assert!(MdTheme::try_from_json_str(r#"{ "name": "custom" }"#).is_err());
+        // Invalid hex color.
+        assert!(MdTheme::try_from_json_str(
+            r##"{ "base": "dark", "styles": { "bold": { "fg": "#zzzzzz" } } }"##
+        )
+        .is_err());
+        assert!(MdTheme::try_from_json_str(
+            r#"{ "base": "dark", "heading_gradients": [{ "stops": ["red"], "ansi256": "RustToPurple" }] }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_heading_gradient() {
+        let md_theme = MdTheme::dark();
+        assert_eq2!(
+            md_theme.get_heading_gradient(1),
+            md_theme.heading_gradients.first()
+        );
+        // Levels after the last gradient use the last one.
+        assert_eq2!(
+            md_theme.get_heading_gradient(9),
+            md_theme.heading_gradients.last()
+        );
+    }
+
+    #[test]
+    fn test_load_syntect_theme() {
+        let mut md_theme = MdTheme::dark();
+        assert_eq2!(
+            md_theme.load_syntect_theme().name,
+            try_load_r3bl_theme().unwrap().name
+        );
+
+        md_theme.syntect_theme = "InspiredGitHub".to_string();
+        assert_eq2!(
+            md_theme.load_syntect_theme().name,
+            Some("GitHub".to_string())
+        );
+
+        // Falls back to the embedded theme.
+        md_theme.syntect_theme = "does/not/exist.tmTheme".to_string();
+        assert_eq2!(
+            md_theme.load_syntect_theme().name,
+            try_load_r3bl_theme().unwrap().name
+        );
+    }
+
+    /// This only uses themes w/ the same styles as [MdTheme::dark], so that it doesn't
+    /// change the styles that other tests (running at the same time) get.
+    #[serial]
+    #[test]
+    fn test_global_md_theme() {
+        let generation = global_md_theme::get_generation();
+        global_md_theme::set(MdTheme {
+            name: "custom".to_string(),
+            ..MdTheme::dark()
+        });
+        assert_eq2!(global_md_theme::get().name, "custom");
+        assert!(global_md_theme::get_generation() > generation);
+
+        let generation = global_md_theme::get_generation();
+        global_md_theme::clear();
+        assert_eq2!(global_md_theme::get().name, "dark");
+        assert!(global_md_theme::get_generation() > generation);
+    }
+}
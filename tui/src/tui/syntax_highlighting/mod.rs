@@ -19,6 +19,7 @@
 pub mod convert_syntect_highlighting_to_tui_style;
 pub mod intermediate_types;
 pub mod md_parser_syn_hi;
+pub mod md_theme;
 pub mod pattern_matcher;
 pub mod r3bl_syntect_theme;
 pub mod syntect_to_styled_text_conversion;
@@ -27,6 +28,7 @@ pub mod syntect_to_styled_text_conversion;
 pub use convert_syntect_highlighting_to_tui_style::*;
 pub use intermediate_types::*;
 pub use md_parser_syn_hi::*;
+pub use md_theme::*;
 pub use pattern_matcher::*;
 pub use r3bl_syntect_theme::*;
 pub use syntect_to_styled_text_conversion::*;
@@ -63,25 +63,43 @@
 //!     - <https://github.com/crossterm-rs/crossterm/wiki/Upgrade-from-0.13-to-0.14#111-new-event-api>
 //!     - <https://github.com/crossterm-rs/crossterm/blob/master/examples/event-stream-tokio.rs>
 
-use crossterm::event::*;
+#[cfg(not(feature = "termion"))]
+use crossterm::event::EventStream;
 use futures_util::{FutureExt, StreamExt};
+#[cfg(not(feature = "termion"))]
 use r3bl_rs_utils_core::*;
 
 use crate::*;
 
+/// Async stream of [InputEvent]s from the terminal, which uses the [TERMINAL_LIB_BACKEND].
 pub struct AsyncEventStream {
+    #[cfg(not(feature = "termion"))]
     event_stream: EventStream,
+    #[cfg(feature = "termion")]
+    event_stream: TermionEventStream,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for AsyncEventStream {
     fn default() -> Self {
         Self {
+            #[cfg(not(feature = "termion"))]
             event_stream: EventStream::new(),
+            #[cfg(feature = "termion")]
+            event_stream: TermionEventStream::default(),
         }
     }
 }
 
 impl AsyncEventStream {
+    #[cfg(feature = "termion")]
+    pub async fn try_to_get_input_event(
+        async_event_stream: &mut AsyncEventStream,
+    ) -> Option<InputEvent> {
+        async_event_stream.event_stream.next().fuse().await
+    }
+
+    #[cfg(not(feature = "termion"))]
     pub async fn try_to_get_input_event(
         async_event_stream: &mut AsyncEventStream,
     ) -> Option<InputEvent> {
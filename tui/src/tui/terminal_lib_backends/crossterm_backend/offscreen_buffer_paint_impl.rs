@@ -22,19 +22,25 @@ use crate::*;
 pub struct OffscreenBufferPaintImplCrossterm;
 
 impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
-    fn paint(&mut self, render_ops: RenderOps, flush_kind: FlushKind, window_size: Size) {
+    fn paint(
+        &mut self,
+        render_ops: RenderOps,
+        flush_kind: FlushKind,
+        window_size: Size,
+        output_device: &mut OutputDevice,
+    ) {
         let mut skip_flush = false;
 
         if let FlushKind::ClearBeforeFlush = flush_kind {
-            RenderOp::default().clear_before_flush();
+            RenderOp::default().clear_before_flush(output_device);
         }
 
         // Execute each RenderOp.
-        render_ops.execute_all(&mut skip_flush, window_size);
+        render_ops.execute_all(&mut skip_flush, window_size, output_device);
 
         // Flush everything to the terminal.
         if !skip_flush {
-            RenderOp::default().flush()
+            RenderOp::default().flush(output_device)
         };
 
         // Debug output.
@@ -46,15 +52,20 @@ impl OffscreenBufferPaint for OffscreenBufferPaintImplCrossterm {
         });
     }
 
-    fn paint_diff(&mut self, render_ops: RenderOps, window_size: Size) {
+    fn paint_diff(
+        &mut self,
+        render_ops: RenderOps,
+        window_size: Size,
+        output_device: &mut OutputDevice,
+    ) {
         let mut skip_flush = false;
 
         // Execute each RenderOp.
-        render_ops.execute_all(&mut skip_flush, window_size);
+        render_ops.execute_all(&mut skip_flush, window_size, output_device);
 
         // Flush everything to the terminal.
         if !skip_flush {
-            RenderOp::default().flush()
+            RenderOp::default().flush(output_device)
         };
 
         // Debug output.
@@ -231,6 +242,7 @@ mod render_helpers {
     /// - `reverse`
     /// - `hidden`
     /// - `strikethrough`
+    /// - `hyperlink`
     pub fn style_eq(this: &Option<TuiStyle>, other: &Option<TuiStyle>) -> bool {
         match (this.is_some(), other.is_some()) {
             (false, false) => true,
@@ -245,6 +257,7 @@ mod render_helpers {
                     && this.reverse == other.reverse
                     && this.hidden == other.hidden
                     && this.strikethrough == other.strikethrough
+                    && this.hyperlink == other.hyperlink
             }
             (_, _) => false,
         }
@@ -287,6 +300,7 @@ mod render_helpers {
 mod tests {
     use r3bl_rs_utils_core::*;
     use r3bl_rs_utils_macro::*;
+    use serial_test::serial;
 
     use super::*;
 
@@ -400,4 +414,94 @@ mod tests {
             )
         );
     }
+
+    fn make_offscreen_buffer_with_hyperlink(link_text: &str) -> OffscreenBuffer {
+        let link_style = TuiStyle {
+            underline: true,
+            hyperlink: Some(TuiHyperlink::new("https://r3bl.com")),
+            ..Default::default()
+        };
+        let pipeline = render_pipeline!(
+            @new ZOrder::Normal
+            =>
+                RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 0)),
+                RenderOp::PaintTextWithAttributes("see ".into(), None),
+                RenderOp::PaintTextWithAttributes(link_text.into(), Some(link_style)),
+                RenderOp::PaintTextWithAttributes(".".into(), None)
+        );
+        pipeline.convert(size! { col_count: 10, row_count: 1 })
+    }
+
+    fn get_hyperlinked_text(virtual_terminal: &SafeVirtualTerminal) -> String {
+        let virtual_terminal = virtual_terminal.lock().unwrap();
+        (0..10)
+            .filter_map(|col_index| {
+                virtual_terminal.get_cell(position!(col_index: col_index, row_index: 0))
+            })
+            .filter(|cell| cell.style.hyperlink.is_some())
+            .map(|cell| cell.text.as_str())
+            .collect()
+    }
+
+    /// Text w/ a hyperlink is painted in its own chunk, and is only clickable until the
+    /// end of that chunk, even when only parts of it are repainted.
+    #[serial]
+    #[test]
+    fn test_render_hyperlink() {
+        global_hyperlink_support::set_override(true);
+        let window_size = size! { col_count: 10, row_count: 1 };
+        let (mut output_device, virtual_terminal) =
+            OutputDevice::new_virtual_terminal(window_size);
+        let mut paint = OffscreenBufferPaintImplCrossterm {};
+
+        // Full paint.
+        let offscreen_buffer = make_offscreen_buffer_with_hyperlink("r3bl");
+        let render_ops = paint.render(&offscreen_buffer);
+        assert!(render_ops.contains(
+            &RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                "r3bl".to_string(),
+                Some(TuiStyle {
+                    underline: true,
+                    hyperlink: Some(TuiHyperlink::new("https://r3bl.com")),
+                    ..Default::default()
+                })
+            )
+        ));
+        paint.paint(
+            render_ops,
+            FlushKind::JustFlush,
+            window_size,
+            &mut output_device,
+        );
+        assert_eq2!(get_hyperlinked_text(&virtual_terminal), "r3bl");
+
+        // Diff paint.
+        let OffscreenBufferDiffResult::Comparable(diff_chunks) =
+            offscreen_buffer.diff(&make_offscreen_buffer_with_hyperlink("rust"))
+        else {
+            panic!("offscreen buffers should be comparable");
+        };
+        let render_ops = paint.render_diff(&diff_chunks);
+        paint.paint_diff(render_ops, window_size, &mut output_device);
+        assert_eq2!(
+            virtual_terminal.lock().unwrap().get_lines(),
+            vec!["see rust."]
+        );
+        assert_eq2!(get_hyperlinked_text(&virtual_terminal), "rust");
+
+        // Not supported.
+        global_hyperlink_support::set_override(false);
+        let (mut output_device, virtual_terminal) =
+            OutputDevice::new_virtual_terminal(window_size);
+        let render_ops = paint.render(&offscreen_buffer);
+        paint.paint(
+            render_ops,
+            FlushKind::JustFlush,
+            window_size,
+            &mut output_device,
+        );
+        assert_eq2!(get_hyperlinked_text(&virtual_terminal), "");
+
+        global_hyperlink_support::clear_override();
+    }
 }
@@ -15,8 +15,7 @@
  *   limitations under the License.
  */
 
-use std::{borrow::Cow,
-          io::{stderr, stdout, Write}};
+use std::{borrow::Cow, io::Write};
 
 use crossterm::{cursor::*,
                 event::*,
@@ -41,20 +40,26 @@ mod render_op_impl_crossterm_impl_trait_paint_render_op {
             command_ref: &RenderOp,
             window_size: Size,
             local_data: &mut RenderOpsLocalData,
+            output_device: &mut OutputDevice,
         ) {
             match command_ref {
                 RenderOp::Noop => {}
                 RenderOp::EnterRawMode => {
-                    RenderOpImplCrossterm::raw_mode_enter(skip_flush, window_size);
+                    RenderOpImplCrossterm::raw_mode_enter(
+                        skip_flush,
+                        window_size,
+                        output_device,
+                    );
                 }
                 RenderOp::ExitRawMode => {
-                    RenderOpImplCrossterm::raw_mode_exit(skip_flush);
+                    RenderOpImplCrossterm::raw_mode_exit(skip_flush, output_device);
                 }
                 RenderOp::MoveCursorPositionAbs(abs_pos) => {
                     RenderOpImplCrossterm::move_cursor_position_abs(
                         *abs_pos,
                         window_size,
                         local_data,
+                        output_device,
                     );
                 }
                 RenderOp::MoveCursorPositionRelTo(box_origin_pos, content_rel_pos) => {
@@ -63,25 +68,26 @@ mod render_op_impl_crossterm_impl_trait_paint_render_op {
                         *content_rel_pos,
                         window_size,
                         local_data,
+                        output_device,
                     );
                 }
                 RenderOp::ClearScreen => {
                     exec_render_op!(
-                        queue!(stdout(), Clear(ClearType::All)),
+                        queue!(output_device, Clear(ClearType::All)),
                         "ClearScreen"
                     )
                 }
                 RenderOp::SetFgColor(color) => {
-                    RenderOpImplCrossterm::set_fg_color(color);
+                    RenderOpImplCrossterm::set_fg_color(color, output_device);
                 }
                 RenderOp::SetBgColor(color) => {
-                    RenderOpImplCrossterm::set_bg_color(color);
+                    RenderOpImplCrossterm::set_bg_color(color, output_device);
                 }
                 RenderOp::ResetColor => {
-                    exec_render_op!(queue!(stdout(), ResetColor), "ResetColor")
+                    exec_render_op!(queue!(output_device, ResetColor), "ResetColor")
                 }
                 RenderOp::ApplyColors(style) => {
-                    RenderOpImplCrossterm::apply_colors(style);
+                    RenderOpImplCrossterm::apply_colors(style, output_device);
                 }
                 RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
                     text,
@@ -92,6 +98,7 @@ mod render_op_impl_crossterm_impl_trait_paint_render_op {
                         maybe_style,
                         window_size,
                         local_data,
+                        output_device,
                     );
                 }
                 RenderOp::PaintTextWithAttributes(_text, _maybe_style) => {
@@ -108,13 +115,15 @@ pub mod render_op_impl_crossterm_impl_trait_flush {
     use super::*;
 
     impl Flush for RenderOpImplCrossterm {
-        fn flush(&mut self) { flush(); }
-        fn clear_before_flush(&mut self) { clear_before_flush(); }
+        fn flush(&mut self, output_device: &mut OutputDevice) { flush(output_device); }
+        fn clear_before_flush(&mut self, output_device: &mut OutputDevice) {
+            clear_before_flush(output_device);
+        }
     }
 
-    fn clear_before_flush() {
+    fn clear_before_flush(output_device: &mut OutputDevice) {
         exec_render_op! {
-          queue!(stdout(),
+          queue!(output_device,
             ResetColor,
             Clear(ClearType::All),
           ),
@@ -122,9 +131,8 @@ pub mod render_op_impl_crossterm_impl_trait_flush {
         }
     }
 
-    pub fn flush() {
-        exec_render_op!(stdout().flush(), "flush() -> stdout");
-        exec_render_op!(stderr().flush(), "flush() -> stderr");
+    pub fn flush(output_device: &mut OutputDevice) {
+        exec_render_op!(output_device.flush(), "flush() -> output device");
     }
 }
 
@@ -137,47 +145,65 @@ mod render_op_impl_crossterm_impl {
             content_rel_pos: Position,
             window_size: Size,
             local_data: &mut RenderOpsLocalData,
+            output_device: &mut OutputDevice,
         ) {
             let new_abs_pos = box_origin_pos + content_rel_pos;
-            Self::move_cursor_position_abs(new_abs_pos, window_size, local_data);
+            Self::move_cursor_position_abs(
+                new_abs_pos,
+                window_size,
+                local_data,
+                output_device,
+            );
         }
 
         pub fn move_cursor_position_abs(
             abs_pos: Position,
             window_size: Size,
             local_data: &mut RenderOpsLocalData,
+            output_device: &mut OutputDevice,
         ) {
             let Position {
                 col_index: col,
                 row_index: row,
             } = sanitize_and_save_abs_position(abs_pos, window_size, local_data);
             exec_render_op!(
-                queue!(stdout(), MoveTo(*col, *row)),
+                queue!(output_device, MoveTo(*col, *row)),
                 format!("MoveCursorPosition(col: {}, row: {})", *col, *row)
             )
         }
 
-        pub fn raw_mode_exit(skip_flush: &mut bool) {
+        /// The real terminal is only taken out of raw mode if the [OutputDevice] is
+        /// stdout.
+        pub fn raw_mode_exit(skip_flush: &mut bool, output_device: &mut OutputDevice) {
             exec_render_op! {
-              queue!(stdout(),
+              queue!(output_device,
                 Show,
                 LeaveAlternateScreen,
                 DisableMouseCapture
               ),
               "ExitRawMode -> Show, LeaveAlternateScreen, DisableMouseCapture"
             };
-            render_op_impl_crossterm_impl_trait_flush::flush();
-            exec_render_op! {terminal::disable_raw_mode(), "ExitRawMode -> disable_raw_mode()"}
+            render_op_impl_crossterm_impl_trait_flush::flush(output_device);
+            if output_device.is_stdout() {
+                exec_render_op! {terminal::disable_raw_mode(), "ExitRawMode -> disable_raw_mode()"}
+            }
             *skip_flush = true;
         }
 
-        pub fn raw_mode_enter(skip_flush: &mut bool, _: Size) {
-            exec_render_op! {
-              terminal::enable_raw_mode(),
-              "EnterRawMode -> enable_raw_mode()"
-            };
+        /// The real terminal is only put in raw mode if the [OutputDevice] is stdout.
+        pub fn raw_mode_enter(
+            skip_flush: &mut bool,
+            _: Size,
+            output_device: &mut OutputDevice,
+        ) {
+            if output_device.is_stdout() {
+                exec_render_op! {
+                  terminal::enable_raw_mode(),
+                  "EnterRawMode -> enable_raw_mode()"
+                };
+            }
             exec_render_op! {
-              queue!(stdout(),
+              queue!(output_device,
                 EnableMouseCapture,
                 EnterAlternateScreen,
                 MoveTo(0,0),
@@ -186,28 +212,28 @@ mod render_op_impl_crossterm_impl {
               ),
             "EnterRawMode -> EnableMouseCapture, EnterAlternateScreen, MoveTo(0,0), Clear(ClearType::All), Hide"
             }
-            render_op_impl_crossterm_impl_trait_flush::flush();
+            render_op_impl_crossterm_impl_trait_flush::flush(output_device);
             *skip_flush = true;
         }
 
-        pub fn set_fg_color(color: &TuiColor) {
+        pub fn set_fg_color(color: &TuiColor, output_device: &mut OutputDevice) {
             let color =
                 crossterm_color_converter::convert_from_tui_color_to_crossterm_color(
                     *color,
                 );
             exec_render_op!(
-                queue!(stdout(), SetForegroundColor(color)),
+                queue!(output_device, SetForegroundColor(color)),
                 format!("SetFgColor({color:?})")
             )
         }
 
-        pub fn set_bg_color(color: &TuiColor) {
+        pub fn set_bg_color(color: &TuiColor, output_device: &mut OutputDevice) {
             let color: crossterm::style::Color =
                 crossterm_color_converter::convert_from_tui_color_to_crossterm_color(
                     *color,
                 );
             exec_render_op!(
-                queue!(stdout(), SetBackgroundColor(color)),
+                queue!(output_device, SetBackgroundColor(color)),
                 format!("SetBgColor({color:?})")
             )
         }
@@ -217,6 +243,7 @@ mod render_op_impl_crossterm_impl {
             maybe_style: &Option<TuiStyle>,
             window_size: Size,
             local_data: &mut RenderOpsLocalData,
+            output_device: &mut OutputDevice,
         ) {
             use perform_paint::*;
 
@@ -235,19 +262,22 @@ mod render_op_impl_crossterm_impl {
             let needs_reset = Cow::Owned(false);
 
             // Paint plain_text.
-            paint_style_and_text(&mut paint_args, needs_reset, local_data);
+            paint_style_and_text(&mut paint_args, needs_reset, local_data, output_device);
         }
 
         /// Use [crossterm::style::Color] to set crossterm Colors.
         /// Docs: <https://docs.rs/crossterm/latest/crossterm/style/index.html#colors>
-        pub fn apply_colors(maybe_style: &Option<TuiStyle>) {
+        pub fn apply_colors(
+            maybe_style: &Option<TuiStyle>,
+            output_device: &mut OutputDevice,
+        ) {
             if let Some(style) = maybe_style {
                 // Handle background color.
                 if let Some(tui_color_bg) = style.color_bg {
                     let color_bg: crossterm::style::Color =
                         crate::convert_from_tui_color_to_crossterm_color(tui_color_bg);
                     exec_render_op!(
-                        queue!(stdout(), SetBackgroundColor(color_bg)),
+                        queue!(output_device, SetBackgroundColor(color_bg)),
                         format!("ApplyColors -> SetBgColor({color_bg:?})")
                     )
                 }
@@ -257,7 +287,7 @@ mod render_op_impl_crossterm_impl {
                     let color_fg: crossterm::style::Color =
                         crate::convert_from_tui_color_to_crossterm_color(tui_color_fg);
                     exec_render_op!(
-                        queue!(stdout(), SetForegroundColor(color_fg)),
+                        queue!(output_device, SetForegroundColor(color_fg)),
                         format!("ApplyColors -> SetFgColor({color_fg:?})")
                     )
                 }
@@ -298,7 +328,7 @@ mod perform_paint {
             it.push(Attribute::Hidden);
         }
         if style.strikethrough {
-            it.push(Attribute::Fraktur);
+            it.push(Attribute::CrossedOut);
         }
         it
     }
@@ -309,6 +339,7 @@ mod perform_paint {
         paint_args: &mut PaintArgs<'_>,
         mut needs_reset: Cow<'_, bool>,
         local_data: &mut RenderOpsLocalData,
+        output_device: &mut OutputDevice,
     ) {
         let PaintArgs { maybe_style, .. } = paint_args;
 
@@ -316,40 +347,45 @@ mod perform_paint {
             let attrib_vec = style_to_attribute(style);
             attrib_vec.iter().for_each(|attr| {
                 exec_render_op!(
-                    queue!(stdout(), SetAttribute(*attr)),
+                    queue!(output_device, SetAttribute(*attr)),
                     format!("PaintWithAttributes -> SetAttribute({attr:?})")
                 );
                 needs_reset = Cow::Owned(true);
             });
         }
 
-        paint_text(paint_args, local_data);
+        paint_text(paint_args, local_data, output_device);
 
         if *needs_reset {
             exec_render_op!(
-                queue!(stdout(), SetAttribute(Attribute::Reset)),
+                queue!(output_device, SetAttribute(Attribute::Reset)),
                 format!("PaintWithAttributes -> SetAttribute(Reset))")
             );
         }
     }
 
-    pub fn paint_text(paint_args: &PaintArgs<'_>, local_data: &mut RenderOpsLocalData) {
+    pub fn paint_text(
+        paint_args: &PaintArgs<'_>,
+        local_data: &mut RenderOpsLocalData,
+        output_device: &mut OutputDevice,
+    ) {
         let PaintArgs {
             text,
             log_msg,
+            maybe_style,
             window_size,
-            ..
         } = paint_args;
 
         let unicode_string: UnicodeString = text.as_ref().into();
         let mut cursor_position_copy = local_data.cursor_position;
 
-        // Actually paint text.
+        // Actually paint text (wrapped in OSC 8 escape sequences if it is a hyperlink).
         {
-            let text = Cow::Borrowed(text);
+            let maybe_hyperlink = maybe_style.and_then(|style| style.hyperlink);
+            let text = TuiHyperlink::wrap_text(&maybe_hyperlink, text);
             let log_msg: &str = log_msg;
             exec_render_op!(
-                queue!(stdout(), Print(&text)),
+                queue!(output_device, Print(&text)),
                 format!("Print( {} {log_msg})", &text)
             );
         };
@@ -377,7 +413,8 @@ macro_rules! exec_render_op {
         // when there are issues accessing the log file for whatever reason.
         use $crate::tui::DEBUG_TUI_SHOW_TERMINAL_BACKEND;
 
-        let _fn_wrap_for_logging_err = || -> CommonResult<()> {
+        #[allow(unused_mut)]
+        let mut _fn_wrap_for_logging_err = || -> CommonResult<()> {
             throws!({
                 // Execute the command.
                 if let Err(err) = $arg_cmd {
@@ -47,13 +47,21 @@
 //! - Sodium:
 //!   - repo: <https://github.com/redox-os/sodium>
 
+/// The terminal library that is used to paint [RenderOps], enter raw mode, and read
+/// [InputEvent]s. Crossterm is the default, and termion is used instead if the `termion`
+/// feature is enabled (it only works on Unix).
 pub enum TerminalLibBackend {
     Crossterm,
+    #[cfg(feature = "termion")]
     Termion,
 }
 
+#[cfg(not(feature = "termion"))]
 pub const TERMINAL_LIB_BACKEND: TerminalLibBackend = TerminalLibBackend::Crossterm;
 
+#[cfg(feature = "termion")]
+pub const TERMINAL_LIB_BACKEND: TerminalLibBackend = TerminalLibBackend::Termion;
+
 // Attach source files.
 pub mod async_event_stream_ext;
 pub mod crossterm_backend;
@@ -64,13 +72,16 @@ pub mod keypress;
 pub mod modifier_keys_mask;
 pub mod mouse_input;
 pub mod offscreen_buffer;
+pub mod output_device;
 pub mod paint;
 pub mod raw_mode;
 pub mod render_op;
 pub mod render_pipeline;
 pub mod render_pipeline_to_offscreen_buffer;
 pub mod terminal_lib_operations;
+#[cfg(feature = "termion")]
 pub mod termion_backend;
+pub mod virtual_terminal;
 
 // Re-export.
 pub use async_event_stream_ext::*;
@@ -82,15 +93,20 @@ pub use keypress::*;
 pub use modifier_keys_mask::*;
 pub use mouse_input::*;
 pub use offscreen_buffer::*;
+pub use output_device::*;
 pub use paint::*;
 pub use raw_mode::*;
 pub use render_op::*;
 pub use render_pipeline::*;
 pub use render_pipeline_to_offscreen_buffer::*;
 pub use terminal_lib_operations::*;
+#[cfg(feature = "termion")]
+pub use termion_backend::*;
+pub use virtual_terminal::*;
 
 // Tests.
 mod test_input_event;
 mod test_keypress;
 mod test_mouse_input;
+mod test_paint;
 mod test_render_pipeline;
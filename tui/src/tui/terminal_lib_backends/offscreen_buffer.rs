@@ -123,6 +123,14 @@ mod offscreen_buffer_impl {
             }
         }
 
+        /// Returns the text of each line (w/out styles and trailing whitespace).
+        pub fn get_text_lines(&self) -> Vec<String> {
+            self.buffer
+                .iter()
+                .map(|line| line.get_text().trim_end().to_string())
+                .collect()
+        }
+
         // Make sure each line is full of empty chars.
         pub fn clear(&mut self) {
             self.buffer = PixelCharLines::new_with_capacity_initialized(self.window_size);
@@ -187,6 +195,19 @@ mod pixel_char_line_impl {
 
     // This represents a single row on the screen (i.e. a line of text).
     impl PixelCharLine {
+        /// Returns the text of all the [PixelChar]s in this line.
+        pub fn get_text(&self) -> String {
+            let mut it = String::new();
+            for pixel_char in self.pixel_chars.iter() {
+                match pixel_char {
+                    PixelChar::Void => {}
+                    PixelChar::Spacer => it.push_str(SPACER),
+                    PixelChar::PlainText { content, .. } => it.push_str(&content.string),
+                }
+            }
+            it
+        }
+
         pub fn pretty_print(&self) -> String {
             let mut it = vec![];
             let mut void_indices: Vec<usize> = vec![];
@@ -414,9 +435,20 @@ pub trait OffscreenBufferPaint {
 
     fn render_diff(&mut self, diff_chunks: &PixelCharDiffChunks) -> RenderOps;
 
-    fn paint(&mut self, render_ops: RenderOps, flush_kind: FlushKind, window_size: Size);
-
-    fn paint_diff(&mut self, render_ops: RenderOps, window_size: Size);
+    fn paint(
+        &mut self,
+        render_ops: RenderOps,
+        flush_kind: FlushKind,
+        window_size: Size,
+        output_device: &mut OutputDevice,
+    );
+
+    fn paint_diff(
+        &mut self,
+        render_ops: RenderOps,
+        window_size: Size,
+        output_device: &mut OutputDevice,
+    );
 }
 
 #[cfg(test)]
@@ -0,0 +1,118 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{fmt::{Debug, Formatter},
+          io::{stderr, stdout, Write},
+          sync::{Arc, Mutex}};
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+pub type SafeWriter = Arc<Mutex<dyn Write + Send>>;
+pub type SafeVirtualTerminal = Arc<Mutex<VirtualTerminal>>;
+
+/// This is where [paint] (and [RawMode]) write the ANSI escape sequences that the terminal
+/// backend generates. It is picked at runtime, using [GlobalData::output_device].
+/// - [OutputDevice::Stdout] is the process' stdout, which is what the [TerminalWindow]
+///   uses. This is the only device that puts the real terminal in raw mode.
+/// - [OutputDevice::Writer] can be any [Write] implementation, eg: a file, a `Vec<u8>`,
+///   or a [VirtualTerminal]. This allows [App]s to be rendered w/out a TTY (eg: in
+///   tests).
+#[derive(Clone, Default)]
+pub enum OutputDevice {
+    #[default]
+    Stdout,
+    Writer(SafeWriter),
+}
+
+impl OutputDevice {
+    pub fn new_writer(writer: impl Write + Send + 'static) -> Self {
+        Self::Writer(Arc::new(Mutex::new(writer)))
+    }
+
+    /// Returns a device that paints to a new [VirtualTerminal] of the given size, and the
+    /// [VirtualTerminal] itself, so that its screen can be inspected.
+    pub fn new_virtual_terminal(size: Size) -> (Self, SafeVirtualTerminal) {
+        let virtual_terminal = Arc::new(Mutex::new(VirtualTerminal::new(size)));
+        (Self::Writer(virtual_terminal.clone()), virtual_terminal)
+    }
+
+    pub fn is_stdout(&self) -> bool { matches!(self, Self::Stdout) }
+}
+
+impl Write for OutputDevice {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Stdout => stdout().write(buf),
+            Self::Writer(writer) => lock_writer(writer)?.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Stdout => {
+                stdout().flush()?;
+                stderr().flush()
+            }
+            Self::Writer(writer) => lock_writer(writer)?.flush(),
+        }
+    }
+}
+
+fn lock_writer(
+    writer: &SafeWriter,
+) -> std::io::Result<std::sync::MutexGuard<'_, dyn Write + Send + 'static>> {
+    writer
+        .lock()
+        .map_err(|_| std::io::Error::other("output device writer lock is poisoned"))
+}
+
+impl Debug for OutputDevice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stdout => write!(f, "OutputDevice::Stdout"),
+            Self::Writer(_) => write!(f, "OutputDevice::Writer"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_ops_to_writer() {
+        let bytes = Arc::new(Mutex::new(Vec::<u8>::new()));
+        let mut output_device = OutputDevice::Writer(bytes.clone());
+        assert!(!output_device.is_stdout());
+
+        let render_ops = render_ops!(
+            @new
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 2, row_index: 1)),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("hi".to_string(), None),
+        );
+        render_ops.execute_all(
+            &mut false,
+            size!(col_count: 10, row_count: 5),
+            &mut output_device,
+        );
+
+        let output = String::from_utf8(bytes.lock().unwrap().clone()).unwrap();
+        assert_eq!(output, "\x1b[2;3Hhi");
+    }
+}
@@ -27,6 +27,7 @@ use crate::*;
 /// This function is responsible for:
 /// 1. Actually executing those [RenderOps] in the correct order.
 /// 2. And routing the execution to the correct backend specified in [TERMINAL_LIB_BACKEND].
+/// 3. Which writes its output to the [OutputDevice] in [GlobalData::output_device].
 ///
 /// See [RenderOps] for more details of "atomic paint operations".
 pub fn paint<S, AS>(
@@ -41,35 +42,55 @@ pub fn paint<S, AS>(
 
     let window_size = global_data.window_size;
 
+    let output_device = &mut global_data.output_device;
+
     let offscreen_buffer = pipeline.convert(window_size);
 
     match maybe_saved_offscreen_buffer {
         None => {
-            perform_full_paint(&offscreen_buffer, flush_kind, window_size);
+            perform_full_paint(&offscreen_buffer, flush_kind, window_size, output_device);
         }
         Some(saved_offscreen_buffer) => {
             // Compare offscreen buffers & paint only the diff.
             match saved_offscreen_buffer.diff(&offscreen_buffer) {
                 OffscreenBufferDiffResult::NotComparable => {
-                    perform_full_paint(&offscreen_buffer, flush_kind, window_size);
+                    perform_full_paint(
+                        &offscreen_buffer,
+                        flush_kind,
+                        window_size,
+                        output_device,
+                    );
                 }
                 OffscreenBufferDiffResult::Comparable(ref diff_chunks) => {
-                    perform_diff_paint(diff_chunks, window_size);
+                    perform_diff_paint(diff_chunks, window_size, output_device);
                 }
             }
         }
     }
 
+    if let Some(ref mut frames) = global_data.maybe_recorded_frames {
+        frames.push(offscreen_buffer.clone());
+    }
+
     global_data.maybe_saved_offscreen_buffer = Some(offscreen_buffer);
 
-    fn perform_diff_paint(diff_chunks: &PixelCharDiffChunks, window_size: Size) {
+    fn perform_diff_paint(
+        diff_chunks: &PixelCharDiffChunks,
+        window_size: Size,
+        output_device: &mut OutputDevice,
+    ) {
         match TERMINAL_LIB_BACKEND {
             TerminalLibBackend::Crossterm => {
                 let mut crossterm_impl = OffscreenBufferPaintImplCrossterm {};
                 let render_ops = crossterm_impl.render_diff(diff_chunks);
-                crossterm_impl.paint_diff(render_ops, window_size);
+                crossterm_impl.paint_diff(render_ops, window_size, output_device);
+            }
+            #[cfg(feature = "termion")]
+            TerminalLibBackend::Termion => {
+                let mut termion_impl = OffscreenBufferPaintImplTermion {};
+                let render_ops = termion_impl.render_diff(diff_chunks);
+                termion_impl.paint_diff(render_ops, window_size, output_device);
             }
-            TerminalLibBackend::Termion => todo!(), // FUTURE: implement OffscreenBufferPaint trait for termion
         }
     }
 
@@ -77,14 +98,20 @@ pub fn paint<S, AS>(
         offscreen_buffer: &OffscreenBuffer,
         flush_kind: FlushKind,
         window_size: Size,
+        output_device: &mut OutputDevice,
     ) {
         match TERMINAL_LIB_BACKEND {
             TerminalLibBackend::Crossterm => {
                 let mut crossterm_impl = OffscreenBufferPaintImplCrossterm {};
                 let render_ops = crossterm_impl.render(offscreen_buffer);
-                crossterm_impl.paint(render_ops, flush_kind, window_size);
+                crossterm_impl.paint(render_ops, flush_kind, window_size, output_device);
+            }
+            #[cfg(feature = "termion")]
+            TerminalLibBackend::Termion => {
+                let mut termion_impl = OffscreenBufferPaintImplTermion {};
+                let render_ops = termion_impl.render(offscreen_buffer);
+                termion_impl.paint(render_ops, flush_kind, window_size, output_device);
             }
-            TerminalLibBackend::Termion => todo!(), // FUTURE: implement OffscreenBufferPaint trait for termion
         }
     }
 }
@@ -152,6 +179,7 @@ pub mod paint_exports {
             render_op: &RenderOp,
             window_size: Size,
             local_data: &mut RenderOpsLocalData,
+            output_device: &mut OutputDevice,
         );
     }
 }
@@ -25,23 +25,25 @@ use crate::*;
 pub struct RawMode;
 
 impl RawMode {
-    pub fn start(window_size: Size) {
+    pub fn start(window_size: Size, output_device: &mut OutputDevice) {
         let mut skip_flush = false;
         RenderOps::route_paint_render_op_to_backend(
             &mut RenderOpsLocalData::default(),
             &mut skip_flush,
             &RenderOp::EnterRawMode,
             window_size,
+            output_device,
         );
     }
 
-    pub fn end(window_size: Size) {
+    pub fn end(window_size: Size, output_device: &mut OutputDevice) {
         let mut skip_flush = false;
         RenderOps::route_paint_render_op_to_backend(
             &mut RenderOpsLocalData::default(),
             &mut skip_flush,
             &RenderOp::ExitRawMode,
             window_size,
+            output_device,
         );
     }
 }
@@ -164,7 +164,12 @@ pub mod render_ops_impl {
     use super::*;
 
     impl RenderOps {
-        pub fn execute_all(&self, skip_flush: &mut bool, window_size: Size) {
+        pub fn execute_all(
+            &self,
+            skip_flush: &mut bool,
+            window_size: Size,
+            output_device: &mut OutputDevice,
+        ) {
             let mut local_data = RenderOpsLocalData::default();
             for render_op in self.list.iter() {
                 RenderOps::route_paint_render_op_to_backend(
@@ -172,6 +177,7 @@ pub mod render_ops_impl {
                     skip_flush,
                     render_op,
                     window_size,
+                    output_device,
                 );
             }
         }
@@ -181,6 +187,7 @@ pub mod render_ops_impl {
             skip_flush: &mut bool,
             render_op: &RenderOp,
             window_size: Size,
+            output_device: &mut OutputDevice,
         ) {
             match TERMINAL_LIB_BACKEND {
                 TerminalLibBackend::Crossterm => {
@@ -189,9 +196,19 @@ pub mod render_ops_impl {
                         render_op,
                         window_size,
                         local_data,
+                        output_device,
+                    );
+                }
+                #[cfg(feature = "termion")]
+                TerminalLibBackend::Termion => {
+                    RenderOpImplTermion {}.paint(
+                        skip_flush,
+                        render_op,
+                        window_size,
+                        local_data,
+                        output_device,
                     );
                 }
-                TerminalLibBackend::Termion => todo!(), // FUTURE: implement PaintRenderOp trait for termion
             }
         }
     }
@@ -302,7 +319,10 @@ mod render_op_impl {
                 TerminalLibBackend::Crossterm => {
                     CrosstermDebugFormatRenderOp {}.debug_format(self, f)
                 }
-                TerminalLibBackend::Termion => todo!(), // FUTURE: implement debug formatter for termion
+                #[cfg(feature = "termion")]
+                TerminalLibBackend::Termion => {
+                    TermionDebugFormatRenderOp {}.debug_format(self, f)
+                }
             }
         }
     }
@@ -312,21 +332,27 @@ mod render_op_impl_trait_flush {
     use super::*;
 
     impl Flush for RenderOp {
-        fn flush(&mut self) {
+        fn flush(&mut self, output_device: &mut OutputDevice) {
             match TERMINAL_LIB_BACKEND {
                 TerminalLibBackend::Crossterm => {
-                    RenderOpImplCrossterm {}.flush();
+                    RenderOpImplCrossterm {}.flush(output_device);
+                }
+                #[cfg(feature = "termion")]
+                TerminalLibBackend::Termion => {
+                    RenderOpImplTermion {}.flush(output_device);
                 }
-                TerminalLibBackend::Termion => todo!(), // FUTURE: implement flush for termion
             }
         }
 
-        fn clear_before_flush(&mut self) {
+        fn clear_before_flush(&mut self, output_device: &mut OutputDevice) {
             match TERMINAL_LIB_BACKEND {
                 TerminalLibBackend::Crossterm => {
-                    RenderOpImplCrossterm {}.clear_before_flush();
+                    RenderOpImplCrossterm {}.clear_before_flush(output_device);
+                }
+                #[cfg(feature = "termion")]
+                TerminalLibBackend::Termion => {
+                    RenderOpImplTermion {}.clear_before_flush(output_device);
                 }
-                TerminalLibBackend::Termion => todo!(), // FUTURE: implement clear_before_flush for termion
             }
         }
     }
@@ -339,8 +365,8 @@ pub enum FlushKind {
 }
 
 pub trait Flush {
-    fn flush(&mut self);
-    fn clear_before_flush(&mut self);
+    fn flush(&mut self, output_device: &mut OutputDevice);
+    fn clear_before_flush(&mut self, output_device: &mut OutputDevice);
 }
 
 pub trait DebugFormatRenderOp {
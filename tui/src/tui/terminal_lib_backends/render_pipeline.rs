@@ -223,7 +223,6 @@ mod render_pipeline_impl {
             AS: Debug + Default + Clone + Sync + Send,
         {
             paint(self, flush_kind, global_data);
-            // FUTURE: support termion, along w/ crossterm, by providing another impl of this fn #24
         }
 
         /// Move the [RenderOps] in the 'from' [ZOrder] (in self) to the 'to' [ZOrder] (in self).
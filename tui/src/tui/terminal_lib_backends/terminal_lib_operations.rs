@@ -17,9 +17,18 @@
 
 use r3bl_rs_utils_core::*;
 
-/// Interrogate crossterm [crossterm::terminal::size()] to get the size of the terminal window.
+use crate::*;
+
+/// Interrogate crossterm [crossterm::terminal::size()] (or termion, if it is the
+/// [TERMINAL_LIB_BACKEND]) to get the size of the terminal window.
 pub fn lookup_size() -> CommonResult<Size> {
-    let (col, row) = crossterm::terminal::size()?;
-    let size: Size = size!(col_count: col, row_count: row);
-    Ok(size)
+    match TERMINAL_LIB_BACKEND {
+        TerminalLibBackend::Crossterm => {
+            let (col, row) = crossterm::terminal::size()?;
+            let size: Size = size!(col_count: col, row_count: row);
+            Ok(size)
+        }
+        #[cfg(feature = "termion")]
+        TerminalLibBackend::Termion => lookup_size_termion(),
+    }
 }
@@ -14,3 +14,21 @@
  *   See the License for the specific language governing permissions and
  *   limitations under the License.
  */
+
+// Attach.
+pub mod termion_color_converter;
+pub mod termion_debug;
+pub mod termion_event_stream;
+pub mod termion_input_event_impl;
+pub mod termion_offscreen_buffer_paint_impl;
+pub mod termion_render_op_impl;
+
+// Re-export.
+pub use termion_color_converter::*;
+pub use termion_debug::*;
+pub use termion_event_stream::*;
+pub use termion_offscreen_buffer_paint_impl::*;
+pub use termion_render_op_impl::*;
+
+// Tests.
+mod test_backend_parity;
@@ -0,0 +1,80 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::fmt::{Formatter, Result};
+
+use r3bl_rs_utils_core::*;
+use termion::color;
+
+use crate::*;
+
+/// Termion represents each kind of color w/ its own type (that implements
+/// [color::Color]), so this wraps the ones that a [TuiColor] can be converted to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TermionColor {
+    Reset,
+    AnsiValue(u8),
+    Rgb(u8, u8, u8),
+}
+
+impl color::Color for TermionColor {
+    fn write_fg(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            TermionColor::Reset => color::Reset.write_fg(f),
+            TermionColor::AnsiValue(number) => color::AnsiValue(*number).write_fg(f),
+            TermionColor::Rgb(r, g, b) => color::Rgb(*r, *g, *b).write_fg(f),
+        }
+    }
+
+    fn write_bg(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            TermionColor::Reset => color::Reset.write_bg(f),
+            TermionColor::AnsiValue(number) => color::AnsiValue(*number).write_bg(f),
+            TermionColor::Rgb(r, g, b) => color::Rgb(*r, *g, *b).write_bg(f),
+        }
+    }
+}
+
+/// Respect the color support of the terminal and downgrade the color if needed. This uses
+/// [convert_from_tui_color_to_crossterm_color] to do the downgrade, so that both backends
+/// paint exactly the same colors.
+#[rustfmt::skip]
+pub fn convert_from_tui_color_to_termion_color(from_tui_color: TuiColor) -> TermionColor {
+    use crossterm::style::Color;
+
+    match convert_from_tui_color_to_crossterm_color(from_tui_color) {
+        Color::Reset            => TermionColor::Reset,
+        Color::Black            => TermionColor::AnsiValue(0),
+        Color::DarkRed          => TermionColor::AnsiValue(1),
+        Color::DarkGreen        => TermionColor::AnsiValue(2),
+        Color::DarkYellow       => TermionColor::AnsiValue(3),
+        Color::DarkBlue         => TermionColor::AnsiValue(4),
+        Color::DarkMagenta      => TermionColor::AnsiValue(5),
+        Color::DarkCyan         => TermionColor::AnsiValue(6),
+        Color::Grey             => TermionColor::AnsiValue(7),
+        Color::DarkGrey         => TermionColor::AnsiValue(8),
+        Color::Red              => TermionColor::AnsiValue(9),
+        Color::Green            => TermionColor::AnsiValue(10),
+        Color::Yellow           => TermionColor::AnsiValue(11),
+        Color::Blue             => TermionColor::AnsiValue(12),
+        Color::Magenta          => TermionColor::AnsiValue(13),
+        Color::Cyan             => TermionColor::AnsiValue(14),
+        Color::White            => TermionColor::AnsiValue(15),
+        Color::AnsiValue(value) => TermionColor::AnsiValue(value),
+        Color::Rgb { r, g, b }  => TermionColor::Rgb(r, g, b),
+    }
+}
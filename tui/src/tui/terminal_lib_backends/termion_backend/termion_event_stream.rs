@@ -0,0 +1,102 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{pin::Pin,
+          task::{Context, Poll}};
+
+use futures_util::Stream;
+use r3bl_rs_utils_core::*;
+use termion::input::TermRead;
+use tokio::{signal::unix::{signal, SignalKind},
+            sync::mpsc::{unbounded_channel, UnboundedReceiver}};
+
+use crate::*;
+
+/// Async stream of [InputEvent]s that are read from stdin using termion. This is what
+/// [AsyncEventStream] uses when termion is the backend.
+///
+/// 1. Termion's input API is blocking, so stdin is read on its own thread, and the
+///    events are sent to this stream over a channel.
+/// 2. Termion doesn't report resize events, so a tokio task listens for the `SIGWINCH`
+///    signal and sends an [InputEvent::Resize] w/ the new size.
+///
+/// This has to be created from inside a tokio runtime.
+pub struct TermionEventStream {
+    receiver: UnboundedReceiver<InputEvent>,
+}
+
+impl Default for TermionEventStream {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded_channel::<InputEvent>();
+
+        // Key & mouse events. The thread exits the next time it reads an event after
+        // this stream is dropped.
+        let stdin_sender = sender.clone();
+        std::thread::spawn(move || {
+            for maybe_event in std::io::stdin().events() {
+                let event = match maybe_event {
+                    Ok(event) => event,
+                    Err(e) => {
+                        call_if_true!(DEBUG_TUI_SHOW_TERMINAL_BACKEND, {
+                            let msg = format!("Error: {e:?}");
+                            log_error(msg);
+                        });
+                        continue;
+                    }
+                };
+                if let Ok(input_event) = InputEvent::try_from(event) {
+                    if stdin_sender.send(input_event).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        // Resize events.
+        tokio::spawn(async move {
+            let Ok(mut window_change_signal) = signal(SignalKind::window_change()) else {
+                return;
+            };
+            while window_change_signal.recv().await.is_some() {
+                if let Ok(size) = lookup_size_termion() {
+                    if sender.send(InputEvent::Resize(size)).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self { receiver }
+    }
+}
+
+impl Stream for TermionEventStream {
+    type Item = InputEvent;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Interrogate termion [termion::terminal_size()] to get the size of the terminal window.
+pub fn lookup_size_termion() -> CommonResult<Size> {
+    let (col, row) = termion::terminal_size()?;
+    Ok(size!(col_count: col, row_count: row))
+}
@@ -0,0 +1,173 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! Convert termion [Event]s into [InputEvent]s. The goal is to produce the same
+//! [InputEvent] that the crossterm backend produces for the same user action, eg:
+//! - `Ctrl+q` is `KeyPress::WithModifiers { key: Key::Character('q'), mask: ctrl }`.
+//! - `Enter` and `Tab` are [SpecialKey]s (termion reports them as chars).
+//! - `Shift+Tab` is [SpecialKey::BackTab] w/ the shift modifier.
+//! - Mouse positions are 0 based (termion's are 1 based).
+//!
+//! Termion doesn't report resize or focus events. Resize events are generated by
+//! [TermionEventStream] instead.
+
+use r3bl_rs_utils_core::*;
+use termion::event::{Event, Key as TermionKey, MouseButton, MouseEvent};
+
+use crate::*;
+
+impl TryFrom<Event> for InputEvent {
+    type Error = ();
+
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        match event {
+            Event::Key(key) => Ok(InputEvent::Keyboard(key.try_into()?)),
+            Event::Mouse(mouse_event) => Ok(InputEvent::Mouse(mouse_event.into())),
+            Event::Unsupported(_) => Err(()),
+        }
+    }
+}
+
+impl TryFrom<TermionKey> for KeyPress {
+    type Error = ();
+
+    fn try_from(key: TermionKey) -> Result<Self, Self::Error> {
+        let plain = |key: Key| KeyPress::Plain { key };
+        let special = |special_key: SpecialKey| plain(Key::SpecialKey(special_key));
+        let with_modifiers =
+            |key: Key, mask: ModifierKeysMask| KeyPress::WithModifiers { key, mask };
+        let shift = ModifierKeysMask::new().with_shift();
+        let ctrl = ModifierKeysMask::new().with_ctrl();
+        let alt = ModifierKeysMask::new().with_alt();
+
+        Ok(match key {
+            // Chars.
+            TermionKey::Char('\n' | '\r') => special(SpecialKey::Enter),
+            TermionKey::Char('\t') => special(SpecialKey::Tab),
+            TermionKey::Char(character) => plain(Key::Character(character)),
+            TermionKey::Ctrl(character) => {
+                with_modifiers(Key::Character(character), ctrl)
+            }
+            TermionKey::Alt(character) => with_modifiers(Key::Character(character), alt),
+            TermionKey::Null => with_modifiers(Key::Character(' '), ctrl),
+
+            // Special keys.
+            TermionKey::Backspace => special(SpecialKey::Backspace),
+            TermionKey::Left => special(SpecialKey::Left),
+            TermionKey::Right => special(SpecialKey::Right),
+            TermionKey::Up => special(SpecialKey::Up),
+            TermionKey::Down => special(SpecialKey::Down),
+            TermionKey::Home => special(SpecialKey::Home),
+            TermionKey::End => special(SpecialKey::End),
+            TermionKey::PageUp => special(SpecialKey::PageUp),
+            TermionKey::PageDown => special(SpecialKey::PageDown),
+            TermionKey::Delete => special(SpecialKey::Delete),
+            TermionKey::Insert => special(SpecialKey::Insert),
+            TermionKey::Esc => special(SpecialKey::Esc),
+
+            // Special keys w/ modifiers.
+            TermionKey::BackTab => {
+                with_modifiers(Key::SpecialKey(SpecialKey::BackTab), shift)
+            }
+            TermionKey::ShiftLeft => {
+                with_modifiers(Key::SpecialKey(SpecialKey::Left), shift)
+            }
+            TermionKey::ShiftRight => {
+                with_modifiers(Key::SpecialKey(SpecialKey::Right), shift)
+            }
+            TermionKey::ShiftUp => with_modifiers(Key::SpecialKey(SpecialKey::Up), shift),
+            TermionKey::ShiftDown => {
+                with_modifiers(Key::SpecialKey(SpecialKey::Down), shift)
+            }
+            TermionKey::AltLeft => with_modifiers(Key::SpecialKey(SpecialKey::Left), alt),
+            TermionKey::AltRight => {
+                with_modifiers(Key::SpecialKey(SpecialKey::Right), alt)
+            }
+            TermionKey::AltUp => with_modifiers(Key::SpecialKey(SpecialKey::Up), alt),
+            TermionKey::AltDown => with_modifiers(Key::SpecialKey(SpecialKey::Down), alt),
+            TermionKey::CtrlLeft => {
+                with_modifiers(Key::SpecialKey(SpecialKey::Left), ctrl)
+            }
+            TermionKey::CtrlRight => {
+                with_modifiers(Key::SpecialKey(SpecialKey::Right), ctrl)
+            }
+            TermionKey::CtrlUp => with_modifiers(Key::SpecialKey(SpecialKey::Up), ctrl),
+            TermionKey::CtrlDown => {
+                with_modifiers(Key::SpecialKey(SpecialKey::Down), ctrl)
+            }
+            TermionKey::CtrlHome => {
+                with_modifiers(Key::SpecialKey(SpecialKey::Home), ctrl)
+            }
+            TermionKey::CtrlEnd => with_modifiers(Key::SpecialKey(SpecialKey::End), ctrl),
+
+            // Function keys.
+            TermionKey::F(number) => plain(Key::FunctionKey(match number {
+                1 => FunctionKey::F1,
+                2 => FunctionKey::F2,
+                3 => FunctionKey::F3,
+                4 => FunctionKey::F4,
+                5 => FunctionKey::F5,
+                6 => FunctionKey::F6,
+                7 => FunctionKey::F7,
+                8 => FunctionKey::F8,
+                9 => FunctionKey::F9,
+                10 => FunctionKey::F10,
+                11 => FunctionKey::F11,
+                12 => FunctionKey::F12,
+                _ => return Err(()),
+            })),
+
+            _ => return Err(()),
+        })
+    }
+}
+
+/// Termion doesn't report which button is released or dragged (held), so it is assumed
+/// to be [Button::Left]. It also doesn't report modifier keys.
+impl From<MouseEvent> for MouseInput {
+    fn from(mouse_event: MouseEvent) -> Self {
+        let (kind, col, row) = match mouse_event {
+            MouseEvent::Press(mouse_button, col, row) => {
+                let kind = match mouse_button {
+                    MouseButton::Left => MouseInputKind::MouseDown(Button::Left),
+                    MouseButton::Right => MouseInputKind::MouseDown(Button::Right),
+                    MouseButton::Middle => MouseInputKind::MouseDown(Button::Middle),
+                    MouseButton::WheelUp => MouseInputKind::ScrollUp,
+                    MouseButton::WheelDown => MouseInputKind::ScrollDown,
+                    MouseButton::WheelLeft => MouseInputKind::ScrollLeft,
+                    MouseButton::WheelRight => MouseInputKind::ScrollRight,
+                };
+                (kind, col, row)
+            }
+            MouseEvent::Release(col, row) => {
+                (MouseInputKind::MouseUp(Button::Left), col, row)
+            }
+            MouseEvent::Hold(col, row) => {
+                (MouseInputKind::MouseDrag(Button::Left), col, row)
+            }
+        };
+
+        MouseInput {
+            pos: position!(
+                col_index: col.saturating_sub(1),
+                row_index: row.saturating_sub(1)
+            ),
+            kind,
+            maybe_modifier_keys: None,
+        }
+    }
+}
@@ -0,0 +1,110 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+pub struct OffscreenBufferPaintImplTermion;
+
+impl OffscreenBufferPaint for OffscreenBufferPaintImplTermion {
+    fn paint(
+        &mut self,
+        render_ops: RenderOps,
+        flush_kind: FlushKind,
+        window_size: Size,
+        output_device: &mut OutputDevice,
+    ) {
+        let mut skip_flush = false;
+
+        if let FlushKind::ClearBeforeFlush = flush_kind {
+            RenderOpImplTermion {}.clear_before_flush(output_device);
+        }
+
+        // Execute each RenderOp.
+        execute_all(&render_ops, &mut skip_flush, window_size, output_device);
+
+        // Flush everything to the terminal.
+        if !skip_flush {
+            RenderOpImplTermion {}.flush(output_device)
+        };
+
+        // Debug output.
+        call_if_true!(DEBUG_TUI_SHOW_PIPELINE, {
+            let msg = format!(
+                "🎨 offscreen_buffer_paint_impl_termion::paint() ok ✅: render_ops: \n{render_ops:?}",
+            );
+            log_info(msg);
+        });
+    }
+
+    fn paint_diff(
+        &mut self,
+        render_ops: RenderOps,
+        window_size: Size,
+        output_device: &mut OutputDevice,
+    ) {
+        let mut skip_flush = false;
+
+        // Execute each RenderOp.
+        execute_all(&render_ops, &mut skip_flush, window_size, output_device);
+
+        // Flush everything to the terminal.
+        if !skip_flush {
+            RenderOpImplTermion {}.flush(output_device)
+        };
+
+        // Debug output.
+        call_if_true!(DEBUG_TUI_SHOW_PIPELINE, {
+            let msg = format!(
+                "🎨 offscreen_buffer_paint_impl_termion::paint_diff() ok ✅: render_ops: \n{render_ops:?}"
+            );
+            log_info(msg);
+        });
+    }
+
+    /// Converting an [OffscreenBuffer] into [RenderOps] doesn't depend on the backend, so
+    /// this is the same as [OffscreenBufferPaintImplCrossterm::render].
+    fn render(&mut self, offscreen_buffer: &OffscreenBuffer) -> RenderOps {
+        OffscreenBufferPaintImplCrossterm {}.render(offscreen_buffer)
+    }
+
+    /// Same as [OffscreenBufferPaintImplCrossterm::render_diff].
+    fn render_diff(&mut self, diff_chunks: &PixelCharDiffChunks) -> RenderOps {
+        OffscreenBufferPaintImplCrossterm {}.render_diff(diff_chunks)
+    }
+}
+
+/// Unlike [RenderOps::execute_all], this always paints w/ [RenderOpImplTermion]
+/// (regardless of [TERMINAL_LIB_BACKEND]).
+fn execute_all(
+    render_ops: &RenderOps,
+    skip_flush: &mut bool,
+    window_size: Size,
+    output_device: &mut OutputDevice,
+) {
+    let mut local_data = RenderOpsLocalData::default();
+    for render_op in render_ops.iter() {
+        RenderOpImplTermion {}.paint(
+            skip_flush,
+            render_op,
+            window_size,
+            &mut local_data,
+            output_device,
+        );
+    }
+}
@@ -0,0 +1,325 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::{io::{stdout, Stdout, Write},
+          sync::Mutex};
+
+use r3bl_rs_utils_core::*;
+use termion::{clear, color, cursor, raw::*, screen, style};
+
+use crate::*;
+
+/// Struct representing the implementation of [RenderOp] for termion terminal backend. This
+/// empty struct is needed since the [Flush] trait needs to be implemented.
+pub struct RenderOpImplTermion;
+
+/// Termion restores the terminal (takes it out of raw mode) when the [RawTerminal] is
+/// dropped, so it is held here between [RenderOp::EnterRawMode] and
+/// [RenderOp::ExitRawMode].
+static RAW_TERMINAL: Mutex<Option<RawTerminal<Stdout>>> = Mutex::new(None);
+
+/// These are the same sequences that [termion::input::MouseTerminal] uses to enable /
+/// disable mouse reporting (it does not expose them).
+const ENABLE_MOUSE_CAPTURE: &str = "\x1b[?1000h\x1b[?1002h\x1b[?1015h\x1b[?1006h";
+const DISABLE_MOUSE_CAPTURE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+
+mod render_op_impl_termion_impl_trait_paint_render_op {
+    use super::*;
+
+    impl PaintRenderOp for RenderOpImplTermion {
+        fn paint(
+            &mut self,
+            skip_flush: &mut bool,
+            command_ref: &RenderOp,
+            window_size: Size,
+            local_data: &mut RenderOpsLocalData,
+            output_device: &mut OutputDevice,
+        ) {
+            match command_ref {
+                RenderOp::Noop => {}
+                RenderOp::EnterRawMode => {
+                    RenderOpImplTermion::raw_mode_enter(skip_flush, output_device);
+                }
+                RenderOp::ExitRawMode => {
+                    RenderOpImplTermion::raw_mode_exit(skip_flush, output_device);
+                }
+                RenderOp::MoveCursorPositionAbs(abs_pos) => {
+                    RenderOpImplTermion::move_cursor_position_abs(
+                        *abs_pos,
+                        window_size,
+                        local_data,
+                        output_device,
+                    );
+                }
+                RenderOp::MoveCursorPositionRelTo(box_origin_pos, content_rel_pos) => {
+                    RenderOpImplTermion::move_cursor_position_abs(
+                        *box_origin_pos + *content_rel_pos,
+                        window_size,
+                        local_data,
+                        output_device,
+                    );
+                }
+                RenderOp::ClearScreen => {
+                    exec_render_op!(
+                        write!(output_device, "{}", clear::All),
+                        "ClearScreen"
+                    )
+                }
+                RenderOp::SetFgColor(color) => {
+                    RenderOpImplTermion::set_fg_color(color, output_device);
+                }
+                RenderOp::SetBgColor(color) => {
+                    RenderOpImplTermion::set_bg_color(color, output_device);
+                }
+                RenderOp::ResetColor => {
+                    exec_render_op!(
+                        write!(output_device, "{}", style::Reset),
+                        "ResetColor"
+                    )
+                }
+                RenderOp::ApplyColors(style) => {
+                    RenderOpImplTermion::apply_colors(style, output_device);
+                }
+                RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                    text,
+                    maybe_style,
+                ) => {
+                    RenderOpImplTermion::paint_text_with_attributes(
+                        text,
+                        maybe_style,
+                        window_size,
+                        local_data,
+                        output_device,
+                    );
+                }
+                RenderOp::PaintTextWithAttributes(_text, _maybe_style) => {
+                    // This should never be executed! The compositor always renders to an offscreen
+                    // buffer first, then that is diff'd and then painted via calls to
+                    // CompositorNoClipTruncPaintTextWithAttributes.
+                }
+            }
+        }
+    }
+}
+
+pub mod render_op_impl_termion_impl_trait_flush {
+    use super::*;
+
+    impl Flush for RenderOpImplTermion {
+        fn flush(&mut self, output_device: &mut OutputDevice) { flush(output_device); }
+        fn clear_before_flush(&mut self, output_device: &mut OutputDevice) {
+            clear_before_flush(output_device);
+        }
+    }
+
+    fn clear_before_flush(output_device: &mut OutputDevice) {
+        exec_render_op!(
+            write!(output_device, "{}{}", style::Reset, clear::All),
+            "flush() -> after ResetColor, Clear"
+        )
+    }
+
+    pub fn flush(output_device: &mut OutputDevice) {
+        exec_render_op!(output_device.flush(), "flush() -> output device");
+    }
+}
+
+mod render_op_impl_termion_impl {
+    use super::*;
+
+    impl RenderOpImplTermion {
+        /// Termion's [cursor::Goto] is 1 based, while [Position] is 0 based.
+        pub fn move_cursor_position_abs(
+            abs_pos: Position,
+            window_size: Size,
+            local_data: &mut RenderOpsLocalData,
+            output_device: &mut OutputDevice,
+        ) {
+            let Position {
+                col_index: col,
+                row_index: row,
+            } = sanitize_and_save_abs_position(abs_pos, window_size, local_data);
+            exec_render_op!(
+                write!(output_device, "{}", cursor::Goto(*col + 1, *row + 1)),
+                format!("MoveCursorPosition(col: {}, row: {})", *col, *row)
+            )
+        }
+
+        /// The real terminal is only taken out of raw mode if the [OutputDevice] is
+        /// stdout.
+        pub fn raw_mode_exit(skip_flush: &mut bool, output_device: &mut OutputDevice) {
+            exec_render_op!(
+                write!(
+                    output_device,
+                    "{}{}{}",
+                    cursor::Show,
+                    screen::ToMainScreen,
+                    DISABLE_MOUSE_CAPTURE
+                ),
+                "ExitRawMode -> Show, ToMainScreen, DisableMouseCapture"
+            );
+            render_op_impl_termion_impl_trait_flush::flush(output_device);
+            if output_device.is_stdout() {
+                // Dropping the raw terminal restores the terminal.
+                if let Ok(mut maybe_raw_terminal) = RAW_TERMINAL.lock() {
+                    maybe_raw_terminal.take();
+                }
+            }
+            *skip_flush = true;
+        }
+
+        /// The real terminal is only put in raw mode if the [OutputDevice] is stdout.
+        pub fn raw_mode_enter(skip_flush: &mut bool, output_device: &mut OutputDevice) {
+            if output_device.is_stdout() {
+                exec_render_op!(
+                    stdout().into_raw_mode().map(|raw_terminal| {
+                        if let Ok(mut maybe_raw_terminal) = RAW_TERMINAL.lock() {
+                            maybe_raw_terminal.replace(raw_terminal);
+                        }
+                    }),
+                    "EnterRawMode -> into_raw_mode()"
+                );
+            }
+            exec_render_op!(
+                write!(
+                    output_device,
+                    "{}{}{}{}{}",
+                    ENABLE_MOUSE_CAPTURE,
+                    screen::ToAlternateScreen,
+                    cursor::Goto(1, 1),
+                    clear::All,
+                    cursor::Hide
+                ),
+                "EnterRawMode -> EnableMouseCapture, ToAlternateScreen, Goto(1,1), clear::All, Hide"
+            );
+            render_op_impl_termion_impl_trait_flush::flush(output_device);
+            *skip_flush = true;
+        }
+
+        pub fn set_fg_color(color: &TuiColor, output_device: &mut OutputDevice) {
+            let color = convert_from_tui_color_to_termion_color(*color);
+            exec_render_op!(
+                write!(output_device, "{}", color::Fg(color)),
+                format!("SetFgColor({color:?})")
+            )
+        }
+
+        pub fn set_bg_color(color: &TuiColor, output_device: &mut OutputDevice) {
+            let color = convert_from_tui_color_to_termion_color(*color);
+            exec_render_op!(
+                write!(output_device, "{}", color::Bg(color)),
+                format!("SetBgColor({color:?})")
+            )
+        }
+
+        /// Docs: <https://docs.rs/termion/latest/termion/color/index.html>
+        pub fn apply_colors(
+            maybe_style: &Option<TuiStyle>,
+            output_device: &mut OutputDevice,
+        ) {
+            if let Some(style) = maybe_style {
+                // Handle background color.
+                if let Some(tui_color_bg) = style.color_bg {
+                    let color_bg = convert_from_tui_color_to_termion_color(tui_color_bg);
+                    exec_render_op!(
+                        write!(output_device, "{}", color::Bg(color_bg)),
+                        format!("ApplyColors -> SetBgColor({color_bg:?})")
+                    )
+                }
+
+                // Handle foreground color.
+                if let Some(tui_color_fg) = style.color_fg {
+                    let color_fg = convert_from_tui_color_to_termion_color(tui_color_fg);
+                    exec_render_op!(
+                        write!(output_device, "{}", color::Fg(color_fg)),
+                        format!("ApplyColors -> SetFgColor({color_fg:?})")
+                    )
+                }
+            }
+        }
+
+        /// Use [TuiStyle] to set termion [style]s (bold, italic, etc), paint the text,
+        /// and then reset the styles (if any were set).
+        pub fn paint_text_with_attributes(
+            text: &str,
+            maybe_style: &Option<TuiStyle>,
+            window_size: Size,
+            local_data: &mut RenderOpsLocalData,
+            output_device: &mut OutputDevice,
+        ) {
+            let attributes = match maybe_style {
+                Some(style) => style_to_attributes(style),
+                None => String::new(),
+            };
+            let needs_reset = !attributes.is_empty();
+
+            if needs_reset {
+                exec_render_op!(
+                    write!(output_device, "{attributes}"),
+                    format!("PaintWithAttributes -> SetAttributes({attributes:?})")
+                );
+            }
+
+            // Actually paint text (wrapped in OSC 8 escape sequences if it is a hyperlink).
+            let maybe_hyperlink = maybe_style.and_then(|style| style.hyperlink);
+            let text_to_paint = TuiHyperlink::wrap_text(&maybe_hyperlink, text);
+            exec_render_op!(
+                write!(output_device, "{text_to_paint}"),
+                format!("Print( {text} \"{text}\")")
+            );
+
+            if needs_reset {
+                exec_render_op!(
+                    write!(output_device, "{}", style::Reset),
+                    "PaintWithAttributes -> Reset"
+                );
+            }
+
+            // Update cursor position after paint.
+            let mut cursor_position_copy = local_data.cursor_position;
+            cursor_position_copy.col_index += UnicodeString::from(text).display_width;
+            sanitize_and_save_abs_position(cursor_position_copy, window_size, local_data);
+        }
+    }
+
+    fn style_to_attributes(style: &TuiStyle) -> String {
+        let mut it = String::new();
+        if style.bold {
+            it.push_str(style::Bold.as_ref());
+        }
+        if style.italic {
+            it.push_str(style::Italic.as_ref());
+        }
+        if style.dim {
+            it.push_str(style::Faint.as_ref());
+        }
+        if style.underline {
+            it.push_str(style::Underline.as_ref());
+        }
+        if style.reverse {
+            it.push_str(style::Invert.as_ref());
+        }
+        if style.hidden {
+            // Termion doesn't have a style for this.
+            it.push_str("\x1b[8m");
+        }
+        if style.strikethrough {
+            it.push_str(style::CrossedOut.as_ref());
+        }
+        it
+    }
+}
@@ -0,0 +1,427 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+//! The termion and crossterm backends don't emit exactly the same bytes (eg: termion
+//! resets w/ `ESC[m` and crossterm w/ `ESC[0m`), so these tests apply the output of both
+//! to a [VirtualTerminal] and compare the resulting screens.
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write,
+              sync::{Arc, Mutex}};
+
+    use crossterm::event::{Event as CrosstermEvent,
+                           KeyCode,
+                           KeyEvent,
+                           KeyModifiers,
+                           MouseButton,
+                           MouseEvent,
+                           MouseEventKind};
+    use r3bl_ansi_color::{global_color_support, ColorSupport};
+    use r3bl_rs_utils_core::*;
+    use serial_test::serial;
+
+    use crate::*;
+
+    fn get_window_size() -> Size { size!(col_count: 12, row_count: 4) }
+
+    /// Paint the `render_ops` w/ the given backend, and return the bytes that it emits,
+    /// along w/ a [VirtualTerminal] that they have been applied to.
+    fn paint_with(
+        backend: &mut (impl PaintRenderOp + Flush),
+        flush_kind: FlushKind,
+        render_ops: &RenderOps,
+    ) -> (String, VirtualTerminal) {
+        let bytes = Arc::new(Mutex::new(Vec::<u8>::new()));
+        let mut output_device = OutputDevice::Writer(bytes.clone());
+
+        if let FlushKind::ClearBeforeFlush = flush_kind {
+            backend.clear_before_flush(&mut output_device);
+        }
+        let mut local_data = RenderOpsLocalData::default();
+        let mut skip_flush = false;
+        for render_op in render_ops.iter() {
+            backend.paint(
+                &mut skip_flush,
+                render_op,
+                get_window_size(),
+                &mut local_data,
+                &mut output_device,
+            );
+        }
+        backend.flush(&mut output_device);
+
+        let bytes = bytes.lock().unwrap().clone();
+        let mut virtual_terminal = VirtualTerminal::new(get_window_size());
+        virtual_terminal.write_all(&bytes).unwrap();
+        (String::from_utf8(bytes).unwrap(), virtual_terminal)
+    }
+
+    fn assert_same_screen(crossterm: &VirtualTerminal, termion: &VirtualTerminal) {
+        assert_eq!(crossterm.get_lines(), termion.get_lines());
+        for row_index in 0..ch!(@to_usize get_window_size().row_count) {
+            for col_index in 0..ch!(@to_usize get_window_size().col_count) {
+                let position = position!(col_index: col_index, row_index: row_index);
+                assert_eq!(
+                    crossterm.get_cell(position),
+                    termion.get_cell(position),
+                    "{position:?}"
+                );
+            }
+        }
+        assert_eq!(
+            crossterm.get_cursor_position(),
+            termion.get_cursor_position()
+        );
+        assert_eq!(crossterm.is_cursor_visible(), termion.is_cursor_visible());
+        assert_eq!(
+            crossterm.is_alternate_screen(),
+            termion.is_alternate_screen()
+        );
+    }
+
+    fn make_render_ops() -> RenderOps {
+        let style = TuiStyle {
+            bold: true,
+            italic: true,
+            dim: true,
+            underline: true,
+            reverse: true,
+            strikethrough: true,
+            ..Default::default()
+        };
+        render_ops!(
+            @new
+            RenderOp::ResetColor,
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 1, row_index: 0)),
+            RenderOp::SetFgColor(color!(@red)),
+            RenderOp::SetBgColor(color!(@dark_grey)),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("ab".into(), None),
+            RenderOp::ResetColor,
+            RenderOp::MoveCursorPositionRelTo(
+                position!(col_index: 2, row_index: 1),
+                position!(col_index: 1, row_index: 0)
+            ),
+            RenderOp::ApplyColors(Some(TuiStyle {
+                color_fg: Some(color!(10, 200, 30)),
+                color_bg: Some(TuiColor::Ansi(AnsiValue::new(123))),
+                ..Default::default()
+            })),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes(
+                "😃c".into(),
+                Some(style)
+            ),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("d".into(), None),
+            RenderOp::ResetColor,
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 50, row_index: 50)),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("e".into(), None),
+        )
+    }
+
+    #[serial]
+    #[test]
+    fn test_render_ops_parity() {
+        for color_support in [
+            ColorSupport::Truecolor,
+            ColorSupport::Ansi256,
+            ColorSupport::Grayscale,
+        ] {
+            global_color_support::set_override(color_support);
+
+            let render_ops = make_render_ops();
+            let (_, crossterm) = paint_with(
+                &mut RenderOpImplCrossterm {},
+                FlushKind::JustFlush,
+                &render_ops,
+            );
+            let (_, termion) = paint_with(
+                &mut RenderOpImplTermion {},
+                FlushKind::JustFlush,
+                &render_ops,
+            );
+            assert_eq!(
+                termion.get_lines(),
+                vec![" ab", "   😃cd", "", "           e"]
+            );
+            assert_same_screen(&crossterm, &termion);
+
+            // Clear screen.
+            let render_ops = render_ops!(@new RenderOp::ClearScreen);
+            let (_, crossterm) = paint_with(
+                &mut RenderOpImplCrossterm {},
+                FlushKind::ClearBeforeFlush,
+                &render_ops,
+            );
+            let (_, termion) = paint_with(
+                &mut RenderOpImplTermion {},
+                FlushKind::ClearBeforeFlush,
+                &render_ops,
+            );
+            assert_same_screen(&crossterm, &termion);
+        }
+
+        global_color_support::clear_override();
+    }
+
+    #[test]
+    fn test_move_cursor_emits_same_bytes() {
+        let render_ops = render_ops!(
+            @new
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 2, row_index: 1))
+        );
+        let (crossterm, _) = paint_with(
+            &mut RenderOpImplCrossterm {},
+            FlushKind::JustFlush,
+            &render_ops,
+        );
+        let (termion, _) = paint_with(
+            &mut RenderOpImplTermion {},
+            FlushKind::JustFlush,
+            &render_ops,
+        );
+        assert_eq!(termion, "\x1b[2;3H");
+        assert_eq!(crossterm, termion);
+    }
+
+    #[test]
+    fn test_raw_mode_parity() {
+        let enter = render_ops!(
+            @new
+            RenderOp::EnterRawMode,
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 2)),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("hi".into(), None),
+        );
+        let (_, crossterm) =
+            paint_with(&mut RenderOpImplCrossterm {}, FlushKind::JustFlush, &enter);
+        let (termion_bytes, termion) =
+            paint_with(&mut RenderOpImplTermion {}, FlushKind::JustFlush, &enter);
+        assert!(termion.is_alternate_screen());
+        assert!(!termion.is_cursor_visible());
+        assert!(termion_bytes.contains("\x1b[?1000h"));
+        assert_same_screen(&crossterm, &termion);
+
+        let exit = render_ops!(@new RenderOp::ExitRawMode);
+        let (_, crossterm) =
+            paint_with(&mut RenderOpImplCrossterm {}, FlushKind::JustFlush, &exit);
+        let (termion_bytes, termion) =
+            paint_with(&mut RenderOpImplTermion {}, FlushKind::JustFlush, &exit);
+        assert!(termion.is_cursor_visible());
+        assert!(termion_bytes.contains("\x1b[?1049l"));
+        assert!(termion_bytes.contains("\x1b[?1000l"));
+        assert_same_screen(&crossterm, &termion);
+    }
+
+    fn make_offscreen_buffer(text: &str) -> OffscreenBuffer {
+        let style = TuiStyle {
+            bold: true,
+            color_fg: Some(color!(@cyan)),
+            color_bg: Some(color!(1, 2, 3)),
+            ..Default::default()
+        };
+        let pipeline = render_pipeline!(
+            @new ZOrder::Normal
+            =>
+                RenderOp::ResetColor,
+                RenderOp::MoveCursorPositionAbs(position!(col_index: 1, row_index: 1)),
+                RenderOp::ApplyColors(Some(style)),
+                RenderOp::PaintTextWithAttributes(text.into(), Some(style)),
+                RenderOp::ResetColor,
+                RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 3)),
+                RenderOp::PaintTextWithAttributes("status".into(), None)
+        );
+        pipeline.convert(get_window_size())
+    }
+
+    #[serial]
+    #[test]
+    fn test_offscreen_buffer_paint_parity() {
+        global_color_support::set_override(ColorSupport::Truecolor);
+
+        // Full paint.
+        let offscreen_buffer = make_offscreen_buffer("hello 😃");
+        let render_ops = OffscreenBufferPaintImplTermion {}.render(&offscreen_buffer);
+        assert_eq!(
+            render_ops,
+            OffscreenBufferPaintImplCrossterm {}.render(&offscreen_buffer)
+        );
+        let (_, mut crossterm) = paint_with(
+            &mut RenderOpImplCrossterm {},
+            FlushKind::ClearBeforeFlush,
+            &render_ops,
+        );
+        let bytes = Arc::new(Mutex::new(Vec::<u8>::new()));
+        OffscreenBufferPaintImplTermion {}.paint(
+            render_ops,
+            FlushKind::ClearBeforeFlush,
+            get_window_size(),
+            &mut OutputDevice::Writer(bytes.clone()),
+        );
+        let mut termion = VirtualTerminal::new(get_window_size());
+        termion.write_all(&bytes.lock().unwrap()).unwrap();
+        assert_eq!(termion.get_lines(), vec!["", " hello 😃", "", "status"]);
+        assert_same_screen(&crossterm, &termion);
+
+        // Diff paint (on top of the full paint).
+        let OffscreenBufferDiffResult::Comparable(diff_chunks) =
+            offscreen_buffer.diff(&make_offscreen_buffer("help"))
+        else {
+            panic!("offscreen buffers should be comparable");
+        };
+        let render_ops = OffscreenBufferPaintImplTermion {}.render_diff(&diff_chunks);
+        let (crossterm_diff, _) = paint_with(
+            &mut RenderOpImplCrossterm {},
+            FlushKind::JustFlush,
+            &render_ops,
+        );
+        crossterm.write_all(crossterm_diff.as_bytes()).unwrap();
+        let bytes = Arc::new(Mutex::new(Vec::<u8>::new()));
+        OffscreenBufferPaintImplTermion {}.paint_diff(
+            render_ops,
+            get_window_size(),
+            &mut OutputDevice::Writer(bytes.clone()),
+        );
+        termion.write_all(&bytes.lock().unwrap()).unwrap();
+        assert_eq!(termion.get_lines(), vec!["", " help", "", "status"]);
+        assert_same_screen(&crossterm, &termion);
+
+        global_color_support::clear_override();
+    }
+
+    #[serial]
+    #[test]
+    fn test_hyperlink_parity() {
+        global_hyperlink_support::set_override(true);
+
+        let style = TuiStyle {
+            hyperlink: Some(TuiHyperlink::new("https://r3bl.com")),
+            ..Default::default()
+        };
+        let render_ops = render_ops!(
+            @new
+            RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 0)),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("a".into(), None),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("r3bl".into(), Some(style)),
+            RenderOp::CompositorNoClipTruncPaintTextWithAttributes("b".into(), None),
+        );
+        let (crossterm_bytes, crossterm) = paint_with(
+            &mut RenderOpImplCrossterm {},
+            FlushKind::JustFlush,
+            &render_ops,
+        );
+        let (termion_bytes, termion) = paint_with(
+            &mut RenderOpImplTermion {},
+            FlushKind::JustFlush,
+            &render_ops,
+        );
+        let osc8 = format!(
+            "{}r3bl{OSC8_END}",
+            style.hyperlink.unwrap().get_osc8_start()
+        );
+        assert!(crossterm_bytes.contains(&osc8));
+        assert!(termion_bytes.contains(&osc8));
+        assert_eq!(
+            termion.get_style_at(position!(col_index: 1, row_index: 0)),
+            Some(style)
+        );
+        assert_eq!(
+            termion.get_style_at(position!(col_index: 5, row_index: 0)),
+            Some(TuiStyle::default())
+        );
+        assert_same_screen(&crossterm, &termion);
+
+        global_hyperlink_support::clear_override();
+    }
+
+    fn parse_termion_event(bytes: &[u8]) -> termion::event::Event {
+        let mut iter = bytes[1..].iter().map(|byte| Ok(*byte));
+        termion::event::parse_event(bytes[0], &mut iter)
+            .unwrap_or_else(|_| panic!("{bytes:?}"))
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> CrosstermEvent {
+        CrosstermEvent::Key(KeyEvent::new(code, modifiers))
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> CrosstermEvent {
+        CrosstermEvent::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    /// The bytes are what the terminal sends, and the crossterm event is what crossterm
+    /// parses them into.
+    #[test]
+    fn test_input_event_parity() {
+        let none = KeyModifiers::NONE;
+        let shift = KeyModifiers::SHIFT;
+        let ctrl = KeyModifiers::CONTROL;
+        let alt = KeyModifiers::ALT;
+
+        let cases: Vec<(&[u8], CrosstermEvent)> = vec![
+            (b"a", key(KeyCode::Char('a'), none)),
+            (b"A", key(KeyCode::Char('A'), shift)),
+            ("😃".as_bytes(), key(KeyCode::Char('😃'), none)),
+            (b"\r", key(KeyCode::Enter, none)),
+            (b"\t", key(KeyCode::Tab, none)),
+            (b"\x1b[Z", key(KeyCode::BackTab, shift)),
+            (b"\x7f", key(KeyCode::Backspace, none)),
+            (b"\x11", key(KeyCode::Char('q'), ctrl)),
+            (b"\x00", key(KeyCode::Char(' '), ctrl)),
+            (b"\x1bx", key(KeyCode::Char('x'), alt)),
+            (b"\x1b[A", key(KeyCode::Up, none)),
+            (b"\x1b[1;2D", key(KeyCode::Left, shift)),
+            (b"\x1b[1;3B", key(KeyCode::Down, alt)),
+            (b"\x1b[1;5C", key(KeyCode::Right, ctrl)),
+            (b"\x1b[1;5H", key(KeyCode::Home, ctrl)),
+            (b"\x1b[3~", key(KeyCode::Delete, none)),
+            (b"\x1b[5~", key(KeyCode::PageUp, none)),
+            (b"\x1bOP", key(KeyCode::F(1), none)),
+            (b"\x1b[15~", key(KeyCode::F(5), none)),
+            (
+                b"\x1b[<0;3;2M",
+                mouse(MouseEventKind::Down(MouseButton::Left), 2, 1),
+            ),
+            (
+                b"\x1b[<0;3;2m",
+                mouse(MouseEventKind::Up(MouseButton::Left), 2, 1),
+            ),
+            (
+                b"\x1b[<32;4;2M",
+                mouse(MouseEventKind::Drag(MouseButton::Left), 3, 1),
+            ),
+            (b"\x1b[<64;1;1M", mouse(MouseEventKind::ScrollUp, 0, 0)),
+            (b"\x1b[<65;1;1M", mouse(MouseEventKind::ScrollDown, 0, 0)),
+        ];
+
+        for (bytes, crossterm_event) in cases {
+            let termion_event = parse_termion_event(bytes);
+            let from_termion: Result<InputEvent, ()> = termion_event.clone().try_into();
+            let from_crossterm: Result<InputEvent, ()> = crossterm_event.try_into();
+            assert!(from_crossterm.is_ok(), "{bytes:?}");
+            assert_eq!(from_termion, from_crossterm, "{bytes:?} {termion_event:?}");
+        }
+
+        // Termion's event iterator (and not its parser) reports a lone `ESC` as this.
+        let from_termion: Result<InputEvent, ()> =
+            termion::event::Event::Key(termion::event::Key::Esc).try_into();
+        let from_crossterm: Result<InputEvent, ()> = key(KeyCode::Esc, none).try_into();
+        assert_eq!(from_termion, from_crossterm);
+    }
+}
@@ -0,0 +1,168 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use r3bl_ansi_color::{global_color_support, ColorSupport};
+    use r3bl_rs_utils_core::*;
+    use serial_test::serial;
+
+    use crate::{editor_buffer_clipboard_support::test_clipboard_service_provider::TestClipboard,
+                test_editor::mock_real_objects_for_editor,
+                *};
+
+    fn make_global_data(window_size: Size) -> (GlobalData<(), ()>, SafeVirtualTerminal) {
+        let mut global_data =
+            mock_real_objects_for_editor::make_global_data(Some(window_size));
+        let (output_device, virtual_terminal) =
+            OutputDevice::new_virtual_terminal(window_size);
+        global_data.output_device = output_device;
+        (global_data, virtual_terminal)
+    }
+
+    fn make_pipeline(text: &str, maybe_style: Option<TuiStyle>) -> RenderPipeline {
+        let mut pipeline = render_pipeline!(
+            @new ZOrder::Normal
+            =>
+                RenderOp::ResetColor,
+                RenderOp::MoveCursorPositionAbs(position!(col_index: 2, row_index: 1))
+        );
+        if let Some(style) = maybe_style {
+            render_pipeline!(
+                @push_into pipeline
+                at ZOrder::Normal =>
+                    RenderOp::ApplyColors(Some(style))
+            );
+        }
+        render_pipeline!(
+            @push_into pipeline
+            at ZOrder::Normal =>
+                RenderOp::PaintTextWithAttributes(text.to_string(), maybe_style)
+        );
+        pipeline
+    }
+
+    #[serial]
+    #[test]
+    fn test_paint_pipeline_to_virtual_terminal() {
+        global_color_support::set_override(ColorSupport::Truecolor);
+
+        let window_size = size!(col_count: 10, row_count: 3);
+        let (mut global_data, virtual_terminal) = make_global_data(window_size);
+        let style = TuiStyle {
+            bold: true,
+            strikethrough: true,
+            color_fg: Some(color!(@red)),
+            color_bg: Some(color!(10, 20, 30)),
+            ..Default::default()
+        };
+
+        // Full paint.
+        RenderOp::default().flush(&mut global_data.output_device);
+        RawMode::start(window_size, &mut global_data.output_device);
+        paint(
+            &make_pipeline("hello", Some(style)),
+            FlushKind::ClearBeforeFlush,
+            &mut global_data,
+        );
+        {
+            let virtual_terminal = virtual_terminal.lock().unwrap();
+            assert!(virtual_terminal.is_alternate_screen());
+            assert!(!virtual_terminal.is_cursor_visible());
+            assert_eq!(virtual_terminal.get_lines(), vec!["", "  hello", ""]);
+            let position = virtual_terminal.find("hello").unwrap();
+            assert_eq!(position, position!(col_index: 2, row_index: 1));
+            assert_eq!(virtual_terminal.get_style_at(position), Some(style));
+        }
+
+        // Diff paint.
+        paint(
+            &make_pipeline("help", None),
+            FlushKind::ClearBeforeFlush,
+            &mut global_data,
+        );
+        {
+            let virtual_terminal = virtual_terminal.lock().unwrap();
+            assert_eq!(virtual_terminal.get_lines(), vec!["", "  help", ""]);
+            assert_eq!(
+                virtual_terminal.get_style_at(position!(col_index: 2, row_index: 1)),
+                Some(TuiStyle::default())
+            );
+        }
+
+        RawMode::end(window_size, &mut global_data.output_device);
+        assert!(!virtual_terminal.lock().unwrap().is_alternate_screen());
+
+        global_color_support::clear_override();
+    }
+
+    fn render_editor(
+        editor_engine: &mut EditorEngine,
+        editor_buffer: &mut EditorBuffer,
+        global_data: &mut GlobalData<(), ()>,
+    ) {
+        let window_size = global_data.window_size;
+        let flex_box = FlexBox {
+            style_adjusted_bounds_size: window_size,
+            ..Default::default()
+        };
+        let pipeline = EditorEngineApi::render_engine(
+            editor_engine,
+            editor_buffer,
+            flex_box,
+            &mut HasFocus::default(),
+            window_size,
+        )
+        .unwrap();
+        paint(&pipeline, FlushKind::ClearBeforeFlush, global_data);
+    }
+
+    #[test]
+    fn test_paint_editor_to_virtual_terminal() {
+        let (mut global_data, virtual_terminal) =
+            make_global_data(size!(col_count: 10, row_count: 3));
+        let mut editor_buffer =
+            EditorBuffer::new_empty(&Some(DEFAULT_SYN_HI_FILE_EXT.to_owned()), &None);
+        let mut editor_engine = mock_real_objects_for_editor::make_editor_engine();
+
+        render_editor(&mut editor_engine, &mut editor_buffer, &mut global_data);
+        assert_eq!(
+            virtual_terminal.lock().unwrap().get_lines(),
+            vec!["", "", ""]
+        );
+
+        for input_event in [
+            InputEvent::Keyboard(keypress!(@char 'h')),
+            InputEvent::Keyboard(keypress!(@char 'i')),
+            InputEvent::Keyboard(keypress!(@special SpecialKey::Enter)),
+            InputEvent::Keyboard(keypress!(@char '😃')),
+        ] {
+            EditorEngineApi::apply_event(
+                &mut editor_buffer,
+                &mut editor_engine,
+                input_event,
+                &mut TestClipboard::default(),
+            )
+            .unwrap();
+        }
+        render_editor(&mut editor_engine, &mut editor_buffer, &mut global_data);
+        assert_eq!(
+            virtual_terminal.lock().unwrap().get_lines(),
+            vec!["hi", "😃", ""]
+        );
+    }
+}
@@ -0,0 +1,595 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+use std::io::Write;
+
+use r3bl_rs_utils_core::*;
+
+use crate::*;
+
+const ESC: u8 = 0x1b;
+const BEL: u8 = 0x07;
+
+/// A cell in the grid of a [VirtualTerminal].
+#[derive(Clone, Debug, PartialEq)]
+pub struct VirtualTerminalCell {
+    /// The grapheme cluster in this cell. This is empty for the cells that are covered by
+    /// a wide grapheme cluster (eg: "😃") in the cell to their left.
+    pub text: String,
+    pub style: TuiStyle,
+}
+
+impl Default for VirtualTerminalCell {
+    fn default() -> Self {
+        Self {
+            text: SPACER.to_string(),
+            style: TuiStyle::default(),
+        }
+    }
+}
+
+/// An in-memory terminal that applies the ANSI escape sequences written to it to a grid of
+/// [VirtualTerminalCell]s. Use it w/ [OutputDevice::new_virtual_terminal] to paint an
+/// [App] w/out a TTY, and then make assertions about the text and styles on its screen.
+///
+/// It only understands the sequences that the terminal backend emits:
+/// - Cursor movement (`CSI H`, `CSI A..D`, `CSI G`, `CSI d`), and showing / hiding it.
+/// - Clearing the screen (`CSI J`) and lines (`CSI K`).
+/// - Colors (16, 256, and RGB) and text attributes (`CSI m`).
+/// - Entering and leaving the alternate screen.
+/// - OSC 8 hyperlinks, which are applied to the [TuiStyle::hyperlink] of the cells.
+///
+/// Everything else is ignored. Text doesn't wrap or scroll, and the parts of it that are
+/// past the right edge of the screen are dropped.
+#[derive(Clone, Debug)]
+pub struct VirtualTerminal {
+    size: Size,
+    cells: Vec<Vec<VirtualTerminalCell>>,
+    /// The main screen's cells and cursor position, while the alternate screen is shown.
+    maybe_saved_screen: Option<(Vec<Vec<VirtualTerminalCell>>, Position)>,
+    cursor_position: Position,
+    /// The style set by the last `CSI m` sequences, which is applied to printed text.
+    style: TuiStyle,
+    /// The link opened by the last OSC 8 sequence, which is applied to printed text.
+    maybe_hyperlink: Option<TuiHyperlink>,
+    is_cursor_visible: bool,
+    /// Bytes at the end of the last write that are an incomplete escape sequence or
+    /// UTF-8 char.
+    pending_bytes: Vec<u8>,
+}
+
+impl VirtualTerminal {
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            cells: make_cells(size),
+            maybe_saved_screen: None,
+            cursor_position: position!(col_index: 0, row_index: 0),
+            style: TuiStyle::default(),
+            maybe_hyperlink: None,
+            is_cursor_visible: true,
+            pending_bytes: vec![],
+        }
+    }
+
+    pub fn get_size(&self) -> Size { self.size }
+
+    pub fn get_cursor_position(&self) -> Position { self.cursor_position }
+
+    pub fn is_cursor_visible(&self) -> bool { self.is_cursor_visible }
+
+    pub fn is_alternate_screen(&self) -> bool { self.maybe_saved_screen.is_some() }
+
+    pub fn get_cell(&self, position: Position) -> Option<&VirtualTerminalCell> {
+        self.cells
+            .get(ch!(@to_usize position.row_index))?
+            .get(ch!(@to_usize position.col_index))
+    }
+
+    pub fn get_style_at(&self, position: Position) -> Option<TuiStyle> {
+        self.get_cell(position).map(|cell| cell.style)
+    }
+
+    /// Returns the text of the line w/out trailing whitespace.
+    pub fn get_line(&self, row_index: usize) -> Option<String> {
+        let line = self.cells.get(row_index)?;
+        let text: String = line.iter().map(|cell| cell.text.as_str()).collect();
+        Some(text.trim_end().to_string())
+    }
+
+    /// Returns the text of every line on the screen w/out trailing whitespace.
+    pub fn get_lines(&self) -> Vec<String> {
+        (0..self.cells.len())
+            .filter_map(|row_index| self.get_line(row_index))
+            .collect()
+    }
+
+    /// Returns the position of the first cell (top to bottom, left to right) at which
+    /// `text` is on the screen.
+    pub fn find(&self, text: &str) -> Option<Position> {
+        for (row_index, line) in self.cells.iter().enumerate() {
+            let mut line_text = String::new();
+            let mut cell_byte_offsets = vec![];
+            for cell in line {
+                cell_byte_offsets.push(line_text.len());
+                line_text.push_str(&cell.text);
+            }
+            let Some(byte_offset) = line_text.find(text) else {
+                continue;
+            };
+            // Skip the empty cells that are covered by a wide grapheme cluster.
+            let col_index = cell_byte_offsets
+                .iter()
+                .zip(line)
+                .position(|(it, cell)| *it == byte_offset && !cell.text.is_empty())
+                .unwrap_or_default();
+            return Some(position!(col_index: col_index, row_index: row_index));
+        }
+        None
+    }
+
+    fn apply(&mut self, bytes: &[u8]) {
+        let mut index = 0;
+        while index < bytes.len() {
+            match bytes[index] {
+                ESC => match get_escape_sequence_len(&bytes[index..]) {
+                    Some(len) => {
+                        self.apply_escape_sequence(&bytes[index..index + len]);
+                        index += len;
+                    }
+                    None => break,
+                },
+                b'\r' => {
+                    self.cursor_position.col_index = ch!(0);
+                    index += 1;
+                }
+                b'\n' => {
+                    self.move_cursor_to(
+                        ch!(@to_usize self.cursor_position.row_index) + 1,
+                        ch!(@to_usize self.cursor_position.col_index),
+                    );
+                    index += 1;
+                }
+                byte if is_control(byte) => index += 1,
+                _ => {
+                    let text_len = bytes[index..]
+                        .iter()
+                        .position(|byte| is_control(*byte))
+                        .unwrap_or(bytes.len() - index);
+                    let text = &bytes[index..index + text_len];
+                    match std::str::from_utf8(text) {
+                        Ok(text) => {
+                            self.print(text);
+                            index += text_len;
+                        }
+                        Err(error) => {
+                            let valid_len = error.valid_up_to();
+                            if let Ok(valid_text) =
+                                std::str::from_utf8(&text[..valid_len])
+                            {
+                                self.print(valid_text);
+                            }
+                            index += valid_len;
+                            match error.error_len() {
+                                // Wait for the rest of the char.
+                                None if index + (text_len - valid_len) == bytes.len() => {
+                                    break
+                                }
+                                None => index += text_len - valid_len,
+                                Some(invalid_len) => {
+                                    self.print(&char::REPLACEMENT_CHARACTER.to_string());
+                                    index += invalid_len;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        self.pending_bytes = bytes[index..].to_vec();
+    }
+
+    fn print(&mut self, text: &str) {
+        let col_count = ch!(@to_usize self.size.col_count);
+        let row_index = ch!(@to_usize self.cursor_position.row_index);
+        let style = TuiStyle {
+            hyperlink: self.maybe_hyperlink,
+            ..self.style
+        };
+        let Some(line) = self.cells.get_mut(row_index) else {
+            return;
+        };
+
+        for segment in UnicodeString::from(text).iter() {
+            let col_index = ch!(@to_usize self.cursor_position.col_index);
+            let width = ch!(@to_usize segment.unicode_width);
+            if width == 0 {
+                // Combine w/ the grapheme cluster to the left.
+                if let Some(cell) =
+                    col_index.checked_sub(1).and_then(|it| line.get_mut(it))
+                {
+                    cell.text.push_str(&segment.string);
+                }
+                continue;
+            }
+            if col_index + width > col_count {
+                break;
+            }
+            line[col_index] = VirtualTerminalCell {
+                text: segment.string.clone(),
+                style,
+            };
+            for cell in line.iter_mut().take(col_index + width).skip(col_index + 1) {
+                *cell = VirtualTerminalCell {
+                    text: String::new(),
+                    style,
+                };
+            }
+            self.cursor_position.col_index += ch!(width);
+        }
+    }
+
+    fn move_cursor_to(&mut self, row_index: usize, col_index: usize) {
+        let max_row_index = ch!(@to_usize self.size.row_count).saturating_sub(1);
+        let max_col_index = ch!(@to_usize self.size.col_count).saturating_sub(1);
+        self.cursor_position = position!(
+            col_index: std::cmp::min(col_index, max_col_index),
+            row_index: std::cmp::min(row_index, max_row_index)
+        );
+    }
+
+    fn clear_cells(&mut self, row_index: usize, col_range: std::ops::Range<usize>) {
+        if let Some(line) = self.cells.get_mut(row_index) {
+            let end = std::cmp::min(col_range.end, line.len());
+            for cell in line.iter_mut().take(end).skip(col_range.start) {
+                *cell = VirtualTerminalCell::default();
+            }
+        }
+    }
+
+    fn apply_escape_sequence(&mut self, sequence: &[u8]) {
+        // OSC 8 hyperlinks (`ESC ] 8 ; <params> ; <url> ST`). An empty url closes the link.
+        if let Some(osc8) = sequence.strip_prefix(b"\x1b]8;") {
+            let osc8 = String::from_utf8_lossy(osc8);
+            let osc8 = osc8
+                .strip_suffix("\x1b\\")
+                .or_else(|| osc8.strip_suffix('\x07'))
+                .unwrap_or(&osc8);
+            self.maybe_hyperlink = match osc8.split_once(';') {
+                Some((_, url)) if !url.is_empty() => Some(TuiHyperlink::new(url)),
+                _ => None,
+            };
+            return;
+        }
+
+        // Other than that, only CSI sequences (`ESC [ <params> <final byte>`) are applied.
+        if sequence.get(1) != Some(&b'[') {
+            return;
+        }
+        let Some((&final_byte, params)) = sequence[2..].split_last() else {
+            return;
+        };
+        let params = String::from_utf8_lossy(params);
+
+        // Private modes, eg: `ESC [ ? 25 h`.
+        if let Some(private_mode) = params.strip_prefix('?') {
+            let is_set = match final_byte {
+                b'h' => true,
+                b'l' => false,
+                _ => return,
+            };
+            for mode in private_mode.split(';') {
+                match mode {
+                    "25" => self.is_cursor_visible = is_set,
+                    "47" | "1047" | "1049" => self.set_alternate_screen(is_set),
+                    _ => {}
+                }
+            }
+            return;
+        }
+
+        let numbers: Vec<usize> = params
+            .split(';')
+            .map(|it| it.parse::<usize>().unwrap_or_default())
+            .collect();
+        // Missing (or 0) params default to 1 for cursor movement.
+        let get_count = |index: usize| match numbers.get(index) {
+            Some(0) | None => 1,
+            Some(it) => *it,
+        };
+        let row_index = ch!(@to_usize self.cursor_position.row_index);
+        let col_index = ch!(@to_usize self.cursor_position.col_index);
+        let col_count = ch!(@to_usize self.size.col_count);
+
+        match final_byte {
+            b'H' | b'f' => self.move_cursor_to(get_count(0) - 1, get_count(1) - 1),
+            b'A' => {
+                self.move_cursor_to(row_index.saturating_sub(get_count(0)), col_index)
+            }
+            b'B' => self.move_cursor_to(row_index + get_count(0), col_index),
+            b'C' => self.move_cursor_to(row_index, col_index + get_count(0)),
+            b'D' => {
+                self.move_cursor_to(row_index, col_index.saturating_sub(get_count(0)))
+            }
+            b'G' => self.move_cursor_to(row_index, get_count(0) - 1),
+            b'd' => self.move_cursor_to(get_count(0) - 1, col_index),
+            b'J' => match numbers.first() {
+                Some(0) | None => {
+                    self.clear_cells(row_index, col_index..col_count);
+                    for it in row_index + 1..self.cells.len() {
+                        self.clear_cells(it, 0..col_count);
+                    }
+                }
+                Some(1) => {
+                    for it in 0..row_index {
+                        self.clear_cells(it, 0..col_count);
+                    }
+                    self.clear_cells(row_index, 0..col_index + 1);
+                }
+                _ => self.cells = make_cells(self.size),
+            },
+            b'K' => match numbers.first() {
+                Some(0) | None => self.clear_cells(row_index, col_index..col_count),
+                Some(1) => self.clear_cells(row_index, 0..col_index + 1),
+                _ => self.clear_cells(row_index, 0..col_count),
+            },
+            b'm' => self.apply_sgr(&params),
+            _ => {}
+        }
+    }
+
+    fn set_alternate_screen(&mut self, is_alternate_screen: bool) {
+        if is_alternate_screen == self.is_alternate_screen() {
+            return;
+        }
+        if is_alternate_screen {
+            let cells = std::mem::replace(&mut self.cells, make_cells(self.size));
+            self.maybe_saved_screen = Some((cells, self.cursor_position));
+        } else if let Some((cells, cursor_position)) = self.maybe_saved_screen.take() {
+            self.cells = cells;
+            self.cursor_position = cursor_position;
+        }
+    }
+
+    /// Apply Select Graphic Rendition params, eg: `1;38;5;9` (bold, red fg).
+    fn apply_sgr(&mut self, params: &str) {
+        let mut params = params
+            .split(';')
+            .map(|it| it.parse::<u8>().unwrap_or_default());
+
+        while let Some(param) = params.next() {
+            match param {
+                0 => self.style = TuiStyle::default(),
+                1 => self.style.bold = true,
+                2 => self.style.dim = true,
+                3 => self.style.italic = true,
+                4 => self.style.underline = true,
+                7 => self.style.reverse = true,
+                8 => self.style.hidden = true,
+                9 => self.style.strikethrough = true,
+                22 => {
+                    self.style.bold = false;
+                    self.style.dim = false;
+                }
+                23 => self.style.italic = false,
+                24 => self.style.underline = false,
+                27 => self.style.reverse = false,
+                28 => self.style.hidden = false,
+                29 => self.style.strikethrough = false,
+                30..=37 => self.style.color_fg = get_ansi_color(param - 30),
+                90..=97 => self.style.color_fg = get_ansi_color(param - 90 + 8),
+                40..=47 => self.style.color_bg = get_ansi_color(param - 40),
+                100..=107 => self.style.color_bg = get_ansi_color(param - 100 + 8),
+                39 => self.style.color_fg = None,
+                49 => self.style.color_bg = None,
+                38 | 48 => {
+                    let maybe_color = match params.next() {
+                        Some(5) => params.next().and_then(get_ansi_color),
+                        Some(2) => {
+                            let rgb: Vec<u8> = params.by_ref().take(3).collect();
+                            match rgb[..] {
+                                [red, green, blue] => {
+                                    Some(TuiColor::Rgb(RgbValue { red, green, blue }))
+                                }
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    };
+                    if param == 38 {
+                        self.style.color_fg = maybe_color;
+                    } else {
+                        self.style.color_bg = maybe_color;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Write for VirtualTerminal {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut bytes = std::mem::take(&mut self.pending_bytes);
+        bytes.extend_from_slice(buf);
+        self.apply(&bytes);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> { Ok(()) }
+}
+
+fn make_cells(size: Size) -> Vec<Vec<VirtualTerminalCell>> {
+    vec![
+        vec![VirtualTerminalCell::default(); ch!(@to_usize size.col_count)];
+        ch!(@to_usize size.row_count)
+    ]
+}
+
+fn is_control(byte: u8) -> bool { byte < 0x20 || byte == 0x7f }
+
+/// Returns the length of the escape sequence at the start of `bytes`, or [None] if it is
+/// incomplete.
+fn get_escape_sequence_len(bytes: &[u8]) -> Option<usize> {
+    match bytes.get(1)? {
+        // CSI: `ESC [`, params and intermediate bytes, and a final byte.
+        b'[' => bytes
+            .iter()
+            .skip(2)
+            .position(|byte| (0x40..=0x7e).contains(byte))
+            .map(|it| it + 3),
+        // OSC: `ESC ]`, terminated by BEL or `ESC \`.
+        b']' => {
+            bytes.windows(2).enumerate().skip(1).find_map(
+                |(index, window)| match window {
+                    [_, BEL] => Some(index + 2),
+                    [ESC, b'\\'] => Some(index + 2),
+                    _ => None,
+                },
+            )
+        }
+        _ => Some(2),
+    }
+}
+
+/// Returns the [TuiColor] for the given index in the ANSI 256 color palette.
+fn get_ansi_color(index: u8) -> Option<TuiColor> {
+    crossterm::style::Color::parse_ansi(&format!("5;{index}"))
+        .map(convert_from_crossterm_color_to_tui_color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_virtual_terminal(bytes: &str) -> VirtualTerminal {
+        let mut virtual_terminal =
+            VirtualTerminal::new(size!(col_count: 10, row_count: 3));
+        virtual_terminal.write_all(bytes.as_bytes()).unwrap();
+        virtual_terminal
+    }
+
+    #[test]
+    fn test_print_and_move_cursor() {
+        let virtual_terminal = make_virtual_terminal("ab\x1b[2;3Hcd😃e\x1b[3;9Hxyz");
+        assert_eq!(
+            virtual_terminal.get_lines(),
+            vec!["ab", "  cd😃e", "        xy"]
+        );
+        assert_eq!(
+            virtual_terminal
+                .get_cell(position!(col_index: 5, row_index: 1))
+                .unwrap()
+                .text,
+            ""
+        );
+        assert_eq!(
+            virtual_terminal.find("e"),
+            Some(position!(col_index: 6, row_index: 1))
+        );
+        assert_eq!(
+            virtual_terminal.get_cursor_position(),
+            position!(col_index: 10, row_index: 2)
+        );
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut virtual_terminal = make_virtual_terminal("abcdef\r\nghi");
+        virtual_terminal.write_all(b"\x1b[1;3H\x1b[K").unwrap();
+        assert_eq!(virtual_terminal.get_lines(), vec!["ab", "ghi", ""]);
+        virtual_terminal.write_all(b"\x1b[2J").unwrap();
+        assert_eq!(virtual_terminal.get_lines(), vec!["", "", ""]);
+    }
+
+    #[test]
+    fn test_styles() {
+        let virtual_terminal = make_virtual_terminal(
+            "\x1b[38;5;9m\x1b[48;2;1;2;3m\x1b[1m\x1b[4mab\x1b[0mc\x1b[38;5;100md",
+        );
+        assert_eq!(
+            virtual_terminal.get_style_at(position!(col_index: 1, row_index: 0)),
+            Some(TuiStyle {
+                bold: true,
+                underline: true,
+                color_fg: Some(color!(@red)),
+                color_bg: Some(color!(1, 2, 3)),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            virtual_terminal.get_style_at(position!(col_index: 2, row_index: 0)),
+            Some(TuiStyle::default())
+        );
+        assert_eq!(
+            virtual_terminal
+                .get_style_at(position!(col_index: 3, row_index: 0))
+                .unwrap()
+                .color_fg,
+            Some(TuiColor::Ansi(AnsiValue::new(100)))
+        );
+    }
+
+    #[test]
+    fn test_hyperlinks() {
+        let virtual_terminal = make_virtual_terminal(
+            "a\x1b]8;id=1;https://r3bl.com\x1b\\b\x1b[0mc\x1b]8;;\x1b\\d\x1b]8;;http://x\x07e",
+        );
+        assert_eq!(virtual_terminal.get_lines(), vec!["abcde", "", ""]);
+        let get_hyperlink = |col_index: usize| {
+            virtual_terminal
+                .get_style_at(position!(col_index: col_index, row_index: 0))
+                .unwrap()
+                .hyperlink
+        };
+        let r3bl = Some(TuiHyperlink::new("https://r3bl.com"));
+        assert_eq!(get_hyperlink(0), None);
+        assert_eq!(get_hyperlink(1), r3bl);
+        assert_eq!(get_hyperlink(2), r3bl);
+        assert_eq!(get_hyperlink(3), None);
+        assert_eq!(get_hyperlink(4), Some(TuiHyperlink::new("http://x")));
+    }
+
+    #[test]
+    fn test_split_writes_and_private_modes() {
+        let mut virtual_terminal = make_virtual_terminal("main");
+        virtual_terminal
+            .write_all(b"\x1b[?1049h\x1b[H\x1b[?25")
+            .unwrap();
+        virtual_terminal.write_all(b"l\x1b[38;5").unwrap();
+        virtual_terminal.write_all(b";10m\xf0\x9f").unwrap();
+        virtual_terminal.write_all(b"\x98\x83").unwrap();
+        assert!(virtual_terminal.is_alternate_screen());
+        assert!(!virtual_terminal.is_cursor_visible());
+        assert_eq!(virtual_terminal.get_line(0).unwrap(), "😃");
+        assert_eq!(
+            virtual_terminal
+                .get_style_at(position!(col_index: 0, row_index: 0))
+                .unwrap()
+                .color_fg,
+            Some(color!(@green))
+        );
+
+        virtual_terminal.write_all(b"\x1b[?1049l").unwrap();
+        assert!(!virtual_terminal.is_alternate_screen());
+        assert_eq!(virtual_terminal.get_line(0).unwrap(), "main");
+        assert_eq!(
+            virtual_terminal.get_cursor_position(),
+            position!(col_index: 4, row_index: 0)
+        );
+    }
+}
@@ -17,6 +17,7 @@
 
 use std::{fmt::Debug, marker::PhantomData};
 
+use futures_util::{Stream, StreamExt};
 use get_size::GetSize;
 use r3bl_rs_utils_core::*;
 use r3bl_rs_utils_macro::*;
@@ -26,6 +27,15 @@ use crate::*;
 
 pub struct TerminalWindow;
 
+/// This is returned by [TerminalWindow::main_event_loop_headless].
+#[derive(Debug)]
+pub struct HeadlessMainEventLoopResult<S> {
+    /// The state of the app when the main event loop exited.
+    pub state: S,
+    /// The [OffscreenBuffer] that was painted for each render of the app, in order.
+    pub frames: Vec<OffscreenBuffer>,
+}
+
 pub const CHANNEL_WIDTH: usize = 1_000;
 
 #[derive(Debug)]
@@ -69,7 +79,7 @@ impl TerminalWindow {
             )?;
 
             // Start raw mode.
-            RawMode::start(global_data.window_size);
+            RawMode::start(global_data.window_size, &mut global_data.output_device);
 
             // Create a new event stream (async).
             let async_event_stream = &mut AsyncEventStream::default();
@@ -96,32 +106,18 @@ impl TerminalWindow {
                     // This branch is cancel safe since recv is cancel safe.
                     maybe_signal = main_thread_channel_receiver.recv() => {
                         if let Some(ref signal) = maybe_signal {
-                            match signal {
-                                TerminalWindowMainThreadSignal::Exit => {
-                                    // 🐒 Actually exit the main loop!
-                                    RawMode::end(global_data.window_size);
-                                    break;
-                                },
-                                TerminalWindowMainThreadSignal::Render(_) => {
-                                    AppManager::render_app(
-                                        app,
-                                        global_data,
-                                        component_registry_map,
-                                        has_focus,
-                                    )?;
-                                },
-                                TerminalWindowMainThreadSignal::ApplyAction(action) => {
-                                    let result = app.app_handle_signal(action, global_data, component_registry_map, has_focus);
-                                    handle_result_generated_by_app_after_handling_action_or_input_event(
-                                        result,
-                                        None,
-                                        &exit_keys,
-                                        app,
-                                        global_data,
-                                        component_registry_map,
-                                        has_focus,
-                                    );
-                                },
+                            let continuation = Self::handle_signal(
+                                signal,
+                                &exit_keys,
+                                app,
+                                global_data,
+                                component_registry_map,
+                                has_focus,
+                            )?;
+                            if let Continuation::Exit = continuation {
+                                // 🐒 Actually exit the main loop!
+                                RawMode::end(global_data.window_size, &mut global_data.output_device);
+                                break;
                             }
                         }
                     }
@@ -168,6 +164,143 @@ impl TerminalWindow {
         });
     }
 
+    /// This is just like [main_event_loop](TerminalWindow::main_event_loop), except that
+    /// it doesn't need a terminal. This makes it possible to test [App]s end-to-end (eg:
+    /// in CI), by typing keys, resizing the window, and pressing the exit keys.
+    /// - Input events come from `input_stream` (eg: [futures_util::stream::iter]) instead
+    ///   of the terminal.
+    /// - The window starts out w/ `window_size` (instead of the size of the terminal), and
+    ///   [InputEvent::Resize] events change it.
+    /// - Raw mode isn't entered, and nothing is painted to stdout.
+    ///
+    /// To make it deterministic, each input event is only handled after all the signals
+    /// that were sent to the main thread while handling the previous one (and the tasks
+    /// that send them, when the tokio runtime is single threaded, as it is in
+    /// `#[tokio::test]`). The loop exits when the exit signal is sent (eg: when one of the
+    /// `exit_keys` is pressed) or when `input_stream` ends. It returns the final state,
+    /// and the [OffscreenBuffer] that was painted for each render.
+    pub async fn main_event_loop_headless<S, AS>(
+        mut app: BoxedSafeApp<S, AS>,
+        exit_keys: Vec<InputEvent>,
+        state: S,
+        window_size: Size,
+        mut input_stream: impl Stream<Item = InputEvent> + Unpin,
+    ) -> CommonResult<HeadlessMainEventLoopResult<S>>
+    where
+        S: Debug + Default + Clone + Sync + Send,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        let (main_thread_channel_sender, mut main_thread_channel_receiver) =
+            mpsc::channel::<TerminalWindowMainThreadSignal<AS>>(CHANNEL_WIDTH);
+
+        let mut global_data = GlobalData::new(
+            main_thread_channel_sender,
+            state,
+            window_size,
+            OutputDevice::new_writer(std::io::sink()),
+        );
+        global_data.maybe_recorded_frames = Some(vec![]);
+
+        let app = &mut app;
+        let component_registry_map = &mut ComponentRegistryMap::default();
+        let has_focus = &mut HasFocus::default();
+
+        // Init the app, and perform first render.
+        app.app_init(component_registry_map, has_focus);
+        AppManager::render_app(app, &mut global_data, component_registry_map, has_focus)?;
+
+        'main_loop: loop {
+            // Handle all the pending signals.
+            loop {
+                tokio::task::yield_now().await;
+                let Ok(signal) = main_thread_channel_receiver.try_recv() else {
+                    break;
+                };
+                let continuation = Self::handle_signal(
+                    &signal,
+                    &exit_keys,
+                    app,
+                    &mut global_data,
+                    component_registry_map,
+                    has_focus,
+                )?;
+                if let Continuation::Exit = continuation {
+                    break 'main_loop;
+                }
+            }
+
+            let Some(input_event) = input_stream.next().await else {
+                break;
+            };
+
+            Self::handle_resize_if_applicable(
+                input_event,
+                &mut global_data,
+                app,
+                component_registry_map,
+                has_focus,
+            );
+
+            Self::actually_process_input_event(
+                &mut global_data,
+                app,
+                input_event,
+                &exit_keys,
+                component_registry_map,
+                has_focus,
+            );
+        }
+
+        Ok(HeadlessMainEventLoopResult {
+            frames: global_data.maybe_recorded_frames.take().unwrap_or_default(),
+            state: global_data.state,
+        })
+    }
+
+    /// Returns [Continuation::Exit] if the main event loop should exit.
+    fn handle_signal<S, AS>(
+        signal: &TerminalWindowMainThreadSignal<AS>,
+        exit_keys: &[InputEvent],
+        app: &mut BoxedSafeApp<S, AS>,
+        global_data: &mut GlobalData<S, AS>,
+        component_registry_map: &mut ComponentRegistryMap<S, AS>,
+        has_focus: &mut HasFocus,
+    ) -> CommonResult<Continuation<String>>
+    where
+        S: Debug + Default + Clone + Sync + Send,
+        AS: Debug + Default + Clone + Sync + Send + 'static,
+    {
+        match signal {
+            TerminalWindowMainThreadSignal::Exit => return Ok(Continuation::Exit),
+            TerminalWindowMainThreadSignal::Render(_) => {
+                AppManager::render_app(
+                    app,
+                    global_data,
+                    component_registry_map,
+                    has_focus,
+                )?;
+            }
+            TerminalWindowMainThreadSignal::ApplyAction(action) => {
+                let result = app.app_handle_signal(
+                    action,
+                    global_data,
+                    component_registry_map,
+                    has_focus,
+                );
+                handle_result_generated_by_app_after_handling_action_or_input_event(
+                    result,
+                    None,
+                    exit_keys,
+                    app,
+                    global_data,
+                    component_registry_map,
+                    has_focus,
+                );
+            }
+        }
+        Ok(Continuation::Continue)
+    }
+
     fn actually_process_input_event<S, AS>(
         global_data: &mut GlobalData<S, AS>,
         app: &mut BoxedSafeApp<S, AS>,
@@ -320,7 +453,7 @@ where
 
             match render_result {
                 Err(error) => {
-                    RenderOp::default().flush();
+                    RenderOp::default().flush(&mut global_data.output_device);
 
                     telemetry_global_static::set_end_ts();
 
@@ -36,3 +36,6 @@ pub use manage_focus::*;
 pub use shared_global_data::*;
 pub use static_global_data::*;
 pub use type_aliases::*;
+
+// Tests.
+mod test_main_event_loop;
@@ -29,6 +29,10 @@ use crate::*;
 /// - The `maybe_saved_offscreen_buffer` holds the last rendered [OffscreenBuffer].
 /// - The `main_thread_channel_sender` is used to send [TerminalWindowMainThreadSignal]s
 /// - The `state` holds the application's state.
+/// - The `output_device` is where the [App] is painted. It is [OutputDevice::Stdout] by
+///   default, and can be replaced (eg: w/ a [VirtualTerminal]) to render w/out a TTY.
+/// - The `maybe_recorded_frames` holds a copy of each [OffscreenBuffer] that is painted,
+///   if it is [Some] (eg: in [TerminalWindow::main_event_loop_headless]).
 pub struct GlobalData<S, AS>
 where
     S: Debug + Default + Clone + Sync + Send,
@@ -38,6 +42,8 @@ where
     pub maybe_saved_offscreen_buffer: Option<OffscreenBuffer>,
     pub main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
     pub state: S,
+    pub output_device: OutputDevice,
+    pub maybe_recorded_frames: Option<Vec<OffscreenBuffer>>,
 }
 
 mod global_data_impl {
@@ -52,6 +58,7 @@ mod global_data_impl {
             let vec_lines = {
                 let mut it = vec![];
                 it.push(format!("window_size: {0:?}", self.window_size));
+                it.push(format!("output_device: {0:?}", self.output_device));
                 it.push(match &self.maybe_saved_offscreen_buffer {
                     None => "no saved offscreen buffer".to_string(),
                     Some(ref offscreen_buffer) => match DEBUG_TUI_COMPOSITOR {
@@ -79,16 +86,34 @@ mod global_data_impl {
         where
             AS: Debug + Default + Clone + Sync + Send,
         {
+            Ok(Self::new(
+                main_thread_channel_sender,
+                state,
+                terminal_lib_operations::lookup_size()?,
+                OutputDevice::Stdout,
+            ))
+        }
+
+        /// Unlike [try_to_create_instance](GlobalData::try_to_create_instance), this
+        /// doesn't need a terminal.
+        pub fn new(
+            main_thread_channel_sender: Sender<TerminalWindowMainThreadSignal<AS>>,
+            state: S,
+            window_size: Size,
+            output_device: OutputDevice,
+        ) -> GlobalData<S, AS> {
             let mut it = GlobalData {
                 window_size: Default::default(),
                 maybe_saved_offscreen_buffer: Default::default(),
                 state,
                 main_thread_channel_sender,
+                output_device,
+                maybe_recorded_frames: None,
             };
 
-            it.set_size(terminal_lib_operations::lookup_size()?);
+            it.set_size(window_size);
 
-            Ok(it)
+            it
         }
 
         pub fn set_size(&mut self, new_size: Size) {
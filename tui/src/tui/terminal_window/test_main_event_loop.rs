@@ -0,0 +1,198 @@
+/*
+ *   Copyright (c) 2024 R3BL LLC
+ *   All rights reserved.
+ *
+ *   Licensed under the Apache License, Version 2.0 (the "License");
+ *   you may not use this file except in compliance with the License.
+ *   You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ *   Unless required by applicable law or agreed to in writing, software
+ *   distributed under the License is distributed on an "AS IS" BASIS,
+ *   WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *   See the License for the specific language governing permissions and
+ *   limitations under the License.
+ */
+
+#[cfg(test)]
+mod tests {
+    use futures_util::stream;
+    use r3bl_rs_utils_core::*;
+
+    use crate::*;
+
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct State {
+        typed_text: String,
+        count: usize,
+    }
+
+    #[derive(Debug, Default, Clone)]
+    struct Increment;
+
+    /// Typing a char appends it to the text, and `+` increments the count (by sending a
+    /// signal to the main thread).
+    struct TestApp;
+
+    impl App for TestApp {
+        type S = State;
+        type AS = Increment;
+
+        fn app_init(
+            &mut self,
+            _: &mut ComponentRegistryMap<State, Increment>,
+            _: &mut HasFocus,
+        ) {
+        }
+
+        fn app_handle_input_event(
+            &mut self,
+            input_event: InputEvent,
+            global_data: &mut GlobalData<State, Increment>,
+            _: &mut ComponentRegistryMap<State, Increment>,
+            _: &mut HasFocus,
+        ) -> CommonResult<EventPropagation> {
+            match input_event {
+                InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::Character('+'),
+                }) => {
+                    send_signal!(
+                        global_data.main_thread_channel_sender,
+                        TerminalWindowMainThreadSignal::ApplyAction(Increment)
+                    );
+                    Ok(EventPropagation::Consumed)
+                }
+                InputEvent::Keyboard(KeyPress::Plain {
+                    key: Key::Character(character),
+                }) => {
+                    global_data.state.typed_text.push(character);
+                    Ok(EventPropagation::ConsumedRender)
+                }
+                _ => Ok(EventPropagation::Propagate),
+            }
+        }
+
+        fn app_handle_signal(
+            &mut self,
+            _: &Increment,
+            global_data: &mut GlobalData<State, Increment>,
+            _: &mut ComponentRegistryMap<State, Increment>,
+            _: &mut HasFocus,
+        ) -> CommonResult<EventPropagation> {
+            global_data.state.count += 1;
+            Ok(EventPropagation::ConsumedRender)
+        }
+
+        fn app_render(
+            &mut self,
+            global_data: &mut GlobalData<State, Increment>,
+            _: &mut ComponentRegistryMap<State, Increment>,
+            _: &mut HasFocus,
+        ) -> CommonResult<RenderPipeline> {
+            let State { typed_text, count } = &global_data.state;
+            Ok(render_pipeline!(
+                @new ZOrder::Normal
+                =>
+                    RenderOp::ResetColor,
+                    RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 0)),
+                    RenderOp::PaintTextWithAttributes(typed_text.clone(), None),
+                    RenderOp::MoveCursorPositionAbs(position!(col_index: 0, row_index: 1)),
+                    RenderOp::PaintTextWithAttributes(format!("count: {count}"), None)
+            ))
+        }
+    }
+
+    fn get_exit_keys() -> Vec<InputEvent> {
+        vec![InputEvent::Keyboard(
+            keypress! { @char ModifierKeysMask::new().with_ctrl(), 'q' },
+        )]
+    }
+
+    fn get_window_size() -> Size { size!(col_count: 70, row_count: 12) }
+
+    #[tokio::test]
+    async fn test_headless_until_exit_keys() {
+        let input_events = vec![
+            InputEvent::Keyboard(keypress!(@char 'h')),
+            InputEvent::Keyboard(keypress!(@char '+')),
+            InputEvent::Keyboard(keypress!(@char 'i')),
+            InputEvent::Keyboard(keypress!(@char '+')),
+            InputEvent::Keyboard(
+                keypress! { @char ModifierKeysMask::new().with_ctrl(), 'q' },
+            ),
+            // This is never handled, since the app exits before it.
+            InputEvent::Keyboard(keypress!(@char 'x')),
+        ];
+
+        let result = TerminalWindow::main_event_loop_headless(
+            Box::new(TestApp),
+            get_exit_keys(),
+            State::default(),
+            get_window_size(),
+            stream::iter(input_events),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            result.state,
+            State {
+                typed_text: "hi".to_string(),
+                count: 2,
+            }
+        );
+
+        // Initial render, and one for each char and signal.
+        let frames: Vec<Vec<String>> = result
+            .frames
+            .iter()
+            .map(|it| it.get_text_lines()[..2].to_vec())
+            .collect();
+        assert_eq!(
+            frames,
+            vec![
+                vec!["", "count: 0"],
+                vec!["h", "count: 0"],
+                vec!["h", "count: 1"],
+                vec!["hi", "count: 1"],
+                vec!["hi", "count: 2"],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_headless_resize_and_end_of_input() {
+        let input_events = vec![
+            InputEvent::Keyboard(keypress!(@char 'a')),
+            InputEvent::Resize(size!(col_count: 80, row_count: 20)),
+            // Too small to render the app.
+            InputEvent::Resize(size!(col_count: 10, row_count: 5)),
+        ];
+
+        let result = TerminalWindow::main_event_loop_headless(
+            Box::new(TestApp),
+            get_exit_keys(),
+            State::default(),
+            get_window_size(),
+            stream::iter(input_events),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.state.typed_text, "a");
+        let window_sizes: Vec<Size> =
+            result.frames.iter().map(|it| it.window_size).collect();
+        assert_eq!(
+            window_sizes,
+            vec![
+                get_window_size(),
+                get_window_size(),
+                size!(col_count: 80, row_count: 20),
+                size!(col_count: 10, row_count: 5),
+            ]
+        );
+        assert_eq!(result.frames[2].get_text_lines()[0], "a");
+        assert!(result.frames[3].get_text_lines()[2].starts_with("Window"));
+    }
+}